@@ -6,7 +6,8 @@ use crate::agent_applicable_state::AgentApplicableState;
 
 pub struct AgentApplicableStateHolder {
     agent_applicable_state: RwLock<Option<AgentApplicableState>>,
-    change_notifier: watch::Sender<Option<AgentApplicableState>>,
+    /// Publishes the version of the desired state the applicable state was converted from.
+    change_notifier: watch::Sender<u64>,
 }
 
 impl AgentApplicableStateHolder {
@@ -17,24 +18,21 @@ impl AgentApplicableStateHolder {
     pub fn set_agent_applicable_state(
         &self,
         agent_applicable_state: Option<AgentApplicableState>,
+        desired_state_version: u64,
     ) -> Result<()> {
-        {
-            let mut state = self.agent_applicable_state.write();
+        *self.agent_applicable_state.write() = agent_applicable_state;
 
-            (*state).clone_from(&agent_applicable_state);
-        }
-
-        Ok(self.change_notifier.send(agent_applicable_state)?)
+        Ok(self.change_notifier.send(desired_state_version)?)
     }
 
-    pub fn subscribe(&self) -> watch::Receiver<Option<AgentApplicableState>> {
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
         self.change_notifier.subscribe()
     }
 }
 
 impl Default for AgentApplicableStateHolder {
     fn default() -> Self {
-        let (change_notifier, _) = watch::channel(None);
+        let (change_notifier, _) = watch::channel(0);
 
         Self {
             agent_applicable_state: RwLock::new(None),
@@ -0,0 +1,7 @@
+use llama_cpp_bindings::token::LlamaToken;
+
+/// Tokens whose KV cells a finished request left behind on an idle sequence.
+pub struct CachedSequencePrefix {
+    pub last_used: u64,
+    pub tokens: Vec<LlamaToken>,
+}
@@ -0,0 +1,209 @@
+use anyhow::Context as _;
+use anyhow::Result;
+use llama_cpp_bindings::SampledToken;
+use llama_cpp_bindings::model::LlamaModel;
+use llama_cpp_bindings::token::LlamaToken;
+use llama_cpp_bindings::token::data::LlamaTokenData;
+use paddler_messaging::token_logprob::TokenLogprob;
+use paddler_messaging::top_logprob::TopLogprob;
+
+/// Log-probabilities of a sampled token and of its most likely alternatives, read from the
+/// candidates left after the grammar and the sampler chain (temperature, top-k, top-p, ...)
+/// reshaped the distribution, so they describe what the sampler actually drew from.
+#[derive(Debug)]
+pub struct CandidateLogprobs {
+    pub logprob: f32,
+    pub token: LlamaToken,
+    pub top_logprobs: Vec<(LlamaToken, f32)>,
+}
+
+impl CandidateLogprobs {
+    pub fn from_candidates(
+        candidates: &[LlamaTokenData],
+        sampled_token: LlamaToken,
+        top_logprobs: u32,
+    ) -> Result<Self> {
+        let normalizer = log_normalizer(candidates);
+        let sampled_logit = candidates
+            .iter()
+            .find(|candidate| candidate.id() == sampled_token)
+            .map(LlamaTokenData::logit)
+            .context("sampled token is not among the sampler candidates")?;
+
+        Ok(Self {
+            logprob: sampled_logit - normalizer,
+            token: sampled_token,
+            top_logprobs: most_likely_candidates(candidates, top_logprobs as usize)
+                .into_iter()
+                .map(|candidate| (candidate.id(), candidate.logit() - normalizer))
+                .collect(),
+        })
+    }
+
+    pub fn into_token_logprob(self, model: &LlamaModel) -> Result<TokenLogprob> {
+        Ok(TokenLogprob {
+            logprob: self.logprob,
+            token: token_text(model, self.token)?,
+            top_logprobs: self
+                .top_logprobs
+                .into_iter()
+                .map(|(token, logprob)| {
+                    Ok(TopLogprob {
+                        logprob,
+                        token: token_text(model, token)?,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+        })
+    }
+}
+
+fn token_text(model: &LlamaModel, token: LlamaToken) -> Result<String> {
+    model
+        .token_to_piece(
+            &SampledToken::Content(token),
+            &mut encoding_rs::UTF_8.new_decoder(),
+            false,
+            None,
+        )
+        .context("failed to decode token for log-probabilities")
+}
+
+fn log_normalizer(candidates: &[LlamaTokenData]) -> f32 {
+    let max_logit = candidates
+        .iter()
+        .map(LlamaTokenData::logit)
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    if !max_logit.is_finite() {
+        return max_logit;
+    }
+
+    max_logit
+        + candidates
+            .iter()
+            .map(|candidate| (candidate.logit() - max_logit).exp())
+            .sum::<f32>()
+            .ln()
+}
+
+/// Candidates the grammar or a sampler eliminated carry a logit of negative infinity and are
+/// never reported as alternatives.
+fn most_likely_candidates(candidates: &[LlamaTokenData], count: usize) -> Vec<LlamaTokenData> {
+    let mut remaining: Vec<LlamaTokenData> = candidates
+        .iter()
+        .copied()
+        .filter(|candidate| candidate.logit().is_finite())
+        .collect();
+    let by_descending_logit =
+        |left: &LlamaTokenData, right: &LlamaTokenData| right.logit().total_cmp(&left.logit());
+
+    if count < remaining.len() {
+        remaining.select_nth_unstable_by(count, by_descending_logit);
+        remaining.truncate(count);
+    }
+
+    remaining.sort_by(by_descending_logit);
+    remaining
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use llama_cpp_bindings::token::LlamaToken;
+    use llama_cpp_bindings::token::data::LlamaTokenData;
+
+    use super::CandidateLogprobs;
+    use super::log_normalizer;
+    use super::most_likely_candidates;
+
+    fn candidates(logits: &[f32]) -> Vec<LlamaTokenData> {
+        logits
+            .iter()
+            .zip(0..)
+            .map(|(logit, token_id)| LlamaTokenData::new(LlamaToken::new(token_id), *logit, 0.0))
+            .collect()
+    }
+
+    fn token_ids(candidates: &[LlamaTokenData]) -> Vec<i32> {
+        candidates
+            .iter()
+            .map(|candidate| candidate.id().0)
+            .collect()
+    }
+
+    #[test]
+    fn normalized_probabilities_sum_to_one() {
+        let candidates = candidates(&[2.0, 1.0, 0.5, -3.0]);
+        let normalizer = log_normalizer(&candidates);
+        let total: f32 = candidates
+            .iter()
+            .map(|candidate| (candidate.logit() - normalizer).exp())
+            .sum();
+
+        assert!((total - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn large_logits_do_not_overflow() {
+        let normalizer = log_normalizer(&candidates(&[1000.0, 1000.0]));
+
+        assert!((normalizer - (1000.0 + 2.0_f32.ln())).abs() < 1e-3);
+    }
+
+    #[test]
+    fn most_likely_candidates_are_ordered_by_logit() {
+        assert_eq!(
+            token_ids(&most_likely_candidates(
+                &candidates(&[0.1, 3.0, -1.0, 2.0, 2.5]),
+                3
+            )),
+            vec![1, 4, 3]
+        );
+    }
+
+    #[test]
+    fn asking_for_no_alternatives_returns_none() {
+        assert!(most_likely_candidates(&candidates(&[0.1, 3.0]), 0).is_empty());
+    }
+
+    #[test]
+    fn asking_for_more_alternatives_than_the_candidates_returns_all() {
+        assert_eq!(
+            token_ids(&most_likely_candidates(&candidates(&[0.1, 3.0]), 5)),
+            vec![1, 0]
+        );
+    }
+
+    #[test]
+    fn eliminated_candidates_are_not_alternatives() {
+        assert_eq!(
+            token_ids(&most_likely_candidates(
+                &candidates(&[0.1, f32::NEG_INFINITY, 3.0]),
+                5
+            )),
+            vec![2, 0]
+        );
+    }
+
+    #[test]
+    fn eliminated_candidates_do_not_take_probability_mass() -> Result<()> {
+        let candidate_logprobs = CandidateLogprobs::from_candidates(
+            &candidates(&[1.0, f32::NEG_INFINITY]),
+            LlamaToken::new(0),
+            1,
+        )?;
+
+        assert!(candidate_logprobs.logprob.abs() < 1e-6);
+        assert_eq!(candidate_logprobs.top_logprobs.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_a_sampled_token_outside_of_the_candidates() {
+        assert!(
+            CandidateLogprobs::from_candidates(&candidates(&[1.0]), LlamaToken::new(7), 0).is_err()
+        );
+    }
+}
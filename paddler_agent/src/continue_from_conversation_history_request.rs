@@ -6,6 +6,7 @@ use paddler_messaging::request_params::continue_from_conversation_history_params
 use tokio::sync::mpsc;
 
 use crate::from_request_params::FromRequestParams;
+use crate::inference_request_trace::InferenceRequestTrace;
 use crate::slot_aggregated_status::SlotAggregatedStatus;
 use crate::slot_guard::SlotGuard;
 
@@ -14,6 +15,7 @@ pub struct ContinueFromConversationHistoryRequest {
     pub generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
     pub params: ContinueFromConversationHistoryParams<ValidatedParametersSchema>,
     pub slot_guard: SlotGuard,
+    pub trace: InferenceRequestTrace,
 }
 
 impl FromRequestParams for ContinueFromConversationHistoryRequest {
@@ -25,12 +27,14 @@ impl FromRequestParams for ContinueFromConversationHistoryRequest {
         generated_tokens_tx: mpsc::UnboundedSender<Self::Response>,
         generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
         slot_aggregated_status: Arc<SlotAggregatedStatus>,
+        trace: InferenceRequestTrace,
     ) -> Self {
         Self {
             generate_tokens_stop_rx,
             generated_tokens_tx,
             params,
             slot_guard: SlotGuard::new(slot_aggregated_status),
+            trace,
         }
     }
 }
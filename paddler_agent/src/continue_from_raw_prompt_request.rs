@@ -5,6 +5,7 @@ use paddler_messaging::request_params::continue_from_raw_prompt_params::Continue
 use tokio::sync::mpsc;
 
 use crate::from_request_params::FromRequestParams;
+use crate::inference_request_trace::InferenceRequestTrace;
 use crate::slot_aggregated_status::SlotAggregatedStatus;
 use crate::slot_guard::SlotGuard;
 
@@ -13,6 +14,7 @@ pub struct ContinueFromRawPromptRequest {
     pub generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
     pub params: ContinueFromRawPromptParams,
     pub slot_guard: SlotGuard,
+    pub trace: InferenceRequestTrace,
 }
 
 impl FromRequestParams for ContinueFromRawPromptRequest {
@@ -24,12 +26,14 @@ impl FromRequestParams for ContinueFromRawPromptRequest {
         generated_tokens_tx: mpsc::UnboundedSender<Self::Response>,
         generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
         slot_aggregated_status: Arc<SlotAggregatedStatus>,
+        trace: InferenceRequestTrace,
     ) -> Self {
         Self {
             generate_tokens_stop_rx,
             generated_tokens_tx,
             params,
             slot_guard: SlotGuard::new(slot_aggregated_status),
+            trace,
         }
     }
 }
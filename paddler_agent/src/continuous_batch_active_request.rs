@@ -1,14 +1,17 @@
 use llama_cpp_bindings::SampledTokenClassifier;
 use llama_cpp_bindings::sampling::LlamaSampler;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::logprobs_request::LogprobsRequest;
 use tokio::sync::mpsc;
 use tokio::sync::mpsc::error::TryRecvError;
 
 use crate::continuous_batch_request_state::ContinuousBatchRequestState;
 use crate::continuous_batch_terminal_delivery::ContinuousBatchTerminalDelivery;
 use crate::continuous_batch_terminal_outcome::ContinuousBatchTerminalOutcome;
+use crate::inference_request_trace::InferenceRequestTrace;
 use crate::sequence_id_guard::SequenceIdGuard;
 use crate::slot_guard::SlotGuard;
+use crate::stop_sequence_matcher::StopSequenceMatcher;
 use crate::tool_call_pipeline::ToolCallPipeline;
 
 pub struct ContinuousBatchActiveRequest {
@@ -16,11 +19,14 @@ pub struct ContinuousBatchActiveRequest {
     pub chain: LlamaSampler,
     pub token_classifier: SampledTokenClassifier<'static>,
     pub grammar_sampler: Option<LlamaSampler>,
+    pub logprobs: Option<LogprobsRequest>,
     pub generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
     pub generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
     pub sequence_id_guard: SequenceIdGuard,
     pub slot_guard: SlotGuard,
+    pub stop_sequence_matcher: StopSequenceMatcher,
     pub tool_call_pipeline: Option<ToolCallPipeline>,
+    pub trace: InferenceRequestTrace,
 }
 
 impl ContinuousBatchActiveRequest {
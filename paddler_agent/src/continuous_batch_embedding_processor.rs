@@ -6,8 +6,10 @@ use anyhow::anyhow;
 use llama_cpp_bindings::context::LlamaContext;
 use llama_cpp_bindings::llama_batch::LlamaBatch;
 use llama_cpp_bindings::model::AddBos;
+use llama_cpp_bindings::token::LlamaToken;
 use log::warn;
 use paddler_messaging::embedding::Embedding;
+use paddler_messaging::embedding_input_document::EmbeddingInputDocument;
 use paddler_messaging::embedding_normalization_method::EmbeddingNormalizationMethod;
 use paddler_messaging::embedding_result::EmbeddingResult;
 use paddler_messaging::oversized_embedding_document_details::OversizedEmbeddingDocumentDetails;
@@ -20,6 +22,27 @@ use crate::generate_embedding_batch_request::GenerateEmbeddingBatchRequest;
 use crate::normalization::normalize_embedding::normalize_embedding;
 use crate::plan_embedding_batches::plan_embedding_batches;
 
+fn invalid_tokens_message(input: &EmbeddingInputDocument, n_vocab: i32) -> Option<String> {
+    let tokens = input.tokens.as_ref()?;
+
+    if tokens.is_empty() {
+        return Some(format!(
+            "input {} must not be an empty token array",
+            input.id
+        ));
+    }
+
+    tokens
+        .iter()
+        .find(|token| **token < 0 || **token >= n_vocab)
+        .map(|token| {
+            format!(
+                "input {} has token {token} outside of the model vocabulary of {n_vocab} tokens",
+                input.id
+            )
+        })
+}
+
 pub struct ContinuousBatchEmbeddingProcessor<'context> {
     llama_context: &'context mut LlamaContext<'static>,
     scheduler_context: &'context Arc<ContinuousBatchSchedulerContext>,
@@ -44,14 +67,19 @@ impl<'context> ContinuousBatchEmbeddingProcessor<'context> {
             params:
                 GenerateEmbeddingBatchParams {
                     input_batch,
+                    model: _,
                     normalization_method,
                 },
             slot_guard,
+            mut trace,
         }: GenerateEmbeddingBatchRequest,
     ) -> Result<()> {
         // Held until this function returns so the slot is released via `Drop`.
         let _slot_guard = slot_guard;
 
+        // Embedding the batch is all prompt ingestion, so the request never starts decoding.
+        trace.start_prefill();
+
         if !self
             .scheduler_context
             .inference_parameters
@@ -62,9 +90,27 @@ impl<'context> ContinuousBatchEmbeddingProcessor<'context> {
             return Err(anyhow!("Embeddings are not enabled"));
         }
 
+        let n_vocab = self.scheduler_context.model.n_vocab();
+
+        if let Some(message) = input_batch
+            .iter()
+            .find_map(|input| invalid_tokens_message(input, n_vocab))
+        {
+            generated_embedding_tx.send(EmbeddingResult::InvalidTokens(message.clone()))?;
+
+            return Err(anyhow!(message));
+        }
+
         let tokens_lines_list = input_batch
             .into_iter()
             .map(|input| {
+                if let Some(tokens) = input.tokens {
+                    return Ok(EmbeddingInputTokenized {
+                        id: input.id,
+                        tokens: tokens.into_iter().map(LlamaToken::new).collect(),
+                    });
+                }
+
                 match self
                     .scheduler_context
                     .model
@@ -182,6 +228,7 @@ impl<'context> ContinuousBatchEmbeddingProcessor<'context> {
                         .pooling_type
                         .clone(),
                     source_document_id: embedding_input_tokenized.id.clone(),
+                    token_count: embedding_input_tokenized.tokens.len(),
                 },
                 normalization_method,
             )?))?;
@@ -1,3 +1,5 @@
+use std::mem::take;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use llama_cpp_bindings::SampledToken;
@@ -8,6 +10,8 @@ use crate::continuous_batch_terminal_outcome::ContinuousBatchTerminalOutcome;
 
 pub struct ContinuousBatchRequestState {
     pub current_token_position: i32,
+    /// Sampled tokens already decoded into the KV cache, in order.
+    pub generated_tokens: Vec<LlamaToken>,
     pub i_batch: Option<i32>,
     pub max_tokens: i32,
     pub pending_sampled_token: Option<SampledToken>,
@@ -22,8 +26,17 @@ impl ContinuousBatchRequestState {
         &self.prompt_tokens[self.prompt_tokens_ingested..]
     }
 
-    pub const fn apply_generating_contribution(&mut self, batch_position: i32) {
-        self.pending_sampled_token = None;
+    pub fn apply_generating_contribution(&mut self, batch_position: i32) {
+        if let Some(
+            SampledToken::Content(token)
+            | SampledToken::Reasoning(token)
+            | SampledToken::ToolCall(token)
+            | SampledToken::Undeterminable(token),
+        ) = self.pending_sampled_token.take()
+        {
+            self.generated_tokens.push(token);
+        }
+
         self.i_batch = Some(batch_position);
         self.current_token_position += 1;
     }
@@ -46,6 +59,20 @@ impl ContinuousBatchRequestState {
         Ok(())
     }
 
+    /// Tokens occupying this sequence's KV cells, or `None` when some cells (such as
+    /// image embeddings) have no token to describe them.
+    pub fn take_sequence_tokens(&mut self) -> Option<Vec<LlamaToken>> {
+        let mut sequence_tokens = take(&mut self.prompt_tokens);
+
+        sequence_tokens.truncate(self.prompt_tokens_ingested);
+        sequence_tokens.append(&mut self.generated_tokens);
+
+        let covers_every_cell =
+            usize::try_from(self.current_token_position).ok() == Some(sequence_tokens.len());
+
+        (covers_every_cell && !sequence_tokens.is_empty()).then_some(sequence_tokens)
+    }
+
     pub const fn store_pending_token(&mut self, token: SampledToken) {
         self.pending_sampled_token = Some(token);
     }
@@ -84,6 +111,7 @@ mod tests {
     fn ingesting_state(prompt_token_count: usize) -> ContinuousBatchRequestState {
         ContinuousBatchRequestState {
             current_token_position: 0,
+            generated_tokens: Vec::new(),
             i_batch: None,
             max_tokens: 64,
             pending_sampled_token: None,
@@ -134,10 +162,40 @@ mod tests {
         state.apply_generating_contribution(3);
 
         assert!(state.pending_sampled_token.is_none());
+        assert_eq!(state.generated_tokens, vec![LlamaToken::new(9)]);
         assert_eq!(state.i_batch, Some(3));
         assert_eq!(state.current_token_position, 8);
     }
 
+    #[test]
+    fn sequence_tokens_cover_the_ingested_prompt_and_generated_tokens() {
+        let mut state = ingesting_state(5);
+        state.prompt_tokens_ingested = 5;
+        state.current_token_position = 6;
+        state.generated_tokens = vec![LlamaToken::new(2)];
+
+        assert_eq!(
+            state.take_sequence_tokens(),
+            Some(vec![
+                LlamaToken::new(1),
+                LlamaToken::new(1),
+                LlamaToken::new(1),
+                LlamaToken::new(1),
+                LlamaToken::new(1),
+                LlamaToken::new(2),
+            ])
+        );
+    }
+
+    #[test]
+    fn sequence_tokens_are_unknown_when_positions_hold_untokenized_input() {
+        let mut state = ingesting_state(0);
+        state.current_token_position = 40;
+        state.generated_tokens = vec![LlamaToken::new(2)];
+
+        assert_eq!(state.take_sequence_tokens(), None);
+    }
+
     #[test]
     fn applying_a_non_final_ingesting_chunk_advances_without_transitioning() {
         let mut state = ingesting_state(10);
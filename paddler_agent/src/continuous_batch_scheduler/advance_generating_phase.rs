@@ -4,6 +4,7 @@ use log::error;
 use log::warn;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::generation_summary::GenerationSummary;
+use paddler_messaging::stop_reason::StopReason;
 
 use crate::continuous_batch_active_request::ContinuousBatchActiveRequest;
 use crate::continuous_batch_request_phase::ContinuousBatchRequestPhase;
@@ -44,12 +45,15 @@ impl AdvanceGeneratingPhase<'_> {
 
         let batch_index = request.state.i_batch?;
 
-        let raw_token = match (SampleTokenPhase {
+        let (candidate_logprobs, raw_token) = match (SampleTokenPhase {
             context: self.llama_context,
         })
         .run(request, batch_index)
         {
-            SampleOutcome::Sampled(token) => token,
+            SampleOutcome::Sampled {
+                candidate_logprobs,
+                token,
+            } => (candidate_logprobs, token),
             SampleOutcome::AllCandidatesEliminated => {
                 error!(
                     "{:?}: sequence {} sampling exhausted candidates",
@@ -108,27 +112,43 @@ impl AdvanceGeneratingPhase<'_> {
             completion_phase.run(request, &raw_as_sampled),
             CompletionCheckOutcome::ReachedEog
         ) {
-            if let Some(pipeline) = request.tool_call_pipeline.as_mut()
-                && !pipeline.buffer_is_empty()
-                && let Some(event) = pipeline.finalize_to_generated_event()
-                && request.generated_tokens_tx.send(event).is_err()
-            {
-                warn!(
-                    "{:?}: sequence {} client disconnected (receiver dropped) during EOG tool-call flush",
+            return Some(self.finish(request, StopReason::EndOfGeneration));
+        }
+
+        // Log-probabilities are only reported for tokens the client receives as content, never
+        // for reasoning or tool call tokens.
+        let mut token_logprob = match candidate_logprobs
+            .filter(|_| {
+                classified_outcomes
+                    .iter()
+                    .any(|classified| classified.is_content_token(raw_token))
+            })
+            .map(|candidate_logprobs| {
+                candidate_logprobs.into_token_logprob(&self.scheduler_context.model)
+            })
+            .transpose()
+        {
+            Ok(token_logprob) => token_logprob,
+            Err(err) => {
+                error!(
+                    "{:?}: sequence {} failed to compute log-probabilities: {err:#}",
                     self.scheduler_context.agent_name,
                     request.sequence_id_guard.sequence_id()
                 );
-                return Some(AdvanceOutcome::ChannelDropped);
+                return Some(AdvanceOutcome::Completed(
+                    GeneratedTokenResult::SamplerError(err.to_string()),
+                ));
             }
-            return Some(AdvanceOutcome::Completed(GeneratedTokenResult::Done(
-                GenerationSummary {
-                    usage: *request.token_classifier.usage(),
-                },
-            )));
-        }
+        };
 
         for classified in &classified_outcomes {
-            match emit_token_phase::run(request, classified) {
+            let classified_logprob = if classified.is_content_token(raw_token) {
+                token_logprob.take()
+            } else {
+                None
+            };
+
+            match emit_token_phase::run(request, classified, classified_logprob) {
                 EmitTokenOutcome::Emitted(_) => {}
                 EmitTokenOutcome::ChannelDropped => {
                     warn!(
@@ -138,6 +158,9 @@ impl AdvanceGeneratingPhase<'_> {
                     );
                     return Some(AdvanceOutcome::ChannelDropped);
                 }
+                EmitTokenOutcome::ReachedStopSequence => {
+                    return Some(self.finish(request, StopReason::StopSequence));
+                }
             }
 
             if let Some(event) =
@@ -154,24 +177,11 @@ impl AdvanceGeneratingPhase<'_> {
         }
 
         match completion_phase.run(request, &raw_as_sampled) {
-            CompletionCheckOutcome::ReachedEog | CompletionCheckOutcome::ReachedMaxTokens => {
-                if let Some(pipeline) = request.tool_call_pipeline.as_mut()
-                    && !pipeline.buffer_is_empty()
-                    && let Some(event) = pipeline.finalize_to_generated_event()
-                    && request.generated_tokens_tx.send(event).is_err()
-                {
-                    warn!(
-                        "{:?}: sequence {} client disconnected (receiver dropped) during tool-call EOG flush",
-                        self.scheduler_context.agent_name,
-                        request.sequence_id_guard.sequence_id()
-                    );
-                    return Some(AdvanceOutcome::ChannelDropped);
-                }
-                Some(AdvanceOutcome::Completed(GeneratedTokenResult::Done(
-                    GenerationSummary {
-                        usage: *request.token_classifier.usage(),
-                    },
-                )))
+            CompletionCheckOutcome::ReachedEog => {
+                Some(self.finish(request, StopReason::EndOfGeneration))
+            }
+            CompletionCheckOutcome::ReachedMaxTokens => {
+                Some(self.finish(request, StopReason::MaxTokens))
             }
             CompletionCheckOutcome::Continue => {
                 Some(AdvanceOutcome::SampledAndStored(raw_as_sampled))
@@ -179,6 +189,44 @@ impl AdvanceGeneratingPhase<'_> {
         }
     }
 
+    fn finish(
+        &self,
+        request: &mut ContinuousBatchActiveRequest,
+        stop_reason: StopReason,
+    ) -> AdvanceOutcome {
+        if !matches!(stop_reason, StopReason::StopSequence)
+            && matches!(
+                emit_token_phase::flush_held_back(request),
+                EmitTokenOutcome::ChannelDropped
+            )
+        {
+            warn!(
+                "{:?}: sequence {} client disconnected (receiver dropped) during stop-sequence flush",
+                self.scheduler_context.agent_name,
+                request.sequence_id_guard.sequence_id()
+            );
+            return AdvanceOutcome::ChannelDropped;
+        }
+
+        if let Some(pipeline) = request.tool_call_pipeline.as_mut()
+            && !pipeline.buffer_is_empty()
+            && let Some(event) = pipeline.finalize_to_generated_event()
+            && request.generated_tokens_tx.send(event).is_err()
+        {
+            warn!(
+                "{:?}: sequence {} client disconnected (receiver dropped) during tool-call flush",
+                self.scheduler_context.agent_name,
+                request.sequence_id_guard.sequence_id()
+            );
+            return AdvanceOutcome::ChannelDropped;
+        }
+
+        AdvanceOutcome::Completed(GeneratedTokenResult::Done(GenerationSummary {
+            stop_reason,
+            usage: *request.token_classifier.usage(),
+        }))
+    }
+
     fn apply_outcome(request: &mut ContinuousBatchActiveRequest, outcome: Option<AdvanceOutcome>) {
         match outcome {
             None => {}
@@ -1,4 +1,5 @@
 use llama_cpp_bindings::SampledToken;
+use llama_cpp_bindings::token::LlamaToken;
 
 pub struct ClassifiedToken {
     pub sampled_token: SampledToken,
@@ -13,3 +14,16 @@ pub struct ClassifiedToken {
     /// (`<tool_call>...</tool_call>` etc.) that llama.cpp's autoparser expects.
     pub raw_piece: String,
 }
+
+impl ClassifiedToken {
+    /// Whether this is `token` itself, classified as (possibly) content with visible text.
+    #[must_use]
+    pub fn is_content_token(&self, token: LlamaToken) -> bool {
+        !self.visible_piece.is_empty()
+            && matches!(
+                self.sampled_token,
+                SampledToken::Content(classified_token) | SampledToken::Undeterminable(classified_token)
+                    if classified_token == token
+            )
+    }
+}
@@ -2,4 +2,5 @@
 pub enum EmitTokenOutcome {
     Emitted(String),
     ChannelDropped,
+    ReachedStopSequence,
 }
@@ -1,28 +1,96 @@
 use llama_cpp_bindings::SampledToken;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::token_logprob::TokenLogprob;
 use tokio::sync::mpsc;
 
 use crate::continuous_batch_active_request::ContinuousBatchActiveRequest;
 use crate::continuous_batch_scheduler::classified_token::ClassifiedToken;
 use crate::continuous_batch_scheduler::emit_token_outcome::EmitTokenOutcome;
+use crate::stop_sequence_scan::StopSequenceScan;
 
+/// `token_logprob` belongs to the emitted content token. It is sent ahead of the token's text
+/// and dropped when the token completes a stop sequence.
 pub fn run(
     request: &mut ContinuousBatchActiveRequest,
     classified: &ClassifiedToken,
+    token_logprob: Option<TokenLogprob>,
 ) -> EmitTokenOutcome {
-    emit_classified(classified, &request.generated_tokens_tx)
+    if !matches!(
+        classified.sampled_token,
+        SampledToken::Content(_) | SampledToken::Undeterminable(_)
+    ) {
+        if matches!(flush_held_back(request), EmitTokenOutcome::ChannelDropped) {
+            return EmitTokenOutcome::ChannelDropped;
+        }
+
+        return emit_classified(classified, &request.generated_tokens_tx);
+    }
+
+    match request
+        .stop_sequence_matcher
+        .scan(classified.sampled_token, &classified.visible_piece)
+    {
+        StopSequenceScan::Continue(releasable) => {
+            if let Some(token_logprob) = token_logprob
+                && request
+                    .generated_tokens_tx
+                    .send(GeneratedTokenResult::TokenLogprob(token_logprob))
+                    .is_err()
+            {
+                return EmitTokenOutcome::ChannelDropped;
+            }
+
+            emit_piece(
+                classified.sampled_token,
+                releasable,
+                &request.generated_tokens_tx,
+            )
+        }
+        StopSequenceScan::Matched(releasable) => match emit_piece(
+            classified.sampled_token,
+            releasable,
+            &request.generated_tokens_tx,
+        ) {
+            EmitTokenOutcome::ChannelDropped => EmitTokenOutcome::ChannelDropped,
+            EmitTokenOutcome::Emitted(_) | EmitTokenOutcome::ReachedStopSequence => {
+                EmitTokenOutcome::ReachedStopSequence
+            }
+        },
+    }
+}
+
+/// Sends text held back by the stop-sequence matcher once generation ends
+/// for a reason other than a stop sequence.
+pub fn flush_held_back(request: &mut ContinuousBatchActiveRequest) -> EmitTokenOutcome {
+    match request.stop_sequence_matcher.flush() {
+        Some((sampled_token, held_back)) => {
+            emit_piece(sampled_token, held_back, &request.generated_tokens_tx)
+        }
+        None => EmitTokenOutcome::Emitted(String::new()),
+    }
 }
 
 fn emit_classified(
     classified: &ClassifiedToken,
     tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
 ) -> EmitTokenOutcome {
-    if classified.visible_piece.is_empty() {
+    emit_piece(
+        classified.sampled_token,
+        classified.visible_piece.clone(),
+        tx,
+    )
+}
+
+fn emit_piece(
+    sampled_token: SampledToken,
+    piece: String,
+    tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+) -> EmitTokenOutcome {
+    if piece.is_empty() {
         return EmitTokenOutcome::Emitted(String::new());
     }
 
-    let piece = classified.visible_piece.clone();
-    let event = token_to_event(classified.sampled_token, piece.clone());
+    let event = token_to_event(sampled_token, piece.clone());
 
     if tx.send(event).is_err() {
         return EmitTokenOutcome::ChannelDropped;
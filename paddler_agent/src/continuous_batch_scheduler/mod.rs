@@ -37,6 +37,7 @@ use llama_cpp_bindings::mtmd::MtmdContext;
 use llama_cpp_bindings::mtmd::MtmdEvalError;
 use llama_cpp_bindings::mtmd::MtmdInputText;
 use llama_cpp_bindings::sampling::LlamaSampler;
+use llama_cpp_bindings::token::LlamaToken;
 use log::debug;
 use log::error;
 use log::info;
@@ -44,10 +45,13 @@ use log::warn;
 use paddler_messaging::embedding_result::EmbeddingResult;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::generation_summary::GenerationSummary;
+use paddler_messaging::logprobs_request::LogprobsRequest;
 use paddler_messaging::oversized_image_details::OversizedImageDetails;
 use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::Tool;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_messaging::sampling_overrides::SamplingOverrides;
+use paddler_messaging::stop_reason::StopReason;
 use rand::Rng as _;
 use rand::rngs::ThreadRng;
 use tokio::sync::mpsc;
@@ -68,15 +72,19 @@ use crate::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
 use crate::decoded_image::DecodedImage;
 use crate::generate_embedding_batch_request::GenerateEmbeddingBatchRequest;
 use crate::grammar_sampler::GrammarSampler;
+use crate::inference_request_trace::InferenceRequestTrace;
 use crate::prepare_conversation_history_request::prepare_conversation_history_request;
 use crate::prepared_conversation_history_request::PreparedConversationHistoryRequest;
 use crate::resolve_grammar::resolve_grammar;
 use crate::sample_token_at_batch_index::sample_token_at_batch_index;
 use crate::sampling_outcome::SamplingOutcome;
 use crate::send_generated_token_result_or_warn::send_generated_token_result_or_warn;
+use crate::sequence_choice::SequenceChoice;
 use crate::sequence_id_guard::SequenceIdGuard;
 use crate::sequence_id_pool::SequenceIdPool;
+use crate::sequence_prefix_cache::SequencePrefixCache;
 use crate::slot_guard::SlotGuard;
+use crate::stop_sequence_matcher::StopSequenceMatcher;
 use crate::tool_call_pipeline::ToolCallPipeline;
 use crate::tool_call_validator::ToolCallValidator;
 use crate::validator_build_error::ValidatorBuildError;
@@ -90,6 +98,7 @@ pub struct ContinuousBatchScheduler {
     running: bool,
     scheduler_context: Arc<ContinuousBatchSchedulerContext>,
     sequence_id_pool: SequenceIdPool,
+    sequence_prefix_cache: SequencePrefixCache,
 }
 
 impl ContinuousBatchScheduler {
@@ -117,6 +126,7 @@ impl ContinuousBatchScheduler {
             running: true,
             scheduler_context,
             sequence_id_pool: SequenceIdPool::new(max_concurrent_sequences),
+            sequence_prefix_cache: SequencePrefixCache::default(),
         }
     }
 
@@ -155,7 +165,7 @@ impl ContinuousBatchScheduler {
         }
 
         while !self.active_requests.is_empty() {
-            self.cleanup_completed_request(0);
+            self.cleanup_completed_request(0, false);
         }
 
         self.llama_context.synchronize();
@@ -205,6 +215,7 @@ impl ContinuousBatchScheduler {
             generated_tokens_tx,
             params,
             slot_guard,
+            trace,
         }: ContinueFromConversationHistoryRequest,
     ) {
         let prepared = match prepare_conversation_history_request(
@@ -228,18 +239,27 @@ impl ContinuousBatchScheduler {
                 raw_prompt,
                 max_tokens,
                 grammar_sampler,
+                logprobs,
+                sampling,
+                stop,
                 parse_tool_calls,
+                tool_call_validator,
                 tools,
             } => {
                 if let Err(err) = self.accept_text_prompt(
                     &raw_prompt,
                     max_tokens,
                     grammar_sampler,
+                    logprobs,
+                    sampling.as_ref(),
+                    stop,
                     parse_tool_calls,
+                    tool_call_validator,
                     tools,
                     generated_tokens_tx,
                     generate_tokens_stop_rx,
                     slot_guard,
+                    trace,
                 ) {
                     error!(
                         "{:?}: failed to accept text prompt: {err:#}",
@@ -252,7 +272,11 @@ impl ContinuousBatchScheduler {
                 images,
                 max_tokens,
                 grammar_sampler,
+                logprobs,
+                sampling,
+                stop,
                 parse_tool_calls,
+                tool_call_validator,
                 tools,
             } => {
                 let multimodal_context = self.scheduler_context.multimodal_context.clone();
@@ -264,11 +288,16 @@ impl ContinuousBatchScheduler {
                         &images,
                         max_tokens,
                         grammar_sampler,
+                        logprobs,
+                        sampling.as_ref(),
+                        stop,
                         parse_tool_calls,
+                        tool_call_validator,
                         tools,
                         generated_tokens_tx,
                         generate_tokens_stop_rx,
                         slot_guard,
+                        trace,
                     )
                 {
                     error!(
@@ -288,10 +317,16 @@ impl ContinuousBatchScheduler {
             params:
                 ContinueFromRawPromptParams {
                     grammar,
+                    logprobs,
                     max_tokens,
+                    model: _,
                     raw_prompt,
+                    sampling,
+                    session_key: _,
+                    stop,
                 },
             slot_guard,
+            trace,
         }: ContinueFromRawPromptRequest,
     ) {
         let grammar_sampler = match resolve_grammar(grammar.as_ref(), false, &generated_tokens_tx) {
@@ -310,11 +345,16 @@ impl ContinuousBatchScheduler {
             &raw_prompt,
             max_tokens,
             grammar_sampler,
+            logprobs,
+            sampling.as_ref(),
+            stop,
             false,
+            None,
             Vec::new(),
             generated_tokens_tx,
             generate_tokens_stop_rx,
             slot_guard,
+            trace,
         ) {
             error!(
                 "{:?}: failed to accept raw prompt: {err:#}",
@@ -323,21 +363,27 @@ impl ContinuousBatchScheduler {
         }
     }
 
-    fn create_sampler_chain(&mut self) -> LlamaSampler {
+    fn create_sampler_chain(&mut self, sampling: Option<&SamplingOverrides>) -> LlamaSampler {
+        let inference_parameters = sampling.map_or_else(
+            || self.scheduler_context.inference_parameters.clone(),
+            |sampling| sampling.apply_to(&self.scheduler_context.inference_parameters),
+        );
+        let seed = sampling
+            .and_then(|sampling| sampling.seed)
+            .unwrap_or_else(|| self.rng.random::<u32>());
+
         LlamaSampler::chain_simple([
             LlamaSampler::penalties(
-                self.scheduler_context.inference_parameters.penalty_last_n,
-                self.scheduler_context.inference_parameters.penalty_repeat,
-                self.scheduler_context
-                    .inference_parameters
-                    .penalty_frequency,
-                self.scheduler_context.inference_parameters.penalty_presence,
+                inference_parameters.penalty_last_n,
+                inference_parameters.penalty_repeat,
+                inference_parameters.penalty_frequency,
+                inference_parameters.penalty_presence,
             ),
-            LlamaSampler::top_k(self.scheduler_context.inference_parameters.top_k),
-            LlamaSampler::top_p(self.scheduler_context.inference_parameters.top_p, 0),
-            LlamaSampler::min_p(self.scheduler_context.inference_parameters.min_p, 0),
-            LlamaSampler::temp(self.scheduler_context.inference_parameters.temperature),
-            LlamaSampler::dist(self.rng.random::<u32>()),
+            LlamaSampler::top_k(inference_parameters.top_k),
+            LlamaSampler::top_p(inference_parameters.top_p, 0),
+            LlamaSampler::min_p(inference_parameters.min_p, 0),
+            LlamaSampler::temp(inference_parameters.temperature),
+            LlamaSampler::dist(seed),
         ])
     }
 
@@ -393,27 +439,31 @@ impl ContinuousBatchScheduler {
         })
     }
 
+    /// Reuses the validator the request was prepared with, if any, instead of compiling the
+    /// tool schemas again.
     fn build_tool_call_pipeline(
         &self,
         tools: Vec<Tool<ValidatedParametersSchema>>,
         parse_tool_calls: bool,
+        tool_call_validator: Option<ToolCallValidator>,
     ) -> Result<ToolCallPipelineBuildOutcome> {
         if !parse_tool_calls || tools.is_empty() {
             return Ok(ToolCallPipelineBuildOutcome::Disabled);
         }
 
-        let validator = match ToolCallValidator::from_tools(&tools) {
-            Ok(validator) => validator,
-            Err(ValidatorBuildError::InvalidSchema { tool_name, message }) => {
-                return Ok(ToolCallPipelineBuildOutcome::SchemaInvalid(format!(
-                    "tool {tool_name:?} parameters are not a valid JSON Schema: {message}"
-                )));
-            }
-            Err(err @ ValidatorBuildError::SerializationFailed { .. }) => {
-                return Err(anyhow::Error::from(err))
-                    .context("failed to serialize tool parameters during validator build");
-            }
-        };
+        let validator =
+            match tool_call_validator.map_or_else(|| ToolCallValidator::from_tools(&tools), Ok) {
+                Ok(validator) => validator,
+                Err(ValidatorBuildError::InvalidSchema { tool_name, message }) => {
+                    return Ok(ToolCallPipelineBuildOutcome::SchemaInvalid(format!(
+                        "tool {tool_name:?} parameters are not a valid JSON Schema: {message}"
+                    )));
+                }
+                Err(err @ ValidatorBuildError::SerializationFailed { .. }) => {
+                    return Err(anyhow::Error::from(err))
+                        .context("failed to serialize tool parameters during validator build");
+                }
+            };
 
         let tools_json: Vec<serde_json::Value> = tools
             .into_iter()
@@ -433,14 +483,19 @@ impl ContinuousBatchScheduler {
         prompt: &str,
         max_tokens: i32,
         grammar_sampler: Option<GrammarSampler>,
+        logprobs: Option<LogprobsRequest>,
+        sampling: Option<&SamplingOverrides>,
+        stop: Vec<String>,
         parse_tool_calls: bool,
+        tool_call_validator: Option<ToolCallValidator>,
         tools: Vec<Tool<ValidatedParametersSchema>>,
         generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
         generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
         slot_guard: SlotGuard,
+        mut trace: InferenceRequestTrace,
     ) -> Result<()> {
         let tool_call_pipeline = match self
-            .build_tool_call_pipeline(tools, parse_tool_calls)
+            .build_tool_call_pipeline(tools, parse_tool_calls, tool_call_validator)
             .context("failed to build tool-call pipeline for text prompt")?
         {
             ToolCallPipelineBuildOutcome::Disabled => None,
@@ -461,23 +516,6 @@ impl ContinuousBatchScheduler {
             }
         };
 
-        let Some(sequence_guard) = SequenceIdGuard::acquire(&self.sequence_id_pool) else {
-            let message = format!(
-                "{:?}: no available sequence slots, all slots are busy",
-                self.scheduler_context.agent_name
-            );
-
-            error!("{message}");
-
-            send_generated_token_result_or_warn(
-                self.scheduler_context.agent_name.as_deref(),
-                &generated_tokens_tx,
-                GeneratedTokenResult::SamplerError(message),
-            );
-
-            return Ok(());
-        };
-
         let prompt_tokens = match self
             .scheduler_context
             .model
@@ -502,23 +540,45 @@ impl ContinuousBatchScheduler {
             }
         };
 
+        let Some((sequence_guard, sequence_choice)) = self.acquire_sequence(&prompt_tokens) else {
+            let message = format!(
+                "{:?}: no available sequence slots, all slots are busy",
+                self.scheduler_context.agent_name
+            );
+
+            error!("{message}");
+
+            send_generated_token_result_or_warn(
+                self.scheduler_context.agent_name.as_deref(),
+                &generated_tokens_tx,
+                GeneratedTokenResult::SamplerError(message),
+            );
+
+            return Ok(());
+        };
+
         let Ok(llama_grammar_sampler) =
             self.create_grammar_llama_sampler(grammar_sampler, &generated_tokens_tx)
         else {
             return Ok(());
         };
 
-        let chain = self.create_sampler_chain();
+        let chain = self.create_sampler_chain(sampling);
 
         let mut token_classifier = self.build_token_classifier_for_active_request()?;
 
+        let reused_prefix_tokens = self.reuse_sequence_prefix(&sequence_choice);
+
         token_classifier.record_prompt_tokens(prompt_tokens.len() as u64);
+        token_classifier
+            .record_cached_prompt_tokens(reused_prefix_tokens as u64)
+            .context("failed to record reused prompt tokens")?;
         token_classifier.ingest_prompt_tokens(&prompt_tokens);
 
-        self.clear_kv_cache_for_sequence(sequence_guard.sequence_id());
+        trace.start_prefill();
 
         debug!(
-            "{:?}: accepted text prompt request on sequence {} ({} tokens)",
+            "{:?}: accepted text prompt request on sequence {} ({} tokens, {reused_prefix_tokens} reused from KV cache)",
             self.scheduler_context.agent_name,
             sequence_guard.sequence_id(),
             prompt_tokens.len()
@@ -526,22 +586,27 @@ impl ContinuousBatchScheduler {
 
         self.active_requests.push(ContinuousBatchActiveRequest {
             state: ContinuousBatchRequestState {
-                current_token_position: 0,
+                current_token_position: i32::try_from(reused_prefix_tokens)
+                    .context("reused prefix length does not fit in i32")?,
+                generated_tokens: Vec::new(),
                 i_batch: None,
                 max_tokens,
                 pending_sampled_token: None,
                 phase: ContinuousBatchRequestPhase::Ingesting,
                 prompt_tokens,
-                prompt_tokens_ingested: 0,
+                prompt_tokens_ingested: reused_prefix_tokens,
             },
             chain,
             token_classifier,
             grammar_sampler: llama_grammar_sampler,
+            logprobs,
             generated_tokens_tx,
             generate_tokens_stop_rx,
             sequence_id_guard: sequence_guard,
             slot_guard,
+            stop_sequence_matcher: StopSequenceMatcher::new(stop),
             tool_call_pipeline,
+            trace,
         });
 
         Ok(())
@@ -554,14 +619,19 @@ impl ContinuousBatchScheduler {
         images: &[DecodedImage],
         max_tokens: i32,
         grammar_sampler: Option<GrammarSampler>,
+        logprobs: Option<LogprobsRequest>,
+        sampling: Option<&SamplingOverrides>,
+        stop: Vec<String>,
         parse_tool_calls: bool,
+        tool_call_validator: Option<ToolCallValidator>,
         tools: Vec<Tool<ValidatedParametersSchema>>,
         generated_tokens_tx: mpsc::UnboundedSender<GeneratedTokenResult>,
         generate_tokens_stop_rx: mpsc::UnboundedReceiver<()>,
         slot_guard: SlotGuard,
+        mut trace: InferenceRequestTrace,
     ) -> Result<()> {
         let tool_call_pipeline = match self
-            .build_tool_call_pipeline(tools, parse_tool_calls)
+            .build_tool_call_pipeline(tools, parse_tool_calls, tool_call_validator)
             .context("failed to build tool-call pipeline for multimodal request")?
         {
             ToolCallPipelineBuildOutcome::Disabled => None,
@@ -582,7 +652,7 @@ impl ContinuousBatchScheduler {
             }
         };
 
-        let Some(sequence_guard) = SequenceIdGuard::acquire(&self.sequence_id_pool) else {
+        let Some((sequence_guard, _)) = self.acquire_sequence(&[]) else {
             let message = format!(
                 "{:?}: no available sequence slots for multimodal request",
                 self.scheduler_context.agent_name
@@ -659,6 +729,8 @@ impl ContinuousBatchScheduler {
 
         let batch_size = self.scheduler_context.inference_parameters.n_batch;
 
+        self.sequence_prefix_cache
+            .forget(sequence_guard.sequence_id());
         self.clear_kv_cache_for_sequence(sequence_guard.sequence_id());
 
         self.harvest_pending_samples_before_external_decode();
@@ -667,6 +739,8 @@ impl ContinuousBatchScheduler {
 
         let batch_size_i32 = i32::try_from(batch_size).context("batch_size does not fit in i32")?;
 
+        trace.start_prefill();
+
         let eval_outcome = token_classifier.eval_multimodal_chunks(
             &input_chunks,
             multimodal_context,
@@ -726,7 +800,7 @@ impl ContinuousBatchScheduler {
             return Ok(());
         };
 
-        let chain = self.create_sampler_chain();
+        let chain = self.create_sampler_chain(sampling);
 
         debug!(
             "{:?}: accepted multimodal request on sequence {} ({tokens_ingested} tokens ingested)",
@@ -737,6 +811,7 @@ impl ContinuousBatchScheduler {
         self.active_requests.push(ContinuousBatchActiveRequest {
             state: ContinuousBatchRequestState {
                 current_token_position: tokens_ingested,
+                generated_tokens: Vec::new(),
                 i_batch: Some(-1),
                 max_tokens,
                 pending_sampled_token: None,
@@ -747,11 +822,14 @@ impl ContinuousBatchScheduler {
             chain,
             token_classifier,
             grammar_sampler: llama_grammar_sampler,
+            logprobs,
             generated_tokens_tx,
             generate_tokens_stop_rx,
             sequence_id_guard: sequence_guard,
             slot_guard,
+            stop_sequence_matcher: StopSequenceMatcher::new(stop),
             tool_call_pipeline,
+            trace,
         });
 
         Ok(())
@@ -779,8 +857,11 @@ impl ContinuousBatchScheduler {
                 batch_index,
                 &mut active_request.chain,
                 &mut active_request.grammar_sampler,
+                None,
             ) {
-                Ok(SamplingOutcome::Token(raw_token)) => {
+                Ok(SamplingOutcome::Token {
+                    token: raw_token, ..
+                }) => {
                     // Update classifier state (section / usage counters) but drop the
                     // outcomes — harvest-sampled tokens are funnelled into the next
                     // batch via `pending_sampled_token`; their user-visible emission
@@ -799,6 +880,7 @@ impl ContinuousBatchScheduler {
                         continue;
                     }
 
+                    active_request.trace.start_decode();
                     active_request.state.pending_sampled_token =
                         Some(llama_cpp_bindings::SampledToken::Content(raw_token));
                     active_request.state.i_batch = None;
@@ -847,6 +929,7 @@ impl ContinuousBatchScheduler {
 
             if active_request.is_stop_requested() {
                 let summary = GenerationSummary {
+                    stop_reason: StopReason::Cancelled,
                     usage: *active_request.token_classifier.usage(),
                 };
 
@@ -886,6 +969,9 @@ impl ContinuousBatchScheduler {
                 self.scheduler_context.agent_name
             );
         }
+
+        // Embedding batches clear the whole KV cache.
+        self.sequence_prefix_cache.clear();
     }
 
     fn has_active_requests(&self) -> bool {
@@ -955,6 +1041,17 @@ impl ContinuousBatchScheduler {
     }
 
     fn evict_largest_sequence(&mut self) {
+        if let Some(sequence_id) = self.sequence_prefix_cache.evict_least_recently_used() {
+            debug!(
+                "{:?}: dropping cached prefix of idle sequence {sequence_id} due to KV cache pressure",
+                self.scheduler_context.agent_name
+            );
+
+            self.clear_kv_cache_for_sequence(sequence_id);
+
+            return;
+        }
+
         let mut largest_seq_index: Option<usize> = None;
         let mut largest_position: i32 = -1;
 
@@ -986,7 +1083,7 @@ impl ContinuousBatchScheduler {
                 "Request evicted due to KV cache pressure".to_owned(),
             ));
 
-            self.cleanup_completed_request(eviction_index);
+            self.cleanup_completed_request(eviction_index, false);
         }
     }
 
@@ -998,13 +1095,66 @@ impl ContinuousBatchScheduler {
                 self.active_requests[removal_index].state.phase,
                 ContinuousBatchRequestPhase::Completed(_)
             ) {
-                self.cleanup_completed_request(removal_index);
+                self.cleanup_completed_request(removal_index, true);
             } else {
                 removal_index += 1;
             }
         }
     }
 
+    fn acquire_sequence(
+        &self,
+        prompt_tokens: &[LlamaToken],
+    ) -> Option<(SequenceIdGuard, SequenceChoice)> {
+        let sequence_choice = self
+            .sequence_prefix_cache
+            .choose_sequence(prompt_tokens, &self.sequence_id_pool.available_ids())?;
+
+        SequenceIdGuard::acquire_id(&self.sequence_id_pool, sequence_choice.sequence_id)
+            .map(|sequence_guard| (sequence_guard, sequence_choice))
+    }
+
+    /// Keeps the reusable prefix of the chosen sequence's KV cells and frees the rest.
+    /// Returns how many prompt tokens do not need to be decoded again.
+    fn reuse_sequence_prefix(&mut self, sequence_choice: &SequenceChoice) -> usize {
+        let SequenceChoice {
+            reusable_prefix_tokens,
+            sequence_id,
+        } = *sequence_choice;
+
+        self.sequence_prefix_cache.forget(sequence_id);
+
+        if reusable_prefix_tokens > 0 {
+            let trimmed = u32::try_from(sequence_id)
+                .ok()
+                .zip(u32::try_from(reusable_prefix_tokens).ok())
+                .is_some_and(|(sequence_id_u32, reusable_prefix_tokens_u32)| {
+                    matches!(
+                        self.llama_context.clear_kv_cache_seq(
+                            Some(sequence_id_u32),
+                            Some(reusable_prefix_tokens_u32),
+                            None,
+                        ),
+                        Ok(true)
+                    )
+                });
+
+            if trimmed {
+                return reusable_prefix_tokens;
+            }
+
+            // Some memory types (recurrent state, for example) cannot be partially removed.
+            debug!(
+                "{:?}: unable to trim the KV cache of sequence {sequence_id}, ingesting the prompt from scratch",
+                self.scheduler_context.agent_name
+            );
+        }
+
+        self.clear_kv_cache_for_sequence(sequence_id);
+
+        0
+    }
+
     fn clear_kv_cache_for_sequence(&mut self, sequence_id: i32) {
         let sequence_id_u32 = match u32::try_from(sequence_id) {
             Ok(sequence_id_u32) => sequence_id_u32,
@@ -1029,13 +1179,25 @@ impl ContinuousBatchScheduler {
         }
     }
 
-    fn cleanup_completed_request(&mut self, index: usize) {
-        let removed_request = self.active_requests.swap_remove(index);
+    /// With `retain_prefix`, the sequence's KV cells stay in place so a later request
+    /// sharing its prefix can pick them up.
+    fn cleanup_completed_request(&mut self, index: usize, retain_prefix: bool) {
+        let mut removed_request = self.active_requests.swap_remove(index);
         let sequence_id = removed_request.sequence_id_guard.sequence_id();
         let usage = *removed_request.token_classifier.usage();
+        let sequence_tokens = if retain_prefix {
+            removed_request.state.take_sequence_tokens()
+        } else {
+            None
+        };
         let terminal_delivery = removed_request.into_terminal_delivery();
 
-        self.clear_kv_cache_for_sequence(sequence_id);
+        match sequence_tokens {
+            Some(sequence_tokens) => self
+                .sequence_prefix_cache
+                .remember(sequence_id, sequence_tokens),
+            None => self.clear_kv_cache_for_sequence(sequence_id),
+        }
 
         debug!(
             "{:?}: cleaned up sequence {sequence_id} ({} completion tokens generated)",
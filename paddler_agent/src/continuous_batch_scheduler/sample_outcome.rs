@@ -1,7 +1,12 @@
 use llama_cpp_bindings::token::LlamaToken;
 
+use crate::candidate_logprobs::CandidateLogprobs;
+
 pub enum SampleOutcome {
-    Sampled(LlamaToken),
+    Sampled {
+        candidate_logprobs: Option<CandidateLogprobs>,
+        token: LlamaToken,
+    },
     AllCandidatesEliminated,
     GrammarRejected(String),
     Failed(String),
@@ -20,8 +20,22 @@ impl SampleTokenPhase<'_> {
             batch_index,
             &mut request.chain,
             &mut request.grammar_sampler,
+            request
+                .logprobs
+                .as_ref()
+                .map(|logprobs| logprobs.top_logprobs),
         ) {
-            Ok(SamplingOutcome::Token(token)) => SampleOutcome::Sampled(token),
+            Ok(SamplingOutcome::Token {
+                candidate_logprobs,
+                token,
+            }) => {
+                request.trace.start_decode();
+
+                SampleOutcome::Sampled {
+                    candidate_logprobs,
+                    token,
+                }
+            }
             Ok(SamplingOutcome::AllCandidatesEliminated) => SampleOutcome::AllCandidatesEliminated,
             Ok(SamplingOutcome::GrammarRejectedModelOutput(message)) => {
                 SampleOutcome::GrammarRejected(message)
@@ -0,0 +1,540 @@
+use std::collections::HashSet;
+
+use llama_cpp_bindings::ToolCallArgsShape;
+use llama_cpp_bindings::ToolCallMarkers;
+use llama_cpp_bindings::XmlTagsShape;
+use llama_cpp_bindings::json_schema_to_grammar;
+use paddler_messaging::grammar_constraint::GrammarConstraint;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool::Tool;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::forced_tool_call_grammar_error::ForcedToolCallGrammarError;
+use crate::tool_call_validator::ToolCallValidator;
+
+const ROOT_RULE: &str = "forced-tool-call";
+
+/// Parameter values the XML tags parser reads as plain text, up to the closing tag.
+const XML_TEXT_RULE: &str = "xml-text ::= ([^<] | \"<\" [^/])*";
+
+fn gbnf_literal(text: &str) -> String {
+    let mut literal = String::with_capacity(text.len() + 2);
+
+    literal.push('"');
+
+    for character in text.chars() {
+        match character {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            other => literal.push(other),
+        }
+    }
+
+    literal.push('"');
+
+    literal
+}
+
+const fn tool_call_format_name(args_shape: &ToolCallArgsShape) -> &'static str {
+    match args_shape {
+        ToolCallArgsShape::BracketedJson(_) => "bracketed JSON",
+        ToolCallArgsShape::JsonObject(_) => "JSON object",
+        ToolCallArgsShape::KeyValueXmlTags(_) => "key-value XML tags",
+        ToolCallArgsShape::PairedQuote(_) => "paired quote",
+        ToolCallArgsShape::XmlTags(_) => "XML tags",
+    }
+}
+
+fn schema_to_grammar(schema: &Value) -> Result<String, ForcedToolCallGrammarError> {
+    json_schema_to_grammar(&schema.to_string())
+        .map_err(|err| ForcedToolCallGrammarError::GrammarConversionFailed(err.to_string()))
+}
+
+fn push_identifier(
+    namespaced: &mut String,
+    identifier: &mut String,
+    rule_names: &HashSet<&str>,
+    namespace: &str,
+) {
+    if rule_names.contains(identifier.as_str()) {
+        namespaced.push_str(namespace);
+        namespaced.push('-');
+    }
+
+    namespaced.push_str(identifier);
+    identifier.clear();
+}
+
+/// Prefixes every rule of a generated grammar, so grammars generated from several schemas
+/// can be combined without their rules clashing.
+fn namespaced_grammar(grammar: &str, namespace: &str) -> String {
+    let rule_names: HashSet<&str> = grammar
+        .lines()
+        .filter_map(|line| line.split_once("::=").map(|(name, _)| name.trim()))
+        .collect();
+    let mut namespaced = String::with_capacity(grammar.len());
+    let mut identifier = String::new();
+    let mut in_class = false;
+    let mut in_literal = false;
+    let mut is_escaped = false;
+
+    for character in grammar.chars() {
+        if in_class || in_literal {
+            namespaced.push(character);
+
+            if is_escaped {
+                is_escaped = false;
+            } else if character == '\\' {
+                is_escaped = true;
+            } else if in_class && character == ']' {
+                in_class = false;
+            } else if in_literal && character == '"' {
+                in_literal = false;
+            }
+
+            continue;
+        }
+
+        if character.is_ascii_alphanumeric() || character == '-' || character == '_' {
+            identifier.push(character);
+
+            continue;
+        }
+
+        push_identifier(&mut namespaced, &mut identifier, &rule_names, namespace);
+
+        match character {
+            '"' => in_literal = true,
+            '[' => in_class = true,
+            _ => {}
+        }
+
+        namespaced.push(character);
+    }
+
+    push_identifier(&mut namespaced, &mut identifier, &rule_names, namespace);
+
+    namespaced
+}
+
+fn json_object_call_grammar(
+    candidates: Vec<(&str, Value)>,
+    name_field: &str,
+    arguments_field: &str,
+) -> Result<String, ForcedToolCallGrammarError> {
+    let mut call_schemas: Vec<Value> = candidates
+        .into_iter()
+        .map(|(name, arguments_schema)| {
+            json!({
+                "type": "object",
+                "properties": {
+                    name_field: {"const": name},
+                    arguments_field: arguments_schema,
+                },
+                "required": [name_field, arguments_field],
+                "additionalProperties": false,
+            })
+        })
+        .collect();
+    let schema = if call_schemas.len() == 1 {
+        call_schemas.remove(0)
+    } else {
+        json!({"anyOf": call_schemas})
+    };
+
+    schema_to_grammar(&schema)
+}
+
+/// Parameters are expected in the order the schema lists them, the required ones always
+/// and the others at most once; without declared properties any parameters are admitted.
+fn xml_tags_function_rules(
+    rule: &str,
+    name: &str,
+    arguments_schema: &Value,
+    shape: &XmlTagsShape,
+) -> Result<String, ForcedToolCallGrammarError> {
+    let mut rules = String::new();
+    let parameter_close = gbnf_literal(&shape.parameter_close);
+    let required: Vec<&str> = arguments_schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+    let parameters = if let Some(properties) = arguments_schema
+        .get("properties")
+        .and_then(Value::as_object)
+    {
+        let mut parameters = Vec::with_capacity(properties.len());
+
+        for (parameter_index, (parameter_name, parameter_schema)) in properties.iter().enumerate() {
+            let parameter_rule = format!("{rule}-parameter-{parameter_index}");
+            let value_rule =
+                if parameter_schema.get("type").and_then(Value::as_str) == Some("string") {
+                    "xml-text".to_owned()
+                } else {
+                    let namespace = format!("{parameter_rule}-value");
+
+                    rules.push_str(&namespaced_grammar(
+                        &schema_to_grammar(parameter_schema)?,
+                        &namespace,
+                    ));
+                    rules.push('\n');
+
+                    format!("{namespace}-root")
+                };
+
+            rules.push_str(&format!(
+                "{parameter_rule} ::= {} \"\\n\"? {value_rule} \"\\n\"? {parameter_close} \"\\n\"?\n",
+                gbnf_literal(&format!("{}{parameter_name}>", shape.parameter_open_prefix)),
+            ));
+            parameters.push(if required.contains(&parameter_name.as_str()) {
+                parameter_rule
+            } else {
+                format!("{parameter_rule}?")
+            });
+        }
+
+        parameters.join(" ")
+    } else {
+        rules.push_str(&format!(
+            "{rule}-parameter ::= {} [^>]+ \">\" \"\\n\"? xml-text \"\\n\"? {parameter_close} \"\\n\"?\n",
+            gbnf_literal(&shape.parameter_open_prefix),
+        ));
+
+        format!("{rule}-parameter*")
+    };
+
+    rules.push_str(&format!(
+        "{rule} ::= {} \"\\n\"? {parameters} {}\n",
+        gbnf_literal(&format!("{}{name}>", shape.function_open_prefix)),
+        gbnf_literal(&shape.function_close),
+    ));
+
+    Ok(rules)
+}
+
+fn xml_tags_call_grammar(
+    candidates: Vec<(&str, Value)>,
+    shape: &XmlTagsShape,
+) -> Result<String, ForcedToolCallGrammarError> {
+    let mut grammar = String::new();
+    let mut function_rules = Vec::with_capacity(candidates.len());
+
+    for (tool_index, (name, arguments_schema)) in candidates.into_iter().enumerate() {
+        let rule = format!("tool-{tool_index}");
+
+        grammar.push_str(&xml_tags_function_rules(
+            &rule,
+            name,
+            &arguments_schema,
+            shape,
+        )?);
+        function_rules.push(rule);
+    }
+
+    grammar.push_str(XML_TEXT_RULE);
+    grammar.push_str(&format!("\nroot ::= {}", function_rules.join(" | ")));
+
+    Ok(grammar)
+}
+
+/// Builds a grammar that only admits a single call, wrapped in the chat template's tool call
+/// markers, to one of the tools the choice allows, with arguments matching the schemas the
+/// validator checks them against.
+pub fn forced_tool_call_grammar(
+    tool_choice: &ToolChoice,
+    tools: &[Tool<ValidatedParametersSchema>],
+    tool_call_validator: &ToolCallValidator,
+    tool_call_markers: Option<&ToolCallMarkers>,
+) -> Result<GrammarConstraint, ForcedToolCallGrammarError> {
+    let Some(ToolCallMarkers {
+        open,
+        close,
+        args_shape,
+    }) = tool_call_markers
+    else {
+        return Err(ForcedToolCallGrammarError::NoToolCallFormat);
+    };
+
+    let candidates: Vec<(&str, Value)> = tools
+        .iter()
+        .map(|Tool::Function(function_call)| function_call.function.name.as_str())
+        .filter(|name| match tool_choice {
+            ToolChoice::Function { name: chosen_name } => *name == chosen_name.as_str(),
+            ToolChoice::Auto | ToolChoice::None | ToolChoice::Required => true,
+        })
+        .filter_map(|name| {
+            tool_call_validator
+                .arguments_schema(name)
+                .map(|arguments_schema| (name, arguments_schema))
+        })
+        .collect();
+
+    if candidates.is_empty() {
+        return Err(ForcedToolCallGrammarError::NoAllowedTool);
+    }
+
+    let call_grammar = match args_shape {
+        ToolCallArgsShape::JsonObject(shape) => {
+            json_object_call_grammar(candidates, &shape.name_field, &shape.arguments_field)?
+        }
+        ToolCallArgsShape::XmlTags(shape) => xml_tags_call_grammar(candidates, shape)?,
+        ToolCallArgsShape::BracketedJson(_)
+        | ToolCallArgsShape::KeyValueXmlTags(_)
+        | ToolCallArgsShape::PairedQuote(_) => {
+            return Err(ForcedToolCallGrammarError::UnsupportedToolCallFormat {
+                format: tool_call_format_name(args_shape),
+                open: open.clone(),
+            });
+        }
+    };
+    let close_literal = if close.is_empty() {
+        String::new()
+    } else {
+        format!(" \"\\n\"? {}", gbnf_literal(close))
+    };
+
+    Ok(GrammarConstraint::Gbnf {
+        grammar: format!(
+            "{call_grammar}\n{ROOT_RULE} ::= {} \"\\n\"? root{close_literal}\n",
+            gbnf_literal(open)
+        ),
+        root: ROOT_RULE.to_owned(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use llama_cpp_bindings::ToolCallArgsShape;
+    use llama_cpp_bindings::ToolCallMarkers;
+    use llama_cpp_bindings::XmlTagsShape;
+    use llama_cpp_bindings_types::JsonObjectShape;
+    use llama_cpp_bindings_types::PairedQuoteShape;
+    use llama_cpp_bindings_types::ToolCallValueQuote;
+    use paddler_messaging::grammar_constraint::GrammarConstraint;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool::Tool;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::FunctionCall;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+    use serde_json::Map;
+    use serde_json::json;
+
+    use super::forced_tool_call_grammar;
+    use super::gbnf_literal;
+    use super::namespaced_grammar;
+    use crate::forced_tool_call_grammar_error::ForcedToolCallGrammarError;
+    use crate::tool_call_validator::ToolCallValidator;
+
+    fn tool_named(name: &str) -> Tool<ValidatedParametersSchema> {
+        Tool::Function(FunctionCall {
+            function: Function {
+                name: name.to_owned(),
+                description: String::new(),
+                parameters: Parameters::Empty,
+            },
+        })
+    }
+
+    fn weather_tool() -> Tool<ValidatedParametersSchema> {
+        let mut properties = Map::new();
+
+        properties.insert("days".to_owned(), json!({"type": "integer"}));
+        properties.insert("location".to_owned(), json!({"type": "string"}));
+
+        Tool::Function(FunctionCall {
+            function: Function {
+                name: "get_weather".to_owned(),
+                description: String::new(),
+                parameters: Parameters::Schema(ValidatedParametersSchema {
+                    schema_type: "object".to_owned(),
+                    properties: Some(properties),
+                    required: Some(vec!["location".to_owned()]),
+                    additional_properties: None,
+                }),
+            },
+        })
+    }
+
+    fn json_object_markers() -> ToolCallMarkers {
+        ToolCallMarkers {
+            open: "<tool_call>".to_owned(),
+            close: "</tool_call>".to_owned(),
+            args_shape: ToolCallArgsShape::JsonObject(JsonObjectShape {
+                name_field: "name".to_owned(),
+                arguments_field: "arguments".to_owned(),
+            }),
+        }
+    }
+
+    fn xml_tags_markers() -> ToolCallMarkers {
+        ToolCallMarkers {
+            open: "<tool_call>".to_owned(),
+            close: "</tool_call>".to_owned(),
+            args_shape: ToolCallArgsShape::XmlTags(XmlTagsShape {
+                function_open_prefix: "<function=".to_owned(),
+                function_close: "</function>".to_owned(),
+                parameter_open_prefix: "<parameter=".to_owned(),
+                parameter_close: "</parameter>".to_owned(),
+            }),
+        }
+    }
+
+    fn grammar_for(
+        tool_choice: &ToolChoice,
+        tools: &[Tool<ValidatedParametersSchema>],
+        tool_call_markers: Option<&ToolCallMarkers>,
+    ) -> Result<GrammarConstraint, ForcedToolCallGrammarError> {
+        let tool_call_validator = ToolCallValidator::from_tools(tools).unwrap();
+
+        forced_tool_call_grammar(tool_choice, tools, &tool_call_validator, tool_call_markers)
+    }
+
+    #[test]
+    fn escapes_quotes_backslashes_and_newlines_in_literals() {
+        assert_eq!(gbnf_literal("a\"b\\c\nd"), "\"a\\\"b\\\\c\\nd\"");
+    }
+
+    #[test]
+    fn namespaces_rule_names_but_not_literals_or_character_classes() {
+        assert_eq!(
+            namespaced_grammar("root ::= \"root\" space [root]\nspace ::= \" \"?", "tool-0"),
+            "tool-0-root ::= \"root\" tool-0-space [root]\ntool-0-space ::= \" \"?"
+        );
+    }
+
+    #[test]
+    fn wraps_the_call_in_the_tool_call_markers() -> Result<()> {
+        let GrammarConstraint::Gbnf { grammar, root } = grammar_for(
+            &ToolChoice::Required,
+            &[tool_named("get_weather"), tool_named("get_time")],
+            Some(&json_object_markers()),
+        )?
+        else {
+            panic!("expected a GBNF grammar");
+        };
+
+        assert_eq!(root, "forced-tool-call");
+        assert!(grammar.contains(
+            "forced-tool-call ::= \"<tool_call>\" \"\\n\"? root \"\\n\"? \"</tool_call>\""
+        ));
+        assert!(grammar.contains("get_weather"));
+        assert!(grammar.contains("get_time"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn a_named_choice_only_admits_that_function() -> Result<()> {
+        let GrammarConstraint::Gbnf { grammar, .. } = grammar_for(
+            &ToolChoice::Function {
+                name: "get_time".to_owned(),
+            },
+            &[tool_named("get_weather"), tool_named("get_time")],
+            Some(&json_object_markers()),
+        )?
+        else {
+            panic!("expected a GBNF grammar");
+        };
+
+        assert!(grammar.contains("get_time"));
+        assert!(!grammar.contains("get_weather"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn xml_tags_calls_list_the_parameters_of_the_schema() -> Result<()> {
+        let GrammarConstraint::Gbnf { grammar, .. } = grammar_for(
+            &ToolChoice::Required,
+            &[weather_tool(), tool_named("get_time")],
+            Some(&xml_tags_markers()),
+        )?
+        else {
+            panic!("expected a GBNF grammar");
+        };
+
+        assert!(grammar.contains(
+            "tool-0 ::= \"<function=get_weather>\" \"\\n\"? tool-0-parameter-0? tool-0-parameter-1 \"</function>\""
+        ));
+        assert!(grammar.contains(
+            "tool-0-parameter-0 ::= \"<parameter=days>\" \"\\n\"? tool-0-parameter-0-value-root"
+        ));
+        assert!(grammar.contains("tool-0-parameter-0-value-root ::= "));
+        assert!(grammar.contains(
+            "tool-0-parameter-1 ::= \"<parameter=location>\" \"\\n\"? xml-text \"\\n\"? \"</parameter>\" \"\\n\"?"
+        ));
+        assert!(grammar.contains(
+            "tool-1 ::= \"<function=get_time>\" \"\\n\"? tool-1-parameter* \"</function>\""
+        ));
+        assert!(grammar.contains("root ::= tool-0 | tool-1"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_a_named_choice_that_matches_no_tool() {
+        let result = grammar_for(
+            &ToolChoice::Function {
+                name: "get_time".to_owned(),
+            },
+            &[tool_named("get_weather")],
+            Some(&json_object_markers()),
+        );
+
+        assert!(matches!(
+            result,
+            Err(ForcedToolCallGrammarError::NoAllowedTool)
+        ));
+    }
+
+    #[test]
+    fn rejects_models_without_tool_call_markers() {
+        let result = grammar_for(&ToolChoice::Required, &[tool_named("get_weather")], None);
+
+        assert!(matches!(
+            result,
+            Err(ForcedToolCallGrammarError::NoToolCallFormat)
+        ));
+    }
+
+    #[test]
+    fn names_the_tool_call_format_it_cannot_force() {
+        let markers = ToolCallMarkers {
+            open: "<|tool_call>".to_owned(),
+            close: "<tool_call|>".to_owned(),
+            args_shape: ToolCallArgsShape::PairedQuote(PairedQuoteShape {
+                name_args_separator: "{".to_owned(),
+                value_quote: ToolCallValueQuote {
+                    open: "<|\"|>".to_owned(),
+                    close: "<|\"|>".to_owned(),
+                },
+            }),
+        };
+
+        let error = grammar_for(
+            &ToolChoice::Required,
+            &[tool_named("get_weather")],
+            Some(&markers),
+        )
+        .err()
+        .unwrap();
+
+        assert!(matches!(
+            error,
+            ForcedToolCallGrammarError::UnsupportedToolCallFormat { .. }
+        ));
+        assert!(
+            error
+                .to_string()
+                .contains("\"<|tool_call>\" tool calls in the paired quote format")
+        );
+    }
+}
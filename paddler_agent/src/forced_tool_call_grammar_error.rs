@@ -0,0 +1,15 @@
+#[derive(Debug, thiserror::Error)]
+pub enum ForcedToolCallGrammarError {
+    #[error("failed to convert the tool schemas to a grammar: {0}")]
+    GrammarConversionFailed(String),
+    #[error("tool_choice does not allow any of the offered tools")]
+    NoAllowedTool,
+    #[error(
+        "tool_choice cannot force a tool call: the model's chat template does not declare a tool call format"
+    )]
+    NoToolCallFormat,
+    #[error(
+        "tool_choice cannot force a tool call: the model's chat template emits {open:?} tool calls in the {format} format, and only the JSON object and XML tags formats can be forced"
+    )]
+    UnsupportedToolCallFormat { format: &'static str, open: String },
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use tokio::sync::mpsc;
 
+use crate::inference_request_trace::InferenceRequestTrace;
 use crate::slot_aggregated_status::SlotAggregatedStatus;
 use paddler_messaging::management_socket::agent::response::Response;
 
@@ -14,5 +15,6 @@ pub trait FromRequestParams: Send + Sync {
         response_tx: mpsc::UnboundedSender<Self::Response>,
         stop_rx: mpsc::UnboundedReceiver<()>,
         slot_aggregated_status: Arc<SlotAggregatedStatus>,
+        trace: InferenceRequestTrace,
     ) -> Self;
 }
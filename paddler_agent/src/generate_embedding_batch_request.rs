@@ -5,6 +5,7 @@ use paddler_messaging::request_params::generate_embedding_batch_params::Generate
 use tokio::sync::mpsc;
 
 use crate::from_request_params::FromRequestParams;
+use crate::inference_request_trace::InferenceRequestTrace;
 use crate::slot_aggregated_status::SlotAggregatedStatus;
 use crate::slot_guard::SlotGuard;
 
@@ -13,6 +14,7 @@ pub struct GenerateEmbeddingBatchRequest {
     pub generated_embedding_tx: mpsc::UnboundedSender<EmbeddingResult>,
     pub params: GenerateEmbeddingBatchParams,
     pub slot_guard: SlotGuard,
+    pub trace: InferenceRequestTrace,
 }
 
 impl FromRequestParams for GenerateEmbeddingBatchRequest {
@@ -24,12 +26,14 @@ impl FromRequestParams for GenerateEmbeddingBatchRequest {
         generated_embedding_tx: mpsc::UnboundedSender<Self::Response>,
         generate_embedding_stop_rx: mpsc::UnboundedReceiver<()>,
         slot_aggregated_status: Arc<SlotAggregatedStatus>,
+        trace: InferenceRequestTrace,
     ) -> Self {
         Self {
             generate_embedding_stop_rx,
             generated_embedding_tx,
             params,
             slot_guard: SlotGuard::new(slot_aggregated_status),
+            trace,
         }
     }
 }
@@ -0,0 +1,101 @@
+use paddler_messaging::trace_context::TraceContext;
+use paddler_tracing::span_guard::SpanGuard;
+use paddler_tracing::tracer::Tracer;
+
+/// Spans of a request inside the scheduler, attached to the trace the balancer sent it with.
+/// The span in progress ends when the request is dropped.
+pub struct InferenceRequestTrace {
+    current_span: Option<SpanGuard>,
+    decoding: bool,
+    request_id: String,
+    trace_context: Option<TraceContext>,
+    tracer: Tracer,
+}
+
+impl InferenceRequestTrace {
+    #[must_use]
+    pub const fn new(
+        request_id: String,
+        trace_context: Option<TraceContext>,
+        tracer: Tracer,
+    ) -> Self {
+        Self {
+            current_span: None,
+            decoding: false,
+            request_id,
+            trace_context,
+            tracer,
+        }
+    }
+
+    /// Ends the prefill span at the first sampled token, and starts the one lasting until the
+    /// request completes.
+    pub fn start_decode(&mut self) {
+        if self.decoding {
+            return;
+        }
+
+        self.decoding = true;
+        self.current_span = None;
+        self.current_span = Some(self.start_span("agent.decode"));
+    }
+
+    pub fn start_prefill(&mut self) {
+        self.current_span = Some(self.start_span("agent.prefill"));
+    }
+
+    fn start_span(&self, name: &'static str) -> SpanGuard {
+        let mut span = self.tracer.start_span(name, self.trace_context.as_ref());
+
+        span.set_attribute("request_id", self.request_id.clone());
+
+        span
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_tracing::tracer::FINISHED_SPAN_CHANNEL_CAPACITY;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn prefill_and_decode_follow_each_other_in_the_trace_of_the_request() {
+        let (finished_span_tx, mut finished_span_rx) =
+            mpsc::channel(FINISHED_SPAN_CHANNEL_CAPACITY);
+        let trace_context = TraceContext {
+            span_id: "00f067aa0ba902b7".to_owned(),
+            trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".to_owned(),
+        };
+        let mut trace = InferenceRequestTrace::new(
+            "req_1".to_owned(),
+            Some(trace_context.clone()),
+            Tracer::new(finished_span_tx),
+        );
+
+        trace.start_prefill();
+        trace.start_decode();
+
+        let prefill = finished_span_rx.try_recv().unwrap();
+
+        assert_eq!(prefill.name, "agent.prefill");
+        assert!(finished_span_rx.try_recv().is_err());
+
+        trace.start_decode();
+
+        assert!(finished_span_rx.try_recv().is_err());
+
+        drop(trace);
+
+        let decode = finished_span_rx.try_recv().unwrap();
+
+        assert_eq!(decode.name, "agent.decode");
+
+        for span in [prefill, decode] {
+            assert_eq!(span.trace_id, trace_context.trace_id);
+            assert_eq!(span.parent_span_id, Some(trace_context.span_id.clone()));
+            assert_eq!(span.attributes, vec![("request_id", "req_1".to_owned())]);
+        }
+    }
+}
@@ -4,6 +4,8 @@ pub mod agent_desired_state_converter;
 pub mod agent_issue_fix;
 pub mod agent_kv_cache_dtype;
 pub mod agent_pooling_type;
+pub mod cached_sequence_prefix;
+pub mod candidate_logprobs;
 pub mod chat_template_load_status;
 pub mod chat_template_renderer;
 pub mod continue_from_conversation_history_request;
@@ -29,11 +31,15 @@ pub mod desired_model_resolution;
 pub mod dispenses_slots;
 pub mod drain_in_flight_requests;
 pub mod embedding_input_tokenized;
+pub mod forced_tool_call_grammar;
+pub mod forced_tool_call_grammar_error;
 mod from_request_params;
 pub mod generate_embedding_batch_request;
 pub mod grammar_sampler;
+pub mod inference_request_trace;
 pub mod llamacpp_arbiter_service;
 pub mod management_socket_client_service;
+pub mod management_tls_configuration;
 pub mod model_metadata_holder;
 pub mod model_source;
 pub mod normalization;
@@ -53,12 +59,16 @@ pub mod sample_token_at_batch_index;
 pub mod sampling_outcome;
 pub mod send_generated_token_result_or_warn;
 pub mod send_startup_signal;
+pub mod sequence_choice;
 pub mod sequence_id_guard;
 pub mod sequence_id_pool;
+pub mod sequence_prefix_cache;
 pub mod slot_aggregated_status;
 pub mod slot_aggregated_status_download_progress;
 pub mod slot_aggregated_status_manager;
 pub mod slot_guard;
+pub mod stop_sequence_matcher;
+pub mod stop_sequence_scan;
 pub mod tool_call_buffer;
 pub mod tool_call_event;
 pub mod tool_call_pipeline;
@@ -210,10 +210,13 @@ impl Service for LlamaCppArbiterService {
                     }
                 }
                 _ = reconciled_state.changed() => {
-                    agent_applicable_state.clone_from(&reconciled_state.borrow_and_update());
+                    let desired_state_version = *reconciled_state.borrow_and_update();
+
+                    agent_applicable_state =
+                        agent_applicable_state_holder.get_agent_applicable_state();
                     slot_aggregated_status_manager
                         .slot_aggregated_status
-                        .set_state_application_status(AgentStateApplicationStatus::Fresh);
+                        .start_applying_desired_state(desired_state_version);
 
                     try_to_apply_state(
                         &shutdown,
@@ -14,12 +14,12 @@ use tokio::sync::mpsc;
 use tokio::time::Duration;
 use tokio::time::MissedTickBehavior;
 use tokio::time::interval;
-use tokio_tungstenite::connect_async;
+use tokio_tungstenite::Connector;
+use tokio_tungstenite::connect_async_tls_with_config;
 use tokio_tungstenite::tungstenite::protocol::Message;
 use tokio_util::sync::CancellationToken;
 use trzcina::Service;
 
-use paddler_messaging::agent_desired_state::AgentDesiredState;
 use paddler_messaging::jsonrpc::error::Error as JsonRpcError;
 use paddler_messaging::jsonrpc::error_envelope::ErrorEnvelope;
 use paddler_messaging::jsonrpc::request_envelope::RequestEnvelope;
@@ -30,6 +30,8 @@ use crate::continue_from_conversation_history_request::ContinueFromConversationH
 use crate::continue_from_raw_prompt_request::ContinueFromRawPromptRequest;
 use crate::from_request_params::FromRequestParams;
 use crate::generate_embedding_batch_request::GenerateEmbeddingBatchRequest;
+use crate::inference_request_trace::InferenceRequestTrace;
+use crate::management_tls_configuration::ManagementTlsConfiguration;
 use crate::model_metadata_holder::ModelMetadataHolder;
 use crate::receive_stream_stopper_collection::ReceiveStreamStopperCollection;
 use crate::slot_aggregated_status::SlotAggregatedStatus;
@@ -37,6 +39,7 @@ use paddler_messaging::management_socket::agent::message::Message as JsonRpcMess
 use paddler_messaging::management_socket::agent::notification::Notification as JsonRpcNotification;
 use paddler_messaging::management_socket::agent::request::Request as JsonRpcRequest;
 use paddler_messaging::management_socket::agent::response::Response as JsonRpcResponse;
+use paddler_messaging::management_socket::agent::notification_params::set_state_params::SetStateParams;
 use paddler_messaging::management_socket::agent::notification_params::version_params::VersionParams;
 use paddler_messaging::management_socket::balancer::message::Message as ManagementJsonRpcMessage;
 use paddler_messaging::management_socket::balancer::notification::Notification as ManagementJsonRpcNotification;
@@ -44,10 +47,12 @@ use paddler_messaging::management_socket::balancer::notification_params::registe
 use paddler_messaging::management_socket::balancer::notification_params::update_agent_status_params::UpdateAgentStatusParams;
 use paddler_messaging::produces_snapshot::ProducesSnapshot;
 use paddler_messaging::subscribes_to_updates::SubscribesToUpdates as _;
+use paddler_messaging::trace_context::TraceContext;
+use paddler_tracing::tracer::Tracer;
 
 struct IncomingMessageContext {
     agent_applicable_state_holder: Arc<AgentApplicableStateHolder>,
-    agent_desired_state_tx: mpsc::UnboundedSender<AgentDesiredState>,
+    agent_desired_state_tx: mpsc::UnboundedSender<SetStateParams>,
     connection_close: CancellationToken,
     continue_from_conversation_history_request_tx:
         mpsc::UnboundedSender<ContinueFromConversationHistoryRequest>,
@@ -57,20 +62,25 @@ struct IncomingMessageContext {
     receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
     message_tx: mpsc::UnboundedSender<ManagementJsonRpcMessage>,
     slot_aggregated_status: Arc<SlotAggregatedStatus>,
+    tracer: Tracer,
 }
 
 pub struct ManagementSocketClientService {
     pub agent_applicable_state_holder: Arc<AgentApplicableStateHolder>,
-    pub agent_desired_state_tx: mpsc::UnboundedSender<AgentDesiredState>,
+    pub agent_desired_state_tx: mpsc::UnboundedSender<SetStateParams>,
     pub continue_from_conversation_history_request_tx:
         mpsc::UnboundedSender<ContinueFromConversationHistoryRequest>,
     pub continue_from_raw_prompt_request_tx: mpsc::UnboundedSender<ContinueFromRawPromptRequest>,
     pub generate_embedding_batch_request_tx: mpsc::UnboundedSender<GenerateEmbeddingBatchRequest>,
+    pub join_token: Option<String>,
     pub model_metadata_holder: Arc<ModelMetadataHolder>,
+    pub model_pool: Option<String>,
     pub name: Option<String>,
     pub receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
     pub slot_aggregated_status: Arc<SlotAggregatedStatus>,
     pub socket_url: String,
+    pub tls_configuration: Option<ManagementTlsConfiguration>,
+    pub tracer: Tracer,
 }
 
 impl ManagementSocketClientService {
@@ -82,6 +92,8 @@ impl ManagementSocketClientService {
         receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
         request_tx: mpsc::UnboundedSender<TRequest>,
         slot_aggregated_status: Arc<SlotAggregatedStatus>,
+        trace_context: Option<TraceContext>,
+        tracer: &Tracer,
     ) -> Result<()>
     where
         TRequest::Response: Send,
@@ -98,6 +110,7 @@ impl ManagementSocketClientService {
             response_tx,
             stop_rx,
             slot_aggregated_status,
+            InferenceRequestTrace::new(id.clone(), trace_context, tracer.clone()),
         ))?;
 
         tokio::spawn(async move {
@@ -145,6 +158,7 @@ impl ManagementSocketClientService {
             model_metadata_holder,
             receive_stream_stopper_collection,
             slot_aggregated_status,
+            tracer,
         }: IncomingMessageContext,
         deserialized_message: JsonRpcMessage,
     ) -> Result<()> {
@@ -160,7 +174,7 @@ impl ManagementSocketClientService {
                 Ok(())
             }
             JsonRpcMessage::Notification(JsonRpcNotification::SetState(set_state_params)) => {
-                agent_desired_state_tx.send(set_state_params.desired_state)?;
+                agent_desired_state_tx.send(*set_state_params)?;
 
                 Ok(())
             }
@@ -192,6 +206,7 @@ impl ManagementSocketClientService {
                     JsonRpcRequest::ContinueFromConversationHistory(
                         continue_from_conversation_history_params,
                     ),
+                trace_context,
             }) => Self::generate_responses(
                 connection_close,
                 id,
@@ -200,10 +215,13 @@ impl ManagementSocketClientService {
                 receive_stream_stopper_collection,
                 continue_from_conversation_history_request_tx,
                 slot_aggregated_status,
+                trace_context,
+                &tracer,
             ),
             JsonRpcMessage::Request(RequestEnvelope {
                 id,
                 request: JsonRpcRequest::ContinueFromRawPrompt(generate_tokens_params),
+                trace_context,
             }) => Self::generate_responses(
                 connection_close,
                 id,
@@ -212,10 +230,13 @@ impl ManagementSocketClientService {
                 receive_stream_stopper_collection,
                 continue_from_raw_prompt_request_tx,
                 slot_aggregated_status,
+                trace_context,
+                &tracer,
             ),
             JsonRpcMessage::Request(RequestEnvelope {
                 id,
                 request: JsonRpcRequest::GenerateEmbeddingBatch(generate_embedding_batch_params),
+                trace_context,
             }) => Self::generate_responses(
                 connection_close,
                 id,
@@ -224,10 +245,13 @@ impl ManagementSocketClientService {
                 receive_stream_stopper_collection,
                 generate_embedding_batch_request_tx,
                 slot_aggregated_status,
+                trace_context,
+                &tracer,
             ),
             JsonRpcMessage::Request(RequestEnvelope {
                 id,
                 request: JsonRpcRequest::GetChatTemplateOverride,
+                trace_context: _,
             }) => Ok(
                 message_tx.send(ManagementJsonRpcMessage::Response(ResponseEnvelope {
                     generated_by: None,
@@ -246,6 +270,7 @@ impl ManagementSocketClientService {
             JsonRpcMessage::Request(RequestEnvelope {
                 id,
                 request: JsonRpcRequest::GetModelMetadata,
+                trace_context: _,
             }) => Ok(
                 message_tx.send(ManagementJsonRpcMessage::Response(ResponseEnvelope {
                     generated_by: None,
@@ -311,8 +336,24 @@ impl ManagementSocketClientService {
     async fn keep_connection_alive(&self, shutdown: CancellationToken) -> Result<()> {
         info!("Connecting to management server at {}", self.socket_url);
 
+        let connector = self
+            .tls_configuration
+            .as_ref()
+            .map(|tls_configuration| {
+                tls_configuration
+                    .load_client_config()
+                    .map(|client_config| Connector::Rustls(Arc::new(client_config)))
+            })
+            .transpose()
+            .context("Unable to load the management TLS configuration")?;
+
         let (ws_stream, _response) = match shutdown
-            .run_until_cancelled(connect_async(self.socket_url.clone()))
+            .run_until_cancelled(connect_async_tls_with_config(
+                self.socket_url.clone(),
+                None,
+                false,
+                connector,
+            ))
             .await
         {
             Some(connect_outcome) => connect_outcome?,
@@ -390,6 +431,8 @@ impl ManagementSocketClientService {
                 message_tx
                     .send(ManagementJsonRpcMessage::Notification(
                         ManagementJsonRpcNotification::RegisterAgent(RegisterAgentParams {
+                            join_token: self.join_token.clone(),
+                            model_pool: self.model_pool.clone(),
                             name: self.name.clone(),
                             slot_aggregated_status_snapshot,
                         }),
@@ -455,6 +498,7 @@ impl ManagementSocketClientService {
                                         receive_stream_stopper_collection: self.receive_stream_stopper_collection.clone(),
                                         message_tx: message_tx.clone(),
                                         slot_aggregated_status: self.slot_aggregated_status.clone(),
+                                        tracer: self.tracer.clone(),
                                     },
                                     msg,
                                     &pong_tx,
@@ -543,7 +587,7 @@ mod tests {
     use tokio_tungstenite::tungstenite::protocol::frame::coding::Data;
     use tokio_tungstenite::tungstenite::protocol::frame::coding::OpCode;
 
-    use paddler_messaging::management_socket::agent::notification_params::set_state_params::SetStateParams;
+    use paddler_messaging::agent_desired_state::AgentDesiredState;
     use paddler_messaging::model_metadata::ModelMetadata;
     use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 
@@ -564,11 +608,15 @@ mod tests {
             continue_from_conversation_history_request_tx,
             continue_from_raw_prompt_request_tx,
             generate_embedding_batch_request_tx,
+            join_token: None,
             model_metadata_holder: Arc::new(ModelMetadataHolder::new()),
+            model_pool: None,
             name: None,
             receive_stream_stopper_collection: Arc::new(ReceiveStreamStopperCollection::default()),
             slot_aggregated_status: Arc::new(SlotAggregatedStatus::new(2)),
             socket_url,
+            tls_configuration: None,
+            tracer: Tracer::default(),
         }
     }
 
@@ -752,7 +800,7 @@ mod tests {
 
     fn build_incoming_message_context(
         agent_applicable_state_holder: Arc<AgentApplicableStateHolder>,
-        agent_desired_state_tx: mpsc::UnboundedSender<AgentDesiredState>,
+        agent_desired_state_tx: mpsc::UnboundedSender<SetStateParams>,
         connection_close: CancellationToken,
         model_metadata_holder: Arc<ModelMetadataHolder>,
         receive_stream_stopper_collection: Arc<ReceiveStreamStopperCollection>,
@@ -784,7 +832,7 @@ mod tests {
     async fn error_message_is_acknowledged_without_side_effects() {
         let (message_tx, mut message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, mut agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -815,7 +863,7 @@ mod tests {
     async fn set_state_notification_forwards_desired_state() {
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, mut agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -830,12 +878,13 @@ mod tests {
             context,
             JsonRpcMessage::Notification(JsonRpcNotification::SetState(Box::new(SetStateParams {
                 desired_state: AgentDesiredState::default(),
+                desired_state_version: 1,
             }))),
         );
 
         assert!(result.is_ok());
         assert_eq!(
-            agent_desired_state_rx.try_recv().unwrap(),
+            agent_desired_state_rx.try_recv().unwrap().desired_state,
             AgentDesiredState::default()
         );
     }
@@ -844,7 +893,7 @@ mod tests {
     async fn set_state_notification_errors_when_receiver_dropped() {
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
 
         drop(agent_desired_state_rx);
 
@@ -862,6 +911,7 @@ mod tests {
             context,
             JsonRpcMessage::Notification(JsonRpcNotification::SetState(Box::new(SetStateParams {
                 desired_state: AgentDesiredState::default(),
+                desired_state_version: 1,
             }))),
         );
 
@@ -872,7 +922,7 @@ mod tests {
     async fn stop_responding_to_a_finished_request_is_not_an_error_and_retains_nothing() {
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let receive_stream_stopper_collection = Arc::new(ReceiveStreamStopperCollection::default());
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
@@ -905,7 +955,7 @@ mod tests {
     async fn stop_responding_to_registered_request_signals_stopper() {
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let receive_stream_stopper_collection = Arc::new(ReceiveStreamStopperCollection::default());
         let (stop_tx, mut stop_rx) = mpsc::unbounded_channel::<()>();
 
@@ -938,7 +988,7 @@ mod tests {
     async fn mismatched_version_notification_is_acknowledged() {
         let (message_tx, mut message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -964,7 +1014,7 @@ mod tests {
     async fn get_chat_template_override_without_applicable_state_responds_with_none() {
         let (message_tx, mut message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -980,6 +1030,7 @@ mod tests {
             JsonRpcMessage::Request(RequestEnvelope {
                 id: "req_template".to_owned(),
                 request: JsonRpcRequest::GetChatTemplateOverride,
+                trace_context: None,
             }),
         );
 
@@ -1001,7 +1052,7 @@ mod tests {
     async fn get_chat_template_override_errors_when_message_receiver_dropped() {
         let (message_tx, message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
 
         drop(message_rx);
 
@@ -1020,6 +1071,7 @@ mod tests {
             JsonRpcMessage::Request(RequestEnvelope {
                 id: "req_template".to_owned(),
                 request: JsonRpcRequest::GetChatTemplateOverride,
+                trace_context: None,
             }),
         );
 
@@ -1030,7 +1082,7 @@ mod tests {
     async fn get_model_metadata_responds_with_stored_metadata() {
         let (message_tx, mut message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let model_metadata_holder = Arc::new(ModelMetadataHolder::new());
         let mut metadata = BTreeMap::new();
 
@@ -1054,6 +1106,7 @@ mod tests {
             JsonRpcMessage::Request(RequestEnvelope {
                 id: "req_metadata".to_owned(),
                 request: JsonRpcRequest::GetModelMetadata,
+                trace_context: None,
             }),
         );
 
@@ -1074,7 +1127,7 @@ mod tests {
     async fn get_model_metadata_errors_when_message_receiver_dropped() {
         let (message_tx, message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
 
         drop(message_rx);
 
@@ -1093,6 +1146,7 @@ mod tests {
             JsonRpcMessage::Request(RequestEnvelope {
                 id: "req_metadata".to_owned(),
                 request: JsonRpcRequest::GetModelMetadata,
+                trace_context: None,
             }),
         );
 
@@ -1104,7 +1158,7 @@ mod tests {
         let (pong_tx, mut pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -1130,7 +1184,7 @@ mod tests {
         let (pong_tx, mut pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -1156,7 +1210,7 @@ mod tests {
         let (pong_tx, mut pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -1186,7 +1240,7 @@ mod tests {
         let (pong_tx, mut pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -1215,7 +1269,7 @@ mod tests {
         let (pong_tx, pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
 
         drop(pong_rx);
 
@@ -1243,7 +1297,7 @@ mod tests {
         let (pong_tx, mut pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, _agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -1269,7 +1323,7 @@ mod tests {
         let (pong_tx, _pong_rx) = mpsc::unbounded_channel::<Bytes>();
         let (message_tx, _message_rx) = mpsc::unbounded_channel::<ManagementJsonRpcMessage>();
         let (agent_desired_state_tx, mut agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let context = build_incoming_message_context(
             Arc::new(AgentApplicableStateHolder::default()),
             agent_desired_state_tx,
@@ -1283,6 +1337,7 @@ mod tests {
         let serialized_set_state = serde_json::to_string(&JsonRpcMessage::Notification(
             JsonRpcNotification::SetState(Box::new(SetStateParams {
                 desired_state: AgentDesiredState::default(),
+                desired_state_version: 7,
             })),
         ))
         .unwrap();
@@ -1293,11 +1348,11 @@ mod tests {
             &pong_tx,
         );
 
+        let set_state_params = agent_desired_state_rx.recv().await.unwrap();
+
         assert!(result.is_ok());
-        assert_eq!(
-            agent_desired_state_rx.recv().await.unwrap(),
-            AgentDesiredState::default()
-        );
+        assert_eq!(set_state_params.desired_state, AgentDesiredState::default());
+        assert_eq!(set_state_params.desired_state_version, 7);
     }
 
     #[tokio::test]
@@ -1315,12 +1370,19 @@ mod tests {
             message_tx,
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 8,
+                model: None,
                 raw_prompt: "hello".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
             receive_stream_stopper_collection.clone(),
             request_tx,
             slot_aggregated_status,
+            None,
+            &Tracer::default(),
         )
         .expect("the request must be accepted");
 
@@ -1354,12 +1416,19 @@ mod tests {
                 message_tx,
                 ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 8,
+                    model: None,
                     raw_prompt: "hello".to_owned(),
+                    sampling: None,
+                    session_key: None,
+                    stop: Vec::new(),
                 },
                 receive_stream_stopper_collection,
                 request_tx,
                 slot_aggregated_status,
+                None,
+                &Tracer::default(),
             );
 
         assert!(result.is_err());
@@ -1385,12 +1454,19 @@ mod tests {
                 message_tx,
                 ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 8,
+                    model: None,
                     raw_prompt: "hello".to_owned(),
+                    sampling: None,
+                    session_key: None,
+                    stop: Vec::new(),
                 },
                 receive_stream_stopper_collection,
                 request_tx,
                 slot_aggregated_status,
+                None,
+                &Tracer::default(),
             );
 
         assert!(result.is_err());
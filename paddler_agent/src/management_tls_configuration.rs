@@ -0,0 +1,177 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use log::warn;
+use rustls::ClientConfig;
+use rustls::RootCertStore;
+use rustls::crypto::ring::default_provider;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::pem::PemObject as _;
+use rustls_native_certs::load_native_certs;
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let certificates: Vec<CertificateDer<'static>> = CertificateDer::pem_file_iter(path)
+        .and_then(Iterator::collect)
+        .with_context(|| format!("Unable to read certificates from {}", path.display()))?;
+
+    if certificates.is_empty() {
+        bail!("No certificates found in {}", path.display());
+    }
+
+    Ok(certificates)
+}
+
+/// How the agent connects to a balancer whose management service is served over TLS (`wss://`).
+#[derive(Clone, Debug, Default)]
+pub struct ManagementTlsConfiguration {
+    /// Trust only this CA instead of the system trust store.
+    pub ca_certificate_path: Option<PathBuf>,
+    /// Certificate presented to balancers that require agents to authenticate with one.
+    pub client_certificate_path: Option<PathBuf>,
+    pub client_private_key_path: Option<PathBuf>,
+}
+
+impl ManagementTlsConfiguration {
+    fn load_root_certificates(&self) -> Result<RootCertStore> {
+        let mut root_certificates = RootCertStore::empty();
+
+        if let Some(ca_certificate_path) = &self.ca_certificate_path {
+            for certificate in load_certificates(ca_certificate_path)? {
+                root_certificates.add(certificate).with_context(|| {
+                    format!(
+                        "Unable to trust the CA from {}",
+                        ca_certificate_path.display()
+                    )
+                })?;
+            }
+        } else {
+            let native_certificates = load_native_certs();
+
+            for error in native_certificates.errors {
+                warn!("Unable to load a certificate from the system trust store: {error}");
+            }
+
+            let (added, ignored) =
+                root_certificates.add_parsable_certificates(native_certificates.certs);
+
+            if added == 0 {
+                bail!("No usable certificates found in the system trust store ({ignored} ignored)");
+            }
+        }
+
+        Ok(root_certificates)
+    }
+
+    pub fn load_client_config(&self) -> Result<ClientConfig> {
+        let client_config_builder =
+            ClientConfig::builder_with_provider(Arc::new(default_provider()))
+                .with_safe_default_protocol_versions()
+                .context("Unable to select TLS protocol versions")?
+                .with_root_certificates(self.load_root_certificates()?);
+
+        match (&self.client_certificate_path, &self.client_private_key_path) {
+            (Some(client_certificate_path), Some(client_private_key_path)) => {
+                let client_private_key = PrivateKeyDer::from_pem_file(client_private_key_path)
+                    .with_context(|| {
+                        format!(
+                            "Unable to read the client private key from {}",
+                            client_private_key_path.display()
+                        )
+                    })?;
+
+                client_config_builder
+                    .with_client_auth_cert(
+                        load_certificates(client_certificate_path)?,
+                        client_private_key,
+                    )
+                    .context("The client certificate does not match its private key")
+            }
+            (None, None) => Ok(client_config_builder.with_no_client_auth()),
+            _ => bail!("The client certificate and its private key must be provided together"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use rcgen::CertifiedKey;
+    use rcgen::generate_simple_self_signed;
+    use tempfile::TempDir;
+
+    use super::ManagementTlsConfiguration;
+
+    fn write_self_signed_certificate(temp_dir: &TempDir, name: &str) -> (PathBuf, PathBuf) {
+        let CertifiedKey { cert, signing_key } =
+            generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+        let certificate_path = temp_dir.path().join(format!("{name}.crt"));
+        let private_key_path = temp_dir.path().join(format!("{name}.key"));
+
+        fs::write(&certificate_path, cert.pem()).unwrap();
+        fs::write(&private_key_path, signing_key.serialize_pem()).unwrap();
+
+        (certificate_path, private_key_path)
+    }
+
+    #[test]
+    fn loads_a_client_config_trusting_a_custom_ca_with_a_client_certificate() {
+        let temp_dir = TempDir::new().unwrap();
+        let (ca_certificate_path, _ca_private_key_path) =
+            write_self_signed_certificate(&temp_dir, "ca");
+        let (client_certificate_path, client_private_key_path) =
+            write_self_signed_certificate(&temp_dir, "agent");
+
+        let client_config = ManagementTlsConfiguration {
+            ca_certificate_path: Some(ca_certificate_path),
+            client_certificate_path: Some(client_certificate_path),
+            client_private_key_path: Some(client_private_key_path),
+        }
+        .load_client_config()
+        .unwrap();
+
+        assert!(client_config.client_auth_cert_resolver.has_certs());
+    }
+
+    #[test]
+    fn loads_a_client_config_without_a_client_certificate() {
+        let temp_dir = TempDir::new().unwrap();
+        let (ca_certificate_path, _ca_private_key_path) =
+            write_self_signed_certificate(&temp_dir, "ca");
+
+        let client_config = ManagementTlsConfiguration {
+            ca_certificate_path: Some(ca_certificate_path),
+            client_certificate_path: None,
+            client_private_key_path: None,
+        }
+        .load_client_config()
+        .unwrap();
+
+        assert!(!client_config.client_auth_cert_resolver.has_certs());
+    }
+
+    #[test]
+    fn rejects_a_client_certificate_without_its_private_key() {
+        let temp_dir = TempDir::new().unwrap();
+        let (ca_certificate_path, _ca_private_key_path) =
+            write_self_signed_certificate(&temp_dir, "ca");
+        let (client_certificate_path, _client_private_key_path) =
+            write_self_signed_certificate(&temp_dir, "agent");
+
+        let error = ManagementTlsConfiguration {
+            ca_certificate_path: Some(ca_certificate_path),
+            client_certificate_path: Some(client_certificate_path),
+            client_private_key_path: None,
+        }
+        .load_client_config()
+        .unwrap_err();
+
+        assert!(error.to_string().contains("must be provided together"));
+    }
+}
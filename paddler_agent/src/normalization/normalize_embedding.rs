@@ -41,6 +41,7 @@ pub fn normalize_embedding(
         normalization_method: normalization_method.clone(),
         pooling_type: embedding.pooling_type,
         source_document_id: embedding.source_document_id,
+        token_count: embedding.token_count,
     })
 }
 
@@ -58,6 +59,7 @@ mod tests {
             normalization_method: method,
             pooling_type: PoolingType::Mean,
             source_document_id: "test".to_owned(),
+            token_count: 0,
         }
     }
 
@@ -149,6 +151,7 @@ mod tests {
             normalization_method: EmbeddingNormalizationMethod::None,
             pooling_type: PoolingType::Cls,
             source_document_id: "doc-42".to_owned(),
+            token_count: 0,
         };
         let result = normalize_embedding(embedding, &EmbeddingNormalizationMethod::L2).unwrap();
 
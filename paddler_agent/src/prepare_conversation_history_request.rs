@@ -6,18 +6,24 @@ use llama_cpp_bindings::mtmd::mtmd_default_marker;
 use log::error;
 use minijinja::context;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::grammar_constraint::GrammarConstraint;
 use paddler_messaging::media_marker::MediaMarker;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool::Tool;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use tokio::sync::mpsc;
 
 use crate::chat_template_renderer::ChatTemplateRenderer;
 use crate::continuous_batch_scheduler_context::ContinuousBatchSchedulerContext;
 use crate::decoded_image::DecodedImage;
 use crate::decoded_image_error::DecodedImageError;
+use crate::forced_tool_call_grammar::forced_tool_call_grammar;
+use crate::forced_tool_call_grammar_error::ForcedToolCallGrammarError;
 use crate::prepared_conversation_history_request::PreparedConversationHistoryRequest;
 use crate::resolve_grammar::resolve_grammar;
 use crate::send_generated_token_result_or_warn::send_generated_token_result_or_warn;
+use crate::tool_call_validator::ToolCallValidator;
 
 fn require_renderer_for_generation(
     chat_template_renderer: Option<&Arc<ChatTemplateRenderer>>,
@@ -44,19 +50,124 @@ fn require_renderer_for_generation(
     )
 }
 
+fn reject_forced_tool_call(
+    result: GeneratedTokenResult,
+    message: String,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> anyhow::Error {
+    error!("{:?}: {message}", scheduler_context.agent_name);
+
+    send_generated_token_result_or_warn(
+        scheduler_context.agent_name.as_deref(),
+        generated_tokens_tx,
+        result,
+    );
+
+    anyhow!(message)
+}
+
+fn build_tool_call_validator(
+    tools: &[Tool<ValidatedParametersSchema>],
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> Result<ToolCallValidator> {
+    ToolCallValidator::from_tools(tools).map_err(|err| {
+        let message = err.to_string();
+
+        reject_forced_tool_call(
+            GeneratedTokenResult::ToolSchemaInvalid(message.clone()),
+            message,
+            generated_tokens_tx,
+            scheduler_context,
+        )
+    })
+}
+
+fn build_forced_tool_call_grammar(
+    tool_choice: &ToolChoice,
+    tools: &[Tool<ValidatedParametersSchema>],
+    tool_call_validator: &ToolCallValidator,
+    generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
+    scheduler_context: &ContinuousBatchSchedulerContext,
+) -> Result<GrammarConstraint> {
+    let tool_call_markers = scheduler_context.model.tool_call_markers().map_err(|err| {
+        let message = format!("failed to read the tool call format of the chat template: {err}");
+
+        reject_forced_tool_call(
+            GeneratedTokenResult::GrammarSyntaxError(message.clone()),
+            message,
+            generated_tokens_tx,
+            scheduler_context,
+        )
+    })?;
+
+    forced_tool_call_grammar(
+        tool_choice,
+        tools,
+        tool_call_validator,
+        tool_call_markers.as_ref(),
+    )
+    .map_err(|err| {
+        let message = err.to_string();
+        let result = match err {
+            ForcedToolCallGrammarError::GrammarConversionFailed(_) => {
+                GeneratedTokenResult::GrammarSyntaxError(message.clone())
+            }
+            ForcedToolCallGrammarError::NoAllowedTool
+            | ForcedToolCallGrammarError::NoToolCallFormat
+            | ForcedToolCallGrammarError::UnsupportedToolCallFormat { .. } => {
+                GeneratedTokenResult::ToolChoiceNotSupported(message.clone())
+            }
+        };
+
+        reject_forced_tool_call(result, message, generated_tokens_tx, scheduler_context)
+    })
+}
+
 pub fn prepare_conversation_history_request(
     ContinueFromConversationHistoryParams {
         add_generation_prompt,
         enable_thinking,
         grammar,
         conversation_history,
+        logprobs,
         max_tokens,
+        model: _,
         parse_tool_calls,
+        sampling,
+        session_key: _,
+        stop,
+        tool_choice,
         tools,
     }: ContinueFromConversationHistoryParams<ValidatedParametersSchema>,
     generated_tokens_tx: &mpsc::UnboundedSender<GeneratedTokenResult>,
     scheduler_context: &ContinuousBatchSchedulerContext,
 ) -> Result<PreparedConversationHistoryRequest> {
+    let (parse_tool_calls, tools) = if tool_choice == ToolChoice::None {
+        (false, Vec::new())
+    } else {
+        (parse_tool_calls, tools)
+    };
+    let tool_call_validator = if tool_choice.forces_tool_call() {
+        Some(build_tool_call_validator(
+            &tools,
+            generated_tokens_tx,
+            scheduler_context,
+        )?)
+    } else {
+        None
+    };
+    let grammar = match &tool_call_validator {
+        Some(tool_call_validator) => Some(build_forced_tool_call_grammar(
+            &tool_choice,
+            &tools,
+            tool_call_validator,
+            generated_tokens_tx,
+            scheduler_context,
+        )?),
+        None => grammar,
+    };
     let grammar_sampler = resolve_grammar(grammar.as_ref(), enable_thinking, generated_tokens_tx)?;
 
     let image_resize_to_fit = scheduler_context.inference_parameters.image_resize_to_fit;
@@ -148,7 +259,11 @@ pub fn prepare_conversation_history_request(
             images,
             max_tokens,
             grammar_sampler,
+            logprobs,
+            sampling,
+            stop,
             parse_tool_calls,
+            tool_call_validator,
             tools,
         });
     }
@@ -157,7 +272,11 @@ pub fn prepare_conversation_history_request(
         raw_prompt,
         max_tokens,
         grammar_sampler,
+        logprobs,
+        sampling,
+        stop,
         parse_tool_calls,
+        tool_call_validator,
         tools,
     })
 }
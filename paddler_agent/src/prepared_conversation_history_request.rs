@@ -1,15 +1,22 @@
+use paddler_messaging::logprobs_request::LogprobsRequest;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::Tool;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_messaging::sampling_overrides::SamplingOverrides;
 
 use crate::decoded_image::DecodedImage;
 use crate::grammar_sampler::GrammarSampler;
+use crate::tool_call_validator::ToolCallValidator;
 
 pub enum PreparedConversationHistoryRequest {
     TextPrompt {
         raw_prompt: String,
         max_tokens: i32,
         grammar_sampler: Option<GrammarSampler>,
+        logprobs: Option<LogprobsRequest>,
+        sampling: Option<SamplingOverrides>,
+        stop: Vec<String>,
         parse_tool_calls: bool,
+        tool_call_validator: Option<ToolCallValidator>,
         tools: Vec<Tool<ValidatedParametersSchema>>,
     },
     MultimodalPrompt {
@@ -17,7 +24,11 @@ pub enum PreparedConversationHistoryRequest {
         images: Vec<DecodedImage>,
         max_tokens: i32,
         grammar_sampler: Option<GrammarSampler>,
+        logprobs: Option<LogprobsRequest>,
+        sampling: Option<SamplingOverrides>,
+        stop: Vec<String>,
         parse_tool_calls: bool,
+        tool_call_validator: Option<ToolCallValidator>,
         tools: Vec<Tool<ValidatedParametersSchema>>,
     },
 }
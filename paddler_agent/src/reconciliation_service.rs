@@ -4,6 +4,7 @@ use anyhow::Result;
 use async_trait::async_trait;
 use log::error;
 use paddler_messaging::agent_desired_state::AgentDesiredState;
+use paddler_messaging::management_socket::agent::notification_params::set_state_params::SetStateParams;
 use tokio::sync::mpsc;
 use tokio::time::Duration;
 use tokio::time::MissedTickBehavior;
@@ -20,6 +21,7 @@ use paddler_state_conversion::converts_to_applicable_state::ConvertsToApplicable
 async fn convert_to_applicable_state(
     cancellation_token: &CancellationToken,
     agent_desired_state: Option<&AgentDesiredState>,
+    desired_state_version: u64,
     slot_aggregated_status: &Arc<SlotAggregatedStatus>,
     agent_applicable_state_holder: &AgentApplicableStateHolder,
     is_converted_to_applicable_state: &mut bool,
@@ -42,7 +44,8 @@ async fn convert_to_applicable_state(
             .is_some_and(|applicable_state| applicable_state.chat_template_override.is_some()),
     );
     slot_aggregated_status.register_fix(&AgentIssueFix::ModelStateIsReconciled);
-    agent_applicable_state_holder.set_agent_applicable_state(applicable_state)?;
+    agent_applicable_state_holder
+        .set_agent_applicable_state(applicable_state, desired_state_version)?;
     *is_converted_to_applicable_state = true;
 
     Ok(())
@@ -51,6 +54,7 @@ async fn convert_to_applicable_state(
 async fn try_convert_to_applicable_state(
     cancellation_token: &CancellationToken,
     agent_desired_state: Option<&AgentDesiredState>,
+    desired_state_version: u64,
     slot_aggregated_status: &Arc<SlotAggregatedStatus>,
     agent_applicable_state_holder: &AgentApplicableStateHolder,
     is_converted_to_applicable_state: &mut bool,
@@ -58,6 +62,7 @@ async fn try_convert_to_applicable_state(
     if let Err(err) = convert_to_applicable_state(
         cancellation_token,
         agent_desired_state,
+        desired_state_version,
         slot_aggregated_status,
         agent_applicable_state_holder,
         is_converted_to_applicable_state,
@@ -71,7 +76,8 @@ async fn try_convert_to_applicable_state(
 pub struct ReconciliationService {
     pub agent_applicable_state_holder: Arc<AgentApplicableStateHolder>,
     pub agent_desired_state: Option<AgentDesiredState>,
-    pub agent_desired_state_rx: mpsc::UnboundedReceiver<AgentDesiredState>,
+    pub agent_desired_state_rx: mpsc::UnboundedReceiver<SetStateParams>,
+    pub desired_state_version: u64,
     pub is_converted_to_applicable_state: bool,
     pub slot_aggregated_status: Arc<SlotAggregatedStatus>,
 }
@@ -87,6 +93,7 @@ impl Service for ReconciliationService {
             agent_applicable_state_holder,
             mut agent_desired_state,
             mut agent_desired_state_rx,
+            mut desired_state_version,
             mut is_converted_to_applicable_state,
             slot_aggregated_status,
         } = *self;
@@ -103,23 +110,31 @@ impl Service for ReconciliationService {
                         try_convert_to_applicable_state(
                             &shutdown,
                             agent_desired_state.as_ref(),
+                            desired_state_version,
                             &slot_aggregated_status,
                             &agent_applicable_state_holder,
                             &mut is_converted_to_applicable_state,
                         ).await;
                     }
                 },
-                next_agent_desired_state = agent_desired_state_rx.recv() => {
+                next_set_state_params = agent_desired_state_rx.recv() => {
                     is_converted_to_applicable_state = false;
-                    agent_desired_state = if let Some(next) = next_agent_desired_state {
-                        Some(next)
-                    } else {
+
+                    let Some(SetStateParams {
+                        desired_state,
+                        desired_state_version: next_desired_state_version,
+                    }) = next_set_state_params else {
                         error!("Agent desired state channel closed, stopping reconciliation service.");
                         break Ok(())
                     };
+
+                    agent_desired_state = Some(desired_state);
+                    desired_state_version = next_desired_state_version;
+                    slot_aggregated_status.receive_desired_state(desired_state_version);
                     try_convert_to_applicable_state(
                         &shutdown,
                         agent_desired_state.as_ref(),
+                        desired_state_version,
                         &slot_aggregated_status,
                         &agent_applicable_state_holder,
                         &mut is_converted_to_applicable_state,
@@ -159,6 +174,7 @@ mod tests {
         try_convert_to_applicable_state(
             &cancellation_token,
             Some(&desired_state),
+            1,
             &slot_aggregated_status,
             &agent_applicable_state_holder,
             &mut is_converted_to_applicable_state,
@@ -177,6 +193,7 @@ mod tests {
         let result = convert_to_applicable_state(
             &CancellationToken::new(),
             None,
+            1,
             &slot_aggregated_status,
             &holder,
             &mut is_converted_to_applicable_state,
@@ -3,6 +3,7 @@ use anyhow::Result;
 use llama_cpp_bindings::context::LlamaContext;
 use llama_cpp_bindings::sampling::LlamaSampler;
 
+use crate::candidate_logprobs::CandidateLogprobs;
 use crate::sampling_outcome::SamplingOutcome;
 
 pub fn sample_token_at_batch_index(
@@ -10,6 +11,7 @@ pub fn sample_token_at_batch_index(
     batch_index: i32,
     chain: &mut LlamaSampler,
     grammar_sampler: &mut Option<LlamaSampler>,
+    top_logprobs: Option<u32>,
 ) -> Result<SamplingOutcome> {
     let mut token_data_array = llama_context
         .token_data_array_ith(batch_index)
@@ -29,6 +31,12 @@ pub fn sample_token_at_batch_index(
         return Ok(SamplingOutcome::AllCandidatesEliminated);
     };
 
+    let candidate_logprobs = top_logprobs
+        .map(|top_logprobs| {
+            CandidateLogprobs::from_candidates(&token_data_array.data, llama_token, top_logprobs)
+        })
+        .transpose()?;
+
     chain
         .accept(llama_token)
         .context("sampler chain failed to accept the selected token")?;
@@ -39,5 +47,8 @@ pub fn sample_token_at_batch_index(
         return Ok(SamplingOutcome::GrammarRejectedModelOutput(err.to_string()));
     }
 
-    Ok(SamplingOutcome::Token(llama_token))
+    Ok(SamplingOutcome::Token {
+        candidate_logprobs,
+        token: llama_token,
+    })
 }
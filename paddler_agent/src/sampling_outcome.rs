@@ -1,8 +1,13 @@
 use llama_cpp_bindings::token::LlamaToken;
 
+use crate::candidate_logprobs::CandidateLogprobs;
+
 #[derive(Debug)]
 pub enum SamplingOutcome {
     AllCandidatesEliminated,
     GrammarRejectedModelOutput(String),
-    Token(LlamaToken),
+    Token {
+        candidate_logprobs: Option<CandidateLogprobs>,
+        token: LlamaToken,
+    },
 }
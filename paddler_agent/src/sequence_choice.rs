@@ -0,0 +1,6 @@
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SequenceChoice {
+    /// Leading prompt tokens whose KV cells are already present on the sequence.
+    pub reusable_prefix_tokens: usize,
+    pub sequence_id: i32,
+}
@@ -14,6 +14,16 @@ impl SequenceIdGuard {
         })
     }
 
+    #[must_use]
+    pub fn acquire_id(sequence_id_pool: &SequenceIdPool, sequence_id: i32) -> Option<Self> {
+        sequence_id_pool
+            .acquire_id(sequence_id)
+            .map(|sequence_id| Self {
+                sequence_id,
+                sequence_id_pool: sequence_id_pool.clone(),
+            })
+    }
+
     #[must_use]
     pub const fn sequence_id(&self) -> i32 {
         self.sequence_id
@@ -50,6 +60,16 @@ mod tests {
         assert!(SequenceIdGuard::acquire(&sequence_id_pool).is_none());
     }
 
+    #[test]
+    fn acquire_id_takes_the_requested_sequence_id_from_the_pool() {
+        let sequence_id_pool = SequenceIdPool::new(2);
+
+        let guard = SequenceIdGuard::acquire_id(&sequence_id_pool, 1).unwrap();
+
+        assert_eq!(guard.sequence_id(), 1);
+        assert_eq!(sequence_id_pool.available_ids(), vec![0]);
+    }
+
     #[test]
     fn dropping_an_uncommitted_guard_releases_the_sequence_id() {
         let sequence_id_pool = SequenceIdPool::new(1);
@@ -19,6 +19,20 @@ impl SequenceIdPool {
         self.available_ids.borrow_mut().pop()
     }
 
+    /// Takes a specific sequence id out of the pool, if it is still available.
+    #[must_use]
+    pub fn acquire_id(&self, sequence_id: i32) -> Option<i32> {
+        let mut available_ids = self.available_ids.borrow_mut();
+        let position = available_ids.iter().position(|id| *id == sequence_id)?;
+
+        Some(available_ids.remove(position))
+    }
+
+    #[must_use]
+    pub fn available_ids(&self) -> Vec<i32> {
+        self.available_ids.borrow().clone()
+    }
+
     pub fn release(&self, sequence_id: i32) {
         self.available_ids.borrow_mut().push(sequence_id);
     }
@@ -65,6 +79,15 @@ mod tests {
         assert_eq!(pool.acquire(), None);
     }
 
+    #[test]
+    fn acquire_id_takes_the_requested_id() {
+        let pool = SequenceIdPool::new(3);
+
+        assert_eq!(pool.acquire_id(2), Some(2));
+        assert_eq!(pool.acquire_id(2), None);
+        assert_eq!(pool.available_ids(), vec![1, 0]);
+    }
+
     #[test]
     fn available_count_tracks_pool_size() {
         let pool = SequenceIdPool::new(3);
@@ -0,0 +1,274 @@
+use std::collections::BTreeMap;
+
+use llama_cpp_bindings::token::LlamaToken;
+
+use crate::cached_sequence_prefix::CachedSequencePrefix;
+use crate::sequence_choice::SequenceChoice;
+
+/// Shorter matches are not worth giving up another cached prefix for.
+pub const MIN_REUSABLE_PREFIX_TOKENS: usize = 16;
+
+/// Remembers which tokens are still in the KV cache of idle sequences, so a new request
+/// sharing a prefix with a finished one can skip re-ingesting it.
+#[derive(Default)]
+pub struct SequencePrefixCache {
+    clock: u64,
+    prefixes: BTreeMap<i32, CachedSequencePrefix>,
+}
+
+impl SequencePrefixCache {
+    /// Picks the idle sequence a new request should run on: the one sharing the longest
+    /// prefix with the prompt, otherwise one without cached tokens, otherwise the least
+    /// recently used one.
+    #[must_use]
+    pub fn choose_sequence(
+        &self,
+        prompt_tokens: &[LlamaToken],
+        available_ids: &[i32],
+    ) -> Option<SequenceChoice> {
+        let longest_match = available_ids
+            .iter()
+            .filter_map(|sequence_id| {
+                self.prefixes.get(sequence_id).map(|cached_prefix| {
+                    (
+                        common_prefix_length(&cached_prefix.tokens, prompt_tokens),
+                        cached_prefix.last_used,
+                        *sequence_id,
+                    )
+                })
+            })
+            .max();
+
+        if let Some((matched_tokens, _, sequence_id)) = longest_match
+            && matched_tokens >= MIN_REUSABLE_PREFIX_TOKENS
+        {
+            return Some(SequenceChoice {
+                // The last prompt token is always decoded again to produce fresh logits.
+                reusable_prefix_tokens: matched_tokens.min(prompt_tokens.len().saturating_sub(1)),
+                sequence_id,
+            });
+        }
+
+        available_ids
+            .iter()
+            .rev()
+            .find(|sequence_id| !self.prefixes.contains_key(sequence_id))
+            .copied()
+            .or_else(|| {
+                available_ids
+                    .iter()
+                    .min_by_key(|sequence_id| {
+                        self.prefixes
+                            .get(sequence_id)
+                            .map_or(0, |cached_prefix| cached_prefix.last_used)
+                    })
+                    .copied()
+            })
+            .map(|sequence_id| SequenceChoice {
+                reusable_prefix_tokens: 0,
+                sequence_id,
+            })
+    }
+
+    pub fn clear(&mut self) {
+        self.prefixes.clear();
+    }
+
+    /// Drops the least recently used prefix and returns the sequence whose KV cells
+    /// should now be freed.
+    pub fn evict_least_recently_used(&mut self) -> Option<i32> {
+        let sequence_id = self
+            .prefixes
+            .iter()
+            .min_by_key(|(_, cached_prefix)| cached_prefix.last_used)
+            .map(|(sequence_id, _)| *sequence_id)?;
+
+        self.prefixes.remove(&sequence_id);
+
+        Some(sequence_id)
+    }
+
+    pub fn forget(&mut self, sequence_id: i32) {
+        self.prefixes.remove(&sequence_id);
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.prefixes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    pub fn remember(&mut self, sequence_id: i32, tokens: Vec<LlamaToken>) {
+        self.clock += 1;
+        self.prefixes.insert(
+            sequence_id,
+            CachedSequencePrefix {
+                last_used: self.clock,
+                tokens,
+            },
+        );
+    }
+}
+
+fn common_prefix_length(cached_tokens: &[LlamaToken], prompt_tokens: &[LlamaToken]) -> usize {
+    cached_tokens
+        .iter()
+        .zip(prompt_tokens)
+        .take_while(|(cached_token, prompt_token)| cached_token == prompt_token)
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use llama_cpp_bindings::token::LlamaToken;
+
+    use super::MIN_REUSABLE_PREFIX_TOKENS;
+    use super::SequencePrefixCache;
+    use crate::sequence_choice::SequenceChoice;
+
+    fn tokens(ids: impl IntoIterator<Item = i32>) -> Vec<LlamaToken> {
+        ids.into_iter().map(LlamaToken::new).collect()
+    }
+
+    fn system_prompt() -> Vec<LlamaToken> {
+        tokens(0..100)
+    }
+
+    #[test]
+    fn an_empty_cache_picks_the_sequence_the_pool_would_hand_out_next() {
+        let cache = SequencePrefixCache::default();
+
+        assert_eq!(
+            cache.choose_sequence(&system_prompt(), &[2, 1, 0]),
+            Some(SequenceChoice {
+                reusable_prefix_tokens: 0,
+                sequence_id: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn no_available_sequence_means_no_choice() {
+        let cache = SequencePrefixCache::default();
+
+        assert_eq!(cache.choose_sequence(&system_prompt(), &[]), None);
+    }
+
+    #[test]
+    fn a_shared_prefix_reuses_the_sequence_that_holds_it() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(1, [system_prompt(), tokens(500..510)].concat());
+
+        let prompt = [system_prompt(), tokens(700..720)].concat();
+
+        assert_eq!(
+            cache.choose_sequence(&prompt, &[1, 0]),
+            Some(SequenceChoice {
+                reusable_prefix_tokens: 100,
+                sequence_id: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn the_last_prompt_token_is_never_reused() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(0, system_prompt());
+
+        assert_eq!(
+            cache.choose_sequence(&system_prompt(), &[0]),
+            Some(SequenceChoice {
+                reusable_prefix_tokens: 99,
+                sequence_id: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn the_longest_shared_prefix_wins() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(0, tokens(0..40));
+        cache.remember(1, tokens(0..80));
+
+        let choice = cache.choose_sequence(&system_prompt(), &[0, 1]);
+
+        assert_eq!(choice.map(|choice| choice.sequence_id), Some(1));
+    }
+
+    #[test]
+    fn a_short_match_prefers_an_uncached_sequence() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(1, tokens((0..).take(MIN_REUSABLE_PREFIX_TOKENS - 1)));
+
+        assert_eq!(
+            cache.choose_sequence(&system_prompt(), &[1, 0]),
+            Some(SequenceChoice {
+                reusable_prefix_tokens: 0,
+                sequence_id: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn without_a_match_or_uncached_sequence_the_least_recently_used_is_overwritten() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(0, tokens(1000..1100));
+        cache.remember(1, tokens(2000..2100));
+
+        assert_eq!(
+            cache.choose_sequence(&system_prompt(), &[1, 0]),
+            Some(SequenceChoice {
+                reusable_prefix_tokens: 0,
+                sequence_id: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn cached_prefixes_of_busy_sequences_are_not_chosen() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(1, system_prompt());
+
+        let choice = cache.choose_sequence(&system_prompt(), &[0]);
+
+        assert_eq!(choice.map(|choice| choice.sequence_id), Some(0));
+    }
+
+    #[test]
+    fn eviction_drops_the_least_recently_used_prefix_first() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(3, system_prompt());
+        cache.remember(1, system_prompt());
+        cache.remember(3, system_prompt());
+
+        assert_eq!(cache.evict_least_recently_used(), Some(1));
+        assert_eq!(cache.evict_least_recently_used(), Some(3));
+        assert_eq!(cache.evict_least_recently_used(), None);
+    }
+
+    #[test]
+    fn forgetting_and_clearing_remove_prefixes() {
+        let mut cache = SequencePrefixCache::default();
+
+        cache.remember(0, system_prompt());
+        cache.remember(1, system_prompt());
+        cache.forget(0);
+
+        assert_eq!(cache.len(), 1);
+
+        cache.clear();
+
+        assert!(cache.is_empty());
+    }
+}
@@ -18,6 +18,7 @@ use paddler_messaging::subscribes_to_updates::SubscribesToUpdates;
 
 pub struct SlotAggregatedStatus {
     desired_slots_total: i32,
+    desired_state_version: AtomicValue<AtomicU64>,
     download_current: AtomicValue<AtomicU64>,
     download_filename: RwLock<Option<String>>,
     download_indeterminate: AtomicValue<AtomicBool>,
@@ -27,6 +28,7 @@ pub struct SlotAggregatedStatus {
     slots_processing: AtomicValue<AtomicI32>,
     slots_total: AtomicValue<AtomicI32>,
     state_application_status_code: AtomicValue<AtomicI32>,
+    state_application_version: AtomicValue<AtomicU64>,
     update_tx: watch::Sender<()>,
     uses_chat_template_override: AtomicValue<AtomicBool>,
     version: AtomicValue<AtomicI32>,
@@ -39,6 +41,7 @@ impl SlotAggregatedStatus {
 
         Self {
             desired_slots_total,
+            desired_state_version: AtomicValue::<AtomicU64>::new(0),
             download_current: AtomicValue::<AtomicU64>::new(0),
             download_filename: RwLock::new(None),
             download_indeterminate: AtomicValue::<AtomicBool>::new(true),
@@ -48,6 +51,7 @@ impl SlotAggregatedStatus {
             state_application_status_code: AtomicValue::<AtomicI32>::new(
                 AgentStateApplicationStatus::Fresh as i32,
             ),
+            state_application_version: AtomicValue::<AtomicU64>::new(0),
             slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(0),
             update_tx,
@@ -91,6 +95,14 @@ impl SlotAggregatedStatus {
         self.update_tx.send_replace(());
     }
 
+    /// Issues registered before a new desired state arrives belong to the previous one.
+    pub fn receive_desired_state(&self, desired_state_version: u64) {
+        self.issues.clear();
+        self.desired_state_version.set(desired_state_version);
+        self.version.increment();
+        self.update_tx.send_replace(());
+    }
+
     pub fn register_issue(&self, issue: AgentIssue) {
         if self.issues.insert(issue) {
             self.update_tx.send_replace(());
@@ -171,6 +183,14 @@ impl SlotAggregatedStatus {
         self.update_tx.send_replace(());
     }
 
+    pub fn start_applying_desired_state(&self, desired_state_version: u64) {
+        self.state_application_status_code
+            .set(AgentStateApplicationStatus::Fresh as i32);
+        self.state_application_version.set(desired_state_version);
+        self.version.increment();
+        self.update_tx.send_replace(());
+    }
+
     pub fn slots_processing_count(&self) -> i32 {
         self.slots_processing.get()
     }
@@ -200,9 +220,14 @@ impl ProducesSnapshot for SlotAggregatedStatus {
     type Snapshot = SlotAggregatedStatusSnapshot;
 
     fn make_snapshot(&self) -> Result<Self::Snapshot> {
+        // Versions are written after the values they describe, so they have to be read first.
+        let desired_state_version = self.desired_state_version.get();
+        let state_application_version = self.state_application_version.get();
+
         Ok(SlotAggregatedStatusSnapshot {
             issues: self.issues.iter().map(|item| item.clone()).collect(),
             desired_slots_total: self.desired_slots_total,
+            desired_state_version,
             download_current: self.download_current.get(),
             download_filename: self.download_filename.read().clone(),
             download_indeterminate: self.download_indeterminate.get(),
@@ -211,6 +236,7 @@ impl ProducesSnapshot for SlotAggregatedStatus {
             slots_processing: self.slots_processing.get(),
             slots_total: self.slots_total.get(),
             state_application_status: self.state_application_status_code.get().try_into()?,
+            state_application_version,
             uses_chat_template_override: self.uses_chat_template_override.get(),
             version: self.version.get(),
         })
@@ -364,6 +390,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn receive_desired_state_drops_issues_of_the_previous_desired_state() {
+        let status = SlotAggregatedStatus::new(2);
+
+        status.register_issue(AgentIssue::ModelFileDoesNotExist(model_path("model_test")));
+        status.receive_desired_state(3);
+
+        let snapshot = status.make_snapshot().unwrap();
+
+        assert!(snapshot.issues.is_empty());
+        assert_eq!(snapshot.desired_state_version, 3);
+        assert_eq!(snapshot.state_application_version, 0);
+    }
+
+    #[test]
+    fn start_applying_desired_state_pairs_the_status_with_its_version() {
+        let status = SlotAggregatedStatus::new(2);
+
+        status.set_state_application_status(AgentStateApplicationStatus::Applied);
+        status.start_applying_desired_state(3);
+
+        let snapshot = status.make_snapshot().unwrap();
+
+        assert_eq!(
+            snapshot.state_application_status,
+            AgentStateApplicationStatus::Fresh
+        );
+        assert_eq!(snapshot.state_application_version, 3);
+    }
+
     #[test]
     fn make_snapshot_propagates_invalid_state_application_status() {
         let status = SlotAggregatedStatus::new(2);
@@ -0,0 +1,190 @@
+use std::mem::take;
+
+use llama_cpp_bindings::SampledToken;
+
+use crate::stop_sequence_scan::StopSequenceScan;
+
+/// Matches caller-supplied stop sequences against generated text that arrives
+/// one token piece at a time. Text that could still be the start of a stop
+/// sequence is held back until the following pieces settle it, so a matched
+/// sequence never reaches the client, even partially.
+#[derive(Debug, Default)]
+pub struct StopSequenceMatcher {
+    held_back: String,
+    held_back_token: Option<SampledToken>,
+    stop_sequences: Vec<String>,
+}
+
+impl StopSequenceMatcher {
+    #[must_use]
+    pub const fn new(stop_sequences: Vec<String>) -> Self {
+        Self {
+            held_back: String::new(),
+            held_back_token: None,
+            stop_sequences,
+        }
+    }
+
+    /// Releases whatever text is still held back, along with the token kind it
+    /// was generated as. Used when generation ends for another reason.
+    pub fn flush(&mut self) -> Option<(SampledToken, String)> {
+        let sampled_token = self.held_back_token.take()?;
+
+        Some((sampled_token, take(&mut self.held_back)))
+    }
+
+    pub fn scan(&mut self, sampled_token: SampledToken, piece: &str) -> StopSequenceScan {
+        if self.stop_sequences.is_empty() {
+            return StopSequenceScan::Continue(piece.to_owned());
+        }
+
+        self.held_back.push_str(piece);
+        self.held_back_token = Some(sampled_token);
+
+        if let Some(match_start) = self.earliest_match_start() {
+            let mut releasable = take(&mut self.held_back);
+
+            releasable.truncate(match_start);
+            self.held_back_token = None;
+
+            return StopSequenceScan::Matched(releasable);
+        }
+
+        let partial_match_start = self.partial_match_start();
+        let releasable: String = self.held_back.drain(..partial_match_start).collect();
+
+        if self.held_back.is_empty() {
+            self.held_back_token = None;
+        }
+
+        StopSequenceScan::Continue(releasable)
+    }
+
+    fn earliest_match_start(&self) -> Option<usize> {
+        self.stop_sequences
+            .iter()
+            .filter_map(|stop_sequence| self.held_back.find(stop_sequence.as_str()))
+            .min()
+    }
+
+    fn partial_match_start(&self) -> usize {
+        self.held_back
+            .char_indices()
+            .map(|(index, _)| index)
+            .find(|&index| {
+                let suffix = &self.held_back[index..];
+
+                self.stop_sequences
+                    .iter()
+                    .any(|stop_sequence| stop_sequence.starts_with(suffix))
+            })
+            .unwrap_or(self.held_back.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use llama_cpp_bindings::SampledToken;
+    use llama_cpp_bindings::token::LlamaToken;
+
+    use super::StopSequenceMatcher;
+    use crate::stop_sequence_scan::StopSequenceScan;
+
+    fn content() -> SampledToken {
+        SampledToken::Content(LlamaToken::new(1))
+    }
+
+    #[test]
+    fn without_stop_sequences_every_piece_is_released() {
+        let mut matcher = StopSequenceMatcher::new(Vec::new());
+
+        assert_eq!(
+            matcher.scan(content(), "\n#"),
+            StopSequenceScan::Continue("\n#".to_owned())
+        );
+        assert!(matcher.flush().is_none());
+    }
+
+    #[test]
+    fn a_stop_sequence_within_one_piece_ends_the_text_before_it() {
+        let mut matcher = StopSequenceMatcher::new(vec!["\n###".to_owned()]);
+
+        assert_eq!(
+            matcher.scan(content(), "4\n###\nQ:"),
+            StopSequenceScan::Matched("4".to_owned())
+        );
+    }
+
+    #[test]
+    fn a_stop_sequence_split_across_pieces_is_never_released() {
+        let mut matcher = StopSequenceMatcher::new(vec!["\n###".to_owned()]);
+
+        assert_eq!(
+            matcher.scan(content(), "A: 4\n"),
+            StopSequenceScan::Continue("A: 4".to_owned())
+        );
+        assert_eq!(
+            matcher.scan(content(), "##"),
+            StopSequenceScan::Continue(String::new())
+        );
+        assert_eq!(
+            matcher.scan(content(), "#"),
+            StopSequenceScan::Matched(String::new())
+        );
+    }
+
+    #[test]
+    fn held_back_text_is_released_once_the_match_breaks() {
+        let mut matcher = StopSequenceMatcher::new(vec!["\n###".to_owned()]);
+
+        assert_eq!(
+            matcher.scan(content(), "one\n#"),
+            StopSequenceScan::Continue("one".to_owned())
+        );
+        assert_eq!(
+            matcher.scan(content(), " two"),
+            StopSequenceScan::Continue("\n# two".to_owned())
+        );
+    }
+
+    #[test]
+    fn the_earliest_of_several_stop_sequences_wins() {
+        let mut matcher = StopSequenceMatcher::new(vec!["END".to_owned(), "\n\n".to_owned()]);
+
+        assert_eq!(
+            matcher.scan(content(), "done\n\nEND"),
+            StopSequenceScan::Matched("done".to_owned())
+        );
+    }
+
+    #[test]
+    fn flush_returns_the_held_back_text_with_its_token_kind() {
+        let mut matcher = StopSequenceMatcher::new(vec!["</answer>".to_owned()]);
+        let undeterminable = SampledToken::Undeterminable(LlamaToken::new(2));
+
+        assert_eq!(
+            matcher.scan(undeterminable, "42</ans"),
+            StopSequenceScan::Continue("42".to_owned())
+        );
+
+        let (sampled_token, held_back) = matcher.flush().unwrap();
+
+        assert_eq!(sampled_token, undeterminable);
+        assert_eq!(held_back, "</ans");
+        assert!(matcher.flush().is_none());
+    }
+
+    #[test]
+    fn multibyte_text_is_split_on_character_boundaries() {
+        let mut matcher = StopSequenceMatcher::new(vec!["«end»".to_owned()]);
+
+        assert_eq!(
+            matcher.scan(content(), "żółw «en"),
+            StopSequenceScan::Continue("żółw ".to_owned())
+        );
+        assert_eq!(
+            matcher.scan(content(), "d»"),
+            StopSequenceScan::Matched(String::new())
+        );
+    }
+}
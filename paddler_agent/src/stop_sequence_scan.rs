@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Eq)]
+pub enum StopSequenceScan {
+    /// No stop sequence matched yet; the text is safe to stream.
+    Continue(String),
+    /// A stop sequence matched; the text precedes it and is the last to stream.
+    Matched(String),
+}
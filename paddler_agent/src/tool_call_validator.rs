@@ -9,12 +9,17 @@ use paddler_messaging::request_params::continue_from_conversation_history_params
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
 
 use paddler_messaging::tool_call_validation_error::ToolCallValidationError;
+use serde_json::Value;
+use serde_json::json;
 
 use crate::validator_build_error::ValidatorBuildError;
 
 enum ValidationStrategy {
     JsonObjectOnly,
-    Schema(Box<Validator>),
+    Schema {
+        schema: Value,
+        validator: Box<Validator>,
+    },
 }
 
 pub struct ToolCallValidator {
@@ -46,7 +51,10 @@ impl ToolCallValidator {
                             message: err.to_string(),
                         }
                     })?;
-                    ValidationStrategy::Schema(Box::new(compiled))
+                    ValidationStrategy::Schema {
+                        schema: schema_value,
+                        validator: Box::new(compiled),
+                    }
                 }
             };
 
@@ -69,7 +77,7 @@ impl ToolCallValidator {
 
         match strategy {
             ValidationStrategy::JsonObjectOnly => Ok(()),
-            ValidationStrategy::Schema(validator) => {
+            ValidationStrategy::Schema { validator, .. } => {
                 let mut messages: Vec<String> = validator
                     .iter_errors(arguments_value)
                     .map(|err| err.to_string())
@@ -87,6 +95,18 @@ impl ToolCallValidator {
         }
     }
 
+    /// The schema the tool's arguments are validated against, so a grammar forcing the call
+    /// constrains them the same way.
+    #[must_use]
+    pub fn arguments_schema(&self, tool_name: &str) -> Option<Value> {
+        self.strategies
+            .get(tool_name)
+            .map(|strategy| match strategy {
+                ValidationStrategy::JsonObjectOnly => json!({"type": "object"}),
+                ValidationStrategy::Schema { schema, .. } => schema.clone(),
+            })
+    }
+
     #[must_use]
     pub fn known_tool_names(&self) -> Vec<&str> {
         self.strategies.keys().map(String::as_str).collect()
@@ -232,6 +252,23 @@ mod tests {
         assert!(validator.validate(&parsed).is_ok());
     }
 
+    #[test]
+    fn arguments_schema_returns_the_schema_each_tool_is_validated_against() {
+        let validator =
+            ToolCallValidator::from_tools(&[weather_tool_with_schema(), schemaless_tool()])
+                .unwrap();
+
+        assert_eq!(
+            validator.arguments_schema("get_weather").unwrap()["required"],
+            json!(["location"])
+        );
+        assert_eq!(
+            validator.arguments_schema("freeform"),
+            Some(json!({"type": "object"}))
+        );
+        assert_eq!(validator.arguments_schema("set_thermostat"), None);
+    }
+
     #[test]
     fn known_tool_names_returns_all_registered_names() {
         let validator =
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU64;
+use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
@@ -13,16 +14,20 @@ use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+use paddler_messaging::agent_cordon_status::AgentCordonStatus;
 use paddler_messaging::agent_desired_state::AgentDesiredState;
 use paddler_messaging::agent_issue::AgentIssue;
+use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
 use paddler_messaging::jsonrpc::request_envelope::RequestEnvelope;
 use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 use paddler_messaging::request_params::generate_embedding_batch_params::GenerateEmbeddingBatchParams;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
 use paddler_messaging::slot_aggregated_status_snapshot::SlotAggregatedStatusSnapshot;
+use paddler_messaging::trace_context::TraceContext;
 
 use crate::agent_controller_update_result::AgentControllerUpdateResult;
+use crate::agent_throughput::AgentThroughput;
 use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use crate::embedding_sender_collection::EmbeddingSenderCollection;
 use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
@@ -43,7 +48,11 @@ pub struct AgentController {
     pub agent_message_tx: mpsc::UnboundedSender<AgentJsonRpcMessage>,
     pub chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
     pub connection_close: CancellationToken,
+    pub cordoned: AtomicValue<AtomicBool>,
     pub desired_slots_total: AtomicValue<AtomicI32>,
+    pub desired_state: RwLock<Option<AgentDesiredState>>,
+    /// Version of the most recently sent desired state, echoed back by the agent.
+    pub desired_state_version: AtomicValue<AtomicU64>,
     pub download_current: AtomicValue<AtomicU64>,
     pub download_filename: RwLock<Option<String>>,
     pub download_indeterminate: AtomicValue<AtomicBool>,
@@ -54,15 +63,23 @@ pub struct AgentController {
     pub issues: RwLock<BTreeSet<AgentIssue>>,
     pub model_metadata_sender_collection: Arc<ModelMetadataSenderCollection>,
     pub model_path: RwLock<Option<String>>,
+    pub model_pool: Option<String>,
     pub name: Option<String>,
     pub newest_update_version: AtomicValue<AtomicI32>,
+    pub reported_desired_state_version: AtomicValue<AtomicU64>,
     pub slots_processing: AtomicValue<AtomicI32>,
     pub slots_total: AtomicValue<AtomicI32>,
     pub state_application_status_code: AtomicValue<AtomicI32>,
+    pub state_application_version: AtomicValue<AtomicU64>,
+    pub throughput: RwLock<AgentThroughput>,
     pub uses_chat_template_override: AtomicValue<AtomicBool>,
 }
 
 impl AgentController {
+    pub fn accepts_new_requests(&self) -> bool {
+        !self.cordoned.get() && !self.connection_close.is_cancelled()
+    }
+
     pub async fn get_chat_template_override(
         &self,
     ) -> Result<ManagesSendersController<ChatTemplateOverrideSenderCollection>> {
@@ -73,6 +90,10 @@ impl AgentController {
         .await
     }
 
+    pub fn get_desired_state(&self) -> Option<AgentDesiredState> {
+        self.desired_state.read().clone()
+    }
+
     pub fn get_download_filename(&self) -> Option<String> {
         self.download_filename.read().clone()
     }
@@ -95,6 +116,51 @@ impl AgentController {
         self.model_path.read().clone()
     }
 
+    pub fn get_throughput(&self) -> AgentThroughput {
+        *self.throughput.read()
+    }
+
+    pub fn has_applied_desired_state(&self) -> bool {
+        self.is_reporting_on_desired_state()
+            && matches!(
+                self.state_application_status(),
+                Ok(AgentStateApplicationStatus::Applied)
+            )
+            && self.issues.read().is_empty()
+    }
+
+    /// Only the issues and the status reported for the most recently sent desired state count.
+    pub fn has_failed_to_apply_desired_state(&self) -> bool {
+        let desired_state_version = self.desired_state_version.get();
+
+        (self.reported_desired_state_version.get() == desired_state_version
+            && !self.issues.read().is_empty())
+            || (self.state_application_version.get() == desired_state_version
+                && matches!(
+                    self.state_application_status(),
+                    Ok(AgentStateApplicationStatus::AttemptedAndNotAppliable
+                        | AgentStateApplicationStatus::Stuck)
+                ))
+    }
+
+    fn is_reporting_on_desired_state(&self) -> bool {
+        let desired_state_version = self.desired_state_version.get();
+
+        self.reported_desired_state_version.get() == desired_state_version
+            && self.state_application_version.get() == desired_state_version
+    }
+
+    pub fn record_throughput(
+        &self,
+        time_to_first_token: Duration,
+        generated_tokens: u64,
+        generation_time: Duration,
+    ) {
+        self.throughput
+            .write()
+            .record(time_to_first_token, generated_tokens, generation_time);
+    }
+
     pub fn set_download_filename(&self, filename: Option<String>) {
         let mut locked_filename = self.download_filename.write();
 
@@ -122,10 +188,15 @@ impl AgentController {
         Ok(())
     }
 
+    pub fn state_application_status(&self) -> Result<AgentStateApplicationStatus> {
+        self.state_application_status_code.get().try_into()
+    }
+
     pub fn update_from_slot_aggregated_status_snapshot(
         &self,
         SlotAggregatedStatusSnapshot {
             desired_slots_total,
+            desired_state_version,
             download_current,
             download_filename,
             download_indeterminate,
@@ -134,6 +205,7 @@ impl AgentController {
             model_path,
             slots_total,
             state_application_status,
+            state_application_version,
             uses_chat_template_override,
             version,
             ..
@@ -155,10 +227,16 @@ impl AgentController {
             .download_indeterminate
             .set_check(download_indeterminate);
         changed |= self.download_total.set_check(download_total);
+        changed |= self
+            .reported_desired_state_version
+            .set_check(desired_state_version);
         changed |= self.slots_total.set_check(slots_total);
         changed |= self
             .state_application_status_code
             .set_check(state_application_status as i32);
+        changed |= self
+            .state_application_version
+            .set_check(state_application_version);
         changed |= self
             .uses_chat_template_override
             .set_check(uses_chat_template_override);
@@ -201,6 +279,7 @@ impl AgentController {
         self.send_rpc_message(AgentJsonRpcMessage::Request(RequestEnvelope {
             id: request_id.clone(),
             request,
+            trace_context: None,
         }))
         .await?;
 
@@ -232,6 +311,7 @@ impl HandlesAgentStreamingResponse<ContinueFromConversationHistoryParams<Validat
         &self,
         request_id: String,
         params: ContinueFromConversationHistoryParams<ValidatedParametersSchema>,
+        trace_context: Option<TraceContext>,
     ) -> Result<ManagesSendersController<Self::SenderCollection>> {
         self.receiver_from_message(
             request_id.clone(),
@@ -239,6 +319,7 @@ impl HandlesAgentStreamingResponse<ContinueFromConversationHistoryParams<Validat
             AgentJsonRpcMessage::Request(RequestEnvelope {
                 id: request_id,
                 request: params.into(),
+                trace_context,
             }),
         )
         .await
@@ -253,6 +334,7 @@ impl HandlesAgentStreamingResponse<ContinueFromRawPromptParams> for AgentControl
         &self,
         request_id: String,
         params: ContinueFromRawPromptParams,
+        trace_context: Option<TraceContext>,
     ) -> Result<ManagesSendersController<Self::SenderCollection>> {
         self.receiver_from_message(
             request_id.clone(),
@@ -260,6 +342,7 @@ impl HandlesAgentStreamingResponse<ContinueFromRawPromptParams> for AgentControl
             AgentJsonRpcMessage::Request(RequestEnvelope {
                 id: request_id,
                 request: params.into(),
+                trace_context,
             }),
         )
         .await
@@ -274,6 +357,7 @@ impl HandlesAgentStreamingResponse<GenerateEmbeddingBatchParams> for AgentContro
         &self,
         request_id: String,
         params: GenerateEmbeddingBatchParams,
+        trace_context: Option<TraceContext>,
     ) -> Result<ManagesSendersController<Self::SenderCollection>> {
         self.receiver_from_message(
             request_id.clone(),
@@ -281,6 +365,7 @@ impl HandlesAgentStreamingResponse<GenerateEmbeddingBatchParams> for AgentContro
             AgentJsonRpcMessage::Request(RequestEnvelope {
                 id: request_id,
                 request: params.into(),
+                trace_context,
             }),
         )
         .await
@@ -291,7 +376,11 @@ impl ProducesSnapshot for AgentController {
     type Snapshot = AgentControllerSnapshot;
 
     fn make_snapshot(&self) -> Result<Self::Snapshot> {
+        let slots_processing = self.slots_processing.get();
+        let throughput = self.get_throughput();
+
         Ok(AgentControllerSnapshot {
+            cordon_status: AgentCordonStatus::new(self.cordoned.get(), slots_processing),
             desired_slots_total: self.desired_slots_total.get(),
             download_current: self.download_current.get(),
             download_filename: self.get_download_filename(),
@@ -300,10 +389,16 @@ impl ProducesSnapshot for AgentController {
             id: self.id.clone(),
             issues: self.get_issues(),
             model_path: self.get_model_path(),
+            model_pool: self.model_pool.clone(),
             name: self.name.clone(),
-            slots_processing: self.slots_processing.get(),
+            slots_processing,
             slots_total: self.slots_total.get(),
-            state_application_status: self.state_application_status_code.get().try_into()?,
+            state_application_status: self.state_application_status()?,
+            time_to_first_token_ms: throughput
+                .time_to_first_token
+                .map(|time_to_first_token| u64::try_from(time_to_first_token.as_millis()))
+                .transpose()?,
+            tokens_per_second: throughput.tokens_per_second,
             uses_chat_template_override: self.uses_chat_template_override.get(),
         })
     }
@@ -322,55 +417,88 @@ impl SendsRpcMessage for AgentController {
 
 #[async_trait]
 impl SetsDesiredState for AgentController {
+    type DesiredState = AgentDesiredState;
+
     async fn set_desired_state(&self, desired_state: AgentDesiredState) -> Result<()> {
+        let desired_state_version = {
+            let mut locked_desired_state = self.desired_state.write();
+            let desired_state_version = self.desired_state_version.get() + 1;
+
+            self.desired_state_version.set(desired_state_version);
+            *locked_desired_state = Some(desired_state.clone());
+
+            desired_state_version
+        };
+
         self.send_rpc_message(AgentJsonRpcMessage::Notification(
-            AgentJsonRpcNotification::SetState(Box::new(SetStateParams { desired_state })),
+            AgentJsonRpcNotification::SetState(Box::new(SetStateParams {
+                desired_state,
+                desired_state_version,
+            })),
         ))
         .await
     }
 }
 
 #[cfg(test)]
-mod tests {
-    use paddler_messaging::agent_issue_params::model_path::ModelPath;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
-
-    use super::*;
-
-    fn is_updated(result: &AgentControllerUpdateResult) -> bool {
-        matches!(result, AgentControllerUpdateResult::Updated)
-    }
-
-    fn fresh_agent_controller() -> AgentController {
-        let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
-
-        AgentController {
+impl AgentController {
+    /// An idle, freshly registered agent with no slots; tests override the fields they care about.
+    #[must_use]
+    pub fn new_for_test(
+        id: &str,
+        agent_message_tx: mpsc::UnboundedSender<AgentJsonRpcMessage>,
+    ) -> Self {
+        Self {
             agent_message_tx,
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
             ),
             connection_close: CancellationToken::new(),
+            cordoned: AtomicValue::<AtomicBool>::new(false),
             desired_slots_total: AtomicValue::<AtomicI32>::new(0),
+            desired_state: RwLock::new(None),
+            desired_state_version: AtomicValue::<AtomicU64>::new(0),
             download_current: AtomicValue::<AtomicU64>::new(0),
             download_filename: RwLock::new(None),
             download_indeterminate: AtomicValue::<AtomicBool>::new(true),
             download_total: AtomicValue::<AtomicU64>::new(0),
             embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
             generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: "agent-test".to_owned(),
+            id: id.to_owned(),
             issues: RwLock::new(BTreeSet::new()),
             model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
             model_path: RwLock::new(None),
+            model_pool: None,
             name: None,
             newest_update_version: AtomicValue::<AtomicI32>::new(0),
+            reported_desired_state_version: AtomicValue::<AtomicU64>::new(0),
             slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(0),
             state_application_status_code: AtomicValue::<AtomicI32>::new(
                 AgentStateApplicationStatus::Fresh as i32,
             ),
+            state_application_version: AtomicValue::<AtomicU64>::new(0),
+            throughput: RwLock::new(AgentThroughput::default()),
             uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
         }
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::agent_issue_params::model_path::ModelPath;
+
+    use super::*;
+
+    fn is_updated(result: &AgentControllerUpdateResult) -> bool {
+        matches!(result, AgentControllerUpdateResult::Updated)
+    }
+
+    fn fresh_agent_controller() -> AgentController {
+        let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
+
+        AgentController::new_for_test("agent-test", agent_message_tx)
+    }
 
     #[test]
     fn multi_field_update_stores_all_changed_atomic_fields() {
@@ -378,6 +506,7 @@ mod tests {
 
         let snapshot = SlotAggregatedStatusSnapshot {
             desired_slots_total: 4,
+            desired_state_version: 0,
             download_current: 10,
             download_filename: None,
             download_indeterminate: false,
@@ -387,6 +516,7 @@ mod tests {
             slots_processing: 0,
             slots_total: 4,
             state_application_status: AgentStateApplicationStatus::Fresh,
+            state_application_version: 0,
             uses_chat_template_override: true,
             version: 1,
         };
@@ -409,6 +539,7 @@ mod tests {
 
         let snapshot = SlotAggregatedStatusSnapshot {
             desired_slots_total: 9,
+            desired_state_version: 0,
             download_current: 0,
             download_filename: None,
             download_indeterminate: true,
@@ -418,6 +549,7 @@ mod tests {
             slots_processing: 0,
             slots_total: 0,
             state_application_status: AgentStateApplicationStatus::Fresh,
+            state_application_version: 0,
             uses_chat_template_override: false,
             version: 1,
         };
@@ -439,6 +571,7 @@ mod tests {
 
         let snapshot = SlotAggregatedStatusSnapshot {
             desired_slots_total: 0,
+            desired_state_version: 0,
             download_current: 0,
             download_filename: Some("weights.gguf".to_owned()),
             download_indeterminate: true,
@@ -448,6 +581,7 @@ mod tests {
             slots_processing: 0,
             slots_total: 0,
             state_application_status: AgentStateApplicationStatus::Fresh,
+            state_application_version: 0,
             uses_chat_template_override: false,
             version: 1,
         };
@@ -472,6 +606,7 @@ mod tests {
 
         let snapshot = SlotAggregatedStatusSnapshot {
             desired_slots_total: 0,
+            desired_state_version: 0,
             download_current: 0,
             download_filename: None,
             download_indeterminate: true,
@@ -481,6 +616,7 @@ mod tests {
             slots_processing: 0,
             slots_total: 0,
             state_application_status: AgentStateApplicationStatus::Fresh,
+            state_application_version: 0,
             uses_chat_template_override: false,
             version: 1,
         };
@@ -490,6 +626,33 @@ mod tests {
         assert!(!is_updated(&result));
     }
 
+    #[test]
+    fn make_snapshot_reports_drained_once_a_cordoned_agent_is_idle() -> Result<()> {
+        let agent_controller = fresh_agent_controller();
+
+        assert_eq!(
+            agent_controller.make_snapshot()?.cordon_status,
+            AgentCordonStatus::Uncordoned
+        );
+
+        agent_controller.cordoned.set(true);
+        agent_controller.slots_processing.set(1);
+
+        assert_eq!(
+            agent_controller.make_snapshot()?.cordon_status,
+            AgentCordonStatus::Draining
+        );
+
+        agent_controller.slots_processing.set(0);
+
+        assert_eq!(
+            agent_controller.make_snapshot()?.cordon_status,
+            AgentCordonStatus::Drained
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn make_snapshot_fails_for_invalid_state_application_status() {
         let agent_controller = fresh_agent_controller();
@@ -537,9 +700,15 @@ mod tests {
             "raw-prompt-request".to_owned(),
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 16,
+                model: None,
                 raw_prompt: "hello".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
+        None,
         )
         .await
         .unwrap();
@@ -567,9 +736,15 @@ mod tests {
                 "duplicate-request".to_owned(),
                 ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 16,
+                    model: None,
                     raw_prompt: "first".to_owned(),
+                    sampling: None,
+                    session_key: None,
+                    stop: Vec::new(),
                 },
+            None,
             )
             .await
             .unwrap();
@@ -580,9 +755,15 @@ mod tests {
                 "duplicate-request".to_owned(),
                 ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 16,
+                    model: None,
                     raw_prompt: "second".to_owned(),
+                    sampling: None,
+                    session_key: None,
+                    stop: Vec::new(),
                 },
+            None,
             )
             .await;
 
@@ -627,9 +808,15 @@ mod tests {
                 "unreachable-request".to_owned(),
                 ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 16,
+                    model: None,
                     raw_prompt: "hello".to_owned(),
+                    sampling: None,
+                    session_key: None,
+                    stop: Vec::new(),
                 },
+            None,
             )
             .await;
 
@@ -642,4 +829,51 @@ mod tests {
             "a dispatch that fails to reach the agent must not leave the response sender registered"
         );
     }
+
+    #[tokio::test]
+    async fn only_reports_about_the_latest_desired_state_count_towards_its_application() {
+        let (agent_message_tx, mut agent_message_rx) = mpsc::unbounded_channel();
+        let agent_controller = AgentController::new_for_test("agent-test", agent_message_tx);
+
+        agent_controller
+            .set_desired_state(AgentDesiredState::default())
+            .await
+            .unwrap();
+
+        let Some(AgentJsonRpcMessage::Notification(AgentJsonRpcNotification::SetState(
+            set_state_params,
+        ))) = agent_message_rx.recv().await
+        else {
+            panic!("expected a SetState notification");
+        };
+
+        assert_eq!(set_state_params.desired_state_version, 1);
+
+        agent_controller.update_from_slot_aggregated_status_snapshot(
+            SlotAggregatedStatusSnapshot {
+                issues: BTreeSet::from([AgentIssue::ModelCannotBeLoaded(ModelPath {
+                    model_path: "previous.gguf".to_owned(),
+                })]),
+                state_application_status: AgentStateApplicationStatus::Stuck,
+                version: 1,
+                ..SlotAggregatedStatusSnapshot::default()
+            },
+        );
+
+        assert!(!agent_controller.has_applied_desired_state());
+        assert!(!agent_controller.has_failed_to_apply_desired_state());
+
+        agent_controller.update_from_slot_aggregated_status_snapshot(
+            SlotAggregatedStatusSnapshot {
+                desired_state_version: 1,
+                state_application_status: AgentStateApplicationStatus::Applied,
+                state_application_version: 1,
+                version: 2,
+                ..SlotAggregatedStatusSnapshot::default()
+            },
+        );
+
+        assert!(agent_controller.has_applied_desired_state());
+        assert!(!agent_controller.has_failed_to_apply_desired_state());
+    }
 }
@@ -1,35 +1,65 @@
+use std::cmp::Ordering;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
 
 use anyhow::Result;
 use anyhow::anyhow;
 use async_trait::async_trait;
 use dashmap::DashMap;
+use log::warn;
 use paddler_messaging::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
 use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
-use paddler_messaging::agent_desired_state::AgentDesiredState;
+use paddler_messaging::atomic_value::AtomicValue;
+use parking_lot::Mutex;
 use tokio::sync::watch;
 
 use super::agent_controller::AgentController;
 use super::agent_controller_pool_total_slots::AgentControllerPoolTotalSlots;
 use crate::agent_controller_slot_guard::AgentControllerSlotGuard;
+use crate::balancer_applicable_state::BalancerApplicableState;
 use crate::dispatch_candidate::DispatchCandidate;
+use crate::dispatch_strategy::DispatchStrategy;
 use crate::dispatched_agent::DispatchedAgent;
+use crate::session_affinity_table::SessionAffinityTable;
 use crate::sets_desired_state::SetsDesiredState;
 use paddler_messaging::produces_snapshot::ProducesSnapshot;
 use paddler_messaging::subscribes_to_updates::SubscribesToUpdates;
 
 pub struct AgentControllerPool {
     pub agents: DashMap<String, Arc<AgentController>>,
+    pub rejected_agent_registrations: AtomicValue<AtomicU64>,
+    pub rejected_model_pool_registrations: AtomicValue<AtomicU64>,
+    pub session_affinity_hits: AtomicValue<AtomicU64>,
+    pub session_affinity_misses: AtomicValue<AtomicU64>,
+    session_affinity_table: Mutex<SessionAffinityTable>,
     update_tx: watch::Sender<()>,
 }
 
 impl AgentControllerPool {
     #[must_use]
-    pub fn select_least_busy_with_capacity(&self) -> Option<DispatchCandidate> {
+    pub fn count_agents_in_model_pool(&self, model_pool: Option<&str>) -> usize {
+        self.agents
+            .iter()
+            .filter(|entry| entry.value().model_pool.as_deref() == model_pool)
+            .count()
+    }
+
+    #[must_use]
+    pub fn select_least_busy_with_capacity(
+        &self,
+        model_pool: Option<&str>,
+    ) -> Option<DispatchCandidate> {
         let mut best: Option<DispatchCandidate> = None;
 
         for entry in &self.agents {
             let agent_controller = entry.value().clone();
+
+            if agent_controller.model_pool.as_deref() != model_pool
+                || !agent_controller.accepts_new_requests()
+            {
+                continue;
+            }
+
             let snapshot = agent_controller.slots_processing.get();
 
             if snapshot >= agent_controller.slots_total.get() {
@@ -48,6 +78,120 @@ impl AgentControllerPool {
         best
     }
 
+    /// Agents are ranked by their tokens per second shared among the requests they would be
+    /// processing. Agents that have not completed a request yet are assumed to be as fast as
+    /// the median agent in the pool, so they are neither starved nor flooded before their
+    /// speed gets measured.
+    #[must_use]
+    pub fn select_fastest_with_capacity(
+        &self,
+        model_pool: Option<&str>,
+    ) -> Option<DispatchCandidate> {
+        // When no agent has been measured yet the prior only has to be equal for all of them,
+        // which ranks them by load.
+        let prior_tokens_per_second = self.median_tokens_per_second(model_pool).unwrap_or(1.0);
+        let mut best: Option<(f64, DispatchCandidate)> = None;
+
+        for entry in &self.agents {
+            let agent_controller = entry.value().clone();
+
+            if agent_controller.model_pool.as_deref() != model_pool
+                || !agent_controller.accepts_new_requests()
+            {
+                continue;
+            }
+
+            let snapshot = agent_controller.slots_processing.get();
+
+            if snapshot >= agent_controller.slots_total.get() {
+                continue;
+            }
+
+            let score = agent_controller
+                .get_throughput()
+                .tokens_per_second
+                .unwrap_or(prior_tokens_per_second)
+                / f64::from(snapshot + 1);
+            let is_better = best.as_ref().is_none_or(|(best_score, current)| {
+                match score.partial_cmp(best_score) {
+                    Some(Ordering::Greater) => true,
+                    Some(Ordering::Equal) => snapshot < current.snapshot,
+                    Some(Ordering::Less) | None => false,
+                }
+            });
+
+            if is_better {
+                best = Some((
+                    score,
+                    DispatchCandidate {
+                        agent_controller,
+                        snapshot,
+                    },
+                ));
+            }
+        }
+
+        best.map(|(_, candidate)| candidate)
+    }
+
+    fn median_tokens_per_second(&self, model_pool: Option<&str>) -> Option<f64> {
+        let mut measurements: Vec<f64> = self
+            .agents
+            .iter()
+            .filter(|entry| entry.value().model_pool.as_deref() == model_pool)
+            .filter_map(|entry| entry.value().get_throughput().tokens_per_second)
+            .collect();
+
+        if measurements.is_empty() {
+            return None;
+        }
+
+        measurements.sort_by(f64::total_cmp);
+
+        let middle = measurements.len() / 2;
+
+        Some(if measurements.len().is_multiple_of(2) {
+            f64::midpoint(measurements[middle - 1], measurements[middle])
+        } else {
+            measurements[middle]
+        })
+    }
+
+    /// The remembered agent is passed over once it is processing more than `max_load_slack`
+    /// requests beyond the least busy agent, so affinity does not pile load onto one agent.
+    fn select_remembered_with_capacity(
+        &self,
+        agent_id: &str,
+        model_pool: Option<&str>,
+        max_load_slack: i32,
+    ) -> Option<DispatchCandidate> {
+        let agent_controller = self.get_agent_controller(agent_id)?;
+
+        if agent_controller.model_pool.as_deref() != model_pool
+            || !agent_controller.accepts_new_requests()
+        {
+            return None;
+        }
+
+        let snapshot = agent_controller.slots_processing.get();
+
+        if snapshot >= agent_controller.slots_total.get() {
+            return None;
+        }
+
+        if self
+            .select_least_busy_with_capacity(model_pool)
+            .is_some_and(|least_busy| snapshot > least_busy.snapshot + max_load_slack)
+        {
+            return None;
+        }
+
+        Some(DispatchCandidate {
+            agent_controller,
+            snapshot,
+        })
+    }
+
     pub fn try_claim(
         &self,
         candidate: DispatchCandidate,
@@ -71,9 +215,12 @@ impl AgentControllerPool {
     }
 
     #[must_use]
-    pub fn take_least_busy_agent_controller(&self) -> Option<DispatchedAgent> {
+    pub fn take_least_busy_agent_controller(
+        &self,
+        model_pool: Option<&str>,
+    ) -> Option<DispatchedAgent> {
         loop {
-            let candidate = self.select_least_busy_with_capacity()?;
+            let candidate = self.select_least_busy_with_capacity(model_pool)?;
 
             if let Ok(dispatched) = self.try_claim(candidate) {
                 return Some(dispatched);
@@ -81,6 +228,101 @@ impl AgentControllerPool {
         }
     }
 
+    /// Without an affinity key, session-affinity dispatch is the same as least-busy dispatch.
+    /// With one, the agent that last served the key is preferred while it has a free slot and
+    /// is not much busier than the least busy agent; otherwise the least busy agent takes over
+    /// the key. Throughput-weighted dispatch ignores affinity keys and treats agents it has not
+    /// measured yet as if they ran at the median speed of their pool.
+    #[must_use]
+    pub fn take_agent_controller(
+        &self,
+        model_pool: Option<&str>,
+        affinity_key: Option<u64>,
+        dispatch_strategy: DispatchStrategy,
+    ) -> Option<DispatchedAgent> {
+        let max_load_slack = match dispatch_strategy {
+            DispatchStrategy::LeastBusy => {
+                return self.take_least_busy_agent_controller(model_pool);
+            }
+            DispatchStrategy::SessionAffinity { max_load_slack } => max_load_slack,
+            DispatchStrategy::ThroughputWeighted => {
+                return self.take_fastest_agent_controller(model_pool);
+            }
+        };
+
+        let Some(affinity_key) = affinity_key else {
+            return self.take_least_busy_agent_controller(model_pool);
+        };
+
+        let remembered_agent_id = self
+            .session_affinity_table
+            .lock()
+            .agent_id_for(affinity_key)
+            .map(str::to_owned);
+
+        if let Some(remembered_agent_id) = remembered_agent_id
+            && let Some(dispatched) = self.take_remembered_agent_controller(
+                &remembered_agent_id,
+                model_pool,
+                max_load_slack,
+            )
+        {
+            self.session_affinity_hits.increment_by(1);
+            self.session_affinity_table
+                .lock()
+                .remember(affinity_key, remembered_agent_id);
+
+            return Some(dispatched);
+        }
+
+        let dispatched = self.take_least_busy_agent_controller(model_pool)?;
+
+        self.session_affinity_misses.increment_by(1);
+        self.session_affinity_table
+            .lock()
+            .remember(affinity_key, dispatched.agent_controller.id.clone());
+
+        Some(dispatched)
+    }
+
+    fn take_fastest_agent_controller(&self, model_pool: Option<&str>) -> Option<DispatchedAgent> {
+        loop {
+            let candidate = self.select_fastest_with_capacity(model_pool)?;
+
+            if let Ok(dispatched) = self.try_claim(candidate) {
+                return Some(dispatched);
+            }
+        }
+    }
+
+    fn take_remembered_agent_controller(
+        &self,
+        agent_id: &str,
+        model_pool: Option<&str>,
+        max_load_slack: i32,
+    ) -> Option<DispatchedAgent> {
+        loop {
+            let candidate =
+                self.select_remembered_with_capacity(agent_id, model_pool, max_load_slack)?;
+
+            if let Ok(dispatched) = self.try_claim(candidate) {
+                return Some(dispatched);
+            }
+        }
+    }
+
+    /// Returns false when no such agent is registered.
+    pub fn set_agent_cordoned(&self, agent_id: &str, cordoned: bool) -> bool {
+        let Some(agent_controller) = self.get_agent_controller(agent_id) else {
+            return false;
+        };
+
+        agent_controller.cordoned.set(cordoned);
+        self.update_tx.send_replace(());
+
+        true
+    }
+
     #[must_use]
     pub fn get_agent_controller(&self, agent_id: &str) -> Option<Arc<AgentController>> {
         self.agents.get(agent_id).map(|entry| entry.value().clone())
@@ -139,6 +381,11 @@ impl Default for AgentControllerPool {
 
         Self {
             agents: DashMap::new(),
+            rejected_agent_registrations: AtomicValue::<AtomicU64>::new(0),
+            rejected_model_pool_registrations: AtomicValue::<AtomicU64>::new(0),
+            session_affinity_hits: AtomicValue::<AtomicU64>::new(0),
+            session_affinity_misses: AtomicValue::<AtomicU64>::new(0),
+            session_affinity_table: Mutex::new(SessionAffinityTable::default()),
             update_tx,
         }
     }
@@ -168,9 +415,26 @@ impl ProducesSnapshot for AgentControllerPool {
 
 #[async_trait]
 impl SetsDesiredState for AgentControllerPool {
-    async fn set_desired_state(&self, desired_state: AgentDesiredState) -> Result<()> {
+    type DesiredState = BalancerApplicableState;
+
+    async fn set_desired_state(
+        &self,
+        balancer_applicable_state: BalancerApplicableState,
+    ) -> Result<()> {
         for agent in &self.agents {
             let agent_controller = agent.value();
+            let model_pool = agent_controller.model_pool.as_deref();
+
+            let Some(desired_state) =
+                balancer_applicable_state.agent_desired_state_for_model_pool(model_pool)
+            else {
+                warn!(
+                    "Agent {:?} joined model pool {model_pool:?}, which is not configured",
+                    agent_controller.id
+                );
+
+                continue;
+            };
 
             agent_controller
                 .set_desired_state(desired_state.clone())
@@ -183,29 +447,25 @@ impl SetsDesiredState for AgentControllerPool {
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
     use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
     use std::time::Duration;
 
     use tokio::sync::mpsc;
     use tokio::sync::watch;
     use tokio::time::timeout;
-    use tokio_util::sync::CancellationToken;
 
     use super::AgentControllerPool;
     use crate::agent_controller::AgentController;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
-    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
-    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
+    use crate::dispatch_strategy::DispatchStrategy;
+    use crate::dispatched_agent::DispatchedAgent;
+    use paddler_messaging::agent_cordon_status::AgentCordonStatus;
     use paddler_messaging::atomic_value::AtomicValue;
     use paddler_messaging::produces_snapshot::ProducesSnapshot;
 
+    const SESSION_AFFINITY: DispatchStrategy =
+        DispatchStrategy::SessionAffinity { max_load_slack: 2 };
+
     fn agent_controller_with_slots(
         slots_processing: i32,
         slots_total: i32,
@@ -213,30 +473,9 @@ mod tests {
         let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
 
         Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
-            desired_slots_total: AtomicValue::<AtomicI32>::new(0),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: "agent-test".to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
             slots_processing: AtomicValue::<AtomicI32>::new(slots_processing),
             slots_total: AtomicValue::<AtomicI32>::new(slots_total),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test("agent-test", agent_message_tx)
         })
     }
 
@@ -310,4 +549,299 @@ mod tests {
         assert_eq!(snapshot.agents[0].slots_processing, 2);
         assert_eq!(snapshot.agents[0].slots_total, 5);
     }
+
+    #[test]
+    fn take_least_busy_agent_controller_only_dispatches_within_model_pool() {
+        let pool = AgentControllerPool::default();
+        let embeddings_agent = Arc::new(AgentController {
+            id: "embeddings".to_owned(),
+            model_pool: Some("embeddings".to_owned()),
+            ..Arc::into_inner(agent_controller_with_slots(0, 1)).unwrap()
+        });
+
+        pool.register_agent_controller("default".to_owned(), agent_controller_with_slots(0, 1))
+            .unwrap();
+        pool.register_agent_controller("embeddings".to_owned(), embeddings_agent)
+            .unwrap();
+
+        assert_eq!(pool.count_agents_in_model_pool(Some("embeddings")), 1);
+        assert_eq!(pool.count_agents_in_model_pool(Some("missing")), 0);
+
+        let dispatched = pool
+            .take_least_busy_agent_controller(Some("embeddings"))
+            .unwrap();
+
+        assert_eq!(dispatched.agent_controller.id, "embeddings");
+        assert!(
+            pool.take_least_busy_agent_controller(Some("embeddings"))
+                .is_none()
+        );
+        assert!(
+            pool.take_least_busy_agent_controller(Some("missing"))
+                .is_none()
+        );
+
+        let dispatched_default = pool.take_least_busy_agent_controller(None).unwrap();
+
+        assert_eq!(dispatched_default.agent_controller.model_pool, None);
+    }
+
+    fn register_named_agent(
+        pool: &AgentControllerPool,
+        agent_id: &str,
+        slots_processing: i32,
+        slots_total: i32,
+    ) {
+        pool.register_agent_controller(
+            agent_id.to_owned(),
+            Arc::new(AgentController {
+                id: agent_id.to_owned(),
+                ..Arc::into_inner(agent_controller_with_slots(slots_processing, slots_total))
+                    .unwrap()
+            }),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn take_agent_controller_returns_to_the_agent_that_served_the_key() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "first", 0, 4);
+        register_named_agent(&pool, "second", 0, 4);
+
+        let initial = pool
+            .take_agent_controller(None, Some(7), SESSION_AFFINITY)
+            .unwrap();
+        let initial_agent_id = initial.agent_controller.id.clone();
+
+        let unrelated = pool
+            .take_agent_controller(None, Some(8), SESSION_AFFINITY)
+            .unwrap();
+
+        assert_ne!(unrelated.agent_controller.id, initial_agent_id);
+
+        let repeated = pool
+            .take_agent_controller(None, Some(7), SESSION_AFFINITY)
+            .unwrap();
+
+        assert_eq!(repeated.agent_controller.id, initial_agent_id);
+        assert_eq!(pool.session_affinity_hits.get(), 1);
+        assert_eq!(pool.session_affinity_misses.get(), 2);
+    }
+
+    #[test]
+    fn take_agent_controller_falls_back_to_least_busy_when_remembered_agent_is_full() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "first", 0, 1);
+
+        let initial = pool
+            .take_agent_controller(None, Some(7), SESSION_AFFINITY)
+            .unwrap();
+
+        assert_eq!(initial.agent_controller.id, "first");
+
+        register_named_agent(&pool, "second", 0, 1);
+
+        let fallback = pool
+            .take_agent_controller(None, Some(7), SESSION_AFFINITY)
+            .unwrap();
+
+        assert_eq!(fallback.agent_controller.id, "second");
+        assert_eq!(pool.session_affinity_hits.get(), 0);
+        assert_eq!(pool.session_affinity_misses.get(), 2);
+
+        drop(initial);
+        drop(fallback);
+
+        let moved = pool
+            .take_agent_controller(None, Some(7), SESSION_AFFINITY)
+            .unwrap();
+
+        assert_eq!(moved.agent_controller.id, "second");
+        assert_eq!(pool.session_affinity_hits.get(), 1);
+    }
+
+    #[test]
+    fn take_agent_controller_leaves_remembered_agent_that_is_much_busier() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "first", 0, 8);
+
+        let held: Vec<DispatchedAgent> = (0..4)
+            .map(|_| {
+                pool.take_agent_controller(None, Some(7), SESSION_AFFINITY)
+                    .unwrap()
+            })
+            .collect();
+
+        assert_eq!(pool.session_affinity_hits.get(), 3);
+
+        register_named_agent(&pool, "second", 0, 8);
+
+        let dispatched = pool
+            .take_agent_controller(None, Some(7), SESSION_AFFINITY)
+            .unwrap();
+
+        assert_eq!(dispatched.agent_controller.id, "second");
+        assert_eq!(pool.session_affinity_misses.get(), 2);
+
+        drop(held);
+    }
+
+    #[test]
+    fn take_agent_controller_without_key_does_not_count_hits_or_misses() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "only", 0, 1);
+
+        assert!(
+            pool.take_agent_controller(None, None, DispatchStrategy::LeastBusy)
+                .is_some()
+        );
+        assert_eq!(pool.session_affinity_hits.get(), 0);
+        assert_eq!(pool.session_affinity_misses.get(), 0);
+    }
+
+    #[test]
+    fn cordoned_agents_are_skipped_until_uncordoned() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "cordoned", 0, 4);
+        register_named_agent(&pool, "busy", 3, 4);
+
+        assert!(pool.set_agent_cordoned("cordoned", true));
+        assert!(!pool.set_agent_cordoned("missing", true));
+
+        let dispatched = pool
+            .take_agent_controller(None, None, DispatchStrategy::LeastBusy)
+            .unwrap();
+
+        assert_eq!(dispatched.agent_controller.id, "busy");
+        assert!(
+            pool.take_agent_controller(None, None, DispatchStrategy::LeastBusy)
+                .is_none()
+        );
+        assert!(pool.make_snapshot().unwrap().agents.iter().any(
+            |agent| agent.id == "cordoned" && agent.cordon_status == AgentCordonStatus::Drained
+        ));
+
+        pool.set_agent_cordoned("cordoned", false);
+
+        let dispatched = pool
+            .take_agent_controller(None, None, DispatchStrategy::LeastBusy)
+            .unwrap();
+
+        assert_eq!(dispatched.agent_controller.id, "cordoned");
+    }
+
+    #[test]
+    fn throughput_weighted_dispatch_prefers_faster_agents_until_they_are_full() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "laptop", 0, 4);
+        register_named_agent(&pool, "workstation", 0, 4);
+
+        for (agent_id, generated_tokens) in [("laptop", 11), ("workstation", 101)] {
+            pool.get_agent_controller(agent_id)
+                .unwrap()
+                .record_throughput(
+                    Duration::from_millis(100),
+                    generated_tokens,
+                    Duration::from_secs(1),
+                );
+        }
+
+        let dispatched_agents: Vec<DispatchedAgent> = (0..4)
+            .map(|_| {
+                pool.take_agent_controller(None, None, DispatchStrategy::ThroughputWeighted)
+                    .unwrap()
+            })
+            .collect();
+
+        assert!(
+            dispatched_agents
+                .iter()
+                .all(|dispatched| dispatched.agent_controller.id == "workstation")
+        );
+        assert_eq!(
+            pool.take_agent_controller(None, None, DispatchStrategy::ThroughputWeighted)
+                .unwrap()
+                .agent_controller
+                .id,
+            "laptop"
+        );
+    }
+
+    #[test]
+    fn throughput_weighted_dispatch_scores_unmeasured_agents_with_the_pool_median() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "fast", 0, 4);
+        register_named_agent(&pool, "new", 0, 4);
+        register_named_agent(&pool, "slow", 0, 4);
+
+        for (agent_id, generated_tokens) in [("fast", 101), ("slow", 11)] {
+            pool.get_agent_controller(agent_id)
+                .unwrap()
+                .record_throughput(
+                    Duration::from_millis(100),
+                    generated_tokens,
+                    Duration::from_secs(1),
+                );
+        }
+
+        let dispatched_agents: Vec<DispatchedAgent> = (0..3)
+            .map(|_| {
+                pool.take_agent_controller(None, None, DispatchStrategy::ThroughputWeighted)
+                    .unwrap()
+            })
+            .collect();
+
+        assert_eq!(
+            dispatched_agents
+                .iter()
+                .map(|dispatched| dispatched.agent_controller.id.as_str())
+                .collect::<Vec<_>>(),
+            ["fast", "new", "fast"]
+        );
+    }
+
+    #[test]
+    fn throughput_weighted_dispatch_ranks_unmeasured_agents_by_load() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "busy", 2, 4);
+        register_named_agent(&pool, "idle", 0, 4);
+
+        assert_eq!(
+            pool.take_agent_controller(None, None, DispatchStrategy::ThroughputWeighted)
+                .unwrap()
+                .agent_controller
+                .id,
+            "idle"
+        );
+    }
+
+    #[test]
+    fn cordoned_agent_is_not_chosen_for_a_remembered_affinity_key() {
+        let pool = AgentControllerPool::default();
+
+        register_named_agent(&pool, "first", 0, 4);
+
+        drop(
+            pool.take_agent_controller(None, Some(7), SESSION_AFFINITY)
+                .unwrap(),
+        );
+
+        register_named_agent(&pool, "second", 0, 4);
+        pool.set_agent_cordoned("first", true);
+
+        let dispatched = pool
+            .take_agent_controller(None, Some(7), SESSION_AFFINITY)
+            .unwrap();
+
+        assert_eq!(dispatched.agent_controller.id, "second");
+    }
 }
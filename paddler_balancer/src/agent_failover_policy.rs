@@ -0,0 +1,55 @@
+/// Decides whether a request may be dispatched again after the agent serving it disconnects.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum AgentFailoverPolicy {
+    #[default]
+    Disabled,
+    /// Re-dispatches the request to another agent, at most `max_failovers` times, as long as
+    /// nothing has been forwarded to the client yet.
+    BeforeFirstToken { max_failovers: u32 },
+}
+
+impl AgentFailoverPolicy {
+    #[must_use]
+    pub const fn from_max_failovers(max_failovers: u32) -> Self {
+        if max_failovers == 0 {
+            Self::Disabled
+        } else {
+            Self::BeforeFirstToken { max_failovers }
+        }
+    }
+
+    #[must_use]
+    pub const fn allows_failover(self, failovers_so_far: u32) -> bool {
+        match self {
+            Self::Disabled => false,
+            Self::BeforeFirstToken { max_failovers } => failovers_so_far < max_failovers,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_the_number_of_failovers() {
+        let policy = AgentFailoverPolicy::BeforeFirstToken { max_failovers: 2 };
+
+        assert!(policy.allows_failover(0));
+        assert!(policy.allows_failover(1));
+        assert!(!policy.allows_failover(2));
+        assert!(!AgentFailoverPolicy::Disabled.allows_failover(0));
+    }
+
+    #[test]
+    fn zero_failovers_disables_failover() {
+        assert_eq!(
+            AgentFailoverPolicy::from_max_failovers(0),
+            AgentFailoverPolicy::Disabled
+        );
+        assert_eq!(
+            AgentFailoverPolicy::from_max_failovers(3),
+            AgentFailoverPolicy::BeforeFirstToken { max_failovers: 3 }
+        );
+    }
+}
@@ -0,0 +1,8 @@
+use crate::request_outcome::RequestOutcome;
+
+pub enum AgentResponseForwardingOutcome<TControlsSession> {
+    /// The agent disconnected before anything was forwarded to the client, so the request
+    /// can still be dispatched to another agent over the same session.
+    AgentLostBeforeFirstResponse(TControlsSession),
+    Finished(RequestOutcome),
+}
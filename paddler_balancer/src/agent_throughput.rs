@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+/// Weight of the newest measurement in the moving averages.
+const SMOOTHING_FACTOR: f64 = 0.2;
+
+/// Generation speed observed by the balancer, averaged over the most recent requests.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AgentThroughput {
+    pub time_to_first_token: Option<Duration>,
+    pub tokens_per_second: Option<f64>,
+}
+
+impl AgentThroughput {
+    /// `generation_time` spans from the first to the last response, so the first token is
+    /// accounted for by `time_to_first_token` instead.
+    pub fn record(
+        &mut self,
+        time_to_first_token: Duration,
+        generated_tokens: u64,
+        generation_time: Duration,
+    ) {
+        self.time_to_first_token = Some(self.time_to_first_token.map_or(
+            time_to_first_token,
+            |average| {
+                Duration::from_secs_f64(smooth(
+                    average.as_secs_f64(),
+                    time_to_first_token.as_secs_f64(),
+                ))
+            },
+        ));
+
+        let Ok(tokens_after_first) = u32::try_from(generated_tokens.saturating_sub(1)) else {
+            return;
+        };
+
+        if tokens_after_first == 0 || generation_time.is_zero() {
+            return;
+        }
+
+        let tokens_per_second = f64::from(tokens_after_first) / generation_time.as_secs_f64();
+
+        self.tokens_per_second =
+            Some(self.tokens_per_second.map_or(tokens_per_second, |average| {
+                smooth(average, tokens_per_second)
+            }));
+    }
+}
+
+fn smooth(average: f64, measurement: f64) -> f64 {
+    SMOOTHING_FACTOR.mul_add(measurement - average, average)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_measurement_becomes_the_average() {
+        let mut throughput = AgentThroughput::default();
+
+        throughput.record(Duration::from_millis(200), 11, Duration::from_secs(1));
+
+        assert_eq!(
+            throughput.time_to_first_token,
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(throughput.tokens_per_second, Some(10.0));
+    }
+
+    #[test]
+    fn later_measurements_move_the_average_gradually() {
+        let mut throughput = AgentThroughput::default();
+
+        throughput.record(Duration::from_millis(100), 11, Duration::from_secs(1));
+        throughput.record(Duration::from_millis(600), 61, Duration::from_secs(1));
+
+        assert_eq!(
+            throughput.time_to_first_token,
+            Some(Duration::from_millis(200))
+        );
+        assert_eq!(throughput.tokens_per_second, Some(20.0));
+    }
+
+    #[test]
+    fn single_token_responses_do_not_affect_tokens_per_second() {
+        let mut throughput = AgentThroughput::default();
+
+        throughput.record(Duration::from_millis(100), 1, Duration::ZERO);
+
+        assert!(throughput.tokens_per_second.is_none());
+        assert!(throughput.time_to_first_token.is_some());
+    }
+}
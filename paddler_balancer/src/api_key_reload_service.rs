@@ -0,0 +1,45 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use log::error;
+use log::info;
+use tokio::time::MissedTickBehavior;
+use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+use trzcina::Service;
+
+use crate::api_key_store::ApiKeyStore;
+
+const API_KEYS_FILE_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+pub struct ApiKeyReloadService {
+    pub api_key_store: Arc<ApiKeyStore>,
+}
+
+#[async_trait]
+impl Service for ApiKeyReloadService {
+    fn name(&self) -> &'static str {
+        "balancer::api_key_reload_service"
+    }
+
+    async fn run(self: Box<Self>, shutdown: CancellationToken) -> Result<()> {
+        let mut ticker = interval(API_KEYS_FILE_POLL_INTERVAL);
+
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break Ok(()),
+                _ = ticker.tick() => {
+                    match self.api_key_store.reload_if_modified() {
+                        Ok(true) => info!("Reloaded {} API keys", self.api_key_store.len()),
+                        Ok(false) => {}
+                        Err(err) => error!("Keeping previously loaded API keys: {err:#}"),
+                    }
+                }
+            }
+        }
+    }
+}
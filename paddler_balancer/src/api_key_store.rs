@@ -0,0 +1,217 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use parking_lot::Mutex;
+use parking_lot::RwLock;
+use sha2::Digest as _;
+use sha2::Sha256;
+
+use crate::constant_time_eq::constant_time_eq;
+use crate::request_priority::RequestPriority;
+
+fn parse_api_keys(contents: &str) -> Result<BTreeMap<String, Option<RequestPriority>>> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| match line.split_once(char::is_whitespace) {
+            Some((api_key, priority)) => Ok((api_key.to_owned(), Some(priority.trim().parse()?))),
+            None => Ok((line.to_owned(), None)),
+        })
+        .collect()
+}
+
+/// API keys read from a file with one key per line. Lines starting with `#` are comments.
+///
+/// A key may be followed by the priority class (`interactive` or `batch`) of requests made
+/// with it. The file is re-read when its contents change, so keys can be added or revoked
+/// without a restart.
+pub struct ApiKeyStore {
+    api_keys: RwLock<BTreeMap<String, Option<RequestPriority>>>,
+    loaded_digest: Mutex<Option<[u8; 32]>>,
+    path: PathBuf,
+}
+
+impl ApiKeyStore {
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let api_key_store = Self {
+            api_keys: RwLock::new(BTreeMap::new()),
+            loaded_digest: Mutex::new(None),
+            path,
+        };
+
+        api_key_store.reload()?;
+
+        Ok(api_key_store)
+    }
+
+    #[must_use]
+    pub fn assigned_priority(&self, presented_api_key: &str) -> Option<RequestPriority> {
+        self.api_keys
+            .read()
+            .iter()
+            .fold(None, |assigned_priority, (api_key, priority)| {
+                if constant_time_eq(api_key.as_bytes(), presented_api_key.as_bytes()) {
+                    *priority
+                } else {
+                    assigned_priority
+                }
+            })
+    }
+
+    #[must_use]
+    pub fn is_authorized(&self, presented_api_key: &str) -> bool {
+        self.api_keys
+            .read()
+            .keys()
+            .fold(false, |is_authorized, api_key| {
+                is_authorized | constant_time_eq(api_key.as_bytes(), presented_api_key.as_bytes())
+            })
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.api_keys.read().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.api_keys.read().is_empty()
+    }
+
+    pub fn reload(&self) -> Result<()> {
+        let contents = self.read_contents()?;
+
+        self.apply(&contents)
+    }
+
+    /// Returns `true` when the file contents changed since the last load and were applied.
+    /// Contents are compared rather than modification times, which can stay the same across
+    /// quick successive writes or go back in time when a file is replaced.
+    pub fn reload_if_modified(&self) -> Result<bool> {
+        let contents = self.read_contents()?;
+
+        if *self.loaded_digest.lock() == Some(digest_of(&contents)) {
+            return Ok(false);
+        }
+
+        self.apply(&contents)?;
+
+        Ok(true)
+    }
+
+    fn apply(&self, contents: &str) -> Result<()> {
+        let api_keys = parse_api_keys(contents)
+            .with_context(|| format!("Unable to parse API keys file {}", self.path.display()))?;
+
+        *self.api_keys.write() = api_keys;
+        *self.loaded_digest.lock() = Some(digest_of(contents));
+
+        Ok(())
+    }
+
+    fn read_contents(&self) -> Result<String> {
+        fs::read_to_string(&self.path)
+            .with_context(|| format!("Unable to read API keys file {}", self.path.display()))
+    }
+}
+
+fn digest_of(contents: &str) -> [u8; 32] {
+    Sha256::digest(contents.as_bytes()).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+
+    use tempfile::NamedTempFile;
+
+    use super::*;
+
+    fn api_keys_file(contents: &str) -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+
+        file.write_all(contents.as_bytes()).unwrap();
+
+        file
+    }
+
+    #[test]
+    fn skips_blank_lines_and_comments() {
+        let file = api_keys_file("# production\nsk-first\n\n  sk-second  \n");
+        let api_key_store = ApiKeyStore::load(file.path().to_path_buf()).unwrap();
+
+        assert_eq!(api_key_store.len(), 2);
+        assert!(api_key_store.is_authorized("sk-first"));
+        assert!(api_key_store.is_authorized("sk-second"));
+        assert!(!api_key_store.is_authorized("# production"));
+        assert!(!api_key_store.is_authorized("sk-firs"));
+        assert!(!api_key_store.is_authorized(""));
+    }
+
+    #[test]
+    fn reload_picks_up_revoked_and_rotated_keys() {
+        let file = api_keys_file("sk-old\n");
+        let api_key_store = ApiKeyStore::load(file.path().to_path_buf()).unwrap();
+
+        fs::write(file.path(), "sk-new\n").unwrap();
+        api_key_store.reload().unwrap();
+
+        assert!(!api_key_store.is_authorized("sk-old"));
+        assert!(api_key_store.is_authorized("sk-new"));
+    }
+
+    #[test]
+    fn reload_if_modified_skips_an_unchanged_file() {
+        let file = api_keys_file("sk-only\n");
+        let api_key_store = ApiKeyStore::load(file.path().to_path_buf()).unwrap();
+
+        assert!(!api_key_store.reload_if_modified().unwrap());
+    }
+
+    #[test]
+    fn reload_if_modified_picks_up_changed_contents() {
+        let file = api_keys_file("sk-old\n");
+        let api_key_store = ApiKeyStore::load(file.path().to_path_buf()).unwrap();
+
+        fs::write(file.path(), "sk-new\n").unwrap();
+
+        assert!(api_key_store.reload_if_modified().unwrap());
+        assert!(!api_key_store.is_authorized("sk-old"));
+        assert!(api_key_store.is_authorized("sk-new"));
+        assert!(!api_key_store.reload_if_modified().unwrap());
+    }
+
+    #[test]
+    fn reads_the_priority_class_assigned_to_a_key() {
+        let file = api_keys_file("sk-nightly batch\nsk-chat interactive\nsk-plain\n");
+        let api_key_store = ApiKeyStore::load(file.path().to_path_buf()).unwrap();
+
+        assert!(api_key_store.is_authorized("sk-nightly"));
+        assert_eq!(
+            api_key_store.assigned_priority("sk-nightly"),
+            Some(RequestPriority::Batch)
+        );
+        assert_eq!(
+            api_key_store.assigned_priority("sk-chat"),
+            Some(RequestPriority::Interactive)
+        );
+        assert_eq!(api_key_store.assigned_priority("sk-plain"), None);
+        assert_eq!(api_key_store.assigned_priority("sk-unknown"), None);
+    }
+
+    #[test]
+    fn load_fails_for_an_unknown_priority_class() {
+        let file = api_keys_file("sk-first urgent\n");
+
+        assert!(ApiKeyStore::load(file.path().to_path_buf()).is_err());
+    }
+
+    #[test]
+    fn load_fails_for_a_missing_file() {
+        assert!(ApiKeyStore::load(PathBuf::from("/nonexistent/paddler/api_keys")).is_err());
+    }
+}
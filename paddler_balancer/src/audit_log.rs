@@ -0,0 +1,101 @@
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use log::error;
+use paddler_messaging::atomic_value::AtomicValue;
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+use crate::audit_log_entry::AuditLogEntry;
+
+/// Entries waiting for the audit log service. Once it falls this far behind, new entries are
+/// dropped and counted rather than held in memory.
+pub const AUDIT_LOG_CHANNEL_CAPACITY: usize = 8192;
+
+/// Hands entries of finished requests to the audit log service. The default audit log
+/// records nothing.
+#[derive(Clone)]
+pub struct AuditLog {
+    dropped_entries: Arc<AtomicValue<AtomicU64>>,
+    entry_tx: Option<mpsc::Sender<AuditLogEntry>>,
+    max_body_bytes: Option<usize>,
+}
+
+impl AuditLog {
+    #[must_use]
+    pub fn new(entry_tx: mpsc::Sender<AuditLogEntry>, max_body_bytes: Option<usize>) -> Self {
+        Self {
+            entry_tx: Some(entry_tx),
+            max_body_bytes,
+            ..Self::default()
+        }
+    }
+
+    #[must_use]
+    pub fn dropped_entries(&self) -> u64 {
+        self.dropped_entries.get()
+    }
+
+    pub fn record(&self, audit_log_entry: AuditLogEntry) {
+        let Some(entry_tx) = &self.entry_tx else {
+            return;
+        };
+
+        match entry_tx.try_send(audit_log_entry) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => {
+                self.dropped_entries.increment_by(1);
+            }
+            Err(TrySendError::Closed(_)) => {
+                self.dropped_entries.increment_by(1);
+
+                error!("Audit log service is gone, an audit log entry was lost");
+            }
+        }
+    }
+
+    #[must_use]
+    pub fn start_entry(&self, endpoint: String, request_id: String) -> AuditLogEntry {
+        AuditLogEntry {
+            endpoint,
+            max_body_bytes: self.entry_tx.as_ref().and(self.max_body_bytes),
+            received_at_unix_ms: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |since_epoch| {
+                    u64::try_from(since_epoch.as_millis()).unwrap_or(u64::MAX)
+                }),
+            request_id,
+            ..AuditLogEntry::default()
+        }
+    }
+}
+
+impl Default for AuditLog {
+    fn default() -> Self {
+        Self {
+            dropped_entries: Arc::new(AtomicValue::<AtomicU64>::new(0)),
+            entry_tx: None,
+            max_body_bytes: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entries_are_dropped_and_counted_once_the_service_falls_behind() {
+        let (entry_tx, mut entry_rx) = mpsc::channel(1);
+        let audit_log = AuditLog::new(entry_tx, None);
+
+        audit_log.record(audit_log.start_entry("/v1/completions".to_owned(), "first".to_owned()));
+        audit_log.record(audit_log.start_entry("/v1/completions".to_owned(), "second".to_owned()));
+
+        assert_eq!(entry_rx.try_recv().unwrap().request_id, "first");
+        assert!(entry_rx.try_recv().is_err());
+        assert_eq!(audit_log.dropped_entries(), 1);
+    }
+}
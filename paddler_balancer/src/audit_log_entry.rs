@@ -0,0 +1,137 @@
+use std::ops::Not as _;
+use std::time::Duration;
+
+use llama_cpp_bindings_types::TokenUsage;
+use serde::Serialize;
+
+use crate::caller_key::CallerKey;
+
+fn duration_millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+fn truncate_to_char_boundary(text: &str, max_bytes: usize) -> &str {
+    let mut end = max_bytes.min(text.len());
+
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    &text[..end]
+}
+
+/// One line of the audit log, describing a single inference request.
+#[derive(Debug, Default, Serialize)]
+pub struct AuditLogEntry {
+    pub agent_id: Option<String>,
+    pub caller_key: Option<CallerKey>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub completion: Option<String>,
+    #[serde(skip_serializing_if = "<&bool>::not")]
+    pub completion_truncated: bool,
+    pub completion_tokens: u64,
+    pub duration_ms: u64,
+    pub endpoint: String,
+    pub error_kind: Option<&'static str>,
+    pub finish_reason: Option<&'static str>,
+    /// Bodies are left out of the entry when this is not set.
+    #[serde(skip)]
+    pub max_body_bytes: Option<usize>,
+    pub model: Option<String>,
+    pub model_pool: Option<String>,
+    pub outcome: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+    #[serde(skip_serializing_if = "<&bool>::not")]
+    pub prompt_truncated: bool,
+    pub prompt_tokens: u64,
+    pub queue_wait_ms: u64,
+    pub received_at_unix_ms: u64,
+    pub request_id: String,
+}
+
+impl AuditLogEntry {
+    pub fn append_completion(&mut self, generated_text: &str) {
+        let Some(max_body_bytes) = self.max_body_bytes else {
+            return;
+        };
+
+        let completion = self.completion.get_or_insert_with(String::new);
+        let remaining_bytes = max_body_bytes.saturating_sub(completion.len());
+
+        if generated_text.len() > remaining_bytes {
+            self.completion_truncated = true;
+        }
+
+        completion.push_str(truncate_to_char_boundary(generated_text, remaining_bytes));
+    }
+
+    #[must_use]
+    pub const fn captures_bodies(&self) -> bool {
+        self.max_body_bytes.is_some()
+    }
+
+    pub fn record_queue_wait(&mut self, queue_wait: Duration) {
+        self.queue_wait_ms = self
+            .queue_wait_ms
+            .saturating_add(duration_millis(queue_wait));
+    }
+
+    /// Adds up, since an embedding request reports the tokens of every document separately.
+    pub const fn record_token_usage(&mut self, token_usage: &TokenUsage) {
+        self.completion_tokens = self
+            .completion_tokens
+            .saturating_add(token_usage.completion_tokens());
+        self.prompt_tokens = self.prompt_tokens.saturating_add(token_usage.prompt_tokens);
+    }
+
+    pub fn set_duration(&mut self, duration: Duration) {
+        self.duration_ms = duration_millis(duration);
+    }
+
+    pub fn set_prompt(&mut self, prompt: &str) {
+        let Some(max_body_bytes) = self.max_body_bytes else {
+            return;
+        };
+
+        self.prompt = Some(truncate_to_char_boundary(prompt, max_body_bytes).to_owned());
+        self.prompt_truncated = prompt.len() > max_body_bytes;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bodies_are_capped_at_a_character_boundary() {
+        let mut audit_log_entry = AuditLogEntry {
+            max_body_bytes: Some(5),
+            ..AuditLogEntry::default()
+        };
+
+        audit_log_entry.set_prompt("hello world");
+        audit_log_entry.append_completion("zaż");
+        audit_log_entry.append_completion("ółć");
+
+        assert_eq!(audit_log_entry.prompt.as_deref(), Some("hello"));
+        assert!(audit_log_entry.prompt_truncated);
+        assert_eq!(audit_log_entry.completion.as_deref(), Some("zaż"));
+        assert!(audit_log_entry.completion_truncated);
+    }
+
+    #[test]
+    fn bodies_are_left_out_unless_opted_in() {
+        let mut audit_log_entry = AuditLogEntry::default();
+
+        audit_log_entry.set_prompt("hello world");
+        audit_log_entry.append_completion("hi");
+
+        let serialized = serde_json::to_value(&audit_log_entry).unwrap();
+
+        assert!(serialized.get("prompt").is_none());
+        assert!(serialized.get("completion").is_none());
+        assert!(serialized.get("prompt_truncated").is_none());
+        assert!(serialized.get("max_body_bytes").is_none());
+    }
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use tokio::fs::File;
+use tokio::fs::OpenOptions;
+use tokio::io::AsyncWriteExt as _;
+use tokio::time::Duration;
+use tokio::time::Instant;
+
+/// Audit log file that is currently appended to.
+pub struct AuditLogFile {
+    file: File,
+    opened_at: Instant,
+    written_bytes: u64,
+}
+
+impl AuditLogFile {
+    pub async fn open(path: &Path) -> Result<Self> {
+        let file = OpenOptions::new()
+            .append(true)
+            .create(true)
+            .open(path)
+            .await
+            .context(format!("Unable to open the audit log {}", path.display()))?;
+        let written_bytes = file.metadata().await?.len();
+
+        Ok(Self {
+            file,
+            opened_at: Instant::now(),
+            written_bytes,
+        })
+    }
+
+    pub fn age(&self) -> Duration {
+        self.opened_at.elapsed()
+    }
+
+    pub async fn sync(&mut self) -> Result<()> {
+        self.file.flush().await?;
+
+        Ok(self.file.sync_data().await?)
+    }
+
+    pub async fn write_line(&mut self, line: &[u8]) -> Result<()> {
+        self.file.write_all(line).await?;
+        self.written_bytes += line.len() as u64;
+
+        Ok(())
+    }
+
+    pub const fn written_bytes(&self) -> u64 {
+        self.written_bytes
+    }
+}
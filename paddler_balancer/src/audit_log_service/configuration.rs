@@ -0,0 +1,12 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+#[derive(Clone)]
+pub struct Configuration {
+    /// Prompts and completions are recorded up to this many bytes each, and left out when
+    /// this is not set.
+    pub max_body_bytes: Option<usize>,
+    pub path: PathBuf,
+    pub rotate_after: Option<Duration>,
+    pub rotate_after_bytes: Option<u64>,
+}
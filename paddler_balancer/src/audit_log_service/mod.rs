@@ -0,0 +1,244 @@
+pub mod audit_log_file;
+pub mod configuration;
+
+use std::path::PathBuf;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use async_trait::async_trait;
+use log::error;
+use tokio::fs::rename;
+use tokio::fs::try_exists;
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+use trzcina::Service;
+
+use crate::audit_log_entry::AuditLogEntry;
+use crate::audit_log_service::audit_log_file::AuditLogFile;
+use crate::audit_log_service::configuration::Configuration as AuditLogServiceConfiguration;
+
+/// Appends audit log entries to a JSONL file. Once the file grows past the size limit or
+/// the rotation interval passes, it is renamed with a timestamp suffix and a fresh file is
+/// started in its place.
+pub struct AuditLogService {
+    pub configuration: AuditLogServiceConfiguration,
+    pub entry_rx: mpsc::Receiver<AuditLogEntry>,
+}
+
+impl AuditLogService {
+    /// Rotations within the same millisecond get a sequence number, so none overwrites
+    /// another.
+    async fn rotated_path(&self) -> Result<PathBuf> {
+        let rotated_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        let mut sequence: u32 = 0;
+
+        loop {
+            let mut rotated_path = self.configuration.path.clone().into_os_string();
+
+            if sequence == 0 {
+                rotated_path.push(format!(".{rotated_at}"));
+            } else {
+                rotated_path.push(format!(".{rotated_at}.{sequence}"));
+            }
+
+            if !try_exists(&rotated_path).await? {
+                return Ok(rotated_path.into());
+            }
+
+            sequence += 1;
+        }
+    }
+
+    async fn rotate(&self, audit_log_file: &mut AuditLogFile) -> Result<()> {
+        audit_log_file.sync().await?;
+        rename(&self.configuration.path, self.rotated_path().await?)
+            .await
+            .context("Unable to rotate the audit log")?;
+
+        *audit_log_file = AuditLogFile::open(&self.configuration.path).await?;
+
+        Ok(())
+    }
+
+    fn should_rotate(&self, audit_log_file: &AuditLogFile, line_bytes: u64) -> bool {
+        let is_too_old = self
+            .configuration
+            .rotate_after
+            .is_some_and(|rotate_after| audit_log_file.age() >= rotate_after);
+        let is_too_large =
+            self.configuration
+                .rotate_after_bytes
+                .is_some_and(|rotate_after_bytes| {
+                    audit_log_file.written_bytes() > 0
+                        && audit_log_file.written_bytes() + line_bytes > rotate_after_bytes
+                });
+
+        is_too_old || is_too_large
+    }
+
+    async fn write_entry(
+        &self,
+        audit_log_file: &mut AuditLogFile,
+        audit_log_entry: &AuditLogEntry,
+    ) -> Result<()> {
+        let mut line = serde_json::to_vec(audit_log_entry)?;
+
+        line.push(b'\n');
+
+        if self.should_rotate(audit_log_file, line.len() as u64) {
+            self.rotate(audit_log_file).await?;
+        }
+
+        audit_log_file.write_line(&line).await
+    }
+}
+
+#[async_trait]
+impl Service for AuditLogService {
+    fn name(&self) -> &'static str {
+        "balancer::audit_log_service"
+    }
+
+    async fn run(mut self: Box<Self>, shutdown: CancellationToken) -> Result<()> {
+        let mut audit_log_file = AuditLogFile::open(&self.configuration.path).await?;
+        let mut is_receiving = true;
+
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => {
+                    while let Ok(audit_log_entry) = self.entry_rx.try_recv() {
+                        if let Err(err) = self.write_entry(&mut audit_log_file, &audit_log_entry).await {
+                            error!("Failed to write audit log entry on shutdown: {err}");
+                        }
+                    }
+
+                    break audit_log_file.sync().await;
+                }
+                audit_log_entry = self.entry_rx.recv(), if is_receiving => {
+                    let Some(audit_log_entry) = audit_log_entry else {
+                        is_receiving = false;
+
+                        continue;
+                    };
+
+                    if let Err(err) = self.write_entry(&mut audit_log_file, &audit_log_entry).await {
+                        error!(
+                            "Failed to write audit log entry for request {:?}: {err}",
+                            audit_log_entry.request_id
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::audit_log::AUDIT_LOG_CHANNEL_CAPACITY;
+    use crate::audit_log::AuditLog;
+
+    fn read_entries(path: &std::path::Path) -> Vec<Value> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn writes_one_line_per_entry_and_rotates_by_size() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("audit.jsonl");
+        let (entry_tx, entry_rx) = mpsc::channel(AUDIT_LOG_CHANNEL_CAPACITY);
+        let audit_log = AuditLog::new(entry_tx, None);
+        let service = Box::new(AuditLogService {
+            configuration: AuditLogServiceConfiguration {
+                max_body_bytes: None,
+                path: path.clone(),
+                rotate_after: Some(Duration::from_hours(1)),
+                rotate_after_bytes: Some(1),
+            },
+            entry_rx,
+        });
+
+        for request_id in ["first", "second"] {
+            let mut audit_log_entry =
+                audit_log.start_entry("/v1/chat/completions".to_owned(), request_id.to_owned());
+
+            audit_log_entry.outcome = "succeeded";
+            audit_log.record(audit_log_entry);
+        }
+
+        let shutdown = CancellationToken::new();
+
+        shutdown.cancel();
+        service.run(shutdown).await.unwrap();
+
+        let rotated_paths: Vec<PathBuf> = std::fs::read_dir(directory.path())
+            .unwrap()
+            .map(|dir_entry| dir_entry.unwrap().path())
+            .filter(|dir_entry_path| *dir_entry_path != path)
+            .collect();
+
+        assert_eq!(rotated_paths.len(), 1);
+        assert_eq!(read_entries(&rotated_paths[0])[0]["request_id"], "first");
+
+        let entries = read_entries(&path);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["request_id"], "second");
+        assert_eq!(entries[0]["endpoint"], "/v1/chat/completions");
+        assert_eq!(entries[0]["outcome"], "succeeded");
+        assert_eq!(entries[0]["caller_key"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn rotations_within_the_same_millisecond_do_not_overwrite_each_other() {
+        let directory = TempDir::new().unwrap();
+        let path = directory.path().join("audit.jsonl");
+        let (entry_tx, entry_rx) = mpsc::channel(AUDIT_LOG_CHANNEL_CAPACITY);
+        let audit_log = AuditLog::new(entry_tx, None);
+        let service = Box::new(AuditLogService {
+            configuration: AuditLogServiceConfiguration {
+                max_body_bytes: None,
+                path: path.clone(),
+                rotate_after: None,
+                rotate_after_bytes: Some(1),
+            },
+            entry_rx,
+        });
+
+        for request_id in ["first", "second", "third", "fourth"] {
+            audit_log.record(
+                audit_log.start_entry("/v1/chat/completions".to_owned(), request_id.to_owned()),
+            );
+        }
+
+        let shutdown = CancellationToken::new();
+
+        shutdown.cancel();
+        service.run(shutdown).await.unwrap();
+
+        let mut request_ids: Vec<String> = std::fs::read_dir(directory.path())
+            .unwrap()
+            .flat_map(|dir_entry| read_entries(&dir_entry.unwrap().path()))
+            .map(|entry| entry["request_id"].as_str().unwrap().to_owned())
+            .collect();
+
+        request_ids.sort();
+
+        assert_eq!(request_ids, ["first", "fourth", "second", "third"]);
+    }
+}
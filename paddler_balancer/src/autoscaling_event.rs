@@ -0,0 +1,20 @@
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoscalingEvent {
+    /// Nothing was processed or buffered for longer than the idle threshold.
+    Idle,
+    /// Requests are buffered, but there are no agents to serve them.
+    ScaleFromZero,
+}
+
+impl AutoscalingEvent {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::ScaleFromZero => "scale_from_zero",
+        }
+    }
+}
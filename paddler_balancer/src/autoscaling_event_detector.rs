@@ -0,0 +1,133 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use paddler_messaging::autoscaling_recommendation::AutoscalingRecommendation;
+
+use crate::autoscaling_event::AutoscalingEvent;
+
+/// Reports each autoscaling event once when its condition starts to hold, and again only
+/// after the condition cleared in between.
+pub struct AutoscalingEventDetector {
+    idle_since: Option<Instant>,
+    idle_threshold: Duration,
+    is_idle_reported: bool,
+    is_scale_from_zero_reported: bool,
+}
+
+impl AutoscalingEventDetector {
+    #[must_use]
+    pub const fn new(idle_threshold: Duration) -> Self {
+        Self {
+            idle_since: None,
+            idle_threshold,
+            is_idle_reported: false,
+            is_scale_from_zero_reported: false,
+        }
+    }
+
+    pub fn observe(
+        &mut self,
+        recommendation: &AutoscalingRecommendation,
+        now: Instant,
+    ) -> Option<AutoscalingEvent> {
+        let is_waiting_for_agents =
+            recommendation.agents == 0 && recommendation.buffered_requests > 0;
+        let is_idle = recommendation.agents > 0
+            && recommendation.buffered_requests == 0
+            && recommendation.slots_processing == 0;
+
+        if !is_idle {
+            self.idle_since = None;
+            self.is_idle_reported = false;
+        }
+
+        if !is_waiting_for_agents {
+            self.is_scale_from_zero_reported = false;
+        }
+
+        if is_waiting_for_agents && !self.is_scale_from_zero_reported {
+            self.is_scale_from_zero_reported = true;
+
+            return Some(AutoscalingEvent::ScaleFromZero);
+        }
+
+        if is_idle && !self.is_idle_reported {
+            let idle_since = *self.idle_since.get_or_insert(now);
+
+            if now.saturating_duration_since(idle_since) >= self.idle_threshold {
+                self.is_idle_reported = true;
+
+                return Some(AutoscalingEvent::Idle);
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::queue_wait_trend::QueueWaitTrend;
+
+    use super::*;
+
+    fn recommendation(
+        agents: usize,
+        buffered_requests: i32,
+        slots_processing: i32,
+    ) -> AutoscalingRecommendation {
+        AutoscalingRecommendation {
+            agents,
+            buffered_requests,
+            queue_wait_average_ms: None,
+            queue_wait_trend: QueueWaitTrend::Steady,
+            recommended_agent_count: 0,
+            slot_utilization: 0.0,
+            slots_processing,
+            slots_total: 4,
+            target_utilization: 0.8,
+        }
+    }
+
+    #[test]
+    fn scale_from_zero_is_reported_once_per_outage() {
+        let mut detector = AutoscalingEventDetector::new(Duration::from_mins(5));
+        let now = Instant::now();
+
+        assert_eq!(
+            detector.observe(&recommendation(0, 2, 0), now),
+            Some(AutoscalingEvent::ScaleFromZero)
+        );
+        assert_eq!(detector.observe(&recommendation(0, 3, 0), now), None);
+        assert_eq!(detector.observe(&recommendation(1, 0, 3), now), None);
+        assert_eq!(
+            detector.observe(&recommendation(0, 1, 0), now),
+            Some(AutoscalingEvent::ScaleFromZero)
+        );
+    }
+
+    #[test]
+    fn idle_is_reported_after_the_threshold() {
+        let idle_threshold = Duration::from_mins(5);
+        let mut detector = AutoscalingEventDetector::new(idle_threshold);
+        let started_at = Instant::now();
+
+        assert_eq!(detector.observe(&recommendation(1, 0, 0), started_at), None);
+        assert_eq!(
+            detector.observe(&recommendation(1, 0, 0), started_at + idle_threshold),
+            Some(AutoscalingEvent::Idle)
+        );
+        assert_eq!(
+            detector.observe(&recommendation(1, 0, 0), started_at + idle_threshold * 2),
+            None
+        );
+        assert_eq!(
+            detector.observe(&recommendation(1, 0, 1), started_at + idle_threshold * 2),
+            None
+        );
+        assert_eq!(
+            detector.observe(&recommendation(1, 0, 0), started_at + idle_threshold * 3),
+            None
+        );
+    }
+}
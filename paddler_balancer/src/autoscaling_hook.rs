@@ -0,0 +1,149 @@
+use std::time::Duration;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use paddler_messaging::autoscaling_recommendation::AutoscalingRecommendation;
+use reqwest::Client;
+use serde_json::json;
+use tokio::process::Command;
+use tokio::time::timeout;
+use url::Url;
+
+use crate::autoscaling_event::AutoscalingEvent;
+
+/// What to notify when the cluster needs agents from zero, or has been idle for a while.
+#[derive(Clone, Debug)]
+pub enum AutoscalingHook {
+    /// Runs the command with `sh -c`, describing the event in `PADDLER_AUTOSCALING_*`
+    /// environment variables. The command is killed once it outlives the hook timeout.
+    Command(String),
+    /// POSTs the event and the current recommendation as JSON.
+    Webhook(Url),
+}
+
+impl AutoscalingHook {
+    pub async fn fire(
+        &self,
+        client: &Client,
+        event: AutoscalingEvent,
+        recommendation: &AutoscalingRecommendation,
+        hook_timeout: Duration,
+    ) -> Result<()> {
+        match self {
+            Self::Command(command) => {
+                let mut child = Command::new("sh")
+                    .arg("-c")
+                    .arg(command)
+                    .env(
+                        "PADDLER_AUTOSCALING_AGENTS",
+                        recommendation.agents.to_string(),
+                    )
+                    .env(
+                        "PADDLER_AUTOSCALING_BUFFERED_REQUESTS",
+                        recommendation.buffered_requests.to_string(),
+                    )
+                    .env("PADDLER_AUTOSCALING_EVENT", event.label())
+                    .env(
+                        "PADDLER_AUTOSCALING_RECOMMENDED_AGENT_COUNT",
+                        recommendation.recommended_agent_count.to_string(),
+                    )
+                    .kill_on_drop(true)
+                    .spawn()
+                    .context("Unable to start the autoscaling hook command")?;
+                let Ok(status) = timeout(hook_timeout, child.wait()).await else {
+                    child.kill().await?;
+
+                    bail!(
+                        "Autoscaling hook command did not finish within {}ms and was killed",
+                        hook_timeout.as_millis()
+                    );
+                };
+                let status = status?;
+
+                if !status.success() {
+                    bail!("Autoscaling hook command exited with {status}");
+                }
+            }
+            Self::Webhook(url) => {
+                client
+                    .post(url.clone())
+                    .json(&json!({
+                        "event": event,
+                        "recommendation": recommendation,
+                    }))
+                    .send()
+                    .await?
+                    .error_for_status()?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::queue_wait_trend::QueueWaitTrend;
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn recommendation() -> AutoscalingRecommendation {
+        AutoscalingRecommendation {
+            agents: 0,
+            buffered_requests: 2,
+            queue_wait_average_ms: None,
+            queue_wait_trend: QueueWaitTrend::Steady,
+            recommended_agent_count: 3,
+            slot_utilization: 0.0,
+            slots_processing: 0,
+            slots_total: 0,
+            target_utilization: 0.8,
+        }
+    }
+
+    #[tokio::test]
+    async fn command_receives_the_event_in_its_environment() {
+        let directory = TempDir::new().unwrap();
+        let output_path = directory.path().join("event");
+        let autoscaling_hook = AutoscalingHook::Command(format!(
+            "echo \"$PADDLER_AUTOSCALING_EVENT $PADDLER_AUTOSCALING_RECOMMENDED_AGENT_COUNT\" > {}",
+            output_path.display()
+        ));
+
+        autoscaling_hook
+            .fire(
+                &Client::new(),
+                AutoscalingEvent::ScaleFromZero,
+                &recommendation(),
+                Duration::from_secs(10),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(output_path).unwrap(),
+            "scale_from_zero 3\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn command_is_killed_once_it_outlives_the_timeout() {
+        let autoscaling_hook = AutoscalingHook::Command("sleep 30".to_owned());
+
+        let result = timeout(
+            Duration::from_secs(5),
+            autoscaling_hook.fire(
+                &Client::new(),
+                AutoscalingEvent::ScaleFromZero,
+                &recommendation(),
+                Duration::from_millis(100),
+            ),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.unwrap_err().to_string().contains("was killed"));
+    }
+}
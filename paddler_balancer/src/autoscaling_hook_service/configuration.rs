@@ -0,0 +1,9 @@
+use std::time::Duration;
+
+use crate::autoscaling_hook::AutoscalingHook;
+
+#[derive(Clone)]
+pub struct Configuration {
+    pub hook: AutoscalingHook,
+    pub idle_threshold: Duration,
+}
@@ -0,0 +1,71 @@
+pub mod configuration;
+
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use log::info;
+use log::warn;
+use reqwest::Client;
+use tokio::time::MissedTickBehavior;
+use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+use trzcina::Service;
+
+use crate::autoscaling_event_detector::AutoscalingEventDetector;
+use crate::autoscaling_hook_service::configuration::Configuration as AutoscalingHookServiceConfiguration;
+use crate::buffered_request_manager::BufferedRequestManager;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+const HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fires the autoscaling hook when requests wait for agents while none are connected, and
+/// when the cluster stays idle past the configured threshold.
+pub struct AutoscalingHookService {
+    pub buffered_request_manager: Arc<BufferedRequestManager>,
+    pub configuration: AutoscalingHookServiceConfiguration,
+}
+
+#[async_trait]
+impl Service for AutoscalingHookService {
+    fn name(&self) -> &'static str {
+        "balancer::autoscaling_hook_service"
+    }
+
+    async fn run(mut self: Box<Self>, shutdown: CancellationToken) -> Result<()> {
+        let client = Client::builder().timeout(HOOK_TIMEOUT).build()?;
+        let mut autoscaling_event_detector =
+            AutoscalingEventDetector::new(self.configuration.idle_threshold);
+        let mut ticker = interval(CHECK_INTERVAL);
+
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => break Ok(()),
+                _ = ticker.tick() => {
+                    let recommendation = self.buffered_request_manager.autoscaling_recommendation();
+
+                    let Some(event) =
+                        autoscaling_event_detector.observe(&recommendation, Instant::now())
+                    else {
+                        continue;
+                    };
+
+                    info!("Firing the autoscaling hook for the {} event", event.label());
+
+                    if let Err(err) = self
+                        .configuration
+                        .hook
+                        .fire(&client, event, &recommendation, HOOK_TIMEOUT)
+                        .await
+                    {
+                        warn!("Autoscaling hook failed for the {} event: {err}", event.label());
+                    }
+                }
+            }
+        }
+    }
+}
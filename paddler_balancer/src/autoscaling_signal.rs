@@ -0,0 +1,165 @@
+use std::sync::atomic::AtomicI32;
+use std::time::Instant;
+
+use paddler_messaging::atomic_value::AtomicValue;
+use paddler_messaging::autoscaling_recommendation::AutoscalingRecommendation;
+use paddler_messaging::queue_wait_trend::QueueWaitTrend;
+use parking_lot::Mutex;
+
+use crate::agent_controller_pool_total_slots::AgentControllerPoolTotalSlots;
+use crate::latency_histogram::LatencyHistogram;
+use crate::queue_wait_sampler::QueueWaitSampler;
+
+pub const DEFAULT_TARGET_UTILIZATION: f64 = 0.8;
+
+/// Turns the load of the cluster into the number of agents an autoscaler should run.
+pub struct AutoscalingSignal {
+    /// Remembered so the recommendation stays meaningful after scaling down to zero agents.
+    last_known_slots_per_agent: AtomicValue<AtomicI32>,
+    queue_wait_sampler: Mutex<QueueWaitSampler>,
+    target_utilization: f64,
+}
+
+impl AutoscalingSignal {
+    #[must_use]
+    pub fn new(target_utilization: f64) -> Self {
+        Self {
+            last_known_slots_per_agent: AtomicValue::<AtomicI32>::new(1),
+            queue_wait_sampler: Mutex::new(QueueWaitSampler::default()),
+            target_utilization,
+        }
+    }
+
+    pub fn recommend(
+        &self,
+        agents: usize,
+        buffered_requests: i32,
+        oldest_enqueued_at: Option<Instant>,
+        queue_wait: &LatencyHistogram,
+        AgentControllerPoolTotalSlots {
+            slots_processing,
+            slots_total,
+        }: AgentControllerPoolTotalSlots,
+    ) -> AutoscalingRecommendation {
+        let (queue_wait_average, queue_wait_trend) = {
+            let mut queue_wait_sampler = self.queue_wait_sampler.lock();
+
+            queue_wait_sampler.sample(queue_wait, oldest_enqueued_at, Instant::now());
+
+            (queue_wait_sampler.average(), queue_wait_sampler.trend())
+        };
+
+        if let Ok(agents) = i32::try_from(agents)
+            && agents > 0
+            && slots_total >= agents
+        {
+            self.last_known_slots_per_agent.set(slots_total / agents);
+        }
+
+        let demand = f64::from(slots_processing.max(0) + buffered_requests.max(0));
+        let capacity_per_agent =
+            f64::from(self.last_known_slots_per_agent.get()) * self.target_utilization;
+        let mut recommended_agent_count = if demand > 0.0 {
+            (demand / capacity_per_agent).ceil().max(1.0)
+        } else {
+            0.0
+        };
+
+        if buffered_requests > 0 && queue_wait_trend == QueueWaitTrend::Rising {
+            recommended_agent_count += 1.0;
+        }
+
+        AutoscalingRecommendation {
+            agents,
+            buffered_requests,
+            queue_wait_average_ms: queue_wait_average
+                .map(|average| u64::try_from(average.as_millis()).unwrap_or(u64::MAX)),
+            queue_wait_trend,
+            #[expect(
+                clippy::cast_possible_truncation,
+                clippy::cast_sign_loss,
+                reason = "the count is a small, non-negative whole number"
+            )]
+            recommended_agent_count: recommended_agent_count as usize,
+            slot_utilization: if slots_total > 0 {
+                f64::from(slots_processing) / f64::from(slots_total)
+            } else {
+                0.0
+            },
+            slots_processing,
+            slots_total,
+            target_utilization: self.target_utilization,
+        }
+    }
+}
+
+impl Default for AutoscalingSignal {
+    fn default() -> Self {
+        Self::new(DEFAULT_TARGET_UTILIZATION)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    fn total_slots(slots_processing: i32, slots_total: i32) -> AgentControllerPoolTotalSlots {
+        AgentControllerPoolTotalSlots {
+            slots_processing,
+            slots_total,
+        }
+    }
+
+    #[test]
+    fn recommends_enough_agents_to_reach_the_target_utilization() {
+        let autoscaling_signal = AutoscalingSignal::new(0.5);
+        let queue_wait = LatencyHistogram::default();
+
+        let recommendation =
+            autoscaling_signal.recommend(2, 0, None, &queue_wait, total_slots(8, 8));
+
+        assert_eq!(recommendation.recommended_agent_count, 4);
+        assert!((recommendation.slot_utilization - 1.0).abs() < f64::EPSILON);
+
+        let recommendation =
+            autoscaling_signal.recommend(2, 0, None, &queue_wait, total_slots(0, 8));
+
+        assert_eq!(recommendation.recommended_agent_count, 0);
+    }
+
+    #[test]
+    fn buffered_requests_without_agents_ask_for_at_least_one_agent() {
+        let autoscaling_signal = AutoscalingSignal::default();
+        let queue_wait = LatencyHistogram::default();
+
+        let recommendation =
+            autoscaling_signal.recommend(0, 3, None, &queue_wait, total_slots(0, 0));
+
+        assert_eq!(recommendation.recommended_agent_count, 4);
+
+        autoscaling_signal.recommend(1, 0, None, &queue_wait, total_slots(0, 4));
+
+        let recommendation =
+            autoscaling_signal.recommend(0, 3, None, &queue_wait, total_slots(0, 0));
+
+        assert_eq!(recommendation.recommended_agent_count, 1);
+    }
+
+    #[test]
+    fn requests_waiting_without_agents_ask_for_one_more_agent_as_the_wait_rises() {
+        let autoscaling_signal = AutoscalingSignal::default();
+        let queue_wait = LatencyHistogram::default();
+        let enqueued_at = Instant::now()
+            .checked_sub(Duration::from_secs(1))
+            .expect("the clock runs long enough");
+
+        let recommendation =
+            autoscaling_signal.recommend(0, 3, Some(enqueued_at), &queue_wait, total_slots(0, 0));
+
+        assert_eq!(recommendation.queue_wait_trend, QueueWaitTrend::Rising);
+        assert!(recommendation.queue_wait_average_ms >= Some(1000));
+        assert_eq!(recommendation.recommended_agent_count, 5);
+    }
+}
@@ -1,6 +1,155 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use anyhow::bail;
 use paddler_messaging::agent_desired_state::AgentDesiredState;
 
 #[derive(Clone, Debug)]
 pub struct BalancerApplicableState {
     pub agent_desired_state: AgentDesiredState,
+    pub model_pools: BTreeMap<String, AgentDesiredState>,
+}
+
+impl BalancerApplicableState {
+    #[must_use]
+    pub fn agent_desired_state_for_model_pool(
+        &self,
+        model_pool: Option<&str>,
+    ) -> Option<&AgentDesiredState> {
+        model_pool.map_or(Some(&self.agent_desired_state), |model_pool| {
+            self.model_pools.get(model_pool)
+        })
+    }
+
+    /// Requests name a pool either by its name or by the id of its model (as listed by
+    /// `/v1/models`). Requests naming no model, or the default pool's model, are served by the
+    /// default pool. While no named pools are configured, every model name is served by the
+    /// default pool, so single-pool clusters keep accepting whatever model clients send.
+    pub fn resolve_model_pool(&self, requested_model: Option<&str>) -> Result<Option<String>> {
+        let Some(requested_model) = requested_model else {
+            return Ok(None);
+        };
+
+        if self.model_pools.is_empty() {
+            return Ok(None);
+        }
+
+        if self.model_pools.contains_key(requested_model) {
+            return Ok(Some(requested_model.to_owned()));
+        }
+
+        if let Some((model_pool, _)) = self.model_pools.iter().find(|(_, agent_desired_state)| {
+            agent_desired_state.model.model_id().as_deref() == Some(requested_model)
+        }) {
+            return Ok(Some(model_pool.clone()));
+        }
+
+        if self.agent_desired_state.model.model_id().as_deref() == Some(requested_model) {
+            return Ok(None);
+        }
+
+        bail!("Model {requested_model:?} does not name a configured model pool")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::agent_desired_model::AgentDesiredModel;
+
+    use super::*;
+
+    fn make_applicable_state() -> BalancerApplicableState {
+        BalancerApplicableState {
+            agent_desired_state: AgentDesiredState {
+                model: AgentDesiredModel::LocalToAgent("chat.gguf".to_owned()),
+                ..AgentDesiredState::default()
+            },
+            model_pools: BTreeMap::from([(
+                "embeddings".to_owned(),
+                AgentDesiredState {
+                    model: AgentDesiredModel::LocalToAgent("embedding.gguf".to_owned()),
+                    ..AgentDesiredState::default()
+                },
+            )]),
+        }
+    }
+
+    #[test]
+    fn resolves_configured_model_pool() -> Result<()> {
+        assert_eq!(
+            make_applicable_state().resolve_model_pool(Some("embeddings"))?,
+            Some("embeddings".to_owned())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_model_pool_by_the_id_of_its_model() -> Result<()> {
+        assert_eq!(
+            make_applicable_state().resolve_model_pool(Some("embedding.gguf"))?,
+            Some("embeddings".to_owned())
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn resolves_missing_model_or_default_model_to_default_pool() -> Result<()> {
+        let applicable_state = make_applicable_state();
+
+        assert_eq!(applicable_state.resolve_model_pool(None)?, None);
+        assert_eq!(
+            applicable_state.resolve_model_pool(Some("chat.gguf"))?,
+            None
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unknown_model_pool() {
+        assert!(
+            make_applicable_state()
+                .resolve_model_pool(Some("gpt-4o"))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn resolves_any_model_to_default_pool_without_named_pools() -> Result<()> {
+        let applicable_state = BalancerApplicableState {
+            model_pools: BTreeMap::new(),
+            ..make_applicable_state()
+        };
+
+        assert_eq!(applicable_state.resolve_model_pool(Some("gpt-4o"))?, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn returns_agent_desired_state_for_model_pool() {
+        let applicable_state = make_applicable_state();
+
+        assert_eq!(
+            applicable_state
+                .agent_desired_state_for_model_pool(Some("embeddings"))
+                .map(|agent_desired_state| &agent_desired_state.model),
+            Some(&AgentDesiredModel::LocalToAgent(
+                "embedding.gguf".to_owned()
+            ))
+        );
+        assert_eq!(
+            applicable_state
+                .agent_desired_state_for_model_pool(None)
+                .map(|agent_desired_state| &agent_desired_state.model),
+            Some(&AgentDesiredModel::LocalToAgent("chat.gguf".to_owned()))
+        );
+        assert!(
+            applicable_state
+                .agent_desired_state_for_model_pool(Some("missing"))
+                .is_none()
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use anyhow::Result;
 use paddler_messaging::agent_desired_state::AgentDesiredState;
 use parking_lot::RwLock;
 use tokio::sync::watch;
@@ -18,6 +19,40 @@ impl BalancerApplicableStateHolder {
             .map(|state| state.agent_desired_state.clone())
     }
 
+    pub fn get_model_pool_agent_desired_state(
+        &self,
+        model_pool: Option<&str>,
+    ) -> Option<AgentDesiredState> {
+        self.balancer_applicable_state
+            .read()
+            .as_ref()
+            .and_then(|state| {
+                state
+                    .agent_desired_state_for_model_pool(model_pool)
+                    .cloned()
+            })
+    }
+
+    /// Agents may only join a named model pool the applicable state configures. Until the
+    /// state is applied, any pool is accepted and its agents wait for their desired state.
+    pub fn rejects_model_pool(&self, model_pool: Option<&str>) -> bool {
+        self.balancer_applicable_state
+            .read()
+            .as_ref()
+            .is_some_and(|state| {
+                state
+                    .agent_desired_state_for_model_pool(model_pool)
+                    .is_none()
+            })
+    }
+
+    pub fn resolve_model_pool(&self, requested_model: Option<&str>) -> Result<Option<String>> {
+        self.balancer_applicable_state
+            .read()
+            .as_ref()
+            .map_or(Ok(None), |state| state.resolve_model_pool(requested_model))
+    }
+
     pub fn get_balancer_applicable_state(&self) -> Option<BalancerApplicableState> {
         self.balancer_applicable_state.read().clone()
     }
@@ -55,6 +90,8 @@ impl SubscribesToUpdates for BalancerApplicableStateHolder {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use paddler_messaging::agent_desired_model::AgentDesiredModel;
     use paddler_messaging::inference_parameters::InferenceParameters;
     use tokio::time::Duration;
@@ -70,6 +107,7 @@ mod tests {
                 model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
                 multimodal_projection: AgentDesiredModel::None,
             },
+            model_pools: BTreeMap::new(),
         }
     }
 
@@ -137,4 +175,16 @@ mod tests {
         assert!(holder.get_balancer_applicable_state().is_none());
         assert!(holder.get_agent_desired_state().is_none());
     }
+
+    #[test]
+    fn rejects_unconfigured_model_pool_only_once_state_is_set() {
+        let holder = BalancerApplicableStateHolder::default();
+
+        assert!(!holder.rejects_model_pool(Some("embeddings")));
+
+        holder.set_balancer_applicable_state(Some(make_applicable_state()));
+
+        assert!(holder.rejects_model_pool(Some("embeddings")));
+        assert!(!holder.rejects_model_pool(None));
+    }
 }
@@ -1,12 +1,16 @@
+use std::mem::take;
+
 use anyhow::Result;
 use async_trait::async_trait;
 
 use paddler_messaging::agent_desired_state::AgentDesiredState;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
+use paddler_messaging::model_pool_desired_state::ModelPoolDesiredState;
 use paddler_state_conversion::converts_to_applicable_state::ConvertsToApplicableState;
 use paddler_state_conversion::converts_to_desired_state::ConvertsToDesiredState;
 
 use crate::balancer_applicable_state::BalancerApplicableState;
+use crate::model_pool_desired_state_converter::ModelPoolDesiredStateConverter;
 
 pub struct BalancerDesiredStateConverter;
 
@@ -20,20 +24,18 @@ impl ConvertsToDesiredState for BalancerDesiredStateConverter {
             chat_template_override,
             inference_parameters,
             model,
+            model_pools: _,
             multimodal_projection,
             use_chat_template_override,
         }: BalancerDesiredState,
     ) -> AgentDesiredState {
-        AgentDesiredState {
-            chat_template_override: if use_chat_template_override {
-                chat_template_override
-            } else {
-                None
-            },
+        ModelPoolDesiredStateConverter.to_desired_state(ModelPoolDesiredState {
+            chat_template_override,
             inference_parameters,
             model,
             multimodal_projection,
-        }
+            use_chat_template_override,
+        })
     }
 }
 
@@ -44,10 +46,21 @@ impl ConvertsToApplicableState for BalancerDesiredStateConverter {
 
     async fn to_applicable_state(
         &self,
-        desired_state: BalancerDesiredState,
+        mut desired_state: BalancerDesiredState,
     ) -> Result<BalancerApplicableState> {
+        let model_pools = take(&mut desired_state.model_pools)
+            .into_iter()
+            .map(|(model_pool_name, model_pool_desired_state)| {
+                (
+                    model_pool_name,
+                    ModelPoolDesiredStateConverter.to_desired_state(model_pool_desired_state),
+                )
+            })
+            .collect();
+
         Ok(BalancerApplicableState {
             agent_desired_state: self.to_desired_state(desired_state),
+            model_pools,
         })
     }
 }
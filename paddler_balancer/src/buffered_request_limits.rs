@@ -0,0 +1,8 @@
+use std::time::Duration;
+
+#[derive(Clone, Copy, Debug)]
+pub struct BufferedRequestLimits {
+    pub buffered_request_timeout: Duration,
+    /// Zero disables buffering for the class.
+    pub max_buffered_requests: i32,
+}
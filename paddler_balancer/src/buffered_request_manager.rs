@@ -1,7 +1,9 @@
+use std::collections::BTreeMap;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
+use paddler_messaging::autoscaling_recommendation::AutoscalingRecommendation;
 use paddler_messaging::buffered_request_manager_snapshot::BufferedRequestManagerSnapshot;
 use tokio::sync::watch;
 use tokio::time::timeout;
@@ -9,65 +11,136 @@ use tokio::time::timeout;
 use crate::agent_controller_pool::AgentControllerPool;
 use crate::buffered_request_agent_wait_result::BufferedRequestAgentWaitResult;
 use crate::buffered_request_counter::BufferedRequestCounter;
+use crate::buffered_request_limits::BufferedRequestLimits;
+use crate::buffered_request_queue::BufferedRequestQueue;
+use crate::dispatch_strategy::DispatchStrategy;
+use crate::request_observability::RequestObservability;
+use crate::request_priority::RequestPriority;
 use paddler_messaging::produces_snapshot::ProducesSnapshot;
 use paddler_messaging::subscribes_to_updates::SubscribesToUpdates;
 
 pub struct BufferedRequestManager {
     agent_controller_pool: Arc<AgentControllerPool>,
     pub buffered_request_counter: Arc<BufferedRequestCounter>,
-    buffered_request_timeout: Duration,
-    max_buffered_requests: i32,
+    buffered_request_limits: BTreeMap<RequestPriority, BufferedRequestLimits>,
+    buffered_request_queue: Arc<BufferedRequestQueue>,
+    pub observability: RequestObservability,
     update_tx: watch::Sender<()>,
 }
 
 impl BufferedRequestManager {
+    /// Applies the same limits to every priority class; use
+    /// `with_buffered_request_limits` to tune a class separately.
     #[must_use]
     pub fn new(
         agent_controller_pool: Arc<AgentControllerPool>,
         buffered_request_timeout: Duration,
         max_buffered_requests: i32,
+        observability: RequestObservability,
     ) -> Self {
         let (update_tx, _initial_rx) = watch::channel(());
 
         Self {
             agent_controller_pool,
             buffered_request_counter: Arc::new(BufferedRequestCounter::new(update_tx.clone())),
-            buffered_request_timeout,
-            max_buffered_requests,
+            buffered_request_limits: RequestPriority::ALL
+                .into_iter()
+                .map(|priority| {
+                    (
+                        priority,
+                        BufferedRequestLimits {
+                            buffered_request_timeout,
+                            max_buffered_requests,
+                        },
+                    )
+                })
+                .collect(),
+            buffered_request_queue: Arc::new(BufferedRequestQueue::new(update_tx.clone())),
+            observability,
             update_tx,
         }
     }
 
-    pub async fn wait_for_available_agent(&self) -> Result<BufferedRequestAgentWaitResult> {
-        // Quick path: a slot is available right now, no buffering needed.
-        if let Some(dispatched_agent) = self
-            .agent_controller_pool
-            .take_least_busy_agent_controller()
+    #[must_use]
+    pub fn with_buffered_request_limits(
+        mut self,
+        priority: RequestPriority,
+        buffered_request_limits: BufferedRequestLimits,
+    ) -> Self {
+        self.buffered_request_limits
+            .insert(priority, buffered_request_limits);
+
+        self
+    }
+
+    #[must_use]
+    pub fn autoscaling_recommendation(&self) -> AutoscalingRecommendation {
+        self.observability.autoscaling_signal.recommend(
+            self.agent_controller_pool.agents.len(),
+            self.buffered_request_counter.get(),
+            self.buffered_request_queue.oldest_enqueued_at(),
+            &self.observability.request_metrics.queue_wait,
+            self.agent_controller_pool.total_slots(),
+        )
+    }
+
+    pub async fn wait_for_available_agent(
+        &self,
+        model_pool: Option<&str>,
+        affinity_key: Option<u64>,
+        dispatch_strategy: DispatchStrategy,
+        priority: RequestPriority,
+    ) -> Result<BufferedRequestAgentWaitResult> {
+        // Quick path: a slot is available right now and nobody is queued ahead of us.
+        if self
+            .buffered_request_queue
+            .is_first_in_line(model_pool, priority, None)
+            && let Some(dispatched_agent) = self.agent_controller_pool.take_agent_controller(
+                model_pool,
+                affinity_key,
+                dispatch_strategy,
+            )
         {
             return Ok(BufferedRequestAgentWaitResult::Found(dispatched_agent));
         }
 
-        // Slot is busy — we would need to wait. Reject if the buffer is full
-        // (max_buffered_requests == 0 means buffering is disabled entirely).
-        if self.buffered_request_counter.get() >= self.max_buffered_requests {
+        let BufferedRequestLimits {
+            buffered_request_timeout,
+            max_buffered_requests,
+        } = self.buffered_request_limits[&priority];
+
+        // We would need to wait. Reject if the buffer of this class is full
+        // (max_buffered_requests == 0 means buffering is disabled for the class).
+        let Some(buffered_request_queue_ticket) = self.buffered_request_queue.enqueue(
+            model_pool,
+            priority,
+            usize::try_from(max_buffered_requests).unwrap_or(0),
+        ) else {
             return Ok(BufferedRequestAgentWaitResult::BufferOverflow);
-        }
-
+        };
         let _buffered_request_count_guard = self.buffered_request_counter.increment_with_guard();
         let agent_controller_pool = self.agent_controller_pool.clone();
-        let mut update_rx = agent_controller_pool.subscribe_to_updates();
+        let mut pool_update_rx = agent_controller_pool.subscribe_to_updates();
+        let mut queue_update_rx = self.update_tx.subscribe();
 
-        match timeout(self.buffered_request_timeout, async {
+        match timeout(buffered_request_timeout, async {
             loop {
-                if let Some(dispatched_agent) =
-                    agent_controller_pool.take_least_busy_agent_controller()
+                if buffered_request_queue_ticket.is_first_in_line()
+                    && let Some(dispatched_agent) = agent_controller_pool.take_agent_controller(
+                        model_pool,
+                        affinity_key,
+                        dispatch_strategy,
+                    )
                 {
                     return Ok::<_, anyhow::Error>(BufferedRequestAgentWaitResult::Found(
                         dispatched_agent,
                     ));
                 }
 
-                update_rx.changed().await?;
+                tokio::select! {
+                    changed = pool_update_rx.changed() => changed?,
+                    changed = queue_update_rx.changed() => changed?,
+                }
             }
         })
         .await
@@ -83,7 +156,14 @@ impl ProducesSnapshot for BufferedRequestManager {
 
     fn make_snapshot(&self) -> Result<Self::Snapshot> {
         Ok(BufferedRequestManagerSnapshot {
+            buffered_requests_batch: i32::try_from(
+                self.buffered_request_queue.depth(RequestPriority::Batch),
+            )?,
             buffered_requests_current: self.buffered_request_counter.get(),
+            buffered_requests_interactive: i32::try_from(
+                self.buffered_request_queue
+                    .depth(RequestPriority::Interactive),
+            )?,
         })
     }
 }
@@ -96,60 +176,30 @@ impl SubscribesToUpdates for BufferedRequestManager {
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
     use std::mem::Discriminant;
     use std::mem::discriminant;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
 
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
     use tokio::sync::mpsc;
-    use tokio_util::sync::CancellationToken;
 
     use super::*;
     use crate::agent_controller::AgentController;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
-    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
-    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
     use paddler_messaging::atomic_value::AtomicValue;
 
     fn found_result_discriminant() -> Discriminant<BufferedRequestAgentWaitResult> {
         let pool = AgentControllerPool::default();
         let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
         let agent = Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
             desired_slots_total: AtomicValue::<AtomicI32>::new(1),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: "agent-discriminant".to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(1),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test("agent-discriminant", agent_message_tx)
         });
 
         pool.register_agent_controller("agent-discriminant".to_owned(), agent)
             .unwrap();
 
-        let dispatched_agent = pool.take_least_busy_agent_controller().unwrap();
+        let dispatched_agent = pool.take_least_busy_agent_controller(None).unwrap();
 
         discriminant(&BufferedRequestAgentWaitResult::Found(dispatched_agent))
     }
@@ -161,6 +211,7 @@ mod tests {
             pool,
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ));
 
         let mut update_rx = manager.subscribe_to_updates();
@@ -184,10 +235,19 @@ mod tests {
             pool.clone(),
             Duration::from_mins(1),
             10,
+            RequestObservability::default(),
         ));
 
-        let mut waiter =
-            tokio_test::task::spawn(async move { manager.wait_for_available_agent().await });
+        let mut waiter = tokio_test::task::spawn(async move {
+            manager
+                .wait_for_available_agent(
+                    None,
+                    None,
+                    DispatchStrategy::LeastBusy,
+                    RequestPriority::Interactive,
+                )
+                .await
+        });
 
         assert!(
             waiter.poll().is_pending(),
@@ -196,30 +256,9 @@ mod tests {
 
         let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
         let agent = Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
             desired_slots_total: AtomicValue::<AtomicI32>::new(1),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: "agent-1".to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(1),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test("agent-1", agent_message_tx)
         });
 
         pool.register_agent_controller("agent-1".to_owned(), agent)
@@ -245,30 +284,9 @@ mod tests {
 
         let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
         let agent = Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
             desired_slots_total: AtomicValue::<AtomicI32>::new(1),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: "agent-pre".to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(1),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test("agent-pre", agent_message_tx)
         });
 
         pool.register_agent_controller("agent-pre".to_owned(), agent)
@@ -278,9 +296,18 @@ mod tests {
             pool,
             Duration::from_mins(1),
             10,
+            RequestObservability::default(),
         ));
 
-        let result = manager.wait_for_available_agent().await.unwrap();
+        let result = manager
+            .wait_for_available_agent(
+                None,
+                None,
+                DispatchStrategy::LeastBusy,
+                RequestPriority::Interactive,
+            )
+            .await
+            .unwrap();
 
         assert_eq!(
             discriminant(&result),
@@ -288,4 +315,152 @@ mod tests {
             "waiter must return Found when an agent is already in the pool"
         );
     }
+
+    fn single_slot_agent_controller(id: &str) -> Arc<AgentController> {
+        let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
+
+        Arc::new(AgentController {
+            desired_slots_total: AtomicValue::<AtomicI32>::new(1),
+            slots_total: AtomicValue::<AtomicI32>::new(1),
+            ..AgentController::new_for_test(id, agent_message_tx)
+        })
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn interactive_waiter_is_served_before_an_earlier_batch_waiter() {
+        let pool = Arc::new(AgentControllerPool::default());
+
+        pool.register_agent_controller(
+            "agent-single".to_owned(),
+            single_slot_agent_controller("agent-single"),
+        )
+        .unwrap();
+
+        let manager = Arc::new(BufferedRequestManager::new(
+            pool,
+            Duration::from_mins(1),
+            10,
+            RequestObservability::default(),
+        ));
+        let busy_slot = manager
+            .wait_for_available_agent(
+                None,
+                None,
+                DispatchStrategy::LeastBusy,
+                RequestPriority::Interactive,
+            )
+            .await
+            .unwrap();
+
+        let mut batch_waiter = tokio_test::task::spawn({
+            let manager = manager.clone();
+
+            async move {
+                manager
+                    .wait_for_available_agent(
+                        None,
+                        None,
+                        DispatchStrategy::LeastBusy,
+                        RequestPriority::Batch,
+                    )
+                    .await
+            }
+        });
+
+        assert!(batch_waiter.poll().is_pending());
+
+        let mut interactive_waiter = tokio_test::task::spawn({
+            let manager = manager.clone();
+
+            async move {
+                manager
+                    .wait_for_available_agent(
+                        None,
+                        None,
+                        DispatchStrategy::LeastBusy,
+                        RequestPriority::Interactive,
+                    )
+                    .await
+            }
+        });
+
+        assert!(interactive_waiter.poll().is_pending());
+
+        let snapshot = manager.make_snapshot().unwrap();
+
+        assert_eq!(snapshot.buffered_requests_batch, 1);
+        assert_eq!(snapshot.buffered_requests_current, 2);
+        assert_eq!(snapshot.buffered_requests_interactive, 1);
+
+        drop(busy_slot);
+
+        assert!(
+            batch_waiter.poll().is_pending(),
+            "batch waiter must not take the slot while an interactive request is queued"
+        );
+
+        let interactive_slot = interactive_waiter.await.unwrap();
+
+        assert!(matches!(
+            interactive_slot,
+            BufferedRequestAgentWaitResult::Found(_)
+        ));
+        assert!(batch_waiter.poll().is_pending());
+
+        drop(interactive_slot);
+
+        assert!(matches!(
+            batch_waiter.await.unwrap(),
+            BufferedRequestAgentWaitResult::Found(_)
+        ));
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn rejects_batch_requests_when_the_batch_buffer_is_full() {
+        let pool = Arc::new(AgentControllerPool::default());
+        let manager = Arc::new(
+            BufferedRequestManager::new(
+                pool,
+                Duration::from_mins(1),
+                10,
+                RequestObservability::default(),
+            )
+            .with_buffered_request_limits(
+                RequestPriority::Batch,
+                BufferedRequestLimits {
+                    buffered_request_timeout: Duration::from_mins(1),
+                    max_buffered_requests: 0,
+                },
+            ),
+        );
+
+        let mut interactive_waiter = tokio_test::task::spawn({
+            let manager = manager.clone();
+
+            async move {
+                manager
+                    .wait_for_available_agent(
+                        None,
+                        None,
+                        DispatchStrategy::LeastBusy,
+                        RequestPriority::Interactive,
+                    )
+                    .await
+            }
+        });
+
+        assert!(interactive_waiter.poll().is_pending());
+        assert!(matches!(
+            manager
+                .wait_for_available_agent(
+                    None,
+                    None,
+                    DispatchStrategy::LeastBusy,
+                    RequestPriority::Batch
+                )
+                .await
+                .unwrap(),
+            BufferedRequestAgentWaitResult::BufferOverflow
+        ));
+    }
 }
@@ -0,0 +1,231 @@
+use std::collections::BTreeMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+use tokio::sync::watch;
+
+use crate::buffered_request_queue_ticket::BufferedRequestQueueTicket;
+use crate::request_priority::RequestPriority;
+
+struct QueuedRequest {
+    enqueued_at: Instant,
+    model_pool: Option<String>,
+    ticket_number: u64,
+}
+
+/// Waiting line of buffered requests, one FIFO per priority class. Requests only
+/// compete with others waiting for the same model pool.
+pub struct BufferedRequestQueue {
+    next_ticket_number: AtomicU64,
+    queued_requests: Mutex<BTreeMap<RequestPriority, VecDeque<QueuedRequest>>>,
+    update_tx: watch::Sender<()>,
+}
+
+impl BufferedRequestQueue {
+    pub const fn new(update_tx: watch::Sender<()>) -> Self {
+        Self {
+            next_ticket_number: AtomicU64::new(0),
+            queued_requests: Mutex::new(BTreeMap::new()),
+            update_tx,
+        }
+    }
+
+    pub fn depth(&self, priority: RequestPriority) -> usize {
+        self.queued_requests
+            .lock()
+            .get(&priority)
+            .map_or(0, VecDeque::len)
+    }
+
+    /// Joins the line unless `max_depth` requests of the priority class are already
+    /// waiting. The check and the insert share one lock, so concurrent requests cannot
+    /// push a class past its limit.
+    pub fn enqueue(
+        self: &Arc<Self>,
+        model_pool: Option<&str>,
+        priority: RequestPriority,
+        max_depth: usize,
+    ) -> Option<BufferedRequestQueueTicket> {
+        let ticket_number = {
+            let mut queued_requests = self.queued_requests.lock();
+            let class_requests = queued_requests.entry(priority).or_default();
+
+            if class_requests.len() >= max_depth {
+                return None;
+            }
+
+            let ticket_number = self.next_ticket_number.fetch_add(1, Ordering::Relaxed);
+
+            class_requests.push_back(QueuedRequest {
+                enqueued_at: Instant::now(),
+                model_pool: model_pool.map(str::to_owned),
+                ticket_number,
+            });
+
+            ticket_number
+        };
+
+        self.update_tx.send_replace(());
+
+        Some(BufferedRequestQueueTicket::new(
+            self.clone(),
+            model_pool.map(str::to_owned),
+            priority,
+            ticket_number,
+        ))
+    }
+
+    /// When the request waiting the longest, across every priority class and model pool,
+    /// was enqueued.
+    pub fn oldest_enqueued_at(&self) -> Option<Instant> {
+        self.queued_requests
+            .lock()
+            .values()
+            .filter_map(|queued_requests| queued_requests.front())
+            .map(|queued_request| queued_request.enqueued_at)
+            .min()
+    }
+
+    /// `true` when nothing with the same or a higher priority is waiting for the
+    /// model pool ahead of the given ticket (or at all, when there is no ticket).
+    pub fn is_first_in_line(
+        &self,
+        model_pool: Option<&str>,
+        priority: RequestPriority,
+        ticket_number: Option<u64>,
+    ) -> bool {
+        !self
+            .queued_requests
+            .lock()
+            .range(..=priority)
+            .any(|(queued_priority, queued_requests)| {
+                queued_requests.iter().any(|queued_request| {
+                    queued_request.model_pool.as_deref() == model_pool
+                        && (*queued_priority < priority
+                            || ticket_number.is_none_or(|ticket_number| {
+                                queued_request.ticket_number < ticket_number
+                            }))
+                })
+            })
+    }
+
+    pub fn remove(&self, priority: RequestPriority, ticket_number: u64) {
+        if let Some(queued_requests) = self.queued_requests.lock().get_mut(&priority) {
+            queued_requests.retain(|queued_request| queued_request.ticket_number != ticket_number);
+        }
+
+        self.update_tx.send_replace(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_queue() -> Arc<BufferedRequestQueue> {
+        let (update_tx, _initial_rx) = watch::channel(());
+
+        Arc::new(BufferedRequestQueue::new(update_tx))
+    }
+
+    #[test]
+    fn serves_requests_in_arrival_order_within_a_class() {
+        let queue = make_queue();
+        let first = queue
+            .enqueue(None, RequestPriority::Interactive, usize::MAX)
+            .unwrap();
+        let second = queue
+            .enqueue(None, RequestPriority::Interactive, usize::MAX)
+            .unwrap();
+
+        assert!(first.is_first_in_line());
+        assert!(!second.is_first_in_line());
+
+        drop(first);
+
+        assert!(second.is_first_in_line());
+        assert_eq!(queue.depth(RequestPriority::Interactive), 1);
+    }
+
+    #[test]
+    fn interactive_requests_go_ahead_of_batch_requests() {
+        let queue = make_queue();
+        let batch = queue
+            .enqueue(None, RequestPriority::Batch, usize::MAX)
+            .unwrap();
+
+        assert!(queue.is_first_in_line(None, RequestPriority::Interactive, None));
+        assert!(!queue.is_first_in_line(None, RequestPriority::Batch, None));
+
+        let interactive = queue
+            .enqueue(None, RequestPriority::Interactive, usize::MAX)
+            .unwrap();
+
+        assert!(interactive.is_first_in_line());
+        assert!(!batch.is_first_in_line());
+        assert!(!queue.is_first_in_line(None, RequestPriority::Interactive, None));
+
+        drop(interactive);
+
+        assert!(batch.is_first_in_line());
+    }
+
+    #[test]
+    fn model_pools_do_not_block_each_other() {
+        let queue = make_queue();
+        let _embeddings = queue
+            .enqueue(Some("embeddings"), RequestPriority::Interactive, usize::MAX)
+            .unwrap();
+        let chat = queue
+            .enqueue(None, RequestPriority::Batch, usize::MAX)
+            .unwrap();
+
+        assert!(chat.is_first_in_line());
+        assert_eq!(queue.depth(RequestPriority::Interactive), 1);
+        assert_eq!(queue.depth(RequestPriority::Batch), 1);
+    }
+
+    #[test]
+    fn reports_when_the_longest_waiting_request_was_enqueued() {
+        let queue = make_queue();
+
+        assert_eq!(queue.oldest_enqueued_at(), None);
+
+        let batch = queue
+            .enqueue(None, RequestPriority::Batch, usize::MAX)
+            .unwrap();
+        let batch_enqueued_at = queue.oldest_enqueued_at();
+        let interactive = queue
+            .enqueue(None, RequestPriority::Interactive, usize::MAX)
+            .unwrap();
+
+        assert_eq!(queue.oldest_enqueued_at(), batch_enqueued_at);
+
+        drop(batch);
+
+        assert!(queue.oldest_enqueued_at() >= batch_enqueued_at);
+
+        drop(interactive);
+
+        assert_eq!(queue.oldest_enqueued_at(), None);
+    }
+
+    #[test]
+    fn refuses_to_queue_past_the_depth_of_a_class() {
+        let queue = make_queue();
+        let _first = queue.enqueue(None, RequestPriority::Batch, 1).unwrap();
+
+        assert!(queue.enqueue(None, RequestPriority::Batch, 1).is_none());
+        assert!(
+            queue
+                .enqueue(None, RequestPriority::Interactive, 1)
+                .is_some()
+        );
+        assert!(queue.enqueue(None, RequestPriority::Batch, 0).is_none());
+        assert_eq!(queue.depth(RequestPriority::Batch), 1);
+    }
+}
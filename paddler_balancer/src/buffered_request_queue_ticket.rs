@@ -0,0 +1,43 @@
+use std::sync::Arc;
+
+use crate::buffered_request_queue::BufferedRequestQueue;
+use crate::request_priority::RequestPriority;
+
+/// Place of a request in the `BufferedRequestQueue`, given up when dropped.
+pub struct BufferedRequestQueueTicket {
+    buffered_request_queue: Arc<BufferedRequestQueue>,
+    model_pool: Option<String>,
+    priority: RequestPriority,
+    ticket_number: u64,
+}
+
+impl BufferedRequestQueueTicket {
+    pub const fn new(
+        buffered_request_queue: Arc<BufferedRequestQueue>,
+        model_pool: Option<String>,
+        priority: RequestPriority,
+        ticket_number: u64,
+    ) -> Self {
+        Self {
+            buffered_request_queue,
+            model_pool,
+            priority,
+            ticket_number,
+        }
+    }
+
+    pub fn is_first_in_line(&self) -> bool {
+        self.buffered_request_queue.is_first_in_line(
+            self.model_pool.as_deref(),
+            self.priority,
+            Some(self.ticket_number),
+        )
+    }
+}
+
+impl Drop for BufferedRequestQueueTicket {
+    fn drop(&mut self) {
+        self.buffered_request_queue
+            .remove(self.priority, self.ticket_number);
+    }
+}
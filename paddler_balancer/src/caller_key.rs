@@ -0,0 +1,49 @@
+use std::fmt::Write as _;
+
+use serde::Serialize;
+use sha2::Digest as _;
+use sha2::Sha256;
+
+/// Number of leading digest bytes kept, enough to tell the keys of a deployment apart.
+const FINGERPRINT_BYTES: usize = 8;
+
+/// Identifies the API key a request was made with, without revealing the key itself.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct CallerKey(String);
+
+impl CallerKey {
+    #[must_use]
+    pub fn from_api_key(api_key: &str) -> Self {
+        let digest = Sha256::digest(api_key.as_bytes());
+
+        Self(digest[..FINGERPRINT_BYTES].iter().fold(
+            "sha256:".to_owned(),
+            |mut fingerprint, byte| {
+                let _ = write!(fingerprint, "{byte:02x}");
+                fingerprint
+            },
+        ))
+    }
+
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints_the_api_key() {
+        let caller_key = CallerKey::from_api_key("sk-valid");
+
+        assert!(caller_key.as_str().starts_with("sha256:"));
+        assert_eq!(caller_key.as_str().len(), "sha256:".len() + 16);
+        assert!(!caller_key.as_str().contains("sk-valid"));
+        assert_eq!(caller_key, CallerKey::from_api_key("sk-valid"));
+        assert_ne!(caller_key, CallerKey::from_api_key("sk-other"));
+    }
+}
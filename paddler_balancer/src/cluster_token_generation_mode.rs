@@ -13,9 +13,10 @@ impl ClusterTokenGenerationMode {
     #[must_use]
     pub fn from_applicable_state_holder(
         balancer_applicable_state_holder: &BalancerApplicableStateHolder,
+        model_pool: Option<&str>,
     ) -> Self {
         if let Some(agent_desired_state) =
-            balancer_applicable_state_holder.get_agent_desired_state()
+            balancer_applicable_state_holder.get_model_pool_agent_desired_state(model_pool)
             && agent_desired_state.inference_parameters.enable_embeddings
         {
             Self::DisabledForEmbeddings
@@ -27,6 +28,8 @@ impl ClusterTokenGenerationMode {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use paddler_messaging::agent_desired_model::AgentDesiredModel;
     use paddler_messaging::agent_desired_state::AgentDesiredState;
     use paddler_messaging::inference_parameters::InferenceParameters;
@@ -49,6 +52,15 @@ mod tests {
                     model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
                     multimodal_projection: AgentDesiredModel::None,
                 },
+                model_pools: BTreeMap::from([(
+                    "chat".to_owned(),
+                    AgentDesiredState {
+                        chat_template_override: None,
+                        inference_parameters: InferenceParameters::default(),
+                        model: AgentDesiredModel::LocalToAgent("chat.gguf".to_owned()),
+                        multimodal_projection: AgentDesiredModel::None,
+                    },
+                )]),
             },
         ));
 
@@ -61,7 +73,8 @@ mod tests {
 
         assert_eq!(
             ClusterTokenGenerationMode::from_applicable_state_holder(
-                &balancer_applicable_state_holder
+                &balancer_applicable_state_holder,
+                None
             ),
             ClusterTokenGenerationMode::Enabled
         );
@@ -70,9 +83,10 @@ mod tests {
     #[test]
     fn enabled_when_embeddings_are_disabled() {
         assert_eq!(
-            ClusterTokenGenerationMode::from_applicable_state_holder(&holder_with_embeddings(
-                false
-            )),
+            ClusterTokenGenerationMode::from_applicable_state_holder(
+                &holder_with_embeddings(false),
+                None
+            ),
             ClusterTokenGenerationMode::Enabled
         );
     }
@@ -80,8 +94,22 @@ mod tests {
     #[test]
     fn disabled_for_embeddings_when_embeddings_are_enabled() {
         assert_eq!(
-            ClusterTokenGenerationMode::from_applicable_state_holder(&holder_with_embeddings(true)),
+            ClusterTokenGenerationMode::from_applicable_state_holder(
+                &holder_with_embeddings(true),
+                None
+            ),
             ClusterTokenGenerationMode::DisabledForEmbeddings
         );
     }
+
+    #[test]
+    fn model_pool_uses_its_own_inference_parameters() {
+        assert_eq!(
+            ClusterTokenGenerationMode::from_applicable_state_holder(
+                &holder_with_embeddings(true),
+                Some("chat")
+            ),
+            ClusterTokenGenerationMode::Enabled
+        );
+    }
 }
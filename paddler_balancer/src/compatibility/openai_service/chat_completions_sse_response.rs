@@ -19,17 +19,21 @@ use crate::chunk_forwarding_session_controller::transforms_outgoing_message::Tra
 use crate::handles_agent_streaming_response::HandlesAgentStreamingResponse;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::manages_senders::ManagesSenders;
+use crate::provides_affinity_key::ProvidesAffinityKey;
+use crate::request_admission::RequestAdmission;
 use crate::unbounded_stream_from_agent::unbounded_stream_from_agent;
 
 pub fn chat_completions_sse_response<TParams, TTransformsOutgoingMessage>(
     buffered_request_manager: Arc<BufferedRequestManager>,
     inference_service_configuration: InferenceServiceConfiguration,
+    model_pool: Option<String>,
     params: TParams,
+    request_admission: RequestAdmission,
     transformer: TTransformsOutgoingMessage,
     shutdown: CancellationToken,
 ) -> HttpResponse
 where
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send + 'static,
+    TParams: Clone + Debug + Into<AgentJsonRpcRequest> + ProvidesAffinityKey + Send + 'static,
     AgentController: HandlesAgentStreamingResponse<TParams>,
     <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
     TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage<Output = TransformResult> + Send + Sync + 'static,
@@ -37,7 +41,9 @@ where
     let event_stream = unbounded_stream_from_agent(
         buffered_request_manager,
         inference_service_configuration,
+        model_pool,
         params,
+        request_admission,
         transformer,
         shutdown,
     )
@@ -72,9 +78,13 @@ mod tests {
 
     use super::chat_completions_sse_response;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
     use crate::buffered_request_manager::BufferedRequestManager;
     use crate::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
+    use crate::dispatch_strategy::DispatchStrategy;
     use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+    use crate::request_admission::RequestAdmission;
+    use crate::request_observability::RequestObservability;
     use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 
     fn empty_pool_manager() -> Arc<BufferedRequestManager> {
@@ -82,22 +92,31 @@ mod tests {
             Arc::new(AgentControllerPool::default()),
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ))
     }
 
     fn inference_service_configuration() -> InferenceServiceConfiguration {
         InferenceServiceConfiguration {
             addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            agent_failover_policy: AgentFailoverPolicy::Disabled,
             cors_allowed_hosts: Vec::new(),
+            dispatch_strategy: DispatchStrategy::LeastBusy,
             inference_item_timeout: Duration::from_secs(1),
+            tls_configuration: None,
         }
     }
 
     fn raw_prompt_params() -> ContinueFromRawPromptParams {
         ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 1,
+            model: None,
             raw_prompt: "hello".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         }
     }
 
@@ -109,7 +128,9 @@ mod tests {
         let response = chat_completions_sse_response(
             empty_pool_manager(),
             inference_service_configuration(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             IdentityTransformer::new(),
             shutdown,
         );
@@ -1,6 +1,9 @@
 use std::net::SocketAddr;
 
+use crate::tls_configuration::TlsConfiguration;
+
 #[derive(Clone)]
 pub struct Configuration {
     pub addr: SocketAddr,
+    pub tls_configuration: Option<TlsConfiguration>,
 }
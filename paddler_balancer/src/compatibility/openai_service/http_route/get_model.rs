@@ -0,0 +1,153 @@
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::web;
+use serde::Deserialize;
+
+use crate::compatibility::openai_service::app_data::AppData;
+use crate::compatibility::openai_service::list_openai_models::list_openai_models;
+use crate::compatibility::openai_service::openai_error::OpenAIError;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PathParams {
+    model_id: String,
+}
+
+/// Hugging Face model ids contain slashes, so the id spans the rest of the path.
+#[get("/v1/models/{model_id:.*}")]
+async fn respond(app_data: web::Data<AppData>, params: web::Path<PathParams>) -> HttpResponse {
+    let model = list_openai_models(
+        &app_data.agent_controller_pool,
+        &app_data.balancer_applicable_state_holder,
+    )
+    .await
+    .into_iter()
+    .find(|model| model.id == params.model_id);
+
+    match model {
+        Some(model) => HttpResponse::Ok().json(model),
+        None => HttpResponse::NotFound().json(
+            OpenAIError {
+                error_type: "invalid_request_error",
+                message: format!("The model '{}' does not exist", params.model_id),
+            }
+            .to_envelope(),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::net::Ipv4Addr;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use actix_web::App;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::read_body_json;
+    use actix_web::web::Data;
+    use paddler_messaging::agent_desired_model::AgentDesiredModel;
+    use paddler_messaging::agent_desired_state::AgentDesiredState;
+    use paddler_messaging::huggingface_model_reference::HuggingFaceModelReference;
+    use serde_json::Value;
+    use tokio_util::sync::CancellationToken;
+
+    use super::register;
+    use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
+    use crate::balancer_applicable_state::BalancerApplicableState;
+    use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+    use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::compatibility::openai_service::app_data::AppData;
+    use crate::dispatch_strategy::DispatchStrategy;
+    use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+    use crate::request_observability::RequestObservability;
+
+    fn app_data() -> AppData {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+        let balancer_applicable_state_holder = Arc::new(BalancerApplicableStateHolder::default());
+
+        balancer_applicable_state_holder.set_balancer_applicable_state(Some(
+            BalancerApplicableState {
+                agent_desired_state: AgentDesiredState {
+                    model: AgentDesiredModel::HuggingFace(HuggingFaceModelReference {
+                        filename: "Qwen3-0.6B-Q8_0.gguf".to_owned(),
+                        repo_id: "Qwen/Qwen3-0.6B-GGUF".to_owned(),
+                        revision: "main".to_owned(),
+                    }),
+                    ..AgentDesiredState::default()
+                },
+                model_pools: BTreeMap::new(),
+            },
+        ));
+
+        AppData {
+            agent_controller_pool: agent_controller_pool.clone(),
+            balancer_applicable_state_holder,
+            buffered_request_manager: Arc::new(BufferedRequestManager::new(
+                agent_controller_pool,
+                Duration::ZERO,
+                0,
+                RequestObservability::default(),
+            )),
+            inference_service_configuration: InferenceServiceConfiguration {
+                addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
+                cors_allowed_hosts: Vec::new(),
+                dispatch_strategy: DispatchStrategy::LeastBusy,
+                inference_item_timeout: Duration::ZERO,
+                tls_configuration: None,
+            },
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    #[actix_web::test]
+    async fn responds_with_the_model_matching_an_id_with_slashes() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data()))
+                .configure(register),
+        )
+        .await;
+        let request = TestRequest::get()
+            .uri("/v1/models/Qwen/Qwen3-0.6B-GGUF/Qwen3-0.6B-Q8_0.gguf")
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let model: Value = read_body_json(response).await;
+
+        assert_eq!(model["id"], "Qwen/Qwen3-0.6B-GGUF/Qwen3-0.6B-Q8_0.gguf");
+        assert_eq!(model["object"], "model");
+        assert_eq!(model["owned_by"], "Qwen");
+    }
+
+    #[actix_web::test]
+    async fn responds_not_found_for_an_unknown_model() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data()))
+                .configure(register),
+        )
+        .await;
+        let request = TestRequest::get().uri("/v1/models/gpt-4o").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let error: Value = read_body_json(response).await;
+
+        assert_eq!(error["error"]["type"], "invalid_request_error");
+    }
+}
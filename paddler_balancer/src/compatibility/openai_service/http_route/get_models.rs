@@ -0,0 +1,25 @@
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::web;
+use serde_json::json;
+
+use crate::compatibility::openai_service::app_data::AppData;
+use crate::compatibility::openai_service::list_openai_models::list_openai_models;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[get("/v1/models")]
+async fn respond(app_data: web::Data<AppData>) -> HttpResponse {
+    let models = list_openai_models(
+        &app_data.agent_controller_pool,
+        &app_data.balancer_applicable_state_holder,
+    )
+    .await;
+
+    HttpResponse::Ok().json(json!({
+        "object": "list",
+        "data": models,
+    }))
+}
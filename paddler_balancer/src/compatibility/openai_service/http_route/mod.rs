@@ -1,2 +1,6 @@
+pub mod get_model;
+pub mod get_models;
 pub mod post_chat_completions;
+pub mod post_completions;
+pub mod post_embeddings;
 pub mod post_responses;
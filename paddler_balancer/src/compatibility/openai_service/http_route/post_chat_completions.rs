@@ -1,8 +1,11 @@
 use std::sync::Arc;
+use std::time::Instant;
 use std::time::SystemTime;
 
 use actix_web::Error;
+use actix_web::HttpRequest;
 use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
 use actix_web::post;
 use actix_web::web;
 use nanoid::nanoid;
@@ -24,29 +27,95 @@ use crate::compatibility::openai_service::openai_non_streaming_state::OpenAINonS
 use crate::compatibility::openai_service::openai_streaming_response_transformer::OpenAIStreamingResponseTransformer;
 use crate::compatibility::openai_service::openai_streaming_state::OpenAIStreamingState;
 use crate::compatibility::openai_service::timestamp_from::timestamp_from;
+use crate::request_admission::RequestAdmission;
 use crate::require_token_generation_enabled::require_token_generation_enabled;
 use crate::unbounded_stream_from_agent::unbounded_stream_from_agent;
 
+fn error_response(status: StatusCode, error: &OpenAIError) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(error.to_envelope().to_string())
+}
+
 #[post("/v1/chat/completions")]
 async fn respond(
     app_data: web::Data<AppData>,
+    http_request: HttpRequest,
     openai_params: web::Json<OpenAICompletionRequestParams>,
 ) -> Result<HttpResponse, Error> {
-    if require_token_generation_enabled(&app_data.balancer_applicable_state_holder).is_err() {
-        return Ok(HttpResponse::NotImplemented()
-            .content_type("application/json")
-            .body(
-                OpenAIError {
-                    error_type: "server_error",
-                    message: "Chat completions are disabled while the cluster is configured for embeddings"
-                        .to_owned(),
-                }
-                .to_envelope()
-                .to_string(),
+    let openai_params = openai_params.into_inner();
+    let received_at = Instant::now();
+    let reject = |status: StatusCode, error_kind: &'static str, error: &OpenAIError| {
+        app_data
+            .buffered_request_manager
+            .observability
+            .record_rejected_request(&http_request, received_at.elapsed(), error_kind);
+
+        error_response(status, error)
+    };
+    let request_admission = RequestAdmission::from_request(&http_request);
+    let model_pool = match app_data
+        .balancer_applicable_state_holder
+        .resolve_model_pool(Some(openai_params.model.as_str()))
+    {
+        Ok(model_pool) => model_pool,
+        Err(err) => {
+            return Ok(reject(
+                StatusCode::NOT_FOUND,
+                "model_pool_not_found",
+                &OpenAIError {
+                    error_type: "invalid_request_error",
+                    message: err.to_string(),
+                },
             ));
+        }
+    };
+
+    if require_token_generation_enabled(
+        &app_data.balancer_applicable_state_holder,
+        model_pool.as_deref(),
+    )
+    .is_err()
+    {
+        return Ok(reject(
+            StatusCode::NOT_IMPLEMENTED,
+            "token_generation_disabled",
+            &OpenAIError {
+                error_type: "server_error",
+                message:
+                    "Chat completions are disabled while the cluster is configured for embeddings"
+                        .to_owned(),
+            },
+        ));
     }
 
-    let openai_params = openai_params.into_inner();
+    let tool_choice = openai_params.to_tool_choice();
+    let grammar = openai_params.to_grammar_constraint();
+    let generation_options = openai_params.to_logprobs_request().and_then(|logprobs| {
+        let grammar = grammar?;
+        let sampling = openai_params.sampling.to_sampling_overrides()?;
+
+        Ok((
+            grammar,
+            logprobs,
+            sampling,
+            openai_params.sampling.into_stop_sequences()?,
+        ))
+    });
+
+    let (grammar, logprobs, sampling, stop) = match generation_options {
+        Ok(generation_options) => generation_options,
+        Err(err) => {
+            return Ok(reject(
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+                &OpenAIError {
+                    error_type: "invalid_request_error",
+                    message: err.to_string(),
+                },
+            ));
+        }
+    };
 
     let validated_tools = match openai_params
         .tools
@@ -57,19 +126,31 @@ async fn respond(
     {
         Ok(tools) => tools,
         Err(err) => {
-            return Ok(HttpResponse::BadRequest()
-                .content_type("application/json")
-                .body(
-                    OpenAIError {
-                        error_type: "invalid_request_error",
-                        message: err.to_string(),
-                    }
-                    .to_envelope()
-                    .to_string(),
-                ));
+            return Ok(reject(
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+                &OpenAIError {
+                    error_type: "invalid_request_error",
+                    message: err.to_string(),
+                },
+            ));
         }
     };
 
+    if let Err(err) = tool_choice.validate_against(&validated_tools, grammar.as_ref()) {
+        return Ok(reject(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            &OpenAIError {
+                error_type: "invalid_request_error",
+                message: err.to_string(),
+            },
+        ));
+    }
+
+    // Agents reject thinking together with a grammar, and a forced tool call is
+    // constrained by one.
+    let enable_thinking = grammar.is_none() && !tool_choice.forces_tool_call();
     let parse_tool_calls = !validated_tools.is_empty();
     let paddler_params = ContinueFromConversationHistoryParams {
         add_generation_prompt: true,
@@ -80,15 +161,32 @@ async fn respond(
                 .map(OpenAIMessage::to_conversation_message)
                 .collect(),
         ),
-        enable_thinking: true,
-        grammar: None,
+        enable_thinking,
+        grammar,
+        logprobs,
         max_tokens: openai_params.max_completion_tokens.unwrap_or(2000),
+        model: Some(openai_params.model.clone()),
         parse_tool_calls,
+        sampling,
+        session_key: openai_params.prompt_cache_key.clone(),
+        stop,
+        tool_choice,
         tools: validated_tools,
     };
 
-    let created =
-        timestamp_from(SystemTime::now()).map_err(actix_web::error::ErrorInternalServerError)?;
+    let created = match timestamp_from(SystemTime::now()) {
+        Ok(created) => created,
+        Err(err) => {
+            return Ok(reject(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "clock_before_unix_epoch",
+                &OpenAIError {
+                    error_type: "server_error",
+                    message: err.to_string(),
+                },
+            ));
+        }
+    };
 
     if openai_params.stream.unwrap_or(false) {
         let include_usage = openai_params
@@ -99,7 +197,9 @@ async fn respond(
         Ok(chat_completions_sse_response(
             app_data.buffered_request_manager.clone(),
             app_data.inference_service_configuration.clone(),
+            model_pool,
             paddler_params,
+            request_admission,
             OpenAIStreamingResponseTransformer {
                 created,
                 include_usage,
@@ -113,7 +213,9 @@ async fn respond(
         let results: Vec<TransformResult> = unbounded_stream_from_agent(
             app_data.buffered_request_manager.clone(),
             app_data.inference_service_configuration.clone(),
+            model_pool,
             paddler_params,
+            request_admission,
             OpenAINonStreamingResponseTransformer {
                 created,
                 model: openai_params.model.clone(),
@@ -128,9 +230,11 @@ async fn respond(
             .iter()
             .find(|result| matches!(result, TransformResult::Error(_)))
         {
-            return Ok(HttpResponse::InternalServerError()
-                .content_type("application/json")
-                .body(error_json.clone()));
+            return Ok(
+                HttpResponse::build(OpenAIError::status_code_of_envelope(error_json))
+                    .content_type("application/json")
+                    .body(error_json.clone()),
+            );
         }
 
         let body = results.into_iter().find_map(|result| match result {
@@ -166,6 +270,7 @@ pub fn register(cfg: &mut web::ServiceConfig) {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::net::Ipv4Addr;
     use std::net::SocketAddr;
     use std::sync::Arc;
@@ -190,23 +295,34 @@ mod tests {
     use super::AppData;
     use super::register;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
     use crate::balancer_applicable_state::BalancerApplicableState;
     use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::dispatch_strategy::DispatchStrategy;
     use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+    use crate::request_observability::RequestObservability;
+    use crate::request_outcome::RequestOutcome;
 
     fn app_data_without_agents(max_buffered_requests: i32) -> AppData {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+
         AppData {
+            agent_controller_pool: agent_controller_pool.clone(),
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
-                Arc::new(AgentControllerPool::default()),
+                agent_controller_pool,
                 Duration::ZERO,
                 max_buffered_requests,
+                RequestObservability::default(),
             )),
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
                 cors_allowed_hosts: Vec::new(),
+                dispatch_strategy: DispatchStrategy::LeastBusy,
                 inference_item_timeout: Duration::ZERO,
+                tls_configuration: None,
             },
             shutdown: CancellationToken::new(),
         }
@@ -226,20 +342,28 @@ mod tests {
                     model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
                     multimodal_projection: AgentDesiredModel::None,
                 },
+                model_pools: BTreeMap::new(),
             },
         ));
 
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+
         AppData {
+            agent_controller_pool: agent_controller_pool.clone(),
             balancer_applicable_state_holder,
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
-                Arc::new(AgentControllerPool::default()),
+                agent_controller_pool,
                 Duration::ZERO,
                 0,
+                RequestObservability::default(),
             )),
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
                 cors_allowed_hosts: Vec::new(),
+                dispatch_strategy: DispatchStrategy::LeastBusy,
                 inference_item_timeout: Duration::ZERO,
+                tls_configuration: None,
             },
             shutdown: CancellationToken::new(),
         }
@@ -274,6 +398,35 @@ mod tests {
         Ok(())
     }
 
+    #[actix_web::test]
+    async fn counts_requests_rejected_before_dispatch() {
+        let app_data = app_data_with_embeddings_enabled();
+        let request_metrics = app_data
+            .buffered_request_manager
+            .observability
+            .request_metrics
+            .clone();
+        let app = init_service(App::new().app_data(Data::new(app_data)).configure(register)).await;
+        let request = TestRequest::post()
+            .uri("/v1/chat/completions")
+            .set_json(json!({
+                "model": "test-model",
+                "messages": [{"role": "user", "content": "hi"}]
+            }))
+            .to_request();
+
+        call_service(&app, request).await;
+
+        assert_eq!(
+            request_metrics.requests_by_endpoint_and_outcome(),
+            vec![(
+                "/v1/chat/completions".to_owned(),
+                RequestOutcome::Rejected,
+                1
+            )]
+        );
+    }
+
     #[actix_web::test]
     async fn invalid_tool_schema_returns_bad_request() {
         let app = init_service(
@@ -321,6 +474,112 @@ mod tests {
         );
     }
 
+    #[actix_web::test]
+    async fn out_of_range_temperature_returns_bad_request() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data_without_agents(0)))
+                .configure(register),
+        )
+        .await;
+
+        let request = TestRequest::post()
+            .uri("/v1/chat/completions")
+            .set_json(json!({
+                "model": "test-model",
+                "messages": [{"role": "user", "content": "hi"}],
+                "temperature": 9.0
+            }))
+            .to_request();
+
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = read_body(response).await;
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(parsed["error"]["type"], "invalid_request_error");
+        assert!(
+            parsed["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("temperature")
+        );
+    }
+
+    #[actix_web::test]
+    async fn strict_json_schema_without_a_schema_returns_bad_request() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data_without_agents(0)))
+                .configure(register),
+        )
+        .await;
+
+        let request = TestRequest::post()
+            .uri("/v1/chat/completions")
+            .set_json(json!({
+                "model": "test-model",
+                "messages": [{"role": "user", "content": "hi"}],
+                "response_format": {
+                    "type": "json_schema",
+                    "json_schema": {"name": "answer", "strict": true}
+                }
+            }))
+            .to_request();
+
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = read_body(response).await;
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(parsed["error"]["type"], "invalid_request_error");
+        assert!(
+            parsed["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("answer")
+        );
+    }
+
+    #[actix_web::test]
+    async fn tool_choice_naming_an_unknown_function_returns_bad_request() {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data_without_agents(0)))
+                .configure(register),
+        )
+        .await;
+
+        let request = TestRequest::post()
+            .uri("/v1/chat/completions")
+            .set_json(json!({
+                "model": "test-model",
+                "messages": [{"role": "user", "content": "hi"}],
+                "tool_choice": {"type": "function", "function": {"name": "get_time"}},
+                "tools": [{"type": "function", "function": {"name": "get_weather"}}]
+            }))
+            .to_request();
+
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let body = read_body(response).await;
+        let parsed: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(parsed["error"]["type"], "invalid_request_error");
+        assert!(
+            parsed["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("get_time")
+        );
+    }
+
     #[actix_web::test]
     async fn opencode_style_tools_are_accepted() {
         let app = init_service(
@@ -0,0 +1,396 @@
+use std::sync::Arc;
+use std::time::Instant;
+use std::time::SystemTime;
+
+use actix_web::Error;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
+use actix_web::post;
+use actix_web::web;
+use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+use parking_lot::Mutex;
+use tokio_stream::StreamExt as _;
+
+use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::compatibility::openai_service::app_data::AppData;
+use crate::compatibility::openai_service::chat_completions_sse_response::chat_completions_sse_response;
+use crate::compatibility::openai_service::openai_error::OpenAIError;
+use crate::compatibility::openai_service::openai_text_completion_non_streaming_response_transformer::OpenAITextCompletionNonStreamingResponseTransformer;
+use crate::compatibility::openai_service::openai_text_completion_request_params::DEFAULT_TEXT_COMPLETION_MAX_TOKENS;
+use crate::compatibility::openai_service::openai_text_completion_request_params::OpenAITextCompletionRequestParams;
+use crate::compatibility::openai_service::openai_text_completion_streaming_response_transformer::OpenAITextCompletionStreamingResponseTransformer;
+use crate::compatibility::openai_service::timestamp_from::timestamp_from;
+use crate::request_admission::RequestAdmission;
+use crate::require_token_generation_enabled::require_token_generation_enabled;
+use crate::unbounded_stream_from_agent::unbounded_stream_from_agent;
+
+fn error_response(status: StatusCode, error: &OpenAIError) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(error.to_envelope().to_string())
+}
+
+#[post("/v1/completions")]
+async fn respond(
+    app_data: web::Data<AppData>,
+    http_request: HttpRequest,
+    openai_params: web::Json<OpenAITextCompletionRequestParams>,
+) -> Result<HttpResponse, Error> {
+    let OpenAITextCompletionRequestParams {
+        echo,
+        logprobs,
+        max_tokens,
+        model,
+        prompt,
+        sampling,
+        stream,
+        stream_options,
+        suffix,
+    } = openai_params.into_inner();
+    let received_at = Instant::now();
+    let reject = |status: StatusCode, error_kind: &'static str, error: &OpenAIError| {
+        app_data
+            .buffered_request_manager
+            .observability
+            .record_rejected_request(&http_request, received_at.elapsed(), error_kind);
+
+        error_response(status, error)
+    };
+    let bad_request = |message: String| {
+        reject(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            &OpenAIError {
+                error_type: "invalid_request_error",
+                message,
+            },
+        )
+    };
+    let request_admission = RequestAdmission::from_request(&http_request);
+    let model_pool = match app_data
+        .balancer_applicable_state_holder
+        .resolve_model_pool(Some(model.as_str()))
+    {
+        Ok(model_pool) => model_pool,
+        Err(err) => {
+            return Ok(reject(
+                StatusCode::NOT_FOUND,
+                "model_pool_not_found",
+                &OpenAIError {
+                    error_type: "invalid_request_error",
+                    message: err.to_string(),
+                },
+            ));
+        }
+    };
+
+    if require_token_generation_enabled(
+        &app_data.balancer_applicable_state_holder,
+        model_pool.as_deref(),
+    )
+    .is_err()
+    {
+        return Ok(reject(
+            StatusCode::NOT_IMPLEMENTED,
+            "token_generation_disabled",
+            &OpenAIError {
+                error_type: "server_error",
+                message: "Completions are disabled while the cluster is configured for embeddings"
+                    .to_owned(),
+            },
+        ));
+    }
+
+    if suffix.is_some_and(|suffix| !suffix.is_empty()) {
+        return Ok(bad_request(
+            "suffix is not supported; fill-in-the-middle completions are not available".to_owned(),
+        ));
+    }
+
+    if logprobs.is_some() {
+        return Ok(bad_request(
+            "logprobs is not supported on /v1/completions; use /v1/chat/completions instead"
+                .to_owned(),
+        ));
+    }
+
+    let prompt_and_sampling = prompt.into_prompt().and_then(|prompt| {
+        Ok((
+            prompt,
+            sampling.to_sampling_overrides()?,
+            sampling.into_stop_sequences()?,
+        ))
+    });
+
+    let (prompt, sampling, stop) = match prompt_and_sampling {
+        Ok(prompt_and_sampling) => prompt_and_sampling,
+        Err(err) => return Ok(bad_request(err.to_string())),
+    };
+
+    let echoed_prompt = echo.then(|| prompt.clone());
+    let paddler_params = ContinueFromRawPromptParams {
+        grammar: None,
+        logprobs: None,
+        max_tokens: max_tokens.unwrap_or(DEFAULT_TEXT_COMPLETION_MAX_TOKENS),
+        model: Some(model.clone()),
+        raw_prompt: prompt,
+        sampling,
+        session_key: None,
+        stop,
+    };
+
+    let created = match timestamp_from(SystemTime::now()) {
+        Ok(created) => created,
+        Err(err) => {
+            return Ok(reject(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "clock_before_unix_epoch",
+                &OpenAIError {
+                    error_type: "server_error",
+                    message: err.to_string(),
+                },
+            ));
+        }
+    };
+
+    if stream.unwrap_or(false) {
+        let include_usage = stream_options
+            .as_ref()
+            .is_some_and(|options| options.include_usage);
+
+        Ok(chat_completions_sse_response(
+            app_data.buffered_request_manager.clone(),
+            app_data.inference_service_configuration.clone(),
+            model_pool,
+            paddler_params,
+            request_admission,
+            OpenAITextCompletionStreamingResponseTransformer {
+                created,
+                include_usage,
+                model,
+                pending_echo: Arc::new(Mutex::new(echoed_prompt)),
+            },
+            app_data.shutdown.clone(),
+        ))
+    } else {
+        let results: Vec<TransformResult> = unbounded_stream_from_agent(
+            app_data.buffered_request_manager.clone(),
+            app_data.inference_service_configuration.clone(),
+            model_pool,
+            paddler_params,
+            request_admission,
+            OpenAITextCompletionNonStreamingResponseTransformer {
+                created,
+                model,
+                text: Arc::new(Mutex::new(echoed_prompt.unwrap_or_default())),
+            },
+            app_data.shutdown.clone(),
+        )
+        .collect()
+        .await;
+
+        if let Some(TransformResult::Error(error_json)) = results
+            .iter()
+            .find(|result| matches!(result, TransformResult::Error(_)))
+        {
+            return Ok(
+                HttpResponse::build(OpenAIError::status_code_of_envelope(error_json))
+                    .content_type("application/json")
+                    .body(error_json.clone()),
+            );
+        }
+
+        let body = results.into_iter().find_map(|result| match result {
+            TransformResult::Chunk(content) => Some(content),
+            TransformResult::Discard | TransformResult::Error(_) => None,
+        });
+
+        Ok(body.map_or_else(
+            || {
+                HttpResponse::InternalServerError()
+                    .content_type("application/json")
+                    .body(
+                        OpenAIError {
+                            error_type: "server_error",
+                            message: "no completion produced".to_owned(),
+                        }
+                        .to_envelope()
+                        .to_string(),
+                    )
+            },
+            |json_body| {
+                HttpResponse::Ok()
+                    .content_type("application/json")
+                    .body(json_body)
+            },
+        ))
+    }
+}
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::Ipv4Addr;
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use actix_web::App;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::read_body_json;
+    use actix_web::web::Data;
+    use serde_json::Value;
+    use serde_json::json;
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
+    use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+    use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::dispatch_strategy::DispatchStrategy;
+    use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+    use crate::request_observability::RequestObservability;
+    use crate::request_outcome::RequestOutcome;
+
+    fn app_data_without_agents() -> AppData {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+
+        AppData {
+            agent_controller_pool: agent_controller_pool.clone(),
+            balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
+            buffered_request_manager: Arc::new(BufferedRequestManager::new(
+                agent_controller_pool,
+                Duration::ZERO,
+                0,
+                RequestObservability::default(),
+            )),
+            inference_service_configuration: InferenceServiceConfiguration {
+                addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
+                cors_allowed_hosts: Vec::new(),
+                dispatch_strategy: DispatchStrategy::LeastBusy,
+                inference_item_timeout: Duration::ZERO,
+                tls_configuration: None,
+            },
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    async fn post_completions(body: Value) -> (StatusCode, Value) {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data_without_agents()))
+                .configure(register),
+        )
+        .await;
+        let request = TestRequest::post()
+            .uri("/v1/completions")
+            .set_json(body)
+            .to_request();
+        let response = call_service(&app, request).await;
+        let status = response.status();
+
+        (status, read_body_json(response).await)
+    }
+
+    #[actix_web::test]
+    async fn rejects_a_suffix() {
+        let (status, body) = post_completions(json!({
+            "model": "test-model",
+            "prompt": "def add(a, b):",
+            "suffix": "    return result",
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("suffix")
+        );
+    }
+
+    #[actix_web::test]
+    async fn counts_requests_rejected_before_dispatch() {
+        let app_data = app_data_without_agents();
+        let request_metrics = app_data
+            .buffered_request_manager
+            .observability
+            .request_metrics
+            .clone();
+        let app = init_service(App::new().app_data(Data::new(app_data)).configure(register)).await;
+        let request = TestRequest::post()
+            .uri("/v1/completions")
+            .set_json(json!({
+                "model": "test-model",
+                "prompt": "def add(a, b):",
+                "suffix": "    return result",
+            }))
+            .to_request();
+
+        call_service(&app, request).await;
+
+        assert_eq!(
+            request_metrics.requests_by_endpoint_and_outcome(),
+            vec![("/v1/completions".to_owned(), RequestOutcome::Rejected, 1)]
+        );
+    }
+
+    #[actix_web::test]
+    async fn rejects_logprobs() {
+        let (status, body) = post_completions(json!({
+            "model": "test-model",
+            "prompt": "def add(a, b):",
+            "logprobs": 2,
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("logprobs")
+        );
+    }
+
+    #[actix_web::test]
+    async fn rejects_a_batch_of_prompts() {
+        let (status, body) = post_completions(json!({
+            "model": "test-model",
+            "prompt": ["first", "second"],
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+    }
+
+    #[actix_web::test]
+    async fn non_streaming_request_without_available_agent_returns_internal_server_error() {
+        let (status, body) = post_completions(json!({
+            "model": "test-model",
+            "prompt": "Once upon a time",
+            "suffix": "",
+        }))
+        .await;
+
+        assert_eq!(status, StatusCode::INTERNAL_SERVER_ERROR);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("Buffered requests overflow")
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use std::time::Instant;
+
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
+use actix_web::post;
+use actix_web::web;
+use paddler_messaging::embedding_normalization_method::EmbeddingNormalizationMethod;
+use paddler_messaging::request_params::generate_embedding_batch_params::GenerateEmbeddingBatchParams;
+use paddler_messaging::request_params::generate_embedding_batch_params::chunk_evenly_with_cap_error::ChunkEvenlyWithCapError;
+use tokio_stream::StreamExt as _;
+
+use crate::compatibility::openai_service::app_data::AppData;
+use crate::compatibility::openai_service::openai_embeddings_request_params::OpenAIEmbeddingsRequestParams;
+use crate::compatibility::openai_service::openai_embeddings_response::openai_embeddings_response;
+use crate::compatibility::openai_service::openai_embeddings_transformer::OpenAIEmbeddingsTransformer;
+use crate::compatibility::openai_service::openai_error::OpenAIError;
+use crate::embedding_batch_stream_from_agents::embedding_batch_stream_from_agents;
+use crate::request_admission::RequestAdmission;
+
+fn error_response(status: StatusCode, error: &OpenAIError) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(error.to_envelope().to_string())
+}
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[post("/v1/embeddings")]
+async fn respond(
+    app_data: web::Data<AppData>,
+    http_request: HttpRequest,
+    openai_params: web::Json<OpenAIEmbeddingsRequestParams>,
+) -> HttpResponse {
+    let OpenAIEmbeddingsRequestParams {
+        dimensions,
+        encoding_format,
+        input,
+        model,
+    } = openai_params.into_inner();
+    let received_at = Instant::now();
+    let reject = |status: StatusCode, error_kind: &'static str, error: &OpenAIError| {
+        app_data
+            .buffered_request_manager
+            .observability
+            .record_rejected_request(&http_request, received_at.elapsed(), error_kind);
+
+        error_response(status, error)
+    };
+    let request_admission = RequestAdmission::from_request(&http_request);
+    let model_pool = match app_data
+        .balancer_applicable_state_holder
+        .resolve_model_pool(Some(model.as_str()))
+    {
+        Ok(model_pool) => model_pool,
+        Err(err) => {
+            return reject(
+                StatusCode::NOT_FOUND,
+                "model_pool_not_found",
+                &OpenAIError {
+                    error_type: "invalid_request_error",
+                    message: err.to_string(),
+                },
+            );
+        }
+    };
+    let Some(agent_desired_state) = app_data
+        .balancer_applicable_state_holder
+        .get_model_pool_agent_desired_state(model_pool.as_deref())
+    else {
+        return reject(
+            StatusCode::SERVICE_UNAVAILABLE,
+            "balancer_state_not_set",
+            &OpenAIError {
+                error_type: "server_error",
+                message: "Balancer applicable state is not yet set".to_owned(),
+            },
+        );
+    };
+
+    if !agent_desired_state.inference_parameters.enable_embeddings {
+        return reject(
+            StatusCode::NOT_IMPLEMENTED,
+            "embeddings_disabled",
+            &OpenAIError {
+                error_type: "server_error",
+                message: "Embedding generation is not enabled in the inference parameters"
+                    .to_owned(),
+            },
+        );
+    }
+
+    if dimensions == Some(0) {
+        return reject(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            &OpenAIError {
+                error_type: "invalid_request_error",
+                message: "dimensions must be at least 1".to_owned(),
+            },
+        );
+    }
+
+    let input_batch = input.into_documents();
+
+    if input_batch.is_empty() {
+        return reject(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            &OpenAIError {
+                error_type: "invalid_request_error",
+                message: "input must not be empty".to_owned(),
+            },
+        );
+    }
+
+    if let Some(document) = input_batch
+        .iter()
+        .find(|document| document.tokens.as_ref().is_some_and(Vec::is_empty))
+    {
+        return reject(
+            StatusCode::BAD_REQUEST,
+            "invalid_request",
+            &OpenAIError {
+                error_type: "invalid_request_error",
+                message: format!("input {} must not be an empty token array", document.id),
+            },
+        );
+    }
+
+    let input_count = input_batch.len();
+    let params = GenerateEmbeddingBatchParams {
+        input_batch,
+        model: Some(model.clone()),
+        normalization_method: EmbeddingNormalizationMethod::L2,
+    };
+    let agent_count = app_data
+        .agent_controller_pool
+        .count_agents_in_model_pool(model_pool.as_deref());
+    let batches = match params.chunk_evenly_with_cap(
+        agent_count,
+        agent_desired_state
+            .inference_parameters
+            .embedding_batch_size,
+    ) {
+        Ok(batches) => batches,
+        Err(ChunkEvenlyWithCapError::ZeroAgentCount) => {
+            return reject(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "no_agents_connected",
+                &OpenAIError {
+                    error_type: "server_error",
+                    message: "No agents are currently connected".to_owned(),
+                },
+            );
+        }
+        Err(ChunkEvenlyWithCapError::ZeroMaxDocumentsPerChunk) => {
+            return reject(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "embedding_batch_size_is_zero",
+                &OpenAIError {
+                    error_type: "server_error",
+                    message: "embedding_batch_size is zero despite validation".to_owned(),
+                },
+            );
+        }
+    };
+
+    let results: Result<Vec<_>, OpenAIError> = embedding_batch_stream_from_agents(
+        app_data.buffered_request_manager.clone(),
+        app_data.inference_service_configuration.clone(),
+        model_pool,
+        batches,
+        request_admission,
+        OpenAIEmbeddingsTransformer,
+        app_data.shutdown.clone(),
+    )
+    .collect()
+    .await;
+
+    match results.and_then(|embeddings| {
+        openai_embeddings_response(embeddings, input_count, dimensions, encoding_format, &model)
+    }) {
+        Ok(body) => HttpResponse::Ok().json(body),
+        Err(error) if error.error_type == "invalid_request_error" => {
+            error_response(StatusCode::BAD_REQUEST, &error)
+        }
+        Err(error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, &error),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use std::net::Ipv4Addr;
+    use std::net::SocketAddr;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use actix_web::App;
+    use actix_web::test::TestRequest;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::read_body_json;
+    use actix_web::web::Data;
+    use paddler_messaging::agent_desired_state::AgentDesiredState;
+    use paddler_messaging::inference_parameters::InferenceParameters;
+    use serde_json::Value;
+    use serde_json::json;
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
+    use crate::balancer_applicable_state::BalancerApplicableState;
+    use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+    use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::dispatch_strategy::DispatchStrategy;
+    use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+    use crate::request_observability::RequestObservability;
+    use crate::request_outcome::RequestOutcome;
+
+    fn app_data(enable_embeddings: bool) -> AppData {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+        let balancer_applicable_state_holder = Arc::new(BalancerApplicableStateHolder::default());
+
+        balancer_applicable_state_holder.set_balancer_applicable_state(Some(
+            BalancerApplicableState {
+                agent_desired_state: AgentDesiredState {
+                    inference_parameters: InferenceParameters {
+                        enable_embeddings,
+                        ..InferenceParameters::default()
+                    },
+                    ..AgentDesiredState::default()
+                },
+                model_pools: BTreeMap::new(),
+            },
+        ));
+
+        AppData {
+            agent_controller_pool: agent_controller_pool.clone(),
+            balancer_applicable_state_holder,
+            buffered_request_manager: Arc::new(BufferedRequestManager::new(
+                agent_controller_pool,
+                Duration::ZERO,
+                0,
+                RequestObservability::default(),
+            )),
+            inference_service_configuration: InferenceServiceConfiguration {
+                addr: SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
+                cors_allowed_hosts: Vec::new(),
+                dispatch_strategy: DispatchStrategy::LeastBusy,
+                inference_item_timeout: Duration::ZERO,
+                tls_configuration: None,
+            },
+            shutdown: CancellationToken::new(),
+        }
+    }
+
+    async fn post_embeddings(enable_embeddings: bool, body: Value) -> (StatusCode, Value) {
+        let app = init_service(
+            App::new()
+                .app_data(Data::new(app_data(enable_embeddings)))
+                .configure(register),
+        )
+        .await;
+        let request = TestRequest::post()
+            .uri("/v1/embeddings")
+            .set_json(body)
+            .to_request();
+        let response = call_service(&app, request).await;
+        let status = response.status();
+
+        (status, read_body_json(response).await)
+    }
+
+    #[actix_web::test]
+    async fn rejects_embeddings_when_they_are_disabled() {
+        let (status, body) =
+            post_embeddings(false, json!({"model": "embedder", "input": "hello"})).await;
+
+        assert_eq!(status, StatusCode::NOT_IMPLEMENTED);
+        assert_eq!(body["error"]["type"], "server_error");
+    }
+
+    #[actix_web::test]
+    async fn rejects_an_empty_input() {
+        let (status, body) = post_embeddings(true, json!({"model": "embedder", "input": []})).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+    }
+
+    #[actix_web::test]
+    async fn rejects_an_empty_token_array() {
+        let (status, body) =
+            post_embeddings(true, json!({"model": "embedder", "input": [[1, 2], []]})).await;
+
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+        assert_eq!(
+            body["error"]["message"],
+            "input 1 must not be an empty token array"
+        );
+    }
+
+    #[actix_web::test]
+    async fn responds_service_unavailable_without_agents() {
+        let (status, body) = post_embeddings(
+            true,
+            json!({"model": "embedder", "input": [[1, 2], [3]], "encoding_format": "base64"}),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert!(
+            body["error"]["message"]
+                .as_str()
+                .unwrap()
+                .contains("No agents")
+        );
+    }
+
+    #[actix_web::test]
+    async fn counts_rejected_requests() {
+        let app_data = app_data(false);
+        let request_metrics = app_data
+            .buffered_request_manager
+            .observability
+            .request_metrics
+            .clone();
+        let app = init_service(App::new().app_data(Data::new(app_data)).configure(register)).await;
+        let request = TestRequest::post()
+            .uri("/v1/embeddings")
+            .set_json(json!({"model": "embedder", "input": "hello"}))
+            .to_request();
+
+        call_service(&app, request).await;
+
+        assert_eq!(
+            request_metrics.requests_by_endpoint_and_outcome(),
+            vec![("/v1/embeddings".to_owned(), RequestOutcome::Rejected, 1)]
+        );
+    }
+}
@@ -1,8 +1,11 @@
 use std::sync::Arc;
+use std::time::Instant;
 use std::time::SystemTime;
 
 use actix_web::Error;
+use actix_web::HttpRequest;
 use actix_web::HttpResponse;
+use actix_web::http::StatusCode;
 use actix_web::post;
 use actix_web::web;
 use nanoid::nanoid;
@@ -20,60 +23,110 @@ use crate::compatibility::openai_service::responses_streaming_response_transform
 use crate::compatibility::openai_service::responses_streaming_state::ResponsesStreamingState;
 use crate::compatibility::openai_service::sse_response_from_agent::sse_response_from_agent;
 use crate::compatibility::openai_service::timestamp_from::timestamp_from;
+use crate::request_admission::RequestAdmission;
 use crate::require_token_generation_enabled::require_token_generation_enabled;
 use crate::unbounded_stream_from_agent::unbounded_stream_from_agent;
 
+fn error_response(status: StatusCode, error: &OpenAIError) -> HttpResponse {
+    HttpResponse::build(status)
+        .content_type("application/json")
+        .body(error.to_envelope().to_string())
+}
+
 #[post("/v1/responses")]
 async fn respond(
     app_data: web::Data<AppData>,
+    http_request: HttpRequest,
     openai_params: web::Json<OpenAIResponsesRequestParams>,
 ) -> Result<HttpResponse, Error> {
-    if require_token_generation_enabled(&app_data.balancer_applicable_state_holder).is_err() {
-        return Ok(HttpResponse::NotImplemented()
-            .content_type("application/json")
-            .body(
-                OpenAIError {
-                    error_type: "server_error",
-                    message:
-                        "Responses are disabled while the cluster is configured for embeddings"
-                            .to_owned(),
-                }
-                .to_envelope()
-                .to_string(),
+    let openai_params = openai_params.into_inner();
+    let received_at = Instant::now();
+    let reject = |status: StatusCode, error_kind: &'static str, error: &OpenAIError| {
+        app_data
+            .buffered_request_manager
+            .observability
+            .record_rejected_request(&http_request, received_at.elapsed(), error_kind);
+
+        error_response(status, error)
+    };
+    let request_admission = RequestAdmission::from_request(&http_request);
+    let model_pool = match app_data
+        .balancer_applicable_state_holder
+        .resolve_model_pool(Some(openai_params.model.as_str()))
+    {
+        Ok(model_pool) => model_pool,
+        Err(err) => {
+            return Ok(reject(
+                StatusCode::NOT_FOUND,
+                "model_pool_not_found",
+                &OpenAIError {
+                    error_type: "invalid_request_error",
+                    message: err.to_string(),
+                },
             ));
+        }
+    };
+
+    if require_token_generation_enabled(
+        &app_data.balancer_applicable_state_holder,
+        model_pool.as_deref(),
+    )
+    .is_err()
+    {
+        return Ok(reject(
+            StatusCode::NOT_IMPLEMENTED,
+            "token_generation_disabled",
+            &OpenAIError {
+                error_type: "server_error",
+                message: "Responses are disabled while the cluster is configured for embeddings"
+                    .to_owned(),
+            },
+        ));
     }
 
-    let prepared = match openai_params.into_inner().into_prepared() {
+    let prepared = match openai_params.into_prepared() {
         Ok(prepared) => prepared,
         Err(err) => {
-            return Ok(HttpResponse::BadRequest()
-                .content_type("application/json")
-                .body(
-                    OpenAIError {
-                        error_type: "invalid_request_error",
-                        message: err.to_string(),
-                    }
-                    .to_envelope()
-                    .to_string(),
-                ));
+            return Ok(reject(
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+                &OpenAIError {
+                    error_type: "invalid_request_error",
+                    message: err.to_string(),
+                },
+            ));
         }
     };
 
-    let created_at =
-        timestamp_from(SystemTime::now()).map_err(actix_web::error::ErrorInternalServerError)?;
+    let created_at = match timestamp_from(SystemTime::now()) {
+        Ok(created_at) => created_at,
+        Err(err) => {
+            return Ok(reject(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "clock_before_unix_epoch",
+                &OpenAIError {
+                    error_type: "server_error",
+                    message: err.to_string(),
+                },
+            ));
+        }
+    };
 
     let builder = ResponsesResponseBuilder {
         id: format!("resp_{}", nanoid!()),
         created_at,
         model: prepared.model,
         instructions: prepared.instructions,
+        tool_choice: prepared.paddler_params.tool_choice.clone(),
     };
 
     if prepared.stream {
         Ok(sse_response_from_agent(
             app_data.buffered_request_manager.clone(),
             app_data.inference_service_configuration.clone(),
+            model_pool,
             prepared.paddler_params,
+            request_admission,
             ResponsesStreamingResponseTransformer {
                 builder,
                 state: Arc::new(Mutex::new(ResponsesStreamingState::default())),
@@ -84,7 +137,9 @@ async fn respond(
         let results: Vec<TransformResult> = unbounded_stream_from_agent(
             app_data.buffered_request_manager.clone(),
             app_data.inference_service_configuration.clone(),
+            model_pool,
             prepared.paddler_params,
+            request_admission,
             ResponsesNonStreamingResponseTransformer {
                 builder,
                 state: Arc::new(Mutex::new(ResponsesNonStreamingState::default())),
@@ -98,9 +153,11 @@ async fn respond(
             .iter()
             .find(|result| matches!(result, TransformResult::Error(_)))
         {
-            return Ok(HttpResponse::InternalServerError()
-                .content_type("application/json")
-                .body(error_json.clone()));
+            return Ok(
+                HttpResponse::build(OpenAIError::status_code_of_envelope(error_json))
+                    .content_type("application/json")
+                    .body(error_json.clone()),
+            );
         }
 
         let body = results.into_iter().find_map(|result| match result {
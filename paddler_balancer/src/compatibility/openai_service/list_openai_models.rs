@@ -0,0 +1,236 @@
+use std::iter;
+use std::time::Duration;
+
+use futures::future::join_all;
+use log::warn;
+use paddler_messaging::agent_desired_model::AgentDesiredModel;
+use paddler_messaging::agent_desired_state::AgentDesiredState;
+use paddler_messaging::huggingface_model_reference::HuggingFaceModelReference;
+use paddler_messaging::model_metadata::ModelMetadata;
+use tokio::time::timeout;
+
+use crate::agent_controller_pool::AgentControllerPool;
+use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+use crate::compatibility::openai_service::openai_model::OpenAIModel;
+
+const MODEL_METADATA_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Asks an agent of the pool that already loaded its model, so listing models never waits
+/// for a model to load.
+async fn fetch_model_metadata(
+    agent_controller_pool: &AgentControllerPool,
+    model_pool: Option<&str>,
+) -> Option<ModelMetadata> {
+    let agent_controller = agent_controller_pool
+        .agents
+        .iter()
+        .find(|entry| {
+            entry.value().model_pool.as_deref() == model_pool
+                && entry.value().has_applied_desired_state()
+        })
+        .map(|entry| entry.value().clone())?;
+
+    let mut model_metadata_controller = match agent_controller.get_model_metadata().await {
+        Ok(model_metadata_controller) => model_metadata_controller,
+        Err(err) => {
+            warn!(
+                "Unable to request model metadata from agent {}: {err}",
+                agent_controller.id
+            );
+
+            return None;
+        }
+    };
+
+    timeout(
+        MODEL_METADATA_TIMEOUT,
+        model_metadata_controller.response_rx.recv(),
+    )
+    .await
+    .ok()
+    .flatten()
+    .flatten()
+}
+
+fn training_context_length(model_metadata: &ModelMetadata) -> Option<u32> {
+    let architecture = model_metadata.metadata.get("general.architecture")?;
+
+    model_metadata
+        .metadata
+        .get(&format!("{architecture}.context_length"))?
+        .parse()
+        .ok()
+}
+
+fn openai_model(
+    agent_desired_state: &AgentDesiredState,
+    model_metadata: Option<&ModelMetadata>,
+    model_pool: Option<String>,
+) -> Option<OpenAIModel> {
+    let context_size = agent_desired_state.inference_parameters.context_size;
+
+    Some(OpenAIModel {
+        context_length: model_metadata
+            .and_then(training_context_length)
+            .map_or(context_size, |training_context_length| {
+                context_size.min(training_context_length)
+            }),
+        created: 0,
+        id: agent_desired_state.model.model_id()?,
+        model_pool,
+        name: model_metadata
+            .and_then(|model_metadata| model_metadata.metadata.get("general.name").cloned()),
+        object: "model",
+        owned_by: match &agent_desired_state.model {
+            AgentDesiredModel::HuggingFace(HuggingFaceModelReference { repo_id, .. }) => repo_id
+                .split_once('/')
+                .map_or("paddler", |(owner, _)| owner)
+                .to_owned(),
+            _ => "paddler".to_owned(),
+        },
+    })
+}
+
+/// Models of the default pool and of every named pool. A model served by a named pool is
+/// listed only once, under that pool, because requests naming it are routed there.
+pub async fn list_openai_models(
+    agent_controller_pool: &AgentControllerPool,
+    balancer_applicable_state_holder: &BalancerApplicableStateHolder,
+) -> Vec<OpenAIModel> {
+    let Some(balancer_applicable_state) =
+        balancer_applicable_state_holder.get_balancer_applicable_state()
+    else {
+        return Vec::new();
+    };
+
+    let pools: Vec<(Option<String>, &AgentDesiredState)> =
+        iter::once((None, &balancer_applicable_state.agent_desired_state))
+            .chain(balancer_applicable_state.model_pools.iter().map(
+                |(model_pool, agent_desired_state)| (Some(model_pool.clone()), agent_desired_state),
+            ))
+            .collect();
+    let model_metadata =
+        join_all(pools.iter().map(|(model_pool, _)| {
+            fetch_model_metadata(agent_controller_pool, model_pool.as_deref())
+        }))
+        .await;
+
+    let named_pool_models: Vec<OpenAIModel> = pools
+        .iter()
+        .zip(&model_metadata)
+        .skip(1)
+        .filter_map(|((model_pool, agent_desired_state), model_metadata)| {
+            openai_model(
+                agent_desired_state,
+                model_metadata.as_ref(),
+                model_pool.clone(),
+            )
+        })
+        .collect();
+
+    openai_model(
+        &balancer_applicable_state.agent_desired_state,
+        model_metadata[0].as_ref(),
+        None,
+    )
+    .filter(|default_pool_model| {
+        !named_pool_models
+            .iter()
+            .any(|named_pool_model| named_pool_model.id == default_pool_model.id)
+    })
+    .into_iter()
+    .chain(named_pool_models)
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use paddler_messaging::inference_parameters::InferenceParameters;
+
+    use super::*;
+    use crate::balancer_applicable_state::BalancerApplicableState;
+
+    #[tokio::test]
+    async fn lists_the_default_pool_and_named_pools() {
+        let balancer_applicable_state_holder = BalancerApplicableStateHolder::default();
+
+        balancer_applicable_state_holder.set_balancer_applicable_state(Some(
+            BalancerApplicableState {
+                agent_desired_state: AgentDesiredState {
+                    inference_parameters: InferenceParameters {
+                        context_size: 4096,
+                        ..InferenceParameters::default()
+                    },
+                    model: AgentDesiredModel::HuggingFace(HuggingFaceModelReference {
+                        filename: "Qwen3-0.6B-Q8_0.gguf".to_owned(),
+                        repo_id: "Qwen/Qwen3-0.6B-GGUF".to_owned(),
+                        revision: "main".to_owned(),
+                    }),
+                    ..AgentDesiredState::default()
+                },
+                model_pools: BTreeMap::from([(
+                    "embeddings".to_owned(),
+                    AgentDesiredState {
+                        model: AgentDesiredModel::LocalToAgent("/models/embedding.gguf".to_owned()),
+                        ..AgentDesiredState::default()
+                    },
+                )]),
+            },
+        ));
+
+        let models = list_openai_models(
+            &AgentControllerPool::default(),
+            &balancer_applicable_state_holder,
+        )
+        .await;
+
+        assert_eq!(
+            models,
+            vec![
+                OpenAIModel {
+                    context_length: 4096,
+                    created: 0,
+                    id: "Qwen/Qwen3-0.6B-GGUF/Qwen3-0.6B-Q8_0.gguf".to_owned(),
+                    model_pool: None,
+                    name: None,
+                    object: "model",
+                    owned_by: "Qwen".to_owned(),
+                },
+                OpenAIModel {
+                    context_length: InferenceParameters::default().context_size,
+                    created: 0,
+                    id: "embedding.gguf".to_owned(),
+                    model_pool: Some("embeddings".to_owned()),
+                    name: None,
+                    object: "model",
+                    owned_by: "paddler".to_owned(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn context_length_is_capped_by_the_training_context() {
+        let model_metadata = ModelMetadata {
+            metadata: BTreeMap::from([
+                ("general.architecture".to_owned(), "qwen3".to_owned()),
+                ("general.name".to_owned(), "Qwen3 0.6B".to_owned()),
+                ("qwen3.context_length".to_owned(), "2048".to_owned()),
+            ]),
+        };
+        let model = openai_model(
+            &AgentDesiredState {
+                model: AgentDesiredModel::LocalToAgent("qwen3.gguf".to_owned()),
+                ..AgentDesiredState::default()
+            },
+            Some(&model_metadata),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(model.context_length, 2048);
+        assert_eq!(model.name.as_deref(), Some("Qwen3 0.6B"));
+    }
+}
@@ -7,15 +7,27 @@ pub mod function_call_arguments_delta_event;
 pub mod function_call_arguments_done_event;
 pub mod function_call_item;
 pub mod http_route;
+pub mod list_openai_models;
 pub mod message_item_done;
 pub mod open_item;
 pub mod openai_chat_completion_function;
 pub mod openai_chat_completion_tool;
+pub mod openai_chat_completion_tool_choice;
 pub mod openai_completion_request_params;
+pub mod openai_embeddings_encoding_format;
+pub mod openai_embeddings_input;
+pub mod openai_embeddings_request_params;
+pub mod openai_embeddings_response;
+pub mod openai_embeddings_transformer;
 pub mod openai_error;
+pub mod openai_finish_reason;
+pub mod openai_json_schema_format;
+pub mod openai_logprobs_json;
 pub mod openai_message;
+pub mod openai_model;
 pub mod openai_non_streaming_response_transformer;
 pub mod openai_non_streaming_state;
+pub mod openai_response_format;
 pub mod openai_responses_function_call_item;
 pub mod openai_responses_function_call_output_item;
 pub mod openai_responses_function_output;
@@ -31,8 +43,18 @@ pub mod openai_responses_tagged_item;
 pub mod openai_responses_text_format;
 pub mod openai_responses_text_param;
 pub mod openai_responses_tool;
+pub mod openai_responses_tool_choice;
+pub mod openai_sampling_params;
+pub mod openai_stop;
 pub mod openai_streaming_response_transformer;
 pub mod openai_streaming_state;
+pub mod openai_text_completion_choice;
+pub mod openai_text_completion_non_streaming_response_transformer;
+pub mod openai_text_completion_prompt;
+pub mod openai_text_completion_request_params;
+pub mod openai_text_completion_streaming_response_transformer;
+pub mod openai_tool_choice_function;
+pub mod openai_tool_choice_mode;
 pub mod openai_tool_parameters_schema;
 pub mod openai_usage_json;
 pub mod output_item_event;
@@ -57,27 +79,36 @@ pub mod try_universal_error_chunk;
 use std::sync::Arc;
 
 use actix_web::App;
+use actix_web::middleware::from_fn;
 use actix_web::web::Data;
 use anyhow::Result;
 use async_trait::async_trait;
 use tokio_util::sync::CancellationToken;
 use trzcina::Service;
 
+use crate::agent_controller_pool::AgentControllerPool;
+use crate::api_key_store::ApiKeyStore;
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::buffered_request_manager::BufferedRequestManager;
 use crate::compatibility::openai_service::app_data::AppData;
 use crate::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
 use crate::create_cors_middleware::create_cors_middleware;
+use crate::enforce_rate_limits::enforce_rate_limits;
 use crate::http_route as common_http_route;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::rate_limiter::RateLimiter;
+use crate::require_api_key::require_api_key;
 use crate::run_http_service::run_http_service;
 use crate::run_http_service_parameters::RunHttpServiceParameters;
 
 pub struct OpenAIService {
+    pub agent_controller_pool: Arc<AgentControllerPool>,
+    pub api_key_store: Option<Arc<ApiKeyStore>>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub inference_service_configuration: InferenceServiceConfiguration,
     pub openai_service_configuration: OpenAIServiceConfiguration,
+    pub rate_limiter: Option<Arc<RateLimiter>>,
 }
 
 #[async_trait]
@@ -95,25 +126,46 @@ impl Service for OpenAIService {
         );
 
         let app_data = Data::new(AppData {
+            agent_controller_pool: self.agent_controller_pool.clone(),
             balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             buffered_request_manager: self.buffered_request_manager.clone(),
             inference_service_configuration: self.inference_service_configuration.clone(),
             shutdown: shutdown.clone(),
         });
+        let api_key_store = self.api_key_store.clone().map(Data::from);
+        let rate_limiter = self.rate_limiter.clone().map(Data::from);
+        let request_observability = Data::new(self.buffered_request_manager.observability.clone());
 
         run_http_service(
             shutdown,
             RunHttpServiceParameters {
                 app_factory: move || {
-                    App::new()
+                    let app = App::new()
+                        .wrap(from_fn(enforce_rate_limits))
+                        .wrap(from_fn(require_api_key))
                         .wrap(create_cors_middleware(&cors_allowed_hosts_arc))
                         .app_data(app_data.clone())
-                        .configure(common_http_route::get_health::register)
+                        .app_data(request_observability.clone());
+                    let app = match &api_key_store {
+                        Some(api_key_store) => app.app_data(api_key_store.clone()),
+                        None => app,
+                    };
+                    let app = match &rate_limiter {
+                        Some(rate_limiter) => app.app_data(rate_limiter.clone()),
+                        None => app,
+                    };
+
+                    app.configure(common_http_route::get_health::register)
+                        .configure(http_route::get_model::register)
+                        .configure(http_route::get_models::register)
                         .configure(http_route::post_chat_completions::register)
+                        .configure(http_route::post_completions::register)
+                        .configure(http_route::post_embeddings::register)
                         .configure(http_route::post_responses::register)
                 },
                 bind_addr: self.openai_service_configuration.addr,
                 service_name,
+                tls_configuration: self.openai_service_configuration.tls_configuration.clone(),
                 worker_count: 16,
             },
         )
@@ -133,27 +185,40 @@ mod tests {
 
     use super::OpenAIService;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
     use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use crate::buffered_request_manager::BufferedRequestManager;
     use crate::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
+    use crate::dispatch_strategy::DispatchStrategy;
     use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+    use crate::request_observability::RequestObservability;
 
     fn build_service(addr: SocketAddr) -> OpenAIService {
         let agent_controller_pool = Arc::new(AgentControllerPool::default());
 
         OpenAIService {
+            agent_controller_pool: agent_controller_pool.clone(),
+            api_key_store: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
                 agent_controller_pool,
                 Duration::from_secs(30),
                 32,
+                RequestObservability::default(),
             )),
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
                 cors_allowed_hosts: vec!["http://127.0.0.1:8080".to_owned()],
+                dispatch_strategy: DispatchStrategy::LeastBusy,
                 inference_item_timeout: Duration::from_secs(30),
+                tls_configuration: None,
             },
-            openai_service_configuration: OpenAIServiceConfiguration { addr },
+            openai_service_configuration: OpenAIServiceConfiguration {
+                addr,
+                tls_configuration: None,
+            },
+            rate_limiter: None,
         }
     }
 
@@ -0,0 +1,61 @@
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+use serde::Deserialize;
+
+use crate::compatibility::openai_service::openai_tool_choice_function::OpenAIToolChoiceFunction;
+use crate::compatibility::openai_service::openai_tool_choice_mode::OpenAIToolChoiceMode;
+
+/// Either a mode string or `{"type": "function", "function": {"name": ...}}`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIChatCompletionToolChoice {
+    Mode(OpenAIToolChoiceMode),
+    Function { function: OpenAIToolChoiceFunction },
+}
+
+impl OpenAIChatCompletionToolChoice {
+    #[must_use]
+    pub fn to_tool_choice(&self) -> ToolChoice {
+        match self {
+            Self::Mode(mode) => mode.to_tool_choice(),
+            Self::Function { function } => ToolChoice::Function {
+                name: function.name.clone(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+    use serde_json::json;
+
+    use super::OpenAIChatCompletionToolChoice;
+
+    fn tool_choice_from(value: serde_json::Value) -> ToolChoice {
+        serde_json::from_value::<OpenAIChatCompletionToolChoice>(value)
+            .unwrap()
+            .to_tool_choice()
+    }
+
+    #[test]
+    fn mode_strings_map_onto_tool_choices() {
+        assert_eq!(tool_choice_from(json!("auto")), ToolChoice::Auto);
+        assert_eq!(tool_choice_from(json!("none")), ToolChoice::None);
+        assert_eq!(tool_choice_from(json!("required")), ToolChoice::Required);
+    }
+
+    #[test]
+    fn named_function_maps_onto_a_function_choice() {
+        assert_eq!(
+            tool_choice_from(json!({"type": "function", "function": {"name": "get_weather"}})),
+            ToolChoice::Function {
+                name: "get_weather".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn unknown_mode_is_rejected() {
+        assert!(serde_json::from_value::<OpenAIChatCompletionToolChoice>(json!("always")).is_err());
+    }
+}
@@ -1,23 +1,80 @@
+use anyhow::Result;
+use anyhow::bail;
+use paddler_messaging::grammar_constraint::GrammarConstraint;
+use paddler_messaging::logprobs_request::LogprobsRequest;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+use paddler_messaging::validates::Validates as _;
 use serde::Deserialize;
 
 use crate::compatibility::openai_service::openai_chat_completion_tool::OpenAIChatCompletionTool;
+use crate::compatibility::openai_service::openai_chat_completion_tool_choice::OpenAIChatCompletionToolChoice;
 use crate::compatibility::openai_service::openai_message::OpenAIMessage;
+use crate::compatibility::openai_service::openai_response_format::OpenAIResponseFormat;
+use crate::compatibility::openai_service::openai_sampling_params::OpenAISamplingParams;
 use crate::compatibility::openai_service::stream_options::StreamOptions;
 
 #[derive(Deserialize)]
 pub struct OpenAICompletionRequestParams {
+    #[serde(default)]
+    pub logprobs: Option<bool>,
     pub max_completion_tokens: Option<i32>,
     pub messages: Vec<OpenAIMessage>,
-    /// This parameter is ignored here, but is required by the `OpenAI` API.
+    /// Names the model pool to dispatch to; unknown names are rejected once pools are configured.
     pub model: String,
+    /// Keeps requests that share a key on the same agent under session-affinity dispatch.
+    #[serde(default)]
+    pub prompt_cache_key: Option<String>,
+    #[serde(default)]
+    pub response_format: Option<OpenAIResponseFormat>,
+    #[serde(flatten)]
+    pub sampling: OpenAISamplingParams,
     pub stream: Option<bool>,
     pub stream_options: Option<StreamOptions>,
     #[serde(default)]
+    pub tool_choice: Option<OpenAIChatCompletionToolChoice>,
+    #[serde(default)]
     pub tools: Vec<OpenAIChatCompletionTool>,
+    #[serde(default)]
+    pub top_logprobs: Option<u32>,
+}
+
+impl OpenAICompletionRequestParams {
+    pub fn to_grammar_constraint(&self) -> Result<Option<GrammarConstraint>> {
+        self.response_format
+            .as_ref()
+            .map_or(Ok(None), OpenAIResponseFormat::to_grammar_constraint)
+    }
+
+    #[must_use]
+    pub fn to_tool_choice(&self) -> ToolChoice {
+        self.tool_choice.as_ref().map_or_else(
+            ToolChoice::default,
+            OpenAIChatCompletionToolChoice::to_tool_choice,
+        )
+    }
+
+    pub fn to_logprobs_request(&self) -> Result<Option<LogprobsRequest>> {
+        if self.logprobs != Some(true) {
+            if self.top_logprobs.is_some() {
+                bail!("top_logprobs requires logprobs to be true");
+            }
+
+            return Ok(None);
+        }
+
+        Ok(Some(
+            LogprobsRequest {
+                top_logprobs: self.top_logprobs.unwrap_or_default(),
+            }
+            .validate()?,
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use paddler_messaging::logprobs_request::LogprobsRequest;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
     use serde_json::json;
 
     use super::OpenAICompletionRequestParams;
@@ -114,6 +171,31 @@ mod tests {
         assert_eq!(params.messages.len(), 4);
     }
 
+    #[test]
+    fn deserialize_request_with_response_format() {
+        let params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "response_format": {"type": "json_object"}
+        }))
+        .unwrap();
+
+        assert!(params.to_grammar_constraint().unwrap().is_some());
+    }
+
+    #[test]
+    fn deserialize_request_with_a_forced_tool_call() {
+        let params: OpenAICompletionRequestParams = serde_json::from_value(json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "tool_choice": "required",
+            "tools": [{"type": "function", "function": {"name": "get_weather"}}]
+        }))
+        .unwrap();
+
+        assert_eq!(params.to_tool_choice(), ToolChoice::Required);
+    }
+
     #[test]
     fn deserialize_request_with_opencode_style_tools() {
         let input = json!({
@@ -163,4 +245,60 @@ mod tests {
 
         assert_eq!(params.tools.len(), 2);
     }
+
+    #[test]
+    fn logprobs_absent_requests_nothing() {
+        let input = json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}]
+        });
+
+        let params: OpenAICompletionRequestParams = serde_json::from_value(input).unwrap();
+
+        assert_eq!(params.to_logprobs_request().unwrap(), None);
+    }
+
+    #[test]
+    fn logprobs_with_top_logprobs_are_forwarded() {
+        let input = json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "logprobs": true,
+            "top_logprobs": 3
+        });
+
+        let params: OpenAICompletionRequestParams = serde_json::from_value(input).unwrap();
+
+        assert_eq!(
+            params.to_logprobs_request().unwrap(),
+            Some(LogprobsRequest { top_logprobs: 3 })
+        );
+    }
+
+    #[test]
+    fn top_logprobs_without_logprobs_are_rejected() {
+        let input = json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "top_logprobs": 3
+        });
+
+        let params: OpenAICompletionRequestParams = serde_json::from_value(input).unwrap();
+
+        assert!(params.to_logprobs_request().is_err());
+    }
+
+    #[test]
+    fn top_logprobs_above_the_limit_are_rejected() {
+        let input = json!({
+            "model": "test-model",
+            "messages": [{"role": "user", "content": "hi"}],
+            "logprobs": true,
+            "top_logprobs": 21
+        });
+
+        let params: OpenAICompletionRequestParams = serde_json::from_value(input).unwrap();
+
+        assert!(params.to_logprobs_request().is_err());
+    }
 }
@@ -0,0 +1,55 @@
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use serde::Deserialize;
+use serde_json::Value;
+use serde_json::json;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenAIEmbeddingsEncodingFormat {
+    /// Little-endian `f32` values, encoded as base64.
+    Base64,
+    #[default]
+    Float,
+}
+
+impl OpenAIEmbeddingsEncodingFormat {
+    #[must_use]
+    pub fn encode(self, embedding: &[f32]) -> Value {
+        match self {
+            Self::Base64 => json!(
+                BASE64_STANDARD.encode(
+                    embedding
+                        .iter()
+                        .flat_map(|value| value.to_le_bytes())
+                        .collect::<Vec<u8>>()
+                )
+            ),
+            Self::Float => json!(embedding),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_encodes_little_endian_floats() {
+        let encoded = OpenAIEmbeddingsEncodingFormat::Base64.encode(&[1.0, -2.5]);
+        let bytes = BASE64_STANDARD.decode(encoded.as_str().unwrap()).unwrap();
+
+        assert_eq!(
+            bytes,
+            [1.0_f32.to_le_bytes(), (-2.5_f32).to_le_bytes()].concat()
+        );
+    }
+
+    #[test]
+    fn float_encodes_a_json_array() {
+        assert_eq!(
+            OpenAIEmbeddingsEncodingFormat::Float.encode(&[0.5, 0.25]),
+            json!([0.5, 0.25])
+        );
+    }
+}
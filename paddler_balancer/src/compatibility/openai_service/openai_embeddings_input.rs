@@ -0,0 +1,85 @@
+use paddler_messaging::embedding_input_document::EmbeddingInputDocument;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIEmbeddingsInput {
+    String(String),
+    Strings(Vec<String>),
+    Tokens(Vec<i32>),
+    TokenArrays(Vec<Vec<i32>>),
+}
+
+impl OpenAIEmbeddingsInput {
+    /// Documents are identified by their position in the input, so the embeddings can be put
+    /// back in order no matter which agent produced them.
+    #[must_use]
+    pub fn into_documents(self) -> Vec<EmbeddingInputDocument> {
+        let inputs: Vec<(String, Option<Vec<i32>>)> = match self {
+            Self::String(content) => vec![(content, None)],
+            Self::Strings(contents) => contents
+                .into_iter()
+                .map(|content| (content, None))
+                .collect(),
+            Self::Tokens(tokens) => vec![(String::new(), Some(tokens))],
+            Self::TokenArrays(token_arrays) => token_arrays
+                .into_iter()
+                .map(|tokens| (String::new(), Some(tokens)))
+                .collect(),
+        };
+
+        inputs
+            .into_iter()
+            .enumerate()
+            .map(|(index, (content, tokens))| EmbeddingInputDocument {
+                content,
+                id: index.to_string(),
+                tokens,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn documents(input: serde_json::Value) -> Vec<EmbeddingInputDocument> {
+        serde_json::from_value::<OpenAIEmbeddingsInput>(input)
+            .unwrap()
+            .into_documents()
+    }
+
+    #[test]
+    fn text_inputs_are_numbered_in_order() {
+        let documents = documents(json!(["first", "second"]));
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[0].content, "first");
+        assert_eq!(documents[0].id, "0");
+        assert_eq!(documents[1].content, "second");
+        assert_eq!(documents[1].id, "1");
+        assert!(documents[1].tokens.is_none());
+    }
+
+    #[test]
+    fn a_single_string_is_one_document() {
+        let documents = documents(json!("hello"));
+
+        assert_eq!(documents.len(), 1);
+        assert_eq!(documents[0].content, "hello");
+    }
+
+    #[test]
+    fn token_arrays_are_passed_through() {
+        assert_eq!(documents(json!([1, 2, 3]))[0].tokens, Some(vec![1, 2, 3]));
+
+        let documents = documents(json!([[1, 2], [3]]));
+
+        assert_eq!(documents.len(), 2);
+        assert_eq!(documents[1].id, "1");
+        assert_eq!(documents[1].tokens, Some(vec![3]));
+    }
+}
@@ -0,0 +1,16 @@
+use serde::Deserialize;
+
+use crate::compatibility::openai_service::openai_embeddings_encoding_format::OpenAIEmbeddingsEncodingFormat;
+use crate::compatibility::openai_service::openai_embeddings_input::OpenAIEmbeddingsInput;
+
+#[derive(Deserialize)]
+pub struct OpenAIEmbeddingsRequestParams {
+    /// Shortens every embedding to this many leading dimensions.
+    #[serde(default)]
+    pub dimensions: Option<usize>,
+    #[serde(default)]
+    pub encoding_format: OpenAIEmbeddingsEncodingFormat,
+    pub input: OpenAIEmbeddingsInput,
+    /// Names the model pool to dispatch to; unknown names are rejected once pools are configured.
+    pub model: String,
+}
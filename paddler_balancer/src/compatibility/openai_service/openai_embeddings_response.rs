@@ -0,0 +1,187 @@
+use paddler_messaging::embedding::Embedding;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::compatibility::openai_service::openai_embeddings_encoding_format::OpenAIEmbeddingsEncodingFormat;
+use crate::compatibility::openai_service::openai_error::OpenAIError;
+
+/// Keeps the leading dimensions and scales them back to unit length, the way models trained
+/// with Matryoshka representation learning expect to be shortened.
+fn shorten(mut embedding: Vec<f32>, dimensions: usize) -> Vec<f32> {
+    embedding.truncate(dimensions);
+
+    let norm = embedding
+        .iter()
+        .map(|value| value * value)
+        .sum::<f32>()
+        .sqrt();
+
+    if norm > 0.0 {
+        for value in &mut embedding {
+            *value /= norm;
+        }
+    }
+
+    embedding
+}
+
+const fn invalid_request(message: String) -> OpenAIError {
+    OpenAIError {
+        error_type: "invalid_request_error",
+        message,
+    }
+}
+
+/// Puts the embeddings back in input order; agents answer their chunks in any order.
+pub fn openai_embeddings_response(
+    embeddings: Vec<Embedding>,
+    input_count: usize,
+    dimensions: Option<usize>,
+    encoding_format: OpenAIEmbeddingsEncodingFormat,
+    model: &str,
+) -> Result<Value, OpenAIError> {
+    let mut ordered: Vec<Option<Vec<f32>>> = vec![None; input_count];
+    let mut prompt_tokens: usize = 0;
+
+    for embedding in embeddings {
+        let Some(slot) = embedding
+            .source_document_id
+            .parse::<usize>()
+            .ok()
+            .and_then(|index| ordered.get_mut(index))
+        else {
+            return Err(OpenAIError {
+                error_type: "server_error",
+                message: format!(
+                    "agent returned an embedding for unknown input {:?}",
+                    embedding.source_document_id
+                ),
+            });
+        };
+
+        prompt_tokens += embedding.token_count;
+        *slot = Some(match dimensions {
+            Some(dimensions) if dimensions > embedding.embedding.len() => {
+                return Err(invalid_request(format!(
+                    "dimensions is {dimensions} but the model produces embeddings with {} dimensions",
+                    embedding.embedding.len()
+                )));
+            }
+            Some(dimensions) => shorten(embedding.embedding, dimensions),
+            None => embedding.embedding,
+        });
+    }
+
+    let data = ordered
+        .into_iter()
+        .enumerate()
+        .map(|(index, embedding)| {
+            embedding
+                .map(|embedding| {
+                    json!({
+                        "object": "embedding",
+                        "index": index,
+                        "embedding": encoding_format.encode(&embedding),
+                    })
+                })
+                .ok_or_else(|| OpenAIError {
+                    error_type: "server_error",
+                    message: format!("no embedding was produced for input {index}"),
+                })
+        })
+        .collect::<Result<Vec<Value>, OpenAIError>>()?;
+
+    Ok(json!({
+        "object": "list",
+        "data": data,
+        "model": model,
+        "usage": {
+            "prompt_tokens": prompt_tokens,
+            "total_tokens": prompt_tokens,
+        },
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::embedding_normalization_method::EmbeddingNormalizationMethod;
+    use paddler_messaging::pooling_type::PoolingType;
+
+    use super::*;
+
+    fn embedding(index: usize, values: Vec<f32>, token_count: usize) -> Embedding {
+        Embedding {
+            embedding: values,
+            normalization_method: EmbeddingNormalizationMethod::L2,
+            pooling_type: PoolingType::Mean,
+            source_document_id: index.to_string(),
+            token_count,
+        }
+    }
+
+    #[test]
+    fn reassembles_embeddings_in_input_order_and_sums_usage() {
+        let response = openai_embeddings_response(
+            vec![
+                embedding(1, vec![0.0, 1.0], 3),
+                embedding(0, vec![1.0, 0.0], 2),
+            ],
+            2,
+            None,
+            OpenAIEmbeddingsEncodingFormat::Float,
+            "embedder",
+        )
+        .unwrap();
+
+        assert_eq!(response["data"][0]["index"], 0);
+        assert_eq!(response["data"][0]["embedding"], json!([1.0, 0.0]));
+        assert_eq!(response["data"][1]["embedding"], json!([0.0, 1.0]));
+        assert_eq!(response["model"], "embedder");
+        assert_eq!(response["usage"]["prompt_tokens"], 5);
+        assert_eq!(response["usage"]["total_tokens"], 5);
+    }
+
+    #[test]
+    fn shortened_embeddings_are_renormalized() {
+        let response = openai_embeddings_response(
+            vec![embedding(0, vec![0.6, 0.6, 0.52], 1)],
+            1,
+            Some(2),
+            OpenAIEmbeddingsEncodingFormat::Float,
+            "embedder",
+        )
+        .unwrap();
+        let values = response["data"][0]["embedding"].as_array().unwrap();
+
+        assert_eq!(values.len(), 2);
+        assert!((values[0].as_f64().unwrap() - 0.5_f64.sqrt()).abs() < 1e-6);
+    }
+
+    #[test]
+    fn rejects_dimensions_larger_than_the_embedding() {
+        let error = openai_embeddings_response(
+            vec![embedding(0, vec![1.0], 1)],
+            1,
+            Some(8),
+            OpenAIEmbeddingsEncodingFormat::Float,
+            "embedder",
+        )
+        .unwrap_err();
+
+        assert_eq!(error.error_type, "invalid_request_error");
+    }
+
+    #[test]
+    fn reports_inputs_without_an_embedding() {
+        let error = openai_embeddings_response(
+            vec![embedding(0, vec![1.0], 1)],
+            2,
+            None,
+            OpenAIEmbeddingsEncodingFormat::Float,
+            "embedder",
+        )
+        .unwrap_err();
+
+        assert!(error.message.contains("input 1"));
+    }
+}
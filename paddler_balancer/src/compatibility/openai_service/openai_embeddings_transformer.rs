@@ -0,0 +1,33 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use paddler_messaging::embedding::Embedding;
+use paddler_messaging::embedding_result::EmbeddingResult;
+use paddler_messaging::inference_client::message::Message as OutgoingMessage;
+use paddler_messaging::inference_client::response::Response as OutgoingResponse;
+use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+
+use crate::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::compatibility::openai_service::openai_error::OpenAIError;
+
+#[derive(Clone)]
+pub struct OpenAIEmbeddingsTransformer;
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAIEmbeddingsTransformer {
+    type Output = Result<Embedding, OpenAIError>;
+
+    async fn transform(&self, message: OutgoingMessage) -> Result<Vec<Self::Output>> {
+        if let OutgoingMessage::Response(ResponseEnvelope {
+            response: OutgoingResponse::Embedding(EmbeddingResult::Embedding(embedding)),
+            ..
+        }) = message
+        {
+            return Ok(vec![Ok(embedding)]);
+        }
+
+        Ok(OpenAIError::classify(&message)
+            .map(Err)
+            .into_iter()
+            .collect())
+    }
+}
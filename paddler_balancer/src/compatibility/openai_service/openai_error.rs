@@ -1,9 +1,12 @@
+use actix_web::http::StatusCode;
+use paddler_messaging::embedding_result::EmbeddingResult;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::inference_client::message::Message as OutgoingMessage;
 use paddler_messaging::inference_client::response::Response as OutgoingResponse;
 use paddler_messaging::jsonrpc::error::Error as JsonRpcError;
 use paddler_messaging::jsonrpc::error_envelope::ErrorEnvelope;
 use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+use paddler_messaging::oversized_embedding_document_details::OversizedEmbeddingDocumentDetails;
 use paddler_messaging::oversized_image_details::OversizedImageDetails;
 use paddler_messaging::raw_tool_call_tokens::RawToolCallTokens;
 use serde_json::Value;
@@ -31,6 +34,43 @@ fn image_exceeds_batch_size_message(details: &OversizedImageDetails) -> String {
     )
 }
 
+fn document_exceeds_batch_size_message(details: &OversizedEmbeddingDocumentDetails) -> String {
+    format!(
+        "input {} has {} tokens but agent n_batch is {}",
+        details.source_document_id, details.document_tokens, details.n_batch,
+    )
+}
+
+fn error_from_embedding_result(embedding_result: &EmbeddingResult) -> Option<OpenAIError> {
+    let (error_type, message) = match embedding_result {
+        EmbeddingResult::DocumentExceedsBatchSize(details) => (
+            "invalid_request_error",
+            document_exceeds_batch_size_message(details),
+        ),
+        EmbeddingResult::EmbeddingRejectedDueToActiveTokenGeneration => (
+            "server_error",
+            "embedding was rejected because the agent is generating tokens".to_owned(),
+        ),
+        EmbeddingResult::EmbeddingsDisabled => (
+            "server_error",
+            "embeddings are not enabled in the inference parameters".to_owned(),
+        ),
+        EmbeddingResult::Error(description) => ("server_error", description.clone()),
+        EmbeddingResult::InvalidTokens(description) => {
+            ("invalid_request_error", description.clone())
+        }
+        EmbeddingResult::NoEmbeddingsProduced => {
+            ("server_error", "no embeddings were produced".to_owned())
+        }
+        EmbeddingResult::Done | EmbeddingResult::Embedding(_) => return None,
+    };
+
+    Some(OpenAIError {
+        error_type,
+        message,
+    })
+}
+
 fn description_from_error_token(token: &GeneratedTokenResult) -> Option<&str> {
     match token {
         GeneratedTokenResult::ChatTemplateError(description)
@@ -44,6 +84,7 @@ fn description_from_error_token(token: &GeneratedTokenResult) -> Option<&str> {
         | GeneratedTokenResult::SamplerError(description)
         | GeneratedTokenResult::TokenGenerationDisabled(description)
         | GeneratedTokenResult::ToolCallParseFailed(description)
+        | GeneratedTokenResult::ToolChoiceNotSupported(description)
         | GeneratedTokenResult::ToolSchemaInvalid(description) => Some(description),
         _ => None,
     }
@@ -55,6 +96,10 @@ fn server_error_from_token(token: &GeneratedTokenResult) -> Option<OpenAIError>
             error_type: "server_error",
             message: image_exceeds_batch_size_message(details),
         }),
+        GeneratedTokenResult::ToolChoiceNotSupported(description) => Some(OpenAIError {
+            error_type: "invalid_request_error",
+            message: description.clone(),
+        }),
         GeneratedTokenResult::ToolCallValidationFailed(errors) => Some(OpenAIError {
             error_type: "server_error",
             message: validation_failure_message(errors),
@@ -70,6 +115,7 @@ fn server_error_from_token(token: &GeneratedTokenResult) -> Option<OpenAIError>
     }
 }
 
+#[derive(Debug)]
 pub struct OpenAIError {
     pub error_type: &'static str,
     pub message: String,
@@ -97,11 +143,27 @@ impl OpenAIError {
                     error_type: "rate_limit_error",
                     message: "too many buffered requests".to_owned(),
                 }),
-                OutgoingResponse::Embedding(_) => None,
+                OutgoingResponse::Embedding(embedding_result) => {
+                    error_from_embedding_result(embedding_result)
+                }
             },
         }
     }
 
+    /// Status of a non-streaming response that failed with the given error envelope: the
+    /// client's fault for invalid requests, the server's otherwise.
+    #[must_use]
+    pub fn status_code_of_envelope(envelope: &str) -> StatusCode {
+        let is_invalid_request = serde_json::from_str::<Value>(envelope)
+            .is_ok_and(|envelope| envelope["error"]["type"] == "invalid_request_error");
+
+        if is_invalid_request {
+            StatusCode::BAD_REQUEST
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+
     #[must_use]
     pub fn to_envelope(&self) -> Value {
         json!({
@@ -117,6 +179,7 @@ impl OpenAIError {
 
 #[cfg(test)]
 mod tests {
+    use actix_web::http::StatusCode;
     use llama_cpp_bindings_types::ToolCallArguments;
     use serde_json::json;
 
@@ -131,6 +194,7 @@ mod tests {
     use paddler_messaging::generated_token_result::GeneratedTokenResult;
     use paddler_messaging::jsonrpc::error::Error as JsonRpcError;
     use paddler_messaging::jsonrpc::error_envelope::ErrorEnvelope;
+    use paddler_messaging::oversized_embedding_document_details::OversizedEmbeddingDocumentDetails;
 
     fn token_message(token_result: GeneratedTokenResult) -> OutgoingMessage {
         OutgoingMessage::Response(ResponseEnvelope {
@@ -253,6 +317,82 @@ mod tests {
         assert!(OpenAIError::classify(&message).is_none());
     }
 
+    #[test]
+    fn classifies_an_oversized_embedding_document_as_invalid_request() {
+        let message = OutgoingMessage::Response(ResponseEnvelope {
+            generated_by: None,
+            request_id: "test-request".to_owned(),
+            response: OutgoingResponse::Embedding(EmbeddingResult::DocumentExceedsBatchSize(
+                OversizedEmbeddingDocumentDetails {
+                    document_tokens: 4096,
+                    n_batch: 512,
+                    source_document_id: "3".to_owned(),
+                },
+            )),
+        });
+
+        let classified = OpenAIError::classify(&message).unwrap();
+
+        assert_eq!(classified.error_type, "invalid_request_error");
+        assert_eq!(
+            classified.message,
+            "input 3 has 4096 tokens but agent n_batch is 512"
+        );
+    }
+
+    #[test]
+    fn classifies_invalid_embedding_tokens_as_invalid_request() {
+        let message = OutgoingMessage::Response(ResponseEnvelope {
+            generated_by: None,
+            request_id: "test-request".to_owned(),
+            response: OutgoingResponse::Embedding(EmbeddingResult::InvalidTokens(
+                "input 0 has token 999999 outside of the model vocabulary of 32000 tokens"
+                    .to_owned(),
+            )),
+        });
+
+        let classified = OpenAIError::classify(&message).unwrap();
+
+        assert_eq!(classified.error_type, "invalid_request_error");
+        assert!(classified.message.contains("999999"));
+    }
+
+    #[test]
+    fn classifies_an_unsupported_tool_choice_as_invalid_request() {
+        let classified = OpenAIError::classify(&token_message(
+            GeneratedTokenResult::ToolChoiceNotSupported("cannot force a tool call".to_owned()),
+        ))
+        .unwrap();
+
+        assert_eq!(classified.error_type, "invalid_request_error");
+        assert_eq!(classified.message, "cannot force a tool call");
+    }
+
+    #[test]
+    fn invalid_request_envelopes_are_client_errors() {
+        let envelope = |error_type| {
+            OpenAIError {
+                error_type,
+                message: "something went wrong".to_owned(),
+            }
+            .to_envelope()
+            .to_string()
+        };
+
+        assert_eq!(
+            OpenAIError::status_code_of_envelope(&envelope("invalid_request_error")),
+            StatusCode::BAD_REQUEST
+        );
+        assert_eq!(
+            OpenAIError::status_code_of_envelope(&envelope("server_error")),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+        assert_eq!(
+            OpenAIError::status_code_of_envelope("not json"),
+            StatusCode::INTERNAL_SERVER_ERROR
+        );
+    }
+
     #[test]
     fn classifies_a_tool_call_with_arguments_is_unrelated_to_errors() {
         let parsed = vec![llama_cpp_bindings_types::ParsedToolCall::new(
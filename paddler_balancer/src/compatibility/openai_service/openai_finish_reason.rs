@@ -0,0 +1,43 @@
+use paddler_messaging::stop_reason::StopReason;
+
+#[must_use]
+pub const fn openai_finish_reason(stop_reason: StopReason, has_tool_calls: bool) -> &'static str {
+    if has_tool_calls {
+        return "tool_calls";
+    }
+
+    match stop_reason {
+        StopReason::MaxTokens => "length",
+        StopReason::Cancelled | StopReason::EndOfGeneration | StopReason::StopSequence => "stop",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_out_of_tokens_finishes_with_length() {
+        assert_eq!(openai_finish_reason(StopReason::MaxTokens, false), "length");
+        assert_eq!(
+            openai_finish_reason(StopReason::StopSequence, false),
+            "stop"
+        );
+        assert_eq!(
+            openai_finish_reason(StopReason::EndOfGeneration, false),
+            "stop"
+        );
+    }
+
+    #[test]
+    fn tool_calls_take_precedence_over_the_stop_reason() {
+        assert_eq!(
+            openai_finish_reason(StopReason::MaxTokens, true),
+            "tool_calls"
+        );
+        assert_eq!(
+            openai_finish_reason(StopReason::EndOfGeneration, true),
+            "tool_calls"
+        );
+    }
+}
@@ -0,0 +1,31 @@
+use paddler_messaging::token_logprob::TokenLogprob;
+use serde_json::Value;
+use serde_json::json;
+
+#[must_use]
+pub fn openai_logprobs_json(token_logprobs: &[TokenLogprob]) -> Value {
+    if token_logprobs.is_empty() {
+        return Value::Null;
+    }
+
+    json!({
+        "content": token_logprobs
+            .iter()
+            .map(|token_logprob| json!({
+                "token": token_logprob.token,
+                "logprob": token_logprob.logprob,
+                "bytes": token_logprob.token.as_bytes(),
+                "top_logprobs": token_logprob
+                    .top_logprobs
+                    .iter()
+                    .map(|top_logprob| json!({
+                        "token": top_logprob.token,
+                        "logprob": top_logprob.logprob,
+                        "bytes": top_logprob.token.as_bytes(),
+                    }))
+                    .collect::<Vec<_>>(),
+            }))
+            .collect::<Vec<_>>(),
+        "refusal": null,
+    })
+}
@@ -0,0 +1,14 @@
+use serde::Serialize;
+
+/// Entry of the `/v1/models` list. `context_length`, `model_pool` and `name` are not part
+/// of the object returned by `OpenAI`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct OpenAIModel {
+    pub context_length: u32,
+    pub created: u64,
+    pub id: String,
+    pub model_pool: Option<String>,
+    pub name: Option<String>,
+    pub object: &'static str,
+    pub owned_by: String,
+}
@@ -1,3 +1,4 @@
+use std::mem::take;
 use std::sync::Arc;
 
 use anyhow::Context as _;
@@ -10,12 +11,15 @@ use paddler_messaging::generation_summary::GenerationSummary;
 use paddler_messaging::inference_client::message::Message as OutgoingMessage;
 use paddler_messaging::inference_client::response::Response as OutgoingResponse;
 use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+use paddler_messaging::token_logprob::TokenLogprob;
 use parking_lot::Mutex;
 use serde_json::json;
 
 use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
 use crate::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
 use crate::compatibility::openai_service::arguments_to_tool_call_string::arguments_to_tool_call_string;
+use crate::compatibility::openai_service::openai_finish_reason::openai_finish_reason;
+use crate::compatibility::openai_service::openai_logprobs_json::openai_logprobs_json;
 use crate::compatibility::openai_service::openai_non_streaming_state::OpenAINonStreamingState;
 use crate::compatibility::openai_service::openai_usage_json::openai_usage_json;
 use crate::compatibility::openai_service::try_universal_error_chunk::try_universal_error_chunk;
@@ -29,7 +33,19 @@ pub struct OpenAINonStreamingResponseTransformer {
 
 impl OpenAINonStreamingResponseTransformer {
     fn append_content(&self, text: &str) {
-        self.state.lock().content.push_str(text);
+        let mut state = self.state.lock();
+        let pending_logprobs = take(&mut state.pending_logprobs);
+
+        state.content.push_str(text);
+        state.logprobs.extend(pending_logprobs);
+    }
+
+    fn append_logprob(&self, token_logprob: TokenLogprob) {
+        self.state.lock().pending_logprobs.push(token_logprob);
+    }
+
+    fn discard_pending_logprobs(&self) {
+        self.state.lock().pending_logprobs.clear();
     }
 
     fn append_tool_calls(&self, parsed_calls: Vec<ParsedToolCall>) {
@@ -40,7 +56,7 @@ impl OpenAINonStreamingResponseTransformer {
         let snapshot = self.snapshot_state();
 
         let has_tool_calls = !snapshot.tool_calls.is_empty();
-        let finish_reason = if has_tool_calls { "tool_calls" } else { "stop" };
+        let finish_reason = openai_finish_reason(summary.stop_reason, has_tool_calls);
 
         let tool_calls_json = snapshot
             .tool_calls
@@ -84,7 +100,7 @@ impl OpenAINonStreamingResponseTransformer {
                     {
                         "index": 0,
                         "message": message_obj,
-                        "logprobs": null,
+                        "logprobs": openai_logprobs_json(&snapshot.logprobs),
                         "finish_reason": finish_reason
                     }
                 ],
@@ -128,7 +144,18 @@ impl TransformsOutgoingMessage for OpenAINonStreamingResponseTransformer {
                         | GeneratedTokenResult::ToolCallToken(_),
                     ),
                 ..
-            }) => Ok(vec![]),
+            }) => {
+                self.discard_pending_logprobs();
+                Ok(vec![])
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::TokenLogprob(token_logprob)),
+                ..
+            }) => {
+                self.append_logprob(token_logprob);
+                Ok(vec![])
+            }
             OutgoingMessage::Response(ResponseEnvelope {
                 response:
                     OutgoingResponse::GeneratedToken(GeneratedTokenResult::ToolCallParsed(parsed_calls)),
@@ -166,6 +193,8 @@ mod tests {
     use paddler_messaging::jsonrpc::error::Error as JsonRpcError;
     use paddler_messaging::jsonrpc::error_envelope::ErrorEnvelope;
     use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+    use paddler_messaging::stop_reason::StopReason;
+    use paddler_messaging::token_logprob::TokenLogprob;
     use parking_lot::Mutex;
     use serde_json::json;
 
@@ -210,6 +239,7 @@ mod tests {
         reasoning_tokens: u64,
     ) -> GenerationSummary {
         GenerationSummary {
+            stop_reason: StopReason::EndOfGeneration,
             usage: TokenUsage {
                 prompt_tokens,
                 content_tokens,
@@ -335,6 +365,45 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn non_streaming_reports_logprobs_of_content_tokens_only() -> Result<()> {
+        let transformer = non_streaming_transformer();
+
+        for (token_result, token) in [
+            (
+                GeneratedTokenResult::ReasoningToken("think".to_owned()),
+                "think",
+            ),
+            (
+                GeneratedTokenResult::ContentToken("answer".to_owned()),
+                "answer",
+            ),
+        ] {
+            transformer
+                .transform(token_message(GeneratedTokenResult::TokenLogprob(
+                    TokenLogprob {
+                        logprob: -0.25,
+                        token: token.to_owned(),
+                        top_logprobs: vec![],
+                    },
+                )))
+                .await?;
+            transformer.transform(token_message(token_result)).await?;
+        }
+
+        let final_chunks = transformer
+            .transform(token_message(GeneratedTokenResult::Done(
+                summary_with_counts(4, 1, 1),
+            )))
+            .await?;
+
+        assert_eq!(final_chunks.len(), 1);
+        assert_chunk_contains(&final_chunks[0], "\"token\":\"answer\"")?;
+        assert_chunk_does_not_contain(&final_chunks[0], "\"token\":\"think\"")?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn non_streaming_drops_reasoning_but_keeps_reasoning_token_count() -> Result<()> {
         let transformer = non_streaming_transformer();
@@ -412,6 +481,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn non_streaming_done_at_max_tokens_uses_length_finish_reason() -> Result<()> {
+        let transformer = non_streaming_transformer();
+
+        transformer
+            .transform(token_message(GeneratedTokenResult::ContentToken(
+                "truncated".to_owned(),
+            )))
+            .await?;
+
+        let summary = GenerationSummary {
+            stop_reason: StopReason::MaxTokens,
+            ..summary_with_counts(4, 1, 0)
+        };
+        let final_chunks = transformer
+            .transform(token_message(GeneratedTokenResult::Done(summary)))
+            .await?;
+
+        assert_eq!(final_chunks.len(), 1);
+        assert_chunk_contains(&final_chunks[0], "\"finish_reason\":\"length\"")?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn non_streaming_tool_call_parse_failed_emits_error() -> Result<()> {
         let transformer = non_streaming_transformer();
@@ -1,7 +1,11 @@
 use llama_cpp_bindings_types::ParsedToolCall;
+use paddler_messaging::token_logprob::TokenLogprob;
 
 #[derive(Clone, Default)]
 pub struct OpenAINonStreamingState {
     pub content: String,
+    pub logprobs: Vec<TokenLogprob>,
+    /// Log-probabilities received ahead of the content token they belong to.
+    pub pending_logprobs: Vec<TokenLogprob>,
     pub tool_calls: Vec<ParsedToolCall>,
 }
@@ -0,0 +1,107 @@
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use paddler_messaging::grammar_constraint::GrammarConstraint;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::compatibility::openai_service::openai_json_schema_format::OpenAIJsonSchemaFormat;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[serde(tag = "type")]
+pub enum OpenAIResponseFormat {
+    JsonObject,
+    JsonSchema { json_schema: OpenAIJsonSchemaFormat },
+    Text,
+}
+
+impl OpenAIResponseFormat {
+    pub fn to_grammar_constraint(&self) -> Result<Option<GrammarConstraint>> {
+        let schema = match self {
+            Self::JsonObject
+            | Self::JsonSchema {
+                json_schema:
+                    OpenAIJsonSchemaFormat {
+                        schema: None,
+                        strict: false,
+                        ..
+                    },
+            } => json!({"type": "object"}),
+            Self::JsonSchema {
+                json_schema:
+                    OpenAIJsonSchemaFormat {
+                        name, schema: None, ..
+                    },
+            } => bail!("response_format json_schema {name:?} is strict but has no schema"),
+            Self::JsonSchema {
+                json_schema:
+                    OpenAIJsonSchemaFormat {
+                        schema: Some(schema),
+                        ..
+                    },
+            } => schema.clone(),
+            Self::Text => return Ok(None),
+        };
+
+        Ok(Some(GrammarConstraint::JsonSchema {
+            schema: serde_json::to_string(&schema)
+                .context("serializing response_format json schema")?,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    fn grammar_constraint(response_format: Value) -> Result<Option<GrammarConstraint>> {
+        serde_json::from_value::<OpenAIResponseFormat>(response_format)?.to_grammar_constraint()
+    }
+
+    #[test]
+    fn text_leaves_the_output_unconstrained() {
+        assert_eq!(grammar_constraint(json!({"type": "text"})).unwrap(), None);
+    }
+
+    #[test]
+    fn json_object_constrains_the_output_to_any_object() {
+        assert_eq!(
+            grammar_constraint(json!({"type": "json_object"})).unwrap(),
+            Some(GrammarConstraint::JsonSchema {
+                schema: r#"{"type":"object"}"#.to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn json_schema_constrains_the_output_to_the_schema() {
+        assert_eq!(
+            grammar_constraint(json!({
+                "type": "json_schema",
+                "json_schema": {
+                    "name": "answer",
+                    "schema": {"type": "object", "properties": {"n": {"type": "integer"}}},
+                    "strict": true
+                }
+            }))
+            .unwrap(),
+            Some(GrammarConstraint::JsonSchema {
+                schema: r#"{"properties":{"n":{"type":"integer"}},"type":"object"}"#.to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn a_strict_json_schema_without_a_schema_is_rejected() {
+        assert!(
+            grammar_constraint(json!({
+                "type": "json_schema",
+                "json_schema": {"name": "answer", "strict": true}
+            }))
+            .is_err()
+        );
+    }
+}
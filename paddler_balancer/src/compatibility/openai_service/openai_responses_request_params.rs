@@ -1,4 +1,5 @@
 use anyhow::Result;
+use anyhow::bail;
 use paddler_messaging::conversation_history::ConversationHistory;
 use paddler_messaging::conversation_message::ConversationMessage;
 use paddler_messaging::conversation_message_content::ConversationMessageContent;
@@ -16,13 +17,16 @@ use crate::compatibility::openai_service::openai_responses_input_item::OpenAIRes
 use crate::compatibility::openai_service::openai_responses_reasoning::OpenAIResponsesReasoning;
 use crate::compatibility::openai_service::openai_responses_text_param::OpenAIResponsesTextParam;
 use crate::compatibility::openai_service::openai_responses_tool::OpenAIResponsesTool;
+use crate::compatibility::openai_service::openai_responses_tool_choice::OpenAIResponsesToolChoice;
+use crate::compatibility::openai_service::openai_sampling_params::OpenAISamplingParams;
 use crate::compatibility::openai_service::responses_prepared_request::ResponsesPreparedRequest;
 
 const DEFAULT_MAX_TOKENS: i32 = 2000;
+const LOGPROBS_INCLUDE: &str = "message.output_text.logprobs";
 
 #[derive(Deserialize)]
 pub struct OpenAIResponsesRequestParams {
-    /// Echoed back in the response object; not used for routing.
+    /// Echoed back in the response object and used to pick the model pool.
     pub model: String,
     #[serde(default)]
     pub input: OpenAIResponsesInput,
@@ -35,9 +39,21 @@ pub struct OpenAIResponsesRequestParams {
     #[serde(default)]
     pub tools: Vec<OpenAIResponsesTool>,
     #[serde(default)]
+    pub tool_choice: Option<OpenAIResponsesToolChoice>,
+    #[serde(default)]
     pub text: Option<OpenAIResponsesTextParam>,
     #[serde(default)]
     pub reasoning: Option<OpenAIResponsesReasoning>,
+    #[serde(default)]
+    pub prompt_cache_key: Option<String>,
+    /// Log-probabilities are not reported by this endpoint, so asking for them through
+    /// `include` or `top_logprobs` is rejected rather than silently ignored.
+    #[serde(default)]
+    pub include: Vec<String>,
+    #[serde(default)]
+    pub top_logprobs: Option<u32>,
+    #[serde(flatten)]
+    pub sampling: OpenAISamplingParams,
 }
 
 impl OpenAIResponsesRequestParams {
@@ -49,10 +65,21 @@ impl OpenAIResponsesRequestParams {
             stream,
             max_output_tokens,
             tools,
+            tool_choice,
             text,
             reasoning,
+            prompt_cache_key,
+            include,
+            top_logprobs,
+            sampling,
         } = self;
 
+        if top_logprobs.is_some_and(|top_logprobs| top_logprobs > 0)
+            || include.iter().any(|included| included == LOGPROBS_INCLUDE)
+        {
+            bail!("logprobs are not supported on /v1/responses; use /v1/chat/completions instead");
+        }
+
         let mut messages: Vec<ConversationMessage> = Vec::new();
 
         if let Some(instructions) = &instructions
@@ -103,21 +130,36 @@ impl OpenAIResponsesRequestParams {
             .map(Validates::validate)
             .collect::<Result<Vec<_>>>()?;
 
+        let grammar = match text {
+            Some(text_param) => text_param.into_grammar_constraint()?,
+            None => None,
+        };
+        let tool_choice = tool_choice.map_or_else(
+            Default::default,
+            OpenAIResponsesToolChoice::into_tool_choice,
+        );
+
+        tool_choice.validate_against(&validated_tools, grammar.as_ref())?;
+
         let parse_tool_calls = !validated_tools.is_empty();
 
         Ok(ResponsesPreparedRequest {
             paddler_params: ContinueFromConversationHistoryParams {
                 add_generation_prompt: true,
                 conversation_history: ConversationHistory::new(messages),
-                enable_thinking: reasoning
-                    .as_ref()
-                    .is_none_or(OpenAIResponsesReasoning::enables_thinking),
-                grammar: match text {
-                    Some(text_param) => text_param.into_grammar_constraint()?,
-                    None => None,
-                },
+                enable_thinking: reasoning.as_ref().map_or_else(
+                    || !tool_choice.forces_tool_call(),
+                    OpenAIResponsesReasoning::enables_thinking,
+                ),
+                grammar,
+                logprobs: None,
                 max_tokens: max_output_tokens.unwrap_or(DEFAULT_MAX_TOKENS),
+                model: Some(model.clone()),
                 parse_tool_calls,
+                sampling: sampling.to_sampling_overrides()?,
+                session_key: prompt_cache_key,
+                stop: sampling.into_stop_sequences()?,
+                tool_choice,
                 tools: validated_tools,
             },
             stream: stream.unwrap_or(false),
@@ -131,6 +173,7 @@ impl OpenAIResponsesRequestParams {
 mod tests {
     use paddler_messaging::grammar_constraint::GrammarConstraint;
     use paddler_messaging::request_params::continue_from_conversation_history_params::tool::Tool;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
     use serde_json::json;
 
     use super::OpenAIResponsesRequestParams;
@@ -263,8 +306,7 @@ mod tests {
             "store": true,
             "previous_response_id": "resp_prev",
             "conversation": "conv_1",
-            "temperature": 0.5,
-            "tool_choice": "required"
+            "temperature": 0.5
         }));
 
         assert_eq!(
@@ -272,4 +314,98 @@ mod tests {
             1
         );
     }
+
+    #[test]
+    fn a_forced_tool_choice_reaches_the_agent_with_thinking_off() {
+        let prepared = prepared_from(json!({
+            "model": "test",
+            "input": "hi",
+            "tools": [ { "type": "function", "name": "get_weather" } ],
+            "tool_choice": { "type": "function", "name": "get_weather" }
+        }));
+
+        assert_eq!(
+            prepared.paddler_params.tool_choice,
+            ToolChoice::Function {
+                name: "get_weather".to_owned()
+            }
+        );
+        assert!(!prepared.paddler_params.enable_thinking);
+    }
+
+    #[test]
+    fn required_tool_choice_without_tools_is_rejected() {
+        let params: OpenAIResponsesRequestParams = serde_json::from_value(json!({
+            "model": "test",
+            "input": "hi",
+            "tool_choice": "required"
+        }))
+        .unwrap();
+
+        assert!(params.into_prepared().is_err());
+    }
+
+    #[test]
+    fn requesting_logprobs_is_rejected() {
+        for request in [
+            json!({ "model": "test", "input": "hi", "top_logprobs": 2 }),
+            json!({ "model": "test", "input": "hi", "include": ["message.output_text.logprobs"] }),
+        ] {
+            let params: OpenAIResponsesRequestParams = serde_json::from_value(request).unwrap();
+
+            assert!(params.into_prepared().is_err());
+        }
+    }
+
+    #[test]
+    fn sampling_fields_become_sampling_overrides() {
+        let prepared = prepared_from(json!({
+            "model": "test",
+            "input": "hi",
+            "seed": 11,
+            "top_p": 0.5
+        }));
+
+        let sampling = prepared.paddler_params.sampling.unwrap();
+
+        assert_eq!(sampling.seed, Some(11));
+        assert_eq!(sampling.top_k, None);
+    }
+
+    #[test]
+    fn stop_becomes_stop_sequences() {
+        let prepared = prepared_from(json!({
+            "model": "test",
+            "input": "hi",
+            "stop": "###"
+        }));
+
+        assert_eq!(prepared.paddler_params.stop, vec!["###".to_owned()]);
+    }
+
+    #[test]
+    fn prompt_cache_key_becomes_the_session_key() {
+        let prepared = prepared_from(json!({
+            "model": "test",
+            "input": "hi",
+            "prompt_cache_key": "user-42"
+        }));
+
+        assert_eq!(
+            prepared.paddler_params.session_key,
+            Some("user-42".to_owned())
+        );
+    }
+
+    #[test]
+    fn empty_stop_sequences_are_rejected() {
+        let params: OpenAIResponsesRequestParams = serde_json::from_value(json!({
+            "model": "test",
+            "input": "hi",
+            "stop": [""]
+        }))
+        .unwrap();
+
+        assert!(params.into_prepared().is_err());
+    }
 }
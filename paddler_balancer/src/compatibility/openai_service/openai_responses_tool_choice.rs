@@ -0,0 +1,23 @@
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+use serde::Deserialize;
+
+use crate::compatibility::openai_service::openai_tool_choice_function::OpenAIToolChoiceFunction;
+use crate::compatibility::openai_service::openai_tool_choice_mode::OpenAIToolChoiceMode;
+
+/// Either a mode string or `{"type": "function", "name": ...}`.
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIResponsesToolChoice {
+    Mode(OpenAIToolChoiceMode),
+    Function(OpenAIToolChoiceFunction),
+}
+
+impl OpenAIResponsesToolChoice {
+    #[must_use]
+    pub fn into_tool_choice(self) -> ToolChoice {
+        match self {
+            Self::Mode(mode) => mode.to_tool_choice(),
+            Self::Function(OpenAIToolChoiceFunction { name }) => ToolChoice::Function { name },
+        }
+    }
+}
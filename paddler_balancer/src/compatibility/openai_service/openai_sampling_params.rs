@@ -0,0 +1,166 @@
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler_messaging::sampling_overrides::SamplingOverrides;
+use paddler_messaging::stop_sequences::validate_stop_sequences;
+use paddler_messaging::validates::Validates as _;
+use serde::Deserialize;
+
+use crate::compatibility::openai_service::openai_stop::OpenAIStop;
+
+/// Sampling fields shared by `/v1/chat/completions` and `/v1/responses`.
+#[derive(Default, Deserialize)]
+pub struct OpenAISamplingParams {
+    #[serde(default)]
+    pub frequency_penalty: Option<f32>,
+    #[serde(default)]
+    pub presence_penalty: Option<f32>,
+    #[serde(default)]
+    pub seed: Option<i64>,
+    #[serde(default)]
+    pub stop: Option<OpenAIStop>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+}
+
+impl OpenAISamplingParams {
+    pub fn to_sampling_overrides(&self) -> Result<Option<SamplingOverrides>> {
+        let sampling_overrides = SamplingOverrides {
+            penalty_frequency: self.frequency_penalty,
+            penalty_presence: self.presence_penalty,
+            seed: self
+                .seed
+                .map(|seed| {
+                    u32::try_from(seed)
+                        .map_err(|_| anyhow!("seed must be between 0 and {}, got {seed}", u32::MAX))
+                })
+                .transpose()?,
+            temperature: self.temperature,
+            top_p: self.top_p,
+            ..SamplingOverrides::default()
+        }
+        .validate()?;
+
+        if sampling_overrides == SamplingOverrides::default() {
+            return Ok(None);
+        }
+
+        Ok(Some(sampling_overrides))
+    }
+
+    pub fn into_stop_sequences(self) -> Result<Vec<String>> {
+        let stop_sequences = self
+            .stop
+            .map(OpenAIStop::into_sequences)
+            .unwrap_or_default();
+
+        validate_stop_sequences(&stop_sequences)?;
+
+        Ok(stop_sequences)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::sampling_overrides::SamplingOverrides;
+    use serde_json::json;
+
+    use super::OpenAISamplingParams;
+
+    fn sampling_params_from(value: serde_json::Value) -> OpenAISamplingParams {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn absent_fields_produce_no_overrides() {
+        let sampling_overrides = sampling_params_from(json!({}))
+            .to_sampling_overrides()
+            .unwrap();
+
+        assert!(sampling_overrides.is_none());
+    }
+
+    #[test]
+    fn openai_fields_map_onto_sampling_overrides() {
+        let sampling_overrides = sampling_params_from(json!({
+            "frequency_penalty": 0.5,
+            "presence_penalty": -0.5,
+            "seed": 7,
+            "temperature": 0.2,
+            "top_p": 0.9
+        }))
+        .to_sampling_overrides()
+        .unwrap()
+        .unwrap();
+
+        assert_eq!(
+            sampling_overrides,
+            SamplingOverrides {
+                penalty_frequency: Some(0.5),
+                penalty_presence: Some(-0.5),
+                seed: Some(7),
+                temperature: Some(0.2),
+                top_p: Some(0.9),
+                ..SamplingOverrides::default()
+            }
+        );
+    }
+
+    #[test]
+    fn out_of_range_temperature_is_rejected() {
+        assert!(
+            sampling_params_from(json!({ "temperature": 2.5 }))
+                .to_sampling_overrides()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn negative_seed_is_rejected() {
+        assert!(
+            sampling_params_from(json!({ "seed": -1 }))
+                .to_sampling_overrides()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn a_single_stop_string_becomes_one_stop_sequence() {
+        assert_eq!(
+            sampling_params_from(json!({ "stop": "\n###" }))
+                .into_stop_sequences()
+                .unwrap(),
+            vec!["\n###".to_owned()]
+        );
+    }
+
+    #[test]
+    fn a_stop_list_is_kept_in_order() {
+        assert_eq!(
+            sampling_params_from(json!({ "stop": ["END", "\n\n"] }))
+                .into_stop_sequences()
+                .unwrap(),
+            vec!["END".to_owned(), "\n\n".to_owned()]
+        );
+    }
+
+    #[test]
+    fn an_empty_stop_string_is_rejected() {
+        assert!(
+            sampling_params_from(json!({ "stop": "" }))
+                .into_stop_sequences()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn stop_sequences_do_not_produce_sampling_overrides() {
+        assert!(
+            sampling_params_from(json!({ "stop": ["\n###"] }))
+                .to_sampling_overrides()
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,18 @@
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum OpenAIStop {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl OpenAIStop {
+    #[must_use]
+    pub fn into_sequences(self) -> Vec<String> {
+        match self {
+            Self::Single(sequence) => vec![sequence],
+            Self::Multiple(sequences) => sequences,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use std::mem::take;
 use std::sync::Arc;
 
 use anyhow::Context as _;
@@ -12,11 +13,14 @@ use paddler_messaging::inference_client::message::Message as OutgoingMessage;
 use paddler_messaging::inference_client::response::Response as OutgoingResponse;
 use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
 use parking_lot::Mutex;
+use serde_json::Value;
 use serde_json::json;
 
 use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
 use crate::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
 use crate::compatibility::openai_service::arguments_to_tool_call_string::arguments_to_tool_call_string;
+use crate::compatibility::openai_service::openai_finish_reason::openai_finish_reason;
+use crate::compatibility::openai_service::openai_logprobs_json::openai_logprobs_json;
 use crate::compatibility::openai_service::openai_streaming_state::OpenAIStreamingState;
 use crate::compatibility::openai_service::openai_usage_json::openai_usage_json;
 use crate::compatibility::openai_service::try_universal_error_chunk::try_universal_error_chunk;
@@ -31,7 +35,7 @@ pub struct OpenAIStreamingResponseTransformer {
 }
 
 impl OpenAIStreamingResponseTransformer {
-    fn content_chunk(&self, request_id: &str, text: &str) -> Result<String> {
+    fn content_chunk(&self, request_id: &str, text: &str, logprobs: &Value) -> Result<String> {
         serde_json::to_string(&json!({
             "id": request_id,
             "object": "chat.completion.chunk",
@@ -45,7 +49,7 @@ impl OpenAIStreamingResponseTransformer {
                         "role": "assistant",
                         "content": text,
                     },
-                    "logprobs": null,
+                    "logprobs": logprobs,
                     "finish_reason": null
                 }
             ]
@@ -131,7 +135,9 @@ impl OpenAIStreamingResponseTransformer {
     }
 
     fn handle_content(&self, request_id: &str, text: &str) -> Result<Vec<TransformResult>> {
-        self.content_chunk(request_id, text)
+        let pending_logprobs = take(&mut self.state.lock().pending_logprobs);
+
+        self.content_chunk(request_id, text, &openai_logprobs_json(&pending_logprobs))
             .map(|chunk| vec![TransformResult::Chunk(chunk)])
     }
 
@@ -156,7 +162,7 @@ impl OpenAIStreamingResponseTransformer {
         summary: &GenerationSummary,
     ) -> Result<Vec<TransformResult>> {
         let saw_tool_call = self.state.lock().saw_tool_call;
-        let finish_reason = if saw_tool_call { "tool_calls" } else { "stop" };
+        let finish_reason = openai_finish_reason(summary.stop_reason, saw_tool_call);
 
         self.finish_chunk(request_id, finish_reason)
             .and_then(|finish_chunk| {
@@ -198,7 +204,18 @@ impl TransformsOutgoingMessage for OpenAIStreamingResponseTransformer {
                         | GeneratedTokenResult::ToolCallToken(_),
                     ),
                 ..
-            }) => Ok(vec![]),
+            }) => {
+                self.state.lock().pending_logprobs.clear();
+                Ok(vec![])
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(GeneratedTokenResult::TokenLogprob(token_logprob)),
+                ..
+            }) => {
+                self.state.lock().pending_logprobs.push(token_logprob);
+                Ok(vec![])
+            }
             OutgoingMessage::Response(ResponseEnvelope {
                 request_id,
                 response:
@@ -232,6 +249,9 @@ mod tests {
     use paddler_messaging::jsonrpc::error::Error as JsonRpcError;
     use paddler_messaging::jsonrpc::error_envelope::ErrorEnvelope;
     use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+    use paddler_messaging::stop_reason::StopReason;
+    use paddler_messaging::token_logprob::TokenLogprob;
+    use paddler_messaging::top_logprob::TopLogprob;
     use parking_lot::Mutex;
     use serde_json::json;
 
@@ -277,6 +297,7 @@ mod tests {
         reasoning_tokens: u64,
     ) -> GenerationSummary {
         GenerationSummary {
+            stop_reason: StopReason::EndOfGeneration,
             usage: TokenUsage {
                 prompt_tokens,
                 content_tokens,
@@ -390,6 +411,46 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn streaming_content_chunk_carries_preceding_logprob() -> Result<()> {
+        let transformer = streaming_transformer(false);
+
+        let logprob_chunks = transformer
+            .transform(token_message(GeneratedTokenResult::TokenLogprob(
+                TokenLogprob {
+                    logprob: -0.5,
+                    token: "hello".to_owned(),
+                    top_logprobs: vec![TopLogprob {
+                        logprob: -1.5,
+                        token: "hi".to_owned(),
+                    }],
+                },
+            )))
+            .await?;
+
+        assert_eq!(logprob_chunks.len(), 0);
+
+        let chunks = transformer
+            .transform(token_message(GeneratedTokenResult::ContentToken(
+                "hello".to_owned(),
+            )))
+            .await?;
+
+        assert_eq!(chunks.len(), 1);
+        assert_chunk_contains(&chunks[0], "\"logprob\":-0.5")?;
+        assert_chunk_contains(&chunks[0], "\"token\":\"hi\"")?;
+
+        let next_chunks = transformer
+            .transform(token_message(GeneratedTokenResult::ContentToken(
+                "!".to_owned(),
+            )))
+            .await?;
+
+        assert_chunk_contains(&next_chunks[0], "\"logprobs\":null")?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn streaming_reasoning_token_is_dropped() -> Result<()> {
         let transformer = streaming_transformer(false);
@@ -497,6 +558,24 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn streaming_done_at_max_tokens_uses_length_finish_reason() -> Result<()> {
+        let transformer = streaming_transformer(false);
+        let summary = GenerationSummary {
+            stop_reason: StopReason::MaxTokens,
+            ..summary_with_counts(2, 16, 0)
+        };
+
+        let chunks = transformer
+            .transform(token_message(GeneratedTokenResult::Done(summary)))
+            .await?;
+
+        assert_eq!(chunks.len(), 1);
+        assert_chunk_contains(&chunks[0], "\"finish_reason\":\"length\"")?;
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn streaming_done_with_include_usage_emits_finish_then_usage_chunk() -> Result<()> {
         let transformer = streaming_transformer(true);
@@ -1,4 +1,8 @@
+use paddler_messaging::token_logprob::TokenLogprob;
+
 #[derive(Default)]
 pub struct OpenAIStreamingState {
+    /// Log-probabilities received ahead of the content chunk they belong to.
+    pub pending_logprobs: Vec<TokenLogprob>,
     pub saw_tool_call: bool,
 }
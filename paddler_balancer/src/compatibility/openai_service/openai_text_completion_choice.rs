@@ -0,0 +1,33 @@
+use paddler_messaging::stop_reason::StopReason;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::compatibility::openai_service::openai_finish_reason::openai_finish_reason;
+
+#[must_use]
+pub fn openai_text_completion_choice(text: &str, stop_reason: Option<StopReason>) -> Value {
+    json!({
+        "text": text,
+        "index": 0,
+        "logprobs": null,
+        "finish_reason": stop_reason.map(|stop_reason| openai_finish_reason(stop_reason, false)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn running_out_of_tokens_finishes_with_length() {
+        assert_eq!(
+            openai_text_completion_choice("", Some(StopReason::MaxTokens))["finish_reason"],
+            "length"
+        );
+        assert_eq!(
+            openai_text_completion_choice("", Some(StopReason::StopSequence))["finish_reason"],
+            "stop"
+        );
+        assert!(openai_text_completion_choice("def", None)["finish_reason"].is_null());
+    }
+}
@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::generation_summary::GenerationSummary;
+use paddler_messaging::inference_client::message::Message as OutgoingMessage;
+use paddler_messaging::inference_client::response::Response as OutgoingResponse;
+use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+use parking_lot::Mutex;
+use serde_json::json;
+
+use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::compatibility::openai_service::openai_text_completion_choice::openai_text_completion_choice;
+use crate::compatibility::openai_service::openai_usage_json::openai_usage_json;
+use crate::compatibility::openai_service::try_universal_error_chunk::try_universal_error_chunk;
+
+#[derive(Clone)]
+pub struct OpenAITextCompletionNonStreamingResponseTransformer {
+    pub created: u64,
+    pub model: String,
+    /// Completion text so far; starts out as the prompt when it is echoed.
+    pub text: Arc<Mutex<String>>,
+}
+
+impl OpenAITextCompletionNonStreamingResponseTransformer {
+    fn build_done_chunk(&self, request_id: &str, summary: &GenerationSummary) -> Result<String> {
+        serde_json::to_string(&json!({
+            "id": request_id,
+            "object": "text_completion",
+            "created": self.created,
+            "model": self.model,
+            "choices": [
+                openai_text_completion_choice(&self.text.lock(), Some(summary.stop_reason)),
+            ],
+            "usage": openai_usage_json(&summary.usage),
+        }))
+        .context("serializing non-streaming text completion")
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAITextCompletionNonStreamingResponseTransformer {
+    type Output = TransformResult;
+
+    async fn transform(&self, message: OutgoingMessage) -> Result<Vec<TransformResult>> {
+        if let Some(error_chunk) = try_universal_error_chunk(&message) {
+            return Ok(vec![error_chunk]);
+        }
+
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(
+                        GeneratedTokenResult::ContentToken(text)
+                        | GeneratedTokenResult::UndeterminableToken(text),
+                    ),
+                ..
+            }) => {
+                self.text.lock().push_str(&text);
+                Ok(vec![])
+            }
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(
+                        GeneratedTokenResult::ReasoningToken(_)
+                        | GeneratedTokenResult::TokenLogprob(_)
+                        | GeneratedTokenResult::ToolCallToken(_),
+                    ),
+                ..
+            }) => Ok(vec![]),
+            OutgoingMessage::Response(ResponseEnvelope {
+                request_id,
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(summary)),
+                ..
+            }) => Ok(vec![TransformResult::Chunk(
+                self.build_done_chunk(&request_id, &summary)?,
+            )]),
+            other => Err(anyhow!(
+                "OpenAITextCompletionNonStreamingResponseTransformer received an outgoing message it does not know how to handle: {other:?}"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::stop_reason::StopReason;
+    use serde_json::Value;
+
+    use super::*;
+
+    fn token_message(token_result: GeneratedTokenResult) -> OutgoingMessage {
+        OutgoingMessage::Response(ResponseEnvelope {
+            generated_by: None,
+            request_id: "test-request".to_owned(),
+            response: OutgoingResponse::GeneratedToken(token_result),
+        })
+    }
+
+    #[tokio::test]
+    async fn done_carries_the_echoed_prompt_and_the_completion() -> Result<()> {
+        let transformer = OpenAITextCompletionNonStreamingResponseTransformer {
+            created: 0,
+            model: "test-model".to_owned(),
+            text: Arc::new(Mutex::new("1, 2, ".to_owned())),
+        };
+
+        transformer
+            .transform(token_message(GeneratedTokenResult::ContentToken(
+                "3".to_owned(),
+            )))
+            .await?;
+
+        let chunks = transformer
+            .transform(token_message(GeneratedTokenResult::Done(
+                GenerationSummary {
+                    stop_reason: StopReason::StopSequence,
+                    ..GenerationSummary::default()
+                },
+            )))
+            .await?;
+        let TransformResult::Chunk(content) = &chunks[0] else {
+            panic!("expected a chunk variant");
+        };
+        let completion: Value = serde_json::from_str(content)?;
+
+        assert_eq!(completion["object"], "text_completion");
+        assert_eq!(completion["choices"][0]["text"], "1, 2, 3");
+        assert_eq!(completion["choices"][0]["finish_reason"], "stop");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,54 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+pub enum OpenAITextCompletionPrompt {
+    Single(String),
+    Batch(Vec<String>),
+}
+
+impl OpenAITextCompletionPrompt {
+    /// Batched prompts would need one choice per prompt; only a batch of one is accepted.
+    pub fn into_prompt(self) -> Result<String> {
+        match self {
+            Self::Single(prompt) => Ok(prompt),
+            Self::Batch(prompts) => match <[String; 1]>::try_from(prompts) {
+                Ok([prompt]) => Ok(prompt),
+                Err(prompts) => bail!(
+                    "prompt must be a single string, got a batch of {}",
+                    prompts.len()
+                ),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::OpenAITextCompletionPrompt;
+
+    fn prompt_from(value: serde_json::Value) -> OpenAITextCompletionPrompt {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn a_string_and_a_batch_of_one_are_the_same_prompt() {
+        assert_eq!(
+            prompt_from(json!("def add(")).into_prompt().unwrap(),
+            "def add("
+        );
+        assert_eq!(
+            prompt_from(json!(["def add("])).into_prompt().unwrap(),
+            "def add("
+        );
+    }
+
+    #[test]
+    fn a_batch_of_several_prompts_is_rejected() {
+        assert!(prompt_from(json!(["a", "b"])).into_prompt().is_err());
+    }
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+
+use crate::compatibility::openai_service::openai_sampling_params::OpenAISamplingParams;
+use crate::compatibility::openai_service::openai_text_completion_prompt::OpenAITextCompletionPrompt;
+use crate::compatibility::openai_service::stream_options::StreamOptions;
+
+/// Default of the legacy completions API, which code-completion clients rely on.
+pub const DEFAULT_TEXT_COMPLETION_MAX_TOKENS: i32 = 16;
+
+#[derive(Deserialize)]
+pub struct OpenAITextCompletionRequestParams {
+    /// Prepends the prompt to the completion text.
+    #[serde(default)]
+    pub echo: bool,
+    /// Log-probabilities are not reported by this endpoint, so asking for them is rejected.
+    #[serde(default)]
+    pub logprobs: Option<u32>,
+    #[serde(default)]
+    pub max_tokens: Option<i32>,
+    /// Names the model pool to dispatch to; unknown names are rejected once pools are configured.
+    pub model: String,
+    pub prompt: OpenAITextCompletionPrompt,
+    #[serde(flatten)]
+    pub sampling: OpenAISamplingParams,
+    pub stream: Option<bool>,
+    pub stream_options: Option<StreamOptions>,
+    /// Fill-in-the-middle is not supported, so a non-empty suffix is rejected.
+    #[serde(default)]
+    pub suffix: Option<String>,
+}
@@ -0,0 +1,208 @@
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::anyhow;
+use async_trait::async_trait;
+use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::generation_summary::GenerationSummary;
+use paddler_messaging::inference_client::message::Message as OutgoingMessage;
+use paddler_messaging::inference_client::response::Response as OutgoingResponse;
+use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+use parking_lot::Mutex;
+use serde_json::Value;
+use serde_json::json;
+
+use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
+use crate::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::compatibility::openai_service::openai_text_completion_choice::openai_text_completion_choice;
+use crate::compatibility::openai_service::openai_usage_json::openai_usage_json;
+use crate::compatibility::openai_service::try_universal_error_chunk::try_universal_error_chunk;
+
+#[derive(Clone)]
+pub struct OpenAITextCompletionStreamingResponseTransformer {
+    pub created: u64,
+    pub include_usage: bool,
+    pub model: String,
+    /// The echoed prompt, until it is sent ahead of the first completion text.
+    pub pending_echo: Arc<Mutex<Option<String>>>,
+}
+
+impl OpenAITextCompletionStreamingResponseTransformer {
+    fn chunk(&self, request_id: &str, choices: &[Value]) -> Result<String> {
+        serde_json::to_string(&json!({
+            "id": request_id,
+            "object": "text_completion",
+            "created": self.created,
+            "model": self.model,
+            "choices": choices,
+        }))
+        .context("serializing text completion chunk")
+    }
+
+    fn handle_content(&self, request_id: &str, text: &str) -> Result<Vec<TransformResult>> {
+        let pending_echo = self.pending_echo.lock().take();
+        let text = pending_echo.map_or_else(|| text.to_owned(), |prompt| prompt + text);
+
+        self.chunk(request_id, &[openai_text_completion_choice(&text, None)])
+            .map(|chunk| vec![TransformResult::Chunk(chunk)])
+    }
+
+    fn handle_done(
+        &self,
+        request_id: &str,
+        summary: &GenerationSummary,
+    ) -> Result<Vec<TransformResult>> {
+        let mut chunks = Vec::new();
+
+        let pending_echo = self.pending_echo.lock().take();
+
+        if let Some(prompt) = pending_echo {
+            chunks.push(TransformResult::Chunk(self.chunk(
+                request_id,
+                &[openai_text_completion_choice(&prompt, None)],
+            )?));
+        }
+
+        chunks.push(TransformResult::Chunk(self.chunk(
+            request_id,
+            &[openai_text_completion_choice("", Some(summary.stop_reason))],
+        )?));
+
+        if self.include_usage {
+            chunks.push(TransformResult::Chunk(
+                serde_json::to_string(&json!({
+                    "id": request_id,
+                    "object": "text_completion",
+                    "created": self.created,
+                    "model": self.model,
+                    "choices": [],
+                    "usage": openai_usage_json(&summary.usage),
+                }))
+                .context("serializing usage chunk")?,
+            ));
+        }
+
+        Ok(chunks)
+    }
+}
+
+#[async_trait]
+impl TransformsOutgoingMessage for OpenAITextCompletionStreamingResponseTransformer {
+    type Output = TransformResult;
+
+    async fn transform(&self, message: OutgoingMessage) -> Result<Vec<TransformResult>> {
+        if let Some(error_chunk) = try_universal_error_chunk(&message) {
+            return Ok(vec![error_chunk]);
+        }
+
+        match message {
+            OutgoingMessage::Response(ResponseEnvelope {
+                request_id,
+                response:
+                    OutgoingResponse::GeneratedToken(
+                        GeneratedTokenResult::ContentToken(text)
+                        | GeneratedTokenResult::UndeterminableToken(text),
+                    ),
+                ..
+            }) => self.handle_content(&request_id, &text),
+            OutgoingMessage::Response(ResponseEnvelope {
+                response:
+                    OutgoingResponse::GeneratedToken(
+                        GeneratedTokenResult::ReasoningToken(_)
+                        | GeneratedTokenResult::TokenLogprob(_)
+                        | GeneratedTokenResult::ToolCallToken(_),
+                    ),
+                ..
+            }) => Ok(vec![]),
+            OutgoingMessage::Response(ResponseEnvelope {
+                request_id,
+                response: OutgoingResponse::GeneratedToken(GeneratedTokenResult::Done(summary)),
+                ..
+            }) => self.handle_done(&request_id, &summary),
+            other => Err(anyhow!(
+                "OpenAITextCompletionStreamingResponseTransformer received an outgoing message it does not know how to handle: {other:?}"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use llama_cpp_bindings_types::TokenUsage;
+    use paddler_messaging::stop_reason::StopReason;
+
+    use super::*;
+
+    fn token_message(token_result: GeneratedTokenResult) -> OutgoingMessage {
+        OutgoingMessage::Response(ResponseEnvelope {
+            generated_by: None,
+            request_id: "test-request".to_owned(),
+            response: OutgoingResponse::GeneratedToken(token_result),
+        })
+    }
+
+    fn chunk_json(result: &TransformResult) -> Value {
+        let TransformResult::Chunk(content) = result else {
+            panic!("expected a chunk variant");
+        };
+
+        serde_json::from_str(content).unwrap()
+    }
+
+    fn transformer(echo: Option<&str>) -> OpenAITextCompletionStreamingResponseTransformer {
+        OpenAITextCompletionStreamingResponseTransformer {
+            created: 0,
+            include_usage: true,
+            model: "test-model".to_owned(),
+            pending_echo: Arc::new(Mutex::new(echo.map(str::to_owned))),
+        }
+    }
+
+    #[tokio::test]
+    async fn the_echoed_prompt_leads_the_first_text_chunk() -> Result<()> {
+        let transformer = transformer(Some("def add("));
+
+        let first = transformer
+            .transform(token_message(GeneratedTokenResult::ContentToken(
+                "a".to_owned(),
+            )))
+            .await?;
+        let second = transformer
+            .transform(token_message(GeneratedTokenResult::ContentToken(
+                ", b".to_owned(),
+            )))
+            .await?;
+
+        assert_eq!(chunk_json(&first[0])["object"], "text_completion");
+        assert_eq!(chunk_json(&first[0])["choices"][0]["text"], "def add(a");
+        assert_eq!(chunk_json(&second[0])["choices"][0]["text"], ", b");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn done_emits_the_finish_reason_and_usage() -> Result<()> {
+        let chunks = transformer(None)
+            .transform(token_message(GeneratedTokenResult::Done(
+                GenerationSummary {
+                    stop_reason: StopReason::MaxTokens,
+                    usage: TokenUsage {
+                        content_tokens: 16,
+                        prompt_tokens: 4,
+                        ..TokenUsage::default()
+                    },
+                },
+            )))
+            .await?;
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(
+            chunk_json(&chunks[0])["choices"][0]["finish_reason"],
+            "length"
+        );
+        assert_eq!(chunk_json(&chunks[1])["usage"]["total_tokens"], 20);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,21 @@
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+use serde::Deserialize;
+
+#[derive(Clone, Copy, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpenAIToolChoiceMode {
+    Auto,
+    None,
+    Required,
+}
+
+impl OpenAIToolChoiceMode {
+    #[must_use]
+    pub const fn to_tool_choice(self) -> ToolChoice {
+        match self {
+            Self::Auto => ToolChoice::Auto,
+            Self::None => ToolChoice::None,
+            Self::Required => ToolChoice::Required,
+        }
+    }
+}
@@ -92,7 +92,9 @@ impl TransformsOutgoingMessage for ResponsesNonStreamingResponseTransformer {
                     self.state.lock().reasoning.push_str(&text);
                     Ok(vec![])
                 }
-                GeneratedTokenResult::ToolCallToken(_) => Ok(vec![]),
+                GeneratedTokenResult::TokenLogprob(_) | GeneratedTokenResult::ToolCallToken(_) => {
+                    Ok(vec![])
+                }
                 GeneratedTokenResult::ToolCallParsed(parsed_calls) => {
                     self.state.lock().tool_calls.extend(parsed_calls);
                     Ok(vec![])
@@ -123,6 +125,8 @@ mod tests {
     use paddler_messaging::inference_client::message::Message as OutgoingMessage;
     use paddler_messaging::inference_client::response::Response as OutgoingResponse;
     use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+    use paddler_messaging::stop_reason::StopReason;
     use paddler_openai_response_format_validator::openai_validator::OpenAIValidator;
     use parking_lot::Mutex;
     use serde_json::json;
@@ -150,6 +154,7 @@ mod tests {
         reasoning_tokens: u64,
     ) -> GenerationSummary {
         GenerationSummary {
+            stop_reason: StopReason::EndOfGeneration,
             usage: TokenUsage {
                 prompt_tokens,
                 content_tokens,
@@ -175,6 +180,7 @@ mod tests {
             created_at: 0,
             model: "test-model".to_owned(),
             instructions: None,
+            tool_choice: ToolChoice::Auto,
         }
     }
 
@@ -1,4 +1,5 @@
 use llama_cpp_bindings_types::TokenUsage;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use serde_json::Value;
 use serde_json::json;
 
@@ -14,12 +15,22 @@ fn responses_usage_json(usage: &TokenUsage) -> Value {
     })
 }
 
+fn responses_tool_choice_json(tool_choice: &ToolChoice) -> Value {
+    match tool_choice {
+        ToolChoice::Auto => json!("auto"),
+        ToolChoice::Function { name } => json!({ "type": "function", "name": name }),
+        ToolChoice::None => json!("none"),
+        ToolChoice::Required => json!("required"),
+    }
+}
+
 #[derive(Clone)]
 pub struct ResponsesResponseBuilder {
     pub id: String,
     pub created_at: u64,
     pub model: String,
     pub instructions: Option<String>,
+    pub tool_choice: ToolChoice,
 }
 
 impl ResponsesResponseBuilder {
@@ -42,7 +53,7 @@ impl ResponsesResponseBuilder {
             "output": output,
             "parallel_tool_calls": true,
             "metadata": {},
-            "tool_choice": "auto",
+            "tool_choice": responses_tool_choice_json(&self.tool_choice),
             "temperature": 1,
             "top_p": 1,
             "text": { "format": { "type": "text" } }
@@ -78,6 +89,7 @@ impl ResponsesResponseBuilder {
 #[cfg(test)]
 mod tests {
     use llama_cpp_bindings_types::TokenUsage;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
     use serde_json::json;
 
     use super::ResponsesResponseBuilder;
@@ -89,6 +101,7 @@ mod tests {
             created_at: 1_234,
             model: "test-model".to_owned(),
             instructions: None,
+            tool_choice: ToolChoice::Auto,
         }
     }
 
@@ -102,6 +115,23 @@ mod tests {
         assert!(response.get("usage").is_none());
     }
 
+    #[test]
+    fn echoes_the_requested_tool_choice() {
+        let response = ResponsesResponseBuilder {
+            tool_choice: ToolChoice::Function {
+                name: "get_weather".to_owned(),
+            },
+            ..builder()
+        }
+        .in_progress();
+
+        assert_eq!(
+            response["tool_choice"],
+            json!({ "type": "function", "name": "get_weather" })
+        );
+        assert_eq!(builder().in_progress()["tool_choice"], "auto");
+    }
+
     #[test]
     fn failed_carries_the_error_message() {
         let response = builder().failed(&OpenAIError {
@@ -99,7 +99,7 @@ impl TransformsOutgoingMessage for ResponsesStreamingResponseTransformer {
                     self.ensure_preamble(&mut state, &mut events);
                     state.handle_reasoning(&mut events, &text);
                 }
-                GeneratedTokenResult::ToolCallToken(_) => {}
+                GeneratedTokenResult::TokenLogprob(_) | GeneratedTokenResult::ToolCallToken(_) => {}
                 GeneratedTokenResult::ToolCallParsed(parsed_calls) => {
                     self.ensure_preamble(&mut state, &mut events);
                     state.handle_tool_calls(&mut events, &parsed_calls)?;
@@ -137,6 +137,8 @@ mod tests {
     use paddler_messaging::inference_client::message::Message as OutgoingMessage;
     use paddler_messaging::inference_client::response::Response as OutgoingResponse;
     use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+    use paddler_messaging::stop_reason::StopReason;
     use paddler_openai_response_format_validator::openai_validator::OpenAIValidator;
     use parking_lot::Mutex;
     use serde_json::json;
@@ -164,6 +166,7 @@ mod tests {
         reasoning_tokens: u64,
     ) -> GenerationSummary {
         GenerationSummary {
+            stop_reason: StopReason::EndOfGeneration,
             usage: TokenUsage {
                 prompt_tokens,
                 content_tokens,
@@ -189,6 +192,7 @@ mod tests {
             created_at: 0,
             model: "test-model".to_owned(),
             instructions: None,
+            tool_choice: ToolChoice::Auto,
         }
     }
 
@@ -18,6 +18,8 @@ use crate::compatibility::openai_service::responses_stream_event::ResponsesStrea
 use crate::handles_agent_streaming_response::HandlesAgentStreamingResponse;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::manages_senders::ManagesSenders;
+use crate::provides_affinity_key::ProvidesAffinityKey;
+use crate::request_admission::RequestAdmission;
 use crate::unbounded_stream_from_agent::unbounded_stream_from_agent;
 
 fn event_to_sse_data(event: &ResponsesStreamEvent) -> sse::Data {
@@ -27,12 +29,14 @@ fn event_to_sse_data(event: &ResponsesStreamEvent) -> sse::Data {
 pub fn sse_response_from_agent<TParams, TTransformsOutgoingMessage>(
     buffered_request_manager: Arc<BufferedRequestManager>,
     inference_service_configuration: InferenceServiceConfiguration,
+    model_pool: Option<String>,
     params: TParams,
+    request_admission: RequestAdmission,
     transformer: TTransformsOutgoingMessage,
     shutdown: CancellationToken,
 ) -> HttpResponse
 where
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send + 'static,
+    TParams: Clone + Debug + Into<AgentJsonRpcRequest> + ProvidesAffinityKey + Send + 'static,
     AgentController: HandlesAgentStreamingResponse<TParams>,
     <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
     TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage<Output = ResponsesStreamEvent> + Send + Sync + 'static,
@@ -40,7 +44,9 @@ where
     let event_stream = unbounded_stream_from_agent(
         buffered_request_manager,
         inference_service_configuration,
+        model_pool,
         params,
+        request_admission,
         transformer,
         shutdown,
     )
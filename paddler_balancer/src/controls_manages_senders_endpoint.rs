@@ -53,28 +53,18 @@ pub trait ControlsManagesSendersEndpoint {
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
     use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
-    use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
 
     use actix_web::http::StatusCode;
     use async_trait::async_trait;
     use tokio::sync::mpsc;
-    use tokio_util::sync::CancellationToken;
 
     use super::ControlsManagesSendersEndpoint;
     use crate::agent_controller::AgentController;
     use crate::agent_controller_pool::AgentControllerPool;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::manages_senders_controller::ManagesSendersController;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
-    use paddler_messaging::atomic_value::AtomicValue;
 
     fn registered_agent_id(pool: &AgentControllerPool) -> String {
         let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
@@ -83,34 +73,13 @@ mod tests {
         pool.register_agent_controller(
             agent_id.clone(),
             Arc::new(AgentController {
-                agent_message_tx,
-                chat_template_override_sender_collection: Arc::new(
-                    ChatTemplateOverrideSenderCollection::default(),
-                ),
-                connection_close: CancellationToken::new(),
-                desired_slots_total: AtomicValue::<AtomicI32>::new(0),
-                download_current: AtomicValue::<AtomicU64>::new(0),
-                download_filename: RwLock::new(None),
-                download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-                download_total: AtomicValue::<AtomicU64>::new(0),
-                embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
                 generate_tokens_sender_collection: Arc::new(
                     GenerateTokensSenderCollection::default(),
                 ),
-                id: agent_id.clone(),
-                issues: RwLock::new(BTreeSet::new()),
                 model_metadata_sender_collection: Arc::new(
                     ModelMetadataSenderCollection::default(),
                 ),
-                model_path: RwLock::new(None),
-                name: None,
-                newest_update_version: AtomicValue::<AtomicI32>::new(0),
-                slots_processing: AtomicValue::<AtomicI32>::new(0),
-                slots_total: AtomicValue::<AtomicI32>::new(0),
-                state_application_status_code: AtomicValue::<AtomicI32>::new(
-                    AgentStateApplicationStatus::Fresh as i32,
-                ),
-                uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+                ..AgentController::new_for_test(&agent_id, agent_message_tx)
             }),
         )
         .unwrap();
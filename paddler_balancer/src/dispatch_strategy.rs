@@ -0,0 +1,127 @@
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+
+use crate::provides_affinity_key::ProvidesAffinityKey;
+
+pub const DEFAULT_SESSION_AFFINITY_MAX_LOAD_SLACK: i32 = 2;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DispatchStrategy {
+    #[default]
+    LeastBusy,
+    /// Prefers the agent that most recently served the same session key or
+    /// prompt prefix, as long as it still has a free slot and is processing at most
+    /// `max_load_slack` more requests than the least busy agent.
+    SessionAffinity { max_load_slack: i32 },
+    /// Prefers the agent with the highest measured tokens per second for each request it
+    /// would be processing, so faster agents take proportionally more of the load.
+    ThroughputWeighted,
+}
+
+impl DispatchStrategy {
+    #[must_use]
+    pub const fn with_session_affinity_max_load_slack(self, max_load_slack: i32) -> Self {
+        match self {
+            Self::SessionAffinity { .. } => Self::SessionAffinity { max_load_slack },
+            Self::LeastBusy | Self::ThroughputWeighted => self,
+        }
+    }
+
+    pub fn affinity_key<TParams: ProvidesAffinityKey>(self, params: &TParams) -> Option<u64> {
+        match self {
+            Self::LeastBusy | Self::ThroughputWeighted => None,
+            Self::SessionAffinity { .. } => params.affinity_key(),
+        }
+    }
+}
+
+impl FromStr for DispatchStrategy {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "least-busy" => Ok(Self::LeastBusy),
+            "session-affinity" => Ok(Self::SessionAffinity {
+                max_load_slack: DEFAULT_SESSION_AFFINITY_MAX_LOAD_SLACK,
+            }),
+            "throughput-weighted" => Ok(Self::ThroughputWeighted),
+            other => Err(anyhow!(
+                "Unsupported dispatch strategy '{other}' (expected 'least-busy', 'session-affinity' or 'throughput-weighted')"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+
+    use super::*;
+
+    fn raw_prompt_params() -> ContinueFromRawPromptParams {
+        ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: None,
+            max_tokens: 10,
+            model: None,
+            raw_prompt: "hello".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn parses_every_strategy() {
+        assert_eq!(
+            DispatchStrategy::from_str("least-busy").unwrap(),
+            DispatchStrategy::LeastBusy
+        );
+        assert_eq!(
+            DispatchStrategy::from_str("session-affinity").unwrap(),
+            DispatchStrategy::SessionAffinity {
+                max_load_slack: DEFAULT_SESSION_AFFINITY_MAX_LOAD_SLACK
+            }
+        );
+        assert_eq!(
+            DispatchStrategy::from_str("throughput-weighted").unwrap(),
+            DispatchStrategy::ThroughputWeighted
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_strategy() {
+        assert!(DispatchStrategy::from_str("round-robin").is_err());
+    }
+
+    #[test]
+    fn least_busy_never_produces_an_affinity_key() {
+        assert_eq!(
+            DispatchStrategy::LeastBusy.affinity_key(&raw_prompt_params()),
+            None
+        );
+        assert!(
+            DispatchStrategy::SessionAffinity { max_load_slack: 0 }
+                .affinity_key(&raw_prompt_params())
+                .is_some()
+        );
+    }
+
+    #[test]
+    fn max_load_slack_only_applies_to_session_affinity() {
+        assert_eq!(
+            DispatchStrategy::SessionAffinity { max_load_slack: 2 }
+                .with_session_affinity_max_load_slack(5),
+            DispatchStrategy::SessionAffinity { max_load_slack: 5 }
+        );
+        assert_eq!(
+            DispatchStrategy::LeastBusy.with_session_affinity_max_load_slack(5),
+            DispatchStrategy::LeastBusy
+        );
+    }
+}
@@ -0,0 +1,135 @@
+use std::sync::Arc;
+
+use actix_web::rt;
+use futures_util::Stream;
+use futures_util::future::join_all;
+use log::error;
+use nanoid::nanoid;
+use paddler_messaging::request_params::generate_embedding_batch_params::GenerateEmbeddingBatchParams;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_util::sync::CancellationToken;
+
+use crate::buffered_request_manager::BufferedRequestManager;
+use crate::cancellation_token_stream_guard::CancellationTokenStreamGuard;
+use crate::chunk_forwarding_session_controller::ChunkForwardingSessionController;
+use crate::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
+use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::request_admission::RequestAdmission;
+use crate::request_from_agent::serve_request_from_agent;
+use crate::request_outcome::RequestOutcome;
+
+/// The batch only succeeds if every chunk did.
+fn batch_outcome(chunk_outcomes: impl IntoIterator<Item = RequestOutcome>) -> RequestOutcome {
+    chunk_outcomes
+        .into_iter()
+        .max_by_key(|chunk_outcome| match chunk_outcome {
+            RequestOutcome::Succeeded => 0,
+            RequestOutcome::Rejected => 1,
+            RequestOutcome::Cancelled => 2,
+            RequestOutcome::Failed => 3,
+        })
+        .unwrap_or(RequestOutcome::Succeeded)
+}
+
+/// Dispatches every chunk of an embedding batch as its own request, so the chunks spread across
+/// agents. The stream ends once all of them are answered. The batch counts as one finished
+/// request.
+pub fn embedding_batch_stream_from_agents<TTransformsOutgoingMessage>(
+    buffered_request_manager: Arc<BufferedRequestManager>,
+    inference_service_configuration: InferenceServiceConfiguration,
+    model_pool: Option<String>,
+    batches: Vec<GenerateEmbeddingBatchParams>,
+    request_admission: RequestAdmission,
+    transformer: TTransformsOutgoingMessage,
+    shutdown: CancellationToken,
+) -> impl Stream<Item = TTransformsOutgoingMessage::Output>
+where
+    TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage + Send + Sync + 'static,
+{
+    let connection_close = CancellationToken::new();
+    let endpoint = request_admission.endpoint.clone();
+    let received_at = Instant::now();
+    let request_metrics = buffered_request_manager
+        .observability
+        .request_metrics
+        .clone();
+    let (chunk_tx, chunk_rx) = mpsc::unbounded_channel();
+    let mut chunk_handles = Vec::with_capacity(batches.len());
+
+    for batch in batches {
+        let buffered_request_manager = buffered_request_manager.clone();
+        let chunk_tx = chunk_tx.clone();
+        let connection_close = connection_close.clone();
+        let inference_service_configuration = inference_service_configuration.clone();
+        let model_pool = model_pool.clone();
+        let request_admission = request_admission.clone();
+        let shutdown = shutdown.clone();
+        let transformer = transformer.clone();
+
+        chunk_handles.push(rt::spawn(async move {
+            let request_id: String = nanoid!();
+            let session_controller = ChunkForwardingSessionController::new(chunk_tx, transformer);
+
+            serve_request_from_agent(
+                buffered_request_manager,
+                connection_close,
+                inference_service_configuration,
+                model_pool,
+                batch,
+                request_admission,
+                request_id,
+                session_controller,
+                shutdown,
+            )
+            .await
+        }));
+    }
+
+    rt::spawn(async move {
+        let chunk_outcomes = join_all(chunk_handles)
+            .await
+            .into_iter()
+            .map(|chunk_outcome| {
+                chunk_outcome.unwrap_or_else(|err| {
+                    error!("Embedding batch chunk task failed: {err}");
+
+                    RequestOutcome::Failed
+                })
+            });
+
+        request_metrics.record_finished_request(
+            received_at.elapsed(),
+            &endpoint,
+            batch_outcome(chunk_outcomes),
+        );
+    });
+
+    CancellationTokenStreamGuard::new(connection_close, UnboundedReceiverStream::new(chunk_rx))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn batch_outcome_is_the_worst_chunk_outcome() {
+        assert_eq!(
+            batch_outcome([RequestOutcome::Succeeded, RequestOutcome::Succeeded]),
+            RequestOutcome::Succeeded
+        );
+        assert_eq!(
+            batch_outcome([RequestOutcome::Cancelled, RequestOutcome::Rejected]),
+            RequestOutcome::Cancelled
+        );
+        assert_eq!(
+            batch_outcome([
+                RequestOutcome::Failed,
+                RequestOutcome::Succeeded,
+                RequestOutcome::Cancelled
+            ]),
+            RequestOutcome::Failed
+        );
+    }
+}
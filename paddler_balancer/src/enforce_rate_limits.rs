@@ -0,0 +1,443 @@
+use std::sync::Arc;
+use std::time::Instant;
+
+use actix_web::Error;
+use actix_web::HttpMessage as _;
+use actix_web::HttpResponse;
+use actix_web::body::BoxBody;
+use actix_web::body::MessageBody;
+use actix_web::dev::ServiceRequest;
+use actix_web::dev::ServiceResponse;
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use actix_web::web::Data;
+use log::warn;
+
+use crate::caller_key::CallerKey;
+use crate::compatibility::openai_service::openai_error::OpenAIError;
+use crate::rate_limit_caller::RateLimitCaller;
+use crate::rate_limited_body::RateLimitedBody;
+use crate::rate_limiter::RateLimiter;
+use crate::request_observability::RequestObservability;
+
+/// Requests on this socket are admitted one by one as they arrive, not at the handshake.
+const INFERENCE_SOCKET_PATH: &str = "/api/v1/inference_socket";
+
+/// Runs after `require_api_key`, so an authenticated request carries its `CallerKey`. The
+/// identity header is only consulted when the operator configured one, as it is only
+/// trustworthy behind a proxy that sets it.
+fn caller_identity(request: &ServiceRequest, rate_limiter: &RateLimiter) -> String {
+    if let Some(caller_key) = request.extensions().get::<CallerKey>() {
+        return caller_key.as_str().to_owned();
+    }
+
+    rate_limiter
+        .configuration()
+        .identity_header
+        .as_ref()
+        .and_then(|identity_header| request.headers().get(identity_header))
+        .and_then(|identity| identity.to_str().ok())
+        .map(str::trim)
+        .filter(|identity| !identity.is_empty())
+        .map_or_else(
+            || {
+                request
+                    .peer_addr()
+                    .map(|peer_addr| peer_addr.ip().to_string())
+                    .unwrap_or_default()
+            },
+            |identity| format!("header:{identity}"),
+        )
+}
+
+/// Passes every request through when the app has no `RateLimiter` registered.
+pub async fn enforce_rate_limits(
+    request: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let received_at = Instant::now();
+    let Some(rate_limiter) = request.app_data::<Data<RateLimiter>>().cloned() else {
+        return Ok(next.call(request).await?.map_into_boxed_body());
+    };
+
+    if request.path() == "/health" {
+        return Ok(next.call(request).await?.map_into_boxed_body());
+    }
+
+    let caller = caller_identity(&request, &rate_limiter);
+
+    if request.path() == INFERENCE_SOCKET_PATH {
+        request
+            .extensions_mut()
+            .insert(RateLimitCaller::new(caller, rate_limiter.into_inner()));
+
+        return Ok(next.call(request).await?.map_into_boxed_body());
+    }
+
+    match rate_limiter.into_inner().admit(&caller) {
+        Ok(rate_limit_permit) => {
+            let rate_limit_permit = Arc::new(rate_limit_permit);
+
+            request.extensions_mut().insert(rate_limit_permit.clone());
+
+            Ok(next
+                .call(request)
+                .await?
+                .map_body(|_, body| RateLimitedBody::new(body.boxed(), rate_limit_permit))
+                .map_into_boxed_body())
+        }
+        Err(rejection) => {
+            warn!("Rejecting request from {caller:?}: {rejection}");
+
+            if let Some(request_observability) = request.app_data::<Data<RequestObservability>>() {
+                request_observability.record_rejected_request(
+                    request.request(),
+                    received_at.elapsed(),
+                    "rate_limited",
+                );
+            }
+
+            let response = HttpResponse::TooManyRequests()
+                .content_type("application/json")
+                .insert_header((
+                    header::RETRY_AFTER,
+                    rejection.retry_after().as_secs().max(1).to_string(),
+                ))
+                .body(
+                    OpenAIError {
+                        error_type: "rate_limit_error",
+                        message: rejection.to_string(),
+                    }
+                    .to_envelope()
+                    .to_string(),
+                );
+
+            Ok(request.into_response(response))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+
+    use actix_web::App;
+    use actix_web::HttpRequest;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::test;
+    use actix_web::web;
+    use serde_json::Value;
+
+    use super::*;
+    use crate::rate_limit_configuration::RateLimitConfiguration;
+    use crate::request_admission::RequestAdmission;
+    use crate::request_outcome::RequestOutcome;
+
+    fn rate_limiter() -> Data<RateLimiter> {
+        Data::new(RateLimiter::new(RateLimitConfiguration {
+            max_requests_per_minute: Some(1),
+            ..RateLimitConfiguration::default()
+        }))
+    }
+
+    fn rate_limiter_behind_a_proxy() -> Data<RateLimiter> {
+        Data::new(RateLimiter::new(RateLimitConfiguration {
+            identity_header: Some("X-Tenant".to_owned()),
+            max_requests_per_minute: Some(1),
+            ..RateLimitConfiguration::default()
+        }))
+    }
+
+    fn request_from(peer_ip: [u8; 4]) -> test::TestRequest {
+        test::TestRequest::post()
+            .uri("/v1/chat/completions")
+            .peer_addr(SocketAddr::from((peer_ip, 40000)))
+    }
+
+    #[actix_web::test]
+    async fn rejects_callers_over_their_limit_with_an_openai_error() {
+        let request_observability = RequestObservability::default();
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter())
+                .app_data(Data::new(request_observability.clone()))
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let first = test::call_service(&app, request_from([10, 0, 0, 1]).to_request()).await;
+
+        assert_eq!(first.status(), StatusCode::OK);
+
+        let other_caller = test::call_service(&app, request_from([10, 0, 0, 2]).to_request()).await;
+
+        assert_eq!(other_caller.status(), StatusCode::OK);
+
+        let rejected = test::call_service(&app, request_from([10, 0, 0, 1]).to_request()).await;
+
+        assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(rejected.headers().contains_key(header::RETRY_AFTER));
+
+        let body: Value = test::read_body_json(rejected).await;
+
+        assert_eq!(body["error"]["type"], "rate_limit_error");
+        assert_eq!(
+            request_observability
+                .request_metrics
+                .requests_by_endpoint_and_outcome(),
+            vec![(
+                "/v1/chat/completions".to_owned(),
+                RequestOutcome::Rejected,
+                1
+            )]
+        );
+    }
+
+    #[actix_web::test]
+    async fn identifies_authenticated_callers_by_their_api_key() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter())
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let request_with_key = |api_key: &str| {
+            let request = request_from([10, 0, 0, 1]).to_request();
+
+            request
+                .extensions_mut()
+                .insert(CallerKey::from_api_key(api_key));
+
+            request
+        };
+
+        let first = test::call_service(&app, request_with_key("sk-a")).await;
+
+        assert_eq!(first.status(), StatusCode::OK);
+
+        let same_address_other_key = test::call_service(&app, request_with_key("sk-b")).await;
+
+        assert_eq!(same_address_other_key.status(), StatusCode::OK);
+
+        let rejected = test::call_service(&app, request_with_key("sk-a")).await;
+
+        assert_eq!(rejected.status(), StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[actix_web::test]
+    async fn ignores_headers_that_claim_a_caller_identity() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter())
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        for (tenant, expected_status) in [
+            ("tenant-a", StatusCode::OK),
+            ("tenant-b", StatusCode::TOO_MANY_REQUESTS),
+        ] {
+            let response = test::call_service(
+                &app,
+                request_from([10, 0, 0, 1])
+                    .insert_header(("X-Tenant", tenant))
+                    .to_request(),
+            )
+            .await;
+
+            assert_eq!(response.status(), expected_status);
+        }
+    }
+
+    #[actix_web::test]
+    async fn identifies_callers_without_an_api_key_by_the_configured_header() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter_behind_a_proxy())
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        for (tenant, expected_status) in [
+            ("tenant-a", StatusCode::OK),
+            ("tenant-b", StatusCode::OK),
+            ("tenant-a", StatusCode::TOO_MANY_REQUESTS),
+        ] {
+            let response = test::call_service(
+                &app,
+                request_from([10, 0, 0, 1])
+                    .insert_header(("X-Tenant", tenant))
+                    .to_request(),
+            )
+            .await;
+
+            assert_eq!(response.status(), expected_status);
+        }
+    }
+
+    #[actix_web::test]
+    async fn prefers_the_api_key_over_the_configured_header() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter_behind_a_proxy())
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        for (api_key, expected_status) in [
+            ("sk-a", StatusCode::OK),
+            ("sk-b", StatusCode::OK),
+            ("sk-a", StatusCode::TOO_MANY_REQUESTS),
+        ] {
+            let request = request_from([10, 0, 0, 1])
+                .insert_header(("X-Tenant", "tenant-a"))
+                .to_request();
+
+            request
+                .extensions_mut()
+                .insert(CallerKey::from_api_key(api_key));
+
+            assert_eq!(
+                test::call_service(&app, request).await.status(),
+                expected_status
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn falls_back_to_the_address_when_the_configured_header_is_missing() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter_behind_a_proxy())
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        for (peer_ip, identity, expected_status) in [
+            ([10, 0, 0, 1], None, StatusCode::OK),
+            ([10, 0, 0, 1], Some("  "), StatusCode::TOO_MANY_REQUESTS),
+            ([10, 0, 0, 2], None, StatusCode::OK),
+            ([10, 0, 0, 2], Some("tenant-a"), StatusCode::OK),
+        ] {
+            let mut request = request_from(peer_ip);
+
+            if let Some(identity) = identity {
+                request = request.insert_header(("X-Tenant", identity));
+            }
+
+            assert_eq!(
+                test::call_service(&app, request.to_request())
+                    .await
+                    .status(),
+                expected_status
+            );
+        }
+    }
+
+    #[actix_web::test]
+    async fn exposes_the_permit_to_handlers() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter())
+                .route(
+                    "/v1/chat/completions",
+                    web::post().to(|request: HttpRequest| async move {
+                        RequestAdmission::from_request(&request)
+                            .rate_limit_permit
+                            .is_some()
+                            .to_string()
+                    }),
+                ),
+        )
+        .await;
+
+        let response = test::call_service(&app, request_from([10, 0, 0, 1]).to_request()).await;
+
+        assert_eq!(test::read_body(response).await, "true");
+    }
+
+    #[actix_web::test]
+    async fn leaves_admission_on_the_inference_socket_to_each_request() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter())
+                .route(
+                    INFERENCE_SOCKET_PATH,
+                    web::get().to(|request: HttpRequest| async move {
+                        let rate_limit_caller = request
+                            .extensions()
+                            .get::<RateLimitCaller>()
+                            .cloned()
+                            .unwrap();
+
+                        format!(
+                            "{} {}",
+                            RequestAdmission::from_request(&request)
+                                .rate_limit_permit
+                                .is_some(),
+                            rate_limit_caller.admit().is_ok()
+                        )
+                    }),
+                ),
+        )
+        .await;
+
+        for _ in 0..2 {
+            let response = test::call_service(
+                &app,
+                test::TestRequest::get()
+                    .uri(INFERENCE_SOCKET_PATH)
+                    .peer_addr(SocketAddr::from(([10, 0, 0, 1], 40000)))
+                    .to_request(),
+            )
+            .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::get()
+                .uri(INFERENCE_SOCKET_PATH)
+                .peer_addr(SocketAddr::from(([10, 0, 0, 2], 40000)))
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(test::read_body(response).await, "false true");
+    }
+
+    #[actix_web::test]
+    async fn health_is_never_limited() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(enforce_rate_limits))
+                .app_data(rate_limiter())
+                .route("/health", web::get().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        for _ in 0..3 {
+            let response = test::call_service(
+                &app,
+                test::TestRequest::get()
+                    .uri("/health")
+                    .peer_addr(SocketAddr::from(([10, 0, 0, 1], 40000)))
+                    .to_request(),
+            )
+            .await;
+
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+}
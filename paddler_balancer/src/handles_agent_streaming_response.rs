@@ -4,6 +4,7 @@ use async_trait::async_trait;
 use crate::manages_senders::ManagesSenders;
 use crate::manages_senders_controller::ManagesSendersController;
 use paddler_messaging::management_socket::agent::request::Request as AgentJsonRpcRequest;
+use paddler_messaging::trace_context::TraceContext;
 
 #[async_trait]
 pub trait HandlesAgentStreamingResponse<TParams>
@@ -16,5 +17,6 @@ where
         &self,
         request_id: String,
         params: TParams,
+        trace_context: Option<TraceContext>,
     ) -> Result<ManagesSendersController<Self::SenderCollection>>;
 }
@@ -1,9 +1,16 @@
 use std::net::SocketAddr;
 use std::time::Duration;
 
+use crate::agent_failover_policy::AgentFailoverPolicy;
+use crate::dispatch_strategy::DispatchStrategy;
+use crate::tls_configuration::TlsConfiguration;
+
 #[derive(Clone)]
 pub struct Configuration {
     pub addr: SocketAddr,
+    pub agent_failover_policy: AgentFailoverPolicy,
     pub cors_allowed_hosts: Vec<String>,
+    pub dispatch_strategy: DispatchStrategy,
     pub inference_item_timeout: Duration,
+    pub tls_configuration: Option<TlsConfiguration>,
 }
@@ -1,12 +1,18 @@
+use std::time::Instant;
+
 use actix_web::Error;
+use actix_web::HttpRequest;
 use actix_web::Responder;
+use actix_web::error::ErrorBadRequest;
 use actix_web::post;
 use actix_web::web;
 use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+use paddler_messaging::validates::Validates as _;
 
 use crate::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
 use crate::http_stream_from_agent::http_stream_from_agent;
 use crate::inference_service::app_data::AppData;
+use crate::request_admission::RequestAdmission;
 use crate::require_token_generation_enabled::require_token_generation_enabled;
 
 pub fn register(cfg: &mut web::ServiceConfig) {
@@ -16,14 +22,45 @@ pub fn register(cfg: &mut web::ServiceConfig) {
 #[post("/api/v1/continue_from_raw_prompt")]
 async fn respond(
     app_data: web::Data<AppData>,
+    http_request: HttpRequest,
     params: web::Json<ContinueFromRawPromptParams>,
 ) -> Result<impl Responder, Error> {
-    require_token_generation_enabled(&app_data.balancer_applicable_state_holder)?;
+    let received_at = Instant::now();
+    let reject = |error_kind: &'static str, error: Error| {
+        app_data
+            .buffered_request_manager
+            .observability
+            .record_rejected_request(&http_request, received_at.elapsed(), error_kind);
+
+        error
+    };
+    let params = params.into_inner();
+    let request_admission = RequestAdmission::from_request(&http_request);
+    let model_pool = app_data
+        .balancer_applicable_state_holder
+        .resolve_model_pool(params.model.as_deref())
+        .map_err(|err| reject("model_pool_not_found", ErrorBadRequest(err)))?;
+
+    require_token_generation_enabled(
+        &app_data.balancer_applicable_state_holder,
+        model_pool.as_deref(),
+    )
+    .map_err(|err| reject("token_generation_disabled", err))?;
 
     Ok(http_stream_from_agent(
         app_data.buffered_request_manager.clone(),
         app_data.inference_service_configuration.clone(),
-        params.into_inner(),
+        model_pool,
+        match params.validate() {
+            Ok(validated_params) => validated_params,
+            Err(validation_error) => {
+                return Err(reject(
+                    "invalid_request",
+                    ErrorBadRequest(format!("Invalid request parameters: {validation_error}")),
+                ));
+            }
+        },
+        request_admission,
         IdentityTransformer::new(),
         app_data.shutdown.clone(),
     ))
@@ -1,16 +1,20 @@
+use std::time::Instant;
+
 use actix_web::Error;
+use actix_web::HttpRequest;
 use actix_web::HttpResponse;
 use actix_web::Responder;
+use actix_web::error::ErrorBadRequest;
 use actix_web::error::ErrorInternalServerError;
 use actix_web::error::ErrorNotImplemented;
 use actix_web::error::ErrorServiceUnavailable;
 use actix_web::http::header;
 use actix_web::post;
-use actix_web::rt;
 use actix_web::web;
 use anyhow::Result;
 use async_trait::async_trait;
 use bytes::Bytes;
+use futures::stream;
 use futures::stream::StreamExt;
 use nanoid::nanoid;
 use paddler_messaging::embedding_result::EmbeddingResult;
@@ -19,19 +23,13 @@ use paddler_messaging::inference_client::response::Response as OutgoingResponse;
 use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
 use paddler_messaging::request_params::generate_embedding_batch_params::chunk_evenly_with_cap_error::ChunkEvenlyWithCapError;
 use paddler_messaging::request_params::generate_embedding_batch_params::GenerateEmbeddingBatchParams;
-use tokio::sync::mpsc;
-use tokio::task::JoinSet;
-use tokio_stream::wrappers::UnboundedReceiverStream;
-use tokio_util::sync::CancellationToken;
 
-use crate::cancellation_token_stream_guard::CancellationTokenStreamGuard;
-use crate::chunk_forwarding_session_controller::ChunkForwardingSessionController;
 use crate::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
 use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
 use crate::chunk_forwarding_session_controller::transforms_outgoing_message::TransformsOutgoingMessage;
-use crate::controls_session::ControlsSession as _;
+use crate::embedding_batch_stream_from_agents::embedding_batch_stream_from_agents;
 use crate::inference_service::app_data::AppData;
-use crate::request_from_agent::request_from_agent;
+use crate::request_admission::RequestAdmission;
 
 #[derive(Clone)]
 struct EmbeddingChunkBodyTransformer;
@@ -62,106 +60,94 @@ pub fn register(cfg: &mut web::ServiceConfig) {
 #[post("/api/v1/generate_embedding_batch")]
 async fn respond(
     app_data: web::Data<AppData>,
+    http_request: HttpRequest,
     params: web::Json<GenerateEmbeddingBatchParams>,
 ) -> Result<impl Responder, Error> {
+    let received_at = Instant::now();
+    let reject = |error_kind: &'static str, error: Error| {
+        app_data
+            .buffered_request_manager
+            .observability
+            .record_rejected_request(&http_request, received_at.elapsed(), error_kind);
+
+        error
+    };
+    let params = params.into_inner();
+    let request_admission = RequestAdmission::from_request(&http_request);
     let balancer_applicable_state_holder = app_data.balancer_applicable_state_holder.clone();
-    let Some(agent_desired_state) = balancer_applicable_state_holder.get_agent_desired_state()
+    let model_pool = balancer_applicable_state_holder
+        .resolve_model_pool(params.model.as_deref())
+        .map_err(|err| reject("model_pool_not_found", ErrorBadRequest(err)))?;
+    let Some(agent_desired_state) =
+        balancer_applicable_state_holder.get_model_pool_agent_desired_state(model_pool.as_deref())
     else {
-        return Err(ErrorServiceUnavailable(
-            "Balancer applicable state is not yet set",
+        return Err(reject(
+            "balancer_state_not_set",
+            ErrorServiceUnavailable("Balancer applicable state is not yet set"),
         ));
     };
 
     if !agent_desired_state.inference_parameters.enable_embeddings {
-        return Err(ErrorNotImplemented(
-            "Embedding generation is not enabled in the inference parameters",
+        return Err(reject(
+            "embeddings_disabled",
+            ErrorNotImplemented("Embedding generation is not enabled in the inference parameters"),
         ));
     }
 
-    let agent_count = app_data.agent_controller_pool.agents.len();
+    let agent_count = app_data
+        .agent_controller_pool
+        .count_agents_in_model_pool(model_pool.as_deref());
     let embedding_batch_size = agent_desired_state
         .inference_parameters
         .embedding_batch_size;
 
-    let connection_close = CancellationToken::new();
-    let (chunk_tx, chunk_rx) = mpsc::unbounded_channel();
-
-    let mut chunk_tasks: JoinSet<()> = JoinSet::new();
-
-    let batches = match params
-        .into_inner()
-        .chunk_evenly_with_cap(agent_count, embedding_batch_size)
-    {
+    let batches = match params.chunk_evenly_with_cap(agent_count, embedding_batch_size) {
         Ok(batches) => batches,
         Err(ChunkEvenlyWithCapError::ZeroAgentCount) => {
-            return Err(ErrorServiceUnavailable("No agents are currently connected"));
+            return Err(reject(
+                "no_agents_connected",
+                ErrorServiceUnavailable("No agents are currently connected"),
+            ));
         }
         Err(ChunkEvenlyWithCapError::ZeroMaxDocumentsPerChunk) => {
-            return Err(ErrorInternalServerError(
-                "embedding_batch_size is zero despite validation",
+            return Err(reject(
+                "embedding_batch_size_is_zero",
+                ErrorInternalServerError("embedding_batch_size is zero despite validation"),
             ));
         }
     };
 
-    for batch in batches {
-        let buffered_request_manager_clone = app_data.buffered_request_manager.clone();
-        let chunk_tx_clone = chunk_tx.clone();
-        let connection_close_clone = connection_close.clone();
-        let inference_service_configuration_clone =
-            app_data.inference_service_configuration.clone();
-        let shutdown_clone = app_data.shutdown.clone();
-
-        chunk_tasks.spawn(async move {
-            let request_id: String = nanoid!();
-            let session_controller = ChunkForwardingSessionController::new(
-                chunk_tx_clone,
-                EmbeddingChunkBodyTransformer,
-            );
-
-            request_from_agent(
-                buffered_request_manager_clone,
-                connection_close_clone,
-                inference_service_configuration_clone,
-                batch,
-                request_id,
-                session_controller,
-                shutdown_clone,
-            )
-            .await;
-        });
-    }
-
-    let final_done_chunk_tx = chunk_tx.clone();
-
-    rt::spawn(async move {
-        while chunk_tasks.join_next().await.is_some() {}
-
-        let final_request_id: String = nanoid!();
-        let mut final_session =
-            ChunkForwardingSessionController::new(final_done_chunk_tx, IdentityTransformer::new());
-
-        final_session
-            .send_response_safe(OutgoingMessage::Response(ResponseEnvelope {
+    let final_done_chunk = stream::once(async {
+        IdentityTransformer::new()
+            .transform(OutgoingMessage::Response(ResponseEnvelope {
                 generated_by: None,
-                request_id: final_request_id,
+                request_id: nanoid!(),
                 response: OutgoingResponse::Embedding(EmbeddingResult::Done),
             }))
-            .await;
+            .await
+            .unwrap_or_default()
+    })
+    .flat_map(stream::iter);
+
+    let stream = embedding_batch_stream_from_agents(
+        app_data.buffered_request_manager.clone(),
+        app_data.inference_service_configuration.clone(),
+        model_pool,
+        batches,
+        request_admission,
+        EmbeddingChunkBodyTransformer,
+        app_data.shutdown.clone(),
+    )
+    .chain(final_done_chunk)
+    .filter_map(|transform_result| async move {
+        match transform_result {
+            TransformResult::Chunk(content) | TransformResult::Error(content) => {
+                Some(Ok::<_, Error>(Bytes::from(format!("{content}\n"))))
+            }
+            TransformResult::Discard => None,
+        }
     });
 
-    drop(chunk_tx);
-
-    let stream =
-        CancellationTokenStreamGuard::new(connection_close, UnboundedReceiverStream::new(chunk_rx))
-            .filter_map(|transform_result| async move {
-                match transform_result {
-                    TransformResult::Chunk(content) | TransformResult::Error(content) => {
-                        Some(Ok::<_, Error>(Bytes::from(format!("{content}\n"))))
-                    }
-                    TransformResult::Discard => None,
-                }
-            });
-
     Ok(HttpResponse::Ok()
         .insert_header(header::ContentType::json())
         .insert_header((header::CACHE_CONTROL, "no-cache"))
@@ -170,13 +156,11 @@ async fn respond(
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
+    use std::collections::BTreeMap;
+
     use std::net::SocketAddr;
     use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
     use std::time::Duration;
 
     use actix_web::App;
@@ -192,18 +176,17 @@ mod tests {
     use super::register;
     use crate::agent_controller::AgentController;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
     use crate::balancer_applicable_state::BalancerApplicableState;
     use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use crate::buffered_request_manager::BufferedRequestManager;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
-    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
+    use crate::dispatch_strategy::DispatchStrategy;
     use crate::inference_service::app_data::AppData;
     use crate::inference_service::configuration::Configuration;
-    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
+    use crate::request_outcome::RequestOutcome;
     use paddler_messaging::agent_desired_model::AgentDesiredModel;
     use paddler_messaging::agent_desired_state::AgentDesiredState;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
     use paddler_messaging::atomic_value::AtomicValue;
     use paddler_messaging::embedding_input_document::EmbeddingInputDocument;
     use paddler_messaging::embedding_normalization_method::EmbeddingNormalizationMethod;
@@ -216,30 +199,9 @@ mod tests {
         drop(agent_message_rx);
 
         Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
             desired_slots_total: AtomicValue::<AtomicI32>::new(1),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: agent_id.to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(1),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test(agent_id, agent_message_tx)
         })
     }
 
@@ -262,6 +224,7 @@ mod tests {
                 model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
                 multimodal_projection: AgentDesiredModel::None,
             },
+            model_pools: BTreeMap::new(),
         }
     }
 
@@ -278,13 +241,17 @@ mod tests {
                 agent_controller_pool.clone(),
                 Duration::from_secs(1),
                 10,
+                RequestObservability::default(),
             )),
             agent_controller_pool,
             balancer_applicable_state_holder,
             inference_service_configuration: Configuration {
                 addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
                 cors_allowed_hosts: Vec::new(),
+                dispatch_strategy: DispatchStrategy::LeastBusy,
                 inference_item_timeout: Duration::from_secs(1),
+                tls_configuration: None,
             },
             shutdown: CancellationToken::new(),
         }
@@ -295,7 +262,9 @@ mod tests {
             input_batch: vec![EmbeddingInputDocument {
                 content: "the quick brown fox".to_owned(),
                 id: "doc-1".to_owned(),
+                tokens: None,
             }],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
         }
     }
@@ -323,14 +292,20 @@ mod tests {
 
     #[actix_web::test]
     async fn responds_service_unavailable_when_no_agents_are_connected() {
+        let app_data = app_data(
+            Arc::new(AgentControllerPool::default()),
+            Some(applicable_state(inference_parameters_with_embeddings(
+                true, 256,
+            ))),
+        );
+        let request_metrics = app_data
+            .buffered_request_manager
+            .observability
+            .request_metrics
+            .clone();
         let app = init_service(
             App::new()
-                .app_data(web::Data::new(app_data(
-                    Arc::new(AgentControllerPool::default()),
-                    Some(applicable_state(inference_parameters_with_embeddings(
-                        true, 256,
-                    ))),
-                )))
+                .app_data(web::Data::new(app_data))
                 .configure(register),
         )
         .await;
@@ -342,6 +317,14 @@ mod tests {
         let response = call_service(&app, request).await;
 
         assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(
+            request_metrics.requests_by_endpoint_and_outcome(),
+            vec![(
+                "/api/v1/generate_embedding_batch".to_owned(),
+                RequestOutcome::Rejected,
+                1
+            )]
+        );
     }
 
     #[actix_web::test]
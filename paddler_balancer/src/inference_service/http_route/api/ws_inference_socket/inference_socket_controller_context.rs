@@ -5,12 +5,16 @@ use tokio_util::sync::CancellationToken;
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::buffered_request_manager::BufferedRequestManager;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::rate_limit_caller::RateLimitCaller;
+use crate::request_admission::RequestAdmission;
 use crate::request_cancellation_tokens::RequestCancellationTokens;
 
 pub struct InferenceSocketControllerContext {
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub inference_service_configuration: InferenceServiceConfiguration,
+    pub rate_limit_caller: Option<RateLimitCaller>,
+    pub request_admission: RequestAdmission,
     pub request_cancellation_tokens: Arc<RequestCancellationTokens>,
     pub shutdown: CancellationToken,
 }
@@ -3,10 +3,12 @@ mod spawn_token_generation_mode_watcher;
 
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
 
 use actix_web::rt;
 use actix_web::Error;
 use actix_web::HttpRequest;
+use actix_web::HttpMessage as _;
 use actix_web::HttpResponse;
 use actix_web::get;
 use actix_web::web::Data;
@@ -16,6 +18,7 @@ use actix_ws::Session;
 use anyhow::Result;
 use async_trait::async_trait;
 use log::error;
+use log::warn;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::inference_client::message::Message as OutgoingMessage;
 use paddler_messaging::inference_client::response::Response as OutgoingResponse;
@@ -24,6 +27,7 @@ use paddler_messaging::inference_server::notification::Notification as Inference
 use paddler_messaging::inference_server::request::Request as InferenceServerRequest;
 use paddler_messaging::management_socket::agent::request::Request as AgentJsonRpcRequest;
 use paddler_messaging::streamable_result::StreamableResult;
+use paddler_messaging::trace_context::TraceContext;
 use paddler_messaging::jsonrpc::error::Error as JsonRpcError;
 use paddler_messaging::jsonrpc::error_envelope::ErrorEnvelope;
 use paddler_messaging::jsonrpc::request_envelope::RequestEnvelope;
@@ -46,6 +50,9 @@ use crate::handles_agent_streaming_response::HandlesAgentStreamingResponse;
 use crate::inference_service::app_data::AppData;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::manages_senders::ManagesSenders;
+use crate::provides_affinity_key::ProvidesAffinityKey;
+use crate::rate_limit_caller::RateLimitCaller;
+use crate::request_admission::RequestAdmission;
 use crate::request_cancellation_registration::RequestCancellationRegistration;
 use crate::request_cancellation_token_guard::RequestCancellationTokenGuard;
 use crate::request_cancellation_tokens::RequestCancellationTokens;
@@ -81,16 +88,39 @@ async fn send_token_generation_disabled(
 async fn handle_inference_request<TParams>(
     connection_close: &CancellationToken,
     context: Arc<InferenceSocketControllerContext>,
+    model: Option<&str>,
     params: TParams,
     request_id: String,
+    trace_context: Option<TraceContext>,
     mut websocket_session_controller: WebSocketSessionController<OutgoingMessage>,
 ) where
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send + 'static,
+    TParams: Clone + Debug + Into<AgentJsonRpcRequest> + ProvidesAffinityKey + Send + 'static,
     AgentController: HandlesAgentStreamingResponse<TParams>,
     <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
 {
+    let model_pool = match context
+        .balancer_applicable_state_holder
+        .resolve_model_pool(model)
+    {
+        Ok(model_pool) => model_pool,
+        Err(err) => {
+            respond_with_error(
+                JsonRpcError {
+                    code: 400,
+                    description: err.to_string(),
+                },
+                request_id,
+                &mut websocket_session_controller,
+            )
+            .await;
+
+            return;
+        }
+    };
+
     match ClusterTokenGenerationMode::from_applicable_state_holder(
         &context.balancer_applicable_state_holder,
+        model_pool.as_deref(),
     ) {
         ClusterTokenGenerationMode::DisabledForEmbeddings => {
             send_token_generation_disabled(request_id, &mut websocket_session_controller).await;
@@ -102,12 +132,56 @@ async fn handle_inference_request<TParams>(
                 request_id.clone(),
             ) {
                 RequestCancellationRegistration::Registered(request_cancellation_token_guard) => {
+                    let mut request_admission = context.request_admission.clone();
+
+                    if let Some(rate_limit_caller) = &context.rate_limit_caller {
+                        match rate_limit_caller.admit() {
+                            Ok(rate_limit_permit) => {
+                                request_admission.rate_limit_permit =
+                                    Some(Arc::new(rate_limit_permit));
+                            }
+                            Err(rejection) => {
+                                warn!("Rejecting inference request {request_id:?}: {rejection}");
+
+                                context
+                                    .buffered_request_manager
+                                    .observability
+                                    .record_rejection(
+                                        request_admission.caller_key.clone(),
+                                        Duration::ZERO,
+                                        request_admission.endpoint.clone(),
+                                        "rate_limited",
+                                    );
+
+                                respond_with_error(
+                                    JsonRpcError {
+                                        code: 429,
+                                        description: rejection.to_string(),
+                                    },
+                                    request_id,
+                                    &mut websocket_session_controller,
+                                )
+                                .await;
+
+                                return;
+                            }
+                        }
+                    }
+
+                    // A trace context sent along with the request takes precedence over the
+                    // `traceparent` header of the connection.
+                    if trace_context.is_some() {
+                        request_admission.trace_context = trace_context;
+                    }
+
                     rt::spawn(async move {
                         request_from_agent(
                             context.buffered_request_manager.clone(),
                             request_cancellation_token_guard.cancellation_token.clone(),
                             context.inference_service_configuration.clone(),
+                            model_pool,
                             params,
+                            request_admission,
                             request_id,
                             websocket_session_controller,
                             context.shutdown.clone(),
@@ -139,6 +213,8 @@ struct InferenceSocketController {
     balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     buffered_request_manager: Arc<BufferedRequestManager>,
     inference_service_configuration: InferenceServiceConfiguration,
+    rate_limit_caller: Option<RateLimitCaller>,
+    request_admission: RequestAdmission,
     shutdown: CancellationToken,
 }
 
@@ -153,6 +229,8 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
             balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             buffered_request_manager: self.buffered_request_manager.clone(),
             inference_service_configuration: self.inference_service_configuration.clone(),
+            rate_limit_caller: self.rate_limit_caller.clone(),
+            request_admission: self.request_admission.clone(),
             request_cancellation_tokens: Arc::new(RequestCancellationTokens::default()),
             shutdown: self.shutdown.clone(),
         }
@@ -184,14 +262,18 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
                     InferenceJsonRpcRequest::ContinueFromConversationHistory(
                         conversation_history_params,
                     ),
+                trace_context,
             }) => {
                 let validated_params = conversation_history_params.validate()?;
+                let model = validated_params.model.clone();
 
                 handle_inference_request(
                     &connection_close,
                     context,
+                    model.as_deref(),
                     validated_params,
                     request_id,
+                    trace_context,
                     websocket_session_controller,
                 )
                 .await;
@@ -199,12 +281,18 @@ impl ControlsWebSocketEndpoint for InferenceSocketController {
             InferenceJsonRpcMessage::Request(RequestEnvelope {
                 id: request_id,
                 request: InferenceJsonRpcRequest::ContinueFromRawPrompt(raw_prompt_params),
+                trace_context,
             }) => {
+                let validated_params = raw_prompt_params.validate()?;
+                let model = validated_params.model.clone();
+
                 handle_inference_request(
                     &connection_close,
                     context,
-                    raw_prompt_params,
+                    model.as_deref(),
+                    validated_params,
                     request_id,
+                    trace_context,
                     websocket_session_controller,
                 )
                 .await;
@@ -239,6 +327,8 @@ async fn respond(
         balancer_applicable_state_holder: app_data.balancer_applicable_state_holder.clone(),
         buffered_request_manager: app_data.buffered_request_manager.clone(),
         inference_service_configuration: app_data.inference_service_configuration.clone(),
+        rate_limit_caller: http_request.extensions().get::<RateLimitCaller>().cloned(),
+        request_admission: RequestAdmission::from_request(&http_request),
         shutdown: app_data.shutdown.clone(),
     };
 
@@ -251,14 +341,12 @@ pub fn register(service_config: &mut ServiceConfig) {
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
+    use std::collections::BTreeMap;
+
     use std::mem::discriminant;
     use std::net::SocketAddr;
     use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
     use std::time::Duration;
 
     use actix_web::App;
@@ -275,19 +363,20 @@ mod tests {
 
     use crate::agent_controller::AgentController;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
     use crate::balancer_applicable_state::BalancerApplicableState;
     use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use crate::buffered_request_manager::BufferedRequestManager;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
     use crate::continuation_decision::ContinuationDecision;
     use crate::controls_websocket_endpoint::ControlsWebSocketEndpoint as _;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
-    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
-    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::dispatch_strategy::DispatchStrategy;
+    use crate::rate_limit_configuration::RateLimitConfiguration;
+    use crate::rate_limiter::RateLimiter;
+    use crate::request_observability::RequestObservability;
+    use crate::request_priority::RequestPriority;
     use crate::websocket_session_controller::WebSocketSessionController;
     use paddler_messaging::agent_desired_model::AgentDesiredModel;
     use paddler_messaging::agent_desired_state::AgentDesiredState;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
     use paddler_messaging::atomic_value::AtomicValue;
     use paddler_messaging::conversation_history::ConversationHistory;
     use paddler_messaging::inference_parameters::InferenceParameters;
@@ -298,6 +387,7 @@ mod tests {
     use paddler_messaging::management_socket::agent::notification::Notification as AgentJsonRpcNotification;
     use paddler_messaging::management_socket::agent::request::Request as AgentJsonRpcRequest;
     use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
     use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 
     use super::AppData;
@@ -307,6 +397,8 @@ mod tests {
     use super::InferenceSocketController;
     use super::InferenceSocketControllerContext;
     use super::OutgoingMessage;
+    use super::RateLimitCaller;
+    use super::RequestAdmission;
     use super::RequestCancellationTokens;
     use super::register;
 
@@ -319,30 +411,9 @@ mod tests {
         let pool = Arc::new(AgentControllerPool::default());
         let (agent_message_tx, agent_message_rx) = mpsc::unbounded_channel();
         let agent_controller = Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
             desired_slots_total: AtomicValue::<AtomicI32>::new(1),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: agent_id.to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(1),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test(agent_id, agent_message_tx)
         });
 
         pool.register_agent_controller(agent_id.to_owned(), agent_controller)
@@ -364,8 +435,11 @@ mod tests {
                 pool,
                 Duration::from_mins(1),
                 10,
+                RequestObservability::default(),
             )),
             inference_service_configuration: inference_service_configuration(),
+            rate_limit_caller: None,
+            request_admission: RequestAdmission::default(),
             request_cancellation_tokens: Arc::new(RequestCancellationTokens::default()),
             shutdown: CancellationToken::new(),
         })
@@ -385,6 +459,7 @@ mod tests {
                     model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
                     multimodal_projection: AgentDesiredModel::None,
                 },
+                model_pools: BTreeMap::new(),
             },
         ));
 
@@ -409,8 +484,11 @@ mod tests {
     fn inference_service_configuration() -> InferenceServiceConfiguration {
         InferenceServiceConfiguration {
             addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+            agent_failover_policy: AgentFailoverPolicy::Disabled,
             cors_allowed_hosts: vec!["http://localhost".to_owned()],
+            dispatch_strategy: DispatchStrategy::LeastBusy,
             inference_item_timeout: Duration::from_secs(30),
+            tls_configuration: None,
         }
     }
 
@@ -421,12 +499,21 @@ mod tests {
             Arc::new(AgentControllerPool::default()),
             Duration::from_mins(1),
             10,
+            RequestObservability::default(),
         ));
         let shutdown = CancellationToken::new();
         let controller = InferenceSocketController {
             balancer_applicable_state_holder: balancer_applicable_state_holder.clone(),
             buffered_request_manager: buffered_request_manager.clone(),
             inference_service_configuration: inference_service_configuration(),
+            rate_limit_caller: None,
+            request_admission: RequestAdmission {
+                caller_key: None,
+                endpoint: String::new(),
+                priority: RequestPriority::Batch,
+                rate_limit_permit: None,
+                trace_context: None,
+            },
             shutdown: shutdown.clone(),
         };
 
@@ -450,6 +537,7 @@ mod tests {
             context.inference_service_configuration.cors_allowed_hosts,
             vec!["http://localhost".to_owned()]
         );
+        assert_eq!(context.request_admission.priority, RequestPriority::Batch);
 
         shutdown.cancel();
 
@@ -465,6 +553,7 @@ mod tests {
                 Arc::new(AgentControllerPool::default()),
                 Duration::from_mins(1),
                 10,
+                RequestObservability::default(),
             )),
             inference_service_configuration: inference_service_configuration(),
             shutdown: CancellationToken::new(),
@@ -492,6 +581,7 @@ mod tests {
                 Arc::new(AgentControllerPool::default()),
                 Duration::from_mins(1),
                 10,
+                RequestObservability::default(),
             )),
             inference_service_configuration: inference_service_configuration(),
             shutdown: CancellationToken::new(),
@@ -526,10 +616,16 @@ mod tests {
                 request: InferenceJsonRpcRequest::ContinueFromRawPrompt(
                     ContinueFromRawPromptParams {
                         grammar: None,
+                        logprobs: None,
                         max_tokens: 1,
+                        model: None,
                         raw_prompt: "fixture prompt".to_owned(),
+                        sampling: None,
+                        session_key: None,
+                        stop: Vec::new(),
                     },
                 ),
+                trace_context: None,
             }),
             session_controller,
         )
@@ -563,11 +659,18 @@ mod tests {
                         conversation_history: ConversationHistory::new(Vec::new()),
                         enable_thinking: false,
                         grammar: None,
+                        logprobs: None,
                         max_tokens: 1,
+                        model: None,
                         parse_tool_calls: false,
+                        sampling: None,
+                        session_key: None,
+                        stop: Vec::new(),
+                        tool_choice: ToolChoice::Auto,
                         tools: Vec::new(),
                     },
                 ),
+                trace_context: None,
             }),
             session_controller,
         )
@@ -628,10 +731,16 @@ mod tests {
                 request: InferenceJsonRpcRequest::ContinueFromRawPrompt(
                     ContinueFromRawPromptParams {
                         grammar: None,
+                        logprobs: None,
                         max_tokens: 1,
+                        model: None,
                         raw_prompt: "fixture prompt".to_owned(),
+                        sampling: None,
+                        session_key: None,
+                        stop: Vec::new(),
                     },
                 ),
+                trace_context: None,
             }),
             session_controller,
         )
@@ -651,9 +760,15 @@ mod tests {
                 id: "request-raw-prompt".to_owned(),
                 request: AgentJsonRpcRequest::ContinueFromRawPrompt(ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 1,
+                    model: None,
                     raw_prompt: "fixture prompt".to_owned(),
+                    sampling: None,
+                    session_key: None,
+                    stop: Vec::new(),
                 }),
+                trace_context: None,
             })),
         );
 
@@ -691,10 +806,16 @@ mod tests {
                 request: InferenceJsonRpcRequest::ContinueFromRawPrompt(
                     ContinueFromRawPromptParams {
                         grammar: None,
+                        logprobs: None,
                         max_tokens: 1,
+                        model: None,
                         raw_prompt: "fixture prompt".to_owned(),
+                        sampling: None,
+                        session_key: None,
+                        stop: Vec::new(),
                     },
                 ),
+                trace_context: None,
             }),
             session_controller,
         )
@@ -729,11 +850,18 @@ mod tests {
                         conversation_history: ConversationHistory::new(Vec::new()),
                         enable_thinking: false,
                         grammar: None,
+                        logprobs: None,
                         max_tokens: 1,
+                        model: None,
                         parse_tool_calls: false,
+                        sampling: None,
+                        session_key: None,
+                        stop: Vec::new(),
+                        tool_choice: ToolChoice::Auto,
                         tools: Vec::new(),
                     },
                 ),
+                trace_context: None,
             }),
             session_controller,
         )
@@ -757,11 +885,18 @@ mod tests {
                         conversation_history: ConversationHistory::new(Vec::new()),
                         enable_thinking: false,
                         grammar: None,
+                        logprobs: None,
                         max_tokens: 1,
+                        model: None,
                         parse_tool_calls: false,
+                        sampling: None,
+                        session_key: None,
+                        stop: Vec::new(),
+                        tool_choice: ToolChoice::Auto,
                         tools: Vec::new(),
                     },
                 ),
+                trace_context: None,
             })),
         );
 
@@ -778,4 +913,84 @@ mod tests {
             )),
         );
     }
+
+    #[actix_web::test]
+    async fn handle_raw_prompt_request_holds_a_rate_limit_permit_per_request() {
+        let pool = Arc::new(AgentControllerPool::default());
+        let (agent_message_tx, mut agent_message_rx) = mpsc::unbounded_channel();
+
+        pool.register_agent_controller(
+            "agent-rate-limited".to_owned(),
+            Arc::new(AgentController {
+                desired_slots_total: AtomicValue::<AtomicI32>::new(2),
+                slots_total: AtomicValue::<AtomicI32>::new(2),
+                ..AgentController::new_for_test("agent-rate-limited", agent_message_tx)
+            }),
+        )
+        .unwrap();
+
+        let rate_limit_caller = RateLimitCaller::new(
+            "caller".to_owned(),
+            Arc::new(RateLimiter::new(RateLimitConfiguration {
+                max_concurrent_requests: Some(1),
+                ..RateLimitConfiguration::default()
+            })),
+        );
+        let context = Arc::new(InferenceSocketControllerContext {
+            rate_limit_caller: Some(rate_limit_caller.clone()),
+            ..Arc::into_inner(context_with_pool(
+                pool,
+                Arc::new(BalancerApplicableStateHolder::default()),
+            ))
+            .unwrap()
+        });
+        let connection_close = CancellationToken::new();
+        let raw_prompt_request = |request_id: &str| {
+            InferenceJsonRpcMessage::Request(RequestEnvelope {
+                id: request_id.to_owned(),
+                request: InferenceJsonRpcRequest::ContinueFromRawPrompt(
+                    ContinueFromRawPromptParams {
+                        grammar: None,
+                        logprobs: None,
+                        max_tokens: 1,
+                        model: None,
+                        raw_prompt: "fixture prompt".to_owned(),
+                        sampling: None,
+                        session_key: None,
+                        stop: Vec::new(),
+                    },
+                ),
+                trace_context: None,
+            })
+        };
+
+        InferenceSocketController::handle_deserialized_message(
+            connection_close.clone(),
+            context.clone(),
+            raw_prompt_request("request-admitted"),
+            open_session_controller().await,
+        )
+        .await
+        .unwrap();
+
+        assert!(agent_message_rx.recv().await.is_some());
+        assert!(rate_limit_caller.admit().is_err());
+
+        InferenceSocketController::handle_deserialized_message(
+            connection_close.clone(),
+            context,
+            raw_prompt_request("request-over-the-limit"),
+            open_session_controller().await,
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(100), agent_message_rx.recv())
+                .await
+                .is_err()
+        );
+
+        connection_close.cancel();
+    }
 }
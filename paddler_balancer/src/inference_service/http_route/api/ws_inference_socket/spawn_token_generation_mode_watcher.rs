@@ -40,6 +40,7 @@ pub fn spawn_token_generation_mode_watcher(
         let mut session_controller = WebSocketSessionController::<OutgoingMessage>::new(session);
         let mut last_mode = ClusterTokenGenerationMode::from_applicable_state_holder(
             &balancer_applicable_state_holder,
+            None,
         );
 
         if last_mode == ClusterTokenGenerationMode::DisabledForEmbeddings
@@ -62,6 +63,7 @@ pub fn spawn_token_generation_mode_watcher(
 
                     let current_mode = ClusterTokenGenerationMode::from_applicable_state_holder(
                         &balancer_applicable_state_holder,
+                        None,
                     );
 
                     if current_mode == last_mode {
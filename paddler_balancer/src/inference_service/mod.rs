@@ -5,6 +5,7 @@ pub mod http_route;
 use std::sync::Arc;
 
 use actix_web::App;
+use actix_web::middleware::from_fn;
 use actix_web::web::Data;
 use anyhow::Result;
 use async_trait::async_trait;
@@ -12,12 +13,16 @@ use tokio_util::sync::CancellationToken;
 use trzcina::Service;
 
 use crate::agent_controller_pool::AgentControllerPool;
+use crate::api_key_store::ApiKeyStore;
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::buffered_request_manager::BufferedRequestManager;
 use crate::create_cors_middleware::create_cors_middleware;
+use crate::enforce_rate_limits::enforce_rate_limits;
 use crate::http_route as common_http_route;
 use crate::inference_service::app_data::AppData;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+use crate::rate_limiter::RateLimiter;
+use crate::require_api_key::require_api_key;
 use crate::run_http_service::run_http_service;
 use crate::run_http_service_parameters::RunHttpServiceParameters;
 #[cfg(feature = "web_admin_panel")]
@@ -25,9 +30,11 @@ use crate::web_admin_panel_service::configuration::Configuration as WebAdminPane
 
 pub struct InferenceService {
     pub agent_controller_pool: Arc<AgentControllerPool>,
+    pub api_key_store: Option<Arc<ApiKeyStore>>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub configuration: InferenceServiceConfiguration,
+    pub rate_limiter: Option<Arc<RateLimiter>>,
     #[cfg(feature = "web_admin_panel")]
     pub web_admin_panel_service_configuration: Option<WebAdminPanelServiceConfiguration>,
 }
@@ -71,15 +78,30 @@ impl Service for InferenceService {
             inference_service_configuration: self.configuration.clone(),
             shutdown: shutdown.clone(),
         });
+        let api_key_store = self.api_key_store.clone().map(Data::from);
+        let rate_limiter = self.rate_limiter.clone().map(Data::from);
+        let request_observability = Data::new(self.buffered_request_manager.observability.clone());
 
         run_http_service(
             shutdown,
             RunHttpServiceParameters {
                 app_factory: move || {
-                    App::new()
+                    let app = App::new()
+                        .wrap(from_fn(enforce_rate_limits))
+                        .wrap(from_fn(require_api_key))
                         .wrap(create_cors_middleware(&cors_allowed_hosts_arc))
                         .app_data(app_data.clone())
-                        .configure(common_http_route::get_health::register)
+                        .app_data(request_observability.clone());
+                    let app = match &api_key_store {
+                        Some(api_key_store) => app.app_data(api_key_store.clone()),
+                        None => app,
+                    };
+                    let app = match &rate_limiter {
+                        Some(rate_limiter) => app.app_data(rate_limiter.clone()),
+                        None => app,
+                    };
+
+                    app.configure(common_http_route::get_health::register)
                         .configure(
                             http_route::api::post_continue_from_conversation_history::register,
                         )
@@ -89,6 +111,7 @@ impl Service for InferenceService {
                 },
                 bind_addr: self.configuration.addr,
                 service_name,
+                tls_configuration: self.configuration.tls_configuration.clone(),
                 worker_count: 16,
             },
         )
@@ -108,9 +131,12 @@ mod tests {
 
     use super::InferenceService;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
     use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::dispatch_strategy::DispatchStrategy;
     use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
+    use crate::request_observability::RequestObservability;
     #[cfg(feature = "web_admin_panel")]
     use crate::resolved_socket_addr::ResolvedSocketAddr;
     #[cfg(feature = "web_admin_panel")]
@@ -123,17 +149,23 @@ mod tests {
 
         InferenceService {
             agent_controller_pool: agent_controller_pool.clone(),
+            api_key_store: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
                 agent_controller_pool,
                 Duration::from_secs(30),
                 32,
+                RequestObservability::default(),
             )),
             configuration: InferenceServiceConfiguration {
                 addr,
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
                 cors_allowed_hosts: vec!["http://127.0.0.1:8080".to_owned()],
+                dispatch_strategy: DispatchStrategy::LeastBusy,
                 inference_item_timeout: Duration::from_secs(30),
+                tls_configuration: None,
             },
+            rate_limiter: None,
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration: Some(WebAdminPanelServiceConfiguration {
                 addr: SocketAddr::from(([127, 0, 0, 1], 8081)),
@@ -153,6 +185,7 @@ mod tests {
                     statsd_prefix: "paddler".to_owned(),
                     statsd_reporting_interval: Duration::from_secs(10),
                 },
+                tls_configuration: None,
             }),
         }
     }
@@ -0,0 +1,202 @@
+use std::array;
+use std::mem;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use paddler_messaging::atomic_value::AtomicValue;
+use parking_lot::Mutex;
+use rand::Rng as _;
+
+use crate::latency_sample::LatencySample;
+
+/// Upper bounds of the histogram buckets, in seconds. Observations above the last bound are
+/// only reflected in the total count.
+pub const LATENCY_BUCKET_BOUNDS: [f64; 15] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0, 120.0, 300.0,
+];
+
+/// Observations kept for statsd between two reports. Past that, the sample is a uniform
+/// reservoir over everything observed since the previous report.
+const MAX_UNREPORTED_OBSERVATIONS: usize = 4096;
+
+pub struct LatencyHistogram {
+    bucket_counts: [AtomicValue<AtomicU64>; LATENCY_BUCKET_BOUNDS.len()],
+    count: AtomicValue<AtomicU64>,
+    sampling: AtomicBool,
+    sum_micros: AtomicValue<AtomicU64>,
+    unreported: Mutex<LatencySample>,
+}
+
+impl LatencyHistogram {
+    #[must_use]
+    pub fn count(&self) -> u64 {
+        self.count.get()
+    }
+
+    /// Counts of observations at or below each of `LATENCY_BUCKET_BOUNDS`.
+    #[must_use]
+    pub fn cumulative_counts(&self) -> [u64; LATENCY_BUCKET_BOUNDS.len()] {
+        let mut running_total = 0;
+
+        array::from_fn(|index| {
+            running_total += self.bucket_counts[index].get();
+
+            running_total
+        })
+    }
+
+    pub fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+
+        if let Some(index) = LATENCY_BUCKET_BOUNDS
+            .iter()
+            .position(|bound| seconds <= *bound)
+        {
+            self.bucket_counts[index].increment_by(1);
+        }
+
+        self.count.increment_by(1);
+        self.sum_micros
+            .increment_by(u64::try_from(duration.as_micros()).unwrap_or(u64::MAX));
+
+        if !self.sampling.load(Ordering::Relaxed) {
+            return;
+        }
+
+        let mut unreported = self.unreported.lock();
+
+        unreported.observed += 1;
+
+        if unreported.observations.len() < MAX_UNREPORTED_OBSERVATIONS {
+            unreported.observations.push(duration);
+        } else if let Ok(index) = usize::try_from(rand::rng().random_range(0..unreported.observed))
+            && index < MAX_UNREPORTED_OBSERVATIONS
+        {
+            unreported.observations[index] = duration;
+        }
+    }
+
+    /// Starts keeping a sample of the observations for statsd. Without a reporter taking it,
+    /// observations only update the buckets.
+    pub fn start_sampling(&self) {
+        self.sampling.store(true, Ordering::Relaxed);
+    }
+
+    #[must_use]
+    pub fn sum(&self) -> Duration {
+        Duration::from_micros(self.sum_micros.get())
+    }
+
+    /// Hands over the observations made since the previous call.
+    pub fn take_unreported_sample(&self) -> LatencySample {
+        mem::take(&mut *self.unreported.lock())
+    }
+}
+
+impl Default for LatencyHistogram {
+    fn default() -> Self {
+        Self {
+            bucket_counts: array::from_fn(|_| AtomicValue::<AtomicU64>::new(0)),
+            count: AtomicValue::<AtomicU64>::new(0),
+            sampling: AtomicBool::new(false),
+            sum_micros: AtomicValue::<AtomicU64>::new(0),
+            unreported: Mutex::new(LatencySample::default()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn buckets_are_cumulative() {
+        let histogram = LatencyHistogram::default();
+
+        histogram.observe(Duration::from_millis(3));
+        histogram.observe(Duration::from_millis(200));
+        histogram.observe(Duration::from_millis(700));
+
+        let cumulative_counts = histogram.cumulative_counts();
+
+        assert_eq!(cumulative_counts[0], 1);
+        assert_eq!(cumulative_counts[4], 1);
+        assert_eq!(cumulative_counts[5], 2);
+        assert_eq!(cumulative_counts[7], 3);
+        assert_eq!(cumulative_counts[LATENCY_BUCKET_BOUNDS.len() - 1], 3);
+        assert_eq!(histogram.count(), 3);
+        assert_eq!(histogram.sum(), Duration::from_millis(903));
+    }
+
+    #[test]
+    fn observations_above_the_last_bound_only_add_to_the_count() {
+        let histogram = LatencyHistogram::default();
+
+        histogram.observe(Duration::from_hours(1));
+
+        assert_eq!(
+            histogram.cumulative_counts()[LATENCY_BUCKET_BOUNDS.len() - 1],
+            0
+        );
+        assert_eq!(histogram.count(), 1);
+    }
+
+    #[test]
+    fn nothing_is_sampled_until_sampling_starts() {
+        let histogram = LatencyHistogram::default();
+
+        histogram.observe(Duration::from_millis(5));
+
+        assert_eq!(histogram.take_unreported_sample(), LatencySample::default());
+        assert_eq!(histogram.count(), 1);
+    }
+
+    #[test]
+    fn unreported_sample_is_bounded_and_taken_once() {
+        let histogram = LatencyHistogram::default();
+
+        histogram.start_sampling();
+
+        for _ in 0..=MAX_UNREPORTED_OBSERVATIONS {
+            histogram.observe(Duration::from_millis(5));
+        }
+
+        let sample = histogram.take_unreported_sample();
+
+        assert_eq!(sample.observations.len(), MAX_UNREPORTED_OBSERVATIONS);
+        assert_eq!(
+            sample.observed,
+            u64::try_from(MAX_UNREPORTED_OBSERVATIONS).unwrap() + 1
+        );
+        assert!(sample.sampling_rate() < 1.0);
+        assert_eq!(histogram.take_unreported_sample(), LatencySample::default());
+    }
+
+    #[test]
+    fn later_observations_replace_part_of_a_full_sample() {
+        let histogram = LatencyHistogram::default();
+
+        histogram.start_sampling();
+
+        for _ in 0..MAX_UNREPORTED_OBSERVATIONS {
+            histogram.observe(Duration::from_millis(5));
+        }
+
+        for _ in 0..MAX_UNREPORTED_OBSERVATIONS {
+            histogram.observe(Duration::from_millis(50));
+        }
+
+        let sample = histogram.take_unreported_sample();
+        let replaced = sample
+            .observations
+            .iter()
+            .filter(|observation| **observation == Duration::from_millis(50))
+            .count();
+
+        assert_eq!(sample.observations.len(), MAX_UNREPORTED_OBSERVATIONS);
+        assert!(replaced > MAX_UNREPORTED_OBSERVATIONS / 4);
+        assert!(replaced < MAX_UNREPORTED_OBSERVATIONS * 3 / 4);
+    }
+}
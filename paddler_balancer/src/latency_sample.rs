@@ -0,0 +1,25 @@
+use std::time::Duration;
+
+/// Observations of a latency histogram that were not reported to statsd yet. Only a bounded
+/// number of them is kept, while `observed` counts every one.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct LatencySample {
+    pub observations: Vec<Duration>,
+    pub observed: u64,
+}
+
+impl LatencySample {
+    /// Share of the observations that made it into the sample.
+    #[must_use]
+    #[expect(
+        clippy::cast_precision_loss,
+        reason = "a sampling rate does not need integer precision"
+    )]
+    pub fn sampling_rate(&self) -> f64 {
+        if self.observed == 0 {
+            return 1.0;
+        }
+
+        self.observations.len() as f64 / self.observed as f64
+    }
+}
@@ -3,20 +3,38 @@ pub mod agent_controller_pool;
 mod agent_controller_pool_total_slots;
 pub mod agent_controller_slot_guard;
 pub mod agent_controller_update_result;
+pub mod agent_failover_policy;
 mod agent_response_forwarding_mode;
+mod agent_response_forwarding_outcome;
 mod agent_stop_outcome;
+pub mod agent_throughput;
+pub mod api_key_reload_service;
+pub mod api_key_store;
+pub mod audit_log;
+pub mod audit_log_entry;
+pub mod audit_log_service;
+pub mod autoscaling_event;
+pub mod autoscaling_event_detector;
+pub mod autoscaling_hook;
+pub mod autoscaling_hook_service;
+pub mod autoscaling_signal;
 pub mod balancer_applicable_state;
 pub mod balancer_applicable_state_holder;
 pub mod balancer_desired_state_converter;
 mod buffered_request_agent_wait_result;
 mod buffered_request_count_guard;
 mod buffered_request_counter;
+pub mod buffered_request_limits;
 pub mod buffered_request_manager;
+mod buffered_request_queue;
+mod buffered_request_queue_ticket;
+pub mod caller_key;
 pub mod cancellation_token_stream_guard;
 pub mod chat_template_override_sender_collection;
 pub mod chunk_forwarding_session_controller;
 pub mod cluster_token_generation_mode;
 pub mod compatibility;
+mod constant_time_eq;
 pub mod continuation_decision;
 pub mod continuation_stop_parameters;
 mod controls_manages_senders_endpoint;
@@ -24,31 +42,59 @@ pub mod controls_session;
 pub mod controls_websocket_endpoint;
 pub mod create_cors_middleware;
 pub mod dispatch_candidate;
+pub mod dispatch_strategy;
 pub mod dispatched_agent;
+pub mod embedding_batch_stream_from_agents;
 pub mod embedding_sender_collection;
+mod enforce_rate_limits;
 pub mod generate_tokens_sender_collection;
 mod handles_agent_streaming_response;
 mod http_route;
 mod http_stream_from_agent;
 pub mod inference_service;
+pub mod latency_histogram;
+pub mod latency_sample;
 pub mod management_service;
 pub mod manages_senders;
 pub mod manages_senders_controller;
 pub mod model_metadata_sender_collection;
+pub mod model_pool_desired_state_converter;
+mod model_rollout;
+mod model_rollout_progress;
+pub mod provides_affinity_key;
+pub mod queue_wait_sampler;
+pub mod rate_limit_caller;
+pub mod rate_limit_configuration;
+pub mod rate_limit_permit;
+pub mod rate_limit_rejection;
+mod rate_limited_body;
+pub mod rate_limiter;
 pub mod reconciliation_service;
+pub mod request_admission;
 pub mod request_cancellation_registration;
 pub mod request_cancellation_token_guard;
 pub mod request_cancellation_tokens;
+pub mod request_endpoint;
 pub mod request_from_agent;
+pub mod request_metrics;
+pub mod request_observability;
+pub mod request_outcome;
+pub mod request_priority;
 pub mod request_registration;
+pub mod require_api_key;
 pub mod require_token_generation_enabled;
 pub mod resolved_socket_addr;
 #[cfg(feature = "web_admin_panel")]
 mod response;
+mod rolled_out_agent;
+pub mod rollout_batch_size;
+pub mod rollout_failure_action;
+pub mod rollout_strategy;
 pub mod run_http_service;
 pub mod run_http_service_parameters;
 pub mod sends_rpc_message;
 pub mod serve_http_until_shutdown;
+pub mod session_affinity_table;
 pub mod sets_desired_state;
 pub mod snapshots_stream;
 pub mod state_database;
@@ -56,7 +102,9 @@ pub mod state_database_type;
 #[cfg(feature = "web_admin_panel")]
 pub mod static_files;
 pub mod statsd_service;
+pub mod tls_configuration;
 mod unbounded_stream_from_agent;
+pub mod verified_client_certificate;
 #[cfg(feature = "web_admin_panel")]
 pub mod web_admin_panel_service;
 pub mod websocket_session_controller;
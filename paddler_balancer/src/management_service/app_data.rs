@@ -12,7 +12,9 @@ use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
 use crate::state_database::StateDatabase;
 
 pub struct AppData {
+    pub agent_client_certificate_required: bool,
     pub agent_controller_pool: Arc<AgentControllerPool>,
+    pub agent_join_token: Option<String>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
     pub chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
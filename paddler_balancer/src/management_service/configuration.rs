@@ -1,7 +1,11 @@
 use std::net::SocketAddr;
 
+use crate::tls_configuration::TlsConfiguration;
+
 #[derive(Clone)]
 pub struct Configuration {
     pub addr: SocketAddr,
+    pub agent_join_token: Option<String>,
     pub cors_allowed_hosts: Vec<String>,
+    pub tls_configuration: Option<TlsConfiguration>,
 }
@@ -0,0 +1,34 @@
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use actix_web::delete;
+use actix_web::error::ErrorNotFound;
+use actix_web::web;
+use serde::Deserialize;
+
+use crate::management_service::app_data::AppData;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PathParams {
+    agent_id: String,
+}
+
+#[delete("/api/v1/agent/{agent_id}/cordon")]
+async fn respond(
+    app_data: web::Data<AppData>,
+    params: web::Path<PathParams>,
+) -> Result<impl Responder, Error> {
+    if !app_data
+        .agent_controller_pool
+        .set_agent_cordoned(&params.agent_id, false)
+    {
+        return Err(ErrorNotFound("Agent not found"));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
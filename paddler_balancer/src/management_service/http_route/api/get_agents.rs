@@ -23,12 +23,8 @@ async fn respond(app_data: web::Data<AppData>) -> Result<HttpResponse, Error> {
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
     use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
     use std::time::Duration;
 
     use actix_web::App;
@@ -52,6 +48,7 @@ mod tests {
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::management_service::app_data::AppData;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
     use crate::state_database::memory::Memory;
     use paddler_messaging::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
     use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
@@ -62,28 +59,8 @@ mod tests {
         let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
 
         Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
-            desired_slots_total: AtomicValue::<AtomicI32>::new(0),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: "agent-test".to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
-            slots_total: AtomicValue::<AtomicI32>::new(0),
             state_application_status_code: AtomicValue::<AtomicI32>::new(status_code),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test("agent-test", agent_message_tx)
         })
     }
 
@@ -92,12 +69,15 @@ mod tests {
             broadcast::channel(1);
 
         Data::new(AppData {
+            agent_client_certificate_required: false,
             agent_controller_pool: agent_controller_pool.clone(),
+            agent_join_token: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
                 agent_controller_pool,
                 Duration::from_secs(1),
                 10,
+                RequestObservability::default(),
             )),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
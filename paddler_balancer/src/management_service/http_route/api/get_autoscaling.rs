@@ -0,0 +1,105 @@
+use actix_web::HttpResponse;
+use actix_web::get;
+use actix_web::web;
+
+use crate::management_service::app_data::AppData;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[get("/api/v1/autoscaling")]
+async fn respond(app_data: web::Data<AppData>) -> HttpResponse {
+    HttpResponse::Ok().json(
+        app_data
+            .buffered_request_manager
+            .autoscaling_recommendation(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use actix_web::App;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::read_body_json;
+    use actix_web::web::Data;
+    use tokio::sync::broadcast;
+    use tokio_util::sync::CancellationToken;
+
+    use super::register;
+    use crate::agent_controller_pool::AgentControllerPool;
+    use crate::autoscaling_signal::AutoscalingSignal;
+    use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+    use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
+    use crate::embedding_sender_collection::EmbeddingSenderCollection;
+    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
+    use crate::management_service::app_data::AppData;
+    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
+    use crate::state_database::memory::Memory;
+    use paddler_messaging::autoscaling_recommendation::AutoscalingRecommendation;
+    use paddler_messaging::balancer_desired_state::BalancerDesiredState;
+
+    #[actix_web::test]
+    async fn recommends_agents_for_buffered_requests_without_agents() {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+        let buffered_request_manager = Arc::new(BufferedRequestManager::new(
+            agent_controller_pool.clone(),
+            Duration::from_secs(1),
+            10,
+            RequestObservability {
+                autoscaling_signal: Arc::new(AutoscalingSignal::new(0.5)),
+                ..RequestObservability::default()
+            },
+        ));
+
+        buffered_request_manager
+            .buffered_request_counter
+            .increment();
+        buffered_request_manager
+            .buffered_request_counter
+            .increment();
+
+        let (balancer_desired_state_notify_tx, _balancer_desired_state_notify_rx) =
+            broadcast::channel(1);
+
+        let app_data = Data::new(AppData {
+            agent_client_certificate_required: false,
+            agent_controller_pool,
+            agent_join_token: None,
+            balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
+            buffered_request_manager,
+            chat_template_override_sender_collection: Arc::new(
+                ChatTemplateOverrideSenderCollection::default(),
+            ),
+            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
+            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
+            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
+            shutdown: CancellationToken::new(),
+            state_database: Arc::new(Memory::new(
+                balancer_desired_state_notify_tx,
+                BalancerDesiredState::default(),
+            )),
+            statsd_prefix: "paddler".to_owned(),
+        });
+
+        let app = init_service(App::new().app_data(app_data).configure(register)).await;
+        let request = TestRequest::get().uri("/api/v1/autoscaling").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let recommendation: AutoscalingRecommendation = read_body_json(response).await;
+
+        assert_eq!(recommendation.agents, 0);
+        assert_eq!(recommendation.buffered_requests, 2);
+        assert_eq!(recommendation.recommended_agent_count, 4);
+    }
+}
@@ -21,6 +21,7 @@ async fn respond(app_data: web::Data<AppData>) -> Result<impl Responder, Error>
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -45,6 +46,7 @@ mod tests {
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::management_service::app_data::AppData;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
     use crate::state_database::memory::Memory;
     use paddler_messaging::agent_desired_model::AgentDesiredModel;
     use paddler_messaging::agent_desired_state::AgentDesiredState;
@@ -58,12 +60,15 @@ mod tests {
             broadcast::channel(1);
 
         Data::new(AppData {
+            agent_client_certificate_required: false,
             agent_controller_pool: Arc::new(AgentControllerPool::default()),
+            agent_join_token: None,
             balancer_applicable_state_holder,
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
                 Arc::new(AgentControllerPool::default()),
                 Duration::from_secs(1),
                 10,
+                RequestObservability::default(),
             )),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
@@ -92,6 +97,7 @@ mod tests {
                     model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
                     multimodal_projection: AgentDesiredModel::None,
                 },
+                model_pools: BTreeMap::new(),
             },
         ));
 
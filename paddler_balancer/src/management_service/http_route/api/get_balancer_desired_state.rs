@@ -24,6 +24,7 @@ async fn respond(app_data: web::Data<AppData>) -> Result<impl Responder, Error>
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -47,6 +48,7 @@ mod tests {
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::management_service::app_data::AppData;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
     use crate::state_database::StateDatabase;
     use crate::state_database::file::File;
     use crate::state_database::memory::Memory;
@@ -56,12 +58,15 @@ mod tests {
 
     fn build_app_data(state_database: Arc<dyn StateDatabase>) -> Data<AppData> {
         Data::new(AppData {
+            agent_client_certificate_required: false,
             agent_controller_pool: Arc::new(AgentControllerPool::default()),
+            agent_join_token: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
                 Arc::new(AgentControllerPool::default()),
                 Duration::from_secs(1),
                 10,
+                RequestObservability::default(),
             )),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
@@ -83,6 +88,7 @@ mod tests {
             chat_template_override: None,
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         };
@@ -45,6 +45,7 @@ mod tests {
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::management_service::app_data::AppData;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
     use crate::state_database::memory::Memory;
     use paddler_messaging::balancer_desired_state::BalancerDesiredState;
     use paddler_messaging::buffered_request_manager_snapshot::BufferedRequestManagerSnapshot;
@@ -55,6 +56,7 @@ mod tests {
             Arc::new(AgentControllerPool::default()),
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ));
 
         buffered_request_manager
@@ -68,7 +70,9 @@ mod tests {
             broadcast::channel(1);
 
         let app_data = Data::new(AppData {
+            agent_client_certificate_required: false,
             agent_controller_pool: Arc::new(AgentControllerPool::default()),
+            agent_join_token: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             buffered_request_manager,
             chat_template_override_sender_collection: Arc::new(
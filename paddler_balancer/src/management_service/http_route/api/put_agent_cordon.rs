@@ -0,0 +1,100 @@
+use actix_web::Error;
+use actix_web::HttpResponse;
+use actix_web::Responder;
+use actix_web::error::ErrorNotFound;
+use actix_web::put;
+use actix_web::web;
+use serde::Deserialize;
+
+use crate::management_service::app_data::AppData;
+
+pub fn register(cfg: &mut web::ServiceConfig) {
+    cfg.service(respond);
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PathParams {
+    agent_id: String,
+}
+
+#[put("/api/v1/agent/{agent_id}/cordon")]
+async fn respond(
+    app_data: web::Data<AppData>,
+    params: web::Path<PathParams>,
+) -> Result<impl Responder, Error> {
+    if !app_data
+        .agent_controller_pool
+        .set_agent_cordoned(&params.agent_id, true)
+    {
+        return Err(ErrorNotFound("Agent not found"));
+    }
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use actix_web::App;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::web::Data;
+    use tokio::sync::broadcast;
+    use tokio_util::sync::CancellationToken;
+
+    use super::register;
+    use crate::agent_controller_pool::AgentControllerPool;
+    use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+    use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
+    use crate::embedding_sender_collection::EmbeddingSenderCollection;
+    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
+    use crate::management_service::app_data::AppData;
+    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
+    use crate::state_database::memory::Memory;
+    use paddler_messaging::balancer_desired_state::BalancerDesiredState;
+
+    #[actix_web::test]
+    async fn responds_with_not_found_for_an_unknown_agent() {
+        let (balancer_desired_state_notify_tx, _balancer_desired_state_notify_rx) =
+            broadcast::channel(1);
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+        let app_data = Data::new(AppData {
+            agent_client_certificate_required: false,
+            agent_controller_pool: agent_controller_pool.clone(),
+            agent_join_token: None,
+            balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
+            buffered_request_manager: Arc::new(BufferedRequestManager::new(
+                agent_controller_pool,
+                Duration::from_secs(1),
+                10,
+                RequestObservability::default(),
+            )),
+            chat_template_override_sender_collection: Arc::new(
+                ChatTemplateOverrideSenderCollection::default(),
+            ),
+            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
+            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
+            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
+            shutdown: CancellationToken::new(),
+            state_database: Arc::new(Memory::new(
+                balancer_desired_state_notify_tx,
+                BalancerDesiredState::default(),
+            )),
+            statsd_prefix: "paddler".to_owned(),
+        });
+        let app = init_service(App::new().app_data(app_data).configure(register)).await;
+        let request = TestRequest::put()
+            .uri("/api/v1/agent/missing/cordon")
+            .to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+}
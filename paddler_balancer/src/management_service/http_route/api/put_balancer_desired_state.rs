@@ -59,18 +59,22 @@ mod tests {
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::management_service::app_data::AppData;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
     use crate::state_database::memory::Memory;
     use paddler_messaging::balancer_desired_state::BalancerDesiredState;
     use paddler_messaging::inference_parameters::InferenceParameters;
 
     fn build_app_data(state_database: Arc<Memory>) -> Data<AppData> {
         Data::new(AppData {
+            agent_client_certificate_required: false,
             agent_controller_pool: Arc::new(AgentControllerPool::default()),
+            agent_join_token: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             buffered_request_manager: Arc::new(BufferedRequestManager::new(
                 Arc::new(AgentControllerPool::default()),
                 Duration::from_secs(1),
                 10,
+                RequestObservability::default(),
             )),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
@@ -13,6 +13,7 @@ use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
 pub struct AgentSocketControllerContext {
     pub agent_controller_pool: Arc<AgentControllerPool>,
     pub agent_id: String,
+    pub agent_join_token: Option<String>,
     pub balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     pub chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
     pub embedding_sender_collection: Arc<EmbeddingSenderCollection>,
@@ -35,15 +36,10 @@ impl Drop for AgentSocketControllerContext {
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
     use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
 
     use tokio::sync::mpsc;
-    use tokio_util::sync::CancellationToken;
 
     use super::AgentSocketControllerContext;
     use crate::agent_controller::AgentController;
@@ -53,7 +49,6 @@ mod tests {
     use crate::embedding_sender_collection::EmbeddingSenderCollection;
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
     use paddler_messaging::atomic_value::AtomicValue;
 
     #[test]
@@ -65,34 +60,14 @@ mod tests {
             .register_agent_controller(
                 "agent-under-drop".to_owned(),
                 Arc::new(AgentController {
-                    agent_message_tx,
-                    chat_template_override_sender_collection: Arc::new(
-                        ChatTemplateOverrideSenderCollection::default(),
-                    ),
-                    connection_close: CancellationToken::new(),
-                    desired_slots_total: AtomicValue::<AtomicI32>::new(0),
-                    download_current: AtomicValue::<AtomicU64>::new(0),
-                    download_filename: RwLock::new(None),
-                    download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-                    download_total: AtomicValue::<AtomicU64>::new(0),
-                    embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
                     generate_tokens_sender_collection: Arc::new(
                         GenerateTokensSenderCollection::default(),
                     ),
-                    id: "agent-under-drop".to_owned(),
-                    issues: RwLock::new(BTreeSet::new()),
                     model_metadata_sender_collection: Arc::new(
                         ModelMetadataSenderCollection::default(),
                     ),
-                    model_path: RwLock::new(None),
-                    name: None,
-                    newest_update_version: AtomicValue::<AtomicI32>::new(0),
-                    slots_processing: AtomicValue::<AtomicI32>::new(0),
                     slots_total: AtomicValue::<AtomicI32>::new(1),
-                    state_application_status_code: AtomicValue::<AtomicI32>::new(
-                        AgentStateApplicationStatus::Fresh as i32,
-                    ),
-                    uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+                    ..AgentController::new_for_test("agent-under-drop", agent_message_tx)
                 }),
             )
             .unwrap();
@@ -100,6 +75,7 @@ mod tests {
         let context = AgentSocketControllerContext {
             agent_controller_pool: agent_controller_pool.clone(),
             agent_id: "agent-under-drop".to_owned(),
+            agent_join_token: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
@@ -15,12 +15,15 @@ use actix_web::web::Data;
 use actix_web::web::Path;
 use actix_web::web::Payload;
 use actix_web::web::ServiceConfig;
+use actix_ws::CloseCode;
+use actix_ws::CloseReason;
 use actix_ws::Session;
 use anyhow::Context;
 use anyhow::Result;
 use async_trait::async_trait;
 use log::error;
 use log::info;
+use log::warn;
 use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
 use paddler_messaging::slot_aggregated_status_snapshot::SlotAggregatedStatusSnapshot;
 use serde::Deserialize;
@@ -31,8 +34,10 @@ use self::agent_socket_controller_context::AgentSocketControllerContext;
 use crate::agent_controller::AgentController;
 use crate::agent_controller_pool::AgentControllerPool;
 use crate::agent_controller_update_result::AgentControllerUpdateResult;
+use crate::agent_throughput::AgentThroughput;
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
+use crate::constant_time_eq::constant_time_eq;
 use crate::continuation_decision::ContinuationDecision;
 use crate::continuation_stop_parameters::ContinuationStopParameters;
 use crate::controls_session::ControlsSession as _;
@@ -43,6 +48,7 @@ use crate::management_service::app_data::AppData;
 use crate::manages_senders::ManagesSenders as _;
 use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
 use crate::sets_desired_state::SetsDesiredState as _;
+use crate::verified_client_certificate::VerifiedClientCertificate;
 use crate::websocket_session_controller::WebSocketSessionController;
 use paddler_messaging::atomic_value::AtomicValue;
 use paddler_messaging::management_socket::agent::message::Message as AgentJsonRpcMessage;
@@ -58,9 +64,18 @@ pub fn register(cfg: &mut ServiceConfig) {
     cfg.service(respond);
 }
 
+fn is_join_token_accepted(expected_join_token: Option<&str>, join_token: Option<&str>) -> bool {
+    expected_join_token.is_none_or(|expected_join_token| {
+        join_token.is_some_and(|join_token| {
+            constant_time_eq(expected_join_token.as_bytes(), join_token.as_bytes())
+        })
+    })
+}
+
 struct AgentSocketController {
     agent_controller_pool: Arc<AgentControllerPool>,
     agent_id: String,
+    agent_join_token: Option<String>,
     balancer_applicable_state_holder: Arc<BalancerApplicableStateHolder>,
     chat_template_override_sender_collection: Arc<ChatTemplateOverrideSenderCollection>,
     embedding_sender_collection: Arc<EmbeddingSenderCollection>,
@@ -78,6 +93,7 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
         AgentSocketControllerContext {
             agent_controller_pool: self.agent_controller_pool.clone(),
             agent_id: self.agent_id.clone(),
+            agent_join_token: self.agent_join_token.clone(),
             balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             chat_template_override_sender_collection: self
                 .chat_template_override_sender_collection
@@ -111,10 +127,13 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
             }
             ManagementJsonRpcMessage::Notification(
                 ManagementJsonRpcNotification::RegisterAgent(RegisterAgentParams {
+                    join_token,
+                    model_pool,
                     name,
                     slot_aggregated_status_snapshot:
                         SlotAggregatedStatusSnapshot {
                             desired_slots_total,
+                            desired_state_version,
                             download_current,
                             download_filename,
                             download_indeterminate,
@@ -124,11 +143,60 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
                             slots_processing,
                             slots_total,
                             state_application_status,
+                            state_application_version,
                             uses_chat_template_override,
                             version,
                         },
                 }),
             ) => {
+                if !is_join_token_accepted(
+                    context.agent_join_token.as_deref(),
+                    join_token.as_deref(),
+                ) {
+                    warn!(
+                        "Rejected agent {}: missing or invalid join token",
+                        context.agent_id
+                    );
+
+                    context
+                        .agent_controller_pool
+                        .rejected_agent_registrations
+                        .increment_by(1);
+                    connection_close.cancel();
+
+                    return Ok(ContinuationDecision::Stop(ContinuationStopParameters {
+                        close_reason: Some(CloseReason {
+                            code: CloseCode::Policy,
+                            description: Some("Missing or invalid join token".to_owned()),
+                        }),
+                    }));
+                }
+
+                if context
+                    .balancer_applicable_state_holder
+                    .rejects_model_pool(model_pool.as_deref())
+                {
+                    warn!(
+                        "Rejected agent {}: model pool {model_pool:?} is not configured",
+                        context.agent_id
+                    );
+
+                    context
+                        .agent_controller_pool
+                        .rejected_model_pool_registrations
+                        .increment_by(1);
+                    connection_close.cancel();
+
+                    return Ok(ContinuationDecision::Stop(ContinuationStopParameters {
+                        close_reason: Some(CloseReason {
+                            code: CloseCode::Policy,
+                            description: Some(format!(
+                                "Model pool {model_pool:?} is not configured"
+                            )),
+                        }),
+                    }));
+                }
+
                 let (agent_message_tx, mut agent_message_rx) =
                     mpsc::unbounded_channel::<AgentJsonRpcMessage>();
                 let agent_controller = Arc::new(AgentController {
@@ -137,7 +205,10 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
                         .chat_template_override_sender_collection
                         .clone(),
                     connection_close: connection_close.clone(),
+                    cordoned: AtomicValue::<AtomicBool>::new(false),
                     desired_slots_total: AtomicValue::<AtomicI32>::new(desired_slots_total),
+                    desired_state: RwLock::new(None),
+                    desired_state_version: AtomicValue::<AtomicU64>::new(desired_state_version),
                     download_current: AtomicValue::<AtomicU64>::new(download_current),
                     download_filename: RwLock::new(download_filename),
                     download_indeterminate: AtomicValue::<AtomicBool>::new(download_indeterminate),
@@ -152,13 +223,21 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
                     id: context.agent_id.clone(),
                     issues: RwLock::new(issues),
                     model_path: RwLock::new(model_path),
+                    model_pool: model_pool.clone(),
                     name,
                     newest_update_version: AtomicValue::<AtomicI32>::new(version),
+                    reported_desired_state_version: AtomicValue::<AtomicU64>::new(
+                        desired_state_version,
+                    ),
                     slots_processing: AtomicValue::<AtomicI32>::new(slots_processing),
                     slots_total: AtomicValue::<AtomicI32>::new(slots_total),
                     state_application_status_code: AtomicValue::<AtomicI32>::new(
                         state_application_status as i32,
                     ),
+                    state_application_version: AtomicValue::<AtomicU64>::new(
+                        state_application_version,
+                    ),
+                    throughput: RwLock::new(AgentThroughput::default()),
                     uses_chat_template_override: AtomicValue::<AtomicBool>::new(
                         uses_chat_template_override,
                     ),
@@ -171,7 +250,7 @@ impl ControlsWebSocketEndpoint for AgentSocketController {
 
                 if let Some(desired_state) = context
                     .balancer_applicable_state_holder
-                    .get_agent_desired_state()
+                    .get_model_pool_agent_desired_state(model_pool.as_deref())
                 {
                     agent_controller
                         .set_desired_state(desired_state)
@@ -319,9 +398,26 @@ async fn respond(
     payload: Payload,
     req: HttpRequest,
 ) -> Result<HttpResponse, Error> {
+    if app_data.agent_client_certificate_required
+        && req.conn_data::<VerifiedClientCertificate>().is_none()
+    {
+        warn!(
+            "Rejected agent {}: no client certificate presented",
+            path_params.agent_id
+        );
+
+        app_data
+            .agent_controller_pool
+            .rejected_agent_registrations
+            .increment_by(1);
+
+        return Ok(HttpResponse::Forbidden().finish());
+    }
+
     let agent_socket_controller = AgentSocketController {
         agent_controller_pool: app_data.agent_controller_pool.clone(),
         agent_id: path_params.agent_id.clone(),
+        agent_join_token: app_data.agent_join_token.clone(),
         balancer_applicable_state_holder: app_data.balancer_applicable_state_holder.clone(),
         chat_template_override_sender_collection: app_data
             .chat_template_override_sender_collection
@@ -336,6 +432,7 @@ async fn respond(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::collections::BTreeSet;
     use std::sync::Arc;
 
@@ -351,7 +448,9 @@ mod tests {
     use super::ManagementJsonRpcMessage;
     use super::ManagementJsonRpcNotification;
     use super::RegisterAgentParams;
+    use super::is_join_token_accepted;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::balancer_applicable_state::BalancerApplicableState;
     use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
     use crate::continuation_decision::ContinuationDecision;
@@ -360,7 +459,11 @@ mod tests {
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
     use crate::websocket_session_controller::WebSocketSessionController;
+    use paddler_messaging::agent_desired_model::AgentDesiredModel;
+    use paddler_messaging::agent_desired_state::AgentDesiredState;
     use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
+
+    use paddler_messaging::inference_parameters::InferenceParameters;
     use paddler_messaging::slot_aggregated_status_snapshot::SlotAggregatedStatusSnapshot;
 
     #[actix_web::test]
@@ -372,6 +475,7 @@ mod tests {
         let context = Arc::new(AgentSocketControllerContext {
             agent_controller_pool: agent_controller_pool.clone(),
             agent_id: agent_id.clone(),
+            agent_join_token: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
@@ -397,9 +501,12 @@ mod tests {
             context,
             ManagementJsonRpcMessage::Notification(ManagementJsonRpcNotification::RegisterAgent(
                 RegisterAgentParams {
+                    join_token: None,
+                    model_pool: None,
                     name: None,
                     slot_aggregated_status_snapshot: SlotAggregatedStatusSnapshot {
                         desired_slots_total: 0,
+                        desired_state_version: 0,
                         download_current: 0,
                         download_filename: None,
                         download_indeterminate: false,
@@ -409,6 +516,7 @@ mod tests {
                         slots_processing: 0,
                         slots_total: 1,
                         state_application_status: AgentStateApplicationStatus::Fresh,
+                        state_application_version: 0,
                         uses_chat_template_override: false,
                         version: 0,
                     },
@@ -446,6 +554,7 @@ mod tests {
         let context = Arc::new(AgentSocketControllerContext {
             agent_controller_pool: Arc::new(AgentControllerPool::default()),
             agent_id: "agent-deregister".to_owned(),
+            agent_join_token: None,
             balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
@@ -486,4 +595,177 @@ mod tests {
             ContinuationDecision::Stop(_)
         ));
     }
+
+    #[test]
+    fn join_token_is_only_checked_when_the_balancer_expects_one() {
+        assert!(is_join_token_accepted(None, None));
+        assert!(is_join_token_accepted(None, Some("anything")));
+        assert!(is_join_token_accepted(
+            Some("join-secret"),
+            Some("join-secret")
+        ));
+        assert!(!is_join_token_accepted(Some("join-secret"), Some("guess")));
+        assert!(!is_join_token_accepted(Some("join-secret"), None));
+    }
+
+    #[actix_web::test]
+    async fn register_agent_with_an_invalid_join_token_is_rejected_and_counted() {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+        let context = Arc::new(AgentSocketControllerContext {
+            agent_controller_pool: agent_controller_pool.clone(),
+            agent_id: "agent-rogue".to_owned(),
+            agent_join_token: Some("join-secret".to_owned()),
+            balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
+            chat_template_override_sender_collection: Arc::new(
+                ChatTemplateOverrideSenderCollection::default(),
+            ),
+            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
+            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
+            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
+        });
+
+        let (request, mut raw_payload) = TestRequest::get()
+            .insert_header((header::CONNECTION, "upgrade"))
+            .insert_header((header::UPGRADE, "websocket"))
+            .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+            .insert_header((header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ=="))
+            .to_http_parts();
+        let payload = Payload::from_request(&request, &mut raw_payload)
+            .await
+            .unwrap();
+        let (_response, session, _msg_stream) = actix_ws::handle(&request, payload).unwrap();
+
+        let connection_close = CancellationToken::new();
+
+        let continuation_decision = AgentSocketController::handle_deserialized_message(
+            connection_close.clone(),
+            context,
+            ManagementJsonRpcMessage::Notification(ManagementJsonRpcNotification::RegisterAgent(
+                RegisterAgentParams {
+                    join_token: Some("guess".to_owned()),
+                    model_pool: None,
+                    name: None,
+                    slot_aggregated_status_snapshot: SlotAggregatedStatusSnapshot {
+                        desired_slots_total: 0,
+                        desired_state_version: 0,
+                        download_current: 0,
+                        download_filename: None,
+                        download_indeterminate: false,
+                        download_total: 0,
+                        issues: BTreeSet::new(),
+                        model_path: None,
+                        slots_processing: 0,
+                        slots_total: 1,
+                        state_application_status: AgentStateApplicationStatus::Fresh,
+                        state_application_version: 0,
+                        uses_chat_template_override: false,
+                        version: 0,
+                    },
+                },
+            )),
+            WebSocketSessionController::new(session),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            continuation_decision,
+            ContinuationDecision::Stop(_)
+        ));
+        assert!(connection_close.is_cancelled());
+        assert!(
+            agent_controller_pool
+                .get_agent_controller("agent-rogue")
+                .is_none()
+        );
+        assert_eq!(agent_controller_pool.rejected_agent_registrations.get(), 1);
+    }
+
+    #[actix_web::test]
+    async fn register_agent_in_an_unconfigured_model_pool_is_rejected_and_counted_apart() {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+        let balancer_applicable_state_holder = Arc::new(BalancerApplicableStateHolder::default());
+
+        balancer_applicable_state_holder.set_balancer_applicable_state(Some(
+            BalancerApplicableState {
+                agent_desired_state: AgentDesiredState {
+                    chat_template_override: None,
+                    inference_parameters: InferenceParameters::default(),
+                    model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
+                    multimodal_projection: AgentDesiredModel::None,
+                },
+                model_pools: BTreeMap::new(),
+            },
+        ));
+
+        let context = Arc::new(AgentSocketControllerContext {
+            agent_controller_pool: agent_controller_pool.clone(),
+            agent_id: "agent-misconfigured".to_owned(),
+            agent_join_token: None,
+            balancer_applicable_state_holder,
+            chat_template_override_sender_collection: Arc::new(
+                ChatTemplateOverrideSenderCollection::default(),
+            ),
+            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
+            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
+            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
+        });
+
+        let (request, mut raw_payload) = TestRequest::get()
+            .insert_header((header::CONNECTION, "upgrade"))
+            .insert_header((header::UPGRADE, "websocket"))
+            .insert_header((header::SEC_WEBSOCKET_VERSION, "13"))
+            .insert_header((header::SEC_WEBSOCKET_KEY, "dGhlIHNhbXBsZSBub25jZQ=="))
+            .to_http_parts();
+        let payload = Payload::from_request(&request, &mut raw_payload)
+            .await
+            .unwrap();
+        let (_response, session, _msg_stream) = actix_ws::handle(&request, payload).unwrap();
+
+        let connection_close = CancellationToken::new();
+
+        let continuation_decision = AgentSocketController::handle_deserialized_message(
+            connection_close.clone(),
+            context,
+            ManagementJsonRpcMessage::Notification(ManagementJsonRpcNotification::RegisterAgent(
+                RegisterAgentParams {
+                    join_token: None,
+                    model_pool: Some("embeddings".to_owned()),
+                    name: None,
+                    slot_aggregated_status_snapshot: SlotAggregatedStatusSnapshot {
+                        desired_slots_total: 0,
+                        desired_state_version: 0,
+                        download_current: 0,
+                        download_filename: None,
+                        download_indeterminate: false,
+                        download_total: 0,
+                        issues: BTreeSet::new(),
+                        model_path: None,
+                        slots_processing: 0,
+                        slots_total: 1,
+                        state_application_status: AgentStateApplicationStatus::Fresh,
+                        state_application_version: 0,
+                        uses_chat_template_override: false,
+                        version: 0,
+                    },
+                },
+            )),
+            WebSocketSessionController::new(session),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            continuation_decision,
+            ContinuationDecision::Stop(_)
+        ));
+        assert!(connection_close.is_cancelled());
+        assert_eq!(agent_controller_pool.rejected_agent_registrations.get(), 0);
+        assert_eq!(
+            agent_controller_pool
+                .rejected_model_pool_registrations
+                .get(),
+            1
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use std::error::Error;
+use std::fmt::Write as _;
 
 use actix_web::HttpResponse;
 use actix_web::Responder;
@@ -6,27 +7,242 @@ use actix_web::get;
 use actix_web::web::Data;
 use actix_web::web::ServiceConfig;
 use indoc::formatdoc;
+use paddler_messaging::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
+use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+use paddler_messaging::produces_snapshot::ProducesSnapshot;
 
 use crate::agent_controller_pool_total_slots::AgentControllerPoolTotalSlots;
+use crate::latency_histogram::LATENCY_BUCKET_BOUNDS;
+use crate::latency_histogram::LatencyHistogram;
 use crate::management_service::app_data::AppData;
+use crate::request_metrics::RequestMetrics;
+
+type AgentGauge = (
+    &'static str,
+    &'static str,
+    fn(&AgentControllerSnapshot) -> String,
+);
+
+const AGENT_GAUGES: [AgentGauge; 5] = [
+    (
+        "agent_slots_processing",
+        "Number of processing slots of the agent",
+        |agent| agent.slots_processing.to_string(),
+    ),
+    (
+        "agent_slots_total",
+        "Number of total slots of the agent",
+        |agent| agent.slots_total.to_string(),
+    ),
+    (
+        "agent_issues",
+        "Number of issues reported by the agent",
+        |agent| agent.issues.len().to_string(),
+    ),
+    (
+        "agent_download_current",
+        "Bytes of the model file downloaded by the agent so far",
+        |agent| agent.download_current.to_string(),
+    ),
+    (
+        "agent_download_total",
+        "Size in bytes of the model file the agent is downloading",
+        |agent| agent.download_total.to_string(),
+    ),
+];
 
 pub fn register(cfg: &mut ServiceConfig) {
     cfg.service(respond);
 }
 
+fn agent_labels(agent: &AgentControllerSnapshot) -> String {
+    format!(
+        "agent_id=\"{}\",agent_name=\"{}\"",
+        escape_label_value(&agent.id),
+        escape_label_value(agent.name.as_deref().unwrap_or_default())
+    )
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_agent_gauges(
+    metrics_response: &mut String,
+    statsd_prefix: &str,
+    agents: &[AgentControllerSnapshot],
+) -> Result<(), Box<dyn Error>> {
+    for (name, help, value) in AGENT_GAUGES {
+        writeln!(metrics_response, "# HELP {statsd_prefix}{name} {help}")?;
+        writeln!(metrics_response, "# TYPE {statsd_prefix}{name} gauge")?;
+
+        for agent in agents {
+            writeln!(
+                metrics_response,
+                "{statsd_prefix}{name}{{{}}} {}",
+                agent_labels(agent),
+                value(agent)
+            )?;
+        }
+
+        writeln!(metrics_response)?;
+    }
+
+    writeln!(
+        metrics_response,
+        "# HELP {statsd_prefix}agent_state_application_status Whether the agent applied its desired state, by status"
+    )?;
+    writeln!(
+        metrics_response,
+        "# TYPE {statsd_prefix}agent_state_application_status gauge"
+    )?;
+
+    for agent in agents {
+        writeln!(
+            metrics_response,
+            "{statsd_prefix}agent_state_application_status{{{},status=\"{:?}\"}} 1",
+            agent_labels(agent),
+            agent.state_application_status
+        )?;
+    }
+
+    writeln!(metrics_response)?;
+
+    Ok(())
+}
+
+fn write_histogram(
+    metrics_response: &mut String,
+    statsd_prefix: &str,
+    name: &str,
+    help: &str,
+    histogram: &LatencyHistogram,
+) -> Result<(), Box<dyn Error>> {
+    let count = histogram.count();
+
+    writeln!(metrics_response, "# HELP {statsd_prefix}{name} {help}")?;
+    writeln!(metrics_response, "# TYPE {statsd_prefix}{name} histogram")?;
+
+    for (bound, cumulative_count) in LATENCY_BUCKET_BOUNDS
+        .iter()
+        .zip(histogram.cumulative_counts())
+    {
+        writeln!(
+            metrics_response,
+            "{statsd_prefix}{name}_bucket{{le=\"{bound}\"}} {cumulative_count}"
+        )?;
+    }
+
+    writeln!(
+        metrics_response,
+        "{statsd_prefix}{name}_bucket{{le=\"+Inf\"}} {count}"
+    )?;
+    writeln!(
+        metrics_response,
+        "{statsd_prefix}{name}_sum {}",
+        histogram.sum().as_secs_f64()
+    )?;
+    writeln!(metrics_response, "{statsd_prefix}{name}_count {count}")?;
+    writeln!(metrics_response)?;
+
+    Ok(())
+}
+
+fn write_request_metrics(
+    metrics_response: &mut String,
+    statsd_prefix: &str,
+    request_metrics: &RequestMetrics,
+) -> Result<(), Box<dyn Error>> {
+    writeln!(
+        metrics_response,
+        "# HELP {statsd_prefix}requests_finished Number of finished inference requests, by endpoint and outcome"
+    )?;
+    writeln!(
+        metrics_response,
+        "# TYPE {statsd_prefix}requests_finished counter"
+    )?;
+
+    for (endpoint, outcome, count) in request_metrics.requests_by_endpoint_and_outcome() {
+        writeln!(
+            metrics_response,
+            "{statsd_prefix}requests_finished{{endpoint=\"{}\",outcome=\"{}\"}} {count}",
+            escape_label_value(&endpoint),
+            outcome.label()
+        )?;
+    }
+
+    writeln!(metrics_response)?;
+
+    write_histogram(
+        metrics_response,
+        statsd_prefix,
+        "queue_wait_seconds",
+        "Time requests waited for a free slot before being dispatched to an agent",
+        &request_metrics.queue_wait,
+    )?;
+    write_histogram(
+        metrics_response,
+        statsd_prefix,
+        "time_to_first_token_seconds",
+        "Time from dispatching a request to an agent until its first response",
+        &request_metrics.time_to_first_token,
+    )?;
+    write_histogram(
+        metrics_response,
+        statsd_prefix,
+        "inter_token_latency_seconds",
+        "Time between consecutive responses streamed by an agent",
+        &request_metrics.inter_token_latency,
+    )?;
+    write_histogram(
+        metrics_response,
+        statsd_prefix,
+        "request_duration_seconds",
+        "Time from receiving a request until it finished",
+        &request_metrics.request_duration,
+    )?;
+
+    Ok(())
+}
+
 #[get("/metrics")]
 async fn respond(app_data: Data<AppData>) -> Result<impl Responder, Box<dyn Error>> {
     let AgentControllerPoolTotalSlots {
         slots_processing,
         slots_total,
     } = app_data.agent_controller_pool.total_slots();
+    let AgentControllerPoolSnapshot { agents } = app_data.agent_controller_pool.make_snapshot()?;
     let buffered_requests_count = app_data
         .buffered_request_manager
         .buffered_request_counter
         .get();
+    let agents_rejected = app_data
+        .agent_controller_pool
+        .rejected_agent_registrations
+        .get();
+    let agents_rejected_model_pool = app_data
+        .agent_controller_pool
+        .rejected_model_pool_registrations
+        .get();
+    let dispatch_affinity_hits = app_data.agent_controller_pool.session_affinity_hits.get();
+    let dispatch_affinity_misses = app_data.agent_controller_pool.session_affinity_misses.get();
+    let request_metrics = &app_data
+        .buffered_request_manager
+        .observability
+        .request_metrics;
+    let audit_log_entries_dropped = app_data
+        .buffered_request_manager
+        .observability
+        .audit_log
+        .dropped_entries();
+    let completion_tokens = request_metrics.completion_tokens.get();
+    let prompt_tokens = request_metrics.prompt_tokens.get();
     let statsd_prefix = app_data.statsd_prefix.clone();
 
-    let metrics_response = formatdoc! {"
+    let mut metrics_response = formatdoc! {"
         # HELP {statsd_prefix}slots_processing Number of processing slots
         # TYPE {statsd_prefix}slots_processing gauge
         {statsd_prefix}slots_processing {slots_processing}
@@ -38,9 +254,173 @@ async fn respond(app_data: Data<AppData>) -> Result<impl Responder, Box<dyn Erro
         # HELP {statsd_prefix}requests_buffered Number of buffered requests
         # TYPE {statsd_prefix}requests_buffered gauge
         {statsd_prefix}requests_buffered {buffered_requests_count}
+
+        # HELP {statsd_prefix}agents_rejected Number of agent registrations rejected for an invalid join token or a missing client certificate
+        # TYPE {statsd_prefix}agents_rejected counter
+        {statsd_prefix}agents_rejected {agents_rejected}
+
+        # HELP {statsd_prefix}agents_rejected_model_pool Number of agent registrations rejected for joining a model pool that is not configured
+        # TYPE {statsd_prefix}agents_rejected_model_pool counter
+        {statsd_prefix}agents_rejected_model_pool {agents_rejected_model_pool}
+
+        # HELP {statsd_prefix}dispatch_affinity_hits Number of requests dispatched to the agent preferred by session affinity
+        # TYPE {statsd_prefix}dispatch_affinity_hits counter
+        {statsd_prefix}dispatch_affinity_hits {dispatch_affinity_hits}
+
+        # HELP {statsd_prefix}dispatch_affinity_misses Number of requests with an affinity key dispatched to another agent
+        # TYPE {statsd_prefix}dispatch_affinity_misses counter
+        {statsd_prefix}dispatch_affinity_misses {dispatch_affinity_misses}
+
+        # HELP {statsd_prefix}prompt_tokens Number of prompt tokens processed by agents
+        # TYPE {statsd_prefix}prompt_tokens counter
+        {statsd_prefix}prompt_tokens {prompt_tokens}
+
+        # HELP {statsd_prefix}completion_tokens Number of tokens generated by agents
+        # TYPE {statsd_prefix}completion_tokens counter
+        {statsd_prefix}completion_tokens {completion_tokens}
+
+        # HELP {statsd_prefix}audit_log_entries_dropped Number of audit log entries lost because the audit log fell behind or stopped
+        # TYPE {statsd_prefix}audit_log_entries_dropped counter
+        {statsd_prefix}audit_log_entries_dropped {audit_log_entries_dropped}
+
     "};
 
+    write_agent_gauges(&mut metrics_response, &statsd_prefix, &agents)?;
+    write_request_metrics(&mut metrics_response, &statsd_prefix, request_metrics)?;
+
     Ok(HttpResponse::Ok()
         .content_type("text/plain; version=0.0.4; charset=utf-8; escaping=values")
         .body(metrics_response))
 }
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::sync::atomic::AtomicI32;
+    use std::time::Duration;
+
+    use actix_web::App;
+    use actix_web::http::StatusCode;
+    use actix_web::test::TestRequest;
+    use actix_web::test::call_service;
+    use actix_web::test::init_service;
+    use actix_web::test::read_body;
+    use tokio::sync::broadcast;
+    use tokio::sync::mpsc;
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::agent_controller::AgentController;
+    use crate::agent_controller_pool::AgentControllerPool;
+    use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+    use crate::buffered_request_manager::BufferedRequestManager;
+    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
+    use crate::embedding_sender_collection::EmbeddingSenderCollection;
+    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
+    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
+    use crate::request_observability::RequestObservability;
+    use crate::request_outcome::RequestOutcome;
+    use crate::state_database::memory::Memory;
+    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
+    use paddler_messaging::atomic_value::AtomicValue;
+    use paddler_messaging::balancer_desired_state::BalancerDesiredState;
+
+    fn agent_controller_with_slots(
+        slots_processing: i32,
+        slots_total: i32,
+    ) -> Arc<AgentController> {
+        let (agent_message_tx, _agent_message_rx) = mpsc::unbounded_channel();
+
+        Arc::new(AgentController {
+            desired_slots_total: AtomicValue::<AtomicI32>::new(slots_total),
+            name: Some("gpu \"one\"".to_owned()),
+            slots_processing: AtomicValue::<AtomicI32>::new(slots_processing),
+            slots_total: AtomicValue::<AtomicI32>::new(slots_total),
+            state_application_status_code: AtomicValue::<AtomicI32>::new(
+                AgentStateApplicationStatus::Applied as i32,
+            ),
+            ..AgentController::new_for_test("agent-test", agent_message_tx)
+        })
+    }
+
+    fn app_data_with_pool(
+        agent_controller_pool: Arc<AgentControllerPool>,
+        buffered_request_manager: Arc<BufferedRequestManager>,
+    ) -> Data<AppData> {
+        let (balancer_desired_state_notify_tx, _balancer_desired_state_notify_rx) =
+            broadcast::channel(1);
+
+        Data::new(AppData {
+            agent_client_certificate_required: false,
+            agent_controller_pool,
+            agent_join_token: None,
+            balancer_applicable_state_holder: Arc::new(BalancerApplicableStateHolder::default()),
+            buffered_request_manager,
+            chat_template_override_sender_collection: Arc::new(
+                ChatTemplateOverrideSenderCollection::default(),
+            ),
+            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
+            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
+            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
+            shutdown: CancellationToken::new(),
+            state_database: Arc::new(Memory::new(
+                balancer_desired_state_notify_tx,
+                BalancerDesiredState::default(),
+            )),
+            statsd_prefix: "paddler_".to_owned(),
+        })
+    }
+
+    #[actix_web::test]
+    async fn exposes_per_agent_gauges_and_request_histograms() {
+        let agent_controller_pool = Arc::new(AgentControllerPool::default());
+        let buffered_request_manager = Arc::new(BufferedRequestManager::new(
+            agent_controller_pool.clone(),
+            Duration::from_secs(1),
+            10,
+            RequestObservability::default(),
+        ));
+
+        agent_controller_pool
+            .register_agent_controller("agent-test".to_owned(), agent_controller_with_slots(2, 4))
+            .unwrap();
+        buffered_request_manager
+            .observability
+            .request_metrics
+            .record_finished_request(
+                Duration::from_millis(300),
+                "/v1/chat/completions",
+                RequestOutcome::Succeeded,
+            );
+
+        let app = init_service(
+            App::new()
+                .app_data(app_data_with_pool(
+                    agent_controller_pool,
+                    buffered_request_manager,
+                ))
+                .configure(register),
+        )
+        .await;
+        let request = TestRequest::get().uri("/metrics").to_request();
+        let response = call_service(&app, request).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = String::from_utf8(read_body(response).await.to_vec()).unwrap();
+
+        assert!(body.contains(
+            "paddler_agent_slots_processing{agent_id=\"agent-test\",agent_name=\"gpu \\\"one\\\"\"} 2\n"
+        ));
+        assert!(body.contains(
+            "paddler_agent_state_application_status{agent_id=\"agent-test\",agent_name=\"gpu \\\"one\\\"\",status=\"Applied\"} 1\n"
+        ));
+        assert!(body.contains(
+            "paddler_requests_finished{endpoint=\"/v1/chat/completions\",outcome=\"succeeded\"} 1\n"
+        ));
+        assert!(body.contains("paddler_request_duration_seconds_bucket{le=\"0.25\"} 0\n"));
+        assert!(body.contains("paddler_request_duration_seconds_bucket{le=\"0.5\"} 1\n"));
+        assert!(body.contains("paddler_request_duration_seconds_count 1\n"));
+        assert!(body.contains("paddler_audit_log_entries_dropped 0\n"));
+    }
+}
@@ -33,7 +33,15 @@ fn collect_web_admin_panel_cors_allowed_hosts(
     web_admin_panel_service_configuration: Option<&WebAdminPanelServiceConfiguration>,
 ) -> Vec<String> {
     web_admin_panel_service_configuration
-        .map(|web_admin_panel_config| format!("http://{}", web_admin_panel_config.addr))
+        .map(|web_admin_panel_config| {
+            let scheme = if web_admin_panel_config.tls_configuration.is_some() {
+                "https"
+            } else {
+                "http"
+            };
+
+            format!("{scheme}://{}", web_admin_panel_config.addr)
+        })
         .into_iter()
         .collect()
 }
@@ -84,7 +92,13 @@ impl Service for ManagementService {
         );
 
         let app_data = Data::new(AppData {
+            agent_client_certificate_required: self
+                .configuration
+                .tls_configuration
+                .as_ref()
+                .is_some_and(|tls_configuration| tls_configuration.client_ca_path.is_some()),
             agent_controller_pool: self.agent_controller_pool.clone(),
+            agent_join_token: self.configuration.agent_join_token.clone(),
             balancer_applicable_state_holder: self.balancer_applicable_state_holder.clone(),
             buffered_request_manager: self.buffered_request_manager.clone(),
             chat_template_override_sender_collection: self
@@ -106,20 +120,24 @@ impl Service for ManagementService {
                         .wrap(create_cors_middleware(&cors_allowed_hosts_arc))
                         .app_data(app_data.clone())
                         .configure(common_http_route::get_health::register)
+                        .configure(http_route::api::delete_agent_cordon::register)
                         .configure(http_route::api::get_agents::register)
                         .configure(http_route::api::get_agents_stream::register)
+                        .configure(http_route::api::get_autoscaling::register)
                         .configure(http_route::api::get_balancer_applicable_state::register)
                         .configure(http_route::api::get_balancer_desired_state::register)
                         .configure(http_route::api::get_buffered_requests::register)
                         .configure(http_route::api::get_buffered_requests_stream::register)
                         .configure(http_route::api::get_chat_template_override::register)
                         .configure(http_route::api::get_model_metadata::register)
+                        .configure(http_route::api::put_agent_cordon::register)
                         .configure(http_route::api::put_balancer_desired_state::register)
                         .configure(http_route::api::ws_agent_socket::register)
                         .configure(http_route::get_metrics::register)
                 },
                 bind_addr: self.configuration.addr,
                 service_name,
+                tls_configuration: self.configuration.tls_configuration.clone(),
                 worker_count: 2,
             },
         )
@@ -131,6 +149,7 @@ impl Service for ManagementService {
 mod tests {
     use std::net::SocketAddr;
     use std::net::TcpListener;
+    use std::path::PathBuf;
     use std::sync::Arc;
     use std::time::Duration;
 
@@ -149,6 +168,7 @@ mod tests {
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
     use crate::resolved_socket_addr::ResolvedSocketAddr;
     use crate::state_database::memory::Memory;
+    use crate::tls_configuration::TlsConfiguration;
     use crate::web_admin_panel_service::template_data::TemplateData;
     use paddler_messaging::balancer_desired_state::BalancerDesiredState;
 
@@ -168,13 +188,16 @@ mod tests {
                 agent_controller_pool,
                 Duration::from_secs(30),
                 32,
+                RequestObservability::default(),
             )),
             chat_template_override_sender_collection: Arc::new(
                 ChatTemplateOverrideSenderCollection::default(),
             ),
             configuration: ManagementServiceConfiguration {
                 addr,
+                agent_join_token: None,
                 cors_allowed_hosts: vec!["http://127.0.0.1:8080".to_owned()],
+                tls_configuration: None,
             },
             embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
             generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
@@ -210,6 +233,7 @@ mod tests {
                 statsd_prefix: "paddler".to_owned(),
                 statsd_reporting_interval: Duration::from_secs(1),
             },
+            tls_configuration: None,
         })
     }
 
@@ -224,6 +248,24 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn builds_https_origin_when_web_admin_panel_serves_tls() -> Result<()> {
+        let configuration = WebAdminPanelServiceConfiguration {
+            tls_configuration: Some(TlsConfiguration {
+                certificate_chain_path: PathBuf::from("certificate.pem"),
+                client_ca_path: None,
+                private_key_path: PathBuf::from("private_key.pem"),
+            }),
+            ..make_web_admin_panel_configuration("127.0.0.1:9000".parse()?)?
+        };
+
+        let allowed_hosts = collect_web_admin_panel_cors_allowed_hosts(Some(&configuration));
+
+        assert_eq!(allowed_hosts, vec!["https://127.0.0.1:9000".to_owned()]);
+
+        Ok(())
+    }
+
     #[test]
     fn yields_no_hosts_when_web_admin_panel_is_absent() {
         let allowed_hosts = collect_web_admin_panel_cors_allowed_hosts(None);
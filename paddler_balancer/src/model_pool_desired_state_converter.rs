@@ -0,0 +1,32 @@
+use paddler_messaging::agent_desired_state::AgentDesiredState;
+use paddler_messaging::model_pool_desired_state::ModelPoolDesiredState;
+use paddler_state_conversion::converts_to_desired_state::ConvertsToDesiredState;
+
+pub struct ModelPoolDesiredStateConverter;
+
+impl ConvertsToDesiredState for ModelPoolDesiredStateConverter {
+    type DesiredState = AgentDesiredState;
+    type Source = ModelPoolDesiredState;
+
+    fn to_desired_state(
+        &self,
+        ModelPoolDesiredState {
+            chat_template_override,
+            inference_parameters,
+            model,
+            multimodal_projection,
+            use_chat_template_override,
+        }: ModelPoolDesiredState,
+    ) -> AgentDesiredState {
+        AgentDesiredState {
+            chat_template_override: if use_chat_template_override {
+                chat_template_override
+            } else {
+                None
+            },
+            inference_parameters,
+            model,
+            multimodal_projection,
+        }
+    }
+}
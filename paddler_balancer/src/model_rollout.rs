@@ -0,0 +1,449 @@
+use std::sync::Arc;
+
+use anyhow::Result;
+use log::error;
+use log::info;
+use log::warn;
+use paddler_messaging::agent_desired_state::AgentDesiredState;
+use paddler_messaging::balancer_desired_state::BalancerDesiredState;
+
+use crate::agent_controller::AgentController;
+use crate::agent_controller_pool::AgentControllerPool;
+use crate::balancer_applicable_state::BalancerApplicableState;
+use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+use crate::model_rollout_progress::ModelRolloutProgress;
+use crate::rolled_out_agent::RolledOutAgent;
+use crate::rollout_batch_size::RolloutBatchSize;
+use crate::rollout_failure_action::RolloutFailureAction;
+use crate::sets_desired_state::SetsDesiredState as _;
+use crate::state_database::StateDatabase;
+
+pub struct ModelRollout {
+    batch_size: RolloutBatchSize,
+    current_batch: Vec<RolledOutAgent>,
+    failure_action: RolloutFailureAction,
+    previous_balancer_desired_state: BalancerDesiredState,
+    rolled_out: Vec<RolledOutAgent>,
+    target_applicable_state: BalancerApplicableState,
+}
+
+impl ModelRollout {
+    #[must_use]
+    pub const fn new(
+        batch_size: RolloutBatchSize,
+        failure_action: RolloutFailureAction,
+        previous_balancer_desired_state: BalancerDesiredState,
+        target_applicable_state: BalancerApplicableState,
+    ) -> Self {
+        Self {
+            batch_size,
+            current_batch: Vec::new(),
+            failure_action,
+            previous_balancer_desired_state,
+            rolled_out: Vec::new(),
+            target_applicable_state,
+        }
+    }
+
+    /// The applicable state holder keeps the previous state until the rollout completes, so
+    /// only the agents picked by the rollout receive the new one.
+    pub async fn advance(
+        &mut self,
+        agent_controller_pool: &AgentControllerPool,
+        balancer_applicable_state_holder: &BalancerApplicableStateHolder,
+        state_database: &dyn StateDatabase,
+    ) -> Result<ModelRolloutProgress> {
+        self.current_batch.retain(|rolled_out_agent| {
+            !rolled_out_agent
+                .agent_controller
+                .connection_close
+                .is_cancelled()
+        });
+
+        if let Some(failed_agent) = self.current_batch.iter().find(|rolled_out_agent| {
+            rolled_out_agent
+                .agent_controller
+                .has_failed_to_apply_desired_state()
+        }) {
+            error!(
+                "Agent {:?} failed to apply the new desired state (issues: {:?}), halting the rollout",
+                failed_agent.agent_controller.id,
+                failed_agent.agent_controller.get_issues()
+            );
+
+            if matches!(self.failure_action, RolloutFailureAction::RollBack) {
+                self.roll_back(state_database).await;
+            }
+
+            return Ok(ModelRolloutProgress::Halted);
+        }
+
+        if !self.current_batch.iter().all(|rolled_out_agent| {
+            rolled_out_agent
+                .agent_controller
+                .has_applied_desired_state()
+        }) {
+            return Ok(ModelRolloutProgress::InProgress);
+        }
+
+        self.rolled_out.append(&mut self.current_batch);
+
+        let next_batch = self.next_batch(agent_controller_pool);
+
+        if next_batch.is_empty() {
+            info!(
+                "Rolled out the new desired state to {} agents",
+                self.rolled_out.len()
+            );
+
+            balancer_applicable_state_holder
+                .set_balancer_applicable_state(Some(self.target_applicable_state.clone()));
+
+            return Ok(ModelRolloutProgress::Completed);
+        }
+
+        for (agent_controller, desired_state) in next_batch {
+            let previous_desired_state = agent_controller.get_desired_state();
+
+            if let Err(err) = agent_controller.set_desired_state(desired_state).await {
+                warn!(
+                    "Unable to send the new desired state to agent {:?}: {err}",
+                    agent_controller.id
+                );
+
+                continue;
+            }
+
+            self.current_batch.push(RolledOutAgent {
+                agent_controller,
+                previous_desired_state,
+            });
+        }
+
+        Ok(ModelRolloutProgress::InProgress)
+    }
+
+    fn next_batch(
+        &self,
+        agent_controller_pool: &AgentControllerPool,
+    ) -> Vec<(Arc<AgentController>, AgentDesiredState)> {
+        let agents_per_batch = self
+            .batch_size
+            .agents_per_batch(agent_controller_pool.agents.len());
+
+        agent_controller_pool
+            .agents
+            .iter()
+            .filter_map(|entry| {
+                let agent_controller = entry.value();
+                let desired_state = self
+                    .target_applicable_state
+                    .agent_desired_state_for_model_pool(agent_controller.model_pool.as_deref())?;
+
+                (!agent_controller.connection_close.is_cancelled()
+                    && agent_controller.get_desired_state().as_ref() != Some(desired_state))
+                .then(|| (agent_controller.clone(), desired_state.clone()))
+            })
+            .take(agents_per_batch)
+            .collect()
+    }
+
+    async fn roll_back(&mut self, state_database: &dyn StateDatabase) {
+        for RolledOutAgent {
+            agent_controller,
+            previous_desired_state,
+        } in self
+            .rolled_out
+            .drain(..)
+            .chain(self.current_batch.drain(..))
+        {
+            let Some(previous_desired_state) = previous_desired_state else {
+                continue;
+            };
+
+            if let Err(err) = agent_controller
+                .set_desired_state(previous_desired_state)
+                .await
+            {
+                warn!(
+                    "Unable to roll back the desired state of agent {:?}: {err}",
+                    agent_controller.id
+                );
+            }
+        }
+
+        if let Err(err) = state_database
+            .store_balancer_desired_state(&self.previous_balancer_desired_state)
+            .await
+        {
+            error!("Unable to restore the previous balancer desired state: {err}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parking_lot::RwLock;
+    use std::collections::BTreeMap;
+    use std::collections::BTreeSet;
+    use std::sync::atomic::AtomicI32;
+
+    use paddler_messaging::agent_desired_model::AgentDesiredModel;
+    use paddler_messaging::agent_issue::AgentIssue;
+    use paddler_messaging::agent_issue_params::model_path::ModelPath;
+    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
+    use paddler_messaging::atomic_value::AtomicValue;
+    use paddler_messaging::management_socket::agent::message::Message as AgentJsonRpcMessage;
+    use tokio::sync::broadcast;
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::state_database::memory::Memory;
+
+    struct RegisteredAgent {
+        agent_controller: Arc<AgentController>,
+        _agent_message_rx: mpsc::UnboundedReceiver<AgentJsonRpcMessage>,
+    }
+
+    fn applicable_state(model: &str) -> BalancerApplicableState {
+        BalancerApplicableState {
+            agent_desired_state: desired_state(model),
+            model_pools: BTreeMap::new(),
+        }
+    }
+
+    fn balancer_desired_state(model: &str) -> BalancerDesiredState {
+        BalancerDesiredState {
+            model: AgentDesiredModel::LocalToAgent(model.to_owned()),
+            ..BalancerDesiredState::default()
+        }
+    }
+
+    fn desired_state(model: &str) -> AgentDesiredState {
+        AgentDesiredState {
+            model: AgentDesiredModel::LocalToAgent(model.to_owned()),
+            ..AgentDesiredState::default()
+        }
+    }
+
+    fn register_agent(
+        agent_controller_pool: &AgentControllerPool,
+        id: &str,
+        model: &str,
+    ) -> RegisteredAgent {
+        let (agent_message_tx, agent_message_rx) = mpsc::unbounded_channel();
+
+        let agent_controller = Arc::new(AgentController {
+            desired_slots_total: AtomicValue::<AtomicI32>::new(1),
+            desired_state: RwLock::new(Some(desired_state(model))),
+            slots_total: AtomicValue::<AtomicI32>::new(1),
+            state_application_status_code: AtomicValue::<AtomicI32>::new(
+                AgentStateApplicationStatus::Applied as i32,
+            ),
+            ..AgentController::new_for_test(id, agent_message_tx)
+        });
+
+        agent_controller_pool
+            .register_agent_controller(id.to_owned(), agent_controller.clone())
+            .unwrap();
+
+        RegisteredAgent {
+            agent_controller,
+            _agent_message_rx: agent_message_rx,
+        }
+    }
+
+    fn report_status(agent_controller: &AgentController, status: AgentStateApplicationStatus) {
+        let desired_state_version = agent_controller.desired_state_version.get();
+
+        agent_controller
+            .reported_desired_state_version
+            .set(desired_state_version);
+        agent_controller
+            .state_application_version
+            .set(desired_state_version);
+        agent_controller
+            .state_application_status_code
+            .set(status as i32);
+    }
+
+    fn agents_on_model(agents: &[RegisteredAgent], model: &str) -> usize {
+        agents
+            .iter()
+            .filter(|agent| {
+                agent.agent_controller.get_desired_state() == Some(desired_state(model))
+            })
+            .count()
+    }
+
+    #[tokio::test]
+    async fn moves_to_the_next_batch_only_after_the_current_one_applied_the_state() {
+        let agent_controller_pool = AgentControllerPool::default();
+        let balancer_applicable_state_holder = BalancerApplicableStateHolder::default();
+        let (balancer_desired_state_tx, _balancer_desired_state_rx) = broadcast::channel(1);
+        let state_database = Memory::new(
+            balancer_desired_state_tx,
+            balancer_desired_state("new.gguf"),
+        );
+
+        balancer_applicable_state_holder
+            .set_balancer_applicable_state(Some(applicable_state("old.gguf")));
+
+        let agents = vec![
+            register_agent(&agent_controller_pool, "agent-1", "old.gguf"),
+            register_agent(&agent_controller_pool, "agent-2", "old.gguf"),
+        ];
+        let mut model_rollout = ModelRollout::new(
+            RolloutBatchSize::Agents(1),
+            RolloutFailureAction::RollBack,
+            balancer_desired_state("old.gguf"),
+            applicable_state("new.gguf"),
+        );
+
+        assert_eq!(
+            model_rollout
+                .advance(
+                    &agent_controller_pool,
+                    &balancer_applicable_state_holder,
+                    &state_database,
+                )
+                .await
+                .unwrap(),
+            ModelRolloutProgress::InProgress
+        );
+        assert_eq!(agents_on_model(&agents, "new.gguf"), 1);
+
+        model_rollout
+            .advance(
+                &agent_controller_pool,
+                &balancer_applicable_state_holder,
+                &state_database,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            agents_on_model(&agents, "new.gguf"),
+            1,
+            "the next batch must wait until the agent reports that it applied the state"
+        );
+
+        for agent in &agents {
+            report_status(
+                &agent.agent_controller,
+                AgentStateApplicationStatus::Applied,
+            );
+        }
+
+        model_rollout
+            .advance(
+                &agent_controller_pool,
+                &balancer_applicable_state_holder,
+                &state_database,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(agents_on_model(&agents, "new.gguf"), 2);
+        assert_eq!(
+            balancer_applicable_state_holder.get_agent_desired_state(),
+            Some(desired_state("old.gguf")),
+            "agents joining mid-rollout must still receive the previous state"
+        );
+
+        for agent in &agents {
+            report_status(
+                &agent.agent_controller,
+                AgentStateApplicationStatus::Applied,
+            );
+        }
+
+        assert_eq!(
+            model_rollout
+                .advance(
+                    &agent_controller_pool,
+                    &balancer_applicable_state_holder,
+                    &state_database,
+                )
+                .await
+                .unwrap(),
+            ModelRolloutProgress::Completed
+        );
+        assert_eq!(
+            balancer_applicable_state_holder.get_agent_desired_state(),
+            Some(desired_state("new.gguf"))
+        );
+    }
+
+    #[tokio::test]
+    async fn rolls_back_when_an_agent_cannot_load_the_new_model() {
+        let agent_controller_pool = AgentControllerPool::default();
+        let balancer_applicable_state_holder = BalancerApplicableStateHolder::default();
+        let (balancer_desired_state_tx, _balancer_desired_state_rx) = broadcast::channel(1);
+        let state_database = Memory::new(
+            balancer_desired_state_tx,
+            balancer_desired_state("new.gguf"),
+        );
+
+        balancer_applicable_state_holder
+            .set_balancer_applicable_state(Some(applicable_state("old.gguf")));
+
+        let agents = vec![
+            register_agent(&agent_controller_pool, "agent-1", "old.gguf"),
+            register_agent(&agent_controller_pool, "agent-2", "old.gguf"),
+        ];
+        let mut model_rollout = ModelRollout::new(
+            RolloutBatchSize::Agents(1),
+            RolloutFailureAction::RollBack,
+            balancer_desired_state("old.gguf"),
+            applicable_state("new.gguf"),
+        );
+
+        model_rollout
+            .advance(
+                &agent_controller_pool,
+                &balancer_applicable_state_holder,
+                &state_database,
+            )
+            .await
+            .unwrap();
+
+        let updated_agent = agents
+            .iter()
+            .find(|agent| {
+                agent.agent_controller.get_desired_state() == Some(desired_state("new.gguf"))
+            })
+            .unwrap();
+
+        updated_agent.agent_controller.set_issues(BTreeSet::from([
+            AgentIssue::ModelCannotBeLoaded(ModelPath {
+                model_path: "new.gguf".to_owned(),
+            }),
+        ]));
+        report_status(
+            &updated_agent.agent_controller,
+            AgentStateApplicationStatus::AttemptedAndNotAppliable,
+        );
+
+        assert_eq!(
+            model_rollout
+                .advance(
+                    &agent_controller_pool,
+                    &balancer_applicable_state_holder,
+                    &state_database,
+                )
+                .await
+                .unwrap(),
+            ModelRolloutProgress::Halted
+        );
+        assert_eq!(agents_on_model(&agents, "old.gguf"), 2);
+        assert_eq!(
+            balancer_applicable_state_holder.get_agent_desired_state(),
+            Some(desired_state("old.gguf"))
+        );
+        assert_eq!(
+            state_database.read_balancer_desired_state().await.unwrap(),
+            balancer_desired_state("old.gguf")
+        );
+    }
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, Eq, PartialEq)]
+pub enum ModelRolloutProgress {
+    Completed,
+    Halted,
+    InProgress,
+}
@@ -0,0 +1,187 @@
+use std::hash::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+use paddler_messaging::request_params::generate_embedding_batch_params::GenerateEmbeddingBatchParams;
+
+/// Long enough to cover a typical system prompt, short enough to keep hashing cheap.
+const RAW_PROMPT_AFFINITY_PREFIX_CHARS: usize = 1024;
+
+pub trait ProvidesAffinityKey {
+    fn affinity_key(&self) -> Option<u64>;
+}
+
+fn hash_session_key(session_key: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+
+    ("session", session_key).hash(&mut hasher);
+
+    hasher.finish()
+}
+
+impl<TParametersSchema> ProvidesAffinityKey
+    for ContinueFromConversationHistoryParams<TParametersSchema>
+{
+    fn affinity_key(&self) -> Option<u64> {
+        if let Some(session_key) = &self.session_key {
+            return Some(hash_session_key(session_key));
+        }
+
+        let messages = &self.conversation_history.messages;
+
+        if messages.is_empty() {
+            return None;
+        }
+
+        let leading_messages_count = messages
+            .iter()
+            .position(|message| message.role == "user")
+            .map_or(messages.len(), |user_message_index| user_message_index + 1);
+
+        let mut hasher = DefaultHasher::new();
+
+        ("conversation", &messages[..leading_messages_count]).hash(&mut hasher);
+
+        Some(hasher.finish())
+    }
+}
+
+impl ProvidesAffinityKey for ContinueFromRawPromptParams {
+    fn affinity_key(&self) -> Option<u64> {
+        if let Some(session_key) = &self.session_key {
+            return Some(hash_session_key(session_key));
+        }
+
+        if self.raw_prompt.is_empty() {
+            return None;
+        }
+
+        let prefix_end = self
+            .raw_prompt
+            .char_indices()
+            .nth(RAW_PROMPT_AFFINITY_PREFIX_CHARS)
+            .map_or(self.raw_prompt.len(), |(byte_index, _)| byte_index);
+
+        let mut hasher = DefaultHasher::new();
+
+        ("raw_prompt", &self.raw_prompt[..prefix_end]).hash(&mut hasher);
+
+        Some(hasher.finish())
+    }
+}
+
+impl ProvidesAffinityKey for GenerateEmbeddingBatchParams {
+    fn affinity_key(&self) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use paddler_messaging::conversation_history::ConversationHistory;
+    use paddler_messaging::conversation_message::ConversationMessage;
+    use paddler_messaging::conversation_message_content::ConversationMessageContent;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
+
+    use super::*;
+
+    fn message(role: &str, text: &str) -> ConversationMessage {
+        ConversationMessage {
+            content: ConversationMessageContent::Text(text.to_owned()),
+            role: role.to_owned(),
+        }
+    }
+
+    fn conversation(
+        messages: Vec<ConversationMessage>,
+        session_key: Option<&str>,
+    ) -> ContinueFromConversationHistoryParams<ValidatedParametersSchema> {
+        ContinueFromConversationHistoryParams {
+            add_generation_prompt: true,
+            conversation_history: ConversationHistory::new(messages),
+            enable_thinking: false,
+            grammar: None,
+            logprobs: None,
+            max_tokens: 10,
+            model: None,
+            parse_tool_calls: false,
+            sampling: None,
+            session_key: session_key.map(str::to_owned),
+            stop: Vec::new(),
+            tool_choice: ToolChoice::Auto,
+            tools: Vec::new(),
+        }
+    }
+
+    fn raw_prompt(raw_prompt: &str, session_key: Option<&str>) -> ContinueFromRawPromptParams {
+        ContinueFromRawPromptParams {
+            grammar: None,
+            logprobs: None,
+            max_tokens: 10,
+            model: None,
+            raw_prompt: raw_prompt.to_owned(),
+            sampling: None,
+            session_key: session_key.map(str::to_owned),
+            stop: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn later_turns_of_a_conversation_share_the_key_of_the_first_turn() {
+        let first_turn = conversation(
+            vec![message("system", "be brief"), message("user", "hi")],
+            None,
+        );
+        let second_turn = conversation(
+            vec![
+                message("system", "be brief"),
+                message("user", "hi"),
+                message("assistant", "hello"),
+                message("user", "how are you?"),
+            ],
+            None,
+        );
+        let other_conversation = conversation(
+            vec![message("system", "be brief"), message("user", "bye")],
+            None,
+        );
+
+        assert_eq!(first_turn.affinity_key(), second_turn.affinity_key());
+        assert_ne!(first_turn.affinity_key(), other_conversation.affinity_key());
+    }
+
+    #[test]
+    fn session_key_takes_precedence_over_content() {
+        let first = conversation(vec![message("user", "one")], Some("session-1"));
+        let second = conversation(vec![message("user", "two")], Some("session-1"));
+
+        assert_eq!(first.affinity_key(), second.affinity_key());
+        assert_eq!(
+            first.affinity_key(),
+            raw_prompt("three", Some("session-1")).affinity_key()
+        );
+    }
+
+    #[test]
+    fn raw_prompts_with_a_shared_long_prefix_share_a_key() {
+        let prefix = "x".repeat(RAW_PROMPT_AFFINITY_PREFIX_CHARS);
+
+        assert_eq!(
+            raw_prompt(&format!("{prefix} first"), None).affinity_key(),
+            raw_prompt(&format!("{prefix} second"), None).affinity_key()
+        );
+        assert_ne!(
+            raw_prompt("short first", None).affinity_key(),
+            raw_prompt("short second", None).affinity_key()
+        );
+    }
+
+    #[test]
+    fn empty_inputs_have_no_key() {
+        assert_eq!(conversation(Vec::new(), None).affinity_key(), None);
+        assert_eq!(raw_prompt("", None).affinity_key(), None);
+    }
+}
@@ -0,0 +1,161 @@
+use std::time::Duration;
+use std::time::Instant;
+
+use paddler_messaging::queue_wait_trend::QueueWaitTrend;
+
+use crate::latency_histogram::LatencyHistogram;
+
+/// Queue wait averages of consecutive windows shorter than this are not compared, so
+/// polling the signal often does not make the trend jittery.
+pub const QUEUE_WAIT_SAMPLING_WINDOW: Duration = Duration::from_secs(10);
+
+/// Changes of the average queue wait smaller than this are treated as noise.
+const MIN_SIGNIFICANT_QUEUE_WAIT_CHANGE: Duration = Duration::from_millis(100);
+
+fn is_significantly_longer(longer: Duration, shorter: Duration) -> bool {
+    longer.saturating_sub(shorter) > MIN_SIGNIFICANT_QUEUE_WAIT_CHANGE.max(shorter / 5)
+}
+
+/// Compares the average queue wait of requests dispatched in consecutive windows.
+///
+/// A request still waiting for longer than that average stands in for it, so the wait
+/// keeps rising while nothing gets dispatched at all.
+#[derive(Debug, Default)]
+pub struct QueueWaitSampler {
+    average: Option<Duration>,
+    sampled_at: Option<Instant>,
+    sampled_count: u64,
+    sampled_sum: Duration,
+    trend: QueueWaitTrend,
+}
+
+impl QueueWaitSampler {
+    #[must_use]
+    pub const fn average(&self) -> Option<Duration> {
+        self.average
+    }
+
+    pub fn sample(
+        &mut self,
+        queue_wait: &LatencyHistogram,
+        oldest_enqueued_at: Option<Instant>,
+        now: Instant,
+    ) {
+        if let Some(sampled_at) = self.sampled_at
+            && now.saturating_duration_since(sampled_at) < QUEUE_WAIT_SAMPLING_WINDOW
+        {
+            return;
+        }
+
+        let count = queue_wait.count();
+        let sum = queue_wait.sum();
+        let window_average = u32::try_from(count.saturating_sub(self.sampled_count))
+            .ok()
+            .and_then(|window_count| {
+                sum.saturating_sub(self.sampled_sum)
+                    .checked_div(window_count)
+            })
+            .max(oldest_enqueued_at.map(|enqueued_at| now.saturating_duration_since(enqueued_at)));
+
+        self.trend = match (self.average, window_average) {
+            (Some(previous), Some(current)) if is_significantly_longer(current, previous) => {
+                QueueWaitTrend::Rising
+            }
+            (Some(previous), Some(current)) if is_significantly_longer(previous, current) => {
+                QueueWaitTrend::Falling
+            }
+            (Some(previous), None) if previous > MIN_SIGNIFICANT_QUEUE_WAIT_CHANGE => {
+                QueueWaitTrend::Falling
+            }
+            (None, Some(current)) if current > MIN_SIGNIFICANT_QUEUE_WAIT_CHANGE => {
+                QueueWaitTrend::Rising
+            }
+            _ => QueueWaitTrend::Steady,
+        };
+        self.average = window_average;
+        self.sampled_at = Some(now);
+        self.sampled_count = count;
+        self.sampled_sum = sum;
+    }
+
+    #[must_use]
+    pub const fn trend(&self) -> QueueWaitTrend {
+        self.trend
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trend_follows_the_average_of_each_window() {
+        let queue_wait = LatencyHistogram::default();
+        let mut sampler = QueueWaitSampler::default();
+        let started_at = Instant::now();
+
+        queue_wait.observe(Duration::from_millis(200));
+        sampler.sample(&queue_wait, None, started_at);
+
+        assert_eq!(sampler.average(), Some(Duration::from_millis(200)));
+        assert_eq!(sampler.trend(), QueueWaitTrend::Rising);
+
+        queue_wait.observe(Duration::from_secs(2));
+        sampler.sample(&queue_wait, None, started_at + Duration::from_secs(1));
+
+        assert_eq!(sampler.average(), Some(Duration::from_millis(200)));
+
+        sampler.sample(&queue_wait, None, started_at + QUEUE_WAIT_SAMPLING_WINDOW);
+
+        assert_eq!(sampler.average(), Some(Duration::from_secs(2)));
+        assert_eq!(sampler.trend(), QueueWaitTrend::Rising);
+
+        queue_wait.observe(Duration::from_millis(1950));
+        sampler.sample(
+            &queue_wait,
+            None,
+            started_at + QUEUE_WAIT_SAMPLING_WINDOW * 2,
+        );
+
+        assert_eq!(sampler.trend(), QueueWaitTrend::Steady);
+
+        sampler.sample(
+            &queue_wait,
+            None,
+            started_at + QUEUE_WAIT_SAMPLING_WINDOW * 3,
+        );
+
+        assert_eq!(sampler.average(), None);
+        assert_eq!(sampler.trend(), QueueWaitTrend::Falling);
+    }
+
+    #[test]
+    fn requests_still_waiting_make_the_wait_rise_without_any_dispatch() {
+        let queue_wait = LatencyHistogram::default();
+        let mut sampler = QueueWaitSampler::default();
+        let enqueued_at = Instant::now();
+
+        sampler.sample(&queue_wait, Some(enqueued_at), enqueued_at);
+
+        assert_eq!(sampler.average(), Some(Duration::ZERO));
+        assert_eq!(sampler.trend(), QueueWaitTrend::Steady);
+
+        sampler.sample(
+            &queue_wait,
+            Some(enqueued_at),
+            enqueued_at + QUEUE_WAIT_SAMPLING_WINDOW,
+        );
+
+        assert_eq!(sampler.average(), Some(QUEUE_WAIT_SAMPLING_WINDOW));
+        assert_eq!(sampler.trend(), QueueWaitTrend::Rising);
+
+        sampler.sample(
+            &queue_wait,
+            Some(enqueued_at),
+            enqueued_at + QUEUE_WAIT_SAMPLING_WINDOW * 2,
+        );
+
+        assert_eq!(sampler.average(), Some(QUEUE_WAIT_SAMPLING_WINDOW * 2));
+        assert_eq!(sampler.trend(), QueueWaitTrend::Rising);
+    }
+}
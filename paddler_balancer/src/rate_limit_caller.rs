@@ -0,0 +1,27 @@
+use std::sync::Arc;
+
+use crate::rate_limit_permit::RateLimitPermit;
+use crate::rate_limit_rejection::RateLimitRejection;
+use crate::rate_limiter::RateLimiter;
+
+/// A caller on a long-lived connection, whose requests are admitted one at a time as they
+/// arrive instead of once when the connection opens.
+#[derive(Clone)]
+pub struct RateLimitCaller {
+    caller: String,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitCaller {
+    #[must_use]
+    pub const fn new(caller: String, rate_limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            caller,
+            rate_limiter,
+        }
+    }
+
+    pub fn admit(&self) -> Result<RateLimitPermit, RateLimitRejection> {
+        self.rate_limiter.admit(&self.caller)
+    }
+}
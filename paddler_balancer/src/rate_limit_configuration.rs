@@ -0,0 +1,23 @@
+/// Limits applied to each caller separately.
+///
+/// A caller is identified by its API key when
+/// authentication is enabled, then by the value of `identity_header` when one is configured
+/// and present, and by its IP address otherwise.
+#[derive(Clone, Debug, Default)]
+pub struct RateLimitConfiguration {
+    /// Only trustworthy behind a reverse proxy that sets (and overwrites) the header;
+    /// clients reaching the balancer directly can put any value in it.
+    pub identity_header: Option<String>,
+    pub max_concurrent_requests: Option<u32>,
+    pub max_generated_tokens_per_day: Option<u64>,
+    pub max_requests_per_minute: Option<u32>,
+}
+
+impl RateLimitConfiguration {
+    #[must_use]
+    pub const fn is_enabled(&self) -> bool {
+        self.max_concurrent_requests.is_some()
+            || self.max_generated_tokens_per_day.is_some()
+            || self.max_requests_per_minute.is_some()
+    }
+}
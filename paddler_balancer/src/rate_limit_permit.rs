@@ -0,0 +1,29 @@
+use std::sync::Arc;
+
+use crate::rate_limiter::RateLimiter;
+
+/// Counts as one of the caller's concurrent requests until dropped.
+pub struct RateLimitPermit {
+    caller: String,
+    rate_limiter: Arc<RateLimiter>,
+}
+
+impl RateLimitPermit {
+    pub const fn new(caller: String, rate_limiter: Arc<RateLimiter>) -> Self {
+        Self {
+            caller,
+            rate_limiter,
+        }
+    }
+
+    pub fn record_generated_tokens(&self, generated_tokens: u64) {
+        self.rate_limiter
+            .record_generated_tokens(&self.caller, generated_tokens);
+    }
+}
+
+impl Drop for RateLimitPermit {
+    fn drop(&mut self) {
+        self.rate_limiter.release(&self.caller);
+    }
+}
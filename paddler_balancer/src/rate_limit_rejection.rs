@@ -0,0 +1,24 @@
+use std::time::Duration;
+
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RateLimitRejection {
+    #[error("Too many concurrent requests (limit: {limit})")]
+    ConcurrentRequests { limit: u32 },
+    #[error("Daily quota of {limit} generated tokens exceeded")]
+    GeneratedTokensPerDay { limit: u64, retry_after: Duration },
+    #[error("Rate limit of {limit} requests per minute exceeded")]
+    RequestsPerMinute { limit: u32, retry_after: Duration },
+}
+
+impl RateLimitRejection {
+    #[must_use]
+    pub const fn retry_after(&self) -> Duration {
+        match self {
+            Self::ConcurrentRequests { .. } => Duration::from_secs(1),
+            Self::GeneratedTokensPerDay { retry_after, .. }
+            | Self::RequestsPerMinute { retry_after, .. } => *retry_after,
+        }
+    }
+}
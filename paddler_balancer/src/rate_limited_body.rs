@@ -0,0 +1,41 @@
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::Context;
+use std::task::Poll;
+
+use actix_web::body::BodySize;
+use actix_web::body::BoxBody;
+use actix_web::body::MessageBody;
+use actix_web::web::Bytes;
+
+use crate::rate_limit_permit::RateLimitPermit;
+
+/// Keeps the caller's concurrency slot taken until the response finishes streaming.
+pub struct RateLimitedBody {
+    body: BoxBody,
+    _rate_limit_permit: Arc<RateLimitPermit>,
+}
+
+impl RateLimitedBody {
+    pub const fn new(body: BoxBody, rate_limit_permit: Arc<RateLimitPermit>) -> Self {
+        Self {
+            body,
+            _rate_limit_permit: rate_limit_permit,
+        }
+    }
+}
+
+impl MessageBody for RateLimitedBody {
+    type Error = <BoxBody as MessageBody>::Error;
+
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        context: &mut Context<'_>,
+    ) -> Poll<Option<Result<Bytes, Self::Error>>> {
+        Pin::new(&mut self.get_mut().body).poll_next(context)
+    }
+}
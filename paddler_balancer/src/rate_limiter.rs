@@ -0,0 +1,257 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
+
+use parking_lot::Mutex;
+
+use crate::rate_limit_configuration::RateLimitConfiguration;
+use crate::rate_limit_permit::RateLimitPermit;
+use crate::rate_limit_rejection::RateLimitRejection;
+
+const DAY: Duration = Duration::from_hours(24);
+const MINUTE: Duration = Duration::from_mins(1);
+const PRUNE_IDLE_CALLERS_ABOVE: usize = 4096;
+/// Bounds how often a large table of active callers is scanned for idle ones.
+const PRUNE_IDLE_CALLERS_EVERY: Duration = MINUTE;
+
+struct CallerUsage {
+    concurrent_requests: u32,
+    day_started_at: Instant,
+    generated_tokens_today: u64,
+    minute_started_at: Instant,
+    requests_this_minute: u32,
+}
+
+impl CallerUsage {
+    const fn new(now: Instant) -> Self {
+        Self {
+            concurrent_requests: 0,
+            day_started_at: now,
+            generated_tokens_today: 0,
+            minute_started_at: now,
+            requests_this_minute: 0,
+        }
+    }
+
+    fn advance_windows(&mut self, now: Instant) {
+        if now.duration_since(self.day_started_at) >= DAY {
+            self.day_started_at = now;
+            self.generated_tokens_today = 0;
+        }
+
+        if now.duration_since(self.minute_started_at) >= MINUTE {
+            self.minute_started_at = now;
+            self.requests_this_minute = 0;
+        }
+    }
+
+    fn is_idle(&self, now: Instant) -> bool {
+        self.concurrent_requests == 0 && now.duration_since(self.day_started_at) >= DAY
+    }
+}
+
+/// Per-caller admission control: fixed one-minute request windows, concurrent requests,
+/// and generated tokens over a fixed one-day window.
+pub struct RateLimiter {
+    callers: Mutex<HashMap<String, CallerUsage>>,
+    configuration: RateLimitConfiguration,
+    /// Only locked while `callers` is held.
+    last_pruned_at: Mutex<Instant>,
+}
+
+impl RateLimiter {
+    #[must_use]
+    pub fn new(configuration: RateLimitConfiguration) -> Self {
+        Self {
+            callers: Mutex::new(HashMap::new()),
+            configuration,
+            last_pruned_at: Mutex::new(Instant::now()),
+        }
+    }
+
+    #[must_use]
+    pub const fn configuration(&self) -> &RateLimitConfiguration {
+        &self.configuration
+    }
+
+    pub fn admit(self: &Arc<Self>, caller: &str) -> Result<RateLimitPermit, RateLimitRejection> {
+        self.admit_at(caller, Instant::now())
+    }
+
+    fn admit_at(
+        self: &Arc<Self>,
+        caller: &str,
+        now: Instant,
+    ) -> Result<RateLimitPermit, RateLimitRejection> {
+        let mut callers = self.callers.lock();
+
+        if callers.len() >= PRUNE_IDLE_CALLERS_ABOVE {
+            let mut last_pruned_at = self.last_pruned_at.lock();
+
+            if now.saturating_duration_since(*last_pruned_at) >= PRUNE_IDLE_CALLERS_EVERY {
+                callers.retain(|_, caller_usage| !caller_usage.is_idle(now));
+                *last_pruned_at = now;
+            }
+        }
+
+        let caller_usage = callers
+            .entry(caller.to_owned())
+            .or_insert_with(|| CallerUsage::new(now));
+
+        caller_usage.advance_windows(now);
+
+        if let Some(limit) = self.configuration.max_generated_tokens_per_day
+            && caller_usage.generated_tokens_today >= limit
+        {
+            return Err(RateLimitRejection::GeneratedTokensPerDay {
+                limit,
+                retry_after: DAY.saturating_sub(now.duration_since(caller_usage.day_started_at)),
+            });
+        }
+
+        if let Some(limit) = self.configuration.max_requests_per_minute
+            && caller_usage.requests_this_minute >= limit
+        {
+            return Err(RateLimitRejection::RequestsPerMinute {
+                limit,
+                retry_after: MINUTE
+                    .saturating_sub(now.duration_since(caller_usage.minute_started_at)),
+            });
+        }
+
+        if let Some(limit) = self.configuration.max_concurrent_requests
+            && caller_usage.concurrent_requests >= limit
+        {
+            return Err(RateLimitRejection::ConcurrentRequests { limit });
+        }
+
+        caller_usage.concurrent_requests += 1;
+        caller_usage.requests_this_minute += 1;
+
+        Ok(RateLimitPermit::new(caller.to_owned(), self.clone()))
+    }
+
+    pub fn record_generated_tokens(&self, caller: &str, generated_tokens: u64) {
+        if let Some(caller_usage) = self.callers.lock().get_mut(caller) {
+            caller_usage.generated_tokens_today = caller_usage
+                .generated_tokens_today
+                .saturating_add(generated_tokens);
+        }
+    }
+
+    pub fn release(&self, caller: &str) {
+        if let Some(caller_usage) = self.callers.lock().get_mut(caller) {
+            caller_usage.concurrent_requests = caller_usage.concurrent_requests.saturating_sub(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rate_limiter(configuration: RateLimitConfiguration) -> Arc<RateLimiter> {
+        Arc::new(RateLimiter::new(configuration))
+    }
+
+    #[test]
+    fn limits_concurrent_requests_per_caller() {
+        let rate_limiter = rate_limiter(RateLimitConfiguration {
+            max_concurrent_requests: Some(1),
+            ..RateLimitConfiguration::default()
+        });
+
+        let permit = rate_limiter.admit("tenant-a").unwrap();
+
+        assert!(matches!(
+            rate_limiter.admit("tenant-a"),
+            Err(RateLimitRejection::ConcurrentRequests { limit: 1 })
+        ));
+        assert!(rate_limiter.admit("tenant-b").is_ok());
+
+        drop(permit);
+
+        assert!(rate_limiter.admit("tenant-a").is_ok());
+    }
+
+    #[test]
+    fn limits_requests_per_minute_until_the_window_passes() {
+        let rate_limiter = rate_limiter(RateLimitConfiguration {
+            max_requests_per_minute: Some(2),
+            ..RateLimitConfiguration::default()
+        });
+        let now = Instant::now();
+
+        drop(rate_limiter.admit_at("tenant-a", now).unwrap());
+        drop(rate_limiter.admit_at("tenant-a", now).unwrap());
+
+        let rejection = rate_limiter
+            .admit_at("tenant-a", now + Duration::from_secs(20))
+            .err()
+            .unwrap();
+
+        assert_eq!(rejection.retry_after(), Duration::from_secs(40));
+        assert!(rate_limiter.admit_at("tenant-a", now + MINUTE).is_ok());
+    }
+
+    #[test]
+    fn stops_admitting_once_the_daily_token_quota_is_used() {
+        let rate_limiter = rate_limiter(RateLimitConfiguration {
+            max_generated_tokens_per_day: Some(100),
+            ..RateLimitConfiguration::default()
+        });
+        let now = Instant::now();
+
+        rate_limiter
+            .admit_at("tenant-a", now)
+            .unwrap()
+            .record_generated_tokens(100);
+
+        let rejection = rate_limiter
+            .admit_at("tenant-a", now + Duration::from_hours(1))
+            .err()
+            .unwrap();
+
+        assert_eq!(rejection.retry_after(), Duration::from_hours(23));
+        assert!(rate_limiter.admit_at("tenant-a", now + DAY).is_ok());
+    }
+
+    #[test]
+    fn prunes_idle_callers_at_most_once_per_interval() {
+        let rate_limiter = rate_limiter(RateLimitConfiguration::default());
+        let now = Instant::now();
+
+        for caller_index in 0..PRUNE_IDLE_CALLERS_ABOVE {
+            drop(
+                rate_limiter
+                    .admit_at(&format!("tenant-{caller_index}"), now)
+                    .unwrap(),
+            );
+        }
+
+        let before_idle = now + DAY.saturating_sub(Duration::from_secs(30));
+
+        drop(rate_limiter.admit_at("tenant-x", before_idle).unwrap());
+
+        assert_eq!(
+            rate_limiter.callers.lock().len(),
+            PRUNE_IDLE_CALLERS_ABOVE + 1
+        );
+
+        drop(rate_limiter.admit_at("tenant-y", now + DAY).unwrap());
+
+        assert_eq!(
+            rate_limiter.callers.lock().len(),
+            PRUNE_IDLE_CALLERS_ABOVE + 2
+        );
+
+        drop(
+            rate_limiter
+                .admit_at("tenant-z", before_idle + PRUNE_IDLE_CALLERS_EVERY)
+                .unwrap(),
+        );
+
+        assert_eq!(rate_limiter.callers.lock().len(), 3);
+    }
+}
@@ -14,23 +14,53 @@ use trzcina::Service;
 use crate::agent_controller_pool::AgentControllerPool;
 use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
 use crate::balancer_desired_state_converter::BalancerDesiredStateConverter;
+use crate::model_rollout::ModelRollout;
+use crate::model_rollout_progress::ModelRolloutProgress;
+use crate::rollout_strategy::RolloutStrategy;
 use crate::sets_desired_state::SetsDesiredState as _;
+use crate::state_database::StateDatabase;
 use paddler_state_conversion::converts_to_applicable_state::ConvertsToApplicableState as _;
 
 async fn convert_to_applicable_state(
     balancer_desired_state: &BalancerDesiredState,
+    applied_balancer_desired_state: &mut BalancerDesiredState,
     agent_controller_pool: &AgentControllerPool,
     balancer_applicable_state_holder: &BalancerApplicableStateHolder,
     is_converted_to_applicable_state: &mut bool,
+    model_rollout: &mut Option<ModelRollout>,
+    rollout_strategy: RolloutStrategy,
 ) -> Result<()> {
     let balancer_applicable_state = BalancerDesiredStateConverter
         .to_applicable_state(balancer_desired_state.clone())
         .await?;
 
-    agent_controller_pool
-        .set_desired_state(balancer_applicable_state.agent_desired_state.clone())
-        .await?;
-    balancer_applicable_state_holder.set_balancer_applicable_state(Some(balancer_applicable_state));
+    if let (
+        RolloutStrategy::Gradual {
+            batch_size,
+            failure_action,
+        },
+        Some(_),
+    ) = (
+        rollout_strategy,
+        balancer_applicable_state_holder.get_balancer_applicable_state(),
+    ) {
+        *model_rollout = Some(ModelRollout::new(
+            batch_size,
+            failure_action,
+            applied_balancer_desired_state.clone(),
+            balancer_applicable_state,
+        ));
+    } else {
+        *model_rollout = None;
+
+        agent_controller_pool
+            .set_desired_state(balancer_applicable_state.clone())
+            .await?;
+
+        balancer_applicable_state_holder
+            .set_balancer_applicable_state(Some(balancer_applicable_state));
+        applied_balancer_desired_state.clone_from(balancer_desired_state);
+    }
 
     *is_converted_to_applicable_state = true;
 
@@ -39,15 +69,21 @@ async fn convert_to_applicable_state(
 
 async fn try_convert_to_applicable_state(
     balancer_desired_state: &BalancerDesiredState,
+    applied_balancer_desired_state: &mut BalancerDesiredState,
     agent_controller_pool: &AgentControllerPool,
     balancer_applicable_state_holder: &BalancerApplicableStateHolder,
     is_converted_to_applicable_state: &mut bool,
+    model_rollout: &mut Option<ModelRollout>,
+    rollout_strategy: RolloutStrategy,
 ) {
     if let Err(err) = convert_to_applicable_state(
         balancer_desired_state,
+        applied_balancer_desired_state,
         agent_controller_pool,
         balancer_applicable_state_holder,
         is_converted_to_applicable_state,
+        model_rollout,
+        rollout_strategy,
     )
     .await
     {
@@ -61,6 +97,8 @@ pub struct ReconciliationService {
     pub balancer_desired_state: BalancerDesiredState,
     pub balancer_desired_state_rx: broadcast::Receiver<BalancerDesiredState>,
     pub is_converted_to_applicable_state: bool,
+    pub rollout_strategy: RolloutStrategy,
+    pub state_database: Arc<dyn StateDatabase>,
 }
 
 #[async_trait]
@@ -76,8 +114,15 @@ impl Service for ReconciliationService {
             mut balancer_desired_state,
             mut balancer_desired_state_rx,
             mut is_converted_to_applicable_state,
+            rollout_strategy,
+            state_database,
         } = *self;
 
+        // The desired state the applicable state holder currently reflects; a failed gradual
+        // rollout restores it.
+        let mut applied_balancer_desired_state = balancer_desired_state.clone();
+        let mut model_rollout: Option<ModelRollout> = None;
+
         let mut ticker = interval(Duration::from_secs(1));
 
         ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
@@ -89,20 +134,47 @@ impl Service for ReconciliationService {
                     if !is_converted_to_applicable_state {
                         try_convert_to_applicable_state(
                             &balancer_desired_state,
+                            &mut applied_balancer_desired_state,
                             &agent_controller_pool,
                             &balancer_applicable_state_holder,
                             &mut is_converted_to_applicable_state,
+                            &mut model_rollout,
+                            rollout_strategy,
                         ).await;
                     }
+
+                    if let Some(rollout) = &mut model_rollout {
+                        match rollout
+                            .advance(
+                                &agent_controller_pool,
+                                &balancer_applicable_state_holder,
+                                state_database.as_ref(),
+                            )
+                            .await
+                        {
+                            Ok(ModelRolloutProgress::InProgress) => {}
+                            Ok(ModelRolloutProgress::Completed) => {
+                                applied_balancer_desired_state.clone_from(&balancer_desired_state);
+                                model_rollout = None;
+                            }
+                            Ok(ModelRolloutProgress::Halted) => {
+                                model_rollout = None;
+                            }
+                            Err(err) => error!("Failed to advance the model rollout: {err}"),
+                        }
+                    }
                 },
                 received_balancer_desired_state = balancer_desired_state_rx.recv() => {
                     is_converted_to_applicable_state = false;
                     balancer_desired_state = received_balancer_desired_state?;
                     try_convert_to_applicable_state(
                         &balancer_desired_state,
+                        &mut applied_balancer_desired_state,
                         &agent_controller_pool,
                         &balancer_applicable_state_holder,
                         &mut is_converted_to_applicable_state,
+                        &mut model_rollout,
+                        rollout_strategy,
                     ).await;
                 }
             }
@@ -112,15 +184,9 @@ impl Service for ReconciliationService {
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
     use std::sync::Arc;
-    use std::sync::atomic::AtomicBool;
-    use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
 
     use tokio::sync::mpsc;
-    use tokio_util::sync::CancellationToken;
 
     use super::convert_to_applicable_state;
     use super::try_convert_to_applicable_state;
@@ -128,12 +194,7 @@ mod tests {
     use crate::agent_controller_pool::AgentControllerPool;
     use crate::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use crate::balancer_desired_state_converter::BalancerDesiredStateConverter;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
-    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
-    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
-    use paddler_messaging::atomic_value::AtomicValue;
+    use crate::rollout_strategy::RolloutStrategy;
     use paddler_messaging::balancer_desired_state::BalancerDesiredState;
     use paddler_state_conversion::converts_to_desired_state::ConvertsToDesiredState as _;
 
@@ -142,32 +203,10 @@ mod tests {
 
         drop(agent_message_rx);
 
-        Arc::new(AgentController {
+        Arc::new(AgentController::new_for_test(
+            "agent-test",
             agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
-            desired_slots_total: AtomicValue::<AtomicI32>::new(0),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: "agent-test".to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
-            slots_total: AtomicValue::<AtomicI32>::new(0),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
-        })
+        ))
     }
 
     #[tokio::test]
@@ -179,9 +218,12 @@ mod tests {
 
         convert_to_applicable_state(
             &balancer_desired_state,
+            &mut BalancerDesiredState::default(),
             &agent_controller_pool,
             &balancer_applicable_state_holder,
             &mut is_converted_to_applicable_state,
+            &mut None,
+            RolloutStrategy::AllAtOnce,
         )
         .await
         .unwrap();
@@ -210,9 +252,12 @@ mod tests {
 
         let result = convert_to_applicable_state(
             &balancer_desired_state,
+            &mut BalancerDesiredState::default(),
             &agent_controller_pool,
             &balancer_applicable_state_holder,
             &mut is_converted_to_applicable_state,
+            &mut None,
+            RolloutStrategy::AllAtOnce,
         )
         .await;
 
@@ -237,9 +282,12 @@ mod tests {
 
         try_convert_to_applicable_state(
             &balancer_desired_state,
+            &mut BalancerDesiredState::default(),
             &agent_controller_pool,
             &balancer_applicable_state_holder,
             &mut is_converted_to_applicable_state,
+            &mut None,
+            RolloutStrategy::AllAtOnce,
         )
         .await;
 
@@ -0,0 +1,45 @@
+use std::sync::Arc;
+
+use actix_web::HttpMessage as _;
+use actix_web::HttpRequest;
+use llama_cpp_bindings_types::TokenUsage;
+use paddler_messaging::trace_context::TraceContext;
+
+use crate::caller_key::CallerKey;
+use crate::rate_limit_permit::RateLimitPermit;
+use crate::request_endpoint::request_endpoint;
+use crate::request_priority::RequestPriority;
+
+/// What the balancer decided about a request before dispatching it to an agent.
+#[derive(Clone, Default)]
+pub struct RequestAdmission {
+    pub caller_key: Option<CallerKey>,
+    pub endpoint: String,
+    pub priority: RequestPriority,
+    pub rate_limit_permit: Option<Arc<RateLimitPermit>>,
+    /// Taken from the `traceparent` header, so the request joins the caller's trace.
+    pub trace_context: Option<TraceContext>,
+}
+
+impl RequestAdmission {
+    #[must_use]
+    pub fn from_request(request: &HttpRequest) -> Self {
+        Self {
+            caller_key: request.extensions().get::<CallerKey>().cloned(),
+            endpoint: request_endpoint(request),
+            priority: RequestPriority::from_request(request),
+            rate_limit_permit: request.extensions().get::<Arc<RateLimitPermit>>().cloned(),
+            trace_context: request
+                .headers()
+                .get("traceparent")
+                .and_then(|traceparent| traceparent.to_str().ok())
+                .and_then(TraceContext::from_traceparent),
+        }
+    }
+
+    pub fn record_token_usage(&self, token_usage: &TokenUsage) {
+        if let Some(rate_limit_permit) = &self.rate_limit_permit {
+            rate_limit_permit.record_generated_tokens(token_usage.completion_tokens());
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use actix_web::HttpRequest;
+
+/// Route pattern the request matched, so requests are counted per route rather than per path.
+#[must_use]
+pub fn request_endpoint(request: &HttpRequest) -> String {
+    request
+        .match_pattern()
+        .unwrap_or_else(|| request.path().to_owned())
+}
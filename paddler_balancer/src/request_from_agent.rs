@@ -10,93 +10,269 @@ use paddler_messaging::jsonrpc::error::Error as JsonRpcError;
 use paddler_messaging::jsonrpc::error_envelope::ErrorEnvelope;
 use paddler_messaging::jsonrpc::response_envelope::ResponseEnvelope;
 use paddler_messaging::streamable_result::StreamableResult;
+use tokio::time::Instant;
 use tokio::time::sleep;
 use tokio_util::sync::CancellationToken;
 
 use crate::agent_controller::AgentController;
 use crate::agent_response_forwarding_mode::AgentResponseForwardingMode;
+use crate::agent_response_forwarding_outcome::AgentResponseForwardingOutcome;
 use crate::agent_stop_outcome::AgentStopOutcome;
+use crate::audit_log_entry::AuditLogEntry;
 use crate::buffered_request_agent_wait_result::BufferedRequestAgentWaitResult;
 use crate::buffered_request_manager::BufferedRequestManager;
 use crate::controls_session::ControlsSession;
+use crate::dispatch_strategy::DispatchStrategy;
 use crate::dispatched_agent::DispatchedAgent;
 use crate::handles_agent_streaming_response::HandlesAgentStreamingResponse;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::manages_senders::ManagesSenders;
 use crate::manages_senders_controller::ManagesSendersController;
+use crate::provides_affinity_key::ProvidesAffinityKey;
+use crate::request_admission::RequestAdmission;
+use crate::request_metrics::RequestMetrics;
+use crate::request_outcome::RequestOutcome;
+use crate::request_priority::RequestPriority;
 use paddler_messaging::management_socket::agent::request::Request as AgentJsonRpcRequest;
 
 pub async fn request_from_agent<TControlsSession, TParams>(
     buffered_request_manager: Arc<BufferedRequestManager>,
     connection_close: CancellationToken,
     inference_service_configuration: InferenceServiceConfiguration,
+    model_pool: Option<String>,
     params: TParams,
+    request_admission: RequestAdmission,
     request_id: String,
-    mut session_controller: TControlsSession,
+    session_controller: TControlsSession,
     shutdown: CancellationToken,
 )
 where
     TControlsSession: ControlsSession<OutgoingMessage>,
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send,
+    TParams: Clone + Debug + Into<AgentJsonRpcRequest> + ProvidesAffinityKey + Send,
     AgentController: HandlesAgentStreamingResponse<TParams>,
     <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
 {
-    let Some(dispatched_agent) = wait_for_agent_controller(
-        buffered_request_manager.clone(),
-        connection_close.clone(),
-        request_id.clone(),
-        &mut session_controller,
-        shutdown.clone(),
+    let endpoint = request_admission.endpoint.clone();
+    let received_at = Instant::now();
+    let request_metrics = buffered_request_manager
+        .observability
+        .request_metrics
+        .clone();
+    let outcome = serve_request_from_agent(
+        buffered_request_manager,
+        connection_close,
+        inference_service_configuration,
+        model_pool,
+        params,
+        request_admission,
+        request_id,
+        session_controller,
+        shutdown,
     )
-    .await
-    else {
-        return;
-    };
+    .await;
 
-    let receive_response_controller = match dispatched_agent
-        .agent_controller
-        .handle_streaming_response(request_id.clone(), params)
-        .await
+    request_metrics.record_finished_request(received_at.elapsed(), &endpoint, outcome);
+}
+
+/// Serves the request without counting it as finished, for callers that answer one client
+/// request with several agent requests.
+pub async fn serve_request_from_agent<TControlsSession, TParams>(
+    buffered_request_manager: Arc<BufferedRequestManager>,
+    connection_close: CancellationToken,
+    inference_service_configuration: InferenceServiceConfiguration,
+    model_pool: Option<String>,
+    params: TParams,
+    request_admission: RequestAdmission,
+    request_id: String,
+    mut session_controller: TControlsSession,
+    shutdown: CancellationToken,
+) -> RequestOutcome
+where
+    TControlsSession: ControlsSession<OutgoingMessage>,
+    TParams: Clone + Debug + Into<AgentJsonRpcRequest> + ProvidesAffinityKey + Send,
+    AgentController: HandlesAgentStreamingResponse<TParams>,
+    <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
+{
+    let affinity_key = inference_service_configuration
+        .dispatch_strategy
+        .affinity_key(&params);
+    let agent_failover_policy = inference_service_configuration.agent_failover_policy;
+    let audit_log = buffered_request_manager.observability.audit_log.clone();
+    let mut audit_log_entry =
+        audit_log.start_entry(request_admission.endpoint.clone(), request_id.clone());
+    let received_at = Instant::now();
+    let request_metrics = buffered_request_manager
+        .observability
+        .request_metrics
+        .clone();
+    let mut failovers: u32 = 0;
+    let mut request_span = buffered_request_manager
+        .observability
+        .tracer
+        .start_span("balancer.request", request_admission.trace_context.as_ref());
+    let mut retained_params = Some(params);
+
+    request_span.set_attribute("endpoint", request_admission.endpoint.clone());
+    request_span.set_attribute("request_id", request_id.clone());
+
+    audit_log_entry
+        .caller_key
+        .clone_from(&request_admission.caller_key);
+    audit_log_entry.model_pool.clone_from(&model_pool);
+
+    if audit_log_entry.captures_bodies()
+        && let Some(params) = &retained_params
     {
-        Ok(receive_response_controller) => receive_response_controller,
-        Err(err) => {
-            error!("Failed to handle request {request_id:?}: {err}");
+        let agent_request: AgentJsonRpcRequest = params.clone().into();
 
-            respond_with_error(
-                JsonRpcError {
-                    code: 500,
-                    description: "Failed to generate response".to_owned(),
-                },
-                request_id.clone(),
-                &mut session_controller,
-            )
-            .await;
+        match serde_json::to_string(&agent_request) {
+            Ok(prompt) => audit_log_entry.set_prompt(&prompt),
+            Err(err) => warn!("Failed to serialize the prompt of request {request_id:?}: {err}"),
+        }
+    }
+
+    let outcome = loop {
+        let Some(params) = retained_params.take() else {
+            break RequestOutcome::Failed;
+        };
+        let buffering_span = request_span.start_child("balancer.buffering");
+        let waiting_since = Instant::now();
+        let dispatched_agent = match wait_for_agent_controller(
+            affinity_key,
+            &mut audit_log_entry,
+            buffered_request_manager.clone(),
+            connection_close.clone(),
+            inference_service_configuration.dispatch_strategy,
+            model_pool.as_deref(),
+            request_admission.priority,
+            request_id.clone(),
+            &mut session_controller,
+            shutdown.clone(),
+        )
+        .await
+        {
+            Ok(dispatched_agent) => dispatched_agent,
+            Err(outcome) => break outcome,
+        };
+
+        drop(buffering_span);
+        audit_log_entry.record_queue_wait(waiting_since.elapsed());
+        request_metrics.queue_wait.observe(waiting_since.elapsed());
+
+        audit_log_entry.agent_id = Some(dispatched_agent.agent_controller.id.clone());
+        audit_log_entry.model = dispatched_agent.agent_controller.get_model_path();
+
+        let mut dispatch_span = request_span.start_child("balancer.dispatch");
+
+        dispatch_span.set_attribute("agent_id", dispatched_agent.agent_controller.id.clone());
+
+        let may_fail_over = agent_failover_policy.allows_failover(failovers);
 
-            return;
+        if may_fail_over {
+            retained_params = Some(params.clone());
+        }
+
+        let receive_response_controller = match dispatched_agent
+            .agent_controller
+            .handle_streaming_response(request_id.clone(), params, Some(dispatch_span.context()))
+            .await
+        {
+            Ok(receive_response_controller) => receive_response_controller,
+            Err(err) => {
+                if may_fail_over
+                    && dispatched_agent
+                        .agent_controller
+                        .connection_close
+                        .is_cancelled()
+                {
+                    failovers += 1;
+
+                    warn!(
+                        "Agent {:?} disconnected before accepting request {request_id:?}, dispatching it again (failover {failovers})",
+                        dispatched_agent.agent_controller.id
+                    );
+
+                    continue;
+                }
+
+                error!("Failed to handle request {request_id:?}: {err}");
+
+                audit_log_entry.error_kind = Some("dispatch_failed");
+
+                respond_with_error(
+                    JsonRpcError {
+                        code: 500,
+                        description: "Failed to generate response".to_owned(),
+                    },
+                    request_id.clone(),
+                    &mut session_controller,
+                )
+                .await;
+
+                break RequestOutcome::Failed;
+            }
+        };
+
+        let lost_agent_id = dispatched_agent.agent_controller.id.clone();
+        let mut stream_span = dispatch_span.start_child("balancer.stream_response");
+
+        match forward_responses_stream(
+            &mut audit_log_entry,
+            connection_close.clone(),
+            dispatched_agent,
+            inference_service_configuration.clone(),
+            may_fail_over,
+            receive_response_controller,
+            request_admission.clone(),
+            request_id.clone(),
+            &request_metrics,
+            session_controller,
+            shutdown.clone(),
+        )
+        .await
+        {
+            AgentResponseForwardingOutcome::AgentLostBeforeFirstResponse(
+                returned_session_controller,
+            ) => {
+                failovers += 1;
+                session_controller = returned_session_controller;
+                stream_span.set_attribute("outcome", "agent_lost");
+
+                warn!(
+                    "Agent {lost_agent_id:?} disconnected before responding to request {request_id:?}, dispatching it again (failover {failovers})"
+                );
+            }
+            AgentResponseForwardingOutcome::Finished(outcome) => {
+                stream_span.set_attribute("outcome", outcome.label());
+
+                break outcome;
+            }
         }
     };
 
-    forward_responses_stream(
-        connection_close,
-        dispatched_agent,
-        inference_service_configuration,
-        receive_response_controller,
-        request_id,
-        session_controller,
-        shutdown,
-    )
-    .await;
+    request_span.set_attribute("outcome", outcome.label());
+
+    audit_log_entry.outcome = outcome.label();
+    audit_log_entry.set_duration(received_at.elapsed());
+    audit_log.record(audit_log_entry);
+
+    outcome
 }
 
 pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
+    audit_log_entry: &mut AuditLogEntry,
     connection_close: CancellationToken,
     dispatched_agent: DispatchedAgent,
     inference_service_configuration: InferenceServiceConfiguration,
+    may_fail_over: bool,
     mut receive_response_controller: ManagesSendersController<TManagesSenders>,
+    request_admission: RequestAdmission,
     request_id: String,
+    request_metrics: &RequestMetrics,
     mut session_controller: TControlsSession,
     shutdown: CancellationToken,
-) where
+) -> AgentResponseForwardingOutcome<TControlsSession>
+where
     TControlsSession: ControlsSession<OutgoingMessage>,
     TManagesSenders: ManagesSenders + Send + Sync,
     TManagesSenders::Value: Debug + Into<OutgoingResponse> + Send + StreamableResult,
@@ -106,7 +282,12 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
     let agent_controller = dispatched_agent.agent_controller.clone();
     let agent_connection_close = agent_controller.connection_close.clone();
     let inference_item_timeout = inference_service_configuration.inference_item_timeout;
+    let dispatched_at = Instant::now();
+    let mut first_response_at: Option<Instant> = None;
     let mut forwarding_mode = AgentResponseForwardingMode::ForwardingToClient;
+    let mut has_forwarded_response = false;
+    let mut outcome = RequestOutcome::Failed;
+    let mut previous_token_at: Option<Instant> = None;
 
     loop {
         let is_forwarding_to_client = matches!(
@@ -129,11 +310,20 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
                     ).await;
 
                     stop_responding_to(&agent_controller, request_id).await;
+
+                    audit_log_entry.error_kind = Some("balancer_shutting_down");
+                    outcome = RequestOutcome::Failed;
                 }
 
                 break;
             }
             () = agent_connection_close.cancelled() => {
+                if may_fail_over && is_forwarding_to_client && !has_forwarded_response {
+                    return AgentResponseForwardingOutcome::AgentLostBeforeFirstResponse(
+                        session_controller,
+                    );
+                }
+
                 if is_forwarding_to_client {
                     error!("Agent controller connection closed");
 
@@ -145,11 +335,16 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
                         request_id,
                         &mut session_controller,
                     ).await;
+
+                    audit_log_entry.error_kind = Some("agent_disconnected");
+                    outcome = RequestOutcome::Failed;
                 }
 
                 break;
             }
             () = connection_close.cancelled(), if is_forwarding_to_client => {
+                outcome = RequestOutcome::Cancelled;
+
                 match stop_responding_to(&agent_controller, request_id.clone()).await {
                     AgentStopOutcome::AgentUnreachable => break,
                     AgentStopOutcome::StopRequested => {
@@ -186,6 +381,9 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
                     &mut session_controller,
                 ).await;
 
+                audit_log_entry.error_kind = Some("inference_timeout");
+                outcome = RequestOutcome::Failed;
+
                 match stop_responding_to(&agent_controller, request_id.clone()).await {
                     AgentStopOutcome::AgentUnreachable => break,
                     AgentStopOutcome::StopRequested => {
@@ -195,6 +393,12 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
             }
             response = receive_response_controller.response_rx.recv() => {
                 let Some(response) = response else {
+                    if may_fail_over && is_forwarding_to_client && !has_forwarded_response {
+                        return AgentResponseForwardingOutcome::AgentLostBeforeFirstResponse(
+                            session_controller,
+                        );
+                    }
+
                     if is_forwarding_to_client {
                         error!(
                             "Response channel closed before terminator for request {request_id:?}"
@@ -209,12 +413,62 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
                             request_id,
                             &mut session_controller,
                         ).await;
+
+                        audit_log_entry.error_kind = Some("response_channel_closed");
+                        outcome = RequestOutcome::Failed;
                     }
 
                     break;
                 };
 
                 let is_done = response.is_done();
+                let response_received_at = Instant::now();
+
+                if first_response_at.is_none() {
+                    request_metrics
+                        .time_to_first_token
+                        .observe(response_received_at - dispatched_at);
+                }
+
+                let first_response_received_at = *first_response_at.get_or_insert(response_received_at);
+
+                if let Some(generated_text) = response.generated_text() {
+                    // Only the gaps between emitted tokens count, not the ones before logprobs
+                    // or the final summary.
+                    if let Some(previous_token_received_at) =
+                        previous_token_at.replace(response_received_at)
+                    {
+                        request_metrics
+                            .inter_token_latency
+                            .observe(response_received_at - previous_token_received_at);
+                    }
+
+                    audit_log_entry.append_completion(generated_text);
+                }
+
+                if let Some(error_kind) = response.error_kind() {
+                    audit_log_entry.error_kind = Some(error_kind);
+                }
+
+                if let Some(stop_reason) = response.stop_reason() {
+                    audit_log_entry.finish_reason = Some(stop_reason.label());
+                }
+
+                if let Some(token_usage) = response.token_usage() {
+                    // Embeddings only consume prompt tokens, so they say nothing about how fast
+                    // the agent generates.
+                    if token_usage.completion_tokens() > 0 {
+                        agent_controller.record_throughput(
+                            first_response_received_at - dispatched_at,
+                            token_usage.completion_tokens(),
+                            first_response_received_at.elapsed(),
+                        );
+                    }
+
+                    audit_log_entry.record_token_usage(&token_usage);
+                    request_admission.record_token_usage(&token_usage);
+                    request_metrics.record_token_usage(&token_usage);
+                }
 
                 if !is_forwarding_to_client {
                     if is_done {
@@ -224,6 +478,8 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
                     continue;
                 }
 
+                has_forwarded_response = true;
+
                 let send_succeeded = send_response_to_client(
                     agent_controller.name.clone(),
                     response,
@@ -231,7 +487,15 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
                     &mut session_controller,
                 ).await;
 
+                if !send_succeeded {
+                    outcome = RequestOutcome::Cancelled;
+                }
+
                 if is_done {
+                    if send_succeeded {
+                        outcome = RequestOutcome::Succeeded;
+                    }
+
                     break;
                 }
 
@@ -247,6 +511,8 @@ pub async fn forward_responses_stream<TControlsSession, TManagesSenders>(
             }
         }
     }
+
+    AgentResponseForwardingOutcome::Finished(outcome)
 }
 
 pub async fn respond_with_error<TControlsSession>(
@@ -311,12 +577,17 @@ async fn stop_responding_to(
 }
 
 async fn wait_for_agent_controller<TControlsSession>(
+    affinity_key: Option<u64>,
+    audit_log_entry: &mut AuditLogEntry,
     buffered_request_manager: Arc<BufferedRequestManager>,
     connection_close: CancellationToken,
+    dispatch_strategy: DispatchStrategy,
+    model_pool: Option<&str>,
+    priority: RequestPriority,
     request_id: String,
     session_controller: &mut TControlsSession,
     shutdown: CancellationToken,
-) -> Option<DispatchedAgent>
+) -> Result<DispatchedAgent, RequestOutcome>
 where
     TControlsSession: ControlsSession<OutgoingMessage>,
 {
@@ -335,16 +606,18 @@ where
                 session_controller,
             ).await;
 
-            None
+            audit_log_entry.error_kind = Some("balancer_shutting_down");
+
+            Err(RequestOutcome::Rejected)
         },
         () = connection_close.cancelled() => {
             debug!("Connection close signal received, stopping GenerateTokens loop.");
 
-            None
+            Err(RequestOutcome::Cancelled)
         },
-        buffered_request_agent_wait_result = buffered_request_manager.wait_for_available_agent() => {
+        buffered_request_agent_wait_result = buffered_request_manager.wait_for_available_agent(model_pool, affinity_key, dispatch_strategy, priority) => {
             match buffered_request_agent_wait_result {
-                Ok(BufferedRequestAgentWaitResult::Found(dispatched_agent)) => Some(dispatched_agent),
+                Ok(BufferedRequestAgentWaitResult::Found(dispatched_agent)) => Ok(dispatched_agent),
                 Ok(BufferedRequestAgentWaitResult::BufferOverflow) => {
                     warn!("Too many buffered requests, dropping request: {request_id:?}");
 
@@ -357,7 +630,9 @@ where
                         session_controller,
                     ).await;
 
-                    None
+                    audit_log_entry.error_kind = Some("buffer_overflow");
+
+                    Err(RequestOutcome::Rejected)
                 }
                 Ok(BufferedRequestAgentWaitResult::Timeout(err)) => {
                     warn!("Buffered request {request_id:?} timed out: {err:?}");
@@ -371,7 +646,9 @@ where
                         session_controller,
                     ).await;
 
-                    None
+                    audit_log_entry.error_kind = Some("buffer_timeout");
+
+                    Err(RequestOutcome::Rejected)
                 }
                 Err(err) => {
                     error!("Error while waiting for available agent controller for GenerateTokens request: {err}");
@@ -385,7 +662,9 @@ where
                         session_controller,
                     ).await;
 
-                    None
+                    audit_log_entry.error_kind = Some("internal_error");
+
+                    Err(RequestOutcome::Failed)
                 }
             }
         }
@@ -394,32 +673,35 @@ where
 
 #[cfg(test)]
 mod tests {
-    use parking_lot::RwLock;
-    use std::collections::BTreeSet;
     use std::mem::discriminant;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
     use std::time::Duration;
 
     use tokio::sync::mpsc;
 
     use super::*;
     use crate::agent_controller_pool::AgentControllerPool;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
+    use crate::audit_log::AUDIT_LOG_CHANNEL_CAPACITY;
+    use crate::audit_log::AuditLog;
+    use crate::caller_key::CallerKey;
     use crate::chunk_forwarding_session_controller::ChunkForwardingSessionController;
     use crate::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
     use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
-    use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
-    use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
+    use crate::dispatch_strategy::DispatchStrategy;
+    use crate::request_observability::RequestObservability;
     use paddler_messaging::atomic_value::AtomicValue;
     use paddler_messaging::generated_token_result::GeneratedTokenResult;
     use paddler_messaging::generation_summary::GenerationSummary;
+    use paddler_messaging::jsonrpc::request_envelope::RequestEnvelope;
     use paddler_messaging::management_socket::agent::message::Message as AgentJsonRpcMessage;
     use paddler_messaging::management_socket::agent::notification::Notification as AgentJsonRpcNotification;
     use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+    use paddler_messaging::stop_reason::StopReason;
+    use paddler_messaging::token_logprob::TokenLogprob;
+    use paddler_messaging::trace_context::TraceContext;
+    use paddler_tracing::tracer::FINISHED_SPAN_CHANNEL_CAPACITY;
+    use paddler_tracing::tracer::Tracer;
 
     struct AgentControllerWithIncomingChannel {
         agent_controller: Arc<AgentController>,
@@ -430,30 +712,9 @@ mod tests {
         let (agent_message_tx, agent_message_rx) = mpsc::unbounded_channel();
 
         let agent_controller = Arc::new(AgentController {
-            agent_message_tx,
-            chat_template_override_sender_collection: Arc::new(
-                ChatTemplateOverrideSenderCollection::default(),
-            ),
-            connection_close: CancellationToken::new(),
             desired_slots_total: AtomicValue::<AtomicI32>::new(1),
-            download_current: AtomicValue::<AtomicU64>::new(0),
-            download_filename: RwLock::new(None),
-            download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-            download_total: AtomicValue::<AtomicU64>::new(0),
-            embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
-            generate_tokens_sender_collection: Arc::new(GenerateTokensSenderCollection::default()),
-            id: id.to_owned(),
-            issues: RwLock::new(BTreeSet::new()),
-            model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
-            model_path: RwLock::new(None),
-            name: None,
-            newest_update_version: AtomicValue::<AtomicI32>::new(0),
-            slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(1),
-            state_application_status_code: AtomicValue::<AtomicI32>::new(
-                AgentStateApplicationStatus::Fresh as i32,
-            ),
-            uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+            ..AgentController::new_for_test(id, agent_message_tx)
         });
 
         AgentControllerWithIncomingChannel {
@@ -467,14 +728,19 @@ mod tests {
 
         pool.register_agent_controller(agent_controller.id.clone(), agent_controller)
             .unwrap();
-        pool.take_least_busy_agent_controller().unwrap()
+        pool.take_least_busy_agent_controller(None).unwrap()
     }
 
     fn raw_prompt_params() -> ContinueFromRawPromptParams {
         ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 1,
+            model: None,
             raw_prompt: "fixture prompt".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         }
     }
 
@@ -483,12 +749,15 @@ mod tests {
 
         InferenceServiceConfiguration {
             addr: "127.0.0.1:0".parse().unwrap(),
+            agent_failover_policy: AgentFailoverPolicy::Disabled,
             cors_allowed_hosts: Vec::new(),
+            dispatch_strategy: DispatchStrategy::LeastBusy,
             inference_item_timeout: TIMEOUT_LONGER_THAN_ANY_TEST_RUN,
+            tls_configuration: None,
         }
     }
 
-    #[tokio::test]
+    #[tokio::test(flavor = "current_thread")]
     async fn request_from_agent_forwards_error_when_agent_connection_closes() {
         let pool = Arc::new(AgentControllerPool::default());
         let AgentControllerWithIncomingChannel {
@@ -496,31 +765,37 @@ mod tests {
             agent_message_rx: _agent_message_rx,
         } = agent_controller_with_one_free_slot("agent-close");
 
-        agent_controller.connection_close.cancel();
-
-        pool.register_agent_controller("agent-close".to_owned(), agent_controller)
+        pool.register_agent_controller("agent-close".to_owned(), agent_controller.clone())
             .unwrap();
 
         let buffered_request_manager = Arc::new(BufferedRequestManager::new(
             pool,
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ));
 
         let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
         let session_controller =
             ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
 
-        request_from_agent(
+        let mut request_task = tokio_test::task::spawn(request_from_agent(
             buffered_request_manager,
             CancellationToken::new(),
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             "request-close".to_owned(),
             session_controller,
             CancellationToken::new(),
-        )
-        .await;
+        ));
+
+        assert!(request_task.poll().is_pending());
+
+        agent_controller.connection_close.cancel();
+
+        assert!(request_task.poll().is_ready());
 
         let forwarded = chunk_rx.recv().await.unwrap();
 
@@ -530,6 +805,268 @@ mod tests {
         );
     }
 
+    #[tokio::test(flavor = "current_thread")]
+    async fn request_from_agent_fails_over_when_agent_disconnects_before_first_token() {
+        let pool = Arc::new(AgentControllerPool::default());
+        let AgentControllerWithIncomingChannel {
+            agent_controller: lost_agent_controller,
+            agent_message_rx: mut lost_agent_message_rx,
+        } = agent_controller_with_one_free_slot("agent-lost");
+        let AgentControllerWithIncomingChannel {
+            agent_controller: standby_agent_controller,
+            agent_message_rx: mut standby_agent_message_rx,
+        } = agent_controller_with_one_free_slot("agent-standby");
+
+        pool.register_agent_controller("agent-lost".to_owned(), lost_agent_controller.clone())
+            .unwrap();
+
+        let buffered_request_manager = Arc::new(BufferedRequestManager::new(
+            pool.clone(),
+            Duration::from_secs(1),
+            10,
+            RequestObservability::default(),
+        ));
+
+        let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
+        let session_controller =
+            ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
+        let request_id = "request-failover".to_owned();
+
+        let mut request_task = tokio_test::task::spawn(request_from_agent(
+            buffered_request_manager,
+            CancellationToken::new(),
+            InferenceServiceConfiguration {
+                agent_failover_policy: AgentFailoverPolicy::BeforeFirstToken { max_failovers: 1 },
+                ..inference_service_configuration_with_long_timeout()
+            },
+            None,
+            raw_prompt_params(),
+            RequestAdmission::default(),
+            request_id.clone(),
+            session_controller,
+            CancellationToken::new(),
+        ));
+
+        assert!(request_task.poll().is_pending());
+        assert!(matches!(
+            lost_agent_message_rx.try_recv(),
+            Ok(AgentJsonRpcMessage::Request(_))
+        ));
+
+        pool.register_agent_controller(
+            "agent-standby".to_owned(),
+            standby_agent_controller.clone(),
+        )
+        .unwrap();
+        lost_agent_controller.connection_close.cancel();
+
+        assert!(request_task.poll().is_pending());
+        assert!(matches!(
+            standby_agent_message_rx.try_recv(),
+            Ok(AgentJsonRpcMessage::Request(_))
+        ));
+        assert_eq!(lost_agent_controller.slots_processing.get(), 0);
+        assert!(
+            chunk_rx.try_recv().is_err(),
+            "the client must not see the lost agent as an error"
+        );
+
+        standby_agent_controller
+            .generate_tokens_sender_collection
+            .forward_response(
+                request_id,
+                GeneratedTokenResult::Done(GenerationSummary::default()),
+            )
+            .await
+            .unwrap();
+
+        assert!(request_task.poll().is_ready());
+        assert_eq!(standby_agent_controller.slots_processing.get(), 0);
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn request_from_agent_continues_the_incoming_trace_on_the_agent() {
+        let pool = Arc::new(AgentControllerPool::default());
+        let AgentControllerWithIncomingChannel {
+            agent_controller,
+            mut agent_message_rx,
+        } = agent_controller_with_one_free_slot("agent-traced");
+
+        pool.register_agent_controller("agent-traced".to_owned(), agent_controller.clone())
+            .unwrap();
+
+        let (finished_span_tx, mut finished_span_rx) =
+            mpsc::channel(FINISHED_SPAN_CHANNEL_CAPACITY);
+        let buffered_request_manager = Arc::new(BufferedRequestManager::new(
+            pool,
+            Duration::from_secs(1),
+            10,
+            RequestObservability {
+                tracer: Tracer::new(finished_span_tx),
+                ..RequestObservability::default()
+            },
+        ));
+        let incoming_trace_context = TraceContext {
+            span_id: "00f067aa0ba902b7".to_owned(),
+            trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".to_owned(),
+        };
+
+        let (chunk_tx, _chunk_rx) = mpsc::unbounded_channel();
+        let session_controller =
+            ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
+        let request_id = "request-traced".to_owned();
+
+        let mut request_task = tokio_test::task::spawn(request_from_agent(
+            buffered_request_manager,
+            CancellationToken::new(),
+            inference_service_configuration_with_long_timeout(),
+            None,
+            raw_prompt_params(),
+            RequestAdmission {
+                trace_context: Some(incoming_trace_context.clone()),
+                ..RequestAdmission::default()
+            },
+            request_id.clone(),
+            session_controller,
+            CancellationToken::new(),
+        ));
+
+        assert!(request_task.poll().is_pending());
+
+        let Ok(AgentJsonRpcMessage::Request(RequestEnvelope {
+            trace_context: Some(agent_trace_context),
+            ..
+        })) = agent_message_rx.try_recv()
+        else {
+            panic!("the agent should receive the request along with its trace context");
+        };
+
+        assert_eq!(
+            agent_trace_context.trace_id,
+            incoming_trace_context.trace_id
+        );
+
+        agent_controller
+            .generate_tokens_sender_collection
+            .forward_response(
+                request_id,
+                GeneratedTokenResult::Done(GenerationSummary::default()),
+            )
+            .await
+            .unwrap();
+
+        assert!(request_task.poll().is_ready());
+
+        let mut finished_spans = Vec::new();
+
+        while let Ok(finished_span) = finished_span_rx.try_recv() {
+            finished_spans.push(finished_span);
+        }
+
+        assert_eq!(
+            finished_spans
+                .iter()
+                .map(|finished_span| finished_span.name)
+                .collect::<Vec<_>>(),
+            vec![
+                "balancer.buffering",
+                "balancer.stream_response",
+                "balancer.dispatch",
+                "balancer.request",
+            ]
+        );
+        assert!(
+            finished_spans
+                .iter()
+                .all(|finished_span| finished_span.trace_id == incoming_trace_context.trace_id)
+        );
+        assert_eq!(finished_spans[2].span_id, agent_trace_context.span_id);
+        assert_eq!(
+            finished_spans[3].parent_span_id,
+            Some(incoming_trace_context.span_id)
+        );
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn request_from_agent_records_the_request_in_the_audit_log() {
+        let pool = Arc::new(AgentControllerPool::default());
+        let AgentControllerWithIncomingChannel {
+            agent_controller,
+            agent_message_rx: _agent_message_rx,
+        } = agent_controller_with_one_free_slot("agent-audited");
+
+        pool.register_agent_controller("agent-audited".to_owned(), agent_controller.clone())
+            .unwrap();
+
+        let (entry_tx, mut entry_rx) = mpsc::channel(AUDIT_LOG_CHANNEL_CAPACITY);
+        let buffered_request_manager = Arc::new(BufferedRequestManager::new(
+            pool,
+            Duration::from_secs(1),
+            10,
+            RequestObservability {
+                audit_log: AuditLog::new(entry_tx, Some(1024)),
+                ..RequestObservability::default()
+            },
+        ));
+        let (chunk_tx, _chunk_rx) = mpsc::unbounded_channel();
+        let session_controller =
+            ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
+        let request_id = "request-audited".to_owned();
+
+        let mut request_task = tokio_test::task::spawn(request_from_agent(
+            buffered_request_manager,
+            CancellationToken::new(),
+            inference_service_configuration_with_long_timeout(),
+            None,
+            raw_prompt_params(),
+            RequestAdmission {
+                caller_key: Some(CallerKey::from_api_key("sk-audited")),
+                endpoint: "/api/v1/inference_socket".to_owned(),
+                ..RequestAdmission::default()
+            },
+            request_id.clone(),
+            session_controller,
+            CancellationToken::new(),
+        ));
+
+        assert!(request_task.poll().is_pending());
+
+        for generated_token_result in [
+            GeneratedTokenResult::ContentToken("Hello".to_owned()),
+            GeneratedTokenResult::Done(GenerationSummary {
+                stop_reason: StopReason::MaxTokens,
+                ..GenerationSummary::default()
+            }),
+        ] {
+            agent_controller
+                .generate_tokens_sender_collection
+                .forward_response(request_id.clone(), generated_token_result)
+                .await
+                .unwrap();
+        }
+
+        assert!(request_task.poll().is_ready());
+
+        let audit_log_entry = entry_rx.try_recv().unwrap();
+
+        assert_eq!(audit_log_entry.agent_id.as_deref(), Some("agent-audited"));
+        assert_eq!(
+            audit_log_entry.caller_key,
+            Some(CallerKey::from_api_key("sk-audited"))
+        );
+        assert_eq!(audit_log_entry.completion.as_deref(), Some("Hello"));
+        assert_eq!(audit_log_entry.endpoint, "/api/v1/inference_socket");
+        assert_eq!(audit_log_entry.error_kind, None);
+        assert_eq!(audit_log_entry.finish_reason, Some("max_tokens"));
+        assert_eq!(audit_log_entry.outcome, "succeeded");
+        assert!(
+            audit_log_entry
+                .prompt
+                .is_some_and(|prompt| prompt.contains("fixture prompt"))
+        );
+        assert_eq!(audit_log_entry.request_id, request_id);
+    }
+
     #[tokio::test(flavor = "current_thread")]
     async fn cancelled_request_holds_the_slot_until_the_agent_terminates_the_response_stream() {
         let pool = Arc::new(AgentControllerPool::default());
@@ -545,6 +1082,7 @@ mod tests {
             pool,
             Duration::from_mins(1),
             10,
+            RequestObservability::default(),
         ));
 
         let (chunk_tx, _chunk_rx) = mpsc::unbounded_channel();
@@ -557,7 +1095,9 @@ mod tests {
             buffered_request_manager,
             connection_close.clone(),
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             request_id.clone(),
             session_controller,
             CancellationToken::new(),
@@ -632,11 +1172,15 @@ mod tests {
         connection_close.cancel();
 
         forward_responses_stream(
+            &mut AuditLogEntry::default(),
             connection_close,
             claim_slot(agent_controller.clone()),
             inference_service_configuration_with_long_timeout(),
+            false,
             receive_response_controller,
+            RequestAdmission::default(),
             request_id,
+            &RequestMetrics::default(),
             session_controller,
             CancellationToken::new(),
         )
@@ -671,11 +1215,15 @@ mod tests {
         agent_controller.connection_close.cancel();
 
         forward_responses_stream(
+            &mut AuditLogEntry::default(),
             CancellationToken::new(),
             dispatched_agent,
             inference_service_configuration_with_long_timeout(),
+            false,
             receive_response_controller,
+            RequestAdmission::default(),
             request_id,
+            &RequestMetrics::default(),
             session_controller,
             CancellationToken::new(),
         )
@@ -710,6 +1258,7 @@ mod tests {
             pool,
             Duration::from_mins(1),
             10,
+            RequestObservability::default(),
         ));
 
         let (chunk_tx, _chunk_rx) = mpsc::unbounded_channel();
@@ -722,7 +1271,9 @@ mod tests {
             buffered_request_manager,
             connection_close.clone(),
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             "request-drain-shutdown".to_owned(),
             session_controller,
             shutdown.clone(),
@@ -764,6 +1315,7 @@ mod tests {
             pool,
             Duration::from_mins(1),
             10,
+            RequestObservability::default(),
         ));
 
         let (chunk_tx, _chunk_rx) = mpsc::unbounded_channel();
@@ -776,7 +1328,9 @@ mod tests {
             buffered_request_manager,
             connection_close.clone(),
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             request_id.clone(),
             session_controller,
             CancellationToken::new(),
@@ -833,11 +1387,15 @@ mod tests {
             ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
 
         forward_responses_stream(
+            &mut AuditLogEntry::default(),
             CancellationToken::new(),
             claim_slot(agent_controller.clone()),
             inference_service_configuration_with_long_timeout(),
+            false,
             receive_response_controller,
+            RequestAdmission::default(),
             request_id,
+            &RequestMetrics::default(),
             session_controller,
             CancellationToken::new(),
         )
@@ -867,6 +1425,7 @@ mod tests {
             pool,
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ));
 
         let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
@@ -877,7 +1436,9 @@ mod tests {
             buffered_request_manager,
             CancellationToken::new(),
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             "request-setup-fail".to_owned(),
             session_controller,
             CancellationToken::new(),
@@ -892,6 +1453,70 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn forward_responses_stream_measures_streamed_responses() {
+        let AgentControllerWithIncomingChannel {
+            agent_controller,
+            agent_message_rx: _agent_message_rx,
+        } = agent_controller_with_one_free_slot("agent-measured");
+
+        let request_id = "request-measured".to_owned();
+        let sender_collection = agent_controller.generate_tokens_sender_collection.clone();
+        let receive_response_controller = ManagesSendersController::from_request_id(
+            request_id.clone(),
+            sender_collection.clone(),
+        )
+        .unwrap();
+
+        for response in [
+            GeneratedTokenResult::TokenLogprob(TokenLogprob {
+                logprob: -0.1,
+                token: "first".to_owned(),
+                top_logprobs: Vec::new(),
+            }),
+            GeneratedTokenResult::ContentToken("first".to_owned()),
+            GeneratedTokenResult::TokenLogprob(TokenLogprob {
+                logprob: -0.2,
+                token: "second".to_owned(),
+                top_logprobs: Vec::new(),
+            }),
+            GeneratedTokenResult::ContentToken("second".to_owned()),
+            GeneratedTokenResult::Done(GenerationSummary::default()),
+        ] {
+            sender_collection
+                .forward_response(request_id.clone(), response)
+                .await
+                .unwrap();
+        }
+
+        let (chunk_tx, _chunk_rx) = mpsc::unbounded_channel();
+        let session_controller =
+            ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
+        let request_metrics = RequestMetrics::default();
+
+        let outcome = forward_responses_stream(
+            &mut AuditLogEntry::default(),
+            CancellationToken::new(),
+            claim_slot(agent_controller),
+            inference_service_configuration_with_long_timeout(),
+            false,
+            receive_response_controller,
+            RequestAdmission::default(),
+            request_id,
+            &request_metrics,
+            session_controller,
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(matches!(
+            outcome,
+            AgentResponseForwardingOutcome::Finished(RequestOutcome::Succeeded)
+        ));
+        assert_eq!(request_metrics.time_to_first_token.count(), 1);
+        assert_eq!(request_metrics.inter_token_latency.count(), 1);
+    }
+
     #[tokio::test]
     async fn forward_responses_stream_stops_responding_when_client_send_fails() {
         let AgentControllerWithIncomingChannel {
@@ -930,11 +1555,15 @@ mod tests {
             ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
 
         forward_responses_stream(
+            &mut AuditLogEntry::default(),
             CancellationToken::new(),
             claim_slot(agent_controller.clone()),
             inference_service_configuration_with_long_timeout(),
+            false,
             receive_response_controller,
+            RequestAdmission::default(),
             request_id,
+            &RequestMetrics::default(),
             session_controller,
             CancellationToken::new(),
         )
@@ -962,6 +1591,7 @@ mod tests {
             pool,
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ));
 
         let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
@@ -976,7 +1606,9 @@ mod tests {
             buffered_request_manager,
             CancellationToken::new(),
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             "request-shutdown".to_owned(),
             session_controller,
             shutdown,
@@ -1001,6 +1633,7 @@ mod tests {
                 pool,
                 Duration::from_secs(1),
                 10,
+                RequestObservability::default(),
             ));
 
             let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
@@ -1017,7 +1650,9 @@ mod tests {
                 buffered_request_manager,
                 connection_close,
                 inference_service_configuration_with_long_timeout(),
+                None,
                 raw_prompt_params(),
+                RequestAdmission::default(),
                 "request-simultaneous-shutdown".to_owned(),
                 session_controller,
                 shutdown,
@@ -1044,18 +1679,24 @@ mod tests {
         pool.register_agent_controller("agent-overflow".to_owned(), agent_controller)
             .unwrap();
 
-        let buffered_request_manager =
-            Arc::new(BufferedRequestManager::new(pool, Duration::from_secs(1), 0));
+        let buffered_request_manager = Arc::new(BufferedRequestManager::new(
+            pool,
+            Duration::from_secs(1),
+            0,
+            RequestObservability::default(),
+        ));
 
         let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
         let session_controller =
             ChunkForwardingSessionController::new(chunk_tx, IdentityTransformer::new());
 
         request_from_agent(
-            buffered_request_manager,
+            buffered_request_manager.clone(),
             CancellationToken::new(),
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             "request-overflow".to_owned(),
             session_controller,
             CancellationToken::new(),
@@ -1068,6 +1709,13 @@ mod tests {
             discriminant(&forwarded),
             discriminant(&TransformResult::Chunk(String::new()))
         );
+        assert_eq!(
+            buffered_request_manager
+                .observability
+                .request_metrics
+                .requests_by_endpoint_and_outcome(),
+            vec![(String::new(), RequestOutcome::Rejected, 1)]
+        );
     }
 
     #[tokio::test]
@@ -1077,6 +1725,7 @@ mod tests {
             pool,
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ));
 
         let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
@@ -1091,7 +1740,9 @@ mod tests {
             buffered_request_manager,
             connection_close,
             inference_service_configuration_with_long_timeout(),
+            None,
             raw_prompt_params(),
+            RequestAdmission::default(),
             "request-connection-close".to_owned(),
             session_controller,
             CancellationToken::new(),
@@ -1126,11 +1777,15 @@ mod tests {
         connection_close.cancel();
 
         forward_responses_stream(
+            &mut AuditLogEntry::default(),
             connection_close,
             claim_slot(agent_controller),
             inference_service_configuration_with_long_timeout(),
+            false,
             receive_response_controller,
+            RequestAdmission::default(),
             request_id,
+            &RequestMetrics::default(),
             session_controller,
             CancellationToken::new(),
         )
@@ -1170,11 +1825,15 @@ mod tests {
             shutdown.cancel();
 
             forward_responses_stream(
+                &mut AuditLogEntry::default(),
                 connection_close,
                 claim_slot(agent_controller),
                 inference_service_configuration_with_long_timeout(),
+                false,
                 receive_response_controller,
+                RequestAdmission::default(),
                 request_id,
+                &RequestMetrics::default(),
                 session_controller,
                 shutdown,
             )
@@ -1212,11 +1871,15 @@ mod tests {
         shutdown.cancel();
 
         forward_responses_stream(
+            &mut AuditLogEntry::default(),
             CancellationToken::new(),
             claim_slot(agent_controller),
             inference_service_configuration_with_long_timeout(),
+            false,
             receive_response_controller,
+            RequestAdmission::default(),
             request_id,
+            &RequestMetrics::default(),
             session_controller,
             shutdown,
         )
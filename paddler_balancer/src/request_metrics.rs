@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::sync::atomic::AtomicU64;
+use std::time::Duration;
+
+use llama_cpp_bindings_types::TokenUsage;
+use paddler_messaging::atomic_value::AtomicValue;
+use parking_lot::Mutex;
+
+use crate::latency_histogram::LatencyHistogram;
+use crate::request_outcome::RequestOutcome;
+
+/// Measurements of the inference requests served by the balancer since it started.
+pub struct RequestMetrics {
+    pub completion_tokens: AtomicValue<AtomicU64>,
+    pub inter_token_latency: LatencyHistogram,
+    pub prompt_tokens: AtomicValue<AtomicU64>,
+    pub queue_wait: LatencyHistogram,
+    pub request_duration: LatencyHistogram,
+    requests: Mutex<BTreeMap<(String, RequestOutcome), u64>>,
+    pub time_to_first_token: LatencyHistogram,
+}
+
+impl RequestMetrics {
+    pub fn record_finished_request(
+        &self,
+        duration: Duration,
+        endpoint: &str,
+        outcome: RequestOutcome,
+    ) {
+        self.request_duration.observe(duration);
+
+        *self
+            .requests
+            .lock()
+            .entry((endpoint.to_owned(), outcome))
+            .or_default() += 1;
+    }
+
+    pub fn record_token_usage(&self, token_usage: &TokenUsage) {
+        self.completion_tokens
+            .increment_by(token_usage.completion_tokens());
+        self.prompt_tokens.increment_by(token_usage.prompt_tokens);
+    }
+
+    pub fn start_sampling_latencies(&self) {
+        self.inter_token_latency.start_sampling();
+        self.queue_wait.start_sampling();
+        self.request_duration.start_sampling();
+        self.time_to_first_token.start_sampling();
+    }
+
+    #[must_use]
+    pub fn requests_by_endpoint_and_outcome(&self) -> Vec<(String, RequestOutcome, u64)> {
+        self.requests
+            .lock()
+            .iter()
+            .map(|((endpoint, outcome), count)| (endpoint.clone(), *outcome, *count))
+            .collect()
+    }
+}
+
+impl Default for RequestMetrics {
+    fn default() -> Self {
+        Self {
+            completion_tokens: AtomicValue::<AtomicU64>::new(0),
+            inter_token_latency: LatencyHistogram::default(),
+            prompt_tokens: AtomicValue::<AtomicU64>::new(0),
+            queue_wait: LatencyHistogram::default(),
+            request_duration: LatencyHistogram::default(),
+            requests: Mutex::new(BTreeMap::new()),
+            time_to_first_token: LatencyHistogram::default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_are_counted_by_endpoint_and_outcome() {
+        let request_metrics = RequestMetrics::default();
+
+        request_metrics.record_finished_request(
+            Duration::from_millis(10),
+            "/v1/chat/completions",
+            RequestOutcome::Succeeded,
+        );
+        request_metrics.record_finished_request(
+            Duration::from_millis(20),
+            "/v1/chat/completions",
+            RequestOutcome::Succeeded,
+        );
+        request_metrics.record_finished_request(
+            Duration::from_millis(30),
+            "/api/v1/inference_socket",
+            RequestOutcome::Rejected,
+        );
+
+        assert_eq!(
+            request_metrics.requests_by_endpoint_and_outcome(),
+            vec![
+                (
+                    "/api/v1/inference_socket".to_owned(),
+                    RequestOutcome::Rejected,
+                    1
+                ),
+                (
+                    "/v1/chat/completions".to_owned(),
+                    RequestOutcome::Succeeded,
+                    2
+                ),
+            ]
+        );
+        assert_eq!(request_metrics.request_duration.count(), 3);
+    }
+}
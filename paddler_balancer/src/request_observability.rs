@@ -0,0 +1,102 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use actix_web::HttpMessage as _;
+use actix_web::HttpRequest;
+use nanoid::nanoid;
+use paddler_tracing::tracer::Tracer;
+
+use crate::audit_log::AuditLog;
+use crate::autoscaling_signal::AutoscalingSignal;
+use crate::caller_key::CallerKey;
+use crate::request_endpoint::request_endpoint;
+use crate::request_metrics::RequestMetrics;
+use crate::request_outcome::RequestOutcome;
+
+/// Everything that watches requests pass through the balancer without influencing them.
+#[derive(Clone, Default)]
+pub struct RequestObservability {
+    pub audit_log: AuditLog,
+    pub autoscaling_signal: Arc<AutoscalingSignal>,
+    pub request_metrics: Arc<RequestMetrics>,
+    pub tracer: Tracer,
+}
+
+impl RequestObservability {
+    /// For requests turned away before they could be dispatched to an agent.
+    pub fn record_rejection(
+        &self,
+        caller_key: Option<CallerKey>,
+        duration: Duration,
+        endpoint: String,
+        error_kind: &'static str,
+    ) {
+        let mut audit_log_entry = self.audit_log.start_entry(endpoint, nanoid!());
+
+        audit_log_entry.caller_key = caller_key;
+        audit_log_entry.error_kind = Some(error_kind);
+        audit_log_entry.outcome = RequestOutcome::Rejected.label();
+        audit_log_entry.set_duration(duration);
+
+        self.request_metrics.record_finished_request(
+            duration,
+            &audit_log_entry.endpoint,
+            RequestOutcome::Rejected,
+        );
+        self.audit_log.record(audit_log_entry);
+    }
+
+    pub fn record_rejected_request(
+        &self,
+        request: &HttpRequest,
+        duration: Duration,
+        error_kind: &'static str,
+    ) {
+        self.record_rejection(
+            request.extensions().get::<CallerKey>().cloned(),
+            duration,
+            request_endpoint(request),
+            error_kind,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::sync::mpsc;
+
+    use super::*;
+    use crate::audit_log::AUDIT_LOG_CHANNEL_CAPACITY;
+
+    #[test]
+    fn rejections_are_counted_and_audited() {
+        let (entry_tx, mut entry_rx) = mpsc::channel(AUDIT_LOG_CHANNEL_CAPACITY);
+        let request_observability = RequestObservability {
+            audit_log: AuditLog::new(entry_tx, None),
+            ..RequestObservability::default()
+        };
+
+        request_observability.record_rejection(
+            Some(CallerKey::from_api_key("sk-test")),
+            Duration::from_millis(2),
+            "/v1/embeddings".to_owned(),
+            "rate_limited",
+        );
+
+        let audit_log_entry = entry_rx.try_recv().unwrap();
+
+        assert_eq!(audit_log_entry.endpoint, "/v1/embeddings");
+        assert_eq!(audit_log_entry.error_kind, Some("rate_limited"));
+        assert_eq!(audit_log_entry.outcome, "rejected");
+        assert_eq!(
+            audit_log_entry.caller_key,
+            Some(CallerKey::from_api_key("sk-test"))
+        );
+        assert_eq!(
+            request_observability
+                .request_metrics
+                .requests_by_endpoint_and_outcome(),
+            vec![("/v1/embeddings".to_owned(), RequestOutcome::Rejected, 1)]
+        );
+    }
+}
@@ -0,0 +1,22 @@
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum RequestOutcome {
+    /// The client went away before the response was complete.
+    Cancelled,
+    Failed,
+    /// The request never reached an agent, because the buffer was full, waiting for a
+    /// slot timed out or the balancer was shutting down.
+    Rejected,
+    Succeeded,
+}
+
+impl RequestOutcome {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Cancelled => "cancelled",
+            Self::Failed => "failed",
+            Self::Rejected => "rejected",
+            Self::Succeeded => "succeeded",
+        }
+    }
+}
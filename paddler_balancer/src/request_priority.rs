@@ -0,0 +1,111 @@
+use std::str::FromStr;
+
+use actix_web::HttpMessage as _;
+use actix_web::HttpRequest;
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+
+pub const REQUEST_PRIORITY_HEADER: &str = "X-Paddler-Priority";
+
+/// Buffered requests are served class by class in declaration order, and in
+/// arrival order within a class.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum RequestPriority {
+    #[default]
+    Interactive,
+    Batch,
+}
+
+impl RequestPriority {
+    pub const ALL: [Self; 2] = [Self::Interactive, Self::Batch];
+
+    /// The API key may assign a class to every request made with it. The header can
+    /// demote a request below that class, but never promote it above.
+    #[must_use]
+    pub fn from_request(request: &HttpRequest) -> Self {
+        let assigned_priority = request
+            .extensions()
+            .get::<Self>()
+            .copied()
+            .unwrap_or_default();
+        let requested_priority = request
+            .headers()
+            .get(REQUEST_PRIORITY_HEADER)
+            .and_then(|header_value| header_value.to_str().ok())
+            .and_then(|header_value| header_value.trim().parse::<Self>().ok());
+
+        requested_priority.map_or(assigned_priority, |requested_priority| {
+            requested_priority.max(assigned_priority)
+        })
+    }
+}
+
+impl FromStr for RequestPriority {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "batch" => Ok(Self::Batch),
+            "interactive" => Ok(Self::Interactive),
+            other => Err(anyhow!(
+                "Unsupported request priority '{other}' (expected 'interactive' or 'batch')"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    #[test]
+    fn defaults_to_interactive() {
+        let request = TestRequest::default().to_http_request();
+
+        assert_eq!(
+            RequestPriority::from_request(&request),
+            RequestPriority::Interactive
+        );
+    }
+
+    #[test]
+    fn header_selects_the_class() {
+        let request = TestRequest::default()
+            .insert_header((REQUEST_PRIORITY_HEADER, "batch"))
+            .to_http_request();
+
+        assert_eq!(
+            RequestPriority::from_request(&request),
+            RequestPriority::Batch
+        );
+    }
+
+    #[test]
+    fn header_cannot_promote_above_the_api_key_class() {
+        let request = TestRequest::default()
+            .insert_header((REQUEST_PRIORITY_HEADER, "interactive"))
+            .to_http_request();
+
+        request.extensions_mut().insert(RequestPriority::Batch);
+
+        assert_eq!(
+            RequestPriority::from_request(&request),
+            RequestPriority::Batch
+        );
+    }
+
+    #[test]
+    fn ignores_unknown_header_values() {
+        let request = TestRequest::default()
+            .insert_header((REQUEST_PRIORITY_HEADER, "urgent"))
+            .to_http_request();
+
+        assert_eq!(
+            RequestPriority::from_request(&request),
+            RequestPriority::Interactive
+        );
+    }
+}
@@ -0,0 +1,309 @@
+use std::time::Instant;
+
+use actix_web::Error;
+use actix_web::HttpMessage as _;
+use actix_web::HttpRequest;
+use actix_web::HttpResponse;
+use actix_web::body::EitherBody;
+use actix_web::body::MessageBody;
+use actix_web::dev::ServiceRequest;
+use actix_web::dev::ServiceResponse;
+use actix_web::http::header;
+use actix_web::middleware::Next;
+use actix_web::web::Data;
+use url::form_urlencoded;
+
+use crate::api_key_store::ApiKeyStore;
+use crate::caller_key::CallerKey;
+use crate::compatibility::openai_service::openai_error::OpenAIError;
+use crate::request_observability::RequestObservability;
+
+fn is_websocket_upgrade(request: &HttpRequest) -> bool {
+    request
+        .headers()
+        .get(header::UPGRADE)
+        .and_then(|upgrade| upgrade.to_str().ok())
+        .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+}
+
+/// The `Bearer` scheme is matched case-insensitively. Browsers cannot set headers on websocket
+/// handshakes, so those may pass the key as an `api_key` query parameter instead.
+fn presented_api_key(request: &HttpRequest) -> Option<String> {
+    if let Some(authorization) = request.headers().get(header::AUTHORIZATION) {
+        return authorization
+            .to_str()
+            .ok()
+            .and_then(|authorization| authorization.trim_start().split_once(' '))
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case("bearer"))
+            .map(|(_, api_key)| api_key.trim().to_owned());
+    }
+
+    if is_websocket_upgrade(request) {
+        return form_urlencoded::parse(request.query_string().as_bytes())
+            .find(|(name, _)| name == "api_key")
+            .map(|(_, api_key)| api_key.into_owned());
+    }
+
+    None
+}
+
+fn unauthorized(message: &str) -> HttpResponse {
+    HttpResponse::Unauthorized()
+        .content_type("application/json")
+        .body(
+            OpenAIError {
+                error_type: "invalid_request_error",
+                message: message.to_owned(),
+            }
+            .to_envelope()
+            .to_string(),
+        )
+}
+
+/// Passes every request through when the app has no `ApiKeyStore` registered.
+pub async fn require_api_key(
+    request: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, Error> {
+    let received_at = Instant::now();
+    let Some(api_key_store) = request.app_data::<Data<ApiKeyStore>>().cloned() else {
+        return Ok(next.call(request).await?.map_into_left_body());
+    };
+
+    if request.path() == "/health" {
+        return Ok(next.call(request).await?.map_into_left_body());
+    }
+
+    let response = match presented_api_key(request.request()) {
+        None => unauthorized(
+            "You didn't provide an API key. Pass it in the Authorization header as 'Bearer <key>'.",
+        ),
+        Some(api_key) if api_key_store.is_authorized(&api_key) => {
+            request
+                .extensions_mut()
+                .insert(CallerKey::from_api_key(&api_key));
+
+            if let Some(priority) = api_key_store.assigned_priority(&api_key) {
+                request.extensions_mut().insert(priority);
+            }
+
+            return Ok(next.call(request).await?.map_into_left_body());
+        }
+        Some(_) => unauthorized("Incorrect API key provided."),
+    };
+
+    if let Some(request_observability) = request.app_data::<Data<RequestObservability>>() {
+        request_observability.record_rejected_request(
+            request.request(),
+            received_at.elapsed(),
+            "unauthorized",
+        );
+    }
+
+    Ok(request.into_response(response).map_into_right_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write as _;
+    use std::sync::Arc;
+
+    use actix_web::App;
+    use actix_web::HttpResponse;
+    use actix_web::http::StatusCode;
+    use actix_web::middleware::from_fn;
+    use actix_web::test;
+    use actix_web::web;
+    use serde_json::Value;
+    use tempfile::NamedTempFile;
+
+    use super::*;
+    use crate::request_outcome::RequestOutcome;
+    use crate::request_priority::REQUEST_PRIORITY_HEADER;
+    use crate::request_priority::RequestPriority;
+
+    fn api_key_store() -> (NamedTempFile, Arc<ApiKeyStore>) {
+        let mut file = NamedTempFile::new().unwrap();
+
+        file.write_all(b"sk-valid\n").unwrap();
+
+        let api_key_store = Arc::new(ApiKeyStore::load(file.path().to_path_buf()).unwrap());
+
+        (file, api_key_store)
+    }
+
+    async fn status_for(request: test::TestRequest) -> StatusCode {
+        let (_file, api_key_store) = api_key_store();
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(require_api_key))
+                .app_data(Data::from(api_key_store))
+                .route("/health", web::get().to(HttpResponse::Ok))
+                .route("/api/v1/inference_socket", web::get().to(HttpResponse::Ok))
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        test::call_service(&app, request.to_request())
+            .await
+            .status()
+    }
+
+    #[actix_web::test]
+    async fn accepts_a_valid_bearer_token() {
+        let status = status_for(
+            test::TestRequest::post()
+                .uri("/v1/chat/completions")
+                .insert_header((header::AUTHORIZATION, "Bearer sk-valid")),
+        )
+        .await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn matches_the_bearer_scheme_case_insensitively() {
+        for authorization in ["bearer sk-valid", "BEARER sk-valid"] {
+            let status = status_for(
+                test::TestRequest::post()
+                    .uri("/v1/chat/completions")
+                    .insert_header((header::AUTHORIZATION, authorization)),
+            )
+            .await;
+
+            assert_eq!(status, StatusCode::OK);
+        }
+
+        assert_eq!(
+            status_for(
+                test::TestRequest::post()
+                    .uri("/v1/chat/completions")
+                    .insert_header((header::AUTHORIZATION, "Basic sk-valid")),
+            )
+            .await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[actix_web::test]
+    async fn rejects_a_missing_or_wrong_token_with_an_openai_error() {
+        let (_file, api_key_store) = api_key_store();
+        let request_observability = RequestObservability::default();
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(require_api_key))
+                .app_data(Data::from(api_key_store))
+                .app_data(Data::new(request_observability.clone()))
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/v1/chat/completions")
+                .insert_header((header::AUTHORIZATION, "Bearer sk-wrong"))
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let body: Value = test::read_body_json(response).await;
+
+        assert_eq!(body["error"]["type"], "invalid_request_error");
+        assert_eq!(
+            request_observability
+                .request_metrics
+                .requests_by_endpoint_and_outcome(),
+            vec![(
+                "/v1/chat/completions".to_owned(),
+                RequestOutcome::Rejected,
+                1
+            )]
+        );
+        assert_eq!(
+            status_for(test::TestRequest::post().uri("/v1/chat/completions")).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[actix_web::test]
+    async fn health_is_always_reachable() {
+        assert_eq!(
+            status_for(test::TestRequest::get().uri("/health")).await,
+            StatusCode::OK
+        );
+    }
+
+    #[actix_web::test]
+    async fn websocket_handshake_may_pass_the_key_as_a_query_parameter() {
+        assert_eq!(
+            status_for(
+                test::TestRequest::get()
+                    .uri("/api/v1/inference_socket?api_key=sk-valid")
+                    .insert_header((header::UPGRADE, "websocket"))
+            )
+            .await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status_for(test::TestRequest::get().uri("/api/v1/inference_socket?api_key=sk-valid"))
+                .await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[actix_web::test]
+    async fn passes_through_without_a_registered_store() {
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(require_api_key))
+                .route("/v1/chat/completions", web::post().to(HttpResponse::Ok)),
+        )
+        .await;
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/v1/chat/completions")
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[actix_web::test]
+    async fn assigns_the_priority_class_of_the_api_key() {
+        let mut file = NamedTempFile::new().unwrap();
+
+        file.write_all(b"sk-nightly batch\n").unwrap();
+
+        let api_key_store = Arc::new(ApiKeyStore::load(file.path().to_path_buf()).unwrap());
+        let app = test::init_service(
+            App::new()
+                .wrap(from_fn(require_api_key))
+                .app_data(Data::from(api_key_store))
+                .route(
+                    "/v1/chat/completions",
+                    web::post().to(|request: HttpRequest| async move {
+                        format!("{:?}", RequestPriority::from_request(&request))
+                    }),
+                ),
+        )
+        .await;
+
+        let response = test::call_service(
+            &app,
+            test::TestRequest::post()
+                .uri("/v1/chat/completions")
+                .insert_header((header::AUTHORIZATION, "Bearer sk-nightly"))
+                .insert_header((REQUEST_PRIORITY_HEADER, "interactive"))
+                .to_request(),
+        )
+        .await;
+
+        assert_eq!(test::read_body(response).await, "Batch");
+    }
+}
@@ -7,9 +7,12 @@ use crate::cluster_token_generation_mode::TOKEN_GENERATION_DISABLED_MESSAGE;
 
 pub fn require_token_generation_enabled(
     balancer_applicable_state_holder: &BalancerApplicableStateHolder,
+    model_pool: Option<&str>,
 ) -> Result<(), Error> {
-    match ClusterTokenGenerationMode::from_applicable_state_holder(balancer_applicable_state_holder)
-    {
+    match ClusterTokenGenerationMode::from_applicable_state_holder(
+        balancer_applicable_state_holder,
+        model_pool,
+    ) {
         ClusterTokenGenerationMode::Enabled => Ok(()),
         ClusterTokenGenerationMode::DisabledForEmbeddings => {
             Err(ErrorNotImplemented(TOKEN_GENERATION_DISABLED_MESSAGE))
@@ -19,6 +22,8 @@ pub fn require_token_generation_enabled(
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use paddler_messaging::agent_desired_model::AgentDesiredModel;
     use paddler_messaging::agent_desired_state::AgentDesiredState;
     use paddler_messaging::inference_parameters::InferenceParameters;
@@ -41,6 +46,7 @@ mod tests {
                     model: AgentDesiredModel::LocalToAgent("model.gguf".to_owned()),
                     multimodal_projection: AgentDesiredModel::None,
                 },
+                model_pools: BTreeMap::new(),
             },
         ));
 
@@ -51,16 +57,16 @@ mod tests {
     fn allows_generation_when_state_is_not_set() {
         let balancer_applicable_state_holder = BalancerApplicableStateHolder::default();
 
-        assert!(require_token_generation_enabled(&balancer_applicable_state_holder).is_ok());
+        assert!(require_token_generation_enabled(&balancer_applicable_state_holder, None).is_ok());
     }
 
     #[test]
     fn allows_generation_when_embeddings_are_disabled() {
-        assert!(require_token_generation_enabled(&holder_with_embeddings(false)).is_ok());
+        assert!(require_token_generation_enabled(&holder_with_embeddings(false), None).is_ok());
     }
 
     #[test]
     fn rejects_generation_when_embeddings_are_enabled() {
-        assert!(require_token_generation_enabled(&holder_with_embeddings(true)).is_err());
+        assert!(require_token_generation_enabled(&holder_with_embeddings(true), None).is_err());
     }
 }
@@ -0,0 +1,10 @@
+use std::sync::Arc;
+
+use paddler_messaging::agent_desired_state::AgentDesiredState;
+
+use crate::agent_controller::AgentController;
+
+pub struct RolledOutAgent {
+    pub agent_controller: Arc<AgentController>,
+    pub previous_desired_state: Option<AgentDesiredState>,
+}
@@ -0,0 +1,73 @@
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RolloutBatchSize {
+    Agents(usize),
+    Percentage(u8),
+}
+
+impl RolloutBatchSize {
+    /// Always at least one agent, so a rollout cannot stall on a small fleet.
+    #[must_use]
+    pub fn agents_per_batch(self, total_agents: usize) -> usize {
+        let agents = match self {
+            Self::Agents(agents) => agents,
+            Self::Percentage(percentage) => (total_agents * usize::from(percentage)).div_ceil(100),
+        };
+
+        agents.max(1)
+    }
+}
+
+impl FromStr for RolloutBatchSize {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        if let Some(percentage) = input.strip_suffix('%') {
+            return match percentage.parse::<u8>() {
+                Ok(percentage @ 1..=100) => Ok(Self::Percentage(percentage)),
+                _ => Err(anyhow!(
+                    "Rollout batch percentage must be between 1% and 100%, got '{input}'"
+                )),
+            };
+        }
+
+        match input.parse::<usize>() {
+            Ok(agents @ 1..) => Ok(Self::Agents(agents)),
+            _ => Err(anyhow!(
+                "Rollout batch size must be a positive number of agents or a percentage, got '{input}'"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_agent_counts_and_percentages() {
+        assert_eq!(
+            RolloutBatchSize::from_str("2").unwrap(),
+            RolloutBatchSize::Agents(2)
+        );
+        assert_eq!(
+            RolloutBatchSize::from_str("25%").unwrap(),
+            RolloutBatchSize::Percentage(25)
+        );
+        assert!(RolloutBatchSize::from_str("0").is_err());
+        assert!(RolloutBatchSize::from_str("0%").is_err());
+        assert!(RolloutBatchSize::from_str("101%").is_err());
+    }
+
+    #[test]
+    fn rounds_percentage_up_to_at_least_one_agent() {
+        assert_eq!(RolloutBatchSize::Percentage(25).agents_per_batch(10), 3);
+        assert_eq!(RolloutBatchSize::Percentage(10).agents_per_batch(3), 1);
+        assert_eq!(RolloutBatchSize::Percentage(50).agents_per_batch(0), 1);
+    }
+}
@@ -0,0 +1,28 @@
+use std::str::FromStr;
+
+use anyhow::Error;
+use anyhow::Result;
+use anyhow::anyhow;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RolloutFailureAction {
+    /// Stops the rollout for good; agents that were already updated stay on the new state.
+    Abort,
+    /// Sends the previous desired state back to every agent the rollout updated.
+    #[default]
+    RollBack,
+}
+
+impl FromStr for RolloutFailureAction {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input {
+            "abort" => Ok(Self::Abort),
+            "roll-back" => Ok(Self::RollBack),
+            other => Err(anyhow!(
+                "Unsupported rollout failure action '{other}' (expected 'abort' or 'roll-back')"
+            )),
+        }
+    }
+}
@@ -0,0 +1,14 @@
+use crate::rollout_batch_size::RolloutBatchSize;
+use crate::rollout_failure_action::RolloutFailureAction;
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RolloutStrategy {
+    #[default]
+    AllAtOnce,
+    /// Applies a new desired state to one batch of agents at a time and moves on only after
+    /// every agent in the batch reports that it applied it.
+    Gradual {
+        batch_size: RolloutBatchSize,
+        failure_action: RolloutFailureAction,
+    },
+}
@@ -12,6 +12,7 @@ use tokio_util::sync::CancellationToken;
 
 use crate::run_http_service_parameters::RunHttpServiceParameters;
 use crate::serve_http_until_shutdown::serve_http_until_shutdown;
+use crate::verified_client_certificate::VerifiedClientCertificate;
 
 pub async fn run_http_service<TAppFactory, TAppEntry, TResponseBody>(
     cancellation_token: CancellationToken,
@@ -19,6 +20,7 @@ pub async fn run_http_service<TAppFactory, TAppEntry, TResponseBody>(
         app_factory,
         bind_addr,
         service_name,
+        tls_configuration,
         worker_count,
     }: RunHttpServiceParameters<TAppFactory>,
 ) -> Result<()>
@@ -33,14 +35,24 @@ where
         > + 'static,
     TResponseBody: MessageBody + 'static,
 {
-    let server = HttpServer::new(app_factory)
+    let http_server = HttpServer::new(app_factory)
         .workers(worker_count)
         .keep_alive(KeepAlive::Disabled)
         .h1_allow_half_closed(false)
         .disable_signals()
-        .bind(bind_addr)
-        .with_context(|| format!("Unable to bind {service_name} to {bind_addr}"))?
-        .run();
+        .on_connect(VerifiedClientCertificate::record_on_connect);
+
+    let server = match tls_configuration {
+        Some(tls_configuration) => http_server.bind_rustls_0_23(
+            bind_addr,
+            tls_configuration.load_server_config().with_context(|| {
+                format!("Unable to load the TLS configuration of {service_name}")
+            })?,
+        ),
+        None => http_server.bind(bind_addr),
+    }
+    .with_context(|| format!("Unable to bind {service_name} to {bind_addr}"))?
+    .run();
 
     serve_http_until_shutdown(cancellation_token, server)
         .await
@@ -70,6 +82,7 @@ mod tests {
                     app_factory: App::new,
                     bind_addr: SocketAddr::from(([127, 0, 0, 1], 0)),
                     service_name: "balancer::test_service",
+                    tls_configuration: None,
                     worker_count: 1,
                 },
             ),
@@ -1,8 +1,11 @@
 use std::net::SocketAddr;
 
+use crate::tls_configuration::TlsConfiguration;
+
 pub struct RunHttpServiceParameters<TAppFactory> {
     pub app_factory: TAppFactory,
     pub bind_addr: SocketAddr,
     pub service_name: &'static str,
+    pub tls_configuration: Option<TlsConfiguration>,
     pub worker_count: usize,
 }
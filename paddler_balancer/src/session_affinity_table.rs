@@ -0,0 +1,108 @@
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+/// Bounded so that a stream of one-off prompts cannot grow the balancer's memory without limit.
+pub const SESSION_AFFINITY_TABLE_CAPACITY: usize = 16_384;
+
+struct SessionAffinityEntry {
+    agent_id: String,
+    last_used: u64,
+}
+
+pub struct SessionAffinityTable {
+    capacity: usize,
+    clock: u64,
+    entries: HashMap<u64, SessionAffinityEntry>,
+    recency: BTreeMap<u64, u64>,
+}
+
+impl SessionAffinityTable {
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            clock: 0,
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+        }
+    }
+
+    #[must_use]
+    pub fn agent_id_for(&self, affinity_key: u64) -> Option<&str> {
+        self.entries
+            .get(&affinity_key)
+            .map(|entry| entry.agent_id.as_str())
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn remember(&mut self, affinity_key: u64, agent_id: String) {
+        self.clock += 1;
+
+        if let Some(previous) = self.entries.insert(
+            affinity_key,
+            SessionAffinityEntry {
+                agent_id,
+                last_used: self.clock,
+            },
+        ) {
+            self.recency.remove(&previous.last_used);
+        }
+
+        self.recency.insert(self.clock, affinity_key);
+
+        while self.entries.len() > self.capacity {
+            let Some((_, least_recently_used_key)) = self.recency.pop_first() else {
+                break;
+            };
+
+            self.entries.remove(&least_recently_used_key);
+        }
+    }
+}
+
+impl Default for SessionAffinityTable {
+    fn default() -> Self {
+        Self::new(SESSION_AFFINITY_TABLE_CAPACITY)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remembers_the_latest_agent_for_a_key() {
+        let mut table = SessionAffinityTable::new(4);
+
+        table.remember(1, "first".to_owned());
+        table.remember(1, "second".to_owned());
+
+        assert_eq!(table.agent_id_for(1), Some("second"));
+        assert_eq!(table.agent_id_for(2), None);
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn evicts_the_least_recently_used_key_at_capacity() {
+        let mut table = SessionAffinityTable::new(2);
+
+        table.remember(1, "agent".to_owned());
+        table.remember(2, "agent".to_owned());
+        table.remember(1, "agent".to_owned());
+        table.remember(3, "agent".to_owned());
+
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.agent_id_for(1), Some("agent"));
+        assert_eq!(table.agent_id_for(2), None);
+        assert_eq!(table.agent_id_for(3), Some("agent"));
+    }
+}
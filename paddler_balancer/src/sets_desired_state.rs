@@ -1,8 +1,9 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use paddler_messaging::agent_desired_state::AgentDesiredState;
 
 #[async_trait]
 pub trait SetsDesiredState {
-    async fn set_desired_state(&self, desired_state: AgentDesiredState) -> Result<()>;
+    type DesiredState;
+
+    async fn set_desired_state(&self, desired_state: Self::DesiredState) -> Result<()>;
 }
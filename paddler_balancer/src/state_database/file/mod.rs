@@ -122,6 +122,7 @@ impl StateDatabase for File {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
     use std::path::PathBuf;
 
     use log::LevelFilter;
@@ -149,6 +150,7 @@ mod tests {
             chat_template_override: None,
             inference_parameters: BalancerDesiredState::default().inference_parameters,
             model: AgentDesiredModel::LocalToAgent("stored_model_path".to_owned()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         };
@@ -14,6 +14,8 @@ pub trait StateDatabase: Send + Sync {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use paddler_messaging::agent_desired_model::AgentDesiredModel;
     use paddler_messaging::chat_template::ChatTemplate;
     use paddler_messaging::inference_parameters::InferenceParameters;
@@ -29,6 +31,7 @@ mod tests {
             chat_template_override: None,
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::LocalToAgent("test_model_path".to_owned()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         };
@@ -72,6 +75,7 @@ mod tests {
             chat_template_override: Some(chat_template.clone()),
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::LocalToAgent("test_model_path".to_owned()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: true,
         };
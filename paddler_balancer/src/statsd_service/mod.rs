@@ -1,4 +1,5 @@
 pub mod configuration;
+mod reported_counter_totals;
 
 use std::net::UdpSocket;
 use std::sync::Arc;
@@ -6,11 +7,17 @@ use std::sync::Arc;
 use anyhow::Context as _;
 use anyhow::Result;
 use async_trait::async_trait;
+use cadence::Counted;
 use cadence::Gauged;
 use cadence::MetricError;
 use cadence::StatsdClient;
+use cadence::Timed;
 use cadence::UdpMetricSink;
 use log::error;
+use paddler_messaging::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
+use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
+use paddler_messaging::produces_snapshot::ProducesSnapshot;
 use tokio::time::MissedTickBehavior;
 use tokio::time::interval;
 use tokio_util::sync::CancellationToken;
@@ -19,12 +26,81 @@ use trzcina::Service;
 use crate::agent_controller_pool::AgentControllerPool;
 use crate::agent_controller_pool_total_slots::AgentControllerPoolTotalSlots;
 use crate::buffered_request_manager::BufferedRequestManager;
+use crate::latency_histogram::LatencyHistogram;
 use crate::statsd_service::configuration::Configuration as StatsdServiceConfiguration;
+use crate::statsd_service::reported_counter_totals::ReportedCounterTotals;
+
+const AGENT_STATE_APPLICATION_STATUSES: [AgentStateApplicationStatus; 5] = [
+    AgentStateApplicationStatus::Applied,
+    AgentStateApplicationStatus::AttemptedAndNotAppliable,
+    AgentStateApplicationStatus::AttemptedAndRetrying,
+    AgentStateApplicationStatus::Fresh,
+    AgentStateApplicationStatus::Stuck,
+];
 
 fn log_statsd_error(error: MetricError) {
     error!("Statsd error: {error}");
 }
 
+fn report_agent_metrics(client: &StatsdClient, agent: &AgentControllerSnapshot) -> Result<()> {
+    let agent_name = agent.name.as_deref().unwrap_or_default();
+    let agent_gauges = [
+        (
+            "agent_slots_processing",
+            u64::try_from(agent.slots_processing)
+                .context("agent slots_processing count is negative")?,
+        ),
+        (
+            "agent_slots_total",
+            u64::try_from(agent.slots_total).context("agent slots_total count is negative")?,
+        ),
+        ("agent_issues", u64::try_from(agent.issues.len())?),
+        ("agent_download_current", agent.download_current),
+        ("agent_download_total", agent.download_total),
+    ];
+
+    for (name, value) in agent_gauges {
+        client
+            .gauge_with_tags(name, value)
+            .with_tag("agent_id", &agent.id)
+            .with_tag("agent_name", agent_name)
+            .try_send()?;
+    }
+
+    // Every status is reported, so the one the agent left no longer reads as current.
+    for status in &AGENT_STATE_APPLICATION_STATUSES {
+        client
+            .gauge_with_tags(
+                "agent_state_application_status",
+                u64::from(agent.state_application_status == *status),
+            )
+            .with_tag("agent_id", &agent.id)
+            .with_tag("agent_name", agent_name)
+            .with_tag("status", &format!("{status:?}"))
+            .try_send()?;
+    }
+
+    Ok(())
+}
+
+/// Sent as timers, with the sampling rate accounting for observations left out of the sample.
+fn report_histogram(client: &StatsdClient, name: &str, histogram: &LatencyHistogram) -> Result<()> {
+    let sample = histogram.take_unreported_sample();
+    let sampling_rate = sample.sampling_rate();
+
+    for observation in sample.observations {
+        let timer = client.time_with_tags(name, observation);
+
+        if sampling_rate < 1.0 {
+            timer.with_sampling_rate(sampling_rate).try_send()?;
+        } else {
+            timer.try_send()?;
+        }
+    }
+
+    Ok(())
+}
+
 pub struct StatsdService {
     pub agent_controller_pool: Arc<AgentControllerPool>,
     pub buffered_request_manager: Arc<BufferedRequestManager>,
@@ -32,12 +108,17 @@ pub struct StatsdService {
 }
 
 impl StatsdService {
-    fn report_metrics(&self, client: &StatsdClient) -> Result<()> {
+    fn report_metrics(
+        &self,
+        client: &StatsdClient,
+        reported_counter_totals: &mut ReportedCounterTotals,
+    ) -> Result<()> {
         let AgentControllerPoolTotalSlots {
             slots_processing,
             slots_total,
         } = self.agent_controller_pool.total_slots();
         let requests_buffered = self.buffered_request_manager.buffered_request_counter.get();
+        let request_metrics = &self.buffered_request_manager.observability.request_metrics;
 
         let slots_processing =
             u64::try_from(slots_processing).context("slots_processing count is negative")?;
@@ -48,6 +129,84 @@ impl StatsdService {
         client.gauge("slots_processing", slots_processing)?;
         client.gauge("slots_total", slots_total)?;
         client.gauge("requests_buffered", requests_buffered)?;
+
+        let counters = [
+            (
+                "dispatch_affinity_hits",
+                self.agent_controller_pool.session_affinity_hits.get(),
+            ),
+            (
+                "dispatch_affinity_misses",
+                self.agent_controller_pool.session_affinity_misses.get(),
+            ),
+            (
+                "agents_rejected",
+                self.agent_controller_pool
+                    .rejected_agent_registrations
+                    .get(),
+            ),
+            (
+                "agents_rejected_model_pool",
+                self.agent_controller_pool
+                    .rejected_model_pool_registrations
+                    .get(),
+            ),
+            ("prompt_tokens", request_metrics.prompt_tokens.get()),
+            ("completion_tokens", request_metrics.completion_tokens.get()),
+            (
+                "audit_log_entries_dropped",
+                self.buffered_request_manager
+                    .observability
+                    .audit_log
+                    .dropped_entries(),
+            ),
+        ];
+
+        for (name, total) in counters {
+            client.count(
+                name,
+                reported_counter_totals.increment_since_last_report(name.to_owned(), total),
+            )?;
+        }
+
+        let AgentControllerPoolSnapshot { agents } = self.agent_controller_pool.make_snapshot()?;
+
+        for agent in &agents {
+            if let Err(err) = report_agent_metrics(client, agent) {
+                error!("Failed to report metrics of agent {:?}: {err}", agent.id);
+            }
+        }
+
+        for (endpoint, outcome, total) in request_metrics.requests_by_endpoint_and_outcome() {
+            let increment = reported_counter_totals.increment_since_last_report(
+                format!("requests_finished|{endpoint}|{}", outcome.label()),
+                total,
+            );
+
+            client
+                .count_with_tags("requests_finished", increment)
+                .with_tag("endpoint", &endpoint)
+                .with_tag("outcome", outcome.label())
+                .try_send()?;
+        }
+
+        report_histogram(client, "queue_wait", &request_metrics.queue_wait)?;
+        report_histogram(
+            client,
+            "time_to_first_token",
+            &request_metrics.time_to_first_token,
+        )?;
+        report_histogram(
+            client,
+            "inter_token_latency",
+            &request_metrics.inter_token_latency,
+        )?;
+        report_histogram(
+            client,
+            "request_duration",
+            &request_metrics.request_duration,
+        )?;
+
         client.flush()?;
 
         Ok(())
@@ -68,6 +227,12 @@ impl Service for StatsdService {
             .with_error_handler(log_statsd_error)
             .build();
 
+        self.buffered_request_manager
+            .observability
+            .request_metrics
+            .start_sampling_latencies();
+
+        let mut reported_counter_totals = ReportedCounterTotals::default();
         let mut ticker = interval(self.configuration.statsd_reporting_interval);
 
         ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
@@ -76,7 +241,7 @@ impl Service for StatsdService {
             tokio::select! {
                 () = shutdown.cancelled() => break Ok(()),
                 _ = ticker.tick() => {
-                    if let Err(err) = self.report_metrics(&client) {
+                    if let Err(err) = self.report_metrics(&client, &mut reported_counter_totals) {
                         error!("Failed to report metrics: {err}");
                     }
                 }
@@ -87,28 +252,23 @@ impl Service for StatsdService {
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeSet;
     use std::net::SocketAddr;
-    use std::sync::atomic::AtomicBool;
     use std::sync::atomic::AtomicI32;
-    use std::sync::atomic::AtomicU64;
     use std::time::Duration;
 
     use cadence::BufferedSpyMetricSink;
     use cadence::ErrorKind;
     use cadence::MetricError;
     use cadence::SpyMetricSink;
-    use parking_lot::RwLock;
     use tokio::net::UdpSocket as TokioUdpSocket;
     use tokio::sync::mpsc;
 
     use super::*;
     use crate::agent_controller::AgentController;
-    use crate::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
-    use crate::embedding_sender_collection::EmbeddingSenderCollection;
     use crate::generate_tokens_sender_collection::GenerateTokensSenderCollection;
     use crate::model_metadata_sender_collection::ModelMetadataSenderCollection;
-    use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
+    use crate::request_observability::RequestObservability;
+    use crate::request_outcome::RequestOutcome;
     use paddler_messaging::atomic_value::AtomicValue;
 
     const REPORTING_INTERVAL: Duration = Duration::from_secs(1);
@@ -125,34 +285,15 @@ mod tests {
         pool.register_agent_controller(
             agent_id.to_owned(),
             Arc::new(AgentController {
-                agent_message_tx,
-                chat_template_override_sender_collection: Arc::new(
-                    ChatTemplateOverrideSenderCollection::default(),
-                ),
-                connection_close: CancellationToken::new(),
-                desired_slots_total: AtomicValue::<AtomicI32>::new(0),
-                download_current: AtomicValue::<AtomicU64>::new(0),
-                download_filename: RwLock::new(None),
-                download_indeterminate: AtomicValue::<AtomicBool>::new(true),
-                download_total: AtomicValue::<AtomicU64>::new(0),
-                embedding_sender_collection: Arc::new(EmbeddingSenderCollection::default()),
                 generate_tokens_sender_collection: Arc::new(
                     GenerateTokensSenderCollection::default(),
                 ),
-                id: agent_id.to_owned(),
-                issues: RwLock::new(BTreeSet::new()),
                 model_metadata_sender_collection: Arc::new(
                     ModelMetadataSenderCollection::default(),
                 ),
-                model_path: RwLock::new(None),
-                name: None,
-                newest_update_version: AtomicValue::<AtomicI32>::new(0),
                 slots_processing: AtomicValue::<AtomicI32>::new(slots_processing),
                 slots_total: AtomicValue::<AtomicI32>::new(slots_total),
-                state_application_status_code: AtomicValue::<AtomicI32>::new(
-                    AgentStateApplicationStatus::Fresh as i32,
-                ),
-                uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
+                ..AgentController::new_for_test(agent_id, agent_message_tx)
             }),
         )
         .unwrap();
@@ -164,6 +305,7 @@ mod tests {
             agent_controller_pool.clone(),
             REPORTING_INTERVAL,
             10,
+            RequestObservability::default(),
         ));
 
         StatsdService {
@@ -194,12 +336,14 @@ mod tests {
         let sink = UdpMetricSink::from(receiver_addr, sender_socket).unwrap();
         let client = StatsdClient::builder(STATSD_PREFIX, sink).build();
 
-        service.report_metrics(&client).unwrap();
+        service
+            .report_metrics(&client, &mut ReportedCounterTotals::default())
+            .unwrap();
 
         let mut received_lines: Vec<String> = Vec::new();
         let mut datagram = [0_u8; 1024];
 
-        for _ in 0..3 {
+        for _ in 0..10 {
             let byte_count = receiver.recv(&mut datagram).await.unwrap();
 
             received_lines.push(String::from_utf8(datagram[..byte_count].to_vec()).unwrap());
@@ -208,6 +352,11 @@ mod tests {
         assert!(received_lines.contains(&"paddler.slots_processing:0|g".to_owned()));
         assert!(received_lines.contains(&"paddler.slots_total:0|g".to_owned()));
         assert!(received_lines.contains(&"paddler.requests_buffered:0|g".to_owned()));
+        assert!(received_lines.contains(&"paddler.dispatch_affinity_hits:0|c".to_owned()));
+        assert!(received_lines.contains(&"paddler.dispatch_affinity_misses:0|c".to_owned()));
+        assert!(received_lines.contains(&"paddler.agents_rejected:0|c".to_owned()));
+        assert!(received_lines.contains(&"paddler.agents_rejected_model_pool:0|c".to_owned()));
+        assert!(received_lines.contains(&"paddler.audit_log_entries_dropped:0|c".to_owned()));
     }
 
     #[tokio::test]
@@ -245,7 +394,7 @@ mod tests {
         let client = StatsdClient::builder(STATSD_PREFIX, sink).build();
         let service = build_service(SocketAddr::from(([127, 0, 0, 1], 0)));
 
-        let result = service.report_metrics(&client);
+        let result = service.report_metrics(&client, &mut ReportedCounterTotals::default());
 
         assert!(result.err().unwrap().is::<MetricError>());
     }
@@ -256,7 +405,7 @@ mod tests {
         let client = StatsdClient::builder(STATSD_PREFIX, sink).build();
         let service = build_service(SocketAddr::from(([127, 0, 0, 1], 0)));
 
-        let result = service.report_metrics(&client);
+        let result = service.report_metrics(&client, &mut ReportedCounterTotals::default());
 
         assert!(result.err().unwrap().is::<MetricError>());
         assert_eq!(receiver.len(), 1);
@@ -268,7 +417,7 @@ mod tests {
         let client = StatsdClient::builder(STATSD_PREFIX, sink).build();
         let service = build_service(SocketAddr::from(([127, 0, 0, 1], 0)));
 
-        let result = service.report_metrics(&client);
+        let result = service.report_metrics(&client, &mut ReportedCounterTotals::default());
 
         assert!(result.err().unwrap().is::<MetricError>());
         assert_eq!(receiver.len(), 2);
@@ -282,7 +431,7 @@ mod tests {
 
         drop(receiver);
 
-        let result = service.report_metrics(&client);
+        let result = service.report_metrics(&client, &mut ReportedCounterTotals::default());
 
         assert!(result.err().unwrap().is::<MetricError>());
     }
@@ -294,7 +443,7 @@ mod tests {
         register_agent_controller_with_slots(&service.agent_controller_pool, "agent", -1, 0);
 
         let client = StatsdClient::builder(STATSD_PREFIX, SpyMetricSink::new().1).build();
-        let result = service.report_metrics(&client);
+        let result = service.report_metrics(&client, &mut ReportedCounterTotals::default());
 
         assert_eq!(
             result.err().unwrap().to_string(),
@@ -309,7 +458,7 @@ mod tests {
         register_agent_controller_with_slots(&service.agent_controller_pool, "agent", 0, -1);
 
         let client = StatsdClient::builder(STATSD_PREFIX, SpyMetricSink::new().1).build();
-        let result = service.report_metrics(&client);
+        let result = service.report_metrics(&client, &mut ReportedCounterTotals::default());
 
         assert_eq!(
             result.err().unwrap().to_string(),
@@ -327,7 +476,7 @@ mod tests {
             .decrement();
 
         let client = StatsdClient::builder(STATSD_PREFIX, SpyMetricSink::new().1).build();
-        let result = service.report_metrics(&client);
+        let result = service.report_metrics(&client, &mut ReportedCounterTotals::default());
 
         assert_eq!(
             result.err().unwrap().to_string(),
@@ -335,6 +484,111 @@ mod tests {
         );
     }
 
+    fn received_lines(datagrams: impl Iterator<Item = Vec<u8>>) -> Vec<String> {
+        datagrams
+            .map(|datagram| String::from_utf8(datagram).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn report_metrics_tags_agent_gauges_with_the_agent() {
+        let (receiver, sink) = SpyMetricSink::new();
+        let client = StatsdClient::builder(STATSD_PREFIX, sink).build();
+        let service = build_service(SocketAddr::from(([127, 0, 0, 1], 0)));
+
+        register_agent_controller_with_slots(&service.agent_controller_pool, "agent", 1, 3);
+
+        service
+            .report_metrics(&client, &mut ReportedCounterTotals::default())
+            .unwrap();
+
+        let received_lines = received_lines(receiver.try_iter());
+
+        assert!(
+            received_lines
+                .contains(&"paddler.agent_slots_total:3|g|#agent_id:agent,agent_name:".to_owned())
+        );
+        assert!(received_lines.contains(
+            &"paddler.agent_state_application_status:1|g|#agent_id:agent,agent_name:,status:Fresh"
+                .to_owned()
+        ));
+        assert!(received_lines.contains(
+            &"paddler.agent_state_application_status:0|g|#agent_id:agent,agent_name:,status:Applied"
+                .to_owned()
+        ));
+    }
+
+    #[test]
+    fn report_metrics_reports_the_other_agents_when_one_fails() {
+        let (receiver, sink) = SpyMetricSink::new();
+        let client = StatsdClient::builder(STATSD_PREFIX, sink).build();
+        let service = build_service(SocketAddr::from(([127, 0, 0, 1], 0)));
+
+        register_agent_controller_with_slots(&service.agent_controller_pool, "broken", -1, 0);
+        register_agent_controller_with_slots(&service.agent_controller_pool, "healthy", 1, 3);
+
+        service
+            .report_metrics(&client, &mut ReportedCounterTotals::default())
+            .unwrap();
+
+        assert!(
+            received_lines(receiver.try_iter()).contains(
+                &"paddler.agent_slots_total:3|g|#agent_id:healthy,agent_name:".to_owned()
+            )
+        );
+    }
+
+    #[test]
+    fn report_metrics_sends_counters_as_increments_since_the_previous_report() {
+        let (receiver, sink) = SpyMetricSink::new();
+        let client = StatsdClient::builder(STATSD_PREFIX, sink).build();
+        let service = build_service(SocketAddr::from(([127, 0, 0, 1], 0)));
+        let mut reported_counter_totals = ReportedCounterTotals::default();
+        let request_metrics = &service
+            .buffered_request_manager
+            .observability
+            .request_metrics;
+
+        request_metrics.start_sampling_latencies();
+        request_metrics.record_finished_request(
+            Duration::from_millis(20),
+            "/v1/chat/completions",
+            RequestOutcome::Succeeded,
+        );
+
+        service
+            .report_metrics(&client, &mut reported_counter_totals)
+            .unwrap();
+
+        let first_report = received_lines(receiver.try_iter());
+
+        assert!(
+            first_report.contains(
+                &"paddler.requests_finished:1|c|#endpoint:/v1/chat/completions,outcome:succeeded"
+                    .to_owned()
+            )
+        );
+        assert!(first_report.contains(&"paddler.request_duration:20|ms".to_owned()));
+
+        service
+            .report_metrics(&client, &mut reported_counter_totals)
+            .unwrap();
+
+        let second_report = received_lines(receiver.try_iter());
+
+        assert!(
+            second_report.contains(
+                &"paddler.requests_finished:0|c|#endpoint:/v1/chat/completions,outcome:succeeded"
+                    .to_owned()
+            )
+        );
+        assert!(
+            !second_report
+                .iter()
+                .any(|line| line.starts_with("paddler.request_duration"))
+        );
+    }
+
     #[test]
     fn log_statsd_error_logs_the_metric_error() {
         log::set_max_level(log::LevelFilter::Trace);
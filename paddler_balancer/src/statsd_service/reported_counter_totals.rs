@@ -0,0 +1,43 @@
+use std::collections::BTreeMap;
+
+/// Totals of the balancer's counters as of the previous report, so statsd only receives what
+/// was added since.
+#[derive(Default)]
+pub struct ReportedCounterTotals {
+    totals: BTreeMap<String, u64>,
+}
+
+impl ReportedCounterTotals {
+    pub fn increment_since_last_report(&mut self, key: String, total: u64) -> u64 {
+        let previous_total = self.totals.insert(key, total).unwrap_or_default();
+
+        total.saturating_sub(previous_total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_the_increment_since_the_previous_report() {
+        let mut reported_counter_totals = ReportedCounterTotals::default();
+
+        assert_eq!(
+            reported_counter_totals.increment_since_last_report("requests".to_owned(), 3),
+            3
+        );
+        assert_eq!(
+            reported_counter_totals.increment_since_last_report("requests".to_owned(), 5),
+            2
+        );
+        assert_eq!(
+            reported_counter_totals.increment_since_last_report("tokens".to_owned(), 1),
+            1
+        );
+        assert_eq!(
+            reported_counter_totals.increment_since_last_report("requests".to_owned(), 5),
+            0
+        );
+    }
+}
@@ -0,0 +1,181 @@
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+use rustls::RootCertStore;
+use rustls::ServerConfig;
+use rustls::crypto::ring::default_provider;
+use rustls::pki_types::CertificateDer;
+use rustls::pki_types::PrivateKeyDer;
+use rustls::pki_types::pem::PemObject as _;
+use rustls::server::WebPkiClientVerifier;
+
+fn load_certificates(path: &Path) -> Result<Vec<CertificateDer<'static>>> {
+    let certificates: Vec<CertificateDer<'static>> = CertificateDer::pem_file_iter(path)
+        .and_then(Iterator::collect)
+        .with_context(|| format!("Unable to read certificates from {}", path.display()))?;
+
+    if certificates.is_empty() {
+        bail!("No certificates found in {}", path.display());
+    }
+
+    Ok(certificates)
+}
+
+/// PEM-encoded certificate chain and private key a balancer service presents to its clients.
+#[derive(Clone, Debug)]
+pub struct TlsConfiguration {
+    pub certificate_chain_path: PathBuf,
+    /// Clients may present a certificate signed by this CA. Agents joining the management service
+    /// must present one.
+    pub client_ca_path: Option<PathBuf>,
+    pub private_key_path: PathBuf,
+}
+
+impl TlsConfiguration {
+    pub fn load_server_config(&self) -> Result<ServerConfig> {
+        let crypto_provider = Arc::new(default_provider());
+        let server_config_builder = ServerConfig::builder_with_provider(crypto_provider.clone())
+            .with_safe_default_protocol_versions()
+            .context("Unable to select TLS protocol versions")?;
+
+        let server_config_builder = match &self.client_ca_path {
+            Some(client_ca_path) => {
+                let mut client_ca_roots = RootCertStore::empty();
+
+                for certificate in load_certificates(client_ca_path)? {
+                    client_ca_roots.add(certificate).with_context(|| {
+                        format!(
+                            "Unable to trust the client CA from {}",
+                            client_ca_path.display()
+                        )
+                    })?;
+                }
+
+                let client_certificate_verifier = WebPkiClientVerifier::builder_with_provider(
+                    Arc::new(client_ca_roots),
+                    crypto_provider,
+                )
+                .allow_unauthenticated()
+                .build()
+                .context("Unable to build the client certificate verifier")?;
+
+                server_config_builder.with_client_cert_verifier(client_certificate_verifier)
+            }
+            None => server_config_builder.with_no_client_auth(),
+        };
+
+        let private_key =
+            PrivateKeyDer::from_pem_file(&self.private_key_path).with_context(|| {
+                format!(
+                    "Unable to read the private key from {}",
+                    self.private_key_path.display()
+                )
+            })?;
+
+        server_config_builder
+            .with_single_cert(
+                load_certificates(&self.certificate_chain_path)?,
+                private_key,
+            )
+            .context("The certificate chain does not match the private key")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use rcgen::CertifiedKey;
+    use rcgen::generate_simple_self_signed;
+    use tempfile::TempDir;
+
+    use super::TlsConfiguration;
+
+    fn write_self_signed_certificate(temp_dir: &TempDir, name: &str) -> (PathBuf, PathBuf) {
+        let CertifiedKey { cert, signing_key } =
+            generate_simple_self_signed(vec!["localhost".to_owned()]).unwrap();
+        let certificate_path = temp_dir.path().join(format!("{name}.crt"));
+        let private_key_path = temp_dir.path().join(format!("{name}.key"));
+
+        fs::write(&certificate_path, cert.pem()).unwrap();
+        fs::write(&private_key_path, signing_key.serialize_pem()).unwrap();
+
+        (certificate_path, private_key_path)
+    }
+
+    #[test]
+    fn loads_a_server_config_without_client_authentication() {
+        let temp_dir = TempDir::new().unwrap();
+        let (certificate_chain_path, private_key_path) =
+            write_self_signed_certificate(&temp_dir, "server");
+
+        TlsConfiguration {
+            certificate_chain_path,
+            client_ca_path: None,
+            private_key_path,
+        }
+        .load_server_config()
+        .unwrap();
+    }
+
+    #[test]
+    fn loads_a_server_config_that_verifies_client_certificates() {
+        let temp_dir = TempDir::new().unwrap();
+        let (certificate_chain_path, private_key_path) =
+            write_self_signed_certificate(&temp_dir, "server");
+        let (client_ca_path, _client_ca_private_key_path) =
+            write_self_signed_certificate(&temp_dir, "client_ca");
+
+        TlsConfiguration {
+            certificate_chain_path,
+            client_ca_path: Some(client_ca_path),
+            private_key_path,
+        }
+        .load_server_config()
+        .unwrap();
+    }
+
+    #[test]
+    fn rejects_a_certificate_file_without_certificates() {
+        let temp_dir = TempDir::new().unwrap();
+        let (_certificate_chain_path, private_key_path) =
+            write_self_signed_certificate(&temp_dir, "server");
+        let empty_certificate_path = temp_dir.path().join("empty.crt");
+
+        fs::write(&empty_certificate_path, "").unwrap();
+
+        let error = TlsConfiguration {
+            certificate_chain_path: empty_certificate_path,
+            client_ca_path: None,
+            private_key_path,
+        }
+        .load_server_config()
+        .unwrap_err();
+
+        assert!(error.to_string().contains("No certificates found"));
+    }
+
+    #[test]
+    fn rejects_a_private_key_that_does_not_match_the_certificate() {
+        let temp_dir = TempDir::new().unwrap();
+        let (certificate_chain_path, _private_key_path) =
+            write_self_signed_certificate(&temp_dir, "server");
+        let (_other_certificate_path, other_private_key_path) =
+            write_self_signed_certificate(&temp_dir, "other");
+
+        let error = TlsConfiguration {
+            certificate_chain_path,
+            client_ca_path: None,
+            private_key_path: other_private_key_path,
+        }
+        .load_server_config()
+        .unwrap_err();
+
+        assert!(error.to_string().contains("does not match the private key"));
+    }
+}
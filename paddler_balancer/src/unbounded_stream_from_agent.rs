@@ -18,18 +18,22 @@ use crate::chunk_forwarding_session_controller::transforms_outgoing_message::Tra
 use crate::handles_agent_streaming_response::HandlesAgentStreamingResponse;
 use crate::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use crate::manages_senders::ManagesSenders;
+use crate::provides_affinity_key::ProvidesAffinityKey;
+use crate::request_admission::RequestAdmission;
 use crate::request_from_agent::request_from_agent;
 use paddler_messaging::management_socket::agent::request::Request as AgentJsonRpcRequest;
 
 pub fn unbounded_stream_from_agent<TParams, TTransformsOutgoingMessage>(
     buffered_request_manager: Arc<BufferedRequestManager>,
     inference_service_configuration: InferenceServiceConfiguration,
+    model_pool: Option<String>,
     params: TParams,
+    request_admission: RequestAdmission,
     transformer: TTransformsOutgoingMessage,
     shutdown: CancellationToken,
 ) -> impl Stream<Item = TTransformsOutgoingMessage::Output>
 where
-    TParams: Debug + Into<AgentJsonRpcRequest> + Send + 'static,
+    TParams: Clone + Debug + Into<AgentJsonRpcRequest> + ProvidesAffinityKey + Send + 'static,
     AgentController: HandlesAgentStreamingResponse<TParams>,
     <<AgentController as HandlesAgentStreamingResponse<TParams>>::SenderCollection as ManagesSenders>::Value: Debug + Into<OutgoingResponse> + StreamableResult,
     TTransformsOutgoingMessage: Clone + TransformsOutgoingMessage + Send + Sync + 'static,
@@ -48,7 +52,9 @@ where
                 buffered_request_manager,
                 connection_close,
                 inference_service_configuration,
+                model_pool,
                 params,
+                request_admission,
                 request_id,
                 session_controller,
                 shutdown,
@@ -69,8 +75,11 @@ mod tests {
 
     use super::*;
     use crate::agent_controller_pool::AgentControllerPool;
+    use crate::agent_failover_policy::AgentFailoverPolicy;
     use crate::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
     use crate::chunk_forwarding_session_controller::transform_result::TransformResult;
+    use crate::dispatch_strategy::DispatchStrategy;
+    use crate::request_observability::RequestObservability;
     use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 
     fn inference_service_configuration() -> InferenceServiceConfiguration {
@@ -78,8 +87,11 @@ mod tests {
 
         InferenceServiceConfiguration {
             addr: "127.0.0.1:0".parse().unwrap(),
+            agent_failover_policy: AgentFailoverPolicy::Disabled,
             cors_allowed_hosts: Vec::new(),
+            dispatch_strategy: DispatchStrategy::LeastBusy,
             inference_item_timeout: TIMEOUT_LONGER_THAN_ANY_TEST_RUN,
+            tls_configuration: None,
         }
     }
 
@@ -90,6 +102,7 @@ mod tests {
             pool,
             Duration::from_secs(1),
             10,
+            RequestObservability::default(),
         ));
 
         let shutdown = CancellationToken::new();
@@ -99,11 +112,18 @@ mod tests {
         let mut stream = Box::pin(unbounded_stream_from_agent(
             buffered_request_manager,
             inference_service_configuration(),
+            None,
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 1,
+                model: None,
                 raw_prompt: "fixture prompt".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
+            RequestAdmission::default(),
             IdentityTransformer::new(),
             shutdown,
         ));
@@ -0,0 +1,24 @@
+use std::any::Any;
+
+use actix_tls::accept::rustls_0_23::TlsStream;
+use actix_web::dev::Extensions;
+use actix_web::rt::net::TcpStream;
+
+/// Connection data recorded when the TLS peer presented a certificate signed by the client CA.
+pub struct VerifiedClientCertificate;
+
+impl VerifiedClientCertificate {
+    pub fn record_on_connect(connection: &dyn Any, extensions: &mut Extensions) {
+        let Some(tls_stream) = connection.downcast_ref::<TlsStream<TcpStream>>() else {
+            return;
+        };
+        let (_tcp_stream, server_connection) = tls_stream.get_ref();
+
+        if server_connection
+            .peer_certificates()
+            .is_some_and(|peer_certificates| !peer_certificates.is_empty())
+        {
+            extensions.insert(Self);
+        }
+    }
+}
@@ -1,9 +1,11 @@
 use std::net::SocketAddr;
 
 use super::template_data::TemplateData;
+use crate::tls_configuration::TlsConfiguration;
 
 #[derive(Clone)]
 pub struct Configuration {
     pub addr: SocketAddr,
     pub template_data: TemplateData,
+    pub tls_configuration: Option<TlsConfiguration>,
 }
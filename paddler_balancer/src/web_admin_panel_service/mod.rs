@@ -43,6 +43,7 @@ impl Service for WebAdminPanelService {
                 },
                 bind_addr: self.configuration.addr,
                 service_name,
+                tls_configuration: self.configuration.tls_configuration.clone(),
                 worker_count: 2,
             },
         )
@@ -84,6 +85,7 @@ mod tests {
                     statsd_prefix: "paddler".to_owned(),
                     statsd_reporting_interval: Duration::from_secs(10),
                 },
+                tls_configuration: None,
             },
         }
     }
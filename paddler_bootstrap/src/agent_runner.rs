@@ -2,10 +2,13 @@ use std::future::Future;
 use std::sync::Arc;
 
 use anyhow::Result;
+use paddler_agent::management_tls_configuration::ManagementTlsConfiguration;
 use paddler_agent::slot_aggregated_status::SlotAggregatedStatus;
+use paddler_tracing::otlp_trace_exporter_service::configuration::Configuration as OtlpTraceExporterServiceConfiguration;
 use tokio_util::sync::CancellationToken;
 use trzcina::ServiceShutdownOptions;
 
+use crate::agent_service_bundle::AgentBootstrapConfig;
 use crate::agent_service_bundle::AgentServiceBundle;
 use crate::run_service_manager::run_service_manager;
 use crate::service_thread::ServiceThread;
@@ -13,8 +16,12 @@ use crate::service_thread::ServiceThread;
 pub struct AgentRunnerParams {
     pub agent_name: Option<String>,
     pub cancellation_token: CancellationToken,
+    pub join_token: Option<String>,
     pub management_address: String,
+    pub management_tls_configuration: Option<ManagementTlsConfiguration>,
+    pub model_pool: Option<String>,
     pub slots: i32,
+    pub trace_exporter_configuration: Option<OtlpTraceExporterServiceConfiguration>,
 }
 
 pub struct AgentRunner {
@@ -28,11 +35,23 @@ impl AgentRunner {
         AgentRunnerParams {
             agent_name,
             cancellation_token,
+            join_token,
             management_address,
+            management_tls_configuration,
+            model_pool,
             slots,
+            trace_exporter_configuration,
         }: AgentRunnerParams,
     ) -> Self {
-        let bundle = AgentServiceBundle::new(agent_name, &management_address, slots);
+        let bundle = AgentServiceBundle::new(AgentBootstrapConfig {
+            agent_name,
+            join_token,
+            management_address,
+            management_tls_configuration,
+            model_pool,
+            slots,
+            trace_exporter_configuration,
+        });
         let slot_aggregated_status = bundle.slot_aggregated_status.clone();
 
         let thread = ServiceThread::spawn(cancellation_token, move |task_shutdown| {
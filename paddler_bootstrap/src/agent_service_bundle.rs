@@ -9,27 +9,53 @@ use paddler_agent::continue_from_raw_prompt_request::ContinueFromRawPromptReques
 use paddler_agent::generate_embedding_batch_request::GenerateEmbeddingBatchRequest;
 use paddler_agent::llamacpp_arbiter_service::LlamaCppArbiterService;
 use paddler_agent::management_socket_client_service::ManagementSocketClientService;
+use paddler_agent::management_tls_configuration::ManagementTlsConfiguration;
 use paddler_agent::model_metadata_holder::ModelMetadataHolder;
 use paddler_agent::reconciliation_service::ReconciliationService;
 use paddler_agent::slot_aggregated_status::SlotAggregatedStatus;
 use paddler_agent::slot_aggregated_status_manager::SlotAggregatedStatusManager;
-use paddler_messaging::agent_desired_state::AgentDesiredState;
+use paddler_messaging::management_socket::agent::notification_params::set_state_params::SetStateParams;
+use paddler_tracing::otlp_trace_exporter_service::OtlpTraceExporterService;
+use paddler_tracing::otlp_trace_exporter_service::configuration::Configuration as OtlpTraceExporterServiceConfiguration;
+use paddler_tracing::tracer::FINISHED_SPAN_CHANNEL_CAPACITY;
+use paddler_tracing::tracer::Tracer;
 use tokio::sync::mpsc;
 use trzcina::Service;
 use trzcina::ServiceBundle;
 
+pub struct AgentBootstrapConfig {
+    pub agent_name: Option<String>,
+    pub join_token: Option<String>,
+    pub management_address: String,
+    pub management_tls_configuration: Option<ManagementTlsConfiguration>,
+    pub model_pool: Option<String>,
+    pub slots: i32,
+    pub trace_exporter_configuration: Option<OtlpTraceExporterServiceConfiguration>,
+}
+
 pub struct AgentServiceBundle {
     pub slot_aggregated_status: Arc<SlotAggregatedStatus>,
     llamacpp_arbiter_service: LlamaCppArbiterService,
     management_socket_client_service: ManagementSocketClientService,
+    otlp_trace_exporter_service: Option<OtlpTraceExporterService>,
     reconciliation_service: ReconciliationService,
 }
 
 impl AgentServiceBundle {
     #[must_use]
-    pub fn new(agent_name: Option<String>, management_address: &str, slots: i32) -> Self {
+    pub fn new(
+        AgentBootstrapConfig {
+            agent_name,
+            join_token,
+            management_address,
+            management_tls_configuration,
+            model_pool,
+            slots,
+            trace_exporter_configuration,
+        }: AgentBootstrapConfig,
+    ) -> Self {
         let (agent_desired_state_tx, agent_desired_state_rx) =
-            mpsc::unbounded_channel::<AgentDesiredState>();
+            mpsc::unbounded_channel::<SetStateParams>();
         let (
             continue_from_conversation_history_request_tx,
             continue_from_conversation_history_request_rx,
@@ -59,27 +85,50 @@ impl AgentServiceBundle {
             slot_aggregated_status_manager,
         };
 
+        let mut tracer = Tracer::default();
+        let otlp_trace_exporter_service = trace_exporter_configuration.map(|configuration| {
+            let (finished_span_tx, finished_span_rx) =
+                mpsc::channel(FINISHED_SPAN_CHANNEL_CAPACITY);
+
+            tracer = Tracer::new(finished_span_tx);
+
+            OtlpTraceExporterService {
+                configuration,
+                finished_span_rx,
+            }
+        });
+
+        let management_socket_scheme = if management_tls_configuration.is_some() {
+            "wss"
+        } else {
+            "ws"
+        };
+
         let management_socket_client_service = ManagementSocketClientService {
             agent_applicable_state_holder: agent_applicable_state_holder.clone(),
             agent_desired_state_tx,
             continue_from_conversation_history_request_tx,
             continue_from_raw_prompt_request_tx,
             generate_embedding_batch_request_tx,
+            join_token,
             model_metadata_holder,
+            model_pool,
             name: agent_name,
             receive_stream_stopper_collection: Arc::default(),
             slot_aggregated_status: slot_aggregated_status.clone(),
             socket_url: format!(
-                "ws://{}/api/v1/agent_socket/{}",
-                management_address,
+                "{management_socket_scheme}://{management_address}/api/v1/agent_socket/{}",
                 nanoid!()
             ),
+            tls_configuration: management_tls_configuration,
+            tracer,
         };
 
         let reconciliation_service = ReconciliationService {
             agent_applicable_state_holder,
             agent_desired_state: None,
             agent_desired_state_rx,
+            desired_state_version: 0,
             is_converted_to_applicable_state: false,
             slot_aggregated_status: slot_aggregated_status.clone(),
         };
@@ -88,6 +137,7 @@ impl AgentServiceBundle {
             slot_aggregated_status,
             llamacpp_arbiter_service,
             management_socket_client_service,
+            otlp_trace_exporter_service,
             reconciliation_service,
         }
     }
@@ -96,10 +146,16 @@ impl AgentServiceBundle {
 #[async_trait]
 impl ServiceBundle for AgentServiceBundle {
     async fn services(self) -> Result<Vec<Box<dyn Service>>> {
-        Ok(vec![
+        let mut services: Vec<Box<dyn Service>> = vec![
             Box::new(self.llamacpp_arbiter_service),
             Box::new(self.management_socket_client_service),
             Box::new(self.reconciliation_service),
-        ])
+        ];
+
+        if let Some(service) = self.otlp_trace_exporter_service {
+            services.push(Box::new(service));
+        }
+
+        Ok(services)
     }
 }
@@ -1,18 +1,25 @@
 use std::future::Future;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
 use paddler_balancer::agent_controller_pool::AgentControllerPool;
+use paddler_balancer::audit_log_service::configuration::Configuration as AuditLogServiceConfiguration;
+use paddler_balancer::autoscaling_hook_service::configuration::Configuration as AutoscalingHookServiceConfiguration;
 use paddler_balancer::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+use paddler_balancer::buffered_request_limits::BufferedRequestLimits;
 use paddler_balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
 use paddler_balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler_balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
+use paddler_balancer::rate_limit_configuration::RateLimitConfiguration;
+use paddler_balancer::rollout_strategy::RolloutStrategy;
 use paddler_balancer::state_database_type::StateDatabaseType;
 use paddler_balancer::statsd_service::configuration::Configuration as StatsdServiceConfiguration;
 #[cfg(feature = "web_admin_panel")]
 use paddler_balancer::web_admin_panel_service::configuration::Configuration as WebAdminPanelServiceConfiguration;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
+use paddler_tracing::otlp_trace_exporter_service::configuration::Configuration as OtlpTraceExporterServiceConfiguration;
 use tokio::sync::broadcast;
 use tokio_util::sync::CancellationToken;
 use trzcina::ServiceShutdownOptions;
@@ -23,16 +30,24 @@ use crate::run_service_manager::run_service_manager;
 use crate::service_thread::ServiceThread;
 
 pub struct BalancerRunnerParams {
+    pub api_keys_file: Option<PathBuf>,
+    pub audit_log_service_configuration: Option<AuditLogServiceConfiguration>,
+    pub autoscaling_hook_service_configuration: Option<AutoscalingHookServiceConfiguration>,
+    pub autoscaling_target_utilization: f64,
+    pub batch_buffered_request_limits: Option<BufferedRequestLimits>,
     pub buffered_request_timeout: Duration,
     pub inference_service_configuration: InferenceServiceConfiguration,
     pub management_service_configuration: ManagementServiceConfiguration,
     pub max_buffered_requests: i32,
     pub openai_service_configuration: Option<OpenAIServiceConfiguration>,
     pub cancellation_token: CancellationToken,
+    pub rate_limit_configuration: RateLimitConfiguration,
+    pub rollout_strategy: RolloutStrategy,
     pub shutdown_options: ServiceShutdownOptions,
     pub state_database_type: StateDatabaseType,
     pub statsd_prefix: String,
     pub statsd_service_configuration: Option<StatsdServiceConfiguration>,
+    pub trace_exporter_configuration: Option<OtlpTraceExporterServiceConfiguration>,
     #[cfg(feature = "web_admin_panel")]
     pub web_admin_panel_service_configuration: Option<WebAdminPanelServiceConfiguration>,
 }
@@ -48,29 +63,45 @@ pub struct BalancerRunner {
 impl BalancerRunner {
     pub async fn start(
         BalancerRunnerParams {
+            api_keys_file,
+            audit_log_service_configuration,
+            autoscaling_hook_service_configuration,
+            autoscaling_target_utilization,
+            batch_buffered_request_limits,
             buffered_request_timeout,
             inference_service_configuration,
             management_service_configuration,
             max_buffered_requests,
             openai_service_configuration,
             cancellation_token,
+            rate_limit_configuration,
+            rollout_strategy,
             shutdown_options,
             state_database_type,
             statsd_prefix,
             statsd_service_configuration,
+            trace_exporter_configuration,
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration,
         }: BalancerRunnerParams,
     ) -> Result<Self> {
         let bundle = BalancerServiceBundle::new(BalancerBootstrapConfig {
+            api_keys_file,
+            audit_log_service_configuration,
+            autoscaling_hook_service_configuration,
+            autoscaling_target_utilization,
+            batch_buffered_request_limits,
             buffered_request_timeout,
             inference_service_configuration,
             management_service_configuration,
             max_buffered_requests,
             openai_service_configuration,
+            rate_limit_configuration,
+            rollout_strategy,
             state_database_type,
             statsd_prefix,
             statsd_service_configuration,
+            trace_exporter_configuration,
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration,
         })
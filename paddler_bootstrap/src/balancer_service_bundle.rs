@@ -1,10 +1,21 @@
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use paddler_balancer::agent_controller_pool::AgentControllerPool;
+use paddler_balancer::api_key_reload_service::ApiKeyReloadService;
+use paddler_balancer::api_key_store::ApiKeyStore;
+use paddler_balancer::audit_log::AUDIT_LOG_CHANNEL_CAPACITY;
+use paddler_balancer::audit_log::AuditLog;
+use paddler_balancer::audit_log_service::AuditLogService;
+use paddler_balancer::audit_log_service::configuration::Configuration as AuditLogServiceConfiguration;
+use paddler_balancer::autoscaling_hook_service::AutoscalingHookService;
+use paddler_balancer::autoscaling_hook_service::configuration::Configuration as AutoscalingHookServiceConfiguration;
+use paddler_balancer::autoscaling_signal::AutoscalingSignal;
 use paddler_balancer::balancer_applicable_state_holder::BalancerApplicableStateHolder;
+use paddler_balancer::buffered_request_limits::BufferedRequestLimits;
 use paddler_balancer::buffered_request_manager::BufferedRequestManager;
 use paddler_balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use paddler_balancer::compatibility::openai_service::OpenAIService;
@@ -16,7 +27,12 @@ use paddler_balancer::inference_service::configuration::Configuration as Inferen
 use paddler_balancer::management_service::ManagementService;
 use paddler_balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
 use paddler_balancer::model_metadata_sender_collection::ModelMetadataSenderCollection;
+use paddler_balancer::rate_limit_configuration::RateLimitConfiguration;
+use paddler_balancer::rate_limiter::RateLimiter;
 use paddler_balancer::reconciliation_service::ReconciliationService;
+use paddler_balancer::request_observability::RequestObservability;
+use paddler_balancer::request_priority::RequestPriority;
+use paddler_balancer::rollout_strategy::RolloutStrategy;
 use paddler_balancer::state_database::StateDatabase;
 use paddler_balancer::state_database::file::File;
 use paddler_balancer::state_database::memory::Memory;
@@ -28,19 +44,32 @@ use paddler_balancer::web_admin_panel_service::WebAdminPanelService;
 #[cfg(feature = "web_admin_panel")]
 use paddler_balancer::web_admin_panel_service::configuration::Configuration as WebAdminPanelServiceConfiguration;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
+use paddler_tracing::otlp_trace_exporter_service::OtlpTraceExporterService;
+use paddler_tracing::otlp_trace_exporter_service::configuration::Configuration as OtlpTraceExporterServiceConfiguration;
+use paddler_tracing::tracer::FINISHED_SPAN_CHANNEL_CAPACITY;
+use paddler_tracing::tracer::Tracer;
 use tokio::sync::broadcast;
+use tokio::sync::mpsc;
 use trzcina::Service;
 use trzcina::ServiceBundle;
 
 pub struct BalancerBootstrapConfig {
+    pub api_keys_file: Option<PathBuf>,
+    pub audit_log_service_configuration: Option<AuditLogServiceConfiguration>,
+    pub autoscaling_hook_service_configuration: Option<AutoscalingHookServiceConfiguration>,
+    pub autoscaling_target_utilization: f64,
+    pub batch_buffered_request_limits: Option<BufferedRequestLimits>,
     pub buffered_request_timeout: Duration,
     pub inference_service_configuration: InferenceServiceConfiguration,
     pub management_service_configuration: ManagementServiceConfiguration,
     pub max_buffered_requests: i32,
     pub openai_service_configuration: Option<OpenAIServiceConfiguration>,
+    pub rate_limit_configuration: RateLimitConfiguration,
+    pub rollout_strategy: RolloutStrategy,
     pub state_database_type: StateDatabaseType,
     pub statsd_prefix: String,
     pub statsd_service_configuration: Option<StatsdServiceConfiguration>,
+    pub trace_exporter_configuration: Option<OtlpTraceExporterServiceConfiguration>,
     #[cfg(feature = "web_admin_panel")]
     pub web_admin_panel_service_configuration: Option<WebAdminPanelServiceConfiguration>,
 }
@@ -51,10 +80,14 @@ pub struct BalancerServiceBundle {
     pub balancer_desired_state_tx: broadcast::Sender<BalancerDesiredState>,
     pub initial_desired_state: BalancerDesiredState,
     pub state_database: Arc<dyn StateDatabase>,
+    api_key_reload_service: Option<ApiKeyReloadService>,
+    audit_log_service: Option<AuditLogService>,
+    autoscaling_hook_service: Option<AutoscalingHookService>,
     inference_service: InferenceService,
     management_service: ManagementService,
     reconciliation_service: ReconciliationService,
     openai_service: Option<OpenAIService>,
+    otlp_trace_exporter_service: Option<OtlpTraceExporterService>,
     statsd_service: Option<StatsdService>,
     #[cfg(feature = "web_admin_panel")]
     web_admin_panel_service: Option<WebAdminPanelService>,
@@ -63,14 +96,22 @@ pub struct BalancerServiceBundle {
 impl BalancerServiceBundle {
     pub async fn new(
         BalancerBootstrapConfig {
+            api_keys_file,
+            audit_log_service_configuration,
+            autoscaling_hook_service_configuration,
+            autoscaling_target_utilization,
+            batch_buffered_request_limits,
             buffered_request_timeout,
             inference_service_configuration,
             management_service_configuration,
             max_buffered_requests,
             openai_service_configuration,
+            rate_limit_configuration,
+            rollout_strategy,
             state_database_type,
             statsd_prefix,
             statsd_service_configuration,
+            trace_exporter_configuration,
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration,
         }: BalancerBootstrapConfig,
@@ -79,11 +120,51 @@ impl BalancerServiceBundle {
 
         let agent_controller_pool = Arc::new(AgentControllerPool::default());
         let balancer_applicable_state_holder = Arc::new(BalancerApplicableStateHolder::default());
-        let buffered_request_manager = Arc::new(BufferedRequestManager::new(
+        let mut observability = RequestObservability {
+            autoscaling_signal: Arc::new(AutoscalingSignal::new(autoscaling_target_utilization)),
+            ..RequestObservability::default()
+        };
+        let audit_log_service = audit_log_service_configuration.map(|configuration| {
+            let (entry_tx, entry_rx) = mpsc::channel(AUDIT_LOG_CHANNEL_CAPACITY);
+
+            observability.audit_log = AuditLog::new(entry_tx, configuration.max_body_bytes);
+
+            AuditLogService {
+                configuration,
+                entry_rx,
+            }
+        });
+        let otlp_trace_exporter_service = trace_exporter_configuration.map(|configuration| {
+            let (finished_span_tx, finished_span_rx) =
+                mpsc::channel(FINISHED_SPAN_CHANNEL_CAPACITY);
+
+            observability.tracer = Tracer::new(finished_span_tx);
+
+            OtlpTraceExporterService {
+                configuration,
+                finished_span_rx,
+            }
+        });
+        let mut buffered_request_manager = BufferedRequestManager::new(
             agent_controller_pool.clone(),
             buffered_request_timeout,
             max_buffered_requests,
-        ));
+            observability,
+        );
+
+        if let Some(batch_buffered_request_limits) = batch_buffered_request_limits {
+            buffered_request_manager = buffered_request_manager.with_buffered_request_limits(
+                RequestPriority::Batch,
+                batch_buffered_request_limits,
+            );
+        }
+
+        let buffered_request_manager = Arc::new(buffered_request_manager);
+        let autoscaling_hook_service =
+            autoscaling_hook_service_configuration.map(|configuration| AutoscalingHookService {
+                buffered_request_manager: buffered_request_manager.clone(),
+                configuration,
+            });
         let chat_template_override_sender_collection =
             Arc::new(ChatTemplateOverrideSenderCollection::default());
         let embedding_sender_collection = Arc::new(EmbeddingSenderCollection::default());
@@ -100,12 +181,21 @@ impl BalancerServiceBundle {
         };
 
         let initial_desired_state = state_database.read_balancer_desired_state().await?;
+        let api_key_store = api_keys_file
+            .map(ApiKeyStore::load)
+            .transpose()?
+            .map(Arc::new);
+        let rate_limiter = rate_limit_configuration
+            .is_enabled()
+            .then(|| Arc::new(RateLimiter::new(rate_limit_configuration)));
 
         let inference_service = InferenceService {
             agent_controller_pool: agent_controller_pool.clone(),
+            api_key_store: api_key_store.clone(),
             balancer_applicable_state_holder: balancer_applicable_state_holder.clone(),
             buffered_request_manager: buffered_request_manager.clone(),
             configuration: inference_service_configuration.clone(),
+            rate_limiter: rate_limiter.clone(),
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration: web_admin_panel_service_configuration.clone(),
         };
@@ -131,14 +221,19 @@ impl BalancerServiceBundle {
             balancer_desired_state: initial_desired_state.clone(),
             balancer_desired_state_rx,
             is_converted_to_applicable_state: false,
+            rollout_strategy,
+            state_database: state_database.clone(),
         };
 
         let openai_service =
             openai_service_configuration.map(|openai_service_configuration| OpenAIService {
+                agent_controller_pool: agent_controller_pool.clone(),
+                api_key_store: api_key_store.clone(),
                 balancer_applicable_state_holder: balancer_applicable_state_holder.clone(),
                 buffered_request_manager: buffered_request_manager.clone(),
                 inference_service_configuration,
                 openai_service_configuration,
+                rate_limiter,
             });
 
         let statsd_service = statsd_service_configuration.map(|configuration| StatsdService {
@@ -147,6 +242,9 @@ impl BalancerServiceBundle {
             configuration,
         });
 
+        let api_key_reload_service =
+            api_key_store.map(|api_key_store| ApiKeyReloadService { api_key_store });
+
         #[cfg(feature = "web_admin_panel")]
         let web_admin_panel_service = web_admin_panel_service_configuration
             .map(|configuration| WebAdminPanelService { configuration });
@@ -157,10 +255,14 @@ impl BalancerServiceBundle {
             balancer_desired_state_tx,
             initial_desired_state,
             state_database,
+            api_key_reload_service,
+            audit_log_service,
+            autoscaling_hook_service,
             inference_service,
             management_service,
             reconciliation_service,
             openai_service,
+            otlp_trace_exporter_service,
             statsd_service,
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service,
@@ -177,10 +279,26 @@ impl ServiceBundle for BalancerServiceBundle {
             Box::new(self.reconciliation_service),
         ];
 
+        if let Some(service) = self.api_key_reload_service {
+            services.push(Box::new(service));
+        }
+
+        if let Some(service) = self.audit_log_service {
+            services.push(Box::new(service));
+        }
+
+        if let Some(service) = self.autoscaling_hook_service {
+            services.push(Box::new(service));
+        }
+
         if let Some(service) = self.openai_service {
             services.push(Box::new(service));
         }
 
+        if let Some(service) = self.otlp_trace_exporter_service {
+            services.push(Box::new(service));
+        }
+
         if let Some(service) = self.statsd_service {
             services.push(Box::new(service));
         }
@@ -198,17 +316,24 @@ impl ServiceBundle for BalancerServiceBundle {
 mod tests {
     use std::net::SocketAddr;
 
+    use paddler_balancer::agent_failover_policy::AgentFailoverPolicy;
+    use paddler_balancer::autoscaling_hook::AutoscalingHook;
+    use paddler_balancer::dispatch_strategy::DispatchStrategy;
     #[cfg(feature = "web_admin_panel")]
     use paddler_balancer::resolved_socket_addr::ResolvedSocketAddr;
     #[cfg(feature = "web_admin_panel")]
     use paddler_balancer::web_admin_panel_service::template_data::TemplateData;
 
+    use tempfile::NamedTempFile;
+    use tempfile::TempDir;
+    use url::Url;
+
     use super::*;
 
     #[cfg(feature = "web_admin_panel")]
-    const EXPECTED_SERVICE_COUNT: usize = 6;
+    const EXPECTED_SERVICE_COUNT: usize = 10;
     #[cfg(not(feature = "web_admin_panel"))]
-    const EXPECTED_SERVICE_COUNT: usize = 5;
+    const EXPECTED_SERVICE_COUNT: usize = 9;
 
     fn loopback_addr() -> SocketAddr {
         SocketAddr::from(([127, 0, 0, 1], 0))
@@ -216,21 +341,44 @@ mod tests {
 
     #[tokio::test]
     async fn services_includes_every_optional_service_when_configured() {
+        let api_keys_file = NamedTempFile::new().unwrap();
+        let audit_log_directory = TempDir::new().unwrap();
         let bundle = BalancerServiceBundle::new(BalancerBootstrapConfig {
+            api_keys_file: Some(api_keys_file.path().to_path_buf()),
+            audit_log_service_configuration: Some(AuditLogServiceConfiguration {
+                max_body_bytes: None,
+                path: audit_log_directory.path().join("audit.jsonl"),
+                rotate_after: None,
+                rotate_after_bytes: None,
+            }),
+            autoscaling_hook_service_configuration: Some(AutoscalingHookServiceConfiguration {
+                hook: AutoscalingHook::Command("true".to_owned()),
+                idle_threshold: Duration::from_mins(5),
+            }),
+            autoscaling_target_utilization: 0.8,
+            batch_buffered_request_limits: None,
             buffered_request_timeout: Duration::from_secs(10),
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: loopback_addr(),
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
                 cors_allowed_hosts: vec![],
+                dispatch_strategy: DispatchStrategy::LeastBusy,
                 inference_item_timeout: Duration::from_secs(30),
+                tls_configuration: None,
             },
             management_service_configuration: ManagementServiceConfiguration {
                 addr: loopback_addr(),
+                agent_join_token: None,
                 cors_allowed_hosts: vec![],
+                tls_configuration: None,
             },
             max_buffered_requests: 30,
             openai_service_configuration: Some(OpenAIServiceConfiguration {
                 addr: loopback_addr(),
+                tls_configuration: None,
             }),
+            rate_limit_configuration: RateLimitConfiguration::default(),
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             state_database_type: StateDatabaseType::Memory(Box::default()),
             statsd_prefix: "paddler_bootstrap_test_".to_owned(),
             statsd_service_configuration: Some(StatsdServiceConfiguration {
@@ -238,6 +386,11 @@ mod tests {
                 statsd_prefix: "paddler_bootstrap_test_".to_owned(),
                 statsd_reporting_interval: Duration::from_secs(10),
             }),
+            trace_exporter_configuration: Some(OtlpTraceExporterServiceConfiguration {
+                export_interval: Duration::from_secs(5),
+                otlp_endpoint: Url::parse("http://127.0.0.1:4318").unwrap(),
+                service_name: "paddler-balancer".to_owned(),
+            }),
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration: Some(WebAdminPanelServiceConfiguration {
                 addr: loopback_addr(),
@@ -257,6 +410,7 @@ mod tests {
                     statsd_prefix: "paddler_bootstrap_test_".to_owned(),
                     statsd_reporting_interval: Duration::from_secs(10),
                 },
+                tls_configuration: None,
             }),
         })
         .await
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fs;
 use std::net::SocketAddr;
 use std::net::TcpListener;
@@ -5,8 +6,13 @@ use std::time::Duration;
 
 use anyhow::Context as _;
 use anyhow::Result;
+use paddler_balancer::agent_failover_policy::AgentFailoverPolicy;
+use paddler_balancer::autoscaling_signal::DEFAULT_TARGET_UTILIZATION;
+use paddler_balancer::dispatch_strategy::DispatchStrategy;
 use paddler_balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler_balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
+use paddler_balancer::rate_limit_configuration::RateLimitConfiguration;
+use paddler_balancer::rollout_strategy::RolloutStrategy;
 use paddler_balancer::state_database::StateDatabase;
 use paddler_balancer::state_database::file::File as StateDatabaseFile;
 use paddler_balancer::state_database_type::StateDatabaseType;
@@ -53,23 +59,36 @@ fn make_balancer_runner_params(
     inference_addr: SocketAddr,
 ) -> BalancerRunnerParams {
     BalancerRunnerParams {
+        api_keys_file: None,
+        audit_log_service_configuration: None,
+        autoscaling_hook_service_configuration: None,
+        autoscaling_target_utilization: DEFAULT_TARGET_UTILIZATION,
+        batch_buffered_request_limits: None,
         buffered_request_timeout: Duration::from_secs(10),
         inference_service_configuration: InferenceServiceConfiguration {
             addr: inference_addr,
+            agent_failover_policy: AgentFailoverPolicy::Disabled,
             cors_allowed_hosts: vec![],
+            dispatch_strategy: DispatchStrategy::LeastBusy,
             inference_item_timeout: Duration::from_secs(30),
+            tls_configuration: None,
         },
         management_service_configuration: ManagementServiceConfiguration {
             addr: management_addr,
+            agent_join_token: None,
             cors_allowed_hosts: vec![],
+            tls_configuration: None,
         },
         max_buffered_requests: 30,
         openai_service_configuration: None,
         cancellation_token,
+        rate_limit_configuration: RateLimitConfiguration::default(),
+        rollout_strategy: RolloutStrategy::AllAtOnce,
         shutdown_options: ServiceShutdownOptions::default(),
         state_database_type: StateDatabaseType::Memory(Box::default()),
         statsd_prefix: "paddler_bootstrap_test_".to_owned(),
         statsd_service_configuration: None,
+        trace_exporter_configuration: None,
         #[cfg(feature = "web_admin_panel")]
         web_admin_panel_service_configuration: None,
     }
@@ -81,9 +100,13 @@ fn make_agent_runner_params(
 ) -> AgentRunnerParams {
     AgentRunnerParams {
         agent_name: Some("test-agent".to_owned()),
+        join_token: None,
         management_address: management_addr.to_string(),
         cancellation_token,
+        management_tls_configuration: None,
+        model_pool: None,
         slots: 1,
+        trace_exporter_configuration: None,
     }
 }
 
@@ -173,6 +196,7 @@ async fn balancer_runner_preserves_persisted_desired_state() -> Result<()> {
         }),
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::LocalToAgent("persisted-model".to_owned()),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: true,
     };
@@ -270,8 +294,13 @@ async fn in_flight_request_is_released_when_balancer_shuts_down() -> Result<()>
         ))
         .json(&ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 10,
+            model: None,
             raw_prompt: "hold the connection open during shutdown".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         })
         .send()
         .await
@@ -1,25 +1,71 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
 use command_handler::handler::Handler;
+use paddler_agent::management_tls_configuration::ManagementTlsConfiguration;
 use paddler_balancer::resolved_socket_addr::ResolvedSocketAddr;
+use paddler_bootstrap::agent_service_bundle::AgentBootstrapConfig;
 use paddler_bootstrap::agent_service_bundle::AgentServiceBundle;
+use paddler_tracing::otlp_trace_exporter_service::configuration::Configuration as OtlpTraceExporterServiceConfiguration;
 use tokio_util::sync::CancellationToken;
 use trzcina::ServiceManager;
 use trzcina::ServiceShutdownOptions;
+use url::Url;
 
+use super::value_parser::parse_duration::parse_duration;
 use super::value_parser::parse_socket_addr::parse_socket_addr;
+use super::value_parser::read_secret_file::read_secret_file;
 
 #[derive(Parser)]
 pub struct Agent {
+    #[arg(long, env = "PADDLER_JOIN_TOKEN", hide_env_values = true)]
+    /// Join token expected by the balancer (see `paddler balancer --agent-join-token`)
+    join_token: Option<String>,
+
+    #[arg(long, conflicts_with = "join_token", value_parser = read_secret_file)]
+    /// File to read the join token from, so it does not show up in the process list
+    join_token_file: Option<String>,
+
     #[arg(long, value_parser = parse_socket_addr)]
     /// Address of the management server that the agent will connect to
     management_addr: ResolvedSocketAddr,
 
+    #[arg(long, requires = "management_tls")]
+    /// CA certificate (PEM) to trust instead of the system trust store when connecting over TLS
+    management_ca_certificate: Option<PathBuf>,
+
+    #[arg(long, requires_all = ["management_tls", "management_client_private_key"])]
+    /// Client certificate (PEM) for balancers that require agents to authenticate with one
+    management_client_certificate: Option<PathBuf>,
+
+    #[arg(long, requires = "management_client_certificate")]
+    /// Private key (PEM) of the client certificate
+    management_client_private_key: Option<PathBuf>,
+
+    #[arg(long)]
+    /// Connect to the management server over TLS (wss://)
+    management_tls: bool,
+
+    #[arg(long)]
+    /// Name of the model pool the agent serves (optional, defaults to the balancer's main model)
+    model_pool: Option<String>,
+
     #[arg(long)]
     /// Name of the agent (optional)
     name: Option<String>,
 
+    #[arg(long)]
+    /// OpenTelemetry collector (OTLP/HTTP) to export request traces to, for example
+    /// 'http://localhost:4318' (enabled only if this endpoint is specified)
+    otlp_endpoint: Option<Url>,
+
+    #[arg(long, default_value = "5000", value_parser = parse_duration)]
+    /// Interval (in milliseconds) at which finished spans are exported to the collector
+    otlp_export_interval: Duration,
+
     #[arg(long)]
     /// Number of parallel requests of any kind that the agent can handle at once
     slots: i32,
@@ -28,11 +74,28 @@ pub struct Agent {
 #[async_trait(?Send)]
 impl Handler for Agent {
     async fn handle(self, shutdown: CancellationToken) -> Result<()> {
-        let bundle = AgentServiceBundle::new(
-            self.name.clone(),
-            &self.management_addr.socket_addr.to_string(),
-            self.slots,
-        );
+        let bundle = AgentServiceBundle::new(AgentBootstrapConfig {
+            agent_name: self.name.clone(),
+            join_token: self
+                .join_token
+                .clone()
+                .or_else(|| self.join_token_file.clone()),
+            management_address: self.management_addr.socket_addr.to_string(),
+            management_tls_configuration: self.management_tls.then(|| ManagementTlsConfiguration {
+                ca_certificate_path: self.management_ca_certificate.clone(),
+                client_certificate_path: self.management_client_certificate.clone(),
+                client_private_key_path: self.management_client_private_key.clone(),
+            }),
+            model_pool: self.model_pool.clone(),
+            slots: self.slots,
+            trace_exporter_configuration: self.otlp_endpoint.clone().map(|otlp_endpoint| {
+                OtlpTraceExporterServiceConfiguration {
+                    export_interval: self.otlp_export_interval,
+                    otlp_endpoint,
+                    service_name: "paddler-agent".to_owned(),
+                }
+            }),
+        });
 
         let mut service_manager = ServiceManager::default();
 
@@ -1,30 +1,106 @@
+use std::path::PathBuf;
 use std::time::Duration;
 
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
 use command_handler::handler::Handler;
+use paddler_balancer::agent_failover_policy::AgentFailoverPolicy;
+use paddler_balancer::audit_log_service::configuration::Configuration as AuditLogServiceConfiguration;
+use paddler_balancer::autoscaling_hook::AutoscalingHook;
+use paddler_balancer::autoscaling_hook_service::configuration::Configuration as AutoscalingHookServiceConfiguration;
+use paddler_balancer::buffered_request_limits::BufferedRequestLimits;
 use paddler_balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
+use paddler_balancer::dispatch_strategy::DispatchStrategy;
 use paddler_balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler_balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
+use paddler_balancer::rate_limit_configuration::RateLimitConfiguration;
 use paddler_balancer::resolved_socket_addr::ResolvedSocketAddr;
+use paddler_balancer::rollout_batch_size::RolloutBatchSize;
+use paddler_balancer::rollout_failure_action::RolloutFailureAction;
+use paddler_balancer::rollout_strategy::RolloutStrategy;
 use paddler_balancer::state_database_type::StateDatabaseType;
 use paddler_balancer::statsd_service::configuration::Configuration as StatsdServiceConfiguration;
+use paddler_balancer::tls_configuration::TlsConfiguration;
 #[cfg(feature = "web_admin_panel")]
 use paddler_balancer::web_admin_panel_service::configuration::Configuration as WebAdminPanelServiceConfiguration;
 #[cfg(feature = "web_admin_panel")]
 use paddler_balancer::web_admin_panel_service::template_data::TemplateData;
 use paddler_bootstrap::balancer_service_bundle::BalancerBootstrapConfig;
 use paddler_bootstrap::balancer_service_bundle::BalancerServiceBundle;
+use paddler_tracing::otlp_trace_exporter_service::configuration::Configuration as OtlpTraceExporterServiceConfiguration;
 use tokio_util::sync::CancellationToken;
 use trzcina::ServiceManager;
 use trzcina::ServiceShutdownOptions;
+use url::Url;
 
 use super::value_parser::parse_duration::parse_duration;
 use super::value_parser::parse_socket_addr::parse_socket_addr;
+use super::value_parser::parse_utilization::parse_utilization;
+use super::value_parser::read_secret_file::read_secret_file;
 
 #[derive(Parser)]
 pub struct Balancer {
+    #[arg(long, env = "PADDLER_AGENT_JOIN_TOKEN", hide_env_values = true)]
+    /// Shared secret that agents must present when joining the cluster (`paddler agent --join-token`).
+    /// Agents that present no token or a wrong one are disconnected. Any agent may join when omitted
+    agent_join_token: Option<String>,
+
+    #[arg(long, conflicts_with = "agent_join_token", value_parser = read_secret_file)]
+    /// File to read the agent join token from, so it does not show up in the process list
+    agent_join_token_file: Option<String>,
+
+    #[arg(long)]
+    /// File with API keys (one per line) that clients must present as 'Authorization: Bearer <key>'
+    /// to the inference and OpenAI-compatible services. The file is re-read when it changes,
+    /// so keys can be rotated or revoked without a restart. A key may be followed by 'batch' or
+    /// 'interactive' to set the priority class of its requests. Authentication is off when omitted
+    api_keys_file: Option<PathBuf>,
+
+    #[arg(long)]
+    /// JSONL file to record every inference request in: the caller, endpoint, agent, model,
+    /// queue wait, token usage, finish reason and error. Audit logging is off when omitted
+    audit_log: Option<PathBuf>,
+
+    #[arg(long, requires = "audit_log")]
+    /// Also record prompts and completions in the audit log, each capped to this many bytes
+    audit_log_max_body_bytes: Option<usize>,
+
+    #[arg(long, requires = "audit_log")]
+    /// Start a new audit log file once the current one would grow past this many bytes.
+    /// The previous file is kept with a timestamp suffix
+    audit_log_rotate_bytes: Option<u64>,
+
+    #[arg(long, requires = "audit_log", value_parser = parse_duration)]
+    /// Start a new audit log file once the current one has been written to for this long
+    /// (in milliseconds). The previous file is kept with a timestamp suffix
+    audit_log_rotate_interval: Option<Duration>,
+
+    #[arg(long, conflicts_with = "autoscaling_hook_url")]
+    /// Shell command to run when requests are buffered while no agents are connected, and when
+    /// the cluster has been idle for --autoscaling-idle-threshold. The event is passed in the
+    /// PADDLER_AUTOSCALING_EVENT environment variable ('scale_from_zero' or 'idle')
+    autoscaling_hook_command: Option<String>,
+
+    #[arg(long)]
+    /// URL to POST the autoscaling events to, as JSON with the event and the current
+    /// recommendation from '/api/v1/autoscaling'
+    autoscaling_hook_url: Option<Url>,
+
+    #[arg(long, default_value = "300000", value_parser = parse_duration)]
+    /// How long (in milliseconds) nothing has to be processed or buffered before the autoscaling
+    /// hook reports the cluster as idle
+    autoscaling_idle_threshold: Duration,
+
+    #[arg(long, default_value = "0.8", value_parser = parse_utilization)]
+    /// Fraction of the slots the agent count recommended by '/api/v1/autoscaling' aims to keep busy
+    autoscaling_target_utilization: f64,
+
+    #[arg(long, value_parser = parse_duration)]
+    /// How long a batch priority request (see 'X-Paddler-Priority') can stay in the buffer.
+    /// Defaults to --buffered-request-timeout
+    batch_buffered_request_timeout: Option<Duration>,
+
     #[arg(long, default_value = "10000", value_parser = parse_duration)]
     /// Specifies how long a request can stay in the buffer before it is processed.
     /// If the request stays in the buffer longer than this time, it is rejected with the 504 error
@@ -34,6 +110,22 @@ pub struct Balancer {
     /// Address of the OpenAI-compatible API server (enabled only if this address is specified)
     compat_openai_addr: Option<ResolvedSocketAddr>,
 
+    #[arg(long, requires_all = ["compat_openai_addr", "compat_openai_tls_private_key"])]
+    /// Certificate chain (PEM) served by the OpenAI-compatible API server. Enables HTTPS
+    compat_openai_tls_certificate: Option<PathBuf>,
+
+    #[arg(long, requires = "compat_openai_tls_certificate")]
+    /// Private key (PEM) of the OpenAI-compatible API server certificate
+    compat_openai_tls_private_key: Option<PathBuf>,
+
+    #[arg(long, default_value = "least-busy")]
+    /// How inference requests are matched with agents. Supported: least-busy, session-affinity,
+    /// throughput-weighted.
+    /// session-affinity keeps requests with the same prompt_cache_key (or the same prompt prefix)
+    /// on the agent that served them before, so they can reuse its KV cache.
+    /// throughput-weighted sends more requests to agents that generate tokens faster
+    dispatch_strategy: DispatchStrategy,
+
     #[arg(long, default_value = "127.0.0.1:8061", value_parser = parse_socket_addr)]
     /// Address of the inference server
     inference_addr: ResolvedSocketAddr,
@@ -49,6 +141,14 @@ pub struct Balancer {
     /// Allowed CORS host for the inference service (can be specified multiple times)
     inference_cors_allowed_hosts: Vec<String>,
 
+    #[arg(long, requires = "inference_tls_private_key")]
+    /// Certificate chain (PEM) served by the inference server. Enables HTTPS
+    inference_tls_certificate: Option<PathBuf>,
+
+    #[arg(long, requires = "inference_tls_certificate")]
+    /// Private key (PEM) of the inference server certificate
+    inference_tls_private_key: Option<PathBuf>,
+
     #[arg(long, default_value = "127.0.0.1:8060", value_parser = parse_socket_addr)]
     /// This is where you can manage your Paddler setup and the agents connect to
     management_addr: ResolvedSocketAddr,
@@ -60,11 +160,83 @@ pub struct Balancer {
     /// Allowed CORS host for the management service (can be specified multiple times)
     management_cors_allowed_hosts: Vec<String>,
 
+    #[arg(long, requires = "management_tls_private_key")]
+    /// Certificate chain (PEM) served by the management server. Enables HTTPS, and agents then
+    /// have to connect with `paddler agent --management-tls`
+    management_tls_certificate: Option<PathBuf>,
+
+    #[arg(long, requires = "management_tls_certificate")]
+    /// CA certificate (PEM) that signs agent client certificates. When set, only agents presenting
+    /// a certificate signed by this CA can join (mutual TLS)
+    management_tls_client_ca: Option<PathBuf>,
+
+    #[arg(long, requires = "management_tls_certificate")]
+    /// Private key (PEM) of the management server certificate
+    management_tls_private_key: Option<PathBuf>,
+
+    #[arg(long, default_value = "0")]
+    /// How many times a request may be dispatched to another agent when its agent disconnects
+    /// before sending the first token. Such disconnects are reported to the client when 0
+    max_agent_failovers: u32,
+
+    #[arg(long)]
+    /// The maximum number of buffered batch priority requests. Interactive requests are always
+    /// served first, so batch jobs cannot starve them. Defaults to --max-buffered-requests
+    max_batch_buffered_requests: Option<i32>,
+
     #[arg(long, default_value = "30")]
     /// The maximum number of buffered requests.
     /// If the buffer is full then new requests are rejected with the 503 error
     max_buffered_requests: i32,
 
+    #[arg(long)]
+    /// OpenTelemetry collector (OTLP/HTTP) to export request traces to, for example
+    /// 'http://localhost:4318' (enabled only if this endpoint is specified)
+    otlp_endpoint: Option<Url>,
+
+    #[arg(long, default_value = "5000", value_parser = parse_duration)]
+    /// Interval (in milliseconds) at which finished spans are exported to the collector
+    otlp_export_interval: Duration,
+
+    #[arg(long)]
+    /// Maximum number of requests a single caller may have in flight at once.
+    /// Callers over the limit are rejected with the 429 error. A caller is identified by its
+    /// API key, then by the '--rate-limit-identity-header' header, and by its IP address
+    /// otherwise
+    rate_limit_concurrent_requests: Option<u32>,
+
+    #[arg(long)]
+    /// Maximum number of tokens the agents may generate for a single caller within a day
+    rate_limit_generated_tokens_per_day: Option<u64>,
+
+    #[arg(long)]
+    /// Header that identifies callers without an API key for rate limiting (for example
+    /// 'X-Tenant-Id'). Only set it when the balancer sits behind a reverse proxy that sets the
+    /// header, since clients reaching the balancer directly can claim any identity with it
+    rate_limit_identity_header: Option<String>,
+
+    #[arg(long)]
+    /// Maximum number of requests a single caller may make within a minute
+    rate_limit_requests_per_minute: Option<u32>,
+
+    #[arg(long)]
+    /// Roll out a new desired state to this many agents at a time (for example '2' or '25%'),
+    /// waiting until each batch applied it. All agents are updated at once when omitted.
+    /// Agents have to be upgraded before the balancer, so they report which state they applied
+    rollout_batch_size: Option<RolloutBatchSize>,
+
+    #[arg(long, default_value = "roll-back")]
+    /// What to do when an agent fails to apply the state during a gradual rollout:
+    /// 'roll-back' restores the previous state on updated agents and in the state database,
+    /// 'abort' stops the rollout and leaves the updated agents on the new state
+    rollout_failure_action: RolloutFailureAction,
+
+    #[arg(long, default_value = "2")]
+    /// With the session-affinity dispatch strategy, how many more requests than the least busy
+    /// agent the agent that served a session before may be processing and still get the
+    /// session's next request
+    session_affinity_max_load_slack: u16,
+
     #[arg(long, default_value = "memory://")]
     /// Balancer state database URL. Supported: memory, memory://, or <file:///path> (optional)
     state_database: StateDatabaseType,
@@ -85,9 +257,48 @@ pub struct Balancer {
     #[arg(long, default_value = None, value_parser = parse_socket_addr)]
     /// Address of the web admin panel (enabled only if this address is specified)
     web_admin_panel_addr: Option<ResolvedSocketAddr>,
+
+    #[cfg(feature = "web_admin_panel")]
+    #[arg(long, requires_all = ["web_admin_panel_addr", "web_admin_panel_tls_private_key"])]
+    /// Certificate chain (PEM) served by the web admin panel. Enables HTTPS
+    web_admin_panel_tls_certificate: Option<PathBuf>,
+
+    #[cfg(feature = "web_admin_panel")]
+    #[arg(long, requires = "web_admin_panel_tls_certificate")]
+    /// Private key (PEM) of the web admin panel certificate
+    web_admin_panel_tls_private_key: Option<PathBuf>,
+}
+
+fn make_tls_configuration(
+    certificate_chain_path: Option<&PathBuf>,
+    private_key_path: Option<&PathBuf>,
+    client_ca_path: Option<&PathBuf>,
+) -> Option<TlsConfiguration> {
+    certificate_chain_path.zip(private_key_path).map(
+        |(certificate_chain_path, private_key_path)| TlsConfiguration {
+            certificate_chain_path: certificate_chain_path.clone(),
+            client_ca_path: client_ca_path.cloned(),
+            private_key_path: private_key_path.clone(),
+        },
+    )
 }
 
 impl Balancer {
+    fn get_autoscaling_hook_service_configuration(
+        &self,
+    ) -> Option<AutoscalingHookServiceConfiguration> {
+        let hook = match (&self.autoscaling_hook_command, &self.autoscaling_hook_url) {
+            (Some(command), _) => AutoscalingHook::Command(command.clone()),
+            (None, Some(url)) => AutoscalingHook::Webhook(url.clone()),
+            (None, None) => return None,
+        };
+
+        Some(AutoscalingHookServiceConfiguration {
+            hook,
+            idle_threshold: self.autoscaling_idle_threshold,
+        })
+    }
+
     #[cfg(feature = "web_admin_panel")]
     fn get_web_admin_panel_service_configuration(
         &self,
@@ -106,6 +317,11 @@ impl Balancer {
                     statsd_prefix: self.statsd_prefix.clone(),
                     statsd_reporting_interval: self.statsd_reporting_interval,
                 },
+                tls_configuration: make_tls_configuration(
+                    self.web_admin_panel_tls_certificate.as_ref(),
+                    self.web_admin_panel_tls_private_key.as_ref(),
+                    None,
+                ),
             })
     }
 }
@@ -116,20 +332,78 @@ impl Handler for Balancer {
         let shutdown_options = ServiceShutdownOptions::default();
 
         let bundle = BalancerServiceBundle::new(BalancerBootstrapConfig {
+            api_keys_file: self.api_keys_file.clone(),
+            audit_log_service_configuration: self.audit_log.clone().map(|path| {
+                AuditLogServiceConfiguration {
+                    max_body_bytes: self.audit_log_max_body_bytes,
+                    path,
+                    rotate_after: self.audit_log_rotate_interval,
+                    rotate_after_bytes: self.audit_log_rotate_bytes,
+                }
+            }),
+            autoscaling_hook_service_configuration: self
+                .get_autoscaling_hook_service_configuration(),
+            autoscaling_target_utilization: self.autoscaling_target_utilization,
+            batch_buffered_request_limits: Some(BufferedRequestLimits {
+                buffered_request_timeout: self
+                    .batch_buffered_request_timeout
+                    .unwrap_or(self.buffered_request_timeout),
+                max_buffered_requests: self
+                    .max_batch_buffered_requests
+                    .unwrap_or(self.max_buffered_requests),
+            }),
             buffered_request_timeout: self.buffered_request_timeout,
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: self.inference_addr.socket_addr,
+                agent_failover_policy: AgentFailoverPolicy::from_max_failovers(
+                    self.max_agent_failovers,
+                ),
                 cors_allowed_hosts: self.inference_cors_allowed_hosts.clone(),
+                dispatch_strategy: self.dispatch_strategy.with_session_affinity_max_load_slack(
+                    i32::from(self.session_affinity_max_load_slack),
+                ),
                 inference_item_timeout: self.inference_item_timeout,
+                tls_configuration: make_tls_configuration(
+                    self.inference_tls_certificate.as_ref(),
+                    self.inference_tls_private_key.as_ref(),
+                    None,
+                ),
             },
             management_service_configuration: ManagementServiceConfiguration {
                 addr: self.management_addr.socket_addr,
+                agent_join_token: self
+                    .agent_join_token
+                    .clone()
+                    .or_else(|| self.agent_join_token_file.clone()),
                 cors_allowed_hosts: self.management_cors_allowed_hosts.clone(),
+                tls_configuration: make_tls_configuration(
+                    self.management_tls_certificate.as_ref(),
+                    self.management_tls_private_key.as_ref(),
+                    self.management_tls_client_ca.as_ref(),
+                ),
             },
             max_buffered_requests: self.max_buffered_requests,
             openai_service_configuration: self.compat_openai_addr.clone().map(
                 |compat_openai_addr| OpenAIServiceConfiguration {
                     addr: compat_openai_addr.socket_addr,
+                    tls_configuration: make_tls_configuration(
+                        self.compat_openai_tls_certificate.as_ref(),
+                        self.compat_openai_tls_private_key.as_ref(),
+                        None,
+                    ),
+                },
+            ),
+            rate_limit_configuration: RateLimitConfiguration {
+                identity_header: self.rate_limit_identity_header.clone(),
+                max_concurrent_requests: self.rate_limit_concurrent_requests,
+                max_generated_tokens_per_day: self.rate_limit_generated_tokens_per_day,
+                max_requests_per_minute: self.rate_limit_requests_per_minute,
+            },
+            rollout_strategy: self.rollout_batch_size.map_or(
+                RolloutStrategy::AllAtOnce,
+                |batch_size| RolloutStrategy::Gradual {
+                    batch_size,
+                    failure_action: self.rollout_failure_action,
                 },
             ),
             state_database_type: self.state_database.clone(),
@@ -141,6 +415,13 @@ impl Handler for Balancer {
                     statsd_reporting_interval: self.statsd_reporting_interval,
                 }
             }),
+            trace_exporter_configuration: self.otlp_endpoint.clone().map(|otlp_endpoint| {
+                OtlpTraceExporterServiceConfiguration {
+                    export_interval: self.otlp_export_interval,
+                    otlp_endpoint,
+                    service_name: "paddler-balancer".to_owned(),
+                }
+            }),
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration: self.get_web_admin_panel_service_configuration(),
         })
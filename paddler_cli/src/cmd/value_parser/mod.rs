@@ -1,2 +1,4 @@
 pub mod parse_duration;
 pub mod parse_socket_addr;
+pub mod parse_utilization;
+pub mod read_secret_file;
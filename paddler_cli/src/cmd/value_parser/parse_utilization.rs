@@ -0,0 +1,28 @@
+use anyhow::Result;
+use anyhow::bail;
+
+pub fn parse_utilization(arg: &str) -> Result<f64> {
+    let utilization: f64 = arg.parse()?;
+
+    if !(utilization > 0.0 && utilization <= 1.0) {
+        bail!("Utilization must be greater than 0 and at most 1, got {utilization}");
+    }
+
+    Ok(utilization)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_utilization;
+
+    #[test]
+    fn parses_a_fraction() {
+        assert!((parse_utilization("0.75").unwrap() - 0.75).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn rejects_values_outside_of_the_unit_interval() {
+        assert!(parse_utilization("0").is_err());
+        assert!(parse_utilization("1.5").is_err());
+    }
+}
@@ -0,0 +1,60 @@
+use std::fs;
+
+use anyhow::Context as _;
+use anyhow::Result;
+use anyhow::bail;
+
+/// Reads a secret from a file so it does not show up in the process list. Surrounding
+/// whitespace, such as a trailing newline, is not part of the secret.
+pub fn read_secret_file(path: &str) -> Result<String> {
+    let contents =
+        fs::read_to_string(path).with_context(|| format!("Unable to read secret file {path}"))?;
+    let secret = contents.trim();
+
+    if secret.is_empty() {
+        bail!("Secret file {path} is empty");
+    }
+
+    Ok(secret.to_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process;
+
+    use super::read_secret_file;
+
+    fn secret_file(name: &str, contents: &str) -> PathBuf {
+        let path = temp_dir().join(format!("paddler-{name}-{}", process::id()));
+
+        fs::write(&path, contents).unwrap();
+
+        path
+    }
+
+    #[test]
+    fn trims_the_trailing_newline() {
+        let path = secret_file("trimmed-secret", "join-me\n");
+
+        assert_eq!(read_secret_file(path.to_str().unwrap()).unwrap(), "join-me");
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_an_empty_file() {
+        let path = secret_file("empty-secret", "\n");
+
+        assert!(read_secret_file(path.to_str().unwrap()).is_err());
+
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn rejects_a_missing_file() {
+        assert!(read_secret_file("/nonexistent/paddler/join_token").is_err());
+    }
+}
@@ -1,5 +1,6 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use anyhow::Result;
@@ -46,6 +47,7 @@ async fn balancer_distributes_buffered_requests_across_two_agents() -> Result<()
                     ..InferenceParameters::default()
                 },
                 model: AgentDesiredModel::HuggingFace(reference),
+                model_pools: BTreeMap::new(),
                 multimodal_projection: AgentDesiredModel::None,
                 use_chat_template_override: false,
             }),
@@ -62,8 +64,13 @@ async fn balancer_distributes_buffered_requests_across_two_agents() -> Result<()
                 CancellationToken::new(),
                 &ContinueFromRawPromptParams {
                     grammar: None,
+                    logprobs: None,
                     max_tokens: 10,
+                    model: None,
                     raw_prompt: "Hello".to_owned(),
+                    sampling: None,
+                    session_key: None,
+                    stop: Vec::new(),
                 },
             )
             .await?;
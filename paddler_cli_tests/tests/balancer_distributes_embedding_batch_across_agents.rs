@@ -32,10 +32,12 @@ async fn balancer_distributes_embedding_batch_across_agents() -> Result<()> {
         .map(|index| EmbeddingInputDocument {
             content: format!("Document number {index:02}: {filler}"),
             id: format!("doc-{index}"),
+            tokens: None,
         })
         .collect();
     let params = GenerateEmbeddingBatchParams {
         input_batch,
+        model: None,
         normalization_method: EmbeddingNormalizationMethod::None,
     };
 
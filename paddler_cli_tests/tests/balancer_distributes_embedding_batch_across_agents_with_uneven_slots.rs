@@ -48,6 +48,7 @@ async fn balancer_distributes_embedding_batch_across_agents_with_uneven_slots()
         .map(|index| EmbeddingInputDocument {
             content: format!("Uneven-slot document number {index}."),
             id: format!("doc-{index}"),
+            tokens: None,
         })
         .collect();
 
@@ -56,6 +57,7 @@ async fn balancer_distributes_embedding_batch_across_agents_with_uneven_slots()
             CancellationToken::new(),
             &GenerateEmbeddingBatchParams {
                 input_batch,
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
@@ -43,6 +43,7 @@ async fn balancer_distributes_embedding_burst_evenly_across_agents() -> Result<(
                      provide an embedding for evaluation."
                 ),
                 id: format!("req-{request_index}-doc-{document_index}"),
+                tokens: None,
             })
             .collect();
 
@@ -50,6 +51,7 @@ async fn balancer_distributes_embedding_burst_evenly_across_agents() -> Result<(
             CancellationToken::new(),
             &GenerateEmbeddingBatchParams {
                 input_batch,
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
@@ -30,8 +30,13 @@ async fn balancer_distributes_token_burst_evenly_across_agents() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 16,
+                model: None,
                 raw_prompt: prompt.clone(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
     });
@@ -38,6 +38,7 @@ async fn balancer_emits_overflow_errors_when_embedding_burst_exceeds_max_buffere
         .map(|index| EmbeddingInputDocument {
             content: format!("Overflow probe document {index}."),
             id: format!("doc-{index}"),
+            tokens: None,
         })
         .collect();
 
@@ -47,6 +48,7 @@ async fn balancer_emits_overflow_errors_when_embedding_burst_exceeds_max_buffere
             CancellationToken::new(),
             &GenerateEmbeddingBatchParams {
                 input_batch,
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         ),
@@ -34,10 +34,12 @@ async fn balancer_fans_out_embedding_batch_to_all_agents() -> Result<()> {
         .map(|index| EmbeddingInputDocument {
             content: format!("Document number {index:02}: {filler}"),
             id: format!("doc-{index}"),
+            tokens: None,
         })
         .collect();
     let params = GenerateEmbeddingBatchParams {
         input_batch,
+        model: None,
         normalization_method: EmbeddingNormalizationMethod::None,
     };
 
@@ -32,14 +32,16 @@ impl ClientInference {
     #[must_use]
     pub fn new(
         ClientInferenceParams {
+            api_key,
             inference_socket_pool_size,
             url,
         }: ClientInferenceParams,
     ) -> Self {
-        let inference_socket_pool = Pool::new(url.clone(), inference_socket_pool_size);
+        let inference_socket_pool =
+            Pool::new(url.clone(), inference_socket_pool_size).with_api_key(api_key.clone());
 
         Self {
-            http_client: HttpClient::new(url),
+            http_client: HttpClient::new(url).with_api_key(api_key),
             inference_socket_pool: Arc::new(inference_socket_pool),
         }
     }
@@ -71,6 +73,7 @@ impl ClientInference {
             InferenceServerMessage::Request(RequestEnvelope {
                 id: request_id.clone(),
                 request,
+                trace_context: None,
             });
 
         self.inference_socket_pool
@@ -160,6 +163,7 @@ mod tests {
     use paddler_messaging::conversation_history::ConversationHistory;
     use paddler_messaging::embedding_input_document::EmbeddingInputDocument;
     use paddler_messaging::embedding_normalization_method::EmbeddingNormalizationMethod;
+    use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
     use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
     use paddler_messaging::request_params::generate_embedding_batch_params::GenerateEmbeddingBatchParams;
     use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
@@ -173,6 +177,7 @@ mod tests {
 
     fn unreachable_client() -> ClientInference {
         ClientInference::new(ClientInferenceParams {
+            api_key: None,
             inference_socket_pool_size: NonZeroUsize::MIN,
             url: Url::parse("http://127.0.0.1:1").expect("the test URL must be valid"),
         })
@@ -181,8 +186,13 @@ mod tests {
     fn raw_prompt_params() -> ContinueFromRawPromptParams {
         ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 16,
+            model: None,
             raw_prompt: "hello".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         }
     }
 
@@ -193,8 +203,14 @@ mod tests {
             conversation_history: ConversationHistory::new(Vec::new()),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 16,
+            model: None,
             parse_tool_calls: false,
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
+            tool_choice: ToolChoice::Auto,
             tools: Vec::new(),
         }
     }
@@ -204,7 +220,9 @@ mod tests {
             input_batch: vec![EmbeddingInputDocument {
                 content: "hello".to_owned(),
                 id: "document-0".to_owned(),
+                tokens: None,
             }],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
         }
     }
@@ -3,6 +3,8 @@ use std::num::NonZeroUsize;
 use url::Url;
 
 pub struct ClientInferenceParams {
+    /// Sent as a bearer token when the balancer requires API keys.
+    pub api_key: Option<String>,
     pub inference_socket_pool_size: NonZeroUsize,
     pub url: Url,
 }
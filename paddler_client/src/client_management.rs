@@ -1,6 +1,7 @@
 use futures_util::StreamExt;
 use paddler_messaging::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
 use paddler_messaging::agent_desired_state::AgentDesiredState;
+use paddler_messaging::autoscaling_recommendation::AutoscalingRecommendation;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
 use paddler_messaging::buffered_request_manager_snapshot::BufferedRequestManagerSnapshot;
 use paddler_messaging::chat_template::ChatTemplate;
@@ -38,6 +39,15 @@ impl ClientManagement {
             .await
     }
 
+    pub async fn get_autoscaling_recommendation(
+        &self,
+        cancellation_token: CancellationToken,
+    ) -> Result<AutoscalingRecommendation> {
+        self.http_client
+            .get_json(cancellation_token, "/api/v1/autoscaling")
+            .await
+    }
+
     pub async fn get_balancer_desired_state(
         &self,
         cancellation_token: CancellationToken,
@@ -136,6 +146,39 @@ impl ClientManagement {
             .await
     }
 
+    /// Stops dispatching new requests to the agent while the ones it is processing finish.
+    /// The agent's snapshot reports its `cordon_status` as draining, then drained once its
+    /// in-flight requests finish.
+    pub async fn cordon_agent(
+        &self,
+        cancellation_token: CancellationToken,
+        agent_id: &str,
+    ) -> Result<()> {
+        self.http_client
+            .put(
+                cancellation_token,
+                &format!("/api/v1/agent/{agent_id}/cordon"),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn uncordon_agent(
+        &self,
+        cancellation_token: CancellationToken,
+        agent_id: &str,
+    ) -> Result<()> {
+        self.http_client
+            .delete(
+                cancellation_token,
+                &format!("/api/v1/agent/{agent_id}/cordon"),
+            )
+            .await?;
+
+        Ok(())
+    }
+
     pub async fn get_metrics(&self, cancellation_token: CancellationToken) -> Result<String> {
         self.http_client
             .get_text(cancellation_token, "/metrics")
@@ -71,6 +71,9 @@ pub enum Error {
 
     #[error("Inference request {request_id} was cancelled before it reached the server")]
     InferenceRequestCancelled { request_id: String },
+
+    #[error("API key contains characters that cannot be sent in an HTTP header")]
+    ApiKeyNotAHeaderValue,
 }
 
 pub type Result<TValue> = std::result::Result<TValue, Error>;
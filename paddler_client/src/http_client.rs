@@ -1,4 +1,5 @@
 use reqwest::Client;
+use reqwest::RequestBuilder;
 use reqwest::Response;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
@@ -11,6 +12,7 @@ use crate::send_checked_request::send_checked_request;
 
 #[derive(Clone)]
 pub struct HttpClient {
+    api_key: Option<String>,
     reqwest_client: Client,
     url: Url,
 }
@@ -19,14 +21,38 @@ impl HttpClient {
     #[must_use]
     pub fn new(url: Url) -> Self {
         Self {
+            api_key: None,
             reqwest_client: Client::new(),
             url,
         }
     }
 
+    #[must_use]
+    pub fn with_api_key(self, api_key: Option<String>) -> Self {
+        Self { api_key, ..self }
+    }
+
+    fn authorized(&self, request_builder: RequestBuilder) -> RequestBuilder {
+        match &self.api_key {
+            Some(api_key) => request_builder.bearer_auth(api_key),
+            None => request_builder,
+        }
+    }
+
+    pub async fn delete(
+        &self,
+        cancellation_token: CancellationToken,
+        path: &str,
+    ) -> Result<Response> {
+        let api_url = format_api_url(&self.url, path);
+        let request_builder = self.authorized(self.reqwest_client.delete(&api_url));
+
+        send_checked_request(cancellation_token, api_url, request_builder).await
+    }
+
     pub async fn get(&self, cancellation_token: CancellationToken, path: &str) -> Result<Response> {
         let api_url = format_api_url(&self.url, path);
-        let request_builder = self.reqwest_client.get(&api_url);
+        let request_builder = self.authorized(self.reqwest_client.get(&api_url));
 
         send_checked_request(cancellation_token, api_url, request_builder).await
     }
@@ -54,7 +80,14 @@ impl HttpClient {
         body: &TBody,
     ) -> Result<Response> {
         let api_url = format_api_url(&self.url, path);
-        let request_builder = self.reqwest_client.post(&api_url).json(body);
+        let request_builder = self.authorized(self.reqwest_client.post(&api_url).json(body));
+
+        send_checked_request(cancellation_token, api_url, request_builder).await
+    }
+
+    pub async fn put(&self, cancellation_token: CancellationToken, path: &str) -> Result<Response> {
+        let api_url = format_api_url(&self.url, path);
+        let request_builder = self.authorized(self.reqwest_client.put(&api_url));
 
         send_checked_request(cancellation_token, api_url, request_builder).await
     }
@@ -66,7 +99,7 @@ impl HttpClient {
         body: &TBody,
     ) -> Result<Response> {
         let api_url = format_api_url(&self.url, path);
-        let request_builder = self.reqwest_client.put(&api_url).json(body);
+        let request_builder = self.authorized(self.reqwest_client.put(&api_url).json(body));
 
         send_checked_request(cancellation_token, api_url, request_builder).await
     }
@@ -145,4 +178,14 @@ mod tests {
             Err(Error::RequestCancelled { .. })
         ));
     }
+
+    #[tokio::test]
+    async fn a_cancelled_token_rejects_a_delete_request() {
+        assert!(matches!(
+            unreachable_client()
+                .delete(cancelled_token(), "/api/v1/agent/agent-1/cordon")
+                .await,
+            Err(Error::RequestCancelled { .. })
+        ));
+    }
 }
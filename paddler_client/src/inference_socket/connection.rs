@@ -18,6 +18,9 @@ use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::sync::mpsc::UnboundedSender;
 use tokio::task::JoinHandle;
 use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest as _;
+use tokio_tungstenite::tungstenite::http::HeaderValue;
+use tokio_tungstenite::tungstenite::http::header::AUTHORIZATION;
 use url::Url;
 
 use crate::error::Error;
@@ -38,11 +41,22 @@ pub struct Connection {
 
 impl Connection {
     pub async fn connect(
+        api_key: Option<&str>,
         connection_url: Url,
         notification_tx: broadcast::Sender<Notification>,
     ) -> Result<Self> {
         let ws_url = url(connection_url)?;
-        let (ws_stream, _) = connect_async(ws_url.as_str()).await?;
+        let mut ws_request = ws_url.as_str().into_client_request()?;
+
+        if let Some(api_key) = api_key {
+            ws_request.headers_mut().insert(
+                AUTHORIZATION,
+                HeaderValue::from_str(&format!("Bearer {api_key}"))
+                    .map_err(|_| Error::ApiKeyNotAHeaderValue)?,
+            );
+        }
+
+        let (ws_stream, _) = connect_async(ws_request).await?;
         let (ws_write, ws_read) = ws_stream.split();
 
         let pending: PendingRequests = Arc::new(DashMap::new());
@@ -149,6 +163,9 @@ mod tests {
     use tokio::task::yield_now;
     use tokio::time::timeout;
     use tokio_tungstenite::accept_async;
+    use tokio_tungstenite::accept_hdr_async;
+    use tokio_tungstenite::tungstenite::handshake::server::Request;
+    use tokio_tungstenite::tungstenite::handshake::server::Response;
     use url::Url;
 
     use super::Connection;
@@ -159,7 +176,11 @@ mod tests {
         let url = Url::parse("http://127.0.0.1:1").unwrap();
         let (notification_tx, _notification_rx) = broadcast::channel(1);
 
-        assert!(Connection::connect(url, notification_tx).await.is_err());
+        assert!(
+            Connection::connect(None, url, notification_tx)
+                .await
+                .is_err()
+        );
     }
 
     #[tokio::test]
@@ -185,7 +206,7 @@ mod tests {
 
         let url = Url::parse(&format!("http://{address}")).expect("the fixture URL must be valid");
         let (notification_tx, _notification_rx) = broadcast::channel(1);
-        let connection = Connection::connect(url, notification_tx)
+        let connection = Connection::connect(None, url, notification_tx)
             .await
             .expect("the client must connect to the fixture server");
 
@@ -197,6 +218,52 @@ mod tests {
             .expect("the fixture server task must not panic");
     }
 
+    #[expect(
+        clippy::result_large_err,
+        reason = "the handshake callback signature is dictated by tungstenite"
+    )]
+    #[tokio::test]
+    async fn connect_sends_the_api_key_as_a_bearer_token() {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("the fixture server must bind a loopback port");
+        let address = listener
+            .local_addr()
+            .expect("the fixture server must report its address");
+
+        let server_task = tokio::spawn(async move {
+            let (tcp_stream, _peer_address) = listener
+                .accept()
+                .await
+                .expect("the fixture server must accept the connection");
+            let mut authorization = None;
+
+            accept_hdr_async(tcp_stream, |request: &Request, response: Response| {
+                authorization = request
+                    .headers()
+                    .get("authorization")
+                    .map(|value| value.to_str().unwrap().to_owned());
+
+                Ok(response)
+            })
+            .await
+            .expect("the fixture server must complete the websocket handshake");
+
+            authorization
+        });
+
+        let url = Url::parse(&format!("http://{address}")).expect("the fixture URL must be valid");
+        let (notification_tx, _notification_rx) = broadcast::channel(1);
+        let _connection = Connection::connect(Some("sk-test"), url, notification_tx)
+            .await
+            .expect("the client must connect to the fixture server");
+
+        assert_eq!(
+            server_task.await.unwrap(),
+            Some("Bearer sk-test".to_owned())
+        );
+    }
+
     #[tokio::test]
     async fn reports_a_dropped_connection_when_sending_over_a_closed_write_channel() {
         let (write_tx, write_rx) = mpsc::unbounded_channel();
@@ -234,7 +301,7 @@ mod tests {
 
         let url = Url::parse(&format!("http://{address}")).expect("the fixture URL must be valid");
         let (notification_tx, _notification_rx) = broadcast::channel(1);
-        let connection = Connection::connect(url, notification_tx)
+        let connection = Connection::connect(None, url, notification_tx)
             .await
             .expect("the client must connect to the fixture server");
 
@@ -23,6 +23,7 @@ struct EstablishedRequest {
 }
 
 pub struct Pool {
+    api_key: Option<String>,
     url: Url,
     connections: Mutex<Vec<Option<Arc<Connection>>>>,
     capacity: NonZeroUsize,
@@ -36,6 +37,7 @@ impl Pool {
         let (notification_tx, _initial_notification_rx) = broadcast::channel(capacity.get());
 
         Self {
+            api_key: None,
             url,
             connections: Mutex::new((0..capacity.get()).map(|_| None).collect()),
             capacity,
@@ -44,6 +46,11 @@ impl Pool {
         }
     }
 
+    #[must_use]
+    pub fn with_api_key(self, api_key: Option<String>) -> Self {
+        Self { api_key, ..self }
+    }
+
     pub fn subscribe_to_notifications(&self) -> broadcast::Receiver<Notification> {
         self.notification_tx.subscribe()
     }
@@ -137,8 +144,12 @@ impl Pool {
         };
 
         if needs_connect {
-            let new_connection =
-                Connection::connect(self.url.clone(), self.notification_tx.clone()).await?;
+            let new_connection = Connection::connect(
+                self.api_key.as_deref(),
+                self.url.clone(),
+                self.notification_tx.clone(),
+            )
+            .await?;
             let mut connections = self.connections.lock().await;
             connections[index] = Some(Arc::new(new_connection));
         }
@@ -4,6 +4,7 @@ import { AgentIssueSchema } from "./AgentIssue";
 
 export const AgentSchema = z
   .object({
+    cordon_status: z.enum(["Drained", "Draining", "Uncordoned"]),
     desired_slots_total: z.number(),
     download_current: z.number(),
     download_filename: z.string().nullable(),
@@ -12,6 +13,7 @@ export const AgentSchema = z
     id: z.string(),
     issues: z.array(AgentIssueSchema),
     model_path: z.string().nullable(),
+    model_pool: z.string().nullable(),
     name: z.string().nullable(),
     slots_processing: z.number(),
     slots_total: z.number(),
@@ -22,6 +24,8 @@ export const AgentSchema = z
       "Fresh",
       "Stuck",
     ]),
+    time_to_first_token_ms: z.number().nullable(),
+    tokens_per_second: z.number().nullable(),
     uses_chat_template_override: z.boolean(),
   })
   .strict();
@@ -3,12 +3,14 @@ import { z } from "zod";
 import { AgentDesiredModelSchema } from "./AgentDesiredModel";
 import { ChatTemplateSchema } from "./ChatTemplate";
 import { InferenceParametersSchema } from "./InferenceParameters";
+import { ModelPoolDesiredStateSchema } from "./ModelPoolDesiredState";
 
 export const BalancerDesiredStateSchema = z
   .object({
     chat_template_override: ChatTemplateSchema.nullable(),
     inference_parameters: InferenceParametersSchema,
     model: AgentDesiredModelSchema,
+    model_pools: z.record(z.string(), ModelPoolDesiredStateSchema).default({}),
     multimodal_projection: AgentDesiredModelSchema,
     use_chat_template_override: z.boolean(),
   })
@@ -2,7 +2,9 @@ import { z } from "zod";
 
 export const BufferedRequestsResponseSchema = z
   .object({
+    buffered_requests_batch: z.number(),
     buffered_requests_current: z.number(),
+    buffered_requests_interactive: z.number(),
   })
   .strict();
 
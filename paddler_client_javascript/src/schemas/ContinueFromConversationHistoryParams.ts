@@ -2,6 +2,8 @@ import { z } from "zod";
 
 import { ConversationMessageSchema } from "./ConversationMessage";
 import { GrammarConstraintSchema } from "./GrammarConstraint";
+import { LogprobsRequestSchema } from "./LogprobsRequest";
+import { SamplingOverridesSchema } from "./SamplingOverrides";
 import { ToolSchema } from "./Tool";
 
 export const ContinueFromConversationHistoryParamsSchema = z
@@ -10,8 +12,13 @@ export const ContinueFromConversationHistoryParamsSchema = z
     conversation_history: z.array(ConversationMessageSchema),
     enable_thinking: z.boolean(),
     grammar: GrammarConstraintSchema.nullable().optional(),
+    logprobs: LogprobsRequestSchema.nullable().optional(),
     max_tokens: z.number().int(),
+    model: z.string().nullable().optional(),
     parse_tool_calls: z.boolean().optional(),
+    sampling: SamplingOverridesSchema.nullable().optional(),
+    session_key: z.string().nullable().optional(),
+    stop: z.array(z.string()).optional(),
     tools: z.array(ToolSchema).optional(),
   })
   .strict();
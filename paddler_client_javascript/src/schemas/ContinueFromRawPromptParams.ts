@@ -1,12 +1,19 @@
 import { z } from "zod";
 
 import { GrammarConstraintSchema } from "./GrammarConstraint";
+import { LogprobsRequestSchema } from "./LogprobsRequest";
+import { SamplingOverridesSchema } from "./SamplingOverrides";
 
 export const ContinueFromRawPromptParamsSchema = z
   .object({
     grammar: GrammarConstraintSchema.nullable().optional(),
+    logprobs: LogprobsRequestSchema.nullable().optional(),
     max_tokens: z.number().int(),
+    model: z.string().nullable().optional(),
     raw_prompt: z.string(),
+    sampling: SamplingOverridesSchema.nullable().optional(),
+    session_key: z.string().nullable().optional(),
+    stop: z.array(z.string()).optional(),
   })
   .strict();
 
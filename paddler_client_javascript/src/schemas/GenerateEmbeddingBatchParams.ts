@@ -5,6 +5,7 @@ import { EmbeddingNormalizationMethodSchema } from "./EmbeddingNormalizationMeth
 
 export const GenerateEmbeddingBatchParamsSchema = z.object({
   input_documents: z.array(EmbeddingInputDocumentSchema),
+  model: z.string().nullable().optional(),
   normalization_method: EmbeddingNormalizationMethodSchema,
 });
 
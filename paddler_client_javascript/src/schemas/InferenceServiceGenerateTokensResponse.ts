@@ -19,10 +19,29 @@ const TokenUsageSchema = z.object({
   undeterminable_tokens: z.number(),
 });
 
+const StopReasonSchema = z.enum([
+  "Cancelled",
+  "EndOfGeneration",
+  "MaxTokens",
+  "StopSequence",
+]);
+
 const GenerationSummarySchema = z.object({
+  stop_reason: StopReasonSchema.default("EndOfGeneration"),
   usage: TokenUsageSchema,
 });
 
+const TopLogprobSchema = z.object({
+  logprob: z.number(),
+  token: z.string(),
+});
+
+const TokenLogprobSchema = z.object({
+  logprob: z.number(),
+  token: z.string(),
+  top_logprobs: z.array(TopLogprobSchema),
+});
+
 const RawToolCallTokensSchema = z.object({
   text: z.string(),
   ffi_error_message: z.string(),
@@ -49,6 +68,7 @@ const GeneratedTokenResultSchema = z.union([
   z.object({ MultimodalNotSupported: z.string() }),
   z.object({ SamplerError: z.string() }),
   z.object({ TokenGenerationDisabled: z.string() }),
+  z.object({ TokenLogprob: TokenLogprobSchema }),
   z.object({ ToolCallParsed: z.array(ParsedToolCallSchema) }),
   z.object({ ToolCallParseFailed: z.string() }),
   z.object({ ToolCallValidationFailed: z.array(z.string()) }),
@@ -61,6 +81,7 @@ type Normalised =
       done: true;
       error: null;
       generated_by: string | null;
+      logprob: null;
       ok: true;
       rawToolCallTokens: null;
       request_id: string;
@@ -73,6 +94,20 @@ type Normalised =
       done: false;
       error: null;
       generated_by: string | null;
+      logprob: z.infer<typeof TokenLogprobSchema>;
+      ok: true;
+      rawToolCallTokens: null;
+      request_id: string;
+      summary: null;
+      token: null;
+      tokenKind: null;
+      toolCalls: null;
+    }
+  | {
+      done: false;
+      error: null;
+      generated_by: string | null;
+      logprob: null;
       ok: true;
       rawToolCallTokens: null;
       request_id: string;
@@ -85,6 +120,7 @@ type Normalised =
       done: false;
       error: null;
       generated_by: string | null;
+      logprob: null;
       ok: true;
       rawToolCallTokens: null;
       request_id: string;
@@ -97,6 +133,7 @@ type Normalised =
       done: false;
       error: null;
       generated_by: string | null;
+      logprob: null;
       ok: true;
       rawToolCallTokens: z.infer<typeof RawToolCallTokensSchema>;
       request_id: string;
@@ -109,6 +146,7 @@ type Normalised =
       done: true;
       error: { code: number; description: string };
       generated_by: string | null;
+      logprob: null;
       ok: false;
       rawToolCallTokens: null;
       request_id: string;
@@ -121,6 +159,7 @@ type Normalised =
       done: false;
       error: { code: number; description: string };
       generated_by: string | null;
+      logprob: null;
       ok: false;
       rawToolCallTokens: null;
       request_id: string;
@@ -140,6 +179,7 @@ function terminalError(
     done: true,
     error: Object.freeze({ code, description }),
     generated_by,
+    logprob: null,
     ok: false,
     rawToolCallTokens: null,
     request_id,
@@ -160,6 +200,7 @@ function nonTerminalError(
     done: false,
     error: Object.freeze({ code, description }),
     generated_by,
+    logprob: null,
     ok: false,
     rawToolCallTokens: null,
     request_id,
@@ -180,6 +221,7 @@ function streamingToken(
     done: false,
     error: null,
     generated_by,
+    logprob: null,
     ok: true,
     rawToolCallTokens: null,
     request_id,
@@ -199,6 +241,7 @@ function unrecognizedToolCallFormat(
     done: false,
     error: null,
     generated_by,
+    logprob: null,
     ok: true,
     rawToolCallTokens: Object.freeze(raw),
     request_id,
@@ -260,6 +303,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       return streamingToken(
         request_id,
         generated_by,
+        logprob: null,
         variant.UndeterminableToken,
         "undeterminable",
       );
@@ -270,6 +314,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
         done: true,
         error: null,
         generated_by,
+        logprob: null,
         ok: true,
         rawToolCallTokens: null,
         request_id,
@@ -280,11 +325,28 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       });
     }
 
+    if ("TokenLogprob" in variant) {
+      return Object.freeze({
+        done: false,
+        error: null,
+        generated_by,
+        logprob: Object.freeze(variant.TokenLogprob),
+        ok: true,
+        rawToolCallTokens: null,
+        request_id,
+        summary: null,
+        token: null,
+        tokenKind: null,
+        toolCalls: null,
+      });
+    }
+
     if ("ToolCallParsed" in variant) {
       return Object.freeze({
         done: false,
         error: null,
         generated_by,
+        logprob: null,
         ok: true,
         rawToolCallTokens: null,
         request_id,
@@ -299,6 +361,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       return unrecognizedToolCallFormat(
         request_id,
         generated_by,
+        logprob: null,
         variant.UnrecognizedToolCallFormat,
       );
     }
@@ -311,6 +374,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       return nonTerminalError(
         request_id,
         generated_by,
+        logprob: null,
         422,
         variant.ToolCallValidationFailed.join("; "),
       );
@@ -320,6 +384,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       return terminalError(
         request_id,
         generated_by,
+        logprob: null,
         400,
         variant.ToolCallValidatorBuildFailed,
       );
@@ -333,6 +398,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       return terminalError(
         request_id,
         generated_by,
+        logprob: null,
         400,
         variant.GrammarIncompatibleWithThinking,
       );
@@ -359,6 +425,7 @@ export const InferenceServiceGenerateTokensResponseSchema = z
       return terminalError(
         request_id,
         generated_by,
+        logprob: null,
         400,
         `image required ${details.image_tokens} tokens but n_batch is ${details.n_batch}`,
       );
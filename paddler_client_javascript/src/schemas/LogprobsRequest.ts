@@ -0,0 +1,9 @@
+import { z } from "zod";
+
+export const LogprobsRequestSchema = z
+  .object({
+    top_logprobs: z.number().int().min(0).max(20).optional(),
+  })
+  .strict();
+
+export type LogprobsRequest = z.infer<typeof LogprobsRequestSchema>;
@@ -0,0 +1,17 @@
+import { z } from "zod";
+
+export const SamplingOverridesSchema = z
+  .object({
+    min_p: z.number().min(0).max(1).nullable().optional(),
+    penalty_frequency: z.number().min(-2).max(2).nullable().optional(),
+    penalty_last_n: z.number().int().min(-1).nullable().optional(),
+    penalty_presence: z.number().min(-2).max(2).nullable().optional(),
+    penalty_repeat: z.number().min(0).max(2).nullable().optional(),
+    seed: z.number().int().min(0).max(4294967295).nullable().optional(),
+    temperature: z.number().min(0).max(2).nullable().optional(),
+    top_k: z.number().int().min(0).nullable().optional(),
+    top_p: z.number().min(0).max(1).nullable().optional(),
+  })
+  .strict();
+
+export type SamplingOverrides = z.infer<typeof SamplingOverridesSchema>;
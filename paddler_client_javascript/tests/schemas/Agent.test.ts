@@ -5,6 +5,7 @@ import { AgentSchema } from "../../src/schemas/Agent";
 
 test("parses a fully populated agent payload", function () {
   const parsed = AgentSchema.parse({
+    cordon_status: "Draining",
     desired_slots_total: 4,
     download_current: 0,
     download_filename: null,
@@ -13,20 +14,26 @@ test("parses a fully populated agent payload", function () {
     id: "agent-0",
     issues: [],
     model_path: "/models/qwen.gguf",
+    model_pool: null,
     name: "agent-0",
     slots_processing: 1,
     slots_total: 4,
     state_application_status: "Applied",
+    time_to_first_token_ms: 180,
+    tokens_per_second: 42.5,
     uses_chat_template_override: false,
   });
 
   strictEqual(parsed.id, "agent-0");
   strictEqual(parsed.state_application_status, "Applied");
+  strictEqual(parsed.cordon_status, "Draining");
+  strictEqual(parsed.tokens_per_second, 42.5);
 });
 
 test("rejects an unknown state_application_status", function () {
   throws(function () {
     AgentSchema.parse({
+      cordon_status: "Uncordoned",
       desired_slots_total: 1,
       download_current: 0,
       download_filename: null,
@@ -35,10 +42,13 @@ test("rejects an unknown state_application_status", function () {
       id: "agent-x",
       issues: [],
       model_path: null,
+      model_pool: null,
       name: null,
       slots_processing: 0,
       slots_total: 1,
       state_application_status: "Unknown",
+      time_to_first_token_ms: null,
+      tokens_per_second: null,
       uses_chat_template_override: false,
     });
   });
@@ -64,6 +64,61 @@ test("Done normalises with the full usage summary", function () {
   strictEqual(parsed.done, true);
   strictEqual(parsed.error, null);
   deepStrictEqual(parsed.summary?.usage.prompt_tokens, 10);
+  strictEqual(parsed.summary?.stop_reason, "EndOfGeneration");
+});
+
+test("Done carries the stop reason", function () {
+  const parsed = InferenceServiceGenerateTokensResponseSchema.parse({
+    Response: {
+      generated_by: null,
+      request_id: "req-3",
+      response: {
+        GeneratedToken: {
+          Done: {
+            stop_reason: "StopSequence",
+            usage: {
+              prompt_tokens: 10,
+              cached_prompt_tokens: 0,
+              input_image_tokens: 0,
+              input_audio_tokens: 0,
+              content_tokens: 5,
+              reasoning_tokens: 0,
+              tool_call_tokens: 0,
+              undeterminable_tokens: 0,
+            },
+          },
+        },
+      },
+    },
+  });
+
+  strictEqual(parsed.summary?.stop_reason, "StopSequence");
+});
+
+test("TokenLogprob normalises to a non-terminal logprob", function () {
+  const parsed = InferenceServiceGenerateTokensResponseSchema.parse({
+    Response: {
+      generated_by: null,
+      request_id: "req-3",
+      response: {
+        GeneratedToken: {
+          TokenLogprob: {
+            logprob: -0.25,
+            token: " yes",
+            top_logprobs: [
+              { logprob: -0.25, token: " yes" },
+              { logprob: -1.5, token: " no" },
+            ],
+          },
+        },
+      },
+    },
+  });
+
+  strictEqual(parsed.done, false);
+  strictEqual(parsed.token, null);
+  strictEqual(parsed.logprob?.logprob, -0.25);
+  strictEqual(parsed.logprob?.top_logprobs[1]?.token, " no");
 });
 
 test("ToolCallValidatorBuildFailed normalises to a terminal error", function () {
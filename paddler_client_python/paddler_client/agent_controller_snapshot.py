@@ -12,11 +12,15 @@ class AgentControllerSnapshot(BaseModel):
     download_filename: str | None = None
     download_indeterminate: bool
     download_total: int
+    draining: bool = False
     id: str
     issues: list[AgentIssue] = []
     model_path: str | None = None
+    model_pool: str | None = None
     name: str | None = None
     slots_processing: int
     slots_total: int
     state_application_status: AgentStateApplicationStatus
+    time_to_first_token_ms: int | None = None
+    tokens_per_second: float | None = None
     uses_chat_template_override: bool
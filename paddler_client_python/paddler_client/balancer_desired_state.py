@@ -3,6 +3,7 @@ from pydantic import BaseModel, Field
 from paddler_client.agent_desired_model import AgentDesiredModel
 from paddler_client.chat_template import ChatTemplate
 from paddler_client.inference_parameters import InferenceParameters
+from paddler_client.model_pool_desired_state import ModelPoolDesiredState
 
 
 class BalancerDesiredState(BaseModel):
@@ -11,6 +12,7 @@ class BalancerDesiredState(BaseModel):
         default_factory=InferenceParameters,
     )
     model: AgentDesiredModel = Field(default_factory=AgentDesiredModel.none)
+    model_pools: dict[str, ModelPoolDesiredState] = {}
     multimodal_projection: AgentDesiredModel = Field(
         default_factory=AgentDesiredModel.none,
     )
@@ -2,4 +2,6 @@ from pydantic import BaseModel
 
 
 class BufferedRequestManagerSnapshot(BaseModel):
+    buffered_requests_batch: int = 0
     buffered_requests_current: int
+    buffered_requests_interactive: int = 0
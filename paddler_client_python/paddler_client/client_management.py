@@ -143,6 +143,22 @@ class ClientManagement:
 
         return ModelMetadata.model_validate_json(response.content)
 
+    async def cordon_agent(self, agent_id: str) -> None:
+        response = await self._http_client.put(
+            f"{self._url}/api/v1/agent/{agent_id}/cordon",
+        )
+
+        if not response.is_success:
+            raise HttpError(response.status_code, response.text)
+
+    async def uncordon_agent(self, agent_id: str) -> None:
+        response = await self._http_client.delete(
+            f"{self._url}/api/v1/agent/{agent_id}/cordon",
+        )
+
+        if not response.is_success:
+            raise HttpError(response.status_code, response.text)
+
     async def get_metrics(self) -> str:
         response = await self._http_client.get(f"{self._url}/metrics")
 
@@ -2,6 +2,8 @@ from pydantic import BaseModel
 
 from paddler_client.conversation_message import ConversationMessage
 from paddler_client.grammar_constraint import GrammarConstraint
+from paddler_client.logprobs_request import LogprobsRequest
+from paddler_client.sampling_overrides import SamplingOverrides
 from paddler_client.tool import Tool
 
 
@@ -10,5 +12,10 @@ class ContinueFromConversationHistoryParams(BaseModel):
     conversation_history: list[ConversationMessage]
     enable_thinking: bool
     grammar: GrammarConstraint | None = None
+    logprobs: LogprobsRequest | None = None
     max_tokens: int
+    model: str | None = None
+    sampling: SamplingOverrides | None = None
+    session_key: str | None = None
+    stop: list[str] = []
     tools: list[Tool] = []
@@ -1,9 +1,16 @@
 from pydantic import BaseModel
 
 from paddler_client.grammar_constraint import GrammarConstraint
+from paddler_client.logprobs_request import LogprobsRequest
+from paddler_client.sampling_overrides import SamplingOverrides
 
 
 class ContinueFromRawPromptParams(BaseModel):
     grammar: GrammarConstraint | None = None
+    logprobs: LogprobsRequest | None = None
     max_tokens: int
+    model: str | None = None
     raw_prompt: str
+    sampling: SamplingOverrides | None = None
+    session_key: str | None = None
+    stop: list[str] = []
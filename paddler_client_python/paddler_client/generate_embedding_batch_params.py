@@ -8,4 +8,5 @@ from paddler_client.embedding_normalization_method import (
 
 class GenerateEmbeddingBatchParams(BaseModel):
     input_batch: list[EmbeddingInputDocument]
+    model: str | None = None
     normalization_method: EmbeddingNormalizationMethod
@@ -34,6 +34,7 @@ class InferenceMessageKind(StrEnum):
     SAMPLER_ERROR = "sampler_error"
     SERVER_ERROR = "server_error"
     TIMEOUT = "timeout"
+    TOKEN_LOGPROB = "token_logprob"
     TOOL_CALL_PARSED = "tool_call_parsed"
     TOOL_CALL_PARSE_FAILED = "tool_call_parse_failed"
     TOOL_CALL_TOKEN = "tool_call_token"
@@ -95,10 +96,42 @@ class TokenUsage:
 @dataclass(frozen=True)
 class GenerationSummary:
     usage: TokenUsage
+    stop_reason: str = "EndOfGeneration"
 
     @classmethod
     def from_dict(cls, data: dict[str, Any]) -> GenerationSummary:
-        return cls(usage=TokenUsage.from_dict(data.get("usage", {})))
+        return cls(
+            usage=TokenUsage.from_dict(data.get("usage", {})),
+            stop_reason=str(data.get("stop_reason", "EndOfGeneration")),
+        )
+
+
+@dataclass(frozen=True)
+class TopLogprob:
+    logprob: float
+    token: str
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> TopLogprob:
+        return cls(logprob=float(data["logprob"]), token=str(data["token"]))
+
+
+@dataclass(frozen=True)
+class TokenLogprob:
+    logprob: float
+    token: str
+    top_logprobs: list[TopLogprob]
+
+    @classmethod
+    def from_dict(cls, data: dict[str, Any]) -> TokenLogprob:
+        return cls(
+            logprob=float(data["logprob"]),
+            token=str(data["token"]),
+            top_logprobs=[
+                TopLogprob.from_dict(top_logprob)
+                for top_logprob in data.get("top_logprobs", [])
+            ],
+        )
 
 
 @dataclass(frozen=True)
@@ -110,6 +143,7 @@ class InferenceMessage:
     error_message: str | None = None
     error_code: int | None = None
     summary: GenerationSummary | None = None
+    token_logprob: TokenLogprob | None = None
     parsed_tool_calls: list[ParsedToolCall] | None = None
     raw_tool_call_tokens: RawToolCallTokens | None = None
     oversized_image_details: OversizedImageDetails | None = None
@@ -125,7 +159,10 @@ class InferenceMessage:
 
     @property
     def is_terminal(self) -> bool:
-        return not self.is_token and self.kind != InferenceMessageKind.EMBEDDING
+        return not self.is_token and self.kind not in {
+            InferenceMessageKind.EMBEDDING,
+            InferenceMessageKind.TOKEN_LOGPROB,
+        }
 
 
 def parse_inference_client_message(
@@ -331,6 +368,23 @@ def _build_image_exceeds_batch_size_message(
     )
 
 
+def _build_token_logprob_message(
+    request_id: str,
+    payload: Any,
+    generated_by: str | None,
+) -> InferenceMessage:
+    if not isinstance(payload, dict):
+        msg = f"TokenLogprob payload is not a dict: {payload!r}"
+        raise TypeError(msg)
+    typed_logprob = cast("dict[str, Any]", payload)
+    return InferenceMessage(
+        request_id=request_id,
+        kind=InferenceMessageKind.TOKEN_LOGPROB,
+        token_logprob=TokenLogprob.from_dict(typed_logprob),
+        generated_by=generated_by,
+    )
+
+
 def _build_token_kind_message(
     request_id: str,
     kind: InferenceMessageKind,
@@ -368,6 +422,7 @@ _STRUCTURED_HANDLERS: dict[str, _StructuredHandler] = {
     "ToolCallValidationFailed": _build_tool_call_validation_failed_message,
     "UnrecognizedToolCallFormat": _build_unrecognized_tool_call_format_message,
     "ImageExceedsBatchSize": _build_image_exceeds_batch_size_message,
+    "TokenLogprob": _build_token_logprob_message,
 }
 
 
@@ -0,0 +1,5 @@
+from pydantic import BaseModel
+
+
+class LogprobsRequest(BaseModel):
+    top_logprobs: int = 0
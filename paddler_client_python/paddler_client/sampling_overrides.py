@@ -0,0 +1,13 @@
+from pydantic import BaseModel
+
+
+class SamplingOverrides(BaseModel):
+    min_p: float | None = None
+    penalty_frequency: float | None = None
+    penalty_last_n: int | None = None
+    penalty_presence: float | None = None
+    penalty_repeat: float | None = None
+    seed: int | None = None
+    temperature: float | None = None
+    top_k: int | None = None
+    top_p: float | None = None
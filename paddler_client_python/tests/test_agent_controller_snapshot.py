@@ -31,6 +31,8 @@ def test_agent_controller_snapshot_deserialization() -> None:
     assert len(snapshot.issues) == 1
     assert snapshot.issues[0].variant == "SlotCannotStart"
     assert snapshot.state_application_status == AgentStateApplicationStatus.FRESH
+    assert snapshot.draining is False
+    assert snapshot.tokens_per_second is None
 
 
 def test_agent_controller_pool_snapshot_deserialization() -> None:
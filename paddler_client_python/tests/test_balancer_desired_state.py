@@ -1,5 +1,6 @@
 from paddler_client.balancer_desired_state import BalancerDesiredState
 from paddler_client.chat_template import ChatTemplate
+from paddler_client.model_pool_desired_state import ModelPoolDesiredState
 
 
 def test_balancer_desired_state_defaults() -> None:
@@ -21,3 +22,14 @@ def test_balancer_desired_state_with_chat_template() -> None:
 
     assert dumped["chat_template_override"] == {"content": "{{ messages }}"}
     assert dumped["use_chat_template_override"] is True
+
+
+def test_balancer_desired_state_with_model_pools() -> None:
+    state = BalancerDesiredState(
+        model_pools={
+            "embeddings": ModelPoolDesiredState(),
+        },
+    )
+    dumped = state.model_dump(mode="json")
+
+    assert dumped["model_pools"]["embeddings"]["model"] == "None"
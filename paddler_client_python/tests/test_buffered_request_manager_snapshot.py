@@ -9,3 +9,16 @@ def test_buffered_request_manager_snapshot_deserialization() -> None:
     )
 
     assert snapshot.buffered_requests_current == 12
+
+
+def test_buffered_request_manager_snapshot_per_priority_deserialization() -> None:
+    snapshot = BufferedRequestManagerSnapshot.model_validate(
+        {
+            "buffered_requests_batch": 9,
+            "buffered_requests_current": 12,
+            "buffered_requests_interactive": 3,
+        }
+    )
+
+    assert snapshot.buffered_requests_batch == 9
+    assert snapshot.buffered_requests_interactive == 3
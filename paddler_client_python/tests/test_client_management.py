@@ -269,6 +269,48 @@ async def test_get_metrics_returns_text() -> None:
         await client.close()
 
 
+async def test_cordon_and_uncordon_agent() -> None:
+    received: list[tuple[str, str]] = []
+
+    def handler(request: httpx.Request) -> httpx.Response:
+        received.append((request.method, str(request.url)))
+
+        return httpx.Response(204)
+
+    transport = httpx.MockTransport(handler)
+    client = ClientManagement(
+        url="http://test:8085",
+        http_client=httpx.AsyncClient(transport=transport),
+    )
+
+    try:
+        await client.cordon_agent("agent-1")
+        await client.uncordon_agent("agent-1")
+        assert received == [
+            ("PUT", "http://test:8085/api/v1/agent/agent-1/cordon"),
+            ("DELETE", "http://test:8085/api/v1/agent/agent-1/cordon"),
+        ]
+    finally:
+        await client.close()
+
+
+async def test_cordon_agent_raises_for_unknown_agent() -> None:
+    def handler(request: httpx.Request) -> httpx.Response:
+        return httpx.Response(404, text="Agent not found")
+
+    transport = httpx.MockTransport(handler)
+    client = ClientManagement(
+        url="http://test:8085",
+        http_client=httpx.AsyncClient(transport=transport),
+    )
+
+    try:
+        with pytest.raises(HttpError):
+            await client.cordon_agent("missing")
+    finally:
+        await client.close()
+
+
 async def test_context_manager() -> None:
     def handler(request: httpx.Request) -> httpx.Response:
         return httpx.Response(200, text="OK")
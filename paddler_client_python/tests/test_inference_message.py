@@ -240,6 +240,34 @@ def test_parse_undeterminable_token_response() -> None:
     assert message.is_token
 
 
+def test_parse_token_logprob_response() -> None:
+    data = {
+        "Response": {
+            "request_id": "req-1",
+            "response": {
+                "GeneratedToken": {
+                    "TokenLogprob": {
+                        "logprob": -0.25,
+                        "token": " yes",
+                        "top_logprobs": [
+                            {"logprob": -0.25, "token": " yes"},
+                            {"logprob": -1.5, "token": " no"},
+                        ],
+                    }
+                }
+            },
+        }
+    }
+    message = parse_inference_client_message(data)
+
+    assert message.kind == InferenceMessageKind.TOKEN_LOGPROB
+    assert not message.is_token
+    assert not message.is_terminal
+    assert message.token_logprob is not None
+    assert message.token_logprob.logprob == -0.25
+    assert message.token_logprob.top_logprobs[1].token == " no"
+
+
 def test_parse_done_response_carries_summary() -> None:
     data = {
         "Response": {
@@ -273,6 +301,7 @@ def test_parse_done_response_carries_summary() -> None:
     assert message.summary.usage.reasoning_tokens == 1
     assert message.summary.usage.completion_tokens == 7
     assert message.summary.usage.total_tokens == 11
+    assert message.summary.stop_reason == "EndOfGeneration"
 
 
 def test_parse_timeout() -> None:
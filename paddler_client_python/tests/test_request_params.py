@@ -16,6 +16,7 @@ from paddler_client.grammar_constraint import (
     GbnfGrammarConstraint,
     JsonSchemaGrammarConstraint,
 )
+from paddler_client.sampling_overrides import SamplingOverrides
 
 
 def test_continue_from_conversation_history_params_serialization() -> None:
@@ -96,3 +97,27 @@ def test_conversation_history_params_with_json_schema_grammar() -> None:
 
     assert dumped["grammar"]["type"] == "json_schema"
     assert dumped["grammar"]["schema"] == '{"type": "object"}'
+
+
+def test_continue_from_raw_prompt_params_with_sampling_overrides() -> None:
+    params = ContinueFromRawPromptParams(
+        max_tokens=50,
+        raw_prompt="Extract the date:",
+        sampling=SamplingOverrides(temperature=0.0, top_k=1),
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert dumped["sampling"]["temperature"] == 0.0
+    assert dumped["sampling"]["top_k"] == 1
+    assert dumped["sampling"]["top_p"] is None
+
+
+def test_continue_from_raw_prompt_params_with_stop_sequences() -> None:
+    params = ContinueFromRawPromptParams(
+        max_tokens=50,
+        raw_prompt="Q: 2+2\nA: 4\n###\nQ: 3+3\nA:",
+        stop=["\n###"],
+    )
+    dumped = params.model_dump(mode="json")
+
+    assert dumped["stop"] == ["\n###"]
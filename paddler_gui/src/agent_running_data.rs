@@ -1,4 +1,5 @@
 use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+use paddler_messaging::agent_cordon_status::AgentCordonStatus;
 use paddler_messaging::slot_aggregated_status_snapshot::SlotAggregatedStatusSnapshot;
 
 pub struct AgentRunningData {
@@ -11,6 +12,7 @@ impl AgentRunningData {
     pub fn apply_status(&mut self, status: SlotAggregatedStatusSnapshot) {
         self.connected = true;
         self.snapshot = AgentControllerSnapshot {
+            cordon_status: AgentCordonStatus::Uncordoned,
             desired_slots_total: status.desired_slots_total,
             download_current: status.download_current,
             download_filename: status.download_filename,
@@ -19,10 +21,13 @@ impl AgentRunningData {
             id: String::new(),
             issues: status.issues,
             model_path: status.model_path,
+            model_pool: None,
             name: self.snapshot.name.clone(),
             slots_processing: status.slots_processing,
             slots_total: status.slots_total,
             state_application_status: status.state_application_status,
+            time_to_first_token_ms: None,
+            tokens_per_second: None,
             uses_chat_template_override: status.uses_chat_template_override,
         };
     }
@@ -20,10 +20,15 @@ use iced::widget::image::Handle as ImageHandle;
 use iced::widget::operation;
 use iced::widget::stack;
 use iced::window;
+use paddler_balancer::agent_failover_policy::AgentFailoverPolicy;
+use paddler_balancer::autoscaling_signal::DEFAULT_TARGET_UTILIZATION;
+use paddler_balancer::dispatch_strategy::DispatchStrategy;
 use paddler_balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler_balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
+use paddler_balancer::rate_limit_configuration::RateLimitConfiguration;
 #[cfg(feature = "web_admin_panel")]
 use paddler_balancer::resolved_socket_addr::ResolvedSocketAddr;
+use paddler_balancer::rollout_strategy::RolloutStrategy;
 use paddler_balancer::state_database_type::StateDatabaseType;
 #[cfg(feature = "web_admin_panel")]
 use paddler_balancer::web_admin_panel_service::configuration::Configuration as WebAdminPanelServiceConfiguration;
@@ -154,9 +159,16 @@ impl App {
                     }
                     join_balancer_form_handler::Action::ConnectAgent {
                         agent_name,
+                        join_token,
                         management_address,
                         slots,
-                    } => self.spawn_agent(form.connect(), agent_name, management_address, slots),
+                    } => self.spawn_agent(
+                        form.connect(),
+                        agent_name,
+                        join_token,
+                        management_address,
+                        slots,
+                    ),
                 }
             }
             (CurrentScreen::StartBalancerForm(mut form), Message::StartBalancerForm(msg)) => {
@@ -367,6 +379,7 @@ impl App {
         &mut self,
         screen: Screen<AgentRunning>,
         agent_name: Option<String>,
+        join_token: Option<String>,
         management_address: String,
         slots: i32,
     ) -> Task<Message> {
@@ -377,9 +390,13 @@ impl App {
         Task::stream(iced::stream::channel(1, async move |mut output| {
             let mut runner = AgentRunner::start(AgentRunnerParams {
                 agent_name,
+                join_token,
                 management_address,
                 cancellation_token: cancel,
+                management_tls_configuration: None,
+                model_pool: None,
                 slots,
+                trace_exporter_configuration: None,
             });
 
             let slot_aggregated_status = runner.slot_aggregated_status.clone();
@@ -488,26 +505,40 @@ impl App {
                     statsd_prefix: statsd_prefix.to_owned(),
                     statsd_reporting_interval: Duration::from_secs(10),
                 },
+                tls_configuration: None,
             });
 
         let params = BalancerRunnerParams {
+            api_keys_file: None,
+            audit_log_service_configuration: None,
+            autoscaling_hook_service_configuration: None,
+            autoscaling_target_utilization: DEFAULT_TARGET_UTILIZATION,
+            batch_buffered_request_limits: None,
             buffered_request_timeout,
             inference_service_configuration: InferenceServiceConfiguration {
                 addr: inference_addr,
+                agent_failover_policy: AgentFailoverPolicy::Disabled,
                 cors_allowed_hosts: vec![],
+                dispatch_strategy: DispatchStrategy::LeastBusy,
                 inference_item_timeout: Duration::from_secs(30),
+                tls_configuration: None,
             },
             management_service_configuration: ManagementServiceConfiguration {
                 addr: management_addr,
+                agent_join_token: None,
                 cors_allowed_hosts: vec![],
+                tls_configuration: None,
             },
             max_buffered_requests,
             openai_service_configuration: None,
             cancellation_token: cancel,
+            rate_limit_configuration: RateLimitConfiguration::default(),
+            rollout_strategy: RolloutStrategy::AllAtOnce,
             shutdown_options: ServiceShutdownOptions::default(),
             state_database_type: StateDatabaseType::Memory(Box::new(desired_state.clone())),
             statsd_prefix: statsd_prefix.to_owned(),
             statsd_service_configuration: None,
+            trace_exporter_configuration: None,
             #[cfg(feature = "web_admin_panel")]
             web_admin_panel_service_configuration,
         };
@@ -6,6 +6,7 @@ use crate::join_balancer_form_data::JoinBalancerFormData;
 pub enum Message {
     SetAgentName(String),
     SetBalancerAddress(String),
+    SetJoinToken(String),
     SetSlotsCount(String),
     Connect,
     Cancel,
@@ -16,6 +17,7 @@ pub enum Action {
     Cancel,
     ConnectAgent {
         agent_name: Option<String>,
+        join_token: Option<String>,
         management_address: String,
         slots: i32,
     },
@@ -35,6 +37,11 @@ impl JoinBalancerFormData {
 
                 Action::None
             }
+            Message::SetJoinToken(join_token) => {
+                self.join_token = join_token;
+
+                Action::None
+            }
             Message::SetSlotsCount(slots) => {
                 if slots.is_empty() || slots.chars().all(|character| character.is_ascii_digit()) {
                     self.slots_count = slots;
@@ -101,8 +108,15 @@ impl JoinBalancerFormData {
             Some(self.agent_name.clone())
         };
 
+        let join_token = if self.join_token.is_empty() {
+            None
+        } else {
+            Some(self.join_token.clone())
+        };
+
         Action::ConnectAgent {
             agent_name,
+            join_token,
             management_address: self.balancer_address.clone(),
             slots,
         }
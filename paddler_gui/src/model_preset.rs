@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::fmt;
 
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -55,6 +56,7 @@ impl ModelPreset {
             chat_template_override: None,
             inference_parameters: self.inference_parameters.clone(),
             model: AgentDesiredModel::HuggingFace(self.model.clone()),
+            model_pools: BTreeMap::new(),
             multimodal_projection,
             use_chat_template_override: false,
         }
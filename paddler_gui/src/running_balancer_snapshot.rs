@@ -45,6 +45,8 @@ impl RunningBalancerSnapshot {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use parking_lot::RwLock;
     use std::collections::BTreeSet;
     use std::sync::Arc;
@@ -55,6 +57,7 @@ mod tests {
     use anyhow::Result;
     use paddler_balancer::agent_controller::AgentController;
     use paddler_balancer::agent_controller_pool::AgentControllerPool;
+    use paddler_balancer::agent_throughput::AgentThroughput;
     use paddler_balancer::balancer_applicable_state::BalancerApplicableState;
     use paddler_balancer::balancer_applicable_state_holder::BalancerApplicableStateHolder;
     use paddler_balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
@@ -80,7 +83,10 @@ mod tests {
                 ChatTemplateOverrideSenderCollection::default(),
             ),
             connection_close: CancellationToken::new(),
+            cordoned: AtomicValue::<AtomicBool>::new(false),
             desired_slots_total: AtomicValue::<AtomicI32>::new(0),
+            desired_state: RwLock::new(None),
+            desired_state_version: AtomicValue::<AtomicU64>::new(0),
             download_current: AtomicValue::<AtomicU64>::new(0),
             download_filename: RwLock::new(None),
             download_indeterminate: AtomicValue::<AtomicBool>::new(true),
@@ -91,13 +97,17 @@ mod tests {
             issues: RwLock::new(BTreeSet::new()),
             model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
             model_path: RwLock::new(None),
+            model_pool: None,
             name: name.map(str::to_owned),
             newest_update_version: AtomicValue::<AtomicI32>::new(0),
+            reported_desired_state_version: AtomicValue::<AtomicU64>::new(0),
             slots_processing: AtomicValue::<AtomicI32>::new(0),
             slots_total: AtomicValue::<AtomicI32>::new(0),
             state_application_status_code: AtomicValue::<AtomicI32>::new(
                 AgentStateApplicationStatus::Fresh as i32,
             ),
+            state_application_version: AtomicValue::<AtomicU64>::new(0),
+            throughput: RwLock::new(AgentThroughput::default()),
             uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
         })
     }
@@ -110,6 +120,7 @@ mod tests {
                 model: AgentDesiredModel::LocalToAgent("configured_model".to_owned()),
                 multimodal_projection: AgentDesiredModel::None,
             },
+            model_pools: BTreeMap::new(),
         }
     }
 
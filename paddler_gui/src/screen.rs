@@ -1,6 +1,7 @@
 use std::collections::BTreeSet;
 
 use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+use paddler_messaging::agent_cordon_status::AgentCordonStatus;
 use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
 use statum::machine;
 use statum::state;
@@ -72,6 +73,7 @@ impl Screen<JoinBalancerForm> {
                 balancer_address: form_data.balancer_address,
                 connected: false,
                 snapshot: AgentControllerSnapshot {
+                    cordon_status: AgentCordonStatus::Uncordoned,
                     desired_slots_total: 0,
                     download_current: 0,
                     download_filename: None,
@@ -80,10 +82,13 @@ impl Screen<JoinBalancerForm> {
                     id: String::new(),
                     issues: BTreeSet::new(),
                     model_path: None,
+                    model_pool: None,
                     name,
                     slots_processing: 0,
                     slots_total: 0,
                     state_application_status: AgentStateApplicationStatus::Fresh,
+                    time_to_first_token_ms: None,
+                    tokens_per_second: None,
                     uses_chat_template_override: false,
                 },
             }
@@ -42,6 +42,13 @@ pub fn view_join_balancer_form(data: &JoinBalancerFormData) -> Element<'_, Messa
         .style(style_field_text_input)
         .into();
 
+    let join_token_input = text_input("Provided by the cluster operator", &data.join_token)
+        .on_input(Message::SetJoinToken)
+        .secure(true)
+        .padding(SPACING_BASE)
+        .style(style_field_text_input)
+        .into();
+
     let slots_input = text_input("e.g. 1", &data.slots_count)
         .on_input(Message::SetSlotsCount)
         .padding(SPACING_BASE)
@@ -60,6 +67,7 @@ pub fn view_join_balancer_form(data: &JoinBalancerFormData) -> Element<'_, Messa
                 ),
                 view_form_field("Agent name (optional)", agent_name_input, None),
                 view_form_field("Slots", slots_input, data.slots_error.as_ref()),
+                view_form_field("Join token (optional)", join_token_input, None),
                 container(
                     row![cancel_button, confirm_button]
                         .align_y(Center)
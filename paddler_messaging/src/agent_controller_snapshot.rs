@@ -3,12 +3,15 @@ use std::collections::BTreeSet;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::agent_cordon_status::AgentCordonStatus;
 use crate::agent_issue::AgentIssue;
 use crate::agent_state_application_status::AgentStateApplicationStatus;
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct AgentControllerSnapshot {
+    #[serde(default)]
+    pub cordon_status: AgentCordonStatus,
     pub desired_slots_total: i32,
     pub download_current: u64,
     pub download_filename: Option<String>,
@@ -17,9 +20,16 @@ pub struct AgentControllerSnapshot {
     pub id: String,
     pub issues: BTreeSet<AgentIssue>,
     pub model_path: Option<String>,
+    #[serde(default)]
+    pub model_pool: Option<String>,
     pub name: Option<String>,
     pub slots_processing: i32,
     pub slots_total: i32,
     pub state_application_status: AgentStateApplicationStatus,
+    /// Moving averages measured by the balancer, absent until the agent completes a request.
+    #[serde(default)]
+    pub time_to_first_token_ms: Option<u64>,
+    #[serde(default)]
+    pub tokens_per_second: Option<f64>,
     pub uses_chat_template_override: bool,
 }
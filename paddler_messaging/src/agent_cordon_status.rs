@@ -0,0 +1,24 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Whether an operator cordoned the agent, and if so, whether its in-flight requests finished.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum AgentCordonStatus {
+    /// Cordoned and no longer processing any request, so it can be stopped safely.
+    Drained,
+    /// Cordoned: no new requests are dispatched to the agent while its in-flight requests finish.
+    Draining,
+    #[default]
+    Uncordoned,
+}
+
+impl AgentCordonStatus {
+    #[must_use]
+    pub const fn new(cordoned: bool, slots_processing: i32) -> Self {
+        match (cordoned, slots_processing) {
+            (false, _) => Self::Uncordoned,
+            (true, 0) => Self::Drained,
+            (true, _) => Self::Draining,
+        }
+    }
+}
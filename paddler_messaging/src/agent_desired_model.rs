@@ -1,3 +1,5 @@
+use std::path::Path;
+
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -13,3 +15,60 @@ pub enum AgentDesiredModel {
     #[default]
     None,
 }
+
+impl AgentDesiredModel {
+    /// Identifier of the model that stays the same for as long as the model reference does:
+    /// `repo_id/filename` for Hugging Face models and the file name otherwise.
+    #[must_use]
+    pub fn model_id(&self) -> Option<String> {
+        match self {
+            Self::HuggingFace(HuggingFaceModelReference {
+                filename, repo_id, ..
+            }) => Some(format!("{repo_id}/{filename}")),
+            Self::LocalToAgent(path) => Path::new(path)
+                .file_name()
+                .map(|file_name| file_name.to_string_lossy().into_owned()),
+            Self::Url(UrlModelReference { url }) => url
+                .split(['?', '#'])
+                .next()
+                .and_then(|url| url.trim_end_matches('/').rsplit('/').next())
+                .filter(|file_name| !file_name.is_empty())
+                .map(ToOwned::to_owned),
+            Self::None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn model_id_is_derived_from_the_model_reference() {
+        assert_eq!(
+            AgentDesiredModel::HuggingFace(HuggingFaceModelReference {
+                filename: "Qwen3-0.6B-Q8_0.gguf".to_owned(),
+                repo_id: "Qwen/Qwen3-0.6B-GGUF".to_owned(),
+                revision: "main".to_owned(),
+            })
+            .model_id()
+            .as_deref(),
+            Some("Qwen/Qwen3-0.6B-GGUF/Qwen3-0.6B-Q8_0.gguf")
+        );
+        assert_eq!(
+            AgentDesiredModel::LocalToAgent("/models/chat.gguf".to_owned())
+                .model_id()
+                .as_deref(),
+            Some("chat.gguf")
+        );
+        assert_eq!(
+            AgentDesiredModel::Url(UrlModelReference {
+                url: "https://example.com/models/chat.gguf?download=true".to_owned(),
+            })
+            .model_id()
+            .as_deref(),
+            Some("chat.gguf")
+        );
+        assert_eq!(AgentDesiredModel::None.model_id(), None);
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::queue_wait_trend::QueueWaitTrend;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct AutoscalingRecommendation {
+    pub agents: usize,
+    pub buffered_requests: i32,
+    /// Average time requests dispatched during the last sampling window spent buffered,
+    /// or how long the oldest request still buffered has waited, whichever is longer.
+    pub queue_wait_average_ms: Option<u64>,
+    pub queue_wait_trend: QueueWaitTrend,
+    pub recommended_agent_count: usize,
+    pub slot_utilization: f64,
+    pub slots_processing: i32,
+    pub slots_total: i32,
+    pub target_utilization: f64,
+}
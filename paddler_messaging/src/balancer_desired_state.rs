@@ -1,16 +1,62 @@
+use std::collections::BTreeMap;
+
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::agent_desired_model::AgentDesiredModel;
 use crate::chat_template::ChatTemplate;
 use crate::inference_parameters::InferenceParameters;
+use crate::model_pool_desired_state::ModelPoolDesiredState;
 
+/// The top-level model settings describe the default pool, which serves agents
+/// that did not join a named pool and requests that do not name one.
 #[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct BalancerDesiredState {
     pub chat_template_override: Option<ChatTemplate>,
     pub inference_parameters: InferenceParameters,
     pub model: AgentDesiredModel,
+    #[serde(default)]
+    pub model_pools: BTreeMap<String, ModelPoolDesiredState>,
     pub multimodal_projection: AgentDesiredModel,
     pub use_chat_template_override: bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deserializes_without_model_pools() {
+        let mut serialized = serde_json::to_value(BalancerDesiredState::default()).unwrap();
+
+        serialized.as_object_mut().unwrap().remove("model_pools");
+
+        let desired_state: BalancerDesiredState = serde_json::from_value(serialized).unwrap();
+
+        assert!(desired_state.model_pools.is_empty());
+    }
+
+    #[test]
+    fn round_trips_named_model_pools() {
+        let desired_state = BalancerDesiredState {
+            model_pools: BTreeMap::from([(
+                "embeddings".to_owned(),
+                ModelPoolDesiredState {
+                    inference_parameters: InferenceParameters {
+                        enable_embeddings: true,
+                        ..InferenceParameters::default()
+                    },
+                    model: AgentDesiredModel::LocalToAgent("embedding.gguf".to_owned()),
+                    ..ModelPoolDesiredState::default()
+                },
+            )]),
+            ..BalancerDesiredState::default()
+        };
+
+        let serialized = serde_json::to_string(&desired_state).unwrap();
+        let deserialized: BalancerDesiredState = serde_json::from_str(&serialized).unwrap();
+
+        assert_eq!(deserialized, desired_state);
+    }
+}
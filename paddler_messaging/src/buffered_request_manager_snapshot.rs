@@ -3,5 +3,9 @@ use serde::Serialize;
 
 #[derive(Deserialize, Serialize)]
 pub struct BufferedRequestManagerSnapshot {
+    #[serde(default)]
+    pub buffered_requests_batch: i32,
     pub buffered_requests_current: i32,
+    #[serde(default)]
+    pub buffered_requests_interactive: i32,
 }
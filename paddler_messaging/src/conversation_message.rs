@@ -3,7 +3,7 @@ use serde::Serialize;
 
 use crate::conversation_message_content::ConversationMessageContent;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ConversationMessage {
     pub content: ConversationMessageContent,
@@ -3,7 +3,7 @@ use serde::Serialize;
 
 use crate::image_url::ImageUrl;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
 #[serde(deny_unknown_fields, tag = "type")]
 pub enum ConversationMessageContentPart {
     #[serde(rename = "text")]
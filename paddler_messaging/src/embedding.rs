@@ -11,4 +11,6 @@ pub struct Embedding {
     pub normalization_method: EmbeddingNormalizationMethod,
     pub pooling_type: PoolingType,
     pub source_document_id: String,
+    #[serde(default)]
+    pub token_count: usize,
 }
@@ -6,4 +6,7 @@ use serde::Serialize;
 pub struct EmbeddingInputDocument {
     pub content: String,
     pub id: String,
+    /// Already tokenized input; when present the agent embeds these tokens instead of the content.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tokens: Option<Vec<i32>>,
 }
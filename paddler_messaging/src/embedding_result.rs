@@ -1,3 +1,4 @@
+use llama_cpp_bindings_types::TokenUsage;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -14,10 +15,26 @@ pub enum EmbeddingResult {
     EmbeddingsDisabled,
     Error(String),
     EmbeddingRejectedDueToActiveTokenGeneration,
+    /// Pre-tokenized input the model cannot embed, such as an empty token array or a
+    /// token outside of its vocabulary.
+    InvalidTokens(String),
     NoEmbeddingsProduced,
 }
 
 impl StreamableResult for EmbeddingResult {
+    fn error_kind(&self) -> Option<&'static str> {
+        match self {
+            Self::EmbeddingRejectedDueToActiveTokenGeneration => {
+                Some("embedding_rejected_due_to_active_token_generation")
+            }
+            Self::EmbeddingsDisabled => Some("embeddings_disabled"),
+            Self::Error(_) => Some("embedding_error"),
+            Self::InvalidTokens(_) => Some("invalid_tokens"),
+            Self::NoEmbeddingsProduced => Some("no_embeddings_produced"),
+            Self::DocumentExceedsBatchSize(_) | Self::Done | Self::Embedding(_) => None,
+        }
+    }
+
     fn is_done(&self) -> bool {
         matches!(
             self,
@@ -25,9 +42,23 @@ impl StreamableResult for EmbeddingResult {
                 | Self::EmbeddingsDisabled
                 | Self::Error(_)
                 | Self::EmbeddingRejectedDueToActiveTokenGeneration
+                | Self::InvalidTokens(_)
                 | Self::NoEmbeddingsProduced,
         )
     }
+
+    fn token_usage(&self) -> Option<TokenUsage> {
+        match self {
+            Self::Embedding(Embedding { token_count, .. }) => {
+                let mut usage = TokenUsage::new();
+
+                usage.record_prompt_tokens(u64::try_from(*token_count).unwrap_or(u64::MAX));
+
+                Some(usage)
+            }
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -46,6 +77,11 @@ mod tests {
         assert!(EmbeddingResult::Error("fail".to_owned()).is_done());
     }
 
+    #[test]
+    fn invalid_tokens_are_done() {
+        assert!(EmbeddingResult::InvalidTokens("empty".to_owned()).is_done());
+    }
+
     #[test]
     fn embeddings_disabled_is_done() {
         assert!(EmbeddingResult::EmbeddingsDisabled.is_done());
@@ -79,8 +115,26 @@ mod tests {
             normalization_method: EmbeddingNormalizationMethod::None,
             pooling_type: PoolingType::Mean,
             source_document_id: "doc".to_owned(),
+            token_count: 0,
         });
 
         assert!(!result.is_done());
     }
+
+    #[test]
+    fn embedding_reports_its_tokens_as_prompt_tokens() {
+        let token_usage = EmbeddingResult::Embedding(Embedding {
+            embedding: vec![1.0],
+            normalization_method: EmbeddingNormalizationMethod::None,
+            pooling_type: PoolingType::Mean,
+            source_document_id: "doc".to_owned(),
+            token_count: 7,
+        })
+        .token_usage()
+        .unwrap();
+
+        assert_eq!(token_usage.prompt_tokens, 7);
+        assert_eq!(token_usage.completion_tokens(), 0);
+        assert!(EmbeddingResult::Done.token_usage().is_none());
+    }
 }
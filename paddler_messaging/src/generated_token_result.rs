@@ -2,11 +2,14 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use llama_cpp_bindings_types::ParsedToolCall;
+use llama_cpp_bindings_types::TokenUsage;
 
 use crate::generation_summary::GenerationSummary;
 use crate::oversized_image_details::OversizedImageDetails;
 use crate::raw_tool_call_tokens::RawToolCallTokens;
+use crate::stop_reason::StopReason;
 use crate::streamable_result::StreamableResult;
+use crate::token_logprob::TokenLogprob;
 
 #[derive(Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
@@ -25,6 +28,8 @@ pub enum GeneratedTokenResult {
     ReasoningToken(String),
     SamplerError(String),
     TokenGenerationDisabled(String),
+    TokenLogprob(TokenLogprob),
+    ToolChoiceNotSupported(String),
     ToolCallParseFailed(String),
     ToolCallParsed(Vec<ParsedToolCall>),
     ToolCallToken(String),
@@ -72,6 +77,29 @@ impl GeneratedTokenResult {
 }
 
 impl StreamableResult for GeneratedTokenResult {
+    fn error_kind(&self) -> Option<&'static str> {
+        match self {
+            Self::ChatTemplateError(_) => Some("chat_template_error"),
+            Self::DetokenizationFailed(_) => Some("detokenization_failed"),
+            Self::GrammarIncompatibleWithThinking(_) => Some("grammar_incompatible_with_thinking"),
+            Self::GrammarInitializationFailed(_) => Some("grammar_initialization_failed"),
+            Self::GrammarRejectedModelOutput(_) => Some("grammar_rejected_model_output"),
+            Self::GrammarSyntaxError(_) => Some("grammar_syntax_error"),
+            Self::ImageDecodingFailed(_) => Some("image_decoding_failed"),
+            Self::ImageExceedsBatchSize(_) => Some("image_exceeds_batch_size"),
+            Self::MultimodalNotSupported(_) => Some("multimodal_not_supported"),
+            Self::SamplerError(_) => Some("sampler_error"),
+            Self::TokenGenerationDisabled(_) => Some("token_generation_disabled"),
+            Self::ToolChoiceNotSupported(_) => Some("tool_choice_not_supported"),
+            Self::ToolSchemaInvalid(_) => Some("tool_schema_invalid"),
+            _ => None,
+        }
+    }
+
+    fn generated_text(&self) -> Option<&str> {
+        self.token_text()
+    }
+
     fn is_done(&self) -> bool {
         matches!(
             self,
@@ -87,9 +115,24 @@ impl StreamableResult for GeneratedTokenResult {
                 | Self::MultimodalNotSupported(_)
                 | Self::SamplerError(_)
                 | Self::TokenGenerationDisabled(_)
+                | Self::ToolChoiceNotSupported(_)
                 | Self::ToolSchemaInvalid(_)
         )
     }
+
+    fn token_usage(&self) -> Option<TokenUsage> {
+        match self {
+            Self::Done(GenerationSummary { usage, .. }) => Some(*usage),
+            _ => None,
+        }
+    }
+
+    fn stop_reason(&self) -> Option<StopReason> {
+        match self {
+            Self::Done(GenerationSummary { stop_reason, .. }) => Some(*stop_reason),
+            _ => None,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -101,6 +144,45 @@ mod tests {
         assert!(GeneratedTokenResult::Done(GenerationSummary::default()).is_done());
     }
 
+    #[test]
+    fn done_reports_token_usage() {
+        let mut usage = TokenUsage::new();
+
+        usage.record_content_token();
+
+        let token_usage = GeneratedTokenResult::Done(GenerationSummary {
+            usage,
+            ..GenerationSummary::default()
+        })
+        .token_usage()
+        .unwrap();
+
+        assert_eq!(token_usage.completion_tokens(), 1);
+        assert!(
+            GeneratedTokenResult::ContentToken("hi".to_owned())
+                .token_usage()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn terminal_errors_report_their_kind() {
+        assert_eq!(
+            GeneratedTokenResult::SamplerError("err".to_owned()).error_kind(),
+            Some("sampler_error")
+        );
+        assert!(
+            GeneratedTokenResult::Done(GenerationSummary::default())
+                .error_kind()
+                .is_none()
+        );
+        assert!(
+            GeneratedTokenResult::ToolCallParseFailed("err".to_owned())
+                .error_kind()
+                .is_none()
+        );
+    }
+
     #[test]
     fn chat_template_error_is_done() {
         assert!(GeneratedTokenResult::ChatTemplateError("err".to_owned()).is_done());
@@ -163,6 +245,11 @@ mod tests {
         assert!(GeneratedTokenResult::TokenGenerationDisabled("err".to_owned()).is_done());
     }
 
+    #[test]
+    fn tool_choice_not_supported_is_done() {
+        assert!(GeneratedTokenResult::ToolChoiceNotSupported("err".to_owned()).is_done());
+    }
+
     #[test]
     fn tool_schema_invalid_is_done() {
         assert!(GeneratedTokenResult::ToolSchemaInvalid("invalid schema".to_owned()).is_done());
@@ -3,8 +3,12 @@ use serde::Serialize;
 
 use llama_cpp_bindings_types::TokenUsage;
 
+use crate::stop_reason::StopReason;
+
 #[derive(Clone, Copy, Debug, Default, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct GenerationSummary {
+    #[serde(default)]
+    pub stop_reason: StopReason,
     pub usage: TokenUsage,
 }
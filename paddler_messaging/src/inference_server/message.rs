@@ -8,6 +8,10 @@ use crate::jsonrpc::error_envelope::ErrorEnvelope;
 use crate::jsonrpc::request_envelope::RequestEnvelope;
 use crate::rpc_message::RpcMessage;
 
+#[expect(
+    clippy::large_enum_variant,
+    reason = "decoded from the wire and immediately dispatched"
+)]
 #[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub enum Message<TParametersSchema> {
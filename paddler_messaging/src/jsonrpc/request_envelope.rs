@@ -1,9 +1,36 @@
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::trace_context::TraceContext;
+
 #[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RequestEnvelope<TRequest> {
     pub id: String,
     pub request: TRequest,
+    /// Left out when absent. Agents that predate it reject envelopes carrying it, so agents
+    /// have to be upgraded before the balancer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_context: Option<TraceContext>,
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn absent_trace_context_is_left_out() {
+        let envelope = RequestEnvelope {
+            id: "request".to_owned(),
+            request: "ping".to_owned(),
+            trace_context: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&envelope).unwrap(),
+            json!({"id": "request", "request": "ping"})
+        );
+    }
 }
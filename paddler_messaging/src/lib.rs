@@ -1,11 +1,13 @@
 pub mod agent_controller_pool_snapshot;
 pub mod agent_controller_snapshot;
+pub mod agent_cordon_status;
 pub mod agent_desired_model;
 pub mod agent_desired_state;
 pub mod agent_issue;
 pub mod agent_issue_params;
 pub mod agent_state_application_status;
 pub mod atomic_value;
+pub mod autoscaling_recommendation;
 pub mod balancer_desired_state;
 pub mod buffered_request_manager_snapshot;
 pub mod chat_template;
@@ -31,19 +33,28 @@ pub mod inference_parameters;
 pub mod inference_server;
 pub mod jsonrpc;
 pub mod kv_cache_dtype;
+pub mod logprobs_request;
 pub mod management_socket;
 pub mod media_marker;
 pub mod model_metadata;
+pub mod model_pool_desired_state;
 pub mod oversized_embedding_document_details;
 pub mod oversized_image_details;
 pub mod pooling_type;
 pub mod produces_snapshot;
+pub mod queue_wait_trend;
 pub mod raw_tool_call_tokens;
 pub mod request_params;
 pub mod rpc_message;
+pub mod sampling_overrides;
 pub mod slot_aggregated_status_snapshot;
+pub mod stop_reason;
+pub mod stop_sequences;
 pub mod streamable_result;
 pub mod subscribes_to_updates;
+pub mod token_logprob;
 pub mod tool_call_validation_error;
+pub mod top_logprob;
+pub mod trace_context;
 pub mod url_model_reference;
 pub mod validates;
@@ -0,0 +1,53 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::validates::Validates;
+
+pub const MAX_TOP_LOGPROBS: u32 = 20;
+
+/// Asks the agent to report the log-probability of every generated token.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct LogprobsRequest {
+    /// How many of the most likely alternatives to report alongside each token.
+    #[serde(default)]
+    pub top_logprobs: u32,
+}
+
+impl Validates<Self> for LogprobsRequest {
+    fn validate(self) -> Result<Self> {
+        if self.top_logprobs > MAX_TOP_LOGPROBS {
+            bail!(
+                "top_logprobs must be at most {MAX_TOP_LOGPROBS}, got {}",
+                self.top_logprobs
+            );
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn top_logprobs_within_the_limit_are_valid() {
+        let logprobs_request = LogprobsRequest {
+            top_logprobs: MAX_TOP_LOGPROBS,
+        };
+
+        assert!(logprobs_request.validate().is_ok());
+    }
+
+    #[test]
+    fn top_logprobs_above_the_limit_are_rejected() {
+        let logprobs_request = LogprobsRequest {
+            top_logprobs: MAX_TOP_LOGPROBS + 1,
+        };
+
+        assert!(logprobs_request.validate().is_err());
+    }
+}
@@ -8,6 +8,10 @@ use serde::Serialize;
 use super::notification::Notification;
 use super::request::Request;
 
+#[expect(
+    clippy::large_enum_variant,
+    reason = "decoded from the wire and immediately dispatched"
+)]
 #[derive(Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub enum Message {
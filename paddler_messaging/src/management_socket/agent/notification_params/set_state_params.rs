@@ -6,4 +6,8 @@ use serde::Serialize;
 #[serde(deny_unknown_fields)]
 pub struct SetStateParams {
     pub desired_state: AgentDesiredState,
+    /// Echoed back in status snapshots so the balancer knows which desired state they describe.
+    /// Agents must be upgraded before the balancer, because older agents reject this field.
+    #[serde(default)]
+    pub desired_state_version: u64,
 }
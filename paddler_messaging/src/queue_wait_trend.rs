@@ -0,0 +1,10 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum QueueWaitTrend {
+    Falling,
+    Rising,
+    #[default]
+    Steady,
+}
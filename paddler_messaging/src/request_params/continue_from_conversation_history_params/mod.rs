@@ -1,17 +1,22 @@
 pub mod tool;
+pub mod tool_choice;
 
 use anyhow::Result;
 use serde::Deserialize;
 use serde::Serialize;
 
 use self::tool::Tool;
+use self::tool_choice::ToolChoice;
 use crate::conversation_history::ConversationHistory;
 use crate::grammar_constraint::GrammarConstraint;
+use crate::logprobs_request::LogprobsRequest;
+use crate::sampling_overrides::SamplingOverrides;
+use crate::stop_sequences::validate_stop_sequences;
 use crate::validates::Validates;
 use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
 use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 #[serde(bound(deserialize = "TParametersSchema: serde::Deserialize<'de>"))]
 pub struct ContinueFromConversationHistoryParams<TParametersSchema> {
@@ -20,10 +25,22 @@ pub struct ContinueFromConversationHistoryParams<TParametersSchema> {
     pub enable_thinking: bool,
     #[serde(default)]
     pub grammar: Option<GrammarConstraint>,
+    #[serde(default)]
+    pub logprobs: Option<LogprobsRequest>,
     pub max_tokens: i32,
     #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
     pub parse_tool_calls: bool,
     #[serde(default)]
+    pub sampling: Option<SamplingOverrides>,
+    #[serde(default)]
+    pub session_key: Option<String>,
+    #[serde(default)]
+    pub stop: Vec<String>,
+    #[serde(default)]
+    pub tool_choice: ToolChoice,
+    #[serde(default)]
     pub tools: Vec<Tool<TParametersSchema>>,
 }
 
@@ -31,13 +48,23 @@ impl Validates<ContinueFromConversationHistoryParams<ValidatedParametersSchema>>
     for ContinueFromConversationHistoryParams<RawParametersSchema>
 {
     fn validate(self) -> Result<ContinueFromConversationHistoryParams<ValidatedParametersSchema>> {
+        validate_stop_sequences(&self.stop)?;
+        self.tool_choice
+            .validate_against(&self.tools, self.grammar.as_ref())?;
+
         Ok(ContinueFromConversationHistoryParams {
             add_generation_prompt: self.add_generation_prompt,
             conversation_history: self.conversation_history,
             enable_thinking: self.enable_thinking,
             grammar: self.grammar,
+            logprobs: self.logprobs.map(Validates::validate).transpose()?,
             max_tokens: self.max_tokens,
+            model: self.model,
             parse_tool_calls: self.parse_tool_calls,
+            sampling: self.sampling.map(Validates::validate).transpose()?,
+            session_key: self.session_key,
+            stop: self.stop,
+            tool_choice: self.tool_choice,
             tools: self
                 .tools
                 .into_iter()
@@ -53,6 +80,8 @@ mod tests {
     use serde_json::json;
 
     use super::ContinueFromConversationHistoryParams;
+    use super::tool_choice::ToolChoice;
+    use crate::validates::Validates as _;
     use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
 
     #[test]
@@ -73,4 +102,87 @@ mod tests {
 
         assert_eq!(params.grammar, None);
     }
+
+    #[test]
+    fn a_request_with_sampling_overrides_keeps_them_after_validation() {
+        let request_with_sampling = json!({
+            "add_generation_prompt": true,
+            "conversation_history": [
+                {"content": "Hello", "role": "user"}
+            ],
+            "enable_thinking": false,
+            "max_tokens": 10,
+            "sampling": {"temperature": 0.0, "top_k": 1},
+        });
+
+        let params: ContinueFromConversationHistoryParams<RawParametersSchema> =
+            from_value(request_with_sampling)
+                .expect("a request with sampling overrides must deserialize");
+
+        let validated_params = params
+            .validate()
+            .expect("sampling overrides within bounds must validate");
+
+        assert_eq!(
+            validated_params
+                .sampling
+                .and_then(|sampling| sampling.top_k),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn a_request_with_too_many_stop_sequences_fails_validation() {
+        let request_with_stop = json!({
+            "add_generation_prompt": true,
+            "conversation_history": [
+                {"content": "Hello", "role": "user"}
+            ],
+            "enable_thinking": false,
+            "max_tokens": 10,
+            "stop": vec!["###"; 17],
+        });
+
+        let params: ContinueFromConversationHistoryParams<RawParametersSchema> =
+            from_value(request_with_stop).expect("a request with stop sequences must deserialize");
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn a_request_that_omits_tool_choice_defaults_to_auto() {
+        let request_without_tool_choice = json!({
+            "add_generation_prompt": true,
+            "conversation_history": [
+                {"content": "Hello", "role": "user"}
+            ],
+            "enable_thinking": false,
+            "max_tokens": 10,
+        });
+
+        let params: ContinueFromConversationHistoryParams<RawParametersSchema> =
+            from_value(request_without_tool_choice)
+                .expect("a request that omits tool_choice must deserialize");
+
+        assert_eq!(params.tool_choice, ToolChoice::Auto);
+    }
+
+    #[test]
+    fn a_request_naming_an_unknown_tool_fails_validation() {
+        let request_with_tool_choice = json!({
+            "add_generation_prompt": true,
+            "conversation_history": [
+                {"content": "Hello", "role": "user"}
+            ],
+            "enable_thinking": false,
+            "max_tokens": 10,
+            "tool_choice": {"type": "function", "name": "get_weather"},
+        });
+
+        let params: ContinueFromConversationHistoryParams<RawParametersSchema> =
+            from_value(request_with_tool_choice)
+                .expect("a request with tool_choice must deserialize");
+
+        assert!(params.validate().is_err());
+    }
 }
@@ -0,0 +1,157 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::grammar_constraint::GrammarConstraint;
+use crate::request_params::continue_from_conversation_history_params::tool::Tool;
+
+/// Whether the model may, must, or must not answer with a call to one of the offered tools.
+#[derive(Clone, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields, rename_all = "snake_case", tag = "type")]
+pub enum ToolChoice {
+    #[default]
+    Auto,
+    Function {
+        name: String,
+    },
+    None,
+    Required,
+}
+
+impl ToolChoice {
+    #[must_use]
+    pub const fn forces_tool_call(&self) -> bool {
+        matches!(self, Self::Function { .. } | Self::Required)
+    }
+
+    pub fn validate_against<TParametersSchema>(
+        &self,
+        tools: &[Tool<TParametersSchema>],
+        grammar: Option<&GrammarConstraint>,
+    ) -> Result<()> {
+        if self.forces_tool_call() && grammar.is_some() {
+            bail!("tool_choice cannot force a tool call when a grammar is also requested");
+        }
+
+        match self {
+            Self::Auto | Self::None => Ok(()),
+            Self::Function { name } => {
+                if tools
+                    .iter()
+                    .any(|Tool::Function(function_call)| function_call.function.name == *name)
+                {
+                    Ok(())
+                } else {
+                    bail!("tool_choice names function '{name}', which is not among the tools")
+                }
+            }
+            Self::Required => {
+                if tools.is_empty() {
+                    bail!("tool_choice 'required' needs at least one tool");
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::ToolChoice;
+    use crate::grammar_constraint::GrammarConstraint;
+    use crate::request_params::continue_from_conversation_history_params::tool::Tool;
+    use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::FunctionCall;
+    use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
+    use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
+    use crate::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+
+    fn weather_tool() -> Tool<RawParametersSchema> {
+        Tool::Function(FunctionCall {
+            function: Function {
+                name: "get_weather".to_owned(),
+                description: String::new(),
+                parameters: Parameters::Empty,
+            },
+        })
+    }
+
+    #[test]
+    fn deserializes_a_named_function_choice() {
+        let tool_choice: ToolChoice =
+            serde_json::from_value(json!({"type": "function", "name": "get_weather"})).unwrap();
+
+        assert_eq!(
+            tool_choice,
+            ToolChoice::Function {
+                name: "get_weather".to_owned()
+            }
+        );
+    }
+
+    #[test]
+    fn accepts_a_named_function_that_is_offered() {
+        let tool_choice = ToolChoice::Function {
+            name: "get_weather".to_owned(),
+        };
+
+        assert!(
+            tool_choice
+                .validate_against(&[weather_tool()], None)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn rejects_a_named_function_that_is_not_offered() {
+        let tool_choice = ToolChoice::Function {
+            name: "get_time".to_owned(),
+        };
+
+        assert!(
+            tool_choice
+                .validate_against(&[weather_tool()], None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_required_without_tools() {
+        assert!(
+            ToolChoice::Required
+                .validate_against::<RawParametersSchema>(&[], None)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn rejects_a_forced_call_combined_with_a_grammar() {
+        let grammar = GrammarConstraint::Gbnf {
+            grammar: "root ::= \"yes\"".to_owned(),
+            root: "root".to_owned(),
+        };
+
+        assert!(
+            ToolChoice::Required
+                .validate_against(&[weather_tool()], Some(&grammar))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn none_and_auto_accept_any_tools() {
+        assert!(
+            ToolChoice::None
+                .validate_against(&[weather_tool()], None)
+                .is_ok()
+        );
+        assert!(
+            ToolChoice::Auto
+                .validate_against::<RawParametersSchema>(&[], None)
+                .is_ok()
+        );
+    }
+}
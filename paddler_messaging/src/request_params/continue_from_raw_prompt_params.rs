@@ -1,15 +1,42 @@
+use anyhow::Result;
 use serde::Deserialize;
 use serde::Serialize;
 
 use crate::grammar_constraint::GrammarConstraint;
+use crate::logprobs_request::LogprobsRequest;
+use crate::sampling_overrides::SamplingOverrides;
+use crate::stop_sequences::validate_stop_sequences;
+use crate::validates::Validates;
 
-#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct ContinueFromRawPromptParams {
     #[serde(default)]
     pub grammar: Option<GrammarConstraint>,
+    #[serde(default)]
+    pub logprobs: Option<LogprobsRequest>,
     pub max_tokens: i32,
+    #[serde(default)]
+    pub model: Option<String>,
     pub raw_prompt: String,
+    #[serde(default)]
+    pub sampling: Option<SamplingOverrides>,
+    #[serde(default)]
+    pub session_key: Option<String>,
+    #[serde(default)]
+    pub stop: Vec<String>,
+}
+
+impl Validates<Self> for ContinueFromRawPromptParams {
+    fn validate(self) -> Result<Self> {
+        validate_stop_sequences(&self.stop)?;
+
+        Ok(Self {
+            logprobs: self.logprobs.map(Validates::validate).transpose()?,
+            sampling: self.sampling.map(Validates::validate).transpose()?,
+            ..self
+        })
+    }
 }
 
 #[cfg(test)]
@@ -18,6 +45,7 @@ mod tests {
     use serde_json::json;
 
     use super::ContinueFromRawPromptParams;
+    use crate::validates::Validates as _;
 
     #[test]
     fn a_request_that_omits_the_grammar_field_keeps_working() {
@@ -31,4 +59,64 @@ mod tests {
 
         assert_eq!(params.grammar, None);
     }
+
+    #[test]
+    fn a_request_with_out_of_bounds_sampling_fails_validation() {
+        let request_with_sampling = json!({
+            "max_tokens": 10,
+            "raw_prompt": "Hello",
+            "sampling": {"temperature": 7.0},
+        });
+
+        let params: ContinueFromRawPromptParams = from_value(request_with_sampling)
+            .expect("a request with sampling overrides must deserialize");
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn a_request_with_stop_sequences_keeps_them_after_validation() {
+        let request_with_stop = json!({
+            "max_tokens": 10,
+            "raw_prompt": "Q: 2+2\nA: 4\n###\nQ: 3+3\nA:",
+            "stop": ["\n###"],
+        });
+
+        let params: ContinueFromRawPromptParams =
+            from_value(request_with_stop).expect("a request with stop sequences must deserialize");
+
+        let validated_params = params
+            .validate()
+            .expect("non-empty stop sequences must validate");
+
+        assert_eq!(validated_params.stop, vec!["\n###".to_owned()]);
+    }
+
+    #[test]
+    fn a_request_with_an_empty_stop_sequence_fails_validation() {
+        let request_with_empty_stop = json!({
+            "max_tokens": 10,
+            "raw_prompt": "Hello",
+            "stop": [""],
+        });
+
+        let params: ContinueFromRawPromptParams = from_value(request_with_empty_stop)
+            .expect("a request with stop sequences must deserialize");
+
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn a_request_asking_for_too_many_top_logprobs_fails_validation() {
+        let request_with_logprobs = json!({
+            "logprobs": {"top_logprobs": 50},
+            "max_tokens": 10,
+            "raw_prompt": "Hello",
+        });
+
+        let params: ContinueFromRawPromptParams =
+            from_value(request_with_logprobs).expect("a request with logprobs must deserialize");
+
+        assert!(params.validate().is_err());
+    }
 }
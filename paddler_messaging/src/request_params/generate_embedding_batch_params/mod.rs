@@ -11,6 +11,8 @@ use crate::embedding_normalization_method::EmbeddingNormalizationMethod;
 #[serde(deny_unknown_fields)]
 pub struct GenerateEmbeddingBatchParams {
     pub input_batch: Vec<EmbeddingInputDocument>,
+    #[serde(default)]
+    pub model: Option<String>,
     pub normalization_method: EmbeddingNormalizationMethod,
 }
 
@@ -53,6 +55,7 @@ impl GenerateEmbeddingBatchParams {
 
             sub_batches.push(Self {
                 input_batch: self.input_batch[start_index..end_index].to_vec(),
+                model: self.model.clone(),
                 normalization_method: self.normalization_method.clone(),
             });
 
@@ -71,12 +74,14 @@ mod tests {
         EmbeddingInputDocument {
             content: content.to_owned(),
             id: id.to_owned(),
+            tokens: None,
         }
     }
 
     fn make_params(docs: Vec<EmbeddingInputDocument>) -> GenerateEmbeddingBatchParams {
         GenerateEmbeddingBatchParams {
             input_batch: docs,
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
         }
     }
@@ -333,6 +338,7 @@ mod tests {
     fn chunk_evenly_with_cap_preserves_normalization_method() {
         let params = GenerateEmbeddingBatchParams {
             input_batch: make_docs(8),
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::L2,
         };
 
@@ -0,0 +1,174 @@
+use anyhow::Result;
+use anyhow::bail;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::inference_parameters::InferenceParameters;
+use crate::validates::Validates;
+
+/// Per-request replacements for the sampling settings in [`InferenceParameters`].
+/// Fields left unset keep the cluster-wide value.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SamplingOverrides {
+    #[serde(default)]
+    pub min_p: Option<f32>,
+    #[serde(default)]
+    pub penalty_frequency: Option<f32>,
+    #[serde(default)]
+    pub penalty_last_n: Option<i32>,
+    #[serde(default)]
+    pub penalty_presence: Option<f32>,
+    #[serde(default)]
+    pub penalty_repeat: Option<f32>,
+    /// Seeds the final sampling step so that repeated requests pick the same tokens
+    #[serde(default)]
+    pub seed: Option<u32>,
+    #[serde(default)]
+    pub temperature: Option<f32>,
+    #[serde(default)]
+    pub top_k: Option<i32>,
+    #[serde(default)]
+    pub top_p: Option<f32>,
+}
+
+fn require_within(name: &str, value: Option<f32>, min: f32, max: f32) -> Result<()> {
+    if let Some(value) = value
+        && !(min..=max).contains(&value)
+    {
+        bail!("{name} must be between {min} and {max}, got {value}");
+    }
+
+    Ok(())
+}
+
+impl SamplingOverrides {
+    #[must_use]
+    pub fn apply_to(&self, inference_parameters: &InferenceParameters) -> InferenceParameters {
+        InferenceParameters {
+            min_p: self.min_p.unwrap_or(inference_parameters.min_p),
+            penalty_frequency: self
+                .penalty_frequency
+                .unwrap_or(inference_parameters.penalty_frequency),
+            penalty_last_n: self
+                .penalty_last_n
+                .unwrap_or(inference_parameters.penalty_last_n),
+            penalty_presence: self
+                .penalty_presence
+                .unwrap_or(inference_parameters.penalty_presence),
+            penalty_repeat: self
+                .penalty_repeat
+                .unwrap_or(inference_parameters.penalty_repeat),
+            temperature: self.temperature.unwrap_or(inference_parameters.temperature),
+            top_k: self.top_k.unwrap_or(inference_parameters.top_k),
+            top_p: self.top_p.unwrap_or(inference_parameters.top_p),
+            ..inference_parameters.clone()
+        }
+    }
+}
+
+impl Validates<Self> for SamplingOverrides {
+    fn validate(self) -> Result<Self> {
+        require_within("min_p", self.min_p, 0.0, 1.0)?;
+        require_within("penalty_frequency", self.penalty_frequency, -2.0, 2.0)?;
+        require_within("penalty_presence", self.penalty_presence, -2.0, 2.0)?;
+        require_within("penalty_repeat", self.penalty_repeat, 0.0, 2.0)?;
+        require_within("temperature", self.temperature, 0.0, 2.0)?;
+        require_within("top_p", self.top_p, 0.0, 1.0)?;
+
+        if let Some(penalty_last_n) = self.penalty_last_n
+            && penalty_last_n < -1
+        {
+            bail!("penalty_last_n must be -1 or greater, got {penalty_last_n}");
+        }
+
+        if let Some(top_k) = self.top_k
+            && top_k < 0
+        {
+            bail!("top_k must not be negative, got {top_k}");
+        }
+
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unset_fields_keep_the_cluster_wide_values() {
+        let inference_parameters = InferenceParameters::default();
+
+        assert_eq!(
+            SamplingOverrides::default().apply_to(&inference_parameters),
+            inference_parameters
+        );
+    }
+
+    #[test]
+    fn set_fields_replace_the_cluster_wide_values() {
+        let applied = SamplingOverrides {
+            temperature: Some(0.0),
+            top_k: Some(1),
+            ..SamplingOverrides::default()
+        }
+        .apply_to(&InferenceParameters::default());
+
+        assert_eq!(
+            applied,
+            InferenceParameters {
+                temperature: 0.0,
+                top_k: 1,
+                ..InferenceParameters::default()
+            }
+        );
+    }
+
+    #[test]
+    fn validate_succeeds_with_values_within_bounds() {
+        let overrides = SamplingOverrides {
+            min_p: Some(0.1),
+            penalty_frequency: Some(-1.0),
+            penalty_last_n: Some(-1),
+            penalty_presence: Some(1.5),
+            penalty_repeat: Some(1.0),
+            seed: Some(42),
+            temperature: Some(2.0),
+            top_k: Some(40),
+            top_p: Some(0.95),
+        };
+
+        assert!(overrides.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_fails_when_temperature_is_out_of_bounds() {
+        let overrides = SamplingOverrides {
+            temperature: Some(3.5),
+            ..SamplingOverrides::default()
+        };
+
+        assert!(overrides.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_when_top_p_is_not_a_number() {
+        let overrides = SamplingOverrides {
+            top_p: Some(f32::NAN),
+            ..SamplingOverrides::default()
+        };
+
+        assert!(overrides.validate().is_err());
+    }
+
+    #[test]
+    fn validate_fails_when_top_k_is_negative() {
+        let overrides = SamplingOverrides {
+            top_k: Some(-5),
+            ..SamplingOverrides::default()
+        };
+
+        assert!(overrides.validate().is_err());
+    }
+}
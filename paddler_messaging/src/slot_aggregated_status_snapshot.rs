@@ -10,6 +10,9 @@ use crate::agent_state_application_status::AgentStateApplicationStatus;
 #[serde(deny_unknown_fields)]
 pub struct SlotAggregatedStatusSnapshot {
     pub desired_slots_total: i32,
+    /// The most recently received desired state version; `issues` relate to this version.
+    #[serde(default)]
+    pub desired_state_version: u64,
     pub download_current: u64,
     pub download_filename: Option<String>,
     pub download_indeterminate: bool,
@@ -19,6 +22,9 @@ pub struct SlotAggregatedStatusSnapshot {
     pub slots_processing: i32,
     pub slots_total: i32,
     pub state_application_status: AgentStateApplicationStatus,
+    /// The desired state version that `state_application_status` relates to.
+    #[serde(default)]
+    pub state_application_version: u64,
     pub uses_chat_template_override: bool,
     pub version: i32,
 }
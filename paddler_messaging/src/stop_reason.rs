@@ -0,0 +1,23 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum StopReason {
+    Cancelled,
+    #[default]
+    EndOfGeneration,
+    MaxTokens,
+    StopSequence,
+}
+
+impl StopReason {
+    #[must_use]
+    pub const fn label(self) -> &'static str {
+        match self {
+            Self::Cancelled => "cancelled",
+            Self::EndOfGeneration => "end_of_generation",
+            Self::MaxTokens => "max_tokens",
+            Self::StopSequence => "stop_sequence",
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use anyhow::Result;
+use anyhow::bail;
+
+pub const MAX_STOP_SEQUENCES: usize = 16;
+
+pub fn validate_stop_sequences(stop: &[String]) -> Result<()> {
+    if stop.len() > MAX_STOP_SEQUENCES {
+        bail!(
+            "at most {MAX_STOP_SEQUENCES} stop sequences are allowed, got {}",
+            stop.len()
+        );
+    }
+
+    if stop.iter().any(String::is_empty) {
+        bail!("stop sequences must not be empty");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_stop_sequences_are_valid() {
+        assert!(validate_stop_sequences(&[]).is_ok());
+    }
+
+    #[test]
+    fn an_empty_stop_sequence_is_rejected() {
+        assert!(validate_stop_sequences(&["\n###".to_owned(), String::new()]).is_err());
+    }
+
+    #[test]
+    fn too_many_stop_sequences_are_rejected() {
+        let stop = vec!["###".to_owned(); MAX_STOP_SEQUENCES + 1];
+
+        assert!(validate_stop_sequences(&stop).is_err());
+    }
+}
@@ -1,3 +1,26 @@
+use llama_cpp_bindings_types::TokenUsage;
+
+use crate::stop_reason::StopReason;
+
 pub trait StreamableResult {
+    /// Name of the error the agent finished the stream with, if it did.
+    fn error_kind(&self) -> Option<&'static str> {
+        None
+    }
+
+    /// Text generated by the model that this result carries.
+    fn generated_text(&self) -> Option<&str> {
+        None
+    }
+
     fn is_done(&self) -> bool;
+
+    fn stop_reason(&self) -> Option<StopReason> {
+        None
+    }
+
+    /// Tokens accounted for by the agent, reported once the result is final.
+    fn token_usage(&self) -> Option<TokenUsage> {
+        None
+    }
 }
@@ -0,0 +1,15 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::top_logprob::TopLogprob;
+
+/// Log-probability of a sampled token under the model's distribution, sent
+/// just before the text of that token.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TokenLogprob {
+    pub logprob: f32,
+    pub token: String,
+    /// The most likely tokens at this position, most likely first.
+    pub top_logprobs: Vec<TopLogprob>,
+}
@@ -0,0 +1,100 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+const TRACEPARENT_VERSION: &str = "00";
+
+/// W3C trace context of the span a request was sent from, so the receiver can attach its own
+/// spans to the same trace.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TraceContext {
+    pub span_id: String,
+    pub trace_id: String,
+}
+
+impl TraceContext {
+    /// Parses a `traceparent` header. Malformed headers are ignored, as the W3C specification
+    /// asks receivers to start a new trace in that case.
+    #[must_use]
+    pub fn from_traceparent(traceparent: &str) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let version = parts.next()?;
+        let trace_id = parts.next()?;
+        let span_id = parts.next()?;
+        let flags = parts.next()?;
+
+        if version == "ff"
+            || !is_lowercase_hex(version, 2)
+            || !is_lowercase_hex(trace_id, 32)
+            || !is_lowercase_hex(span_id, 16)
+            || !is_lowercase_hex(flags, 2)
+            || is_all_zeros(trace_id)
+            || is_all_zeros(span_id)
+            || (version == TRACEPARENT_VERSION && parts.next().is_some())
+        {
+            return None;
+        }
+
+        Some(Self {
+            span_id: span_id.to_owned(),
+            trace_id: trace_id.to_owned(),
+        })
+    }
+
+    #[must_use]
+    pub fn to_traceparent(&self) -> String {
+        format!(
+            "{TRACEPARENT_VERSION}-{}-{}-01",
+            self.trace_id, self.span_id
+        )
+    }
+}
+
+fn is_all_zeros(value: &str) -> bool {
+    value.bytes().all(|byte| byte == b'0')
+}
+
+fn is_lowercase_hex(value: &str, length: usize) -> bool {
+    value.len() == length
+        && value
+            .bytes()
+            .all(|byte| byte.is_ascii_digit() || (b'a'..=b'f').contains(&byte))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_valid_traceparent() {
+        let trace_context = TraceContext::from_traceparent(
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+        )
+        .unwrap();
+
+        assert_eq!(trace_context.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(trace_context.span_id, "00f067aa0ba902b7");
+        assert_eq!(
+            trace_context.to_traceparent(),
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"
+        );
+    }
+
+    #[test]
+    fn ignores_malformed_traceparents() {
+        for traceparent in [
+            "",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7",
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-0000000000000000-01",
+            "00-4BF92F3577B34DA6A3CE929D0E0E4736-00f067aa0ba902b7-01",
+            "ff-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01-extra",
+        ] {
+            assert!(
+                TraceContext::from_traceparent(traceparent).is_none(),
+                "{traceparent:?} should be rejected"
+            );
+        }
+    }
+}
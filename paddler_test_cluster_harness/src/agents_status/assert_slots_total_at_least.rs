@@ -20,6 +20,7 @@ mod tests {
 
     use paddler_messaging::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
     use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+    use paddler_messaging::agent_cordon_status::AgentCordonStatus;
     use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
 
     use super::assert_slots_total_at_least;
@@ -27,6 +28,7 @@ mod tests {
     fn snapshot_with(id: &str, slots_total: i32) -> AgentControllerPoolSnapshot {
         AgentControllerPoolSnapshot {
             agents: vec![AgentControllerSnapshot {
+                cordon_status: AgentCordonStatus::Uncordoned,
                 desired_slots_total: slots_total,
                 download_current: 0,
                 download_filename: None,
@@ -35,10 +37,13 @@ mod tests {
                 id: id.to_owned(),
                 issues: BTreeSet::new(),
                 model_path: None,
+                model_pool: None,
                 name: None,
                 slots_processing: 0,
                 slots_total,
                 state_application_status: AgentStateApplicationStatus::Applied,
+                time_to_first_token_ms: None,
+                tokens_per_second: None,
                 uses_chat_template_override: false,
             }],
         }
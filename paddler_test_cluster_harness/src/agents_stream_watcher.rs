@@ -203,6 +203,7 @@ mod tests {
 
     use futures_util::stream;
     use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+    use paddler_messaging::agent_cordon_status::AgentCordonStatus;
     use paddler_messaging::agent_issue::AgentIssue;
     use paddler_messaging::agent_issue_params::model_path::ModelPath;
     use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
@@ -222,6 +223,7 @@ mod tests {
         slots_total: i32,
     ) -> AgentControllerSnapshot {
         AgentControllerSnapshot {
+            cordon_status: AgentCordonStatus::Uncordoned,
             desired_slots_total: 1,
             download_current: 0,
             download_filename: None,
@@ -230,10 +232,13 @@ mod tests {
             id: agent_id.to_owned(),
             issues,
             model_path: None,
+            model_pool: None,
             name: Some(agent_id.to_owned()),
             slots_processing: 0,
             slots_total,
             state_application_status: AgentStateApplicationStatus::Fresh,
+            time_to_first_token_ms: None,
+            tokens_per_second: None,
             uses_chat_template_override: false,
         }
     }
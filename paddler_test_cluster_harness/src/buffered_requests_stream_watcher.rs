@@ -90,7 +90,9 @@ mod tests {
 
     fn snapshot(buffered_requests_current: i32) -> BufferedRequestManagerSnapshot {
         BufferedRequestManagerSnapshot {
+            buffered_requests_batch: 0,
             buffered_requests_current,
+            buffered_requests_interactive: buffered_requests_current,
         }
     }
 
@@ -66,6 +66,7 @@ impl Cluster {
 
         let client_management = ClientManagement::new(management_base_url);
         let client_inference = ClientInference::new(ClientInferenceParams {
+            api_key: None,
             inference_socket_pool_size: INFERENCE_SOCKET_POOL_SIZE,
             url: inference_base_url,
         });
@@ -17,6 +17,7 @@ pub async fn collect_embedding_results(
     let mut embeddings_disabled = false;
     let mut errors: Vec<String> = Vec::new();
     let mut embedding_rejected_due_to_active_token_generation_count: usize = 0;
+    let mut invalid_tokens = Vec::new();
     let mut no_embeddings_produced_count: usize = 0;
     let mut oversized_documents = Vec::new();
     let mut saw_done = false;
@@ -57,6 +58,9 @@ pub async fn collect_embedding_results(
                     ) => {
                         embedding_rejected_due_to_active_token_generation_count += 1;
                     }
+                    InferenceResponse::Embedding(EmbeddingResult::InvalidTokens(message)) => {
+                        invalid_tokens.push(message);
+                    }
                     InferenceResponse::Embedding(EmbeddingResult::NoEmbeddingsProduced) => {
                         no_embeddings_produced_count += 1;
                     }
@@ -91,6 +95,7 @@ pub async fn collect_embedding_results(
         embeddings_disabled,
         errors,
         embedding_rejected_due_to_active_token_generation_count,
+        invalid_tokens,
         no_embeddings_produced_count,
         oversized_documents,
         saw_done,
@@ -143,6 +148,7 @@ mod tests {
             normalization_method: EmbeddingNormalizationMethod::None,
             pooling_type: PoolingType::Last,
             source_document_id: "doc".to_owned(),
+            token_count: 0,
         }
     }
 
@@ -202,12 +208,16 @@ mod tests {
             Ok(embedding_message(
                 EmbeddingResult::EmbeddingRejectedDueToActiveTokenGeneration,
             )),
+            Ok(embedding_message(EmbeddingResult::InvalidTokens(
+                "empty".to_owned(),
+            ))),
             Ok(embedding_message(EmbeddingResult::NoEmbeddingsProduced)),
         ]))
         .await
         .unwrap();
 
         assert_eq!(collected.errors, vec!["boom".to_owned()]);
+        assert_eq!(collected.invalid_tokens, vec!["empty".to_owned()]);
         assert_eq!(
             collected.embedding_rejected_due_to_active_token_generation_count,
             1
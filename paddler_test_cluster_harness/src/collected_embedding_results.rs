@@ -8,6 +8,7 @@ pub struct CollectedEmbeddingResults {
     pub embeddings_disabled: bool,
     pub errors: Vec<String>,
     pub embedding_rejected_due_to_active_token_generation_count: usize,
+    pub invalid_tokens: Vec<String>,
     pub no_embeddings_produced_count: usize,
     pub oversized_documents: Vec<OversizedEmbeddingDocumentDetails>,
     pub saw_done: bool,
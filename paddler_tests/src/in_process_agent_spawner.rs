@@ -24,8 +24,12 @@ impl AgentSpawner for InProcessAgentSpawner {
         let runner = AgentRunner::start(AgentRunnerParams {
             agent_name: Some(config.name.clone()),
             cancellation_token: CancellationToken::new(),
+            join_token: None,
             management_address: self.management_address.clone(),
+            management_tls_configuration: None,
+            model_pool: None,
             slots: config.slot_count,
+            trace_exporter_configuration: None,
         });
 
         Ok(Box::new(InProcessAgent::new(runner)))
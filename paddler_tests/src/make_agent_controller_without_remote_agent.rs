@@ -6,6 +6,7 @@ use std::sync::atomic::AtomicI32;
 use std::sync::atomic::AtomicU64;
 
 use paddler_balancer::agent_controller::AgentController;
+use paddler_balancer::agent_throughput::AgentThroughput;
 use paddler_balancer::chat_template_override_sender_collection::ChatTemplateOverrideSenderCollection;
 use paddler_balancer::embedding_sender_collection::EmbeddingSenderCollection;
 use paddler_balancer::generate_tokens_sender_collection::GenerateTokensSenderCollection;
@@ -25,7 +26,10 @@ pub fn make_agent_controller_without_remote_agent(id: &str) -> AgentController {
             ChatTemplateOverrideSenderCollection::default(),
         ),
         connection_close: CancellationToken::new(),
+        cordoned: AtomicValue::<AtomicBool>::new(false),
         desired_slots_total: AtomicValue::<AtomicI32>::new(0),
+        desired_state: RwLock::new(None),
+        desired_state_version: AtomicValue::<AtomicU64>::new(0),
         download_current: AtomicValue::<AtomicU64>::new(0),
         download_filename: RwLock::new(None),
         download_indeterminate: AtomicValue::<AtomicBool>::new(true),
@@ -36,13 +40,17 @@ pub fn make_agent_controller_without_remote_agent(id: &str) -> AgentController {
         issues: RwLock::new(BTreeSet::new()),
         model_metadata_sender_collection: Arc::new(ModelMetadataSenderCollection::default()),
         model_path: RwLock::new(None),
+        model_pool: None,
         name: None,
         newest_update_version: AtomicValue::<AtomicI32>::new(0),
+        reported_desired_state_version: AtomicValue::<AtomicU64>::new(0),
         slots_processing: AtomicValue::<AtomicI32>::new(0),
         slots_total: AtomicValue::<AtomicI32>::new(0),
         state_application_status_code: AtomicValue::<AtomicI32>::new(
             AgentStateApplicationStatus::Fresh as i32,
         ),
+        state_application_version: AtomicValue::<AtomicU64>::new(0),
+        throughput: RwLock::new(AgentThroughput::default()),
         uses_chat_template_override: AtomicValue::<AtomicBool>::new(false),
     }
 }
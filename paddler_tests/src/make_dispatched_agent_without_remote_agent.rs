@@ -14,6 +14,6 @@ pub fn make_dispatched_agent_without_remote_agent(
     agent_controller.slots_total.set(1);
 
     pool.register_agent_controller(agent_controller.id.clone(), agent_controller)?;
-    pool.take_least_busy_agent_controller()
+    pool.take_least_busy_agent_controller(None)
         .context("a freshly registered agent controller must have a free slot")
 }
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
 use paddler_messaging::inference_parameters::InferenceParameters;
@@ -19,6 +21,7 @@ pub fn qwen3_desired_state() -> BalancerDesiredState {
             ..InferenceParameters::deterministic()
         },
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: false,
     }
@@ -2,9 +2,14 @@ use std::str::FromStr as _;
 
 use anyhow::Context as _;
 use anyhow::Result;
+use paddler_balancer::agent_failover_policy::AgentFailoverPolicy;
+use paddler_balancer::autoscaling_signal::DEFAULT_TARGET_UTILIZATION;
 use paddler_balancer::compatibility::openai_service::configuration::Configuration as OpenAIServiceConfiguration;
+use paddler_balancer::dispatch_strategy::DispatchStrategy;
 use paddler_balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler_balancer::management_service::configuration::Configuration as ManagementServiceConfiguration;
+use paddler_balancer::rate_limit_configuration::RateLimitConfiguration;
+use paddler_balancer::rollout_strategy::RolloutStrategy;
 use paddler_balancer::state_database_type::StateDatabaseType;
 use paddler_bootstrap::balancer_runner::BalancerRunner;
 use paddler_bootstrap::balancer_runner::BalancerRunnerParams;
@@ -39,25 +44,39 @@ pub async fn start_cluster(
         .context("failed to parse state_database_url")?;
 
     let balancer_runner = BalancerRunner::start(BalancerRunnerParams {
+        api_keys_file: None,
+        audit_log_service_configuration: None,
+        autoscaling_hook_service_configuration: None,
+        autoscaling_target_utilization: DEFAULT_TARGET_UTILIZATION,
+        batch_buffered_request_limits: None,
         buffered_request_timeout,
         inference_service_configuration: InferenceServiceConfiguration {
             addr: addresses.inference,
+            agent_failover_policy: AgentFailoverPolicy::Disabled,
             cors_allowed_hosts: inference_cors_allowed_hosts,
+            dispatch_strategy: DispatchStrategy::LeastBusy,
             inference_item_timeout,
+            tls_configuration: None,
         },
         management_service_configuration: ManagementServiceConfiguration {
             addr: addresses.management,
+            agent_join_token: None,
             cors_allowed_hosts: management_cors_allowed_hosts,
+            tls_configuration: None,
         },
         max_buffered_requests,
         openai_service_configuration: Some(OpenAIServiceConfiguration {
             addr: addresses.compat_openai,
+            tls_configuration: None,
         }),
         cancellation_token: CancellationToken::new(),
+        rate_limit_configuration: RateLimitConfiguration::default(),
+        rollout_strategy: RolloutStrategy::AllAtOnce,
         shutdown_options: ServiceShutdownOptions::default(),
         state_database_type,
         statsd_prefix: "paddler_tests_".to_owned(),
         statsd_service_configuration: None,
+        trace_exporter_configuration: None,
         #[cfg(feature = "web_admin_panel")]
         web_admin_panel_service_configuration: None,
     })
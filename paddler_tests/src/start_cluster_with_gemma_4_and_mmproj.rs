@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -30,6 +32,7 @@ pub async fn start_cluster_with_gemma_4_and_mmproj(agents: Vec<AgentConfig>) ->
                 ..InferenceParameters::deterministic()
             },
             model: AgentDesiredModel::HuggingFace(primary_reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::HuggingFace(mmproj_reference),
             use_chat_template_override: false,
         }),
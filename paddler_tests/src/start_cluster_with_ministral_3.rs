@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -27,6 +29,7 @@ pub async fn start_cluster_with_ministral_3(
                 ..InferenceParameters::deterministic()
             },
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -40,6 +42,7 @@ pub async fn start_cluster_with_qwen3_5(
                 ..InferenceParameters::deterministic()
             },
             model: AgentDesiredModel::HuggingFace(primary_reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection,
             use_chat_template_override: false,
         }),
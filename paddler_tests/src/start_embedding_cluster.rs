@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -35,6 +37,7 @@ pub async fn start_embedding_cluster(
             chat_template_override: None,
             inference_parameters: inference_parameters_with_offload,
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -28,6 +28,7 @@ async fn agent_chunks_embedding_batch_larger_than_slot_count() -> Result<()> {
         .map(|index| EmbeddingInputDocument {
             content: format!("Document number {index}."),
             id: format!("doc-{index}"),
+            tokens: None,
         })
         .collect();
 
@@ -36,6 +37,7 @@ async fn agent_chunks_embedding_batch_larger_than_slot_count() -> Result<()> {
             CancellationToken::new(),
             &GenerateEmbeddingBatchParams {
                 input_batch,
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
@@ -13,6 +13,7 @@ use paddler_messaging::conversation_message_content::ConversationMessageContent;
 use paddler_messaging::conversation_message_content_part::ConversationMessageContentPart;
 use paddler_messaging::image_url::ImageUrl;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_test_cluster_harness::cluster::Cluster;
 use paddler_tests::start_cluster_with_smolvlm2::start_cluster_with_smolvlm2;
@@ -54,8 +55,14 @@ fn drive_normal_image_fixture(
             }]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
             parse_tool_calls: false,
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
+            tool_choice: ToolChoice::Auto,
             tools: vec![],
         },
     );
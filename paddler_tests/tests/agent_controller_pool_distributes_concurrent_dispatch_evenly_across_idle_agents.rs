@@ -34,7 +34,7 @@ async fn agent_controller_pool_distributes_concurrent_dispatch_evenly_across_idl
         handles.push(tokio::spawn(async move {
             barrier_for_task.wait().await;
 
-            pool_for_task.take_least_busy_agent_controller()
+            pool_for_task.take_least_busy_agent_controller(None)
         }));
     }
 
@@ -24,7 +24,7 @@ async fn agent_controller_pool_does_not_oversubscribe_under_concurrent_dispatch(
         handles.push(tokio::spawn(async move {
             barrier_for_task.wait().await;
 
-            pool_for_task.take_least_busy_agent_controller()
+            pool_for_task.take_least_busy_agent_controller(None)
         }));
     }
 
@@ -22,7 +22,7 @@ fn agent_controller_pool_notifies_subscribers_when_slot_guard_drops() -> Result<
     update_rx.borrow_and_update();
 
     let dispatched = pool
-        .take_least_busy_agent_controller()
+        .take_least_busy_agent_controller(None)
         .ok_or_else(|| anyhow!("a free slot must be available"))?;
 
     update_rx.borrow_and_update();
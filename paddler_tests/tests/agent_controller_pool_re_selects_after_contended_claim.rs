@@ -18,7 +18,7 @@ async fn agent_controller_pool_re_selects_after_contended_claim() -> Result<()>
     pool.register_agent_controller("agent-b".to_owned(), controller_b)?;
 
     let candidate_first = pool
-        .select_least_busy_with_capacity()
+        .select_least_busy_with_capacity(None)
         .ok_or_else(|| anyhow!("expected a candidate when both agents have free capacity"))?;
     let first_pick_id = candidate_first.agent_controller.id.clone();
 
@@ -43,7 +43,7 @@ async fn agent_controller_pool_re_selects_after_contended_claim() -> Result<()>
     );
 
     let candidate_second = pool
-        .select_least_busy_with_capacity()
+        .select_least_busy_with_capacity(None)
         .ok_or_else(|| anyhow!("expected a candidate after re-selection"))?;
 
     assert_ne!(
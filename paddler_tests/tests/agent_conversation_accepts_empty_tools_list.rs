@@ -5,6 +5,7 @@ use paddler_messaging::conversation_history::ConversationHistory;
 use paddler_messaging::conversation_message::ConversationMessage;
 use paddler_messaging::conversation_message_content::ConversationMessageContent;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_tests::start_cluster_with_qwen3::start_cluster_with_qwen3;
 use tokio_util::sync::CancellationToken;
@@ -24,8 +25,14 @@ async fn agent_conversation_accepts_empty_tools_list() -> Result<()> {
                 }]),
                 enable_thinking: true,
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
                 parse_tool_calls: false,
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
+                tool_choice: ToolChoice::Auto,
                 tools: vec![],
             },
         )
@@ -6,6 +6,7 @@ use paddler_messaging::conversation_message::ConversationMessage;
 use paddler_messaging::conversation_message_content::ConversationMessageContent;
 use paddler_messaging::grammar_constraint::GrammarConstraint;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_tests::start_cluster_with_qwen3::start_cluster_with_qwen3;
 use tokio_util::sync::CancellationToken;
@@ -25,8 +26,14 @@ async fn agent_conversation_with_json_schema_grammar_returns_valid_json() -> Res
             grammar: Some(GrammarConstraint::JsonSchema {
                 schema: r#"{"type": "object", "properties": {"answer": {"type": "string"}}, "required": ["answer"]}"#.to_owned(),
             }),
+            logprobs: None,
             max_tokens: 50,
+            model: None,
             parse_tool_calls: false,
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
+            tool_choice: ToolChoice::Auto,
             tools: vec![],
         })
         .await?;
@@ -14,6 +14,7 @@ use paddler_messaging::conversation_message_content_part::ConversationMessageCon
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::image_url::ImageUrl;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_test_cluster_harness::cluster::Cluster;
 use paddler_tests::start_cluster_with_smolvlm2_and_n_batch::start_cluster_with_smolvlm2_and_n_batch;
@@ -55,8 +56,14 @@ fn drive_oversized_image_fixture(
             }]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
             parse_tool_calls: false,
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
+            tool_choice: ToolChoice::Auto,
             tools: vec![],
         },
     );
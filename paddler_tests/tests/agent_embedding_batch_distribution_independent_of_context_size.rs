@@ -32,20 +32,25 @@ async fn agent_embedding_batch_distribution_independent_of_context_size() -> Res
                 EmbeddingInputDocument {
                     content: "This is the first document with enough content to contribute meaningfully to the batch size calculation".to_owned(),
                     id: "doc-chunk-1".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "This is the second document that should be processed in a potentially different batch from the first".to_owned(),
                     id: "doc-chunk-2".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "This is the third document adding more content to ensure the total exceeds the configured chunk limit".to_owned(),
                     id: "doc-chunk-3".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "This is the fourth document which should demonstrate that batching distributes across agent requests".to_owned(),
                     id: "doc-chunk-4".to_owned(),
+                    tokens: None,
                 },
             ],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
         })
         .await?;
@@ -32,13 +32,16 @@ async fn agent_embedding_batch_returns_one_embedding_per_input_document() -> Res
                     EmbeddingInputDocument {
                         content: "The quick brown fox jumps over the lazy dog".to_owned(),
                         id: "doc-alpha".to_owned(),
+                        tokens: None,
                     },
                     EmbeddingInputDocument {
                         content: "Machine learning is a subset of artificial intelligence"
                             .to_owned(),
                         id: "doc-beta".to_owned(),
+                        tokens: None,
                     },
                 ],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
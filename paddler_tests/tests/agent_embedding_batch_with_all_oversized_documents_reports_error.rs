@@ -36,12 +36,15 @@ async fn agent_embedding_batch_with_all_oversized_documents_reports_error() -> R
                     EmbeddingInputDocument {
                         content: huge_content.clone(),
                         id: "huge-1".to_owned(),
+                        tokens: None,
                     },
                     EmbeddingInputDocument {
                         content: huge_content,
                         id: "huge-2".to_owned(),
+                        tokens: None,
                     },
                 ],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
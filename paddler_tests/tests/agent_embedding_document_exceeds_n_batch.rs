@@ -36,12 +36,15 @@ async fn agent_embedding_document_exceeds_n_batch() -> Result<()> {
                     EmbeddingInputDocument {
                         content: "ok".to_owned(),
                         id: "tiny".to_owned(),
+                        tokens: None,
                     },
                     EmbeddingInputDocument {
                         content: huge_content,
                         id: "huge".to_owned(),
+                        tokens: None,
                     },
                 ],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
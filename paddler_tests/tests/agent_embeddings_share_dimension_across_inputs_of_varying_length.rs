@@ -28,16 +28,20 @@ async fn agent_embeddings_share_dimension_across_inputs_of_varying_length() -> R
                 EmbeddingInputDocument {
                     content: "Hello".to_owned(),
                     id: "doc-short".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "The quick brown fox jumped over the lazy dog.".to_owned(),
                     id: "doc-medium".to_owned(),
+                    tokens: None,
                 },
                 EmbeddingInputDocument {
                     content: "Rust is a systems programming language focused on safety, speed, and concurrency. It achieves memory safety without garbage collection.".to_owned(),
                     id: "doc-long".to_owned(),
+                    tokens: None,
                 },
             ],
+            model: None,
             normalization_method: EmbeddingNormalizationMethod::None,
         })
         .await?;
@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -37,6 +39,7 @@ async fn agent_evicts_largest_sequence_under_kv_cache_pressure() -> Result<()> {
             chat_template_override: None,
             inference_parameters,
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -48,8 +51,13 @@ async fn agent_evicts_largest_sequence_under_kv_cache_pressure() -> Result<()> {
     let collected = cluster
         .continue_from_raw_prompt(CancellationToken::new(), &ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 4096,
+            model: None,
             raw_prompt: "Write an exhaustive, never-ending encyclopedia entry that lists every fact about the natural world in extreme detail:".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         })
         .await?;
 
@@ -32,6 +32,7 @@ async fn agent_isolates_concurrent_embedding_requests_per_client() -> Result<()>
             .map(|document_index| EmbeddingInputDocument {
                 content: format!("Content from client {client_index} document {document_index}."),
                 id: format!("client-{client_index}-doc-{document_index}"),
+                tokens: None,
             })
             .collect();
 
@@ -39,6 +40,7 @@ async fn agent_isolates_concurrent_embedding_requests_per_client() -> Result<()>
             CancellationToken::new(),
             &GenerateEmbeddingBatchParams {
                 input_batch,
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
@@ -29,7 +29,9 @@ async fn agent_l2_normalized_embeddings_have_unit_norm() -> Result<()> {
                 input_batch: vec![EmbeddingInputDocument {
                     content: "Testing L2 normalization on embeddings".to_owned(),
                     id: "doc-l2".to_owned(),
+                    tokens: None,
                 }],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::L2,
             },
         )
@@ -15,8 +15,13 @@ async fn agent_raw_prompt_respects_max_tokens() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 20,
+                model: None,
                 raw_prompt: "The capital of France is".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
@@ -17,10 +17,15 @@ async fn agent_raw_prompt_with_gbnf_grammar_constrains_output() -> Result<()> {
                 grammar: r#"root ::= "yes" | "no""#.to_owned(),
                 root: "root".to_owned(),
             }),
+            logprobs: None,
             max_tokens: 10,
+            model: None,
             raw_prompt:
                 "<|im_start|>user\nIs the sky blue? Answer yes or no.<|im_end|>\n<|im_start|>assistant\n<think>\n\n</think>\n\n"
                     .to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         })
         .await?;
 
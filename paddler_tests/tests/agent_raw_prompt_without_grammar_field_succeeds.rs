@@ -15,8 +15,13 @@ async fn agent_raw_prompt_without_grammar_field_succeeds() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
                 raw_prompt: "Hello".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
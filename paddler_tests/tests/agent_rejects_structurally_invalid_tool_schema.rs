@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use anyhow::anyhow;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -15,6 +17,7 @@ use paddler_messaging::request_params::continue_from_conversation_history_params
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_test_cluster_harness::cluster_params::ClusterParams;
 use paddler_tests::model_card::ModelCard;
@@ -44,6 +47,7 @@ async fn agent_rejects_structurally_invalid_tool_schema() -> Result<()> {
                 ..InferenceParameters::default()
             },
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -73,8 +77,14 @@ async fn agent_rejects_structurally_invalid_tool_schema() -> Result<()> {
                 }]),
                 enable_thinking: false,
                 grammar: None,
+                logprobs: None,
                 max_tokens: 64,
+                model: None,
                 parse_tool_calls: true,
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
+                tool_choice: ToolChoice::Auto,
                 tools: vec![Tool::Function(FunctionCall {
                     function: Function {
                         name: "get_weather".to_owned(),
@@ -12,6 +12,7 @@ use paddler_messaging::request_params::continue_from_conversation_history_params
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use serde_json::Map;
 use serde_json::json;
 use tokio_util::sync::CancellationToken;
@@ -35,8 +36,14 @@ async fn agent_rejects_tool_with_invalid_required_field_in_schema() -> Result<()
                 }]),
                 enable_thinking: true,
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
                 parse_tool_calls: true,
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
+                tool_choice: ToolChoice::Auto,
                 tools: vec![Tool::Function(FunctionCall {
                     function: Function {
                         name: "test_fn".to_owned(),
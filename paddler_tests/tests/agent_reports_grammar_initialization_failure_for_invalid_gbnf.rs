@@ -24,10 +24,15 @@ async fn agent_reports_grammar_initialization_failure_for_invalid_gbnf() -> Resu
                 grammar: r#"root ::= "unterminated"#.to_owned(),
                 root: "root".to_owned(),
             }),
+            logprobs: None,
             max_tokens: 10,
+            model: None,
             raw_prompt:
                 "<|im_start|>user\nSay hi.<|im_end|>\n<|im_start|>assistant\n<think>\n\n</think>\n\n"
                     .to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         })
         .await?;
 
@@ -1,5 +1,6 @@
 #![cfg(all(feature = "tests_that_use_llms", feature = "metal"))]
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use anyhow::Context as _;
@@ -40,6 +41,7 @@ async fn agent_reports_slot_cannot_start_for_metal_quantized_distinct_kv() -> Re
             chat_template_override: None,
             inference_parameters,
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
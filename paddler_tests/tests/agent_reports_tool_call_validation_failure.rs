@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use anyhow::anyhow;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -20,6 +22,7 @@ use paddler_messaging::request_params::continue_from_conversation_history_params
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::function::Function;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters::Parameters;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::validated_parameters_schema::ValidatedParametersSchema;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use serde_json::Map;
 use serde_json::json;
 use serde_json::Value;
@@ -45,6 +48,7 @@ async fn agent_reports_tool_call_validation_failure() -> Result<()> {
                 ..InferenceParameters::default()
             },
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -73,8 +77,14 @@ async fn agent_reports_tool_call_validation_failure() -> Result<()> {
                 }]),
                 enable_thinking: false,
                 grammar: None,
+                logprobs: None,
                 max_tokens: 400,
+                model: None,
                 parse_tool_calls: true,
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
+                tool_choice: ToolChoice::Auto,
                 tools: vec![Tool::Function(FunctionCall {
                     function: Function {
                         name: "get_weather".to_owned(),
@@ -33,12 +33,15 @@ async fn agent_returns_identical_embeddings_for_identical_documents() -> Result<
                     EmbeddingInputDocument {
                         content: repeated_content.to_owned(),
                         id: "doc-first".to_owned(),
+                        tokens: None,
                     },
                     EmbeddingInputDocument {
                         content: repeated_content.to_owned(),
                         id: "doc-second".to_owned(),
+                        tokens: None,
                     },
                 ],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
@@ -29,7 +29,9 @@ async fn agent_returns_rms_normalized_embeddings_when_requested() -> Result<()>
                 input_batch: vec![EmbeddingInputDocument {
                     content: "Testing RMS normalization on embeddings".to_owned(),
                     id: "doc-rms".to_owned(),
+                    tokens: None,
                 }],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::RmsNorm { epsilon: 1e-6 },
             },
         )
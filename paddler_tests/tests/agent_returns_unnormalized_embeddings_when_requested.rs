@@ -29,7 +29,9 @@ async fn agent_returns_unnormalized_embeddings_when_requested() -> Result<()> {
                 input_batch: vec![EmbeddingInputDocument {
                     content: "Testing no normalization on embeddings".to_owned(),
                     id: "doc-none".to_owned(),
+                    tokens: None,
                 }],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
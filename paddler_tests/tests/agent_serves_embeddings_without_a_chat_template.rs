@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -28,6 +30,7 @@ async fn agent_serves_embeddings_without_a_chat_template() -> Result<()> {
                 ..InferenceParameters::default()
             },
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -42,7 +45,9 @@ async fn agent_serves_embeddings_without_a_chat_template() -> Result<()> {
                 input_batch: vec![EmbeddingInputDocument {
                     content: "the quick brown fox jumps over the lazy dog".to_owned(),
                     id: "doc-1".to_owned(),
+                    tokens: None,
                 }],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
@@ -17,8 +17,13 @@ async fn agent_serves_four_concurrent_clients_streaming_tokens() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 8,
+                model: None,
                 raw_prompt: prompt.to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
     });
@@ -15,8 +15,13 @@ async fn agent_streams_tokens_from_raw_prompt() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
                 raw_prompt: "The capital of France is".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
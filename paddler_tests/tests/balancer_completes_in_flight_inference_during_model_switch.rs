@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use anyhow::anyhow;
 use futures_util::StreamExt as _;
@@ -29,9 +31,14 @@ async fn balancer_completes_in_flight_inference_during_model_switch() -> Result<
                     grammar: format!("root ::= \"{expected_output}\""),
                     root: "root".to_owned(),
                 }),
+                logprobs: None,
                 max_tokens: 200,
+                model: None,
                 raw_prompt: "Say the following: the quick brown fox jumps over the lazy dog"
                     .to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
@@ -63,6 +70,7 @@ async fn balancer_completes_in_flight_inference_during_model_switch() -> Result<
         chat_template_override: None,
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::LocalToAgent("/nonexistent/model.gguf".to_owned()),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: false,
     };
@@ -5,9 +5,11 @@ use std::time::Duration;
 use anyhow::Context as _;
 use anyhow::Result;
 use anyhow::anyhow;
+use paddler_balancer::agent_failover_policy::AgentFailoverPolicy;
 use paddler_balancer::chunk_forwarding_session_controller::ChunkForwardingSessionController;
 use paddler_balancer::chunk_forwarding_session_controller::identity_transformer::IdentityTransformer;
 use paddler_balancer::chunk_forwarding_session_controller::transform_result::TransformResult;
+use paddler_balancer::dispatch_strategy::DispatchStrategy;
 use paddler_balancer::embedding_sender_collection::EmbeddingSenderCollection;
 use paddler_balancer::inference_service::configuration::Configuration as InferenceServiceConfiguration;
 use paddler_balancer::manages_senders_controller::ManagesSendersController;
@@ -37,8 +39,11 @@ async fn balancer_forwards_504_timeout_error_when_agent_stops_emitting_chunks()
     let inference_item_timeout = Duration::from_millis(150);
     let configuration = InferenceServiceConfiguration {
         addr: SocketAddr::from(([127, 0, 0, 1], 0)),
+        agent_failover_policy: AgentFailoverPolicy::Disabled,
         cors_allowed_hosts: Vec::new(),
+        dispatch_strategy: DispatchStrategy::LeastBusy,
         inference_item_timeout,
+        tls_configuration: None,
     };
 
     let dispatched_agent = make_dispatched_agent_without_remote_agent(agent_controller.clone())?;
@@ -1,5 +1,6 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use anyhow::Context as _;
@@ -30,8 +31,13 @@ const MODEL_RELOAD_CEILING: Duration = Duration::from_mins(2);
 fn capital_of_france_prompt() -> ContinueFromRawPromptParams {
     ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 16,
+        model: None,
         raw_prompt: "The capital of France is".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     }
 }
 
@@ -50,6 +56,7 @@ async fn balancer_inference_socket_recovers_from_embeddings_mode() -> Result<()>
             ..InferenceParameters::deterministic()
         },
         model: AgentDesiredModel::HuggingFace(reference.clone()),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: false,
     };
@@ -104,6 +111,7 @@ async fn balancer_inference_socket_recovers_from_embeddings_mode() -> Result<()>
             ..InferenceParameters::deterministic()
         },
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: false,
     };
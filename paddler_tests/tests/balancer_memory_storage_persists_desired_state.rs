@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -19,6 +21,7 @@ async fn balancer_memory_storage_persists_desired_state() -> Result<()> {
         chat_template_override: None,
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: false,
     };
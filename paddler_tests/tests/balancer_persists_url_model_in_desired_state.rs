@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -23,6 +25,7 @@ async fn balancer_persists_url_model_in_desired_state() -> Result<()> {
             model: AgentDesiredModel::Url(UrlModelReference {
                 url: configured_url.clone(),
             }),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -1,5 +1,6 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use anyhow::Context as _;
@@ -36,6 +37,7 @@ async fn balancer_reports_chat_template_does_not_compile_recovers_when_template_
             chat_template_override: Some(invalid_template),
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::HuggingFace(reference.clone()),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: true,
         }),
@@ -68,6 +70,7 @@ async fn balancer_reports_chat_template_does_not_compile_recovers_when_template_
         chat_template_override: Some(valid_template),
         inference_parameters: InferenceParameters::default(),
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: true,
     };
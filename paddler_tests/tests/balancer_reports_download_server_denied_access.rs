@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -27,6 +29,7 @@ async fn balancer_reports_download_server_denied_access() -> Result<()> {
             model: AgentDesiredModel::Url(UrlModelReference {
                 url: model_url.clone(),
             }),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -25,6 +27,7 @@ async fn balancer_reports_download_url_is_malformed() -> Result<()> {
             model: AgentDesiredModel::Url(UrlModelReference {
                 url: malformed_url.clone(),
             }),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -25,6 +27,7 @@ async fn balancer_reports_huggingface_model_does_not_exist() -> Result<()> {
                 repo_id: "nonexistent-org/nonexistent-model-gguf".to_owned(),
                 revision: "main".to_owned(),
             }),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -33,8 +33,13 @@ async fn balancer_returns_503_when_request_buffering_disabled() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
                 raw_prompt: "Hello".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
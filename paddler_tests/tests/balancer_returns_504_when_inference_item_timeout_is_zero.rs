@@ -1,5 +1,6 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use anyhow::Context as _;
@@ -35,6 +36,7 @@ async fn balancer_returns_504_when_inference_item_timeout_is_zero() -> Result<()
                 ..InferenceParameters::default()
             },
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -47,8 +49,13 @@ async fn balancer_returns_504_when_inference_item_timeout_is_zero() -> Result<()
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
                 raw_prompt: "Hello".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
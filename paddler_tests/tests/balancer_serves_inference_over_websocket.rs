@@ -19,8 +19,13 @@ async fn balancer_serves_inference_over_websocket() -> Result<()> {
             CancellationToken::new(),
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 16,
+                model: None,
                 raw_prompt: "The capital of France is".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await
@@ -6,6 +6,7 @@ use paddler_messaging::conversation_message::ConversationMessage;
 use paddler_messaging::conversation_message_content::ConversationMessageContent;
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_test_cluster_harness::token_result_with_producer::TokenResultWithProducer;
 use paddler_tests::start_cluster_with_qwen3::start_cluster_with_qwen3;
@@ -27,8 +28,14 @@ async fn continuous_batch_concurrent_conversation_history_requests_complete() ->
         conversation_history: ConversationHistory::new(vec![user_message("What is 2+2?")]),
         enable_thinking: false,
         grammar: None,
+        logprobs: None,
         max_tokens: 20,
+        model: None,
         parse_tool_calls: false,
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
+        tool_choice: ToolChoice::Auto,
         tools: vec![],
     };
     let params_b = ContinueFromConversationHistoryParams {
@@ -36,8 +43,14 @@ async fn continuous_batch_concurrent_conversation_history_requests_complete() ->
         conversation_history: ConversationHistory::new(vec![user_message("Name a color")]),
         enable_thinking: false,
         grammar: None,
+        logprobs: None,
         max_tokens: 20,
+        model: None,
         parse_tool_calls: false,
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
+        tool_choice: ToolChoice::Auto,
         tools: vec![],
     };
     let (results_a, results_b) = tokio::join!(
@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -26,6 +28,7 @@ async fn two_concurrent_prompts_produce_distinct_outputs() -> Result<()> {
             ..InferenceParameters::default()
         },
         model: AgentDesiredModel::HuggingFace(reference),
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: false,
     };
@@ -43,13 +46,23 @@ async fn two_concurrent_prompts_produce_distinct_outputs() -> Result<()> {
 
     let params_a = ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 20,
+        model: None,
         raw_prompt: "Count from one to ten in English: one, two,".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     };
     let params_b = ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 20,
+        model: None,
         raw_prompt: "The capital of France is".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     };
     let (collected_a, collected_b) = tokio::join!(
         cluster.continue_from_raw_prompt(CancellationToken::new(), &params_a),
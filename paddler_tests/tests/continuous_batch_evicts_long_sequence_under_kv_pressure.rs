@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
 use paddler_messaging::balancer_desired_state::BalancerDesiredState;
@@ -39,6 +41,7 @@ async fn continuous_batch_evicts_long_sequence_under_kv_pressure() -> Result<()>
             chat_template_override: None,
             inference_parameters,
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -51,13 +54,23 @@ async fn continuous_batch_evicts_long_sequence_under_kv_pressure() -> Result<()>
 
     let long_params = ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 200,
+        model: None,
         raw_prompt: long_prompt.to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     };
     let short_params = ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 20,
+        model: None,
         raw_prompt: "Hi".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     };
     let (long_collected, short_collected) = tokio::join!(
         cluster.continue_from_raw_prompt(CancellationToken::new(), &long_params),
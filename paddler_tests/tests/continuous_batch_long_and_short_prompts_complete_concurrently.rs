@@ -16,13 +16,23 @@ async fn continuous_batch_long_and_short_prompts_complete_concurrently() -> Resu
 
     let long_params = ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 20,
+        model: None,
         raw_prompt: long_prompt,
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     };
     let short_params = ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 20,
+        model: None,
         raw_prompt: "Hi".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     };
     let (long_collected, short_collected) = tokio::join!(
         cluster.continue_from_raw_prompt(CancellationToken::new(), &long_params),
@@ -8,6 +8,7 @@ use paddler_messaging::conversation_message_content_part::ConversationMessageCon
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::image_url::ImageUrl;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_test_cluster_harness::load_test_image_data_uri::load_test_image_data_uri;
@@ -51,16 +52,27 @@ async fn continuous_batch_plain_and_multimodal_run_concurrently() -> Result<()>
 
     let plain_params = ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 64,
+        model: None,
         raw_prompt: "Write a long poem about the sea.".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     };
     let multimodal_params = ContinueFromConversationHistoryParams {
         add_generation_prompt: true,
         conversation_history: multimodal_conversation,
         enable_thinking: false,
         grammar: None,
+        logprobs: None,
         max_tokens: 32,
+        model: None,
         parse_tool_calls: false,
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
+        tool_choice: ToolChoice::Auto,
         tools: vec![],
     };
     let (plain_collected, multimodal_collected) = tokio::join!(
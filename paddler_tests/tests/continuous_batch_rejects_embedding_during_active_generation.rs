@@ -21,8 +21,13 @@ async fn continuous_batch_rejects_embedding_during_active_generation() -> Result
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 50,
+                model: None,
                 raw_prompt: "Tell me a long story about a cat".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
@@ -39,7 +44,9 @@ async fn continuous_batch_rejects_embedding_during_active_generation() -> Result
                 input_batch: vec![EmbeddingInputDocument {
                     content: "test".to_owned(),
                     id: "doc1".to_owned(),
+                    tokens: None,
                 }],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
@@ -24,8 +24,13 @@ async fn continuous_batch_releases_slot_when_client_disconnects() -> Result<()>
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 500,
+                model: None,
                 raw_prompt: "Write a long story about an explorer".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
@@ -17,8 +17,13 @@ async fn continuous_batch_releases_slots_on_shutdown_with_active_request() -> Re
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 500,
+                model: None,
                 raw_prompt: "Write a long essay".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
@@ -19,8 +19,13 @@ async fn continuous_batch_serves_four_concurrent_requests() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 8,
+                model: None,
                 raw_prompt: prompt.to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
     }))
@@ -24,8 +24,13 @@ async fn continuous_batch_stop_signal_terminates_generation_before_max_tokens()
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 500,
+                model: None,
                 raw_prompt: "Write a very long story about a dragon".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
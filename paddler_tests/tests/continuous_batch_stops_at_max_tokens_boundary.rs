@@ -17,8 +17,13 @@ async fn continuous_batch_stops_at_max_tokens_boundary() -> Result<()> {
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 5,
+                model: None,
                 raw_prompt: "Count from one to one hundred:".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
@@ -19,8 +19,13 @@ async fn continuous_batch_stops_generation_when_stop_sender_dropped() -> Result<
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 500,
+                model: None,
                 raw_prompt: "Write a long essay about photosynthesis".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
@@ -37,8 +42,13 @@ async fn continuous_batch_stops_generation_when_stop_sender_dropped() -> Result<
             CancellationToken::new(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 10,
+                model: None,
                 raw_prompt: "Hello".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await?;
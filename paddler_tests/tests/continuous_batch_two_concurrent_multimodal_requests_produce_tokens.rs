@@ -8,6 +8,7 @@ use paddler_messaging::conversation_message_content_part::ConversationMessageCon
 use paddler_messaging::generated_token_result::GeneratedTokenResult;
 use paddler_messaging::image_url::ImageUrl;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_test_cluster_harness::load_test_image_data_uri::load_test_image_data_uri;
 use paddler_test_cluster_harness::token_result_with_producer::TokenResultWithProducer;
@@ -55,8 +56,14 @@ async fn continuous_batch_two_concurrent_multimodal_requests_produce_tokens() ->
         conversation_history: build_multimodal_conversation(&image_data_uri),
         enable_thinking: false,
         grammar: None,
+        logprobs: None,
         max_tokens: 32,
+        model: None,
         parse_tool_calls: false,
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
+        tool_choice: ToolChoice::Auto,
         tools: vec![],
     };
     let params_b = ContinueFromConversationHistoryParams {
@@ -64,8 +71,14 @@ async fn continuous_batch_two_concurrent_multimodal_requests_produce_tokens() ->
         conversation_history: build_multimodal_conversation(&image_data_uri),
         enable_thinking: false,
         grammar: None,
+        logprobs: None,
         max_tokens: 32,
+        model: None,
         parse_tool_calls: false,
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
+        tool_choice: ToolChoice::Auto,
         tools: vec![],
     };
     let (collected_a, collected_b) = tokio::join!(
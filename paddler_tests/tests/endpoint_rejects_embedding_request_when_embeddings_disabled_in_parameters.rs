@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_client::error::Error as ClientError;
@@ -31,6 +33,7 @@ async fn endpoint_rejects_embedding_request_when_embeddings_disabled_in_paramete
             chat_template_override: None,
             inference_parameters: InferenceParameters::default(),
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -47,7 +50,9 @@ async fn endpoint_rejects_embedding_request_when_embeddings_disabled_in_paramete
                 input_batch: vec![EmbeddingInputDocument {
                     content: "Hello world".to_owned(),
                     id: "doc-1".to_owned(),
+                    tokens: None,
                 }],
+                model: None,
                 normalization_method: EmbeddingNormalizationMethod::None,
             },
         )
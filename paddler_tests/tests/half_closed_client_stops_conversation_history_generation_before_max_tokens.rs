@@ -1,5 +1,7 @@
 #![cfg(feature = "tests_that_use_llms")]
 
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_messaging::agent_desired_model::AgentDesiredModel;
@@ -10,6 +12,7 @@ use paddler_messaging::conversation_message_content::ConversationMessageContent;
 use paddler_messaging::inference_parameters::InferenceParameters;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::agent_config::AgentConfig;
 use paddler_test_cluster_harness::observation_window::ObservationWindow;
 use paddler_test_cluster_harness::cluster_params::ClusterParams;
@@ -38,6 +41,7 @@ async fn half_closed_client_stops_conversation_history_generation_before_max_tok
                 ..InferenceParameters::deterministic()
             },
             model: AgentDesiredModel::HuggingFace(reference),
+            model_pools: BTreeMap::new(),
             multimodal_projection: AgentDesiredModel::None,
             use_chat_template_override: false,
         }),
@@ -62,8 +66,14 @@ async fn half_closed_client_stops_conversation_history_generation_before_max_tok
             }]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: MAX_TOKENS_TOO_MANY_TO_FINISH_INSIDE_THE_OBSERVATION_WINDOW,
+            model: None,
             parse_tool_calls: false,
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
+            tool_choice: ToolChoice::Auto,
             tools: Vec::new(),
         };
 
@@ -5,6 +5,7 @@ use paddler_messaging::conversation_message::ConversationMessage;
 use paddler_messaging::conversation_message_content::ConversationMessageContent;
 use paddler_messaging::request_params::continue_from_conversation_history_params::ContinueFromConversationHistoryParams;
 use paddler_messaging::request_params::continue_from_conversation_history_params::tool::tool_params::function_call::parameters_schema::raw_parameters_schema::RawParametersSchema;
+use paddler_messaging::request_params::continue_from_conversation_history_params::tool_choice::ToolChoice;
 use paddler_test_cluster_harness::cluster_params::ClusterParams;
 use paddler_test_cluster_harness::observation_window::ObservationWindow;
 use paddler_test_cluster_harness::half_closed_client::HalfClosedClient;
@@ -29,8 +30,14 @@ async fn half_closed_http_client_releases_the_buffered_request() -> Result<()> {
             }]),
             enable_thinking: false,
             grammar: None,
+            logprobs: None,
             max_tokens: 2048,
+            model: None,
             parse_tool_calls: false,
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
+            tool_choice: ToolChoice::Auto,
             tools: Vec::new(),
         };
     let mut client = HalfClosedClient::post_json_then_half_close(
@@ -5,6 +5,7 @@ use anyhow::anyhow;
 use futures_util::stream;
 use paddler_messaging::agent_controller_pool_snapshot::AgentControllerPoolSnapshot;
 use paddler_messaging::agent_controller_snapshot::AgentControllerSnapshot;
+use paddler_messaging::agent_cordon_status::AgentCordonStatus;
 use paddler_messaging::agent_issue::AgentIssue;
 use paddler_messaging::agent_issue_params::model_path::ModelPath;
 use paddler_messaging::agent_state_application_status::AgentStateApplicationStatus;
@@ -14,6 +15,7 @@ use paddler_test_cluster_harness::observation_window::ObservationWindow;
 fn make_snapshot(agent_id: &str, slots_total: i32) -> AgentControllerPoolSnapshot {
     AgentControllerPoolSnapshot {
         agents: vec![AgentControllerSnapshot {
+            cordon_status: AgentCordonStatus::Uncordoned,
             desired_slots_total: slots_total,
             download_current: 0,
             download_filename: None,
@@ -22,10 +24,13 @@ fn make_snapshot(agent_id: &str, slots_total: i32) -> AgentControllerPoolSnapsho
             id: agent_id.to_owned(),
             issues: BTreeSet::new(),
             model_path: None,
+            model_pool: None,
             name: None,
             slots_processing: 0,
             slots_total,
             state_application_status: AgentStateApplicationStatus::Applied,
+            time_to_first_token_ms: None,
+            tokens_per_second: None,
             uses_chat_template_override: false,
         }],
     }
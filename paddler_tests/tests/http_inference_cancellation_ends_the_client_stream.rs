@@ -23,8 +23,13 @@ async fn http_inference_cancellation_ends_the_client_stream() -> Result<()> {
             cancellation_token.clone(),
             &ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 16,
+                model: None,
                 raw_prompt: "The capital of France is".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await
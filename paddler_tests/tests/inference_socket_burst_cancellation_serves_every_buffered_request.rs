@@ -18,16 +18,26 @@ const WAITING_REQUEST_COUNT: i32 = 4;
 fn slot_filling_prompt() -> ContinueFromRawPromptParams {
     ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 500,
+        model: None,
         raw_prompt: "Write a very long, detailed story about an explorer.".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     }
 }
 
 fn waiting_prompt() -> ContinueFromRawPromptParams {
     ContinueFromRawPromptParams {
         grammar: None,
+        logprobs: None,
         max_tokens: 32,
+        model: None,
         raw_prompt: "The capital of France is".to_owned(),
+        sampling: None,
+        session_key: None,
+        stop: Vec::new(),
     }
 }
 
@@ -23,8 +23,13 @@ async fn inference_socket_cancellation_releases_a_buffered_request() -> Result<(
             cancellation_token.clone(),
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 16,
+                model: None,
                 raw_prompt: "The capital of France is".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await
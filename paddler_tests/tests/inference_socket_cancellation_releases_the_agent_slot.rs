@@ -27,8 +27,13 @@ async fn inference_socket_cancellation_releases_the_agent_slot() -> Result<()> {
             cancellation_token.clone(),
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 500,
+                model: None,
                 raw_prompt: "Write a long story about an explorer".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await
@@ -29,8 +29,13 @@ async fn inference_socket_cancelling_one_request_leaves_a_sibling_request_runnin
             cancelled_request_token.clone(),
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 500,
+                model: None,
                 raw_prompt: "Write a long story about an explorer".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await
@@ -42,8 +47,13 @@ async fn inference_socket_cancelling_one_request_leaves_a_sibling_request_runnin
             kept_request_token.clone(),
             ContinueFromRawPromptParams {
                 grammar: None,
+                logprobs: None,
                 max_tokens: 32,
+                model: None,
                 raw_prompt: "The capital of France is".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: Vec::new(),
             },
         )
         .await
@@ -21,9 +21,15 @@ fn raw_prompt_message(request_id: &str) -> InferenceServerMessage<ValidatedParam
         id: request_id.to_owned(),
         request: InferenceServerRequest::ContinueFromRawPrompt(ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 16,
+            model: None,
             raw_prompt: "The capital of France is".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         }),
+        trace_context: None,
     })
 }
 
@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use anyhow::Context as _;
 use anyhow::Result;
 use paddler_client::client_management::ClientManagement;
@@ -36,6 +38,7 @@ async fn management_returns_balancer_desired_state_that_was_put() -> Result<()>
         chat_template_override: None,
         inference_parameters: InferenceParameters::deterministic(),
         model: AgentDesiredModel::None,
+        model_pools: BTreeMap::new(),
         multimodal_projection: AgentDesiredModel::None,
         use_chat_template_override: false,
     };
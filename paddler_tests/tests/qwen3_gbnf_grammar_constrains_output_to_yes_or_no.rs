@@ -17,8 +17,13 @@ async fn qwen3_gbnf_grammar_constrains_output_to_yes_or_no() -> Result<()> {
                 grammar: r#"root ::= "yes" | "no""#.to_owned(),
                 root: "root".to_owned(),
             }),
+            logprobs: None,
             max_tokens: 10,
+            model: None,
             raw_prompt: "<|im_start|>user\nIs the sky blue? Answer yes or no.<|im_end|>\n<|im_start|>assistant\n<think>\n\n</think>\n\n".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         })
         .await?;
 
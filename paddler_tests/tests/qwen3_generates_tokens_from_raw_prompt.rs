@@ -15,10 +15,15 @@ async fn qwen3_generates_tokens_from_raw_prompt() -> Result<()> {
     let collected = cluster
         .continue_from_raw_prompt(CancellationToken::new(), &ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 30,
+            model: None,
             raw_prompt:
                 "<|im_start|>user\nHow can I make a cat happy?<|im_end|>\n<|im_start|>assistant\n"
                     .to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         })
         .await?;
 
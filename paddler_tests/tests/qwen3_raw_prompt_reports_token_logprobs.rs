@@ -0,0 +1,56 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::logprobs_request::LogprobsRequest;
+use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+use paddler_test_cluster_harness::agent_config::AgentConfig;
+use paddler_tests::start_cluster_with_qwen3::start_cluster_with_qwen3;
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_raw_prompt_reports_token_logprobs() -> Result<()> {
+    let cluster = start_cluster_with_qwen3(vec![AgentConfig::single(1)]).await?;
+
+    let collected = cluster
+        .continue_from_raw_prompt(
+            CancellationToken::new(),
+            &ContinueFromRawPromptParams {
+                grammar: None,
+                logprobs: Some(LogprobsRequest { top_logprobs: 3 }),
+                max_tokens: 10,
+                model: None,
+                raw_prompt: "The capital of France is".to_owned(),
+                sampling: None,
+                session_key: None,
+                stop: vec![],
+            },
+        )
+        .await?;
+
+    let token_logprobs: Vec<_> = collected
+        .token_results
+        .iter()
+        .filter_map(|result| match &result.token_result {
+            GeneratedTokenResult::TokenLogprob(token_logprob) => Some(token_logprob),
+            _ => None,
+        })
+        .collect();
+
+    assert!(!token_logprobs.is_empty(), "expected token logprobs");
+
+    for token_logprob in token_logprobs {
+        assert!(token_logprob.logprob <= 0.0);
+        assert_eq!(token_logprob.top_logprobs.len(), 3);
+        assert!(
+            token_logprob
+                .top_logprobs
+                .windows(2)
+                .all(|pair| pair[0].logprob >= pair[1].logprob)
+        );
+    }
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
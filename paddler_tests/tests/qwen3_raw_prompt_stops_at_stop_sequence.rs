@@ -0,0 +1,62 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+use paddler_messaging::sampling_overrides::SamplingOverrides;
+use paddler_messaging::stop_reason::StopReason;
+use paddler_test_cluster_harness::agent_config::AgentConfig;
+use paddler_tests::start_cluster_with_qwen3::start_cluster_with_qwen3;
+use tokio_util::sync::CancellationToken;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_raw_prompt_stops_at_stop_sequence() -> Result<()> {
+    let cluster = start_cluster_with_qwen3(vec![AgentConfig::single(1)]).await?;
+
+    let collected = cluster
+        .continue_from_raw_prompt(
+            CancellationToken::new(),
+            &ContinueFromRawPromptParams {
+                grammar: None,
+                logprobs: None,
+                max_tokens: 40,
+                model: None,
+                raw_prompt: "Count from one to ten in words, separated by commas: one, two,"
+                    .to_owned(),
+                sampling: Some(SamplingOverrides {
+                    temperature: Some(0.0),
+                    top_k: Some(1),
+                    ..SamplingOverrides::default()
+                }),
+                session_key: None,
+                stop: vec!["five".to_owned()],
+            },
+        )
+        .await?;
+
+    let generated_text: String = collected
+        .token_results
+        .iter()
+        .filter_map(|result| result.token_result.token_text())
+        .collect();
+
+    assert!(
+        !generated_text.contains("five"),
+        "the stop sequence must not be streamed, got {generated_text:?}"
+    );
+
+    let last = collected
+        .token_results
+        .last()
+        .ok_or_else(|| anyhow!("no token results received"))?;
+    let GeneratedTokenResult::Done(summary) = &last.token_result else {
+        anyhow::bail!("last result was not Done: {last:?}");
+    };
+
+    assert_eq!(summary.stop_reason, StopReason::StopSequence);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "tests_that_use_llms")]
+
+use anyhow::Result;
+use anyhow::anyhow;
+use paddler_messaging::generated_token_result::GeneratedTokenResult;
+use paddler_messaging::generation_summary::GenerationSummary;
+use paddler_messaging::request_params::continue_from_raw_prompt_params::ContinueFromRawPromptParams;
+use paddler_test_cluster_harness::agent_config::AgentConfig;
+use paddler_test_cluster_harness::cluster::Cluster;
+use paddler_tests::start_cluster_with_qwen3::start_cluster_with_qwen3;
+use tokio_util::sync::CancellationToken;
+
+const SHARED_PREFIX: &str = "You are a meticulous assistant. Answer every question in one short \
+    sentence, never use lists, never apologise, and always mention the question topic by name.\n";
+
+async fn summary_for_question(cluster: &Cluster, question: &str) -> Result<GenerationSummary> {
+    let collected = cluster
+        .continue_from_raw_prompt(
+            CancellationToken::new(),
+            &ContinueFromRawPromptParams {
+                grammar: None,
+                logprobs: None,
+                max_tokens: 8,
+                model: None,
+                raw_prompt: format!("{SHARED_PREFIX}Question: {question}\nAnswer:"),
+                sampling: None,
+                session_key: None,
+                stop: vec![],
+            },
+        )
+        .await?;
+
+    let last = collected
+        .token_results
+        .last()
+        .ok_or_else(|| anyhow!("no token results received"))?;
+    let GeneratedTokenResult::Done(summary) = &last.token_result else {
+        anyhow::bail!("last result was not Done: {last:?}");
+    };
+
+    Ok(*summary)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn qwen3_reuses_kv_cache_prefix_across_requests() -> Result<()> {
+    let cluster = start_cluster_with_qwen3(vec![AgentConfig::single(1)]).await?;
+
+    let first_summary = summary_for_question(&cluster, "What colour is the sky?").await?;
+
+    assert_eq!(first_summary.usage.cached_prompt_tokens, 0);
+
+    let second_summary =
+        summary_for_question(&cluster, "What is the boiling point of water?").await?;
+
+    assert!(
+        second_summary.usage.cached_prompt_tokens >= 16,
+        "expected the shared prefix to be reused, got {:?}",
+        second_summary.usage
+    );
+    assert!(second_summary.usage.cached_prompt_tokens < second_summary.usage.prompt_tokens);
+
+    cluster.shutdown().await?;
+
+    Ok(())
+}
@@ -13,8 +13,13 @@ async fn qwen3_without_grammar_generates_unconstrained_output() -> Result<()> {
     let collected = cluster
         .continue_from_raw_prompt(CancellationToken::new(), &ContinueFromRawPromptParams {
             grammar: None,
+            logprobs: None,
             max_tokens: 20,
+            model: None,
             raw_prompt: "<|im_start|>user\nSay hello<|im_end|>\n<|im_start|>assistant\n<think>\n\n</think>\n\n".to_owned(),
+            sampling: None,
+            session_key: None,
+            stop: Vec::new(),
         })
         .await?;
 
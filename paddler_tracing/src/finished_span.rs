@@ -0,0 +1,11 @@
+use std::time::SystemTime;
+
+pub struct FinishedSpan {
+    pub attributes: Vec<(&'static str, String)>,
+    pub ended_at: SystemTime,
+    pub name: &'static str,
+    pub parent_span_id: Option<String>,
+    pub span_id: String,
+    pub started_at: SystemTime,
+    pub trace_id: String,
+}
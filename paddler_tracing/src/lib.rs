@@ -0,0 +1,4 @@
+pub mod finished_span;
+pub mod otlp_trace_exporter_service;
+pub mod span_guard;
+pub mod tracer;
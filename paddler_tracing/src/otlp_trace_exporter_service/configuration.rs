@@ -0,0 +1,11 @@
+use std::time::Duration;
+
+use url::Url;
+
+#[derive(Clone)]
+pub struct Configuration {
+    pub export_interval: Duration,
+    /// Base URL of an OTLP/HTTP collector; spans are posted to its `/v1/traces` path.
+    pub otlp_endpoint: Url,
+    pub service_name: String,
+}
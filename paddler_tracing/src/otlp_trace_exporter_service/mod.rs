@@ -0,0 +1,336 @@
+pub mod configuration;
+
+use std::mem::take;
+use std::time::Duration;
+use std::time::SystemTime;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use log::warn;
+use reqwest::Client;
+use serde_json::Value;
+use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::time::MissedTickBehavior;
+use tokio::time::interval;
+use tokio_util::sync::CancellationToken;
+use trzcina::Service;
+use url::Url;
+
+use crate::finished_span::FinishedSpan;
+use crate::otlp_trace_exporter_service::configuration::Configuration as OtlpTraceExporterServiceConfiguration;
+
+/// Keeps an unresponsive collector from stalling the exporter while spans pile up.
+const EXPORT_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_SPANS_PER_EXPORT: usize = 512;
+
+fn string_attribute(key: &str, value: &str) -> Value {
+    json!({
+        "key": key,
+        "value": {
+            "stringValue": value,
+        },
+    })
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+fn span_body(finished_span: &FinishedSpan) -> Value {
+    let mut span_body = json!({
+        "attributes": finished_span
+            .attributes
+            .iter()
+            .map(|(key, value)| string_attribute(key, value))
+            .collect::<Vec<Value>>(),
+        "endTimeUnixNano": unix_nanos(finished_span.ended_at),
+        "name": finished_span.name,
+        "spanId": finished_span.span_id,
+        "startTimeUnixNano": unix_nanos(finished_span.started_at),
+        "traceId": finished_span.trace_id,
+    });
+
+    if let Some(parent_span_id) = &finished_span.parent_span_id {
+        span_body["parentSpanId"] = json!(parent_span_id);
+    }
+
+    span_body
+}
+
+/// Body of an OTLP/HTTP export request in its JSON encoding.
+fn export_request_body(service_name: &str, finished_spans: &[FinishedSpan]) -> Value {
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [string_attribute("service.name", service_name)],
+            },
+            "scopeSpans": [{
+                "scope": {
+                    "name": "paddler",
+                    "version": env!("CARGO_PKG_VERSION"),
+                },
+                "spans": finished_spans.iter().map(span_body).collect::<Vec<Value>>(),
+            }],
+        }],
+    })
+}
+
+pub struct OtlpTraceExporterService {
+    pub configuration: OtlpTraceExporterServiceConfiguration,
+    pub finished_span_rx: mpsc::Receiver<FinishedSpan>,
+}
+
+impl OtlpTraceExporterService {
+    async fn export(&self, client: &Client, traces_url: &Url, finished_spans: Vec<FinishedSpan>) {
+        if finished_spans.is_empty() {
+            return;
+        }
+
+        if let Err(err) = client
+            .post(traces_url.clone())
+            .json(&export_request_body(
+                &self.configuration.service_name,
+                &finished_spans,
+            ))
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+        {
+            warn!(
+                "Failed to export {} spans to {traces_url}: {err}",
+                finished_spans.len()
+            );
+        }
+    }
+
+    fn traces_url(&self) -> Result<Url> {
+        Ok(Url::parse(&format!(
+            "{}/v1/traces",
+            self.configuration
+                .otlp_endpoint
+                .as_str()
+                .trim_end_matches('/')
+        ))?)
+    }
+}
+
+#[async_trait]
+impl Service for OtlpTraceExporterService {
+    fn name(&self) -> &'static str {
+        "tracing::otlp_trace_exporter_service"
+    }
+
+    async fn run(mut self: Box<Self>, shutdown: CancellationToken) -> Result<()> {
+        let client = Client::builder().timeout(EXPORT_TIMEOUT).build()?;
+        let traces_url = self.traces_url()?;
+        let mut pending_spans: Vec<FinishedSpan> = Vec::new();
+        let mut is_receiving = true;
+        let mut ticker = interval(self.configuration.export_interval);
+
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            tokio::select! {
+                () = shutdown.cancelled() => {
+                    while let Ok(finished_span) = self.finished_span_rx.try_recv() {
+                        pending_spans.push(finished_span);
+                    }
+
+                    self.export(&client, &traces_url, pending_spans).await;
+
+                    break Ok(());
+                }
+                _ = ticker.tick() => {
+                    self.export(&client, &traces_url, take(&mut pending_spans)).await;
+                }
+                finished_span = self.finished_span_rx.recv(), if is_receiving => {
+                    let Some(finished_span) = finished_span else {
+                        is_receiving = false;
+
+                        continue;
+                    };
+
+                    pending_spans.push(finished_span);
+
+                    if pending_spans.len() >= MAX_SPANS_PER_EXPORT {
+                        self.export(&client, &traces_url, take(&mut pending_spans)).await;
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::net::SocketAddr;
+    use std::time::Duration;
+
+    use paddler_messaging::trace_context::TraceContext;
+    use tokio::io::AsyncReadExt as _;
+    use tokio::io::AsyncWriteExt as _;
+    use tokio::net::TcpListener;
+
+    use super::*;
+    use crate::tracer::FINISHED_SPAN_CHANNEL_CAPACITY;
+    use crate::tracer::Tracer;
+
+    /// Accepts a single OTLP/HTTP export request, acknowledges it and returns its path and
+    /// JSON body.
+    async fn receive_export_request(listener: &TcpListener) -> (String, Value) {
+        let (mut stream, _) = listener.accept().await.unwrap();
+        let mut received = Vec::new();
+        let mut buffer = [0_u8; 4096];
+
+        let header_end = loop {
+            let byte_count = stream.read(&mut buffer).await.unwrap();
+
+            received.extend_from_slice(&buffer[..byte_count]);
+
+            if let Some(position) = received.windows(4).position(|window| window == b"\r\n\r\n") {
+                break position + 4;
+            }
+        };
+
+        let headers = String::from_utf8(received[..header_end].to_vec()).unwrap();
+        let content_length: usize = headers
+            .lines()
+            .find_map(|line| {
+                line.to_ascii_lowercase()
+                    .strip_prefix("content-length:")
+                    .map(|value| value.trim().parse().unwrap())
+            })
+            .unwrap();
+
+        while received.len() < header_end + content_length {
+            let byte_count = stream.read(&mut buffer).await.unwrap();
+
+            received.extend_from_slice(&buffer[..byte_count]);
+        }
+
+        stream
+            .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\nconnection: close\r\n\r\n")
+            .await
+            .unwrap();
+
+        let path = headers.split_whitespace().nth(1).unwrap().to_owned();
+
+        (
+            path,
+            serde_json::from_slice(&received[header_end..header_end + content_length]).unwrap(),
+        )
+    }
+
+    fn exporter(
+        collector_addr: SocketAddr,
+        service_name: &str,
+    ) -> (Tracer, Box<OtlpTraceExporterService>) {
+        let (finished_span_tx, finished_span_rx) = mpsc::channel(FINISHED_SPAN_CHANNEL_CAPACITY);
+
+        (
+            Tracer::new(finished_span_tx),
+            Box::new(OtlpTraceExporterService {
+                configuration: OtlpTraceExporterServiceConfiguration {
+                    export_interval: Duration::from_hours(1),
+                    otlp_endpoint: Url::parse(&format!("http://{collector_addr}/")).unwrap(),
+                    service_name: service_name.to_owned(),
+                },
+                finished_span_rx,
+            }),
+        )
+    }
+
+    /// Runs the exporter until it flushes on shutdown, and returns the spans the collector got.
+    async fn export_on_shutdown(
+        listener: &TcpListener,
+        service: Box<OtlpTraceExporterService>,
+    ) -> Vec<Value> {
+        let shutdown = CancellationToken::new();
+        let run_handle = tokio::spawn(service.run(shutdown.clone()));
+
+        shutdown.cancel();
+
+        let (_, body) = receive_export_request(listener).await;
+
+        assert!(run_handle.await.unwrap().is_ok());
+
+        body["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap()
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn exports_finished_spans_to_the_collector_on_shutdown() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let collector_addr = listener.local_addr().unwrap();
+        let (tracer, service) = exporter(collector_addr, "paddler-balancer");
+
+        let root = tracer.start_span("balancer.request", None);
+        let root_context = root.context();
+
+        drop(root.start_child("balancer.buffering"));
+        drop(root);
+
+        let shutdown = CancellationToken::new();
+        let run_handle = tokio::spawn(service.run(shutdown.clone()));
+
+        shutdown.cancel();
+
+        let (path, body) = receive_export_request(&listener).await;
+
+        assert!(run_handle.await.unwrap().is_ok());
+        assert_eq!(path, "/v1/traces");
+
+        let resource_spans = &body["resourceSpans"][0];
+        let spans = resource_spans["scopeSpans"][0]["spans"].as_array().unwrap();
+
+        assert_eq!(
+            resource_spans["resource"]["attributes"][0]["value"]["stringValue"],
+            "paddler-balancer"
+        );
+        assert_eq!(spans.len(), 2);
+        assert_eq!(spans[0]["name"], "balancer.buffering");
+        assert_eq!(spans[0]["parentSpanId"], json!(root_context.span_id));
+        assert_eq!(spans[1]["name"], "balancer.request");
+        assert_eq!(spans[1]["traceId"], json!(root_context.trace_id));
+        assert!(spans[1].get("parentSpanId").is_none());
+    }
+
+    #[tokio::test]
+    async fn agent_spans_are_exported_in_the_trace_of_the_balancer_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let collector_addr = listener.local_addr().unwrap();
+        let (balancer_tracer, balancer_exporter) = exporter(collector_addr, "paddler-balancer");
+        let (agent_tracer, agent_exporter) = exporter(collector_addr, "paddler-agent");
+
+        let request_span = balancer_tracer.start_span("balancer.request", None);
+        let dispatch_span = request_span.start_child("balancer.dispatch");
+        let sent_trace_context: TraceContext =
+            serde_json::from_value(serde_json::to_value(dispatch_span.context()).unwrap()).unwrap();
+
+        drop(agent_tracer.start_span("agent.prefill", Some(&sent_trace_context)));
+        drop(agent_tracer.start_span("agent.decode", Some(&sent_trace_context)));
+        drop(dispatch_span);
+        drop(request_span);
+
+        let balancer_spans = export_on_shutdown(&listener, balancer_exporter).await;
+        let agent_spans = export_on_shutdown(&listener, agent_exporter).await;
+        let balancer_trace_id = &balancer_spans[1]["traceId"];
+
+        assert_eq!(balancer_spans[1]["name"], "balancer.request");
+        assert_eq!(agent_spans.len(), 2);
+        assert_eq!(agent_spans[0]["name"], "agent.prefill");
+        assert_eq!(agent_spans[1]["name"], "agent.decode");
+
+        for agent_span in &agent_spans {
+            assert_eq!(&agent_span["traceId"], balancer_trace_id);
+            assert_eq!(agent_span["parentSpanId"], balancer_spans[0]["spanId"]);
+        }
+    }
+}
@@ -0,0 +1,75 @@
+use std::time::SystemTime;
+
+use paddler_messaging::trace_context::TraceContext;
+use tokio::sync::mpsc;
+
+use crate::finished_span::FinishedSpan;
+
+/// Span that is in progress until the guard is dropped.
+pub struct SpanGuard {
+    attributes: Vec<(&'static str, String)>,
+    finished_span_tx: Option<mpsc::Sender<FinishedSpan>>,
+    name: &'static str,
+    parent_span_id: Option<String>,
+    span_id: String,
+    started_at: SystemTime,
+    trace_id: String,
+}
+
+impl SpanGuard {
+    pub(crate) fn start(
+        finished_span_tx: Option<mpsc::Sender<FinishedSpan>>,
+        name: &'static str,
+        parent: Option<&TraceContext>,
+    ) -> Self {
+        Self {
+            attributes: Vec::new(),
+            finished_span_tx,
+            name,
+            parent_span_id: parent.map(|parent| parent.span_id.clone()),
+            span_id: format!("{:016x}", rand::random::<u64>() | 1),
+            started_at: SystemTime::now(),
+            trace_id: parent.map_or_else(
+                || format!("{:032x}", rand::random::<u128>() | 1),
+                |parent| parent.trace_id.clone(),
+            ),
+        }
+    }
+
+    #[must_use]
+    pub fn context(&self) -> TraceContext {
+        TraceContext {
+            span_id: self.span_id.clone(),
+            trace_id: self.trace_id.clone(),
+        }
+    }
+
+    pub fn set_attribute<TValue: Into<String>>(&mut self, key: &'static str, value: TValue) {
+        self.attributes.push((key, value.into()));
+    }
+
+    #[must_use]
+    pub fn start_child(&self, name: &'static str) -> Self {
+        Self::start(self.finished_span_tx.clone(), name, Some(&self.context()))
+    }
+}
+
+impl Drop for SpanGuard {
+    fn drop(&mut self) {
+        let Some(finished_span_tx) = &self.finished_span_tx else {
+            return;
+        };
+
+        // The span is lost when the exporter is gone during shutdown, or so far behind that its
+        // channel is full. Either is preferable to holding up the request.
+        let _ = finished_span_tx.try_send(FinishedSpan {
+            attributes: std::mem::take(&mut self.attributes),
+            ended_at: SystemTime::now(),
+            name: self.name,
+            parent_span_id: self.parent_span_id.take(),
+            span_id: std::mem::take(&mut self.span_id),
+            started_at: self.started_at,
+            trace_id: std::mem::take(&mut self.trace_id),
+        });
+    }
+}
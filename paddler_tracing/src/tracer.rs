@@ -0,0 +1,93 @@
+use paddler_messaging::trace_context::TraceContext;
+use tokio::sync::mpsc;
+
+use crate::finished_span::FinishedSpan;
+use crate::span_guard::SpanGuard;
+
+/// Spans waiting for the exporter. Once it falls this far behind, new spans are dropped rather
+/// than held in memory.
+pub const FINISHED_SPAN_CHANNEL_CAPACITY: usize = 8192;
+
+/// Starts spans and hands them to the exporter once they end. The default tracer exports
+/// nothing, but still generates trace context so that it can be propagated to agents.
+#[derive(Clone, Default)]
+pub struct Tracer {
+    finished_span_tx: Option<mpsc::Sender<FinishedSpan>>,
+}
+
+impl Tracer {
+    #[must_use]
+    pub const fn new(finished_span_tx: mpsc::Sender<FinishedSpan>) -> Self {
+        Self {
+            finished_span_tx: Some(finished_span_tx),
+        }
+    }
+
+    #[must_use]
+    pub fn start_span(&self, name: &'static str, parent: Option<&TraceContext>) -> SpanGuard {
+        SpanGuard::start(self.finished_span_tx.clone(), name, parent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn child_spans_belong_to_the_trace_of_their_parent() {
+        let (finished_span_tx, mut finished_span_rx) =
+            mpsc::channel(FINISHED_SPAN_CHANNEL_CAPACITY);
+        let tracer = Tracer::new(finished_span_tx);
+        let incoming = TraceContext {
+            span_id: "00f067aa0ba902b7".to_owned(),
+            trace_id: "4bf92f3577b34da6a3ce929d0e0e4736".to_owned(),
+        };
+
+        let root = tracer.start_span("root", Some(&incoming));
+        let mut child = root.start_child("child");
+
+        child.set_attribute("agent_id", "agent-1");
+
+        let child_context = child.context();
+
+        drop(child);
+        drop(root);
+
+        let finished_child = finished_span_rx.try_recv().unwrap();
+        let finished_root = finished_span_rx.try_recv().unwrap();
+
+        assert_eq!(finished_root.trace_id, incoming.trace_id);
+        assert_eq!(finished_root.parent_span_id, Some(incoming.span_id));
+        assert_eq!(finished_child.trace_id, incoming.trace_id);
+        assert_eq!(finished_child.span_id, child_context.span_id);
+        assert_eq!(finished_child.parent_span_id, Some(finished_root.span_id));
+        assert_eq!(
+            finished_child.attributes,
+            vec![("agent_id", "agent-1".to_owned())]
+        );
+    }
+
+    #[test]
+    fn spans_without_a_parent_start_a_new_trace() {
+        let tracer = Tracer::default();
+
+        let first = tracer.start_span("first", None).context();
+        let second = tracer.start_span("second", None).context();
+
+        assert_eq!(first.trace_id.len(), 32);
+        assert_eq!(first.span_id.len(), 16);
+        assert_ne!(first.trace_id, second.trace_id);
+    }
+
+    #[test]
+    fn spans_are_dropped_once_the_exporter_falls_behind() {
+        let (finished_span_tx, mut finished_span_rx) = mpsc::channel(1);
+        let tracer = Tracer::new(finished_span_tx);
+
+        drop(tracer.start_span("first", None));
+        drop(tracer.start_span("second", None));
+
+        assert_eq!(finished_span_rx.try_recv().unwrap().name, "first");
+        assert!(finished_span_rx.try_recv().is_err());
+    }
+}
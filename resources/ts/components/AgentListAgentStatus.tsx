@@ -6,6 +6,7 @@ import { agentListAgentStatus__progress } from "./AgentListAgentStatus.module.cs
 
 export function AgentListAgentStatus({
   agent: {
+    cordon_status,
     desired_slots_total,
     slots_processing,
     slots_total,
@@ -33,6 +34,16 @@ export function AgentListAgentStatus({
           <abbr title="Slots processing / total / desired total">
             {slots_processing}/{slots_total}/{desired_slots_total}
           </abbr>
+          {cordon_status === "Draining" && (
+            <abbr title="Cordoned, finishing in-flight requests">
+              <i>Draining</i>
+            </abbr>
+          )}
+          {cordon_status === "Drained" && (
+            <abbr title="Cordoned, no requests in flight">
+              <i>Drained</i>
+            </abbr>
+          )}
         </div>
       );
     case "AttemptedAndNotAppliable":
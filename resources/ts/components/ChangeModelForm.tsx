@@ -36,9 +36,11 @@ import {
 export function ChangeModelForm({
   defaultBaseModelUri,
   defaultMultimodalProjectionUri,
+  modelPools,
 }: {
   defaultBaseModelUri: null | string;
   defaultMultimodalProjectionUri: null | string;
+  modelPools: BalancerDesiredState["model_pools"];
 }) {
   const [, navigate] = useLocation();
   const { chatTemplateOverride, useChatTemplateOverride } =
@@ -87,6 +89,7 @@ export function ChangeModelForm({
         chat_template_override: chatTemplateOverride,
         inference_parameters: parameters,
         model: baseModelAgentDesiredModelState.agentDesiredModel,
+        model_pools: modelPools,
         multimodal_projection:
           multimodalProjecttionAgentDesiredModelState.agentDesiredModel,
         use_chat_template_override: useChatTemplateOverride,
@@ -97,6 +100,7 @@ export function ChangeModelForm({
     [
       baseModelAgentDesiredModelState,
       chatTemplateOverride,
+      modelPools,
       multimodalProjecttionAgentDesiredModelState,
       parameters,
       useChatTemplateOverride,
@@ -54,6 +54,7 @@ export function ChangeModelPage() {
         chat_template_override,
         inference_parameters,
         model,
+        model_pools,
         multimodal_projection,
         use_chat_template_override,
       },
@@ -71,6 +72,7 @@ export function ChangeModelPage() {
               defaultMultimodalProjectionUri={modelSchemaToUrl(
                 multimodal_projection,
               )}
+              modelPools={model_pools}
             />
           </InferenceParametersContextProvider>
         </ChatTemplateContextProvider>